@@ -98,6 +98,12 @@ impl<Idx, T> Arena<Idx, T> {
         self.len() == 0
     }
 
+    /// Returns the number of entities the arena can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+
     /// Clears all entities from the arena.
     pub fn clear(&mut self) {
         self.entities.clear();
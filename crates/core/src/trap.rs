@@ -13,8 +13,57 @@ use std::error::Error as StdError;
 /// host embedder.
 #[derive(Debug)]
 pub struct Trap {
-    /// The cloneable reason of a [`Trap`].
-    reason: Box<TrapReason>,
+    /// The reason and optional location of a [`Trap`].
+    inner: Box<TrapInner>,
+}
+
+/// The boxed contents of a [`Trap`].
+#[derive(Debug)]
+struct TrapInner {
+    /// The reason of the [`Trap`].
+    reason: TrapReason,
+    /// The location in the Wasm module at which the [`Trap`] occurred if known.
+    location: Option<TrapLocation>,
+    /// An optional message further describing the cause of the [`Trap`].
+    message: Option<Box<str>>,
+}
+
+/// The location within a Wasm module at which a [`Trap`] occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrapLocation {
+    /// The name of the trapping function.
+    func_name: Box<str>,
+    /// The offset of the trapping instruction within the Wasm binary.
+    wasm_offset: usize,
+}
+
+impl TrapLocation {
+    /// Creates a new [`TrapLocation`] from the trapping function's name and Wasm binary offset.
+    pub fn new<T>(func_name: T, wasm_offset: usize) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            func_name: func_name.into().into_boxed_str(),
+            wasm_offset,
+        }
+    }
+
+    /// Returns the name of the trapping function.
+    pub fn func_name(&self) -> &str {
+        &self.func_name
+    }
+
+    /// Returns the offset of the trapping instruction within the Wasm binary.
+    pub fn wasm_offset(&self) -> usize {
+        self.wasm_offset
+    }
+}
+
+impl Display for TrapLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{:#x}", self.func_name, self.wasm_offset)
+    }
 }
 
 #[test]
@@ -25,6 +74,36 @@ fn trap_size() {
     );
 }
 
+#[test]
+fn trap_display_with_location() {
+    use alloc::string::ToString;
+    let trap = Trap::from(TrapCode::IntegerDivisionByZero);
+    assert_eq!(trap.to_string(), "integer divide by zero");
+    let trap = trap.with_location(TrapLocation::new("divide", 0x2a));
+    assert_eq!(trap.location().map(TrapLocation::func_name), Some("divide"));
+    assert_eq!(trap.to_string(), "divide@0x2a: integer divide by zero");
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+}
+
+#[test]
+fn trap_display_with_message() {
+    use alloc::string::ToString;
+    let trap = Trap::from(TrapCode::IntegerDivisionByZero);
+    assert_eq!(trap.message(), None);
+    let trap = trap.with_message("i32.div_s with operands 1 and 0");
+    assert_eq!(trap.message(), Some("i32.div_s with operands 1 and 0"));
+    assert_eq!(
+        trap.to_string(),
+        "integer divide by zero (i32.div_s with operands 1 and 0)"
+    );
+    let trap = trap.with_location(TrapLocation::new("divide", 0x2a));
+    assert_eq!(
+        trap.to_string(),
+        "divide@0x2a: integer divide by zero (i32.div_s with operands 1 and 0)"
+    );
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+}
+
 /// The reason of a [`Trap`].
 #[derive(Debug)]
 enum TrapReason {
@@ -94,10 +173,48 @@ impl Trap {
     /// Create a new [`Trap`] from the [`TrapReason`].
     fn with_reason(reason: TrapReason) -> Self {
         Self {
-            reason: Box::new(reason),
+            inner: Box::new(TrapInner {
+                reason,
+                location: None,
+                message: None,
+            }),
         }
     }
 
+    /// Attaches the `location` at which the [`Trap`] occurred.
+    ///
+    /// The location is shown in front of the trap reason by the [`Display`] implementation
+    /// in the form `function_name@wasm_offset: reason`.
+    #[cold] // see Trap::new
+    pub fn with_location(mut self, location: TrapLocation) -> Self {
+        self.inner.location = Some(location);
+        self
+    }
+
+    /// Returns the [`TrapLocation`] at which the [`Trap`] occurred if known.
+    #[inline]
+    pub fn location(&self) -> Option<&TrapLocation> {
+        self.inner.location.as_ref()
+    }
+
+    /// Attaches a `message` further describing the cause of the [`Trap`].
+    ///
+    /// The message is shown in parentheses after the trap reason by the [`Display`] implementation.
+    #[cold] // see Trap::new
+    pub fn with_message<T>(mut self, message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.inner.message = Some(message.into().into_boxed_str());
+        self
+    }
+
+    /// Returns the message further describing the cause of the [`Trap`] if any.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.inner.message.as_deref()
+    }
+
     /// Creates a new [`Trap`] described by a `message`.
     #[cold] // traps are exceptional, this helps move handling off the main path
     pub fn new<T>(message: T) -> Self
@@ -115,7 +232,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -128,7 +246,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -141,7 +260,8 @@ impl Trap {
     where
         T: HostError,
     {
-        self.reason
+        self.inner
+            .reason
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
@@ -159,13 +279,13 @@ impl Trap {
     /// Otherwise returns `None`.
     #[inline]
     pub fn i32_exit_status(&self) -> Option<i32> {
-        self.reason.i32_exit_status()
+        self.inner.reason.i32_exit_status()
     }
 
     /// Returns the [`TrapCode`] traps originating from Wasm execution.
     #[inline]
     pub fn trap_code(&self) -> Option<TrapCode> {
-        self.reason.trap_code()
+        self.inner.reason.trap_code()
     }
 }
 
@@ -200,7 +320,14 @@ impl Display for TrapReason {
 
 impl Display for Trap {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        <TrapReason as Display>::fmt(&self.reason, f)
+        if let Some(location) = &self.inner.location {
+            write!(f, "{location}: ")?;
+        }
+        <TrapReason as Display>::fmt(&self.inner.reason, f)?;
+        if let Some(message) = &self.inner.message {
+            write!(f, " ({message})")?;
+        }
+        Ok(())
     }
 }
 
@@ -291,6 +418,25 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// Attempt to access memory with an atomic operator at an unaligned address.
+    ///
+    /// Atomic operators of the Wasm `threads` proposal require their effective
+    /// address to be aligned to the number of bytes they access.
+    UnalignedAtomic,
+
+    /// Attempt to execute `memory.atomic.wait32` or `memory.atomic.wait64` on a non-shared memory.
+    ///
+    /// Since nothing could ever notify a waiter of a non-shared memory
+    /// the Wasm `threads` proposal defines such a wait to trap.
+    ExpectedSharedMemory,
+
+    /// An executed instruction accessed a register outside of its call frame.
+    ///
+    /// This trap is only raised by the register-machine `wasmi` executor if the
+    /// `checked-registers` crate feature of `wasmi` is enabled and indicates
+    /// malformed bytecode. Without this feature such accesses are undefined behavior.
+    RegisterOutOfBounds,
 }
 
 impl TrapCode {
@@ -313,6 +459,9 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::UnalignedAtomic => "unaligned atomic",
+            Self::ExpectedSharedMemory => "expected shared memory",
+            Self::RegisterOutOfBounds => "out of bounds register access",
         }
     }
 }
@@ -15,6 +15,7 @@ use crate::{
 };
 use core::{
     fmt::{self, Display},
+    mem,
     ops::{Neg, Shl, Shr},
 };
 use paste::paste;
@@ -484,6 +485,114 @@ impl UntypedValue {
         Self::store_wrap::<i64, i32>(memory, address, offset, value)
     }
 
+    /// Executes a generic atomic `T.load` as required by the Wasm `threads` proposal.
+    ///
+    /// # Errors
+    ///
+    /// - If `address + offset` overflows.
+    /// - If `address + offset` is not aligned to the size of `T`.
+    /// - If `address + offset` loads out of bounds from `memory`.
+    fn atomic_load<T>(memory: &[u8], address: Self, offset: u32) -> Result<Self, TrapCode>
+    where
+        T: LittleEndianConvert + ExtendInto<T> + Into<Self>,
+    {
+        let effective_address = effective_address(u32::from(address), offset)?;
+        if effective_address % mem::size_of::<T>() != 0 {
+            return Err(TrapCode::UnalignedAtomic);
+        }
+        Self::load::<T>(memory, address, offset)
+    }
+
+    /// Executes a generic `memory.atomic.waitN` Wasm operation.
+    ///
+    /// Returns `1` (not-equal) if the loaded value differs from `expected` and `2` (timed-out) otherwise.
+    /// Since no other thread could ever notify the waiter it times out immediately regardless of its timeout.
+    ///
+    /// # Errors
+    ///
+    /// - If `address + offset` overflows.
+    /// - If `address + offset` is not aligned to the size of `T`.
+    /// - If `address + offset` loads out of bounds from `memory`.
+    fn memory_atomic_wait<T>(
+        memory: &[u8],
+        address: Self,
+        offset: u32,
+        expected: Self,
+    ) -> Result<Self, TrapCode>
+    where
+        T: LittleEndianConvert + ExtendInto<T> + Into<Self> + From<Self> + PartialEq,
+    {
+        let value = Self::atomic_load::<T>(memory, address, offset)?;
+        let status: i32 = match T::from(value) == T::from(expected) {
+            true => 2,
+            false => 1,
+        };
+        Ok(Self::from(status))
+    }
+
+    /// Executes the `memory.atomic.notify` Wasm operation.
+    ///
+    /// Always returns `0` since there are no waiters without other threads.
+    ///
+    /// # Errors
+    ///
+    /// - If `address + offset` overflows.
+    /// - If `address + offset` is not aligned to 4 bytes.
+    /// - If `address + offset` accesses out of bounds of `memory`.
+    pub fn memory_atomic_notify(
+        memory: &[u8],
+        address: Self,
+        offset: u32,
+    ) -> Result<Self, TrapCode> {
+        Self::atomic_load::<i32>(memory, address, offset)?;
+        Ok(Self::from(0_i32))
+    }
+
+    /// Executes the `memory.atomic.wait32` Wasm operation.
+    ///
+    /// Read [`UntypedValue::memory_atomic_wait64`] for the returned status.
+    ///
+    /// # Note
+    ///
+    /// The caller is responsible to trap if `memory` is not a shared memory.
+    ///
+    /// # Errors
+    ///
+    /// - If `address + offset` overflows.
+    /// - If `address + offset` is not aligned to 4 bytes.
+    /// - If `address + offset` loads out of bounds from `memory`.
+    pub fn memory_atomic_wait32(
+        memory: &[u8],
+        address: Self,
+        offset: u32,
+        expected: Self,
+    ) -> Result<Self, TrapCode> {
+        Self::memory_atomic_wait::<i32>(memory, address, offset, expected)
+    }
+
+    /// Executes the `memory.atomic.wait64` Wasm operation.
+    ///
+    /// Returns `1` (not-equal) if the loaded value differs from `expected` and `2` (timed-out) otherwise.
+    /// Since no other thread could ever notify the waiter it times out immediately regardless of its timeout.
+    ///
+    /// # Note
+    ///
+    /// The caller is responsible to trap if `memory` is not a shared memory.
+    ///
+    /// # Errors
+    ///
+    /// - If `address + offset` overflows.
+    /// - If `address + offset` is not aligned to 8 bytes.
+    /// - If `address + offset` loads out of bounds from `memory`.
+    pub fn memory_atomic_wait64(
+        memory: &[u8],
+        address: Self,
+        offset: u32,
+        expected: Self,
+    ) -> Result<Self, TrapCode> {
+        Self::memory_atomic_wait::<i64>(memory, address, offset, expected)
+    }
+
     /// Execute an infallible generic operation on `T` that returns an `R`.
     fn execute_unary<T, R>(self, op: fn(T) -> R) -> Self
     where
@@ -949,6 +1058,19 @@ impl UntypedValue {
         self.execute_unary(<F32 as Float<F32>>::sqrt)
     }
 
+    /// Execute `f32.sqrt` Wasm operation using an approximation.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`UntypedValue::f32_sqrt`] the result is not correctly rounded as required by Wasm.
+    /// Where the target provides a reciprocal square root estimate it is used for positive
+    /// normal inputs yielding roughly 12 bits of precision for `f32` and roughly 22 bits
+    /// for `f64` values that fit into the `f32` range. All other inputs, or targets without
+    /// such an estimate, yield the correctly rounded result.
+    pub fn f32_sqrt_approx(self) -> Self {
+        self.execute_unary(<F32 as Float<F32>>::sqrt_approx)
+    }
+
     /// Execute `f32.min` Wasm operation.
     pub fn f32_min(self, other: Self) -> Self {
         self.execute_binary(other, <F32 as Float<F32>>::min)
@@ -999,6 +1121,19 @@ impl UntypedValue {
         self.execute_unary(<F64 as Float<F64>>::sqrt)
     }
 
+    /// Execute `f64.sqrt` Wasm operation using an approximation.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`UntypedValue::f64_sqrt`] the result is not correctly rounded as required by Wasm.
+    /// Where the target provides a reciprocal square root estimate it is used for positive
+    /// normal inputs yielding roughly 12 bits of precision for `f32` and roughly 22 bits
+    /// for `f64` values that fit into the `f32` range. All other inputs, or targets without
+    /// such an estimate, yield the correctly rounded result.
+    pub fn f64_sqrt_approx(self) -> Self {
+        self.execute_unary(<F64 as Float<F64>>::sqrt_approx)
+    }
+
     /// Execute `f32.add` Wasm operation.
     pub fn f32_add(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F32 as ArithmeticOps<F32>>::add)
@@ -254,6 +254,16 @@ pub trait Float<T>: ArithmeticOps<T> {
     fn nearest(self) -> T;
     /// Takes the square root of a number.
     fn sqrt(self) -> T;
+    /// Approximates the square root of a number.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Float::sqrt`] the result is not correctly rounded as required by Wasm.
+    /// Where the target provides a reciprocal square root estimate it is used for
+    /// positive normal inputs yielding roughly 12 bits of precision for `f32` and
+    /// roughly 22 bits for `f64` values that fit into the `f32` range.
+    /// All other inputs, or targets without such an estimate, use [`Float::sqrt`].
+    fn sqrt_approx(self) -> T;
     /// Returns `true` if the sign of the number is positive.
     fn is_sign_positive(self) -> bool;
     /// Returns `true` if the sign of the number is negative.
@@ -301,6 +311,10 @@ impl_wrap_into!(i64, i64);
 impl_wrap_into!(F32, F32);
 impl_wrap_into!(F64, F64);
 
+/// Implements `f32.demote_f64`.
+///
+/// The `as` cast rounds to nearest with ties to even, overflows to infinity for values
+/// beyond the `f32` range and yields a quiet NaN for NaN inputs as required by Wasm.
 impl WrapInto<F32> for F64 {
     #[inline]
     fn wrap_into(self) -> F32 {
@@ -677,6 +691,74 @@ mod fmath {
     pub use super::libm_adapters::{f32, f64};
 }
 
+/// Approximate math functions used by [`Float::sqrt_approx`].
+mod approx {
+    #[cfg(all(target_arch = "x86", target_feature = "sse"))]
+    use core::arch::x86::{_mm_cvtss_f32, _mm_rsqrt_ss, _mm_set_ss};
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::{_mm_cvtss_f32, _mm_rsqrt_ss, _mm_set_ss};
+
+    /// Returns the hardware estimate of `1 / sqrt(v)` if `v` is positive and normal.
+    ///
+    /// Returns `None` for all other inputs or if the target has no such estimate.
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse"
+    ))]
+    #[inline]
+    fn rsqrt_estimate(v: f32) -> Option<f32> {
+        if !v.is_normal() || v.is_sign_negative() {
+            return None;
+        }
+        // Safety: the `cfg` of this function guarantees that SSE is available.
+        let rsqrt = unsafe { _mm_cvtss_f32(_mm_rsqrt_ss(_mm_set_ss(v))) };
+        Some(rsqrt)
+    }
+
+    /// Returns the hardware estimate of `1 / sqrt(v)` if `v` is positive and normal.
+    ///
+    /// Returns `None` for all other inputs or if the target has no such estimate.
+    #[cfg(not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse"
+    )))]
+    #[inline]
+    fn rsqrt_estimate(_v: f32) -> Option<f32> {
+        None
+    }
+
+    pub mod f32 {
+        use super::{super::fmath, rsqrt_estimate};
+
+        #[inline]
+        pub fn sqrt(v: f32) -> f32 {
+            match rsqrt_estimate(v) {
+                Some(rsqrt) => v * rsqrt,
+                None => fmath::f32::sqrt(v),
+            }
+        }
+    }
+
+    pub mod f64 {
+        use super::{super::fmath, rsqrt_estimate};
+
+        #[inline]
+        pub fn sqrt(v: f64) -> f64 {
+            // Note: inputs outside of the normal `f32` range become zero,
+            //       subnormal or infinite and thus take the exact path.
+            match rsqrt_estimate(v as f32) {
+                Some(rsqrt) => {
+                    // A single Newton-Raphson step roughly doubles the precision.
+                    let rsqrt = f64::from(rsqrt);
+                    let rsqrt = rsqrt * (1.5 - 0.5 * v * rsqrt * rsqrt);
+                    v * rsqrt
+                }
+                None => fmath::f64::sqrt(v),
+            }
+        }
+    }
+}
+
 // We cannot call the math functions directly, because they are not all available in `core`.
 // In no-std cases we instead rely on `libm`.
 // These wrappers handle that delegation.
@@ -686,7 +768,8 @@ macro_rules! impl_float {
         impl Float<Self> for $type {
             #[inline]
             fn abs(self) -> Self {
-                fmath::$fXX::abs(<$fXX>::from(self)).into()
+                // Note: this clears the sign bit directly in order to preserve NaN payloads.
+                <$type>::abs(self)
             }
             #[inline]
             fn floor(self) -> Self {
@@ -724,6 +807,10 @@ macro_rules! impl_float {
                 fmath::$fXX::sqrt(<$fXX>::from(self)).into()
             }
             #[inline]
+            fn sqrt_approx(self) -> Self {
+                approx::$fXX::sqrt(<$fXX>::from(self)).into()
+            }
+            #[inline]
             fn is_sign_positive(self) -> bool {
                 <$fXX>::is_sign_positive(<$fXX>::from(self)).into()
             }
@@ -744,8 +831,13 @@ macro_rules! impl_float {
                     (false, true) => other,
                     _ => {
                         // Case: Both values are NaN; OR both values are non-NaN.
-                        if other.is_sign_negative() {
-                            return other.min(self);
+                        if <$fXX>::from(self) == 0.0 && <$fXX>::from(other) == 0.0 {
+                            // Note: `-0.0 < +0.0` for Wasm but the sign of zero is
+                            //       unspecified for the underlying `min` primitive.
+                            if self.is_sign_negative() {
+                                return self;
+                            }
+                            return other;
                         }
                         self.min(other)
                     }
@@ -760,8 +852,13 @@ macro_rules! impl_float {
                     (false, true) => other,
                     _ => {
                         // Case: Both values are NaN; OR both values are non-NaN.
-                        if other.is_sign_positive() {
-                            return other.max(self);
+                        if <$fXX>::from(self) == 0.0 && <$fXX>::from(other) == 0.0 {
+                            // Note: `-0.0 < +0.0` for Wasm but the sign of zero is
+                            //       unspecified for the underlying `max` primitive.
+                            if self.is_sign_positive() {
+                                return self;
+                            }
+                            return other;
                         }
                         self.max(other)
                     }
@@ -797,6 +894,10 @@ fn wasm_float_min_regression_works() {
         Float::min(F32::from(0.0), F32::from(-0.0)).to_bits(),
         0x8000_0000,
     );
+    assert_eq!(
+        Float::min(F64::from(0.0), F64::from(-0.0)).to_bits(),
+        0x8000_0000_0000_0000,
+    );
 }
 
 #[test]
@@ -809,6 +910,10 @@ fn wasm_float_max_regression_works() {
         Float::max(F32::from(0.0), F32::from(-0.0)).to_bits(),
         0x0000_0000,
     );
+    assert_eq!(
+        Float::max(F64::from(-0.0), F64::from(0.0)).to_bits(),
+        0x0000_0000_0000_0000,
+    );
 }
 
 impl_float!(f32, f32, i32);
@@ -832,11 +937,6 @@ fn copysign_regression_works() {
 #[cfg(not(feature = "std"))]
 mod libm_adapters {
     pub mod f32 {
-        #[inline]
-        pub fn abs(v: f32) -> f32 {
-            libm::fabsf(v)
-        }
-
         #[inline]
         pub fn floor(v: f32) -> f32 {
             libm::floorf(v)
@@ -869,11 +969,6 @@ mod libm_adapters {
     }
 
     pub mod f64 {
-        #[inline]
-        pub fn abs(v: f64) -> f64 {
-            libm::fabs(v)
-        }
-
         #[inline]
         pub fn floor(v: f64) -> f64 {
             libm::floor(v)
@@ -905,3 +1000,249 @@ mod libm_adapters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs and their expected `nearest` results with ties rounding to even.
+    const NEAREST_F64: &[(f64, f64)] = &[
+        (0.0, 0.0),
+        (0.5, 0.0),
+        (1.5, 2.0),
+        (2.5, 2.0),
+        (3.5, 4.0),
+        (-0.5, -0.0),
+        (-1.5, -2.0),
+        (-2.5, -2.0),
+        (0.49999999999999994, 0.0),
+        (0.5000000000000001, 1.0),
+        (2.4999999999999996, 2.0),
+        (-2.5000000000000004, -3.0),
+        (4503599627370495.5, 4503599627370496.0),
+        (4503599627370497.0, 4503599627370497.0),
+        (f64::MAX, f64::MAX),
+        (f64::INFINITY, f64::INFINITY),
+        (f64::NEG_INFINITY, f64::NEG_INFINITY),
+    ];
+
+    /// Inputs and their expected `nearest` results with ties rounding to even.
+    const NEAREST_F32: &[(f32, f32)] = &[
+        (0.0, 0.0),
+        (0.5, 0.0),
+        (1.5, 2.0),
+        (2.5, 2.0),
+        (3.5, 4.0),
+        (-0.5, -0.0),
+        (-1.5, -2.0),
+        (-2.5, -2.0),
+        (0.49999997, 0.0),
+        (0.50000006, 1.0),
+        (2.4999998, 2.0),
+        (-2.5000002, -3.0),
+        (8388607.5, 8388608.0),
+        (8388609.0, 8388609.0),
+        (f32::MAX, f32::MAX),
+        (f32::INFINITY, f32::INFINITY),
+        (f32::NEG_INFINITY, f32::NEG_INFINITY),
+    ];
+
+    /// Inputs and their expected `f32.demote_f64` results with ties rounding to even.
+    const DEMOTE: &[(f64, f32)] = &[
+        (0.0, 0.0),
+        (-0.0, -0.0),
+        (1.5, 1.5),
+        // Ties between two `f32` values round to the one with an even mantissa.
+        (1.0000000596046448, 1.0),
+        (1.0000001788139343, 1.0000002),
+        // Values above the tie round up to a different `f32` value.
+        (1.0000000894069672, 1.0000001),
+        (-1.0000000894069672, -1.0000001),
+        // Values beyond `f32::MAX` round to `f32::MAX` or overflow to infinity.
+        (3.4028235677973362e38, f32::MAX),
+        (3.4028235677973366e38, f32::INFINITY),
+        (-3.4028235677973366e38, f32::NEG_INFINITY),
+        (1e39, f32::INFINITY),
+        (f64::MAX, f32::INFINITY),
+        (f64::MIN, f32::NEG_INFINITY),
+        (f64::INFINITY, f32::INFINITY),
+        (f64::NEG_INFINITY, f32::NEG_INFINITY),
+        // Subnormal `f32` results are rounded with ties to even as well.
+        (1.401298464324817e-45, 1e-45),
+        (7.006492321624085e-46, 0.0),
+        (-7.006492321624085e-46, -0.0),
+        (2.1019476964872256e-45, 3e-45),
+        (1.1754942807573643e-38, f32::MIN_POSITIVE),
+        (1e-50, 0.0),
+        (f64::MIN_POSITIVE, 0.0),
+    ];
+
+    #[test]
+    fn f32_demote_f64_rounds_ties_to_even() {
+        for &(input, expected) in DEMOTE {
+            let result = f32::from(<F64 as WrapInto<F32>>::wrap_into(F64::from(input)));
+            assert_eq!(result.to_bits(), expected.to_bits(), "demote({input:?})");
+        }
+    }
+
+    #[test]
+    fn f32_demote_f64_nan() {
+        let demote = |input: f64| f32::from(<F64 as WrapInto<F32>>::wrap_into(F64::from(input)));
+        // Canonical NaN inputs yield canonical NaN results.
+        let result = demote(f64::NAN);
+        assert!(result.is_nan());
+        assert_eq!(result.to_bits() & 0x7FFF_FFFF, 0x7FC0_0000);
+        // Arithmetic NaN inputs yield arithmetic NaN results.
+        for bits in [0x7FF8_0000_0000_0001, 0xFFFF_FFFF_FFFF_FFFF] {
+            let result = demote(f64::from_bits(bits));
+            assert!(result.is_nan(), "demote({bits:#X})");
+            assert_ne!(result.to_bits() & 0x0040_0000, 0, "demote({bits:#X})");
+        }
+    }
+
+    #[test]
+    fn sqrt_matches_std() {
+        let inputs = [
+            0.0,
+            -0.0,
+            1.0,
+            2.0,
+            3.0,
+            0.5,
+            1e-40,
+            1e300,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::INFINITY,
+        ];
+        for input in inputs {
+            let result = f64::from(F64::from(input).sqrt());
+            assert_eq!(result.to_bits(), input.sqrt().to_bits(), "sqrt({input:?})");
+            let input = input as f32;
+            let result = f32::from(F32::from(input).sqrt());
+            assert_eq!(result.to_bits(), input.sqrt().to_bits(), "sqrt({input:?})");
+        }
+        for input in [-1.0, f64::NEG_INFINITY, f64::NAN] {
+            assert!(
+                f64::from(F64::from(input).sqrt()).is_nan(),
+                "sqrt({input:?})"
+            );
+            let input = input as f32;
+            assert!(
+                f32::from(F32::from(input).sqrt()).is_nan(),
+                "sqrt({input:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn sqrt_approx_is_close() {
+        let inputs = [
+            1.0,
+            2.0,
+            3.0,
+            0.5,
+            1e-30,
+            1e30,
+            12345.678,
+            f64::from(f32::MIN_POSITIVE),
+        ];
+        for input in inputs {
+            let exact = input.sqrt();
+            let result = f64::from(F64::from(input).sqrt_approx());
+            assert!(
+                ((result - exact) / exact).abs() < 1e-5,
+                "sqrt_approx({input:?})"
+            );
+            let input = input as f32;
+            let exact = input.sqrt();
+            let result = f32::from(F32::from(input).sqrt_approx());
+            assert!(
+                ((result - exact) / exact).abs() < 1e-3,
+                "sqrt_approx({input:?})"
+            );
+        }
+        // Inputs outside of the estimate's domain are computed exactly.
+        for input in [0.0, -0.0, 1e-40, 1e300, f64::MAX, f64::INFINITY] {
+            let result = f64::from(F64::from(input).sqrt_approx());
+            assert_eq!(
+                result.to_bits(),
+                input.sqrt().to_bits(),
+                "sqrt_approx({input:?})"
+            );
+        }
+        for input in [-1.0, f64::NEG_INFINITY, f64::NAN] {
+            assert!(
+                f64::from(F64::from(input).sqrt_approx()).is_nan(),
+                "sqrt_approx({input:?})"
+            );
+            let input = input as f32;
+            assert!(
+                f32::from(F32::from(input).sqrt_approx()).is_nan(),
+                "sqrt_approx({input:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn f64_nearest_ties_to_even() {
+        for &(input, expected) in NEAREST_F64 {
+            let result = f64::from(F64::from(input).nearest());
+            assert_eq!(result.to_bits(), expected.to_bits(), "nearest({input:?})");
+        }
+        assert!(f64::from(F64::from(f64::NAN).nearest()).is_nan());
+    }
+
+    #[test]
+    fn f32_nearest_ties_to_even() {
+        for &(input, expected) in NEAREST_F32 {
+            let result = f32::from(F32::from(input).nearest());
+            assert_eq!(result.to_bits(), expected.to_bits(), "nearest({input:?})");
+        }
+        assert!(f32::from(F32::from(f32::NAN).nearest()).is_nan());
+    }
+
+    #[test]
+    fn abs_neg_operate_on_sign_bit() {
+        const SIGN_F32: u32 = 1 << 31;
+        const SIGN_F64: u64 = 1 << 63;
+        let inputs_f32 = [
+            0x7FC0_0000, // quiet NaN
+            0x7FC0_0001, // quiet NaN with payload
+            0x7F80_0001, // signaling NaN
+            0xFFFF_FFFF, // negative NaN with all payload bits set
+            f32::INFINITY.to_bits(),
+            f32::NEG_INFINITY.to_bits(),
+            0.0_f32.to_bits(),
+            (-0.0_f32).to_bits(),
+            1.5_f32.to_bits(),
+            (-1.5_f32).to_bits(),
+            1e-45_f32.to_bits(),
+        ];
+        for bits in inputs_f32 {
+            let input = F32::from_bits(bits);
+            let abs = <F32 as Float<F32>>::abs(input).to_bits();
+            assert_eq!(abs, bits & !SIGN_F32, "abs({bits:#X})");
+            assert_eq!((-input).to_bits(), bits ^ SIGN_F32, "neg({bits:#X})");
+        }
+        let inputs_f64 = [
+            0x7FF8_0000_0000_0000, // quiet NaN
+            0x7FF8_0000_0000_0001, // quiet NaN with payload
+            0x7FF0_0000_0000_0001, // signaling NaN
+            0xFFFF_FFFF_FFFF_FFFF, // negative NaN with all payload bits set
+            f64::INFINITY.to_bits(),
+            f64::NEG_INFINITY.to_bits(),
+            0.0_f64.to_bits(),
+            (-0.0_f64).to_bits(),
+            1.5_f64.to_bits(),
+            (-1.5_f64).to_bits(),
+            5e-324_f64.to_bits(),
+        ];
+        for bits in inputs_f64 {
+            let input = F64::from_bits(bits);
+            let abs = <F64 as Float<F64>>::abs(input).to_bits();
+            assert_eq!(abs, bits & !SIGN_F64, "abs({bits:#X})");
+            assert_eq!((-input).to_bits(), bits ^ SIGN_F64, "neg({bits:#X})");
+        }
+    }
+}
@@ -22,6 +22,7 @@ criterion_group!(
     targets =
         bench_translate_wasm_kernel,
         bench_translate_spidermonkey,
+        bench_translate_spidermonkey_parallel,
         bench_translate_erc20,
         bench_translate_erc721,
         bench_translate_erc1155,
@@ -99,6 +100,18 @@ fn bench_translate_spidermonkey(c: &mut Criterion) {
     bench_translate_for(c, "spidermonkey", "benches/wasm/spidermonkey.wasm")
 }
 
+fn bench_translate_spidermonkey_parallel(c: &mut Criterion) {
+    c.bench_function("translate/spidermonkey/parallel", |b| {
+        let wasm_bytes = load_wasm_from_file("benches/wasm/spidermonkey.wasm");
+        let mut config = bench_config();
+        config.set_compilation_threads(4);
+        b.iter(|| {
+            let engine = Engine::new(&config);
+            let _module = Module::new(&engine, &wasm_bytes[..]).unwrap();
+        })
+    });
+}
+
 fn bench_translate_erc20(c: &mut Criterion) {
     bench_translate_for(c, "erc20", "benches/wasm/erc20.wasm")
 }
@@ -18,6 +18,7 @@ pub use self::utils::{
     FuncIdx,
     GlobalIdx,
     LocalDepth,
+    MemoryIdx,
     SignatureIdx,
     TableIdx,
 };
@@ -170,6 +171,20 @@ pub enum Instruction {
     I64Store8(AddressOffset),
     I64Store16(AddressOffset),
     I64Store32(AddressOffset),
+    /// Wasm `memory.atomic.notify` instruction from the `threads` proposal.
+    MemoryAtomicNotify(AddressOffset),
+    /// Wasm `memory.atomic.wait32` instruction from the `threads` proposal.
+    MemoryAtomicWait32(AddressOffset),
+    /// Wasm `memory.atomic.wait64` instruction from the `threads` proposal.
+    MemoryAtomicWait64(AddressOffset),
+    /// Makes the linear memory at the index the one accessed by subsequent memory instructions.
+    ///
+    /// # Note
+    ///
+    /// Used by the multi-memory Wasm proposal. Memory instructions that access a
+    /// non-default linear memory are surrounded by a [`Instruction::UseMemory`]
+    /// selecting their linear memory and one selecting the default linear memory again.
+    UseMemory(MemoryIdx),
     MemorySize,
     MemoryGrow,
     MemoryFill,
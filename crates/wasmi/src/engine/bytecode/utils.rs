@@ -59,6 +59,24 @@ impl TableIdx {
     }
 }
 
+/// A linear memory index.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct MemoryIdx([u8; 4]);
+
+impl From<u32> for MemoryIdx {
+    fn from(index: u32) -> Self {
+        Self(index.to_ne_bytes())
+    }
+}
+
+impl MemoryIdx {
+    /// Returns the index value as `u32`.
+    pub fn to_u32(self) -> u32 {
+        u32::from_ne_bytes(self.0)
+    }
+}
+
 /// An index of a unique function signature.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(transparent)]
@@ -273,7 +291,6 @@ impl AddressOffset {
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct BranchOffset(i32);
 
-#[cfg(test)]
 impl From<i32> for BranchOffset {
     fn from(index: i32) -> Self {
         Self(index)
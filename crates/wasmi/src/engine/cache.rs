@@ -1,4 +1,15 @@
-use super::bytecode::{DataSegmentIdx, ElementSegmentIdx, FuncIdx, GlobalIdx, TableIdx};
+use super::{
+    bytecode::{
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        GlobalIdx,
+        MemoryIdx,
+        SignatureIdx,
+        TableIdx,
+    },
+    DedupFuncType,
+};
 use crate::{
     instance::InstanceEntity,
     memory::DataSegment,
@@ -19,18 +30,27 @@ use wasmi_core::UntypedValue;
 #[derive(Debug)]
 #[repr(C)]
 pub struct InstanceCache {
-    /// The bytes of a default linear memory of the currently used [`Instance`].
+    /// The bytes of the linear memory returned by [`InstanceCache::default_memory`].
     default_memory_bytes: Option<NonNull<[u8]>>,
     /// The last accessed global variable value of the currently used [`Instance`].
     last_global: Option<(GlobalIdx, NonNull<UntypedValue>)>,
     /// The current instance in use.
     instance: Instance,
-    /// The default linear memory of the currently used [`Instance`].
+    /// The linear memory accessed by memory instructions of the currently used [`Instance`].
+    ///
+    /// This is the default linear memory unless selected otherwise via [`InstanceCache::use_memory`].
     default_memory: Option<Memory>,
+    /// The last accessed non-default linear memory of the currently used [`Instance`].
+    last_memory: Option<(MemoryIdx, Memory)>,
     /// The last accessed table of the currently used [`Instance`].
     last_table: Option<(TableIdx, Table)>,
     /// The last accessed function of the currently used [`Instance`].
     last_func: Option<(FuncIdx, Func)>,
+    /// The last resolved function signature of the currently used [`Instance`].
+    last_signature: Option<(SignatureIdx, DedupFuncType)>,
+    /// The number of function signatures resolved from the currently used [`Instance`].
+    #[cfg(test)]
+    signature_loads: usize,
 }
 
 impl From<&'_ Instance> for InstanceCache {
@@ -38,10 +58,14 @@ impl From<&'_ Instance> for InstanceCache {
         Self {
             instance: *instance,
             default_memory: None,
+            last_memory: None,
             last_table: None,
             last_func: None,
+            last_signature: None,
             last_global: None,
             default_memory_bytes: None,
+            #[cfg(test)]
+            signature_loads: 0,
         }
     }
 }
@@ -59,8 +83,10 @@ impl InstanceCache {
     fn set_instance(&mut self, instance: &Instance) {
         self.instance = *instance;
         self.default_memory = None;
+        self.last_memory = None;
         self.last_table = None;
         self.last_func = None;
+        self.last_signature = None;
         self.last_global = None;
         self.default_memory_bytes = None;
     }
@@ -165,7 +191,9 @@ impl InstanceCache {
         self.default_memory.insert(default_memory)
     }
 
-    /// Returns the default [`Memory`] of the currently used [`Instance`].
+    /// Returns the [`Memory`] accessed by memory instructions of the currently used [`Instance`].
+    ///
+    /// This is the default linear memory unless selected otherwise via [`InstanceCache::use_memory`].
     ///
     /// # Panics
     ///
@@ -203,6 +231,59 @@ impl InstanceCache {
             .insert(ctx.resolve_memory_mut(&memory).data().into())
     }
 
+    /// Makes memory instructions access the linear memory at `index` of the currently used [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// This is used by the multi-memory Wasm proposal. Selecting the default
+    /// linear memory again restores the default behavior.
+    ///
+    /// # Panics
+    ///
+    /// If the currently used [`Instance`] does not have a linear memory at `index`.
+    #[inline]
+    pub fn use_memory(&mut self, ctx: &StoreInner, index: MemoryIdx) {
+        self.default_memory_bytes = None;
+        self.default_memory = match index.to_u32() {
+            DEFAULT_MEMORY_INDEX => None,
+            _ => Some(self.get_memory(ctx, index)),
+        };
+    }
+
+    /// Returns the non-default [`Memory`] at `index` of the currently used [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// If the currently used [`Instance`] does not have a linear memory at `index`.
+    #[inline]
+    fn get_memory(&mut self, ctx: &StoreInner, index: MemoryIdx) -> Memory {
+        match self.last_memory {
+            Some((memory_index, memory)) if index == memory_index => memory,
+            _ => self.load_memory_at(ctx, index),
+        }
+    }
+
+    /// Loads the [`Memory`] at `index` of the currently used [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// If the currently used [`Instance`] does not have the linear memory.
+    #[cold]
+    #[inline]
+    fn load_memory_at(&mut self, ctx: &StoreInner, index: MemoryIdx) -> Memory {
+        let memory = ctx
+            .resolve_instance(self.instance())
+            .get_memory(index.to_u32())
+            .unwrap_or_else(|| {
+                unreachable!(
+                    "missing linear memory at index {index:?} for instance: {:?}",
+                    self.instance
+                )
+            });
+        self.last_memory = Some((index, memory));
+        memory
+    }
+
     /// Clears the cached default memory instance.
     ///
     /// # Note
@@ -302,6 +383,51 @@ impl InstanceCache {
         }
     }
 
+    /// Loads the function signature at `index` of the currently used [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// If the currently used [`Instance`] does not have the function signature.
+    #[cold]
+    #[inline]
+    fn load_signature_at(&mut self, ctx: &StoreInner, index: SignatureIdx) -> DedupFuncType {
+        let signature = ctx
+            .resolve_instance(self.instance())
+            .get_signature(index.to_u32())
+            .copied()
+            .unwrap_or_else(|| {
+                unreachable!(
+                    "missing signature at index {index:?} for instance: {:?}",
+                    self.instance
+                )
+            });
+        #[cfg(test)]
+        {
+            self.signature_loads += 1;
+        }
+        self.last_signature = Some((index, signature));
+        signature
+    }
+
+    /// Returns the function signature at `index` of the currently used [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// The signature expected by a `call_indirect` is constant for its call site.
+    /// Caching the last resolved signature avoids resolving it for every call
+    /// when the same call site is executed repeatedly, e.g. in a loop.
+    ///
+    /// # Panics
+    ///
+    /// If the currently used [`Instance`] does not have a function signature at the index.
+    #[inline]
+    pub fn get_signature(&mut self, ctx: &StoreInner, signature: SignatureIdx) -> DedupFuncType {
+        match self.last_signature {
+            Some((index, dedup)) if index == signature => dedup,
+            _ => self.load_signature_at(ctx, signature),
+        }
+    }
+
     /// Loads the pointer to the value of the global variable at `index`
     /// of the currently used [`Instance`].
     ///
@@ -375,3 +501,41 @@ impl InstanceCache {
         *self.get_global_mut(ctx, global_index) = new_value;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Engine, Linker, Module, Store};
+
+    #[test]
+    fn get_signature_is_cached() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (type $a (func))
+                (type $b (func (param i32)))
+            )
+            "#,
+        )
+        .unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let mut cache = InstanceCache::from(&instance);
+        let a = SignatureIdx::from(0);
+        let b = SignatureIdx::from(1);
+        let sig_a = cache.get_signature(&store.inner, a);
+        for _ in 0..10 {
+            assert_eq!(cache.get_signature(&store.inner, a), sig_a);
+        }
+        assert_eq!(cache.signature_loads, 1);
+        let sig_b = cache.get_signature(&store.inner, b);
+        assert_ne!(sig_a, sig_b);
+        assert_eq!(cache.signature_loads, 2);
+    }
+}
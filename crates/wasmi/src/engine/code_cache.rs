@@ -0,0 +1,345 @@
+//! Eviction of the compiled code of least recently used Wasm modules.
+//!
+//! If enabled via [`Config::set_compiled_code_budget`] the [`Engine`] registers the
+//! compiled functions of every Wasm module as a [`CodeUnit`] together with the Wasm
+//! module bytes. Whenever the compiled code of all [`CodeUnit`] exceeds the budget the
+//! compiled functions of the least recently used [`CodeUnit`] are deallocated. They are
+//! recompiled from the retained Wasm module bytes before they are executed again.
+//!
+//! [`Config::set_compiled_code_budget`]: crate::Config::set_compiled_code_budget
+//! [`Engine`]: super::Engine
+
+use super::CompiledFunc;
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A reference to the compiled functions of a Wasm module registered to a [`CodeCache`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CodeUnit(usize);
+
+/// The compiled functions of a Wasm module and the Wasm module bytes to recompile them.
+#[derive(Debug)]
+struct CodeUnitEntity {
+    /// The Wasm module bytes that the compiled functions are translated from.
+    wasm: Arc<[u8]>,
+    /// The compiled functions of all internal functions of the Wasm module.
+    funcs: Arc<[CompiledFunc]>,
+    /// The estimated bytes held by the compiled functions or `None` if evicted.
+    footprint: Option<usize>,
+    /// The value of the [`CodeCache`] clock when the [`CodeUnit`] has been used last.
+    last_use: AtomicUsize,
+}
+
+/// The compiled function bodies of a [`CodeUnit`] that has to be recompiled.
+#[derive(Debug)]
+pub struct EvictedCode {
+    /// The Wasm module bytes to recompile the compiled functions from.
+    pub wasm: Arc<[u8]>,
+    /// The uninitialized compiled functions in the order of their Wasm functions.
+    pub funcs: Arc<[CompiledFunc]>,
+}
+
+/// The registered [`CodeUnit`] of an [`Engine`] in the order of their registration.
+///
+/// [`Engine`]: super::Engine
+#[derive(Debug, Default)]
+pub struct CodeCache {
+    /// The number of [`CodeUnit`] registered before the last [`CodeCache::reset`].
+    ///
+    /// The `units` are indexed relative to `base` so that
+    /// [`CodeUnit`] from before the reset are never reused.
+    base: usize,
+    /// The entities of all registered [`CodeUnit`].
+    units: Vec<CodeUnitEntity>,
+    /// A counter that advances whenever a [`CodeUnit`] is used.
+    clock: AtomicUsize,
+}
+
+impl CodeCache {
+    /// Removes all registered [`CodeUnit`] from the [`CodeCache`].
+    pub fn reset(&mut self) {
+        let base = self.base + self.units.len();
+        *self = Self {
+            base,
+            ..Self::default()
+        };
+    }
+
+    /// Returns the [`CodeUnitEntity`] of `unit` if registered since the last [`CodeCache::reset`].
+    fn get(&self, unit: CodeUnit) -> Option<&CodeUnitEntity> {
+        self.units.get(unit.0.checked_sub(self.base)?)
+    }
+
+    /// Returns the next value of the clock of the [`CodeCache`].
+    fn tick(&self) -> usize {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Registers the compiled `funcs` of the Wasm module `wasm` as a new [`CodeUnit`].
+    ///
+    /// The `footprint` is the estimated number of bytes held by the compiled `funcs`.
+    pub fn register(
+        &mut self,
+        wasm: Arc<[u8]>,
+        funcs: Arc<[CompiledFunc]>,
+        footprint: usize,
+    ) -> CodeUnit {
+        let unit = CodeUnit(self.base + self.units.len());
+        let last_use = AtomicUsize::new(self.tick());
+        self.units.push(CodeUnitEntity {
+            wasm,
+            funcs,
+            footprint: Some(footprint),
+            last_use,
+        });
+        unit
+    }
+
+    /// Marks all `units` as used and returns the first evicted [`CodeUnit`] of them if any.
+    ///
+    /// [`CodeUnit`] registered before the last [`CodeCache::reset`] are ignored.
+    pub fn use_units(&self, units: &[CodeUnit]) -> Option<CodeUnit> {
+        for &unit in units {
+            let entity = match self.get(unit) {
+                Some(entity) => entity,
+                None => continue,
+            };
+            if entity.footprint.is_none() {
+                return Some(unit);
+            }
+            entity.last_use.store(self.tick(), Ordering::Relaxed);
+        }
+        None
+    }
+
+    /// Returns the [`EvictedCode`] of `unit` if it has been evicted.
+    pub fn evicted_code(&self, unit: CodeUnit) -> Option<EvictedCode> {
+        let entity = self.get(unit)?;
+        if entity.footprint.is_some() {
+            return None;
+        }
+        Some(EvictedCode {
+            wasm: entity.wasm.clone(),
+            funcs: entity.funcs.clone(),
+        })
+    }
+
+    /// Marks the evicted `unit` as recompiled with a new `footprint`.
+    ///
+    /// # Panics
+    ///
+    /// If `unit` has been registered before the last [`CodeCache::reset`].
+    pub fn recompiled(&mut self, unit: CodeUnit, footprint: usize) {
+        let last_use = self.tick();
+        let entity = unit
+            .0
+            .checked_sub(self.base)
+            .and_then(|index| self.units.get_mut(index))
+            .unwrap_or_else(|| panic!("tried to recompile invalid code unit: {unit:?}"));
+        entity.footprint = Some(footprint);
+        *entity.last_use.get_mut() = last_use;
+    }
+
+    /// Evicts the least recently used [`CodeUnit`] until their footprint fits into `budget`.
+    ///
+    /// Calls `evict` for every compiled function of the evicted [`CodeUnit`].
+    ///
+    /// # Note
+    ///
+    /// The [`CodeUnit`] in `keep` are never evicted. Therefore the footprint of
+    /// the remaining compiled [`CodeUnit`] might still exceed the `budget`.
+    pub fn evict_lru(
+        &mut self,
+        budget: usize,
+        keep: &[CodeUnit],
+        mut evict: impl FnMut(CompiledFunc),
+    ) {
+        let mut total = self
+            .units
+            .iter()
+            .filter_map(|entity| entity.footprint)
+            .sum::<usize>();
+        while total > budget {
+            let lru = self
+                .units
+                .iter()
+                .enumerate()
+                .filter(|(index, entity)| {
+                    entity.footprint.is_some() && !keep.contains(&CodeUnit(self.base + index))
+                })
+                .min_by_key(|(_, entity)| entity.last_use.load(Ordering::Relaxed))
+                .map(|(index, _)| index);
+            let entity = match lru {
+                Some(index) => &mut self.units[index],
+                None => return,
+            };
+            entity.funcs.iter().copied().for_each(&mut evict);
+            total -= entity.footprint.take().unwrap_or(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Config,
+        Engine,
+        EngineBackend,
+        Linker,
+        Module,
+        StepOutcome,
+        Store,
+        TypedFunc,
+        Value,
+    };
+
+    /// Returns a Wasm module whose exported `f` function returns `n` times `value`.
+    fn wasm(value: i32) -> Vec<u8> {
+        let wat = format!(
+            r#"
+            (module
+                (func $add (param $acc i32) (result i32)
+                    (i32.add (local.get $acc) (i32.const {value}))
+                )
+                (func (export "f") (param $n i32) (result i32)
+                    (local $acc i32)
+                    (block $exit
+                        (loop $continue
+                            (br_if $exit (i32.eqz (local.get $n)))
+                            (local.set $acc (call $add (local.get $acc)))
+                            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                            (br $continue)
+                        )
+                    )
+                    (local.get $acc)
+                )
+            )
+            "#
+        );
+        wat::parse_str(wat).unwrap()
+    }
+
+    /// Returns an [`Engine`] that keeps `budget` bytes of compiled code if any.
+    fn engine(budget: Option<usize>) -> Engine {
+        let mut config = Config::default();
+        config.set_engine_backend(EngineBackend::RegisterMachine);
+        if let Some(budget) = budget {
+            config.set_compiled_code_budget(budget);
+        }
+        Engine::new(&config)
+    }
+
+    /// Returns the compiled functions of the internal functions of `module`.
+    fn compiled_funcs(module: &Module) -> Vec<CompiledFunc> {
+        module.internal_funcs().map(|(_, func)| func).collect()
+    }
+
+    /// Returns the estimated bytes held by the compiled functions of a module of [`wasm`].
+    fn footprint() -> usize {
+        let engine = engine(None);
+        let module = Module::new(&engine, &wasm(1)[..]).unwrap();
+        let funcs = compiled_funcs(&module);
+        let res = engine.inner.res.read();
+        res.code_map_2.funcs_memory_usage(&funcs)
+    }
+
+    /// Returns `true` if all compiled functions of `module` are initialized.
+    fn is_compiled(module: &Module) -> bool {
+        let res = module.engine().inner.res.read();
+        compiled_funcs(module)
+            .into_iter()
+            .all(|func| res.code_map_2.is_initialized(func))
+    }
+
+    /// Instantiates `module` in `store` and returns its exported `f` function.
+    fn instantiate(store: &mut Store<()>, module: &Module) -> TypedFunc<i32, i32> {
+        <Linker<()>>::new(store.engine())
+            .instantiate(&mut *store, module)
+            .unwrap()
+            .start(&mut *store)
+            .unwrap()
+            .get_typed_func(&*store, "f")
+            .unwrap()
+    }
+
+    #[test]
+    fn evicts_lru_module_and_recompiles_it() {
+        let footprint = footprint();
+        let engine = engine(Some(2 * footprint + footprint / 2));
+        let a = Module::new(&engine, &wasm(1)[..]).unwrap();
+        let b = Module::new(&engine, &wasm(2)[..]).unwrap();
+        let mut store_a = Store::new(&engine, ());
+        let mut store_b = Store::new(&engine, ());
+        let f_a = instantiate(&mut store_a, &a);
+        let f_b = instantiate(&mut store_b, &b);
+        assert_eq!(f_b.call(&mut store_b, 10).unwrap(), 20);
+        assert_eq!(f_a.call(&mut store_a, 10).unwrap(), 10);
+        // Module `b` is the least recently used module.
+        let c = Module::new(&engine, &wasm(3)[..]).unwrap();
+        assert!(is_compiled(&a));
+        assert!(!is_compiled(&b));
+        assert!(is_compiled(&c));
+        // Calling `b` recompiles it and evicts the now least recently used module `a`.
+        assert_eq!(f_b.call(&mut store_b, 10).unwrap(), 20);
+        assert!(!is_compiled(&a));
+        assert!(is_compiled(&b));
+        assert!(is_compiled(&c));
+        assert_eq!(f_a.call(&mut store_a, 5).unwrap(), 5);
+        assert_eq!(f_b.call(&mut store_b, 5).unwrap(), 10);
+        let mut store_c = Store::new(&engine, ());
+        let f_c = instantiate(&mut store_c, &c);
+        assert_eq!(f_c.call(&mut store_c, 5).unwrap(), 15);
+    }
+
+    #[test]
+    fn keeps_modules_of_calling_store() {
+        let engine = engine(Some(1));
+        let a = Module::new(&engine, &wasm(1)[..]).unwrap();
+        let b = Module::new(&engine, &wasm(2)[..]).unwrap();
+        assert!(!is_compiled(&a));
+        assert!(is_compiled(&b));
+        let mut store = Store::new(&engine, ());
+        let f_a = instantiate(&mut store, &a);
+        let f_b = instantiate(&mut store, &b);
+        assert_eq!(f_a.call(&mut store, 3).unwrap(), 3);
+        assert_eq!(f_b.call(&mut store, 3).unwrap(), 6);
+        // Both modules are kept although they exceed the budget
+        // since both have been instantiated in the calling store.
+        assert!(is_compiled(&a));
+        assert!(is_compiled(&b));
+    }
+
+    #[test]
+    fn stepped_calls_pin_compiled_code() {
+        let engine = engine(Some(1));
+        let a = Module::new(&engine, &wasm(1)[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let f_a = instantiate(&mut store, &a);
+        let mut stepped = f_a
+            .func()
+            .call_stepped(&mut store, &[Value::I32(2)])
+            .unwrap();
+        let b = Module::new(&engine, &wasm(2)[..]).unwrap();
+        assert!(is_compiled(&a));
+        assert!(is_compiled(&b));
+        let mut outputs = [Value::I32(0)];
+        while stepped.step(&mut store, &mut outputs).unwrap() == StepOutcome::Running {}
+        assert_eq!(outputs[0].i32(), Some(2));
+        drop(stepped);
+        let c = Module::new(&engine, &wasm(3)[..]).unwrap();
+        assert!(!is_compiled(&a));
+        assert!(!is_compiled(&b));
+        assert!(is_compiled(&c));
+    }
+
+    #[test]
+    fn no_eviction_without_budget() {
+        let engine = engine(None);
+        let modules = (0..3)
+            .map(|value| Module::new(&engine, &wasm(value)[..]).unwrap())
+            .collect::<Vec<_>>();
+        assert!(modules.iter().all(is_compiled));
+        assert!(engine.inner.res.read().code_cache.units.is_empty());
+    }
+}
@@ -1,7 +1,13 @@
 //! Datastructure to efficiently store function bodies and their instructions.
 
-use super::Instruction;
+use super::{
+    func_locations::{FuncLocations, WasmOffsets},
+    memory_usage::vec_bytes,
+    Instruction,
+};
+use crate::core::TrapLocation;
 use alloc::vec::Vec;
+use core::mem::size_of;
 use wasmi_arena::ArenaIndex;
 
 /// A reference to a compiled function stored in the [`CodeMap`] of an [`Engine`](crate::Engine).
@@ -18,6 +24,32 @@ impl CompiledFunc {
     pub fn from_u32(index: u32) -> Self {
         Self(index)
     }
+
+    /// Returns the index of the [`CompiledFunc`] relative to the first function
+    /// allocated after the last [`Engine::reset`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] was allocated before the last [`Engine::reset`].
+    ///
+    /// [`Engine::reset`]: crate::Engine::reset
+    #[track_caller]
+    pub fn index_since(self, base: usize) -> usize {
+        match self.into_usize().checked_sub(base) {
+            Some(index) => index,
+            None => used_after_reset(self),
+        }
+    }
+}
+
+/// Panics because `func` was used after the [`Engine::reset`] that invalidated it.
+///
+/// [`Engine::reset`]: crate::Engine::reset
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn used_after_reset(func: CompiledFunc) -> ! {
+    panic!("tried to use compiled function {func:?} after Engine::reset")
 }
 
 impl ArenaIndex for CompiledFunc {
@@ -133,6 +165,11 @@ impl FuncHeader {
 /// Datastructure to efficiently store Wasm function bodies.
 #[derive(Debug)]
 pub struct CodeMap {
+    /// The number of [`CompiledFunc`] allocated before the last [`CodeMap::reset`].
+    ///
+    /// New [`CompiledFunc`] continue to count up from here so that
+    /// [`CompiledFunc`] from before the reset are never reused.
+    base: usize,
     /// The headers of all compiled functions.
     headers: Vec<FuncHeader>,
     /// The instructions of all allocated function bodies.
@@ -145,17 +182,25 @@ pub struct CodeMap {
     /// Also this improves efficiency of deallocating the [`CodeMap`]
     /// and generally improves data locality.
     instrs: Vec<Instruction>,
+    /// The [`FuncLocations`] of compiled functions that record them.
+    ///
+    /// Each entry also holds the index of the first instruction of the function
+    /// within `instrs` and the index of the Wasm function within its module.
+    /// Entries are sorted by the index of the first instruction of the function.
+    locations: Vec<(usize, u32, FuncLocations)>,
 }
 
 impl Default for CodeMap {
     fn default() -> Self {
         Self {
+            base: 0,
             headers: Vec::new(),
             // The first instruction always is a simple trapping instruction
             // so that we safely can use `InstructionsRef(0)` as an uninitialized
             // index value for compiled functions that have yet to be
             // initialized with their actual function bodies.
             instrs: vec![Instruction::Unreachable],
+            locations: Vec::new(),
         }
     }
 }
@@ -168,11 +213,23 @@ impl CodeMap {
     /// The uninitialized [`CompiledFunc`] must be initialized using
     /// [`CodeMap::init_func`] before it is executed.
     pub fn alloc_func(&mut self) -> CompiledFunc {
-        let header_index = self.headers.len();
+        let header_index = self.base + self.headers.len();
         self.headers.push(FuncHeader::uninit());
         CompiledFunc::from_usize(header_index)
     }
 
+    /// Removes all compiled functions from the [`CodeMap`].
+    ///
+    /// All [`CompiledFunc`] allocated so far are invalidated and
+    /// cause a panic when used with the [`CodeMap`] afterwards.
+    pub fn reset(&mut self) {
+        let base = self.base + self.headers.len();
+        *self = Self {
+            base,
+            ..Self::default()
+        };
+    }
+
     /// Initializes the [`CompiledFunc`].
     ///
     /// # Panics
@@ -195,7 +252,72 @@ impl CodeMap {
         let start = self.instrs.len();
         self.instrs.extend(instrs);
         let iref = InstructionsRef::new(start);
-        self.headers[func.into_usize()] = FuncHeader::new(iref, len_locals, local_stack_height);
+        self.headers[func.index_since(self.base)] =
+            FuncHeader::new(iref, len_locals, local_stack_height);
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`] of the Wasm function at `func_index`.
+    ///
+    /// The [`WasmOffsets`] are empty after this operation.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_wasm_offsets(
+        &mut self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_offsets: &mut WasmOffsets,
+    ) {
+        let start = self.header(func).iref.to_usize();
+        let mut locations = FuncLocations::default();
+        locations.set_wasm_offsets(wasm_offsets);
+        let pos = self
+            .locations
+            .partition_point(|(first, _, _)| *first < start);
+        self.locations.insert(pos, (start, func_index, locations));
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] if it records its [`FuncLocations`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_func_name(&mut self, func: CompiledFunc, name: &str) {
+        let start = self.header(func).iref.to_usize();
+        if let Ok(pos) = self
+            .locations
+            .binary_search_by_key(&start, |(first, _, _)| *first)
+        {
+            self.locations[pos].2.set_name(name);
+        }
+    }
+
+    /// Returns the [`TrapLocation`] of the instruction that `ip` points to if any.
+    ///
+    /// Returns `None` if `ip` does not point to an instruction of a compiled
+    /// function that records its [`FuncLocations`].
+    pub fn trap_location(&self, ip: InstructionPtr) -> Option<TrapLocation> {
+        if self.locations.is_empty() {
+            return None;
+        }
+        let index = (ip.ptr as usize).checked_sub(self.instrs.as_ptr() as usize)?
+            / size_of::<Instruction>();
+        let pos = self
+            .locations
+            .partition_point(|(first, _, _)| *first <= index);
+        let (first, func_index, locations) = self.locations.get(pos.checked_sub(1)?)?;
+        locations.locate(*func_index, index - first)
+    }
+
+    /// Returns the estimated bytes held by the [`CodeMap`].
+    pub fn memory_usage(&self) -> usize {
+        let locations = self
+            .locations
+            .iter()
+            .map(|(_, _, locations)| locations.memory_usage())
+            .sum::<usize>();
+        vec_bytes(&self.headers) + vec_bytes(&self.instrs) + vec_bytes(&self.locations) + locations
     }
 
     /// Returns an [`InstructionPtr`] to the instruction at [`InstructionsRef`].
@@ -204,9 +326,23 @@ impl CodeMap {
         InstructionPtr::new(self.instrs[iref.to_usize()..].as_ptr())
     }
 
+    /// Returns `true` if `func` refers to an initialized [`CompiledFunc`] of the [`CodeMap`].
+    pub fn is_initialized(&self, func: CompiledFunc) -> bool {
+        let header = func
+            .into_usize()
+            .checked_sub(self.base)
+            .and_then(|index| self.headers.get(index));
+        matches!(header, Some(header) if !header.is_uninit())
+    }
+
     /// Returns the [`FuncHeader`] of the [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func_body` was allocated before the last [`CodeMap::reset`].
+    #[track_caller]
     pub fn header(&self, func_body: CompiledFunc) -> &FuncHeader {
-        &self.headers[func_body.into_usize()]
+        &self.headers[func_body.index_since(self.base)]
     }
 
     /// Resolves the instruction at `index` of the [`CompiledFunc`].
@@ -226,7 +362,7 @@ impl CodeMap {
     #[cfg(test)]
     fn instr_end(&self, func_body: CompiledFunc) -> usize {
         self.headers
-            .get(func_body.into_usize() + 1)
+            .get(func_body.index_since(self.base) + 1)
             .map(|header| header.iref.to_usize())
             .unwrap_or(self.instrs.len())
     }
@@ -1,5 +1,6 @@
-use super::{stack::StackLimits, DropKeep};
-use core::{mem::size_of, num::NonZeroU64};
+use super::{func_body_bytes::BYTECODE_VERSION, stack::StackLimits, DropKeep};
+use crate::memory::guarded;
+use core::{hash::Hasher, mem::size_of, num::NonZeroU64};
 use wasmi_core::UntypedValue;
 use wasmparser::WasmFeatures;
 
@@ -31,6 +32,12 @@ pub struct Config {
     tail_call: bool,
     /// Is `true` if the [`extended-const`] Wasm proposal is enabled.
     extended_const: bool,
+    /// Is `true` if the [`multi-memory`] Wasm proposal is enabled.
+    multi_memory: bool,
+    /// Is `true` if the [`simd`] Wasm proposal is enabled.
+    simd: bool,
+    /// Is `true` if the [`threads`] Wasm proposal is enabled.
+    threads: bool,
     /// Is `true` if Wasm instructions on `f32` and `f64` types are allowed.
     floats: bool,
     /// Is `true` if `wasmi` executions shall consume fuel.
@@ -41,6 +48,54 @@ pub struct Config {
     fuel_consumption_mode: FuelConsumptionMode,
     /// The configured fuel costs of all `wasmi` bytecode instructions.
     fuel_costs: FuelCosts,
+    /// The maximum number of functions a Wasm module may define if any.
+    max_functions: Option<u32>,
+    /// The maximum number of parameters a Wasm function type may declare if any.
+    max_function_params: Option<u32>,
+    /// The maximum number of results a Wasm function type may declare if any.
+    max_function_results: Option<u32>,
+    /// The maximum length of provider slices encoded by the register-machine translator if any.
+    max_provider_slice_len: Option<u32>,
+    /// The maximum number of labels a Wasm function may require if any.
+    max_function_labels: Option<u32>,
+    /// The maximum number of branches a Wasm function may contain if any.
+    max_function_branches: Option<u32>,
+    /// Is `true` if the register-machine translator may fuse multiple copies into one instruction.
+    fused_copies: bool,
+    /// Is `true` if the register-machine translator hoists bounds checks out of counted loops.
+    hoist_loop_bounds_checks: bool,
+    /// Is `true` if out of bounds linear memory accesses are detected via guard pages if supported.
+    signal_bounds_checks: bool,
+    /// Is `true` if internal invariant failures are reported as errors instead of panics.
+    recoverable_internal_errors: bool,
+    /// Is `true` if the executor checks additional runtime invariants in debug builds.
+    checked_execution: bool,
+    /// Is `true` if the executor verifies its wrapping integer arithmetic in debug builds.
+    checked_arithmetic: bool,
+    /// Is `true` if the executor corrupts its wrapping integer arithmetic.
+    #[cfg(all(test, feature = "checked-arithmetic"))]
+    corrupt_arithmetic: bool,
+    /// Is `true` if `f32.sqrt` and `f64.sqrt` are executed using an approximation.
+    approx_float_sqrt: bool,
+    /// Is `true` if translators check their emulated value stack height against validation.
+    strict_stack_height_checks: bool,
+    /// The number of translated operators after which translators corrupt their value stack if any.
+    #[cfg(test)]
+    corrupt_stack_height_after: Option<usize>,
+    /// The data structure used to deduplicate constant values.
+    const_dedup: ConstDedup,
+    /// Is `true` if traps folded at compilation time carry a descriptive message.
+    folded_trap_messages: bool,
+    /// Is `true` if traps of Wasm functions carry their function name and Wasm binary offset.
+    trap_locations: bool,
+    /// Is `true` if the executor counts executed instructions per opcode.
+    profile_instructions: bool,
+    /// The bytecode version that exported and serialized code must be compatible with.
+    bytecode_version: u32,
+    /// The number of threads translating the function bodies of a Wasm module.
+    compilation_threads: usize,
+    /// The bytes of compiled code kept by the engine before evicting least recently used modules.
+    compiled_code_budget: Option<usize>,
 }
 
 /// The backend executor of an [`Engine`](crate::Engine).
@@ -101,6 +156,43 @@ pub enum FuelConsumptionMode {
     Eager,
 }
 
+/// The data structure used to deduplicate constant values during translation.
+///
+/// # Note
+///
+/// Both data structures yield identical deduplication results and only
+/// differ in their compilation performance.
+///
+/// The default is [`ConstDedup::BTree`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ConstDedup {
+    /// Deduplicates constant values using an ordered B-tree map.
+    #[default]
+    BTree,
+    /// Deduplicates constant values using a hash map with a fast non-cryptographic hasher.
+    ///
+    /// # Note
+    ///
+    /// This may speed up compilation of modules with many distinct constant values.
+    /// Without the `std` crate feature this falls back to [`ConstDedup::BTree`].
+    Hash,
+}
+
+impl ConstDedup {
+    /// Returns the [`ConstDedup`] data structure that is actually in use.
+    ///
+    /// # Note
+    ///
+    /// Without the `std` crate feature [`ConstDedup::Hash`] falls back to [`ConstDedup::BTree`].
+    fn effective(self) -> Self {
+        match self {
+            #[cfg(not(feature = "std"))]
+            Self::Hash => Self::BTree,
+            dedup => dedup,
+        }
+    }
+}
+
 /// Type storing all kinds of fuel costs of instructions.
 #[derive(Debug, Copy, Clone)]
 pub struct FuelCosts {
@@ -216,11 +308,39 @@ impl Default for Config {
             reference_types: true,
             tail_call: false,
             extended_const: false,
+            multi_memory: false,
+            simd: false,
+            threads: false,
             floats: true,
             consume_fuel: false,
             backend: EngineBackend::default(),
             fuel_costs: FuelCosts::default(),
             fuel_consumption_mode: FuelConsumptionMode::default(),
+            max_functions: None,
+            max_function_params: None,
+            max_function_results: None,
+            max_provider_slice_len: None,
+            max_function_labels: None,
+            max_function_branches: None,
+            fused_copies: true,
+            hoist_loop_bounds_checks: false,
+            signal_bounds_checks: false,
+            recoverable_internal_errors: false,
+            checked_execution: false,
+            checked_arithmetic: false,
+            #[cfg(all(test, feature = "checked-arithmetic"))]
+            corrupt_arithmetic: false,
+            approx_float_sqrt: false,
+            strict_stack_height_checks: false,
+            #[cfg(test)]
+            corrupt_stack_height_after: None,
+            const_dedup: ConstDedup::default(),
+            folded_trap_messages: false,
+            trap_locations: false,
+            profile_instructions: false,
+            bytecode_version: BYTECODE_VERSION,
+            compilation_threads: 1,
+            compiled_code_budget: None,
         }
     }
 }
@@ -349,8 +469,67 @@ impl Config {
         self
     }
 
+    /// Enable or disable the [`multi-memory`] Wasm proposal for the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// Memory instructions that access a non-default linear memory are slightly
+    /// slower to execute than those accessing the default linear memory.
+    /// A `memory.copy` between two distinct linear memories is not yet supported
+    /// and fails to compile.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`multi-memory`]: https://github.com/WebAssembly/multi-memory
+    pub fn wasm_multi_memory(&mut self, enable: bool) -> &mut Self {
+        self.multi_memory = enable;
+        self
+    }
+
+    /// Enable or disable the [`simd`] Wasm proposal for the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// Only the `v128.load`, `v128.store`, `i32x4.splat`, `f32x4.splat` as well as the
+    /// `i32x4` and `f32x4` `min` and `max` operators are supported and only by the
+    /// [`EngineBackend::RegisterMachine`].
+    /// Functions using other `simd` operators as well as modules using `v128` values
+    /// in function signatures, block types, local or global variables fail to compile.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`simd`]: https://github.com/WebAssembly/simd
+    pub fn wasm_simd(&mut self, enable: bool) -> &mut Self {
+        self.simd = enable;
+        self
+    }
+
+    /// Enable or disable the [`threads`] Wasm proposal for the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// Only shared memories and the `memory.atomic.notify`, `memory.atomic.wait32`
+    /// and `memory.atomic.wait64` operators are supported. Since `wasmi` executes
+    /// single threaded `memory.atomic.notify` never finds waiters and `memory.atomic.wait`
+    /// times out immediately if the loaded value matches the expected one.
+    /// Functions using other atomic operators fail to compile.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`threads`]: https://github.com/WebAssembly/threads
+    pub fn wasm_threads(&mut self, enable: bool) -> &mut Self {
+        self.threads = enable;
+        self
+    }
+
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
+    /// # Note
+    ///
+    /// If disabled, Wasm modules fail to validate upon compilation if they use any
+    /// `f32` or `f64` instruction, including loads and stores, or declare any
+    /// `f32` or `f64` typed parameter, result, local or global variable.
+    ///
     /// Enabled by default.
     pub fn floats(&mut self, enable: bool) -> &mut Self {
         self.floats = enable;
@@ -383,6 +562,7 @@ impl Config {
     ///
     /// Disabled by default.
     ///
+    /// [`Engine`]: crate::Engine
     /// [`Store`]: crate::Store
     /// [`Engine`]: crate::Engine
     pub fn consume_fuel(&mut self, enable: bool) -> &mut Self {
@@ -424,6 +604,590 @@ impl Config {
             .then_some(self.fuel_consumption_mode)
     }
 
+    /// Sets the maximum number of functions a Wasm module may define.
+    ///
+    /// # Note
+    ///
+    /// Compiling a Wasm module that defines more functions than `limit` fails.
+    /// Imported functions do not count towards this limit.
+    ///
+    /// No limit by default.
+    pub fn set_max_functions(&mut self, limit: u32) -> &mut Self {
+        self.max_functions = Some(limit);
+        self
+    }
+
+    /// Returns the maximum number of functions a Wasm module may define if any.
+    pub(crate) fn get_max_functions(&self) -> Option<u32> {
+        self.max_functions
+    }
+
+    /// Sets the maximum number of parameters a Wasm function type may declare.
+    ///
+    /// # Note
+    ///
+    /// Compiling a Wasm module that declares a function type with more parameters
+    /// than `limit` fails. This bounds the value stack usage of a single call.
+    ///
+    /// No limit by default.
+    pub fn set_max_function_params(&mut self, limit: u32) -> &mut Self {
+        self.max_function_params = Some(limit);
+        self
+    }
+
+    /// Returns the maximum number of parameters a Wasm function type may declare if any.
+    pub(crate) fn get_max_function_params(&self) -> Option<u32> {
+        self.max_function_params
+    }
+
+    /// Sets the maximum number of results a Wasm function type may declare.
+    ///
+    /// # Note
+    ///
+    /// Compiling a Wasm module that declares a function type with more results
+    /// than `limit` fails. This bounds the value stack usage of a single call.
+    ///
+    /// No limit by default.
+    pub fn set_max_function_results(&mut self, limit: u32) -> &mut Self {
+        self.max_function_results = Some(limit);
+        self
+    }
+
+    /// Returns the maximum number of results a Wasm function type may declare if any.
+    pub(crate) fn get_max_function_results(&self) -> Option<u32> {
+        self.max_function_results
+    }
+
+    /// Sets the maximum length of provider slices encoded during function translation.
+    ///
+    /// # Note
+    ///
+    /// Provider slices are the lists of registers that are encoded for
+    /// instructions with many inputs such as calls, multi-value returns and copies.
+    /// Compiling a Wasm function that requires a longer provider slice than `limit` fails.
+    ///
+    /// This only affects the register-machine backend.
+    /// Inputs that form a contiguous register span are encoded in constant space
+    /// and therefore do not count towards this limit.
+    ///
+    /// No limit by default.
+    pub fn set_max_provider_slice_len(&mut self, limit: u32) -> &mut Self {
+        self.max_provider_slice_len = Some(limit);
+        self
+    }
+
+    /// Returns the maximum length of encoded provider slices if any.
+    pub(crate) fn get_max_provider_slice_len(&self) -> Option<u32> {
+        self.max_provider_slice_len
+    }
+
+    /// Sets the maximum number of labels a Wasm function may require.
+    ///
+    /// # Note
+    ///
+    /// Labels are the branch targets created during function translation, e.g. for
+    /// every Wasm `block`, `loop` and `if` as well as for the function body itself.
+    /// Compiling a Wasm function that requires more labels than `limit` fails.
+    /// This bounds the resources required to translate a single function.
+    ///
+    /// No limit by default.
+    pub fn set_max_function_labels(&mut self, limit: u32) -> &mut Self {
+        self.max_function_labels = Some(limit);
+        self
+    }
+
+    /// Returns the maximum number of labels a Wasm function may require if any.
+    pub(crate) fn get_max_function_labels(&self) -> Option<u32> {
+        self.max_function_labels
+    }
+
+    /// Sets the maximum number of branches a Wasm function may contain.
+    ///
+    /// # Note
+    ///
+    /// Every branch to a label counts towards this limit, e.g. every target of a
+    /// Wasm `br_table` or the branches encoded for `br`, `br_if` and `if`.
+    /// Compiling a Wasm function that contains more branches than `limit` fails.
+    /// This bounds the resources required to translate a single function.
+    ///
+    /// No limit by default.
+    pub fn set_max_function_branches(&mut self, limit: u32) -> &mut Self {
+        self.max_function_branches = Some(limit);
+        self
+    }
+
+    /// Returns the maximum number of branches a Wasm function may contain if any.
+    pub(crate) fn get_max_function_branches(&self) -> Option<u32> {
+        self.max_function_branches
+    }
+
+    /// Enables or disables fusing of multiple copies into a single instruction.
+    ///
+    /// # Note
+    ///
+    /// When disabled the register-machine translator encodes multiple copies,
+    /// e.g. of branch parameters or multi-value results, as one `copy`
+    /// instruction per value wherever possible. Execution results are identical
+    /// and only the shape of the bytecode differs. This is useful to debug
+    /// issues caused by the copy optimizations and has no effect on the
+    /// stack-machine backend.
+    ///
+    /// Enabled by default.
+    pub fn set_fused_copies(&mut self, enable: bool) -> &mut Self {
+        self.fused_copies = enable;
+        self
+    }
+
+    /// Returns `true` if multiple copies may be fused into a single instruction.
+    pub(crate) fn get_fused_copies(&self) -> bool {
+        self.fused_copies
+    }
+
+    /// Enables or disables hoisting of linear memory bounds checks out of counted loops.
+    ///
+    /// # Note
+    ///
+    /// When enabled the register-machine translator recognizes loops of the form
+    ///
+    /// ```wat
+    /// (loop
+    ///     (br_if $exit (i32.ge_u (local.get $i) (i32.const N)))
+    ///     ;; straight-line code without stores, calls or other side effects
+    ///     (local.set $i (i32.add (local.get $i) (i32.const S)))
+    ///     (br 0)
+    /// )
+    /// ```
+    ///
+    /// and checks the bounds of all loads from `$i` of the last loop iteration once in front
+    /// of the loop instead of once per loop iteration. The loads in the loop body are then
+    /// executed without bounds checks. If the pre-loop check fails the loop traps before
+    /// its first iteration with the same [`TrapCode::MemoryOutOfBounds`] that the original
+    /// loop would have trapped with in one of its iterations.
+    ///
+    /// Has no effect if fuel metering or [`Config::set_trap_locations`] are enabled since
+    /// both could observe that the trap is raised earlier. Also has no effect on the
+    /// stack-machine backend.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`TrapCode::MemoryOutOfBounds`]: crate::core::TrapCode::MemoryOutOfBounds
+    pub fn set_hoist_loop_bounds_checks(&mut self, enable: bool) -> &mut Self {
+        self.hoist_loop_bounds_checks = enable;
+        self
+    }
+
+    /// Returns `true` if the register-machine translator hoists bounds checks out of counted loops.
+    ///
+    /// # Note
+    ///
+    /// This already accounts for fuel metering and trap locations which disable the hoisting.
+    pub(crate) fn get_hoist_loop_bounds_checks(&self) -> bool {
+        self.hoist_loop_bounds_checks && !self.consume_fuel && !self.trap_locations
+    }
+
+    /// Enables or disables detection of out of bounds linear memory accesses via guard pages.
+    ///
+    /// # Note
+    ///
+    /// When enabled all linear memories reserve the whole address range that can be reached
+    /// by Wasm loads and stores upfront and only make their current size accessible. The
+    /// rest of the reservation consists of guard pages. Loads and stores with dynamic
+    /// addresses are then executed without comparing their effective address against the
+    /// size of the linear memory. Instead a signal handler turns accesses of guard pages into
+    /// [`TrapCode::MemoryOutOfBounds`] traps that are returned like any other trap so that
+    /// fuel metering and [`Config::set_trap_locations`] are unaffected.
+    ///
+    /// This is only supported on Linux on `x86_64` with the `std` crate feature and by the
+    /// register-machine backend. Everywhere else all linear memory accesses fall back to
+    /// explicit bounds checks. Use [`Config::uses_signal_bounds_checks`] to query whether
+    /// guard pages are actually used.
+    ///
+    /// Accesses with constant addresses, fused instructions and `v128` accesses are always
+    /// bounds checked explicitly.
+    ///
+    /// # Process-global side effects
+    ///
+    /// The signal handler is installed for `SIGSEGV` of the whole process upon creation of
+    /// the first guarded linear memory by any [`Engine`] and is never uninstalled again.
+    /// It forwards all signals that were not caused by guarded accesses to the handler that
+    /// was installed before it, including handlers installed with `SA_SIGINFO`. A previously
+    /// ignored or defaulted `SIGSEGV` terminates the process.
+    ///
+    /// Embedders that install their own `SIGSEGV` handler afterwards must forward signals
+    /// they do not handle themselves to the previous handler, otherwise out of bounds accesses
+    /// of guarded linear memories are no longer turned into traps. Since this is an [`Engine`]
+    /// wide setting, use separate [`Engine`]s if only some [`Store`]s may use guard pages.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Store`]: crate::Store
+    ///
+    /// [`TrapCode::MemoryOutOfBounds`]: crate::core::TrapCode::MemoryOutOfBounds
+    pub fn set_signal_bounds_checks(&mut self, enable: bool) -> &mut Self {
+        self.signal_bounds_checks = enable;
+        self
+    }
+
+    /// Returns `true` if out of bounds linear memory accesses are detected via guard pages.
+    ///
+    /// # Note
+    ///
+    /// This is `false` unless [`Config::set_signal_bounds_checks`] is enabled, guard pages
+    /// are supported by the current platform and the register-machine backend is in use.
+    pub fn uses_signal_bounds_checks(&self) -> bool {
+        self.signal_bounds_checks
+            && guarded::SUPPORTED
+            && matches!(self.backend, EngineBackend::RegisterMachine)
+    }
+
+    /// Enables or disables reporting of recoverable internal failures as errors.
+    ///
+    /// # Note
+    ///
+    /// When enabled the [`Engine`](crate::Engine) checks some of its internal
+    /// invariants, such as the validity of the compiled function body of a called
+    /// Wasm function, and returns an [`InternalError`] instead of panicking when
+    /// they are violated. This is useful for embedders that must never abort and
+    /// comes at a small performance cost per function call from the host.
+    ///
+    /// Invariants that guard memory safety are unaffected by this setting.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`InternalError`]: crate::errors::InternalError
+    pub fn set_recoverable_internal_errors(&mut self, enable: bool) -> &mut Self {
+        self.recoverable_internal_errors = enable;
+        self
+    }
+
+    /// Returns `true` if recoverable internal failures are reported as errors.
+    pub(crate) fn get_recoverable_internal_errors(&self) -> bool {
+        self.recoverable_internal_errors
+    }
+
+    /// Enables or disables additional runtime invariant checks of the executor.
+    ///
+    /// # Note
+    ///
+    /// When enabled the register-machine executor validates every register access
+    /// against the bounds of the current call frame as well as the consistency of
+    /// the call frame itself and panics with a descriptive message upon violation.
+    /// This is meant to catch miscompilations during development of `wasmi`.
+    ///
+    /// The checks only exist with the `checked-execution` crate feature and are
+    /// fully elided otherwise, in which case this setting has no effect.
+    ///
+    /// Disabled by default.
+    pub fn set_checked_execution(&mut self, enable: bool) -> &mut Self {
+        self.checked_execution = enable;
+        self
+    }
+
+    /// Returns `true` if the executor checks additional runtime invariants.
+    #[cfg(feature = "checked-execution")]
+    pub(crate) fn get_checked_execution(&self) -> bool {
+        self.checked_execution
+    }
+
+    /// Enables or disables verification of wrapping integer arithmetic during execution.
+    ///
+    /// # Note
+    ///
+    /// When enabled the register-machine executor recomputes the results of all executed
+    /// integer `add`, `sub` and `mul` instructions using checked `i128` arithmetic, reduces
+    /// them modulo `2^N` as defined by Wasm and panics with a descriptive message if the
+    /// result written by the instruction disagrees.
+    /// This is meant to catch miscompilations during development of `wasmi`.
+    ///
+    /// The checks only exist with the `checked-arithmetic` crate feature and are
+    /// fully elided otherwise, in which case this setting has no effect.
+    ///
+    /// Disabled by default.
+    pub fn set_checked_arithmetic(&mut self, enable: bool) -> &mut Self {
+        self.checked_arithmetic = enable;
+        self
+    }
+
+    /// Returns `true` if the executor verifies its wrapping integer arithmetic.
+    #[cfg(feature = "checked-arithmetic")]
+    pub(crate) fn get_checked_arithmetic(&self) -> bool {
+        self.checked_arithmetic
+    }
+
+    /// Makes the executor saturate instead of wrap the results of its checked integer arithmetic.
+    ///
+    /// # Note
+    ///
+    /// This is a test-only API to check that miscompiled arithmetic is caught
+    /// by [`Config::set_checked_arithmetic`].
+    #[cfg(all(test, feature = "checked-arithmetic"))]
+    pub(crate) fn set_corrupt_arithmetic(&mut self, enable: bool) -> &mut Self {
+        self.corrupt_arithmetic = enable;
+        self
+    }
+
+    /// Returns `true` if the executor corrupts its checked integer arithmetic.
+    #[cfg(all(test, feature = "checked-arithmetic"))]
+    pub(crate) fn get_corrupt_arithmetic(&self) -> bool {
+        self.corrupt_arithmetic
+    }
+
+    /// Enables or disables approximate execution of `f32.sqrt` and `f64.sqrt`.
+    ///
+    /// # Note
+    ///
+    /// When enabled both executors compute square roots from the reciprocal square root
+    /// estimate of the host CPU where available which trades precision for speed.
+    /// Results are no longer correctly rounded and thus violate the Wasm specification.
+    /// Zeros, subnormals, infinities, NaNs and negative inputs are still handled exactly.
+    /// Square roots of constant operands may still be computed exactly during translation.
+    ///
+    /// Read [`UntypedValue::f32_sqrt_approx`] for details about the achieved precision.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`UntypedValue::f32_sqrt_approx`]: crate::core::UntypedValue::f32_sqrt_approx
+    pub fn set_approx_float_sqrt(&mut self, enable: bool) -> &mut Self {
+        self.approx_float_sqrt = enable;
+        self
+    }
+
+    /// Returns `true` if `f32.sqrt` and `f64.sqrt` are executed using an approximation.
+    pub(crate) fn get_approx_float_sqrt(&self) -> bool {
+        self.approx_float_sqrt
+    }
+
+    /// Enables or disables strict stack height checks during function translation.
+    ///
+    /// # Note
+    ///
+    /// When enabled the function translators verify after every translated operator
+    /// that the height of their emulated value stack matches the height of the operand
+    /// stack expected by Wasm validation and return an error upon mismatch.
+    /// This is meant to catch translation bugs early during development of `wasmi`.
+    ///
+    /// Disabled by default.
+    pub fn set_strict_stack_height_checks(&mut self, enable: bool) -> &mut Self {
+        self.strict_stack_height_checks = enable;
+        self
+    }
+
+    /// Returns `true` if translators check their emulated value stack height.
+    pub(crate) fn get_strict_stack_height_checks(&self) -> bool {
+        self.strict_stack_height_checks
+    }
+
+    /// Makes translators corrupt their emulated value stack after `amount` translated operators.
+    ///
+    /// # Note
+    ///
+    /// This is a test-only API to check that translation bugs are caught
+    /// by [`Config::set_strict_stack_height_checks`].
+    #[cfg(test)]
+    pub(crate) fn set_corrupt_stack_height_after(&mut self, amount: usize) -> &mut Self {
+        self.corrupt_stack_height_after = Some(amount);
+        self
+    }
+
+    /// Returns the number of translated operators after which translators corrupt their value stack if any.
+    #[cfg(test)]
+    pub(crate) fn get_corrupt_stack_height_after(&self) -> Option<usize> {
+        self.corrupt_stack_height_after
+    }
+
+    /// Sets the [`ConstDedup`] data structure used to deduplicate constant values.
+    ///
+    /// # Note
+    ///
+    /// This affects the engine-wide constant pool as well as the function local
+    /// constant values of the register-machine translator.
+    ///
+    /// The default is [`ConstDedup::BTree`]. Without the `std` crate feature
+    /// [`ConstDedup::Hash`] falls back to [`ConstDedup::BTree`].
+    pub fn set_const_dedup(&mut self, dedup: ConstDedup) -> &mut Self {
+        self.const_dedup = dedup;
+        self
+    }
+
+    /// Returns the [`ConstDedup`] data structure used to deduplicate constant values.
+    pub(crate) fn get_const_dedup(&self) -> ConstDedup {
+        self.const_dedup.effective()
+    }
+
+    /// Enables or disables descriptive messages for traps folded at compilation time.
+    ///
+    /// # Note
+    ///
+    /// The register-machine translator evaluates fallible operations with constant
+    /// operands at compilation time and replaces them with a trap instruction if they
+    /// always trap, e.g. `i32.div_s` with a zero divisor. When enabled the translator
+    /// records the original operation and its operands for every such folded trap and
+    /// attaches them as message to the resulting [`Trap`] upon execution.
+    ///
+    /// This has no effect on the stack-machine backend which does not fold traps.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Trap`]: crate::core::Trap
+    pub fn set_folded_trap_messages(&mut self, enable: bool) -> &mut Self {
+        self.folded_trap_messages = enable;
+        self
+    }
+
+    /// Returns `true` if folded traps carry a descriptive message.
+    pub(crate) fn get_folded_trap_messages(&self) -> bool {
+        self.folded_trap_messages
+    }
+
+    /// Enables or disables locating traps of Wasm functions within their Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// When enabled the translators record the Wasm binary offset of every translated
+    /// Wasm operator and function names are taken from the `name` custom section.
+    /// Traps raised by the execution of a Wasm function then carry a [`TrapLocation`]
+    /// which is displayed as `function_name@wasm_offset` in front of the trap reason.
+    /// Functions without name are displayed as `<wasm function N>` instead. As recommended
+    /// by the Wasm specification the `name` custom section is expected to follow the
+    /// code section.
+    ///
+    /// Function bodies restored via [`Engine::deserialize_code`] are not located.
+    ///
+    /// Disabled by default since the recorded offsets increase the memory usage of
+    /// compiled functions.
+    ///
+    /// [`TrapLocation`]: crate::core::TrapLocation
+    /// [`Engine::deserialize_code`]: crate::Engine::deserialize_code
+    pub fn set_trap_locations(&mut self, enable: bool) -> &mut Self {
+        self.trap_locations = enable;
+        self
+    }
+
+    /// Returns `true` if traps of Wasm functions carry their function name and Wasm binary offset.
+    pub(crate) fn get_trap_locations(&self) -> bool {
+        self.trap_locations
+    }
+
+    /// Enables or disables counting of executed instructions per opcode.
+    ///
+    /// # Note
+    ///
+    /// When enabled the register-machine executor counts how often it executes
+    /// each kind of instruction. The counters are accumulated over all executions
+    /// and can be retrieved via [`Engine::take_instruction_profile`].
+    ///
+    /// Whether to count is decided once per Wasm execution so that disabled
+    /// profiling does not slow down the execution of individual instructions.
+    /// Stepped executions and the stack-machine backend are not profiled.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine::take_instruction_profile`]: crate::Engine::take_instruction_profile
+    pub fn profile_instructions(&mut self, enable: bool) -> &mut Self {
+        self.profile_instructions = enable;
+        self
+    }
+
+    /// Returns `true` if the executor counts executed instructions per opcode.
+    pub(crate) fn get_profile_instructions(&self) -> bool {
+        self.profile_instructions
+    }
+
+    /// Pins the bytecode version of exported and serialized code to `version`.
+    ///
+    /// # Note
+    ///
+    /// - Function bodies and code that were compiled for another bytecode version
+    ///   are refused by [`Engine::load_function_body`] and [`Engine::deserialize_code`]
+    ///   with [`FuncBodyError::BytecodeVersionMismatch`].
+    /// - Pinning a version other than [`BYTECODE_VERSION`] makes exporting and serializing
+    ///   code fail as well. This allows embedders that persist compiled code to detect
+    ///   an incompatible `wasmi` upgrade early.
+    ///
+    /// Pinned to [`BYTECODE_VERSION`] by default.
+    ///
+    /// [`Engine::load_function_body`]: crate::Engine::load_function_body
+    /// [`Engine::deserialize_code`]: crate::Engine::deserialize_code
+    /// [`FuncBodyError::BytecodeVersionMismatch`]: crate::errors::FuncBodyError::BytecodeVersionMismatch
+    pub fn pin_bytecode_version(&mut self, version: u32) -> &mut Self {
+        self.bytecode_version = version;
+        self
+    }
+
+    /// Returns the bytecode version that exported and serialized code must be compatible with.
+    pub(crate) fn get_bytecode_version(&self) -> u32 {
+        self.bytecode_version
+    }
+
+    /// Sets the number of threads that translate the function bodies of a Wasm module.
+    ///
+    /// # Note
+    ///
+    /// - With more than one thread [`Module::new`] first validates and collects all
+    ///   function bodies of the code section and then translates them in parallel.
+    /// - The translated function bodies are committed to the [`Engine`] in the order of
+    ///   their appearance so that the resulting bytecode is identical to the one of a
+    ///   serial translation.
+    /// - This has no effect without the `std` crate feature.
+    ///
+    /// Defaults to 1 which translates function bodies serially while parsing.
+    ///
+    /// [`Module::new`]: crate::Module::new
+    /// [`Engine`]: crate::Engine
+    pub fn set_compilation_threads(&mut self, threads: usize) -> &mut Self {
+        self.compilation_threads = threads;
+        self
+    }
+
+    /// Returns the number of threads that translate the function bodies of a Wasm module.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn get_compilation_threads(&self) -> usize {
+        self.compilation_threads
+    }
+
+    /// Sets the bytes of compiled code that the [`Engine`] keeps for all Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// - Whenever the estimated bytes of the compiled functions of all Wasm modules exceed
+    ///   `budget` the compiled functions of the least recently used Wasm modules are
+    ///   deallocated until the budget is met again.
+    /// - The compiled functions of an evicted Wasm module are recompiled from the Wasm
+    ///   module bytes before they are executed again. Therefore the [`Engine`] retains
+    ///   the bytes of all Wasm modules in addition to their compiled code.
+    /// - A Wasm module is used whenever a function is called using a [`Store`] that
+    ///   instantiated the Wasm module. The compiled functions of all Wasm modules
+    ///   instantiated in that [`Store`] are kept for the call which might exceed `budget`.
+    /// - Compiled functions are never evicted while a [`SteppedCall`] is alive.
+    /// - A host function that calls a function of an evicted Wasm module which was
+    ///   instantiated during the ongoing Wasm execution traps with an [`InternalError`]
+    ///   since the [`Engine`] cannot recompile during its own executions.
+    /// - This has no effect on the [`EngineBackend::StackMachine`].
+    ///
+    /// No budget by default, in which case the [`Engine`] never deallocates compiled code.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Store`]: crate::Store
+    /// [`SteppedCall`]: crate::SteppedCall
+    /// [`InternalError`]: crate::errors::InternalError
+    pub fn set_compiled_code_budget(&mut self, budget: usize) -> &mut Self {
+        self.compiled_code_budget = Some(budget);
+        self
+    }
+
+    /// Returns the bytes of compiled code that the [`Engine`] keeps for all Wasm modules if any.
+    ///
+    /// This is `None` unless the [`EngineBackend::RegisterMachine`] is in use.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub(crate) fn get_compiled_code_budget(&self) -> Option<usize> {
+        match self.backend {
+            EngineBackend::StackMachine => None,
+            EngineBackend::RegisterMachine => self.compiled_code_budget,
+        }
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
         WasmFeatures {
@@ -437,13 +1201,67 @@ impl Config {
             extended_const: self.extended_const,
             floats: self.floats,
             component_model: false,
-            simd: false,
+            simd: self.simd,
             relaxed_simd: false,
-            threads: false,
-            multi_memory: false,
+            threads: self.threads,
+            multi_memory: self.multi_memory,
             exceptions: false,
             memory64: false,
             memory_control: false,
         }
     }
+
+    /// Feeds all settings of the [`Config`] that influence the generated bytecode into `state`.
+    ///
+    /// # Note
+    ///
+    /// This is used to fingerprint compiled code so that it is only reused by an
+    /// [`Engine`](crate::Engine) that would have generated the same bytecode.
+    /// The settings are encoded explicitly and independent of the host's endianness.
+    pub(crate) fn hash_bytecode_settings<H: Hasher>(&self, state: &mut H) {
+        let flags = [
+            self.mutable_global,
+            self.sign_extension,
+            self.saturating_float_to_int,
+            self.multi_value,
+            self.bulk_memory,
+            self.reference_types,
+            self.tail_call,
+            self.extended_const,
+            self.multi_memory,
+            self.simd,
+            self.threads,
+            self.floats,
+            self.consume_fuel,
+            self.fused_copies,
+            self.folded_trap_messages,
+            self.get_hoist_loop_bounds_checks(),
+            self.uses_signal_bounds_checks(),
+        ];
+        for flag in flags {
+            state.write_u8(u8::from(flag));
+        }
+        state.write_u8(match self.backend {
+            EngineBackend::StackMachine => 0,
+            EngineBackend::RegisterMachine => 1,
+        });
+        state.write_u8(match self.fuel_consumption_mode {
+            FuelConsumptionMode::Lazy => 0,
+            FuelConsumptionMode::Eager => 1,
+        });
+        let costs = &self.fuel_costs;
+        let costs = [
+            costs.base,
+            costs.entity,
+            costs.load,
+            costs.store,
+            costs.call,
+            costs.branch_kept_per_fuel,
+            costs.func_locals_per_fuel,
+        ];
+        for cost in costs {
+            state.write(&cost.to_le_bytes());
+        }
+        state.write(&self.bytecode_version.to_le_bytes());
+    }
 }
@@ -1,10 +1,150 @@
-use super::{func_builder::TranslationErrorInner, TranslationError};
+use super::{
+    func_builder::TranslationErrorInner,
+    memory_usage::vec_bytes,
+    ConstDedup,
+    TranslationError,
+};
 use alloc::{
     collections::{btree_map, BTreeMap},
     vec::Vec,
 };
+#[cfg(feature = "std")]
+use core::hash::{BuildHasherDefault, Hasher};
+use core::mem::size_of;
+#[cfg(feature = "std")]
+use std::collections::{hash_map, HashMap};
 use wasmi_core::UntypedValue;
 
+/// A map deduplicating constant [`UntypedValue`] values to their identifiers.
+///
+/// The used data structure is chosen via [`ConstDedup`].
+#[derive(Debug)]
+pub enum ConstDedupMap<T> {
+    /// Deduplication using an ordered B-tree map.
+    BTree(BTreeMap<UntypedValue, T>),
+    /// Deduplication using a hash map over the bits of the constant values.
+    #[cfg(feature = "std")]
+    Hash(HashMap<u64, T, BuildHasherDefault<ConstHasher>>),
+}
+
+impl<T> Default for ConstDedupMap<T> {
+    fn default() -> Self {
+        Self::new(ConstDedup::default())
+    }
+}
+
+impl<T> ConstDedupMap<T> {
+    /// Creates a new empty [`ConstDedupMap`] using the `dedup` data structure.
+    pub fn new(dedup: ConstDedup) -> Self {
+        match dedup {
+            ConstDedup::BTree => Self::BTree(BTreeMap::new()),
+            #[cfg(feature = "std")]
+            ConstDedup::Hash => Self::Hash(HashMap::default()),
+            #[cfg(not(feature = "std"))]
+            ConstDedup::Hash => Self::BTree(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the [`ConstDedup`] data structure of the [`ConstDedupMap`].
+    pub fn dedup(&self) -> ConstDedup {
+        match self {
+            Self::BTree(_) => ConstDedup::BTree,
+            #[cfg(feature = "std")]
+            Self::Hash(_) => ConstDedup::Hash,
+        }
+    }
+
+    /// Returns the estimated bytes held by the [`ConstDedupMap`].
+    ///
+    /// # Note
+    ///
+    /// The B-tree map allocates its nodes on demand and therefore is estimated
+    /// by its number of entries whereas the hash map is estimated by its capacity.
+    pub fn memory_usage(&self) -> usize {
+        match self {
+            Self::BTree(map) => map.len() * (size_of::<UntypedValue>() + size_of::<T>()),
+            #[cfg(feature = "std")]
+            Self::Hash(map) => map.capacity() * (size_of::<u64>() + size_of::<T>()),
+        }
+    }
+
+    /// Removes all deduplicated constant values from the [`ConstDedupMap`].
+    pub fn clear(&mut self) {
+        match self {
+            Self::BTree(map) => map.clear(),
+            #[cfg(feature = "std")]
+            Self::Hash(map) => map.clear(),
+        }
+    }
+}
+
+impl<T: Copy> ConstDedupMap<T> {
+    /// Returns the identifier of `value` or inserts the one created by `make_id`.
+    ///
+    /// # Errors
+    ///
+    /// If `make_id` is called and returns an error.
+    pub fn get_or_try_insert_with<E>(
+        &mut self,
+        value: UntypedValue,
+        make_id: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        match self {
+            Self::BTree(map) => match map.entry(value) {
+                btree_map::Entry::Occupied(entry) => Ok(*entry.get()),
+                btree_map::Entry::Vacant(entry) => Ok(*entry.insert(make_id()?)),
+            },
+            #[cfg(feature = "std")]
+            Self::Hash(map) => match map.entry(value.to_bits()) {
+                hash_map::Entry::Occupied(entry) => Ok(*entry.get()),
+                hash_map::Entry::Vacant(entry) => Ok(*entry.insert(make_id()?)),
+            },
+        }
+    }
+}
+
+/// A fast non-cryptographic [`Hasher`] for the bits of constant values.
+///
+/// # Note
+///
+/// This uses the multiply-rotate scheme of the `FxHasher` used in `rustc`
+/// which is sufficient since constant values are not chosen adversarially
+/// in a way that could harm anything but compilation performance.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ConstHasher {
+    /// The current hash state.
+    hash: u64,
+}
+
+#[cfg(feature = "std")]
+impl ConstHasher {
+    /// The multiplicative seed of the [`ConstHasher`].
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    /// Mixes the `word` into the hash state.
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+#[cfg(feature = "std")]
+impl Hasher for ConstHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.add_to_hash(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.add_to_hash(word);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ConstRef(u32);
 
@@ -41,12 +181,20 @@ impl ConstRef {
 #[derive(Debug, Default)]
 pub struct ConstPool {
     /// Mapping from constant [`UntypedValue`] values to [`ConstRef`] indices.
-    const2idx: BTreeMap<UntypedValue, ConstRef>,
+    const2idx: ConstDedupMap<ConstRef>,
     /// Mapping from [`ConstRef`] indices to constant [`UntypedValue`] values.
     idx2const: Vec<UntypedValue>,
 }
 
 impl ConstPool {
+    /// Creates a new empty [`ConstPool`] using the `dedup` data structure.
+    pub fn new(dedup: ConstDedup) -> Self {
+        Self {
+            const2idx: ConstDedupMap::new(dedup),
+            idx2const: Vec::new(),
+        }
+    }
+
     /// Allocates a new constant `value` on the [`ConstPool`] and returns its identifier.
     ///
     /// # Note
@@ -58,30 +206,39 @@ impl ConstPool {
     ///
     /// If too many constant values have been allocated for this [`ConstPool`].
     pub fn alloc(&mut self, value: UntypedValue) -> Result<ConstRef, TranslationError> {
-        match self.const2idx.entry(value) {
-            btree_map::Entry::Occupied(entry) => Ok(*entry.get()),
-            btree_map::Entry::Vacant(entry) => {
-                let idx = self.idx2const.len();
-                let cref = ConstRef::try_from(idx)?;
-                entry.insert(cref);
-                self.idx2const.push(value);
-                Ok(cref)
-            }
-        }
+        let idx2const = &mut self.idx2const;
+        self.const2idx.get_or_try_insert_with(value, || {
+            let cref = ConstRef::try_from(idx2const.len())?;
+            idx2const.push(value);
+            Ok(cref)
+        })
     }
 
     /// Returns the [`UntypedValue`] for the given [`ConstRef`] if existing.
     ///
     /// Returns `None` is the [`ConstPool`] does not store a value for the [`ConstRef`].
-    ///
-    /// # Note
-    ///
-    /// This API is mainly used and useful in testing code.
-    #[allow(dead_code)]
     pub fn get(&self, cref: ConstRef) -> Option<UntypedValue> {
         self.idx2const.get(cref.to_usize()).copied()
     }
 
+    /// Returns an iterator over the constant values of the [`ConstPool`] in allocation order.
+    pub fn values(&self) -> impl ExactSizeIterator<Item = UntypedValue> + '_ {
+        self.idx2const.iter().copied()
+    }
+
+    /// Removes all constant values from the [`ConstPool`] to allow for reuse.
+    pub fn clear(&mut self) {
+        self.const2idx.clear();
+        self.idx2const.clear();
+    }
+
+    /// Returns the estimated bytes held by the [`ConstPool`].
+    ///
+    /// This accounts for both the deduplication map and the constant values.
+    pub fn memory_usage(&self) -> usize {
+        self.const2idx.memory_usage() + vec_bytes(&self.idx2const)
+    }
+
     /// Returns the read-only [`ConstPoolView`] of this [`ConstPool`].
     pub fn view(&self) -> ConstPoolView {
         ConstPoolView {
@@ -108,3 +265,29 @@ impl ConstPoolView<'_> {
         self.idx2const.get(cref.to_usize()).copied()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Allocates `values` on a [`ConstPool`] using `dedup` and returns their [`ConstRef`].
+    fn alloc_all(dedup: ConstDedup, values: &[u64]) -> Vec<ConstRef> {
+        let mut pool = ConstPool::new(dedup);
+        values
+            .iter()
+            .map(|&value| pool.alloc(UntypedValue::from(value)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn dedup_results_are_identical() {
+        let values = [5, u64::MAX, 0, 5, 1 << 40, 0, u64::MAX, 7, 1 << 40];
+        let btree = alloc_all(ConstDedup::BTree, &values);
+        assert_eq!(btree[0], btree[3]);
+        assert_eq!(btree[1], btree[6]);
+        assert_eq!(btree[2], btree[5]);
+        assert_eq!(btree[4], btree[8]);
+        assert_eq!(btree[7].to_usize(), 4);
+        assert_eq!(alloc_all(ConstDedup::Hash, &values), btree);
+    }
+}
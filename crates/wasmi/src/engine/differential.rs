@@ -0,0 +1,104 @@
+//! Differential execution of Wasm functions using different [`Config`].
+//!
+//! # Note
+//!
+//! This is a test-support API meant to guard the correctness of optimizations
+//! and is only available with the `test-utils` crate feature.
+
+use super::{Config, Engine};
+use crate::{core::TrapCode, Error, Linker, Module, Store, Value};
+use alloc::vec::Vec;
+
+/// The observable outcome of executing a Wasm function via [`Engine::compare_execution`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    /// The execution returned the results encoded as their bit patterns.
+    ///
+    /// # Note
+    ///
+    /// Bit patterns are compared so that NaN payloads are checked as well.
+    Results(Vec<u64>),
+    /// The execution trapped with the [`TrapCode`] if any.
+    Trap(Option<TrapCode>),
+}
+
+impl Engine {
+    /// Executes the exported function `name` of `wasm` with `inputs` under both
+    /// `lhs` and `rhs` [`Config`] and asserts that their outcomes are identical.
+    ///
+    /// Both the results of successful executions as well as the [`TrapCode`]
+    /// of trapping executions are compared.
+    ///
+    /// Returns the common [`ExecutionOutcome`].
+    ///
+    /// # Note
+    ///
+    /// This is a test-support API meant to guard the correctness of optimizations,
+    /// for example by comparing an optimized against an unoptimized [`Config`].
+    ///
+    /// # Panics
+    ///
+    /// - If the outcomes of both executions differ.
+    /// - If `wasm` fails to compile or instantiate without imports.
+    /// - If `wasm` does not export a function called `name`.
+    /// - If the function is called with reference typed `inputs` or returns reference typed results.
+    /// - If the function fails with an error that is not a trap.
+    pub fn compare_execution(
+        lhs: &Config,
+        rhs: &Config,
+        wasm: &[u8],
+        name: &str,
+        inputs: &[Value],
+    ) -> ExecutionOutcome {
+        let lhs_outcome = Self::new(lhs).execute_export(wasm, name, inputs);
+        let rhs_outcome = Self::new(rhs).execute_export(wasm, name, inputs);
+        assert_eq!(
+            lhs_outcome, rhs_outcome,
+            "outcomes differ for {name} with inputs {inputs:?}"
+        );
+        lhs_outcome
+    }
+
+    /// Executes the exported function `name` of `wasm` with `inputs` using the [`Engine`].
+    fn execute_export(&self, wasm: &[u8], name: &str, inputs: &[Value]) -> ExecutionOutcome {
+        let module = Module::new(self, wasm).unwrap();
+        let mut store = Store::new(self, ());
+        let instance = <Linker<()>>::new(self)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = instance
+            .get_func(&store, name)
+            .unwrap_or_else(|| panic!("missing exported function: {name}"));
+        let mut results = func
+            .ty(&store)
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        match func.call(&mut store, inputs, &mut results) {
+            Ok(()) => ExecutionOutcome::Results(results.iter().map(value_bits).collect()),
+            Err(Error::Trap(trap)) => ExecutionOutcome::Trap(trap.trap_code()),
+            Err(error) => panic!("unexpected non-trap error: {error}"),
+        }
+    }
+}
+
+/// Returns the bit pattern of the [`Value`].
+///
+/// # Panics
+///
+/// If `value` is of reference type.
+fn value_bits(value: &Value) -> u64 {
+    match value {
+        Value::I32(value) => u64::from(*value as u32),
+        Value::I64(value) => *value as u64,
+        Value::F32(value) => u64::from(value.to_bits()),
+        Value::F64(value) => value.to_bits(),
+        Value::FuncRef(_) | Value::ExternRef(_) => {
+            panic!("differential execution does not support reference types: {value:?}")
+        }
+    }
+}
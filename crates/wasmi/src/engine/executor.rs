@@ -17,6 +17,7 @@ use crate::{
             GlobalIdx,
             Instruction,
             LocalDepth,
+            MemoryIdx,
             SignatureIdx,
             TableIdx,
         },
@@ -30,6 +31,7 @@ use crate::{
     },
     error::EntityGrowError,
     func::FuncEntity,
+    memory::fill_bytes,
     store::ResourceLimiterRef,
     table::TableEntity,
     FuelConsumptionMode,
@@ -97,7 +99,8 @@ pub enum ReturnOutcome {
 ///
 /// # Errors
 ///
-/// If the Wasm execution traps.
+/// If the Wasm execution traps. The error holds the [`TrapCode`] and the
+/// [`InstructionPtr`] pointing to the trapping instruction.
 #[inline(never)]
 pub fn execute_wasm<'ctx, 'engine>(
     ctx: &'ctx mut StoreInner,
@@ -107,9 +110,11 @@ pub fn execute_wasm<'ctx, 'engine>(
     code_map: &'engine CodeMap,
     const_pool: ConstPoolView<'engine>,
     resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
-) -> Result<WasmOutcome, TrapCode> {
-    Executor::new(ctx, cache, value_stack, call_stack, code_map, const_pool)
+) -> Result<WasmOutcome, (TrapCode, InstructionPtr)> {
+    let mut executor = Executor::new(ctx, cache, value_stack, call_stack, code_map, const_pool);
+    executor
         .execute(resource_limiter)
+        .map_err(|trap_code| (trap_code, executor.ip))
 }
 
 /// The function signature of Wasm load operations.
@@ -124,6 +129,14 @@ type WasmStoreOp = fn(
     value: UntypedValue,
 ) -> Result<(), TrapCode>;
 
+/// The function signature of Wasm `memory.atomic.wait` operations.
+type WasmWaitOp = fn(
+    memory: &[u8],
+    address: UntypedValue,
+    offset: u32,
+    expected: UntypedValue,
+) -> Result<UntypedValue, TrapCode>;
+
 /// The WebAssembly specification demands to return this value
 /// if the `memory.grow` or `table.grow` operations fail.
 const INVALID_GROWTH_ERRCODE: u32 = u32::MAX;
@@ -162,6 +175,12 @@ struct Executor<'ctx, 'engine> {
     code_map: &'engine CodeMap,
     /// A read-only view to a pool of constant values.
     const_pool: ConstPoolView<'engine>,
+    /// Is `true` if `f32.sqrt` and `f64.sqrt` are executed using an approximation.
+    ///
+    /// Read [`Config::set_approx_float_sqrt`] for more information.
+    ///
+    /// [`Config::set_approx_float_sqrt`]: crate::Config::set_approx_float_sqrt
+    approx_sqrt: bool,
 }
 
 macro_rules! forward_call {
@@ -193,6 +212,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let frame = call_stack.pop().expect("must have frame on the call stack");
         let sp = value_stack.stack_ptr();
         let ip = frame.ip();
+        let approx_sqrt = ctx.engine().config().get_approx_float_sqrt();
         Self {
             sp,
             ip,
@@ -202,13 +222,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             call_stack,
             code_map,
             const_pool,
+            approx_sqrt,
         }
     }
 
     /// Executes the function frame until it returns or traps.
     #[inline(always)]
     fn execute(
-        mut self,
+        &mut self,
         resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
     ) -> Result<WasmOutcome, TrapCode> {
         use Instruction as Instr;
@@ -276,6 +297,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::I64Store8(offset) => self.visit_i64_store_8(offset)?,
                 Instr::I64Store16(offset) => self.visit_i64_store_16(offset)?,
                 Instr::I64Store32(offset) => self.visit_i64_store_32(offset)?,
+                Instr::MemoryAtomicNotify(offset) => self.visit_memory_atomic_notify(offset)?,
+                Instr::MemoryAtomicWait32(offset) => self.visit_memory_atomic_wait32(offset)?,
+                Instr::MemoryAtomicWait64(offset) => self.visit_memory_atomic_wait64(offset)?,
+                Instr::UseMemory(memory) => self.visit_use_memory(memory),
                 Instr::MemorySize => self.visit_memory_size(),
                 Instr::MemoryGrow => self.visit_memory_grow(&mut *resource_limiter)?,
                 Instr::MemoryFill => self.visit_memory_fill()?,
@@ -480,6 +505,33 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.try_next_instr()
     }
 
+    /// Executes a generic Wasm `memory.atomic.wait` operation.
+    ///
+    /// # Note
+    ///
+    /// This can be used to emulate the following Wasm operands:
+    ///
+    /// - `memory.atomic.wait32`
+    /// - `memory.atomic.wait64`
+    #[inline(always)]
+    fn execute_memory_atomic_wait(
+        &mut self,
+        offset: AddressOffset,
+        wait: WasmWaitOp,
+    ) -> Result<(), TrapCode> {
+        let memory = self.cache.default_memory(self.ctx);
+        if !self.ctx.resolve_memory(memory).ty().is_shared() {
+            return Err(TrapCode::ExpectedSharedMemory);
+        }
+        // Note: The timeout is irrelevant since no other thread could ever notify the waiter.
+        self.sp.drop();
+        self.sp.try_eval_top2(|address, expected| {
+            let memory = self.cache.default_memory_bytes(self.ctx);
+            wait(memory, address, offset.into_inner(), expected)
+        })?;
+        self.try_next_instr()
+    }
+
     /// Executes an infallible unary `wasmi` instruction.
     #[inline(always)]
     fn execute_unary(&mut self, f: fn(UntypedValue) -> UntypedValue) {
@@ -613,7 +665,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 .push(FuncFrame::new(self.ip, self.cache.instance()))?;
         }
         match self.ctx.resolve_func(func) {
-            FuncEntity::Wasm(wasm_func) => {
+            FuncEntity::Wasm(wasm_func) if self.code_map.is_initialized(wasm_func.func_body()) => {
                 let header = self.code_map.header(wasm_func.func_body());
                 self.value_stack.prepare_wasm_call(header)?;
                 self.sp = self.value_stack.stack_ptr();
@@ -621,7 +673,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 self.ip = self.code_map.instr_ptr(header.iref());
                 Ok(CallOutcome::Continue)
             }
-            FuncEntity::Host(_host_func) => {
+            _ => {
+                // Note: Wasm functions with an invalid compiled function body are handed
+                //       back the same way as host functions and reported by the engine.
                 self.cache.reset();
                 Ok(CallOutcome::Call {
                     host_func: *func,
@@ -804,14 +858,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .ok_or(TrapCode::TableOutOfBounds)?;
         let func = funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
         let actual_signature = self.ctx.resolve_func(func).ty_dedup();
-        let expected_signature = self
-            .ctx
-            .resolve_instance(self.cache.instance())
-            .get_signature(func_type.to_u32())
-            .unwrap_or_else(|| {
-                panic!("missing signature for call_indirect at index: {func_type:?}")
-            });
-        if actual_signature != expected_signature {
+        let expected_signature = self.cache.get_signature(self.ctx, func_type);
+        if *actual_signature != expected_signature {
             return Err(TrapCode::BadSignature);
         }
         self.call_func(skip, func, kind)
@@ -1070,6 +1118,33 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.next_instr()
     }
 
+    #[inline(always)]
+    fn visit_use_memory(&mut self, memory: MemoryIdx) {
+        self.cache.use_memory(self.ctx, memory);
+        self.next_instr()
+    }
+
+    #[inline(always)]
+    fn visit_memory_atomic_notify(&mut self, offset: AddressOffset) -> Result<(), TrapCode> {
+        // Note: The number of waiters to notify is irrelevant since there are none.
+        self.sp.drop();
+        self.sp.try_eval_top(|address| {
+            let memory = self.cache.default_memory_bytes(self.ctx);
+            UntypedValue::memory_atomic_notify(memory, address, offset.into_inner())
+        })?;
+        self.try_next_instr()
+    }
+
+    #[inline(always)]
+    fn visit_memory_atomic_wait32(&mut self, offset: AddressOffset) -> Result<(), TrapCode> {
+        self.execute_memory_atomic_wait(offset, UntypedValue::memory_atomic_wait32)
+    }
+
+    #[inline(always)]
+    fn visit_memory_atomic_wait64(&mut self, offset: AddressOffset) -> Result<(), TrapCode> {
+        self.execute_memory_atomic_wait(offset, UntypedValue::memory_atomic_wait64)
+    }
+
     #[inline(always)]
     fn visit_memory_size(&mut self) {
         let memory = self.cache.default_memory(self.ctx);
@@ -1130,14 +1205,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.consume_fuel_with(
             |costs| costs.fuel_for_bytes(n as u64),
             |this| {
-                let memory = this
-                    .cache
-                    .default_memory_bytes(this.ctx)
-                    .get_mut(offset..)
-                    .and_then(|memory| memory.get_mut(..n))
-                    .ok_or(TrapCode::MemoryOutOfBounds)?;
-                memory.fill(byte);
-                Ok(())
+                let memory = this.cache.default_memory_bytes(this.ctx);
+                fill_bytes(memory, offset, byte, n).map_err(|_| TrapCode::MemoryOutOfBounds)
             },
         )?;
         self.try_next_instr()
@@ -1445,7 +1514,6 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         fn visit_f32_floor(f32_floor);
         fn visit_f32_trunc(f32_trunc);
         fn visit_f32_nearest(f32_nearest);
-        fn visit_f32_sqrt(f32_sqrt);
 
         fn visit_f64_abs(f64_abs);
         fn visit_f64_neg(f64_neg);
@@ -1453,7 +1521,6 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         fn visit_f64_floor(f64_floor);
         fn visit_f64_trunc(f64_trunc);
         fn visit_f64_nearest(f64_nearest);
-        fn visit_f64_sqrt(f64_sqrt);
 
         fn visit_i32_wrap_i64(i32_wrap_i64);
         fn visit_i64_extend_i32_s(i64_extend_i32_s);
@@ -1485,6 +1552,24 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         fn visit_i64_trunc_sat_f64_s(i64_trunc_sat_f64_s);
         fn visit_i64_trunc_sat_f64_u(i64_trunc_sat_f64_u);
     }
+
+    #[inline(always)]
+    fn visit_f32_sqrt(&mut self) {
+        if self.approx_sqrt {
+            self.execute_unary(UntypedValue::f32_sqrt_approx)
+        } else {
+            self.execute_unary(UntypedValue::f32_sqrt)
+        }
+    }
+
+    #[inline(always)]
+    fn visit_f64_sqrt(&mut self) {
+        if self.approx_sqrt {
+            self.execute_unary(UntypedValue::f64_sqrt_approx)
+        } else {
+            self.execute_unary(UntypedValue::f64_sqrt)
+        }
+    }
 }
 
 macro_rules! impl_visit_fallible_unary {
@@ -0,0 +1,692 @@
+use super::{
+    regmach::{
+        bytecode::{Decode, Encode, Instruction as Instruction2},
+        code_map::CompiledFuncEntity,
+        CodeMap as CodeMap2,
+    },
+    CompiledFunc,
+    Config,
+    EngineBackend,
+};
+use crate::{
+    core::{UntypedValue, ValueType},
+    errors::ModuleError,
+    func::{FuncEntity, WasmFuncEntity},
+    AsContext,
+    AsContextMut,
+    Engine,
+    Error,
+    Func,
+    FuncType,
+    GlobalType,
+    MemoryType,
+    TableType,
+};
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{
+    fmt::{self, Display},
+    hash::Hasher,
+};
+
+/// Errors that can occur upon exporting or reloading compiled function bodies.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FuncBodyError {
+    /// The [`Engine`] does not use the register-machine backend.
+    UnsupportedBackend,
+    /// The function is a host function without compiled function body.
+    HostFunc,
+    /// The function body calls other compiled functions of its [`Engine`].
+    NotSelfContained,
+    /// The bytes do not encode compiled function bodies.
+    InvalidFormat,
+    /// The function body was exported from an [`Engine`] with a different configuration.
+    ConfigMismatch,
+    /// The function body does not match the type of the function it is loaded for.
+    TypeMismatch,
+    /// The executed instruction cannot be located within the Wasm binary.
+    ///
+    /// This is the case unless trap locations are enabled via [`Config::set_trap_locations`].
+    ///
+    /// [`Config::set_trap_locations`]: crate::Config::set_trap_locations
+    Unlocated,
+    /// The function body was compiled for an incompatible bytecode version.
+    BytecodeVersionMismatch {
+        /// The bytecode version required by the [`Engine`].
+        expected: u32,
+        /// The bytecode version of the function body.
+        found: u32,
+    },
+}
+
+impl Display for FuncBodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedBackend => {
+                write!(
+                    f,
+                    "function bodies can only be exported with the register-machine backend"
+                )
+            }
+            Self::HostFunc => write!(f, "host functions have no compiled function body"),
+            Self::NotSelfContained => {
+                write!(
+                    f,
+                    "function bodies that call internal functions are not self-contained"
+                )
+            }
+            Self::InvalidFormat => write!(f, "encountered malformed function body bytes"),
+            Self::ConfigMismatch => {
+                write!(
+                    f,
+                    "function body was exported from an engine with a different configuration"
+                )
+            }
+            Self::TypeMismatch => write!(f, "function body does not match the function type"),
+            Self::Unlocated => {
+                write!(
+                    f,
+                    "executed instruction cannot be located within the Wasm binary"
+                )
+            }
+            Self::BytecodeVersionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "function body was compiled for bytecode version {found} \
+                    but the engine requires bytecode version {expected}"
+                )
+            }
+        }
+    }
+}
+
+/// The magic bytes at the start of every exported function body.
+const MAGIC: [u8; 4] = *b"\0wfb";
+
+/// The magic bytes at the start of all code serialized via [`Engine::serialize_code`].
+const CODE_MAGIC: [u8; 4] = *b"\0wcm";
+
+/// The version of the exported function body and serialized code formats.
+const VERSION: u8 = 3;
+
+/// The version of the register-machine bytecode executed by the [`Engine`].
+///
+/// # Note
+///
+/// This is embedded into exported function bodies and serialized code and must be
+/// bumped whenever instructions are added, removed or change their encoding.
+pub const BYTECODE_VERSION: u32 = 4;
+
+/// A 64-bit FNV-1a hasher used to compute [`Config`] and Wasm module fingerprints.
+#[derive(Debug, Clone)]
+pub struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl core::hash::Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// The structure of a Wasm module that its compiled function bodies depend on.
+///
+/// This consists of the types of all tables, memories and globals of the Wasm module
+/// including the imported ones in the order of their indices.
+///
+/// # Note
+///
+/// The fingerprint of the Wasm module bytes is not collision-resistant. Therefore
+/// restored function bodies are additionally checked against the [`ModuleStructure`]
+/// so that they never access tables, memories or globals that do not exist or
+/// have other types than those they were translated for.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModuleStructure(Arc<[u8]>);
+
+impl ModuleStructure {
+    /// Creates the [`ModuleStructure`] of a Wasm module with `tables`, `memories` and `globals`.
+    pub fn new(tables: &[TableType], memories: &[MemoryType], globals: &[GlobalType]) -> Self {
+        let mut out = Vec::new();
+        encode_len(tables.len(), &mut out);
+        for table in tables {
+            out.push(encode_value_type(table.element()));
+            encode_limits(table.minimum(), table.maximum(), &mut out);
+        }
+        encode_len(memories.len(), &mut out);
+        for memory in memories {
+            out.push(u8::from(memory.is_shared()));
+            encode_limits(
+                u32::from(memory.initial_pages()),
+                memory.maximum_pages().map(u32::from),
+                &mut out,
+            );
+        }
+        encode_len(globals.len(), &mut out);
+        for global in globals {
+            out.push(encode_value_type(global.content()));
+            out.push(u8::from(global.mutability().is_mut()));
+        }
+        Self(out.into())
+    }
+}
+
+/// Encodes the number of items `len` of a [`ModuleStructure`].
+fn encode_len(len: usize, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+/// Encodes the `min` and `max` limits of a table or memory of a [`ModuleStructure`].
+fn encode_limits(min: u32, max: Option<u32>, out: &mut Vec<u8>) {
+    out.extend_from_slice(&min.to_le_bytes());
+    match max {
+        Some(max) => {
+            out.push(1);
+            out.extend_from_slice(&max.to_le_bytes());
+        }
+        None => out.push(0),
+    }
+}
+
+/// Returns the encoded [`ModuleStructure`] of `entity` for [`Engine::serialize_code`].
+///
+/// # Note
+///
+/// This is empty if the compiled function was not translated from a Wasm module
+/// which never matches the [`ModuleStructure`] of any Wasm module.
+fn module_structure_bytes(entity: &CompiledFuncEntity) -> &[u8] {
+    entity
+        .module_structure()
+        .map(|structure| &structure.0[..])
+        .unwrap_or_default()
+}
+
+/// Returns the fingerprint of the `wasmi` version and the [`Config`].
+///
+/// # Note
+///
+/// This only covers the [`Config`] settings that influence the generated bytecode.
+fn config_fingerprint(config: &Config) -> u64 {
+    let mut hasher = Fnv1a::default();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    config.hash_bytecode_settings(&mut hasher);
+    hasher.finish()
+}
+
+/// Encodes the [`ValueType`] as a single byte.
+fn encode_value_type(ty: ValueType) -> u8 {
+    match ty {
+        ValueType::I32 => 0,
+        ValueType::I64 => 1,
+        ValueType::F32 => 2,
+        ValueType::F64 => 3,
+        ValueType::FuncRef => 4,
+        ValueType::ExternRef => 5,
+    }
+}
+
+/// Encodes the parameter and result types of `func_type`.
+fn encode_func_type(func_type: &FuncType, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+    for types in [func_type.params(), func_type.results()] {
+        let len = u16::try_from(types.len()).map_err(|_| FuncBodyError::TypeMismatch)?;
+        len.encode(out)?;
+        for ty in types {
+            encode_value_type(*ty).encode(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes `len` items of type `T` from `input`.
+fn decode_many<T: Decode>(input: &mut &[u8], len: usize) -> Result<Vec<T>, FuncBodyError> {
+    // Note: the capacity is bounded by `input` to not trust `len` blindly.
+    let mut items = Vec::with_capacity(len.min(input.len()));
+    for _ in 0..len {
+        items.push(T::decode(input)?);
+    }
+    Ok(items)
+}
+
+/// Returns `Ok` if the `instrs` do not call other compiled functions.
+///
+/// # Errors
+///
+/// Otherwise returns [`FuncBodyError::NotSelfContained`].
+fn ensure_self_contained(instrs: &[Instruction2]) -> Result<(), FuncBodyError> {
+    let calls_internal = instrs.iter().any(|instr| {
+        matches!(
+            instr,
+            Instruction2::CallInternal0 { .. }
+                | Instruction2::CallInternal { .. }
+                | Instruction2::ReturnCallInternal0 { .. }
+                | Instruction2::ReturnCallInternal { .. }
+        )
+    });
+    match calls_internal {
+        true => Err(FuncBodyError::NotSelfContained),
+        false => Ok(()),
+    }
+}
+
+/// Returns `Ok` if the `found` bytecode version is compatible with `config`.
+///
+/// # Errors
+///
+/// If `found` differs from the bytecode version pinned by `config`.
+fn ensure_bytecode_version(config: &Config, found: u32) -> Result<(), FuncBodyError> {
+    let expected = config.get_bytecode_version();
+    if found != expected {
+        return Err(FuncBodyError::BytecodeVersionMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Encodes the `magic` bytes, the format and bytecode versions and the fingerprint of `config`.
+///
+/// # Errors
+///
+/// If `config` pins a bytecode version different from [`BYTECODE_VERSION`].
+fn encode_header(magic: &[u8; 4], config: &Config, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+    ensure_bytecode_version(config, BYTECODE_VERSION)?;
+    out.extend_from_slice(magic);
+    VERSION.encode(out)?;
+    BYTECODE_VERSION.encode(out)?;
+    config_fingerprint(config).encode(out)
+}
+
+/// Decodes and checks the header encoded by [`encode_header`].
+///
+/// # Errors
+///
+/// - If the `magic` bytes or the format version do not match.
+/// - If the bytecode version is incompatible with `config`.
+/// - If the header was encoded for a different `config`.
+fn decode_header(magic: &[u8; 4], config: &Config, input: &mut &[u8]) -> Result<(), FuncBodyError> {
+    if input.get(..magic.len()) != Some(&magic[..]) {
+        return Err(FuncBodyError::InvalidFormat);
+    }
+    *input = &input[magic.len()..];
+    if u8::decode(input)? != VERSION {
+        return Err(FuncBodyError::InvalidFormat);
+    }
+    ensure_bytecode_version(config, u32::decode(input)?)?;
+    if u64::decode(input)? != config_fingerprint(config) {
+        return Err(FuncBodyError::ConfigMismatch);
+    }
+    Ok(())
+}
+
+/// The decoded parts of a compiled function body.
+#[derive(Debug)]
+struct FuncBodyParts {
+    /// The number of registers used by the function body.
+    len_registers: u16,
+    /// The number of results returned by the function body.
+    len_results: u16,
+    /// The bits of the function local constant values.
+    consts: Vec<u64>,
+    /// The instructions of the function body.
+    instrs: Vec<Instruction2>,
+}
+
+impl FuncBodyParts {
+    /// Encodes the parts of the compiled function body `entity`.
+    fn encode(entity: &CompiledFuncEntity, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        entity.len_registers().encode(out)?;
+        entity.len_results().encode(out)?;
+        let consts = entity.consts();
+        (consts.len() as u32).encode(out)?;
+        for value in consts {
+            value.to_bits().encode(out)?;
+        }
+        let instrs = entity.instrs();
+        (instrs.len() as u32).encode(out)?;
+        for instr in instrs {
+            instr.encode(out)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the parts of a compiled function body from `input`.
+    ///
+    /// # Errors
+    ///
+    /// If `input` does not start with the parts of a compiled function body.
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        let len_registers = u16::decode(input)?;
+        let len_results = u16::decode(input)?;
+        let len_consts = u32::decode(input)? as usize;
+        let consts = decode_many::<u64>(input, len_consts)?;
+        let len_instrs = u32::decode(input)? as usize;
+        let instrs = decode_many::<Instruction2>(input, len_instrs)?;
+        if instrs.is_empty() || consts.len() > usize::from(len_registers) {
+            return Err(FuncBodyError::InvalidFormat);
+        }
+        Ok(Self {
+            len_registers,
+            len_results,
+            consts,
+            instrs,
+        })
+    }
+}
+
+/// A compiled function body restored via [`Engine::deserialize_code`].
+#[derive(Debug)]
+pub struct RestoredFunc {
+    /// The [`CompiledFunc`] that the function body was serialized for.
+    func: CompiledFunc,
+    /// The index of the Wasm function within its module.
+    func_index: u32,
+    /// The fingerprint of the Wasm module bytes up to and including the Wasm function body.
+    wasm_hash: u64,
+    /// The structure of the Wasm module of the Wasm function.
+    structure: ModuleStructure,
+    /// The decoded function body.
+    body: FuncBodyParts,
+}
+
+impl RestoredFunc {
+    /// Returns `true` if the [`RestoredFunc`] was serialized for `func` at `func_index`.
+    ///
+    /// The `wasm_hash` is the fingerprint of the Wasm module bytes up to and including
+    /// the function body of `func` and makes sure the [`RestoredFunc`] is only used for
+    /// the very same Wasm function it was translated from. Since fingerprints might collide
+    /// the [`ModuleStructure`] of the Wasm module of `func` must match as well.
+    pub fn is_for(
+        &self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_hash: u64,
+        structure: &ModuleStructure,
+    ) -> bool {
+        self.func == func
+            && self.func_index == func_index
+            && self.wasm_hash == wasm_hash
+            && self.structure == *structure
+    }
+
+    /// Initializes the uninitialized [`CompiledFunc`] of the [`RestoredFunc`] in `code_map`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid or already initialized for `code_map`.
+    pub fn init(self, code_map: &mut CodeMap2) {
+        let FuncBodyParts {
+            len_registers,
+            len_results,
+            consts,
+            instrs,
+        } = self.body;
+        code_map.init_func(
+            self.func,
+            self.func_index,
+            len_registers,
+            len_results,
+            consts.into_iter().map(UntypedValue::from),
+            instrs,
+        );
+    }
+}
+
+impl Engine {
+    /// Exports the compiled function body of the Wasm `func` as self-contained bytes.
+    ///
+    /// The bytes contain the `wasmi` bytecode of `func` together with its function
+    /// local constant values and a fingerprint of the [`Config`] of the [`Engine`].
+    /// They can be reloaded via [`Engine::load_function_body`] into an [`Engine`]
+    /// with the same [`Config`] which allows to cache single compiled functions.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If `func` is a host function.
+    /// - If the [`Config`] pins a bytecode version other than [`BYTECODE_VERSION`].
+    /// - If the function body of `func` calls other Wasm functions of its module
+    ///   since those calls refer to function bodies that are private to the [`Engine`].
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own `func` or does not use this [`Engine`].
+    pub fn function_body_bytes(&self, ctx: impl AsContext, func: &Func) -> Result<Vec<u8>, Error> {
+        let ctx = ctx.as_context();
+        assert!(
+            Engine::same(self, ctx.store.engine()),
+            "the store of the function must use this engine"
+        );
+        self.ensure_register_machine()?;
+        let wasm_func = match ctx.store.inner.resolve_func(func) {
+            FuncEntity::Wasm(wasm_func) => wasm_func,
+            FuncEntity::Host(_) => return Err(FuncBodyError::HostFunc.into()),
+        };
+        let mut out = Vec::new();
+        encode_header(&MAGIC, self.config(), &mut out)?;
+        ctx.store
+            .inner
+            .resolve_func_type_with(wasm_func.ty_dedup(), |func_type| {
+                encode_func_type(func_type, &mut out)
+            })?;
+        self.resolve_func_2(wasm_func.func_body(), |func_body| {
+            ensure_self_contained(func_body.instrs())?;
+            FuncBodyParts::encode(func_body, &mut out)
+        })?;
+        Ok(out)
+    }
+
+    /// Reloads a function body exported via [`Engine::function_body_bytes`].
+    ///
+    /// Returns a new [`Func`] with the reloaded function body that shares its
+    /// function type and module instance with the Wasm `func`.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If `func` is a host function.
+    /// - If `bytes` are malformed or were exported using a different [`Config`].
+    /// - If `bytes` were exported for a bytecode version other than the one pinned by the [`Config`].
+    /// - If `bytes` encode a function body that does not match the type of `func`.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own `func` or does not use this [`Engine`].
+    ///
+    /// # Safety
+    ///
+    /// The `wasmi` executor trusts the bytecode it executes and only parts of the
+    /// reloaded function body are validated. The caller must make sure that `bytes`
+    /// have been produced by [`Engine::function_body_bytes`] for a function of the
+    /// same Wasm module that `func` belongs to.
+    pub unsafe fn load_function_body<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        func: &Func,
+        bytes: &[u8],
+    ) -> Result<Func, Error> {
+        assert!(
+            Engine::same(self, ctx.as_context().store.engine()),
+            "the store of the function must use this engine"
+        );
+        self.ensure_register_machine()?;
+        let (ty, instance, func_index) = match ctx.as_context().store.inner.resolve_func(func) {
+            FuncEntity::Wasm(wasm_func) => (
+                *wasm_func.ty_dedup(),
+                *wasm_func.instance(),
+                self.resolve_func_2(wasm_func.func_body(), CompiledFuncEntity::func_index),
+            ),
+            FuncEntity::Host(_) => return Err(FuncBodyError::HostFunc.into()),
+        };
+        let mut input = bytes;
+        decode_header(&MAGIC, self.config(), &mut input)?;
+        let mut expected_type = Vec::new();
+        let len_results =
+            ctx.as_context()
+                .store
+                .inner
+                .resolve_func_type_with(&ty, |func_type| {
+                    encode_func_type(func_type, &mut expected_type)?;
+                    Ok::<usize, FuncBodyError>(func_type.results().len())
+                })?;
+        if input.get(..expected_type.len()) != Some(&expected_type[..]) {
+            return Err(FuncBodyError::TypeMismatch.into());
+        }
+        input = &input[expected_type.len()..];
+        let body = FuncBodyParts::decode(&mut input)?;
+        ensure_self_contained(&body.instrs)?;
+        if usize::from(body.len_results) != len_results {
+            return Err(FuncBodyError::TypeMismatch.into());
+        }
+        if !input.is_empty() {
+            return Err(FuncBodyError::InvalidFormat.into());
+        }
+        let func_body = self.alloc_func_2();
+        self.init_func_2(
+            func_body,
+            func_index,
+            body.len_registers,
+            body.len_results,
+            body.consts.into_iter().map(UntypedValue::from),
+            body.instrs,
+        );
+        let func = ctx
+            .as_context_mut()
+            .store
+            .inner
+            .alloc_func(WasmFuncEntity::new(ty, func_body, instance).into());
+        Ok(func)
+    }
+
+    /// Serializes the compiled function bodies of all Wasm modules compiled by the [`Engine`].
+    ///
+    /// The bytes contain the `wasmi` bytecode of all compiled functions together with
+    /// their function local constant values and a fingerprint of the [`Config`] of the
+    /// [`Engine`]. They can be restored via [`Engine::deserialize_code`] into a new
+    /// [`Engine`] with the same [`Config`] which allows to cache compiled Wasm modules.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If the [`Config`] pins a bytecode version other than [`BYTECODE_VERSION`].
+    pub fn serialize_code(&self) -> Result<Vec<u8>, Error> {
+        self.ensure_register_machine()?;
+        let mut out = Vec::new();
+        encode_header(&CODE_MAGIC, self.config(), &mut out)?;
+        let res = self.inner.res.read();
+        let funcs: Vec<_> = res.code_map_2.iter().collect();
+        // Note: the module structures are shared by all functions of a Wasm module
+        //       and are therefore encoded only once and referred to by their index.
+        let mut structures = BTreeMap::new();
+        let mut ordered = Vec::new();
+        for (_, entity) in &funcs {
+            let bytes = module_structure_bytes(entity);
+            structures.entry(bytes).or_insert_with(|| {
+                ordered.push(bytes);
+                ordered.len() as u32 - 1
+            });
+        }
+        (ordered.len() as u32).encode(&mut out)?;
+        for bytes in ordered {
+            (bytes.len() as u32).encode(&mut out)?;
+            out.extend_from_slice(bytes);
+        }
+        (funcs.len() as u32).encode(&mut out)?;
+        for (func, entity) in &funcs {
+            func.encode(&mut out)?;
+            entity.func_index().encode(&mut out)?;
+            entity.wasm_hash().encode(&mut out)?;
+            structures[module_structure_bytes(entity)].encode(&mut out)?;
+            FuncBodyParts::encode(entity, &mut out)?;
+        }
+        Ok(out)
+    }
+
+    /// Restores the compiled function bodies serialized via [`Engine::serialize_code`].
+    ///
+    /// The restored function bodies are used instead of translating the Wasm functions
+    /// of the Wasm modules that are compiled afterwards by the [`Engine`]. Wasm functions
+    /// without matching restored function body are translated as usual.
+    ///
+    /// # Note
+    ///
+    /// A restored function body is only used if its Wasm function is compiled to the
+    /// same [`CompiledFunc`] as it was compiled to by the serializing [`Engine`] and
+    /// if the Wasm module bytes up to and including the function body as well as the
+    /// types of the tables, memories and globals of its Wasm module match those seen
+    /// by the serializing [`Engine`]. Therefore the same Wasm modules must be compiled
+    /// in the same order as by the serializing [`Engine`] and before any other Wasm
+    /// module is compiled. Otherwise the Wasm functions are validated and translated
+    /// as usual.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If `bytes` are malformed or were serialized using a different [`Config`]
+    ///   or `wasmi` version.
+    /// - If `bytes` were serialized for a bytecode version other than the one pinned by the [`Config`].
+    /// - If called from within a Wasm execution of the [`Engine`], e.g. by a host function.
+    ///
+    /// # Safety
+    ///
+    /// The `wasmi` executor trusts the bytecode it executes and the Wasm functions
+    /// using restored function bodies are neither validated nor translated. The caller
+    /// must make sure that `bytes` have been produced by [`Engine::serialize_code`].
+    pub unsafe fn deserialize_code(&self, bytes: &[u8]) -> Result<(), Error> {
+        self.ensure_register_machine()?;
+        self.ensure_not_executing().map_err(ModuleError::from)?;
+        let mut input = bytes;
+        decode_header(&CODE_MAGIC, self.config(), &mut input)?;
+        let len_structures = u32::decode(&mut input)? as usize;
+        let mut structures = Vec::with_capacity(len_structures.min(input.len()));
+        for _ in 0..len_structures {
+            let len_bytes = u32::decode(&mut input)? as usize;
+            let bytes = input.get(..len_bytes).ok_or(FuncBodyError::InvalidFormat)?;
+            input = &input[len_bytes..];
+            structures.push(ModuleStructure(bytes.into()));
+        }
+        let len_funcs = u32::decode(&mut input)? as usize;
+        let mut funcs = Vec::with_capacity(len_funcs.min(input.len()));
+        for _ in 0..len_funcs {
+            let func = CompiledFunc::decode(&mut input)?;
+            let func_index = u32::decode(&mut input)?;
+            let wasm_hash = u64::decode(&mut input)?;
+            let structure = structures
+                .get(u32::decode(&mut input)? as usize)
+                .cloned()
+                .ok_or(FuncBodyError::InvalidFormat)?;
+            let body = FuncBodyParts::decode(&mut input)?;
+            funcs.push(RestoredFunc {
+                func,
+                func_index,
+                wasm_hash,
+                structure,
+                body,
+            });
+        }
+        if !input.is_empty() {
+            return Err(FuncBodyError::InvalidFormat.into());
+        }
+        self.inner.res_mut().restored_funcs_2.extend(funcs);
+        Ok(())
+    }
+
+    /// Returns `Ok` if the [`Engine`] uses [`EngineBackend::RegisterMachine`].
+    ///
+    /// # Errors
+    ///
+    /// Otherwise returns [`FuncBodyError::UnsupportedBackend`].
+    fn ensure_register_machine(&self) -> Result<(), FuncBodyError> {
+        match self.config().engine_backend() {
+            EngineBackend::RegisterMachine => Ok(()),
+            EngineBackend::StackMachine => Err(FuncBodyError::UnsupportedBackend),
+        }
+    }
+}
@@ -32,6 +32,48 @@ impl TranslationError {
             inner: Box::new(TranslationErrorInner::UnsupportedValueType(value_type)),
         }
     }
+
+    /// Creates a new error indicating an unsupported Wasm operator named `name`.
+    pub fn unsupported_operator(name: &'static str) -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::UnsupportedOperator(name)),
+        }
+    }
+
+    /// Creates a new error indicating that a module defines too many functions.
+    pub fn too_many_functions() -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::TooManyFunctions),
+        }
+    }
+
+    /// Creates a new error indicating that a function type declares too many parameters.
+    pub fn too_many_function_type_params() -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::TooManyFunctionTypeParams),
+        }
+    }
+
+    /// Creates a new error indicating a mismatch of the emulated and the validated stack height.
+    pub fn stack_height_mismatch(expected: usize, found: usize) -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::StackHeightMismatch { expected, found }),
+        }
+    }
+
+    /// Creates a new error indicating that a function type declares too many results.
+    pub fn too_many_function_type_results() -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::TooManyFunctionTypeResults),
+        }
+    }
+
+    /// Creates a new error indicating a `memory.copy` between distinct linear memories.
+    pub fn unsupported_cross_memory_copy() -> Self {
+        Self {
+            inner: Box::new(TranslationErrorInner::UnsupportedCrossMemoryCopy),
+        }
+    }
 }
 
 impl From<wasmparser::BinaryReaderError> for TranslationError {
@@ -60,6 +102,9 @@ impl Display for TranslationError {
             TranslationErrorInner::UnsupportedValueType(error) => {
                 write!(f, "encountered unsupported Wasm value type: {error:?}")
             }
+            TranslationErrorInner::UnsupportedOperator(name) => {
+                write!(f, "encountered unsupported Wasm operator: {name}")
+            }
             TranslationErrorInner::DropKeep(error) => error.fmt(f),
             TranslationErrorInner::BranchTableTargetsOutOfBounds => {
                 write!(
@@ -97,6 +142,9 @@ impl Display for TranslationError {
             TranslationErrorInner::ProviderSliceOverflow => {
                 write!(f, "tried to allocate too many or too large provider slices")
             }
+            TranslationErrorInner::ProviderSliceTooLong => {
+                write!(f, "encountered provider slice longer than configured")
+            }
             TranslationErrorInner::TooManyFuncLocalConstValues => {
                 write!(
                     f,
@@ -109,6 +157,48 @@ impl Display for TranslationError {
             TranslationErrorInner::TooManyFunctionParams => {
                 write!(f, "encountered function with too many function parameters")
             }
+            TranslationErrorInner::TooManyFunctions => {
+                write!(
+                    f,
+                    "encountered module defining more functions than configured"
+                )
+            }
+            TranslationErrorInner::TooManyFunctionTypeParams => {
+                write!(
+                    f,
+                    "encountered function type with more parameters than configured"
+                )
+            }
+            TranslationErrorInner::TooManyFunctionTypeResults => {
+                write!(
+                    f,
+                    "encountered function type with more results than configured"
+                )
+            }
+            TranslationErrorInner::TooManyLabels => {
+                write!(f, "encountered function with more labels than configured")
+            }
+            TranslationErrorInner::TooManyBranches => {
+                write!(f, "encountered function with more branches than configured")
+            }
+            TranslationErrorInner::UnsupportedCrossMemoryCopy => {
+                write!(
+                    f,
+                    "encountered unsupported memory.copy between distinct linear memories"
+                )
+            }
+            TranslationErrorInner::StackHeightMismatch { expected, found } => {
+                write!(
+                    f,
+                    "emulated value stack height {found} does not match validated height {expected}"
+                )
+            }
+            TranslationErrorInner::ReentrantEngineMutation => {
+                write!(
+                    f,
+                    "tried to compile using an engine from within one of its own Wasm executions"
+                )
+            }
         }
     }
 }
@@ -122,6 +212,8 @@ pub enum TranslationErrorInner {
     UnsupportedBlockType(wasmparser::BlockType),
     /// Encountered an unsupported Wasm value type.
     UnsupportedValueType(wasmparser::ValType),
+    /// Encountered a Wasm operator that is not supported by the chosen engine backend.
+    UnsupportedOperator(&'static str),
     /// An error with limitations of `DropKeep`.
     DropKeep(DropKeepError),
     /// When using too many branch table targets.
@@ -140,10 +232,33 @@ pub enum TranslationErrorInner {
     EmulatedValueStackOverflow,
     /// Tried to allocate too many or large provider slices.
     ProviderSliceOverflow,
+    /// Tried to encode a provider slice that is longer than configured.
+    ProviderSliceTooLong,
     /// Tried to allocate too many function local constant values.
     TooManyFuncLocalConstValues,
     /// Tried to define a function with too many function results.
     TooManyFunctionResults,
     /// Tried to define a function with too many function parameters.
     TooManyFunctionParams,
+    /// Tried to define more functions in a module than configured.
+    TooManyFunctions,
+    /// Tried to declare a function type with more parameters than configured.
+    TooManyFunctionTypeParams,
+    /// Tried to declare a function type with more results than configured.
+    TooManyFunctionTypeResults,
+    /// Tried to define a function with more labels than configured.
+    TooManyLabels,
+    /// Tried to define a function with more branches than configured.
+    TooManyBranches,
+    /// Encountered an unsupported `memory.copy` between distinct linear memories.
+    UnsupportedCrossMemoryCopy,
+    /// The emulated value stack height does not match the height expected by validation.
+    StackHeightMismatch {
+        /// The operand stack height expected by Wasm validation.
+        expected: usize,
+        /// The height of the emulated value stack of the translator.
+        found: usize,
+    },
+    /// Tried to compile using an engine from within one of its own Wasm executions.
+    ReentrantEngineMutation,
 }
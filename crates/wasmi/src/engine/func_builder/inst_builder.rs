@@ -6,11 +6,13 @@ use super::{
 };
 use crate::engine::{
     bytecode::{BranchOffset, Instruction},
+    const_pool::{ConstPool, ConstRef},
     CompiledFunc,
     DropKeep,
     Engine,
 };
 use alloc::vec::Vec;
+use wasmi_core::UntypedValue;
 
 /// A reference to an instruction of the partially
 /// constructed function body of the [`InstructionsBuilder`].
@@ -77,6 +79,13 @@ pub struct InstructionsBuilder {
     insts: Vec<Instruction>,
     /// All labels and their uses.
     labels: LabelRegistry,
+    /// The constant values referenced by the partially constructed function body.
+    ///
+    /// # Note
+    ///
+    /// The [`ConstRef`] of these constant values are local to the function body
+    /// and are mapped to the [`ConstRef`] of the [`Engine`] upon finishing it.
+    consts: ConstPool,
 }
 
 impl InstructionsBuilder {
@@ -84,6 +93,16 @@ impl InstructionsBuilder {
     pub fn reset(&mut self) {
         self.insts.clear();
         self.labels.reset();
+        self.consts.clear();
+    }
+
+    /// Allocates a constant `value` for the function body and returns its local [`ConstRef`].
+    ///
+    /// # Errors
+    ///
+    /// If too many constant values have been allocated for the function body.
+    pub fn alloc_const(&mut self, value: UntypedValue) -> Result<ConstRef, TranslationError> {
+        self.consts.alloc(value)
     }
 
     /// Returns the current instruction pointer as index.
@@ -91,8 +110,17 @@ impl InstructionsBuilder {
         Instr::from_usize(self.insts.len())
     }
 
+    /// Configures the maximum number of labels and branches of a function if any.
+    pub fn set_label_limits(&mut self, max_labels: Option<u32>, max_branches: Option<u32>) {
+        self.labels.set_limits(max_labels, max_branches)
+    }
+
     /// Creates a new unresolved label and returns an index to it.
-    pub fn new_label(&mut self) -> LabelRef {
+    ///
+    /// # Errors
+    ///
+    /// If this exceeds the configured maximum number of labels.
+    pub fn new_label(&mut self) -> Result<LabelRef, TranslationError> {
         self.labels.new_label()
     }
 
@@ -135,6 +163,36 @@ impl InstructionsBuilder {
         idx
     }
 
+    /// Removes the last pushed instruction if it pushes a constant value satisfying `f`.
+    ///
+    /// Returns `true` if the instruction has been removed.
+    ///
+    /// # Note
+    ///
+    /// Nothing is removed if a label is pinned to the last instruction or to the
+    /// current instruction pointer since then the value on top of the stack is
+    /// not necessarily the constant value pushed by the last instruction.
+    pub fn pop_const_if(&mut self, f: impl FnOnce(UntypedValue) -> bool) -> bool {
+        let value = match self.insts.last() {
+            Some(Instruction::Const32(bytes)) => UntypedValue::from(u32::from_ne_bytes(*bytes)),
+            Some(Instruction::I64Const32(value)) => UntypedValue::from(i64::from(*value)),
+            Some(Instruction::ConstRef(cref)) => match self.consts.get(*cref) {
+                Some(value) => value,
+                None => return false,
+            },
+            _ => return false,
+        };
+        let last = Instr::from_usize(self.insts.len() - 1);
+        if self.labels.is_pinned_to(last) || self.labels.is_pinned_to(self.current_pc()) {
+            return false;
+        }
+        if !f(value) {
+            return false;
+        }
+        self.insts.pop();
+        true
+    }
+
     /// Pushes an [`Instruction::BrAdjust`] to the [`InstructionsBuilder`].
     ///
     /// Returns an [`Instr`] to refer to the pushed instruction.
@@ -186,10 +244,14 @@ impl InstructionsBuilder {
     ///
     /// # Note
     ///
-    /// This feeds the built-up instructions of the function body
-    /// into the [`Engine`] so that the [`Engine`] is
+    /// This feeds the built-up instructions and constant values of the
+    /// function body into the [`Engine`] so that the [`Engine`] is
     /// aware of the Wasm function existence. Returns a [`CompiledFunc`]
     /// reference that allows to retrieve the instructions.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Engine`] cannot allocate the constant values of the function body.
     pub fn finish(
         &mut self,
         engine: &Engine,
@@ -198,8 +260,13 @@ impl InstructionsBuilder {
         local_stack_height: usize,
     ) -> Result<(), TranslationError> {
         self.update_branch_offsets()?;
-        engine.init_func(func, len_locals, local_stack_height, self.insts.drain(..));
-        Ok(())
+        engine.init_func(
+            func,
+            len_locals,
+            local_stack_height,
+            &self.consts,
+            self.insts.drain(..),
+        )
     }
 
     /// Updates the branch offsets of all branch instructions inplace.
@@ -1,4 +1,4 @@
-use super::{Instr, TranslationError};
+use super::{Instr, TranslationError, TranslationErrorInner};
 use crate::engine::bytecode::BranchOffset;
 use alloc::vec::Vec;
 use core::{
@@ -34,6 +34,12 @@ impl LabelRef {
 pub struct LabelRegistry {
     labels: Vec<Label>,
     users: Vec<LabelUser>,
+    /// The number of branches that resolved a label so far.
+    len_branches: u32,
+    /// The maximum number of labels if any.
+    max_labels: Option<u32>,
+    /// The maximum number of branches if any.
+    max_branches: Option<u32>,
 }
 
 /// A user of a label.
@@ -82,17 +88,31 @@ impl LabelRegistry {
     pub fn reset(&mut self) {
         self.labels.clear();
         self.users.clear();
+        self.len_branches = 0;
+    }
+
+    /// Configures the maximum number of labels and branches if any.
+    pub fn set_limits(&mut self, max_labels: Option<u32>, max_branches: Option<u32>) {
+        self.max_labels = max_labels;
+        self.max_branches = max_branches;
     }
 
     /// Allocates a new unpinned [`Label`].
-    pub fn new_label(&mut self) -> LabelRef {
+    ///
+    /// # Errors
+    ///
+    /// If this exceeds the configured maximum number of labels.
+    pub fn new_label(&mut self) -> Result<LabelRef, TranslationError> {
         let index: u32 = self
             .labels
             .len()
             .try_into()
             .unwrap_or_else(|err| panic!("cannot have more than u32::MAX label refs: {err}"));
+        if matches!(self.max_labels, Some(limit) if index >= limit) {
+            return Err(TranslationError::new(TranslationErrorInner::TooManyLabels));
+        }
         self.labels.push(Label::Unpinned);
-        LabelRef(index)
+        Ok(LabelRef(index))
     }
 
     /// Returns a shared reference to the underlying [`Label`].
@@ -139,11 +159,22 @@ impl LabelRegistry {
     ///
     /// In case the `label` has not yet been pinned the `user` is registered
     /// for deferred label resolution.
+    ///
+    /// # Errors
+    ///
+    /// - If the resulting [`BranchOffset`] is out of bounds.
+    /// - If this exceeds the configured maximum number of branches.
     pub fn try_resolve_label(
         &mut self,
         label: LabelRef,
         user: Instr,
     ) -> Result<BranchOffset, TranslationError> {
+        if matches!(self.max_branches, Some(limit) if self.len_branches >= limit) {
+            return Err(TranslationError::new(
+                TranslationErrorInner::TooManyBranches,
+            ));
+        }
+        self.len_branches = self.len_branches.saturating_add(1);
         let offset = match *self.get_label(label) {
             Label::Pinned(target) => BranchOffset::from_src_to_dst(user, target)?,
             Label::Unpinned => {
@@ -166,6 +197,13 @@ impl LabelRegistry {
         }
     }
 
+    /// Returns `true` if any label is pinned to `instr`.
+    pub fn is_pinned_to(&self, instr: Instr) -> bool {
+        self.labels
+            .iter()
+            .any(|label| matches!(label, Label::Pinned(pinned) if *pinned == instr))
+    }
+
     /// Returns an iterator over pairs of user [`Instr`] and their [`BranchOffset`].
     ///
     /// # Panics
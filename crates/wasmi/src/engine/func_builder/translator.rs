@@ -29,6 +29,7 @@ use crate::{
         },
         config::FuelCosts,
         func_builder::control_frame::ControlFrameKind,
+        func_locations::WasmOffsets,
         CompiledFunc,
         DropKeep,
         Instr,
@@ -66,6 +67,8 @@ pub struct FuncTranslatorAllocations {
     inst_builder: InstructionsBuilder,
     /// Buffer for translating `br_table`.
     br_table_branches: Vec<Instruction>,
+    /// The Wasm binary offsets of the translated Wasm operators.
+    wasm_offsets: WasmOffsets,
 }
 
 impl FuncTranslatorAllocations {
@@ -79,6 +82,7 @@ impl FuncTranslatorAllocations {
         self.control_frames.reset();
         self.inst_builder.reset();
         self.br_table_branches.clear();
+        self.wasm_offsets.reset();
     }
 }
 
@@ -115,7 +119,7 @@ impl<'parser> FuncTranslator<'parser> {
         compiled_func: CompiledFunc,
         res: ModuleResources<'parser>,
         alloc: FuncTranslatorAllocations,
-    ) -> Self {
+    ) -> Result<Self, TranslationError> {
         Self {
             func,
             compiled_func,
@@ -134,18 +138,23 @@ impl<'parser> FuncTranslator<'parser> {
     }
 
     /// Initializes a newly constructed [`FuncTranslator`].
-    fn init(mut self) -> Self {
+    fn init(mut self) -> Result<Self, TranslationError> {
         self.alloc.reset();
-        self.init_func_body_block();
+        let max_labels = self.engine().config().get_max_function_labels();
+        let max_branches = self.engine().config().get_max_function_branches();
+        self.alloc
+            .inst_builder
+            .set_label_limits(max_labels, max_branches);
+        self.init_func_body_block()?;
         self.init_func_params();
-        self
+        Ok(self)
     }
 
     /// Registers the `block` control frame surrounding the entire function body.
-    fn init_func_body_block(&mut self) {
+    fn init_func_body_block(&mut self) -> Result<(), TranslationError> {
         let func_type = self.res.get_type_of_func(self.func);
         let block_type = BlockType::func_type(func_type);
-        let end_label = self.alloc.inst_builder.new_label();
+        let end_label = self.alloc.inst_builder.new_label()?;
         let consume_fuel = self.is_fuel_metering_enabled().then(|| {
             self.alloc
                 .inst_builder
@@ -153,6 +162,7 @@ impl<'parser> FuncTranslator<'parser> {
         });
         let block_frame = BlockControlFrame::new(block_type, end_label, 0, consume_fuel);
         self.alloc.control_frames.push_frame(block_frame);
+        Ok(())
     }
 
     /// Registers the function parameters in the emulated value stack.
@@ -193,7 +203,21 @@ impl<'parser> FuncTranslator<'parser> {
             self.compiled_func,
             self.len_locals(),
             self.stack_height.max_stack_height() as usize,
-        )
+        )?;
+        if !self.alloc.wasm_offsets.is_empty() {
+            self.res.engine().set_wasm_offsets(
+                self.compiled_func,
+                self.func.into_u32(),
+                &mut self.alloc.wasm_offsets,
+            );
+        }
+        Ok(())
+    }
+
+    /// Records that the next translated Wasm operator is located at `wasm_offset` in the Wasm binary.
+    pub fn record_wasm_offset(&mut self, wasm_offset: usize) {
+        let instr = self.alloc.inst_builder.current_pc().into_u32();
+        self.alloc.wasm_offsets.record(instr, wasm_offset);
     }
 
     /// Consumes `self` and returns the underlying reusable [`FuncTranslatorAllocations`].
@@ -284,6 +308,20 @@ impl<'parser> FuncTranslator<'parser> {
         self.reachable
     }
 
+    /// Returns the height of the emulated value stack if the current code is reachable.
+    ///
+    /// This does not include input parameters and local variables.
+    pub fn stack_height(&self) -> Option<usize> {
+        self.is_reachable()
+            .then(|| self.stack_height.height() as usize)
+    }
+
+    /// Pushes a value to the emulated value stack that no Wasm operator produced.
+    #[cfg(test)]
+    pub fn corrupt_stack_height(&mut self) {
+        self.stack_height.push()
+    }
+
     /// Translates into `wasmi` bytecode if the current code path is reachable.
     ///
     /// # Note
@@ -431,7 +469,7 @@ impl<'parser> FuncTranslator<'parser> {
     fn optimize_global_get(
         global_type: &GlobalType,
         init_value: Option<&ConstExpr>,
-        engine: &Engine,
+        inst_builder: &mut InstructionsBuilder,
     ) -> Result<Option<Instruction>, TranslationError> {
         if let (Mutability::Const, Some(init_expr)) = (global_type.mutability(), init_value) {
             if let Some(value) = init_expr.eval_const() {
@@ -449,7 +487,7 @@ impl<'parser> FuncTranslator<'parser> {
                 }
                 // No optimized case was applicable so we have to allocate
                 // a constant value in the const pool and reference it.
-                let cref = engine.alloc_const(value)?;
+                let cref = inst_builder.alloc_const(value)?;
                 return Ok(Some(Instruction::ConstRef(cref)));
             }
             if let Some(func_index) = init_expr.funcref() {
@@ -468,6 +506,26 @@ impl<'parser> FuncTranslator<'parser> {
         (memory_idx, offset)
     }
 
+    /// Pushes the memory instruction `inst` that accesses the linear memory at `memory_idx`.
+    ///
+    /// # Note
+    ///
+    /// Memory instructions that access a non-default linear memory are surrounded
+    /// by [`Instruction::UseMemory`] instructions that select their linear memory
+    /// and restore the default linear memory afterwards. This keeps the execution
+    /// of memory instructions accessing the default linear memory free of overhead.
+    fn push_memory_inst(&mut self, memory_idx: MemoryIdx, inst: Instruction) {
+        let memory_idx = memory_idx.into_u32();
+        let inst_builder = &mut self.alloc.inst_builder;
+        if memory_idx == DEFAULT_MEMORY_INDEX {
+            inst_builder.push_inst(inst);
+            return;
+        }
+        inst_builder.push_inst(Instruction::UseMemory(memory_idx.into()));
+        inst_builder.push_inst(inst);
+        inst_builder.push_inst(Instruction::UseMemory(DEFAULT_MEMORY_INDEX.into()));
+    }
+
     /// Translate a Wasm `<ty>.load` instruction.
     ///
     /// # Note
@@ -496,12 +554,11 @@ impl<'parser> FuncTranslator<'parser> {
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
             let (memory_idx, offset) = Self::decompose_memarg(memarg);
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
             builder.bump_fuel_consumption(builder.fuel_costs().load)?;
             builder.stack_height.pop1();
             builder.stack_height.push();
             let offset = AddressOffset::from(offset);
-            builder.alloc.inst_builder.push_inst(make_inst(offset));
+            builder.push_memory_inst(memory_idx, make_inst(offset));
             Ok(())
         })
     }
@@ -529,11 +586,33 @@ impl<'parser> FuncTranslator<'parser> {
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
             let (memory_idx, offset) = Self::decompose_memarg(memarg);
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
             builder.bump_fuel_consumption(builder.fuel_costs().store)?;
             builder.stack_height.pop2();
             let offset = AddressOffset::from(offset);
-            builder.alloc.inst_builder.push_inst(make_inst(offset));
+            builder.push_memory_inst(memory_idx, make_inst(offset));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.atomic.wait` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used as the translation backend of the following Wasm instructions:
+    ///
+    /// - `memory.atomic.wait32`
+    /// - `memory.atomic.wait64`
+    fn translate_memory_atomic_wait(
+        &mut self,
+        memarg: wasmparser::MemArg,
+        make_inst: fn(AddressOffset) -> Instruction,
+    ) -> Result<(), TranslationError> {
+        self.translate_if_reachable(|builder| {
+            let (memory_idx, offset) = Self::decompose_memarg(memarg);
+            builder.bump_fuel_consumption(builder.fuel_costs().load)?;
+            builder.stack_height.pop2();
+            let offset = AddressOffset::from(offset);
+            builder.push_memory_inst(memory_idx, make_inst(offset));
             Ok(())
         })
     }
@@ -555,7 +634,7 @@ impl<'parser> FuncTranslator<'parser> {
             builder.bump_fuel_consumption(builder.fuel_costs().base)?;
             let value = value.into();
             builder.stack_height.push();
-            let cref = builder.engine().alloc_const(value)?;
+            let cref = builder.alloc.inst_builder.alloc_const(value)?;
             builder
                 .alloc
                 .inst_builder
@@ -610,6 +689,52 @@ impl<'parser> FuncTranslator<'parser> {
         })
     }
 
+    /// Translate a Wasm integer comparison that always yields `result` against the constant `extreme`.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate the ordering comparisons of `i32` and `i64` whose
+    /// result is known if their right-hand side is the minimum or maximum value of
+    /// their type, for example `x <u 0` is always `false` and `x <=s MAX` is always `true`.
+    ///
+    /// The `extreme` value is sign-extended to `i64` for `i32` comparisons so that
+    /// the unsigned maximum of `i32` is represented as `-1`.
+    ///
+    /// If the right-hand side operand has been pushed by the immediately preceding
+    /// constant instruction with the `extreme` value, the constant is removed and the
+    /// left-hand side operand is replaced by the constant `result`.
+    fn translate_extreme_cmp(
+        &mut self,
+        value_type: ValueType,
+        inst: Instruction,
+        extreme: i64,
+        result: bool,
+    ) -> Result<(), TranslationError> {
+        self.translate_if_reachable(|builder| {
+            let is_extreme = builder.alloc.inst_builder.pop_const_if(|rhs| {
+                let rhs = match value_type {
+                    ValueType::I32 => i64::from(i32::from(rhs)),
+                    _ => i64::from(rhs),
+                };
+                rhs == extreme
+            });
+            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            if is_extreme {
+                builder.stack_height.pop1();
+                builder.alloc.inst_builder.push_inst(Instruction::Drop);
+                builder
+                    .alloc
+                    .inst_builder
+                    .push_inst(Instruction::i32_const(i32::from(result)));
+                return Ok(());
+            }
+            builder.stack_height.pop2();
+            builder.stack_height.push();
+            builder.alloc.inst_builder.push_inst(inst);
+            Ok(())
+        })
+    }
+
     /// Translate a unary Wasm instruction.
     ///
     /// # Note
@@ -673,6 +798,46 @@ impl<'parser> FuncTranslator<'parser> {
         })
     }
 
+    /// Translate a Wasm shift or rotate instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
+    /// - `{i32, i64}.shl`
+    /// - `{i32, u32, i64, u64}.shr`
+    /// - `{i32, i64}.rotl`
+    /// - `{i32, i64}.rotr`
+    ///
+    /// Shifting or rotating by a constant amount of zero bits is a no-op.
+    /// In this case the constant shift amount is removed and the shifted
+    /// value stays in place on the stack.
+    fn translate_shift(
+        &mut self,
+        value_type: ValueType,
+        inst: Instruction,
+    ) -> Result<(), TranslationError> {
+        self.translate_if_reachable(|builder| {
+            let mask = match value_type {
+                ValueType::I64 => 0x3F,
+                _ => 0x1F,
+            };
+            let is_noop = builder
+                .alloc
+                .inst_builder
+                .pop_const_if(|amount| u64::from(amount) & mask == 0);
+            if is_noop {
+                builder.stack_height.pop1();
+                return Ok(());
+            }
+            builder.bump_fuel_consumption(builder.fuel_costs().base)?;
+            builder.stack_height.pop2();
+            builder.stack_height.push();
+            builder.alloc.inst_builder.push_inst(inst);
+            Ok(())
+        })
+    }
+
     /// Translate a Wasm conversion instruction.
     ///
     /// - `i32.wrap_i64`
@@ -810,6 +975,15 @@ macro_rules! impl_visit_operator {
     ( @tail_call $($rest:tt)* ) => {
         impl_visit_operator!(@@skipped $($rest)*);
     };
+    ( @threads MemoryAtomicNotify $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicNotify $($rest)*);
+    };
+    ( @threads MemoryAtomicWait32 $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicWait32 $($rest)*);
+    };
+    ( @threads MemoryAtomicWait64 $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicWait64 $($rest)*);
+    };
     ( @@skipped $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         // We skip Wasm operators that we already implement manually.
         impl_visit_operator!($($rest)*);
@@ -853,7 +1027,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             // and is applicable since Wasm `block` unconditionally executes all its instructions.
             let consume_fuel = self.alloc.control_frames.last().consume_fuel_instr();
             let stack_height = self.frame_stack_height(block_type);
-            let end_label = self.alloc.inst_builder.new_label();
+            let end_label = self.alloc.inst_builder.new_label()?;
             self.alloc.control_frames.push_frame(BlockControlFrame::new(
                 block_type,
                 end_label,
@@ -875,7 +1049,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         let block_type = BlockType::new(block_type, self.res);
         if self.is_reachable() {
             let stack_height = self.frame_stack_height(block_type);
-            let header = self.alloc.inst_builder.new_label();
+            let header = self.alloc.inst_builder.new_label()?;
             self.alloc.inst_builder.pin_label(header);
             let consume_fuel = self.is_fuel_metering_enabled().then(|| {
                 self.alloc
@@ -904,8 +1078,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         if self.is_reachable() {
             self.stack_height.pop1();
             let stack_height = self.frame_stack_height(block_type);
-            let else_label = self.alloc.inst_builder.new_label();
-            let end_label = self.alloc.inst_builder.new_label();
+            let else_label = self.alloc.inst_builder.new_label()?;
+            let end_label = self.alloc.inst_builder.new_label()?;
             self.bump_fuel_consumption(self.fuel_costs().base)?;
             let branch_offset = self.branch_offset(else_label)?;
             self.alloc
@@ -1423,11 +1597,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             builder.stack_height.push();
             let (global_type, init_value) = builder.res.get_global(global_idx);
             let global_idx = bytecode::GlobalIdx::from(global_idx.into_u32());
-            let engine = builder.engine();
-            let instr = Self::optimize_global_get(&global_type, init_value, engine)?.unwrap_or({
-                // No optimization took place in this case.
-                Instruction::GlobalGet(global_idx)
-            });
+            let inst_builder = &mut builder.alloc.inst_builder;
+            let instr = Self::optimize_global_get(&global_type, init_value, inst_builder)?
+                .unwrap_or({
+                    // No optimization took place in this case.
+                    Instruction::GlobalGet(global_idx)
+                });
             builder.alloc.inst_builder.push_inst(instr);
             Ok(())
         })
@@ -1549,12 +1724,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         self.translate_if_reachable(|builder| {
             builder.bump_fuel_consumption(builder.fuel_costs().entity)?;
             let memory_idx = MemoryIdx::from(memory_idx);
-            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
             builder.stack_height.push();
-            builder
-                .alloc
-                .inst_builder
-                .push_inst(Instruction::MemorySize);
+            builder.push_memory_inst(memory_idx, Instruction::MemorySize);
             Ok(())
         })
     }
@@ -1565,12 +1736,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         _mem_byte: u8,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_index, DEFAULT_MEMORY_INDEX);
             builder.bump_fuel_consumption(builder.fuel_costs().entity)?;
-            builder
-                .alloc
-                .inst_builder
-                .push_inst(Instruction::MemoryGrow);
+            builder.push_memory_inst(MemoryIdx::from(memory_index), Instruction::MemoryGrow);
             Ok(())
         })
     }
@@ -1581,40 +1748,61 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         memory_index: u32,
     ) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_index, DEFAULT_MEMORY_INDEX);
             builder.bump_fuel_consumption(builder.fuel_costs().entity)?;
             builder.stack_height.pop3();
-            builder
-                .alloc
-                .inst_builder
-                .push_inst(Instruction::MemoryInit(DataSegmentIdx::from(segment_index)));
+            builder.push_memory_inst(
+                MemoryIdx::from(memory_index),
+                Instruction::MemoryInit(DataSegmentIdx::from(segment_index)),
+            );
             Ok(())
         })
     }
 
+    fn visit_memory_atomic_notify(
+        &mut self,
+        memarg: wasmparser::MemArg,
+    ) -> Result<(), TranslationError> {
+        self.translate_if_reachable(|builder| {
+            let (memory_idx, offset) = Self::decompose_memarg(memarg);
+            builder.bump_fuel_consumption(builder.fuel_costs().load)?;
+            builder.stack_height.pop1();
+            let offset = AddressOffset::from(offset);
+            builder.push_memory_inst(memory_idx, Instruction::MemoryAtomicNotify(offset));
+            Ok(())
+        })
+    }
+
+    fn visit_memory_atomic_wait32(
+        &mut self,
+        memarg: wasmparser::MemArg,
+    ) -> Result<(), TranslationError> {
+        self.translate_memory_atomic_wait(memarg, Instruction::MemoryAtomicWait32)
+    }
+
+    fn visit_memory_atomic_wait64(
+        &mut self,
+        memarg: wasmparser::MemArg,
+    ) -> Result<(), TranslationError> {
+        self.translate_memory_atomic_wait(memarg, Instruction::MemoryAtomicWait64)
+    }
+
     fn visit_memory_fill(&mut self, memory_index: u32) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(memory_index, DEFAULT_MEMORY_INDEX);
             builder.bump_fuel_consumption(builder.fuel_costs().entity)?;
             builder.stack_height.pop3();
-            builder
-                .alloc
-                .inst_builder
-                .push_inst(Instruction::MemoryFill);
+            builder.push_memory_inst(MemoryIdx::from(memory_index), Instruction::MemoryFill);
             Ok(())
         })
     }
 
     fn visit_memory_copy(&mut self, dst_mem: u32, src_mem: u32) -> Result<(), TranslationError> {
         self.translate_if_reachable(|builder| {
-            debug_assert_eq!(dst_mem, DEFAULT_MEMORY_INDEX);
-            debug_assert_eq!(src_mem, DEFAULT_MEMORY_INDEX);
+            if dst_mem != src_mem {
+                return Err(TranslationError::unsupported_cross_memory_copy());
+            }
             builder.bump_fuel_consumption(builder.fuel_costs().entity)?;
             builder.stack_height.pop3();
-            builder
-                .alloc
-                .inst_builder
-                .push_inst(Instruction::MemoryCopy);
+            builder.push_memory_inst(MemoryIdx::from(dst_mem), Instruction::MemoryCopy);
             Ok(())
         })
     }
@@ -1821,35 +2009,55 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_lt_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LtS)
+        self.translate_extreme_cmp(
+            ValueType::I32,
+            Instruction::I32LtS,
+            i64::from(i32::MIN),
+            false,
+        )
     }
 
     fn visit_i32_lt_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LtU)
+        self.translate_extreme_cmp(ValueType::I32, Instruction::I32LtU, 0, false)
     }
 
     fn visit_i32_gt_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GtS)
+        self.translate_extreme_cmp(
+            ValueType::I32,
+            Instruction::I32GtS,
+            i64::from(i32::MAX),
+            false,
+        )
     }
 
     fn visit_i32_gt_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GtU)
+        self.translate_extreme_cmp(ValueType::I32, Instruction::I32GtU, -1, false)
     }
 
     fn visit_i32_le_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LeS)
+        self.translate_extreme_cmp(
+            ValueType::I32,
+            Instruction::I32LeS,
+            i64::from(i32::MAX),
+            true,
+        )
     }
 
     fn visit_i32_le_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LeU)
+        self.translate_extreme_cmp(ValueType::I32, Instruction::I32LeU, -1, true)
     }
 
     fn visit_i32_ge_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GeS)
+        self.translate_extreme_cmp(
+            ValueType::I32,
+            Instruction::I32GeS,
+            i64::from(i32::MIN),
+            true,
+        )
     }
 
     fn visit_i32_ge_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GeU)
+        self.translate_extreme_cmp(ValueType::I32, Instruction::I32GeU, 0, true)
     }
 
     fn visit_i64_eqz(&mut self) -> Result<(), TranslationError> {
@@ -1865,35 +2073,35 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i64_lt_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LtS)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64LtS, i64::MIN, false)
     }
 
     fn visit_i64_lt_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LtU)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64LtU, 0, false)
     }
 
     fn visit_i64_gt_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GtS)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64GtS, i64::MAX, false)
     }
 
     fn visit_i64_gt_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GtU)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64GtU, -1, false)
     }
 
     fn visit_i64_le_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LeS)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64LeS, i64::MAX, true)
     }
 
     fn visit_i64_le_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LeU)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64LeU, -1, true)
     }
 
     fn visit_i64_ge_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GeS)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64GeS, i64::MIN, true)
     }
 
     fn visit_i64_ge_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GeU)
+        self.translate_extreme_cmp(ValueType::I64, Instruction::I64GeU, 0, true)
     }
 
     fn visit_f32_eq(&mut self) -> Result<(), TranslationError> {
@@ -1997,23 +2205,23 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_shl(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Shl)
+        self.translate_shift(ValueType::I32, Instruction::I32Shl)
     }
 
     fn visit_i32_shr_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32ShrS)
+        self.translate_shift(ValueType::I32, Instruction::I32ShrS)
     }
 
     fn visit_i32_shr_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32ShrU)
+        self.translate_shift(ValueType::I32, Instruction::I32ShrU)
     }
 
     fn visit_i32_rotl(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Rotl)
+        self.translate_shift(ValueType::I32, Instruction::I32Rotl)
     }
 
     fn visit_i32_rotr(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Rotr)
+        self.translate_shift(ValueType::I32, Instruction::I32Rotr)
     }
 
     fn visit_i64_clz(&mut self) -> Result<(), TranslationError> {
@@ -2069,23 +2277,23 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i64_shl(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Shl)
+        self.translate_shift(ValueType::I64, Instruction::I64Shl)
     }
 
     fn visit_i64_shr_s(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64ShrS)
+        self.translate_shift(ValueType::I64, Instruction::I64ShrS)
     }
 
     fn visit_i64_shr_u(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64ShrU)
+        self.translate_shift(ValueType::I64, Instruction::I64ShrU)
     }
 
     fn visit_i64_rotl(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Rotl)
+        self.translate_shift(ValueType::I64, Instruction::I64Rotl)
     }
 
     fn visit_i64_rotr(&mut self) -> Result<(), TranslationError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Rotr)
+        self.translate_shift(ValueType::I64, Instruction::I64Rotr)
     }
 
     fn visit_f32_abs(&mut self) -> Result<(), TranslationError> {
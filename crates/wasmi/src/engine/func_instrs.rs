@@ -0,0 +1,92 @@
+use super::{regmach::bytecode::Instruction as Instruction2, EngineBackend, FuncBodyError};
+use crate::{func::FuncEntity, AsContext, Engine, Error, Func};
+use alloc::vec::{self, Vec};
+use core::{fmt, iter::FusedIterator};
+
+/// A single compiled register-machine instruction of a Wasm function.
+///
+/// # Note
+///
+/// The encoding of instructions is not stable across `wasmi` versions.
+/// Their [`Debug`](fmt::Debug) representation is intended for disassembling
+/// and inspecting compiled functions.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct FuncInstr(pub(super) Instruction2);
+
+impl fmt::Debug for FuncInstr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An iterator over the [`FuncInstr`] of a compiled Wasm function.
+///
+/// Created by [`Engine::func_instrs`].
+///
+/// # Note
+///
+/// The iterator owns a copy of the instructions and does not lock the [`Engine`].
+/// Therefore it is fine to call back into the [`Engine`] while iterating.
+#[derive(Debug)]
+pub struct FuncInstrs {
+    /// The instructions of the compiled Wasm function.
+    instrs: vec::IntoIter<Instruction2>,
+}
+
+impl Iterator for FuncInstrs {
+    type Item = FuncInstr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.instrs.next().map(FuncInstr)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.instrs.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for FuncInstrs {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.instrs.next_back().map(FuncInstr)
+    }
+}
+
+impl ExactSizeIterator for FuncInstrs {}
+impl FusedIterator for FuncInstrs {}
+
+impl Engine {
+    /// Returns an iterator over the compiled register-machine instructions of the Wasm `func`.
+    ///
+    /// This is useful for tooling that disassembles or visualizes compiled Wasm functions.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If `func` is a host function.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own `func` or does not use this [`Engine`].
+    pub fn func_instrs(&self, ctx: impl AsContext, func: &Func) -> Result<FuncInstrs, Error> {
+        let ctx = ctx.as_context();
+        assert!(
+            Engine::same(self, ctx.store.engine()),
+            "the store of the function must use this engine"
+        );
+        if !matches!(
+            self.config().engine_backend(),
+            EngineBackend::RegisterMachine
+        ) {
+            return Err(FuncBodyError::UnsupportedBackend.into());
+        }
+        let func_body = match ctx.store.inner.resolve_func(func) {
+            FuncEntity::Wasm(wasm_func) => wasm_func.func_body(),
+            FuncEntity::Host(_) => return Err(FuncBodyError::HostFunc.into()),
+        };
+        let instrs: Vec<Instruction2> =
+            self.resolve_func_2(func_body, |func_body| func_body.instrs().to_vec());
+        Ok(FuncInstrs {
+            instrs: instrs.into_iter(),
+        })
+    }
+}
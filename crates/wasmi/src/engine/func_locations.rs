@@ -0,0 +1,97 @@
+//! Data structures to locate traps of compiled functions within their Wasm binary.
+
+use crate::core::TrapLocation;
+use alloc::{boxed::Box, format, vec::Vec};
+use core::mem::size_of_val;
+
+/// Records the Wasm binary offsets of translated Wasm operators.
+///
+/// # Note
+///
+/// Used by the function translators if enabled via [`Config::set_trap_locations`].
+///
+/// [`Config::set_trap_locations`]: crate::Config::set_trap_locations
+#[derive(Debug, Default)]
+pub struct WasmOffsets {
+    /// The index of the first instruction of a Wasm operator and its Wasm binary offset.
+    ///
+    /// Sorted by instruction index.
+    offsets: Vec<(u32, usize)>,
+}
+
+impl WasmOffsets {
+    /// Resets the [`WasmOffsets`].
+    pub fn reset(&mut self) {
+        self.offsets.clear();
+    }
+
+    /// Returns `true` if no Wasm binary offsets have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Records that the instructions starting at `instr` are translated from the Wasm operator at `wasm_offset`.
+    ///
+    /// # Note
+    ///
+    /// Previously recorded Wasm operators starting at or after `instr` are dropped
+    /// since they did not produce any instructions or their instructions have been
+    /// removed again by the translator.
+    pub fn record(&mut self, instr: u32, wasm_offset: usize) {
+        while matches!(self.offsets.last(), Some((last, _)) if *last >= instr) {
+            self.offsets.pop();
+        }
+        self.offsets.push((instr, wasm_offset));
+    }
+}
+
+/// The information required to locate traps of a compiled function within its Wasm binary.
+#[derive(Debug, Default)]
+pub struct FuncLocations {
+    /// The name of the Wasm function as found in the `name` custom section if any.
+    name: Option<Box<str>>,
+    /// The index of the first instruction of a Wasm operator and its Wasm binary offset.
+    ///
+    /// Sorted by instruction index.
+    wasm_offsets: Box<[(u32, usize)]>,
+}
+
+impl FuncLocations {
+    /// Sets the name of the Wasm function.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.into());
+    }
+
+    /// Takes the recorded `wasm_offsets` of the translated Wasm function.
+    ///
+    /// The [`WasmOffsets`] are empty after this operation.
+    pub fn set_wasm_offsets(&mut self, wasm_offsets: &mut WasmOffsets) {
+        self.wasm_offsets = wasm_offsets.offsets.drain(..).collect();
+    }
+
+    /// Returns the [`TrapLocation`] of the instruction at `instr` of the Wasm function at `func_index`.
+    ///
+    /// Functions without name are named after their index within the Wasm module.
+    /// Instructions in front of the first Wasm operator, such as fuel consumption
+    /// of the function entry, are attributed to the first Wasm operator.
+    ///
+    /// Returns `None` if no Wasm binary offsets have been recorded.
+    pub fn locate(&self, func_index: u32, instr: usize) -> Option<TrapLocation> {
+        let instr = u32::try_from(instr).ok()?;
+        let pos = self
+            .wasm_offsets
+            .partition_point(|(first, _)| *first <= instr);
+        let (_, wasm_offset) = self.wasm_offsets.get(pos.saturating_sub(1))?;
+        let location = match &self.name {
+            Some(name) => TrapLocation::new(&**name, *wasm_offset),
+            None => TrapLocation::new(format!("<wasm function {func_index}>"), *wasm_offset),
+        };
+        Some(location)
+    }
+
+    /// Returns the estimated bytes held by the [`FuncLocations`].
+    pub fn memory_usage(&self) -> usize {
+        let name = self.name.as_deref().map(str::len).unwrap_or(0);
+        name + size_of_val(&self.wasm_offsets[..])
+    }
+}
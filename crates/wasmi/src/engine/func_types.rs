@@ -1,5 +1,6 @@
 use super::{EngineIdx, Guarded};
 use crate::FuncType;
+use alloc::vec::Vec;
 use wasmi_arena::{ArenaIndex, DedupArena, GuardedEntity};
 
 /// A raw index to a function signature entity.
@@ -68,6 +69,12 @@ pub struct FuncTypeRegistry {
     ///
     /// This is used to guard against invalid entity indices.
     engine_idx: EngineIdx,
+    /// The identifiers used by the associated engine before each [`FuncTypeRegistry::reset`].
+    ///
+    /// # Note
+    ///
+    /// This is used to tell apart function types used after a reset from foreign ones.
+    retired: Vec<EngineIdx>,
     /// Deduplicated function types.
     ///
     /// # Note
@@ -82,20 +89,39 @@ impl FuncTypeRegistry {
     pub(crate) fn new(engine_idx: EngineIdx) -> Self {
         Self {
             engine_idx,
+            retired: Vec::new(),
             func_types: DedupArena::default(),
         }
     }
 
+    /// Removes all function types from the [`FuncTypeRegistry`].
+    ///
+    /// All [`DedupFuncType`] allocated so far are invalidated and
+    /// cause a panic when used with the [`FuncTypeRegistry`] afterwards.
+    pub(crate) fn reset(&mut self) {
+        self.retired.push(self.engine_idx);
+        self.engine_idx = EngineIdx::new();
+        self.func_types = DedupArena::default();
+    }
+
     /// Unpacks the entity and checks if it is owned by the engine.
     ///
     /// # Panics
     ///
-    /// If the guarded entity is not owned by the engine.
+    /// - If the guarded entity is not owned by the engine.
+    /// - If the guarded entity was allocated before the last [`FuncTypeRegistry::reset`].
     fn unwrap_index<Idx>(&self, func_type: Guarded<Idx>) -> Idx
     where
         Idx: ArenaIndex,
     {
         func_type.entity_index(self.engine_idx).unwrap_or_else(|| {
+            if self
+                .retired
+                .iter()
+                .any(|&retired| func_type.entity_index(retired).is_some())
+            {
+                panic!("tried to use function type after Engine::reset")
+            }
             panic!(
                 "encountered foreign entity in func type registry: {}",
                 self.engine_idx.into_usize()
@@ -0,0 +1,140 @@
+use crate::{
+    core::{HostError, Trap},
+    FuncType,
+};
+use core::{fmt, fmt::Display};
+
+/// An internal invariant of the `wasmi` [`Engine`] has been violated.
+///
+/// # Note
+///
+/// Some internal errors are only reported instead of panicking if enabled via
+/// [`Config::set_recoverable_internal_errors`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Config::set_recoverable_internal_errors`]: crate::Config::set_recoverable_internal_errors
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InternalError {
+    /// Tried to call a Wasm function with an invalid or uninitialized compiled function body.
+    InvalidFuncBody,
+    /// Tried to call a function with a number of parameters or results that
+    /// does not match its function type.
+    ///
+    /// # Note
+    ///
+    /// This is always reported instead of panicking.
+    CallArityMismatch,
+    /// Tried to recompile the evicted compiled code of a called Wasm function from within
+    /// a Wasm execution of the same engine.
+    ///
+    /// # Note
+    ///
+    /// This is always reported instead of panicking.
+    /// Read [`Config::set_compiled_code_budget`] for more information.
+    ///
+    /// [`Config::set_compiled_code_budget`]: crate::Config::set_compiled_code_budget
+    EvictedFuncBody,
+}
+
+impl InternalError {
+    /// Returns an error indicating that a called Wasm function has an invalid function body.
+    ///
+    /// # Note
+    ///
+    /// This method exists to indicate that this execution path is cold.
+    #[cold]
+    pub fn invalid_func_body() -> Self {
+        Self::InvalidFuncBody
+    }
+
+    /// Returns an error indicating that a function has been called with a mismatching arity.
+    ///
+    /// # Note
+    ///
+    /// This method exists to indicate that this execution path is cold.
+    #[cold]
+    pub fn call_arity_mismatch() -> Self {
+        Self::CallArityMismatch
+    }
+
+    /// Converts the [`InternalError`] into a [`Trap`] tagged as originating from the engine.
+    ///
+    /// # Note
+    ///
+    /// Only such [`Trap`] are reported as [`Error::Internal`]. This way host
+    /// functions cannot disguise their own errors as internal errors of the engine.
+    ///
+    /// [`Error::Internal`]: crate::Error::Internal
+    pub(crate) fn into_trap(self) -> Trap {
+        Trap::from(EngineInternalError(self))
+    }
+
+    /// Returns an error indicating that the evicted code of a called Wasm function cannot be recompiled.
+    ///
+    /// # Note
+    ///
+    /// This method exists to indicate that this execution path is cold.
+    #[cold]
+    pub fn evicted_func_body() -> Self {
+        Self::EvictedFuncBody
+    }
+
+    /// Ensures that `len_params` and `len_results` match the arity of `func_type`.
+    ///
+    /// # Errors
+    ///
+    /// If the number of parameters or results does not match `func_type`.
+    pub(crate) fn ensure_call_arity(
+        func_type: &FuncType,
+        len_params: usize,
+        len_results: usize,
+    ) -> Result<(), Self> {
+        if func_type.params().len() != len_params || func_type.results().len() != len_results {
+            return Err(Self::call_arity_mismatch());
+        }
+        Ok(())
+    }
+}
+
+impl Display for InternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFuncBody => write!(f, "called Wasm function has an invalid function body"),
+            Self::CallArityMismatch => {
+                write!(
+                    f,
+                    "called function with a mismatching number of parameters or results"
+                )
+            }
+            Self::EvictedFuncBody => write!(
+                f,
+                "cannot recompile evicted code of a called Wasm function during Wasm execution"
+            ),
+        }
+    }
+}
+
+/// An [`InternalError`] raised by the engine itself.
+///
+/// # Note
+///
+/// This type is private to the crate so that only the engine can tag
+/// a [`Trap`] as an [`InternalError`] via [`InternalError::into_trap`].
+#[derive(Debug)]
+pub(crate) struct EngineInternalError(InternalError);
+
+impl EngineInternalError {
+    /// Returns the underlying [`InternalError`].
+    pub(crate) fn error(&self) -> InternalError {
+        self.0
+    }
+}
+
+impl Display for EngineInternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl HostError for EngineInternalError {}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+#[cfg(doc)]
+use crate::{Config, Engine, EngineBackend};
+
+/// Estimated heap memory in bytes held by the compiled code of an [`Engine`].
+///
+/// Returned by [`Engine::estimate_memory_usage`].
+///
+/// # Note
+///
+/// - The estimates are computed from the capacities of the underlying
+///   allocations and do not account for the overhead of the allocator.
+/// - The estimates only grow while compiling additional functions since the
+///   [`Engine`] never deallocates compiled code unless a budget has been set via
+///   [`Config::set_compiled_code_budget`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The bytes held by the compiled function bodies of both engine backends.
+    code_map: usize,
+    /// The bytes held by the deduplicated constant values of the engine.
+    const_pool: usize,
+    /// The bytes held by the function local constant values of the [`EngineBackend::RegisterMachine`].
+    provider_pool: usize,
+}
+
+impl MemoryUsage {
+    /// Creates a new [`MemoryUsage`] from its estimates in bytes.
+    pub(super) fn new(code_map: usize, const_pool: usize, provider_pool: usize) -> Self {
+        Self {
+            code_map,
+            const_pool,
+            provider_pool,
+        }
+    }
+
+    /// Returns the estimated bytes held by the compiled function bodies.
+    ///
+    /// This includes the bytecode instructions and function headers of both engine backends.
+    pub fn code_map(&self) -> usize {
+        self.code_map
+    }
+
+    /// Returns the estimated bytes held by the deduplicated constant values of the [`Engine`].
+    pub fn const_pool(&self) -> usize {
+        self.const_pool
+    }
+
+    /// Returns the estimated bytes held by the function local constant values.
+    ///
+    /// These are the constant values that register-machine instructions refer to
+    /// via their providers. They are stored separately for every compiled function
+    /// and therefore are always `0` when using [`EngineBackend::StackMachine`].
+    pub fn provider_pool(&self) -> usize {
+        self.provider_pool
+    }
+
+    /// Returns the sum of all estimated bytes.
+    pub fn total(&self) -> usize {
+        self.code_map + self.const_pool + self.provider_pool
+    }
+}
+
+/// Returns the number of bytes allocated by `vec`.
+pub(super) fn vec_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
@@ -2,16 +2,27 @@
 
 pub mod bytecode;
 mod cache;
+mod code_cache;
 pub mod code_map;
 mod config;
 mod const_pool;
+#[cfg(feature = "test-utils")]
+mod differential;
 pub mod executor;
 mod func_args;
+mod func_body_bytes;
 mod func_builder;
+mod func_instrs;
+mod func_locations;
 mod func_types;
+mod internal_error;
+mod memory_usage;
+mod reentrancy;
 mod regmach;
+mod reproduce;
 mod resumable;
 pub mod stack;
+mod stepped;
 mod traits;
 mod translator;
 mod trap;
@@ -22,28 +33,40 @@ mod tests;
 #[cfg(test)]
 use self::regmach::bytecode::RegisterSpan;
 
+#[cfg(feature = "test-utils")]
+pub use self::differential::ExecutionOutcome;
 pub use self::{
     bytecode::DropKeep,
     code_map::CompiledFunc,
-    config::{Config, EngineBackend, FuelConsumptionMode},
+    config::{Config, ConstDedup, EngineBackend, FuelConsumptionMode},
+    func_body_bytes::{FuncBodyError, BYTECODE_VERSION},
     func_builder::{Instr, RelativeDepth, TranslationError},
+    func_instrs::{FuncInstr, FuncInstrs},
+    internal_error::InternalError,
+    memory_usage::MemoryUsage,
+    regmach::{InstructionProfile, PeepholeReport},
+    reproduce::{RecordedCall, TrapReproduction},
     resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
     stack::StackLimits,
+    stepped::{StepOutcome, SteppedCall},
     traits::{CallParams, CallResults},
     translator::FuncBuilder,
 };
 use self::{
     bytecode::Instruction,
     cache::InstanceCache,
-    code_map::CodeMap,
+    code_cache::CodeCache,
+    code_map::{CodeMap, InstructionPtr},
     const_pool::{ConstPool, ConstPoolView, ConstRef},
     executor::{execute_wasm, WasmOutcome},
+    func_builder::TranslationErrorInner,
+    func_locations::WasmOffsets,
     func_types::FuncTypeRegistry,
+    reentrancy::{ExecutionGuard, Executions},
     regmach::{
         bytecode::Instruction as Instruction2,
-        code_map::CompiledFuncEntity,
+        code_map::{CompiledFuncEntity, InstructionPtr as InstructionPtr2},
         CodeMap as CodeMap2,
-        FuncLocalConstsIter,
         Stack as Stack2,
     },
     resumable::ResumableCallBase,
@@ -51,22 +74,26 @@ use self::{
     trap::TaggedTrap,
 };
 pub(crate) use self::{
+    code_cache::CodeUnit,
     func_args::{FuncFinished, FuncParams, FuncResults},
+    func_body_bytes::{Fnv1a, ModuleStructure, RestoredFunc},
     func_types::DedupFuncType,
+    internal_error::EngineInternalError,
     translator::ChosenFuncTranslatorAllocations,
 };
 use crate::{
-    core::{Trap, TrapCode},
+    core::{Trap, TrapCode, TrapLocation},
     func::FuncEntity,
+    module::recompile,
     AsContext,
     AsContextMut,
     Func,
     FuncType,
     StoreContextMut,
 };
-use alloc::{sync::Arc, vec::Vec};
-use core::sync::atomic::{AtomicU32, Ordering};
-use spin::{Mutex, RwLock};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use spin::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use wasmi_arena::{ArenaIndex, GuardedEntity};
 use wasmi_core::UntypedValue;
 
@@ -142,11 +169,79 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Sets the [`StackLimits`] used by all future executions of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - The new `limits` take effect starting with the next function execution.
+    ///   Executions that are already in progress, such as resumable calls, keep their limits.
+    /// - Stacks cached by the [`Engine`] are only reallocated if they
+    ///   hold more values than the new maximum value stack height allows.
+    /// - The [`Config`] of the [`Engine`] is not affected.
+    pub fn set_stack_limits(&self, limits: StackLimits) {
+        self.inner.set_stack_limits(limits)
+    }
+
+    /// Returns the estimated heap memory held by the compiled code of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This is useful for embedders that cache many [`Module`](crate::Module)s and
+    /// need to decide when to evict some of them. Since the [`Engine`] only deallocates
+    /// compiled code upon [`Engine::reset`] or to meet the budget set via
+    /// [`Config::set_compiled_code_budget`] the estimation otherwise only grows while
+    /// compiling additional functions.
+    pub fn estimate_memory_usage(&self) -> MemoryUsage {
+        self.inner.estimate_memory_usage()
+    }
+
+    /// Removes all compiled code and function types from the [`Engine`].
+    ///
+    /// This releases the memory held by the compiled functions, the constant
+    /// values and the function types of all Wasm modules compiled so far.
+    /// The [`Config`] and the stack limits of the [`Engine`] are kept.
+    ///
+    /// # Note
+    ///
+    /// All [`Module`](crate::Module)s, instances and functions created before
+    /// the reset as well as the function types of the [`Engine`] are invalidated.
+    /// This includes Wasm modules that are compiled concurrently to the reset.
+    /// Instead of silently executing stale or foreign code using them after
+    /// the reset panics with a message mentioning [`Engine::reset`].
+    /// The same applies to resuming or stepping calls that were suspended
+    /// before the reset, such as resumable calls.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] is executing Wasm on the current thread, e.g. when
+    /// called from within a host function.
+    pub fn reset(&self) {
+        self.inner.reset()
+    }
+
+    /// Takes the [`InstructionProfile`] accumulated by all executions since the last call.
+    ///
+    /// Returns `None` if instruction profiling is disabled via [`Config::profile_instructions`]
+    /// or if no Wasm function has been executed since the last call.
+    pub fn take_instruction_profile(&self) -> Option<InstructionProfile> {
+        self.inner.profile.lock().take()
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
     }
 
+    /// Returns an error if the [`Engine`] is mutated from within one of its own Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// Such mutations would otherwise never succeed since the [`Engine`] resources
+    /// are locked for the duration of its Wasm executions.
+    pub(crate) fn ensure_not_executing(&self) -> Result<(), TranslationError> {
+        self.inner.ensure_not_executing()
+    }
+
     /// Allocates a new function type to the [`Engine`].
     pub(super) fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
         self.inner.alloc_func_type(func_type)
@@ -170,6 +265,7 @@ impl Engine {
     /// # Errors
     ///
     /// If too many constant values have been allocated for the [`Engine`] this way.
+    #[cfg(test)]
     pub(super) fn alloc_const(
         &self,
         value: impl Into<UntypedValue>,
@@ -193,6 +289,13 @@ impl Engine {
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
     ///
+    /// The [`Instruction::ConstRef`] of `instrs` refer to the function local `consts`
+    /// and are mapped to the [`ConstRef`] of the [`Engine`] upon initialization.
+    ///
+    /// # Errors
+    ///
+    /// If too many constant values have been allocated for the [`Engine`].
+    ///
     /// # Panics
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
@@ -202,12 +305,14 @@ impl Engine {
         func: CompiledFunc,
         len_locals: usize,
         local_stack_height: usize,
+        consts: &ConstPool,
         instrs: I,
-    ) where
+    ) -> Result<(), TranslationError>
+    where
         I: IntoIterator<Item = Instruction>,
     {
         self.inner
-            .init_func(func, len_locals, local_stack_height, instrs)
+            .init_func(func, len_locals, local_stack_height, consts, instrs)
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
@@ -216,18 +321,135 @@ impl Engine {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    fn init_func_2<I>(
+    fn init_func_2<I, C>(
         &self,
         func: CompiledFunc,
+        func_index: u32,
         len_registers: u16,
         len_results: u16,
-        func_locals: FuncLocalConstsIter,
+        func_locals: C,
         instrs: I,
     ) where
         I: IntoIterator<Item = Instruction2>,
+        C: IntoIterator<Item = UntypedValue>,
     {
+        self.inner.init_func_2(
+            func,
+            func_index,
+            len_registers,
+            len_results,
+            func_locals,
+            instrs,
+        )
+    }
+
+    /// Sets the `messages` of traps folded at compilation time for the [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    fn set_trap_messages_2<M>(&self, func: CompiledFunc, messages: M)
+    where
+        M: IntoIterator<Item = (u32, Box<str>)>,
+    {
+        self.inner.set_trap_messages_2(func, messages)
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`] of the Wasm function at `func_index`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    fn set_wasm_offsets(
+        &self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_offsets: &mut WasmOffsets,
+    ) {
+        self.inner.set_wasm_offsets(func, func_index, wasm_offsets)
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    fn set_wasm_offsets_2(&self, func: CompiledFunc, wasm_offsets: &mut WasmOffsets) {
+        self.inner.set_wasm_offsets_2(func, wasm_offsets)
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] used to locate its traps.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub(crate) fn set_func_name(&self, func: CompiledFunc, name: &str) {
+        self.inner.set_func_name(func, name)
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] used to locate its traps.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub(crate) fn set_func_name_2(&self, func: CompiledFunc, name: &str) {
+        self.inner.set_func_name_2(func, name)
+    }
+
+    /// Initializes the uninitialized [`CompiledFunc`] with the next restored function body.
+    ///
+    /// The `wasm_hash` is the fingerprint of the Wasm module bytes up to and including
+    /// the function body of `func` and `structure` is the [`ModuleStructure`] of its Wasm
+    /// module. Both are recorded for [`Engine::serialize_code`].
+    ///
+    /// Returns `true` if the next function body restored via [`Engine::deserialize_code`]
+    /// belongs to `func`, the Wasm function at `func_index`, `wasm_hash` and `structure`
+    /// and `func` has been initialized with it. Otherwise returns `false` and `func` must
+    /// be validated and translated.
+    pub(super) fn restore_func_2(
+        &self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_hash: u64,
+        structure: &ModuleStructure,
+    ) -> bool {
         self.inner
-            .init_func_2(func, len_registers, len_results, func_locals, instrs)
+            .restore_func_2(func, func_index, wasm_hash, structure)
+    }
+
+    /// Registers the compiled `funcs` of the Wasm module `wasm` as a new [`CodeUnit`].
+    ///
+    /// Afterwards evicts the compiled code of the least recently used other Wasm modules
+    /// while the budget set via [`Config::set_compiled_code_budget`] is exceeded.
+    pub(crate) fn register_code_unit(
+        &self,
+        wasm: Arc<[u8]>,
+        funcs: Arc<[CompiledFunc]>,
+    ) -> CodeUnit {
+        self.inner.register_code_unit(wasm, funcs)
+    }
+
+    /// Recompiles the compiled code of `unit` from its Wasm module bytes if it has been evicted.
+    ///
+    /// Afterwards evicts the compiled code of the least recently used Wasm modules
+    /// except for `keep` while the budget set via [`Config::set_compiled_code_budget`]
+    /// is exceeded.
+    ///
+    /// # Panics
+    ///
+    /// If the Wasm module bytes of `unit` fail to compile again.
+    fn recompile_code_unit(&self, unit: CodeUnit, keep: &[CodeUnit]) {
+        // Note: Concurrent recompilations of the same `unit` would otherwise
+        //       initialize its compiled functions twice.
+        let _recompilation = self.inner.recompilation.lock();
+        let evicted = match self.inner.res.read().code_cache.evicted_code(unit) {
+            Some(evicted) => evicted,
+            None => return,
+        };
+        recompile(self, &evicted.wasm[..], &evicted.funcs[..]).unwrap_or_else(|error| {
+            panic!("failed to recompile the evicted code of a Wasm module: {error}")
+        });
+        self.inner.recompiled(unit, &evicted.funcs[..], keep);
     }
 
     /// Resolves the [`CompiledFuncEntity`] for [`CompiledFunc`] and applies `f` to it.
@@ -242,6 +464,24 @@ impl Engine {
         self.inner.resolve_func_2(func, f)
     }
 
+    /// Returns the [`TrapLocation`] and the [`Instruction2`] pointed to by `ip`.
+    ///
+    /// Returns `None` if `ip` does not point to an instruction of the [`Engine`]
+    /// or if the Wasm binary offsets of its compiled function are not recorded.
+    pub(super) fn locate_instr_2(
+        &self,
+        ip: InstructionPtr2,
+    ) -> Option<(TrapLocation, Instruction2)> {
+        self.inner.locate_instr_2(ip)
+    }
+
+    /// Returns the index of the Wasm function within its module that `ip` points into.
+    ///
+    /// Returns `None` if `ip` does not point to an instruction of the [`Engine`].
+    pub(crate) fn func_index_at_2(&self, ip: InstructionPtr2) -> Option<u32> {
+        self.inner.func_index_at_2(ip)
+    }
+
     /// Resolves the [`CompiledFunc`] to the underlying `wasmi` bytecode instructions.
     ///
     /// # Note
@@ -298,6 +538,23 @@ impl Engine {
         self.inner.get_func_const_2(func, index)
     }
 
+    /// Returns the number of registers used by the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// This API is intended for unit testing purposes and shall not be used
+    /// outside of this context. The function bodies are intended to be data
+    /// private to the `wasmi` interpreter.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`CompiledFunc`] is invalid for the [`Engine`].
+    /// - If register machine bytecode translation is disabled.
+    #[cfg(test)]
+    fn get_func_len_registers_2(&self, func: CompiledFunc) -> u16 {
+        self.inner.get_func_len_registers_2(func)
+    }
+
     /// Executes the given [`Func`] with parameters `params`.
     ///
     /// Stores the execution result into `results` upon a successful execution.
@@ -408,6 +665,77 @@ impl Engine {
     pub(crate) fn recycle_stack(&self, stack: Stack) {
         self.inner.recycle_stack(stack)
     }
+
+    /// Initializes the execution of the given [`Func`] with the given `params` step by step.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] does not use the register-machine backend.
+    ///
+    /// # Errors
+    ///
+    /// - If the stack overflows while initializing the call.
+    /// - When encountering a host trap during the execution of a host `func`.
+    pub(crate) fn init_stepped_func<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        len_results: usize,
+    ) -> Result<Stack2, Trap> {
+        self.inner.init_stepped_func(ctx, func, params, len_results)
+    }
+
+    /// Executes exactly one instruction of the stepped execution held by `stack`.
+    ///
+    /// Returns `true` once the root function has returned.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during execution.
+    pub(crate) fn step_func<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        stack: &mut Stack2,
+    ) -> Result<bool, Trap> {
+        self.inner.step_func(ctx, stack)
+    }
+
+    /// Writes the results of the finished stepped execution held by `stack` into `results`.
+    pub(crate) fn write_stepped_results<Results>(
+        &self,
+        stack: &mut Stack2,
+        func_type: DedupFuncType,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Trap>
+    where
+        Results: CallResults,
+    {
+        self.inner.write_stepped_results(stack, func_type, results)
+    }
+
+    /// Recycles the register-machine `stack` of a stepped execution.
+    pub(crate) fn recycle_stack_2(&self, stack: Stack2) {
+        self.inner.recycle_stack_2(stack)
+    }
+}
+
+/// Returns the [`Trap`] for a called Wasm function with an invalid compiled function body.
+///
+/// # Note
+///
+/// The executors hand such Wasm functions back to the [`EngineExecutor`]
+/// the same way as host functions upon nested calls.
+///
+/// # Panics
+///
+/// If [`Config::set_recoverable_internal_errors`] is disabled.
+#[cold]
+fn invalid_func_body(config: &Config) -> Trap {
+    if !config.get_recoverable_internal_errors() {
+        panic!("{}", InternalError::invalid_func_body())
+    }
+    InternalError::invalid_func_body().into_trap()
 }
 
 /// The internal state of the `wasmi` [`Engine`].
@@ -423,6 +751,18 @@ pub struct EngineInner {
     /// operate on. Therefore a Wasm engine is required to provide stacks and
     /// ideally recycles old ones since creation of a new stack is rather expensive.
     stacks: Mutex<EngineStacks>,
+    /// The instruction counters accumulated if enabled via [`Config::profile_instructions`].
+    profile: Mutex<Option<InstructionProfile>>,
+    /// The in-progress Wasm executions of the engine.
+    ///
+    /// Used to detect reentrant mutations of the engine by host functions.
+    executions: Executions,
+    /// The number of alive [`SteppedCall`] of the engine.
+    ///
+    /// Compiled code is not evicted while a [`SteppedCall`] refers to it in between its steps.
+    stepped_calls: AtomicUsize,
+    /// Serializes the recompilation of evicted compiled code.
+    recompilation: Mutex<()>,
 }
 
 /// The engine's stacks for reuse.
@@ -471,16 +811,37 @@ impl EngineStacks {
         }
     }
 
+    /// Updates the [`StackLimits`] for all current and future engine stacks.
+    ///
+    /// Reusable stacks are kept unless they hold more values than the new limits allow.
+    pub fn set_limits(&mut self, limits: StackLimits) {
+        self.limits = limits;
+        self.stacks.retain_mut(|stack| stack.set_limits(limits));
+        self.stacks2.retain_mut(|stack| stack.set_limits(limits));
+    }
+
     /// Disose and recycle the `stack`.
-    pub fn recycle(&mut self, stack: Stack) {
-        if !stack.is_empty() && self.stacks.len() < self.keep {
+    ///
+    /// # Note
+    ///
+    /// - The `stack` is reset so that no call frame outlives its execution.
+    /// - The `stack` is dropped if it cannot respect the current [`StackLimits`].
+    pub fn recycle(&mut self, mut stack: Stack) {
+        if !stack.is_empty() && self.stacks.len() < self.keep && stack.set_limits(self.limits) {
+            stack.reset();
             self.stacks.push(stack);
         }
     }
 
     /// Disose and recycle the `stack`.
-    pub fn recycle_2(&mut self, stack: Stack2) {
-        if !stack.is_empty() && self.stacks2.len() < self.keep {
+    ///
+    /// # Note
+    ///
+    /// - The `stack` is reset so that no call frame outlives its execution.
+    /// - The `stack` is dropped if it cannot respect the current [`StackLimits`].
+    pub fn recycle_2(&mut self, mut stack: Stack2) {
+        if !stack.is_empty() && self.stacks2.len() < self.keep && stack.set_limits(self.limits) {
+            stack.reset();
             self.stacks2.push(stack);
         }
     }
@@ -491,8 +852,12 @@ impl EngineInner {
     fn new(config: &Config) -> Self {
         Self {
             config: *config,
-            res: RwLock::new(EngineResources::new()),
+            res: RwLock::new(EngineResources::new(config)),
             stacks: Mutex::new(EngineStacks::new(config)),
+            profile: Mutex::new(None),
+            executions: Executions::default(),
+            stepped_calls: AtomicUsize::new(0),
+            recompilation: Mutex::new(()),
         }
     }
 
@@ -501,9 +866,113 @@ impl EngineInner {
         &self.config
     }
 
+    /// Read-locks the [`EngineResources`] for the duration of a Wasm execution.
+    ///
+    /// The returned [`ExecutionGuard`] must be kept alive until the execution ends.
+    fn res_for_execution(&self) -> (RwLockReadGuard<'_, EngineResources>, ExecutionGuard<'_>) {
+        (self.res.read(), self.executions.enter())
+    }
+
+    /// Read-locks the [`EngineResources`] for the duration of a Wasm execution using `ctx`.
+    ///
+    /// Recompiles the evicted code of the Wasm modules instantiated in the [`Store`] of
+    /// `ctx` first since the execution can only call functions of these Wasm modules.
+    ///
+    /// # Errors
+    ///
+    /// If evicted code must be recompiled from within one of the Wasm executions
+    /// of the [`EngineInner`].
+    fn res_for_execution_in<T>(
+        &self,
+        ctx: &StoreContextMut<T>,
+    ) -> Result<(RwLockReadGuard<'_, EngineResources>, ExecutionGuard<'_>), Trap> {
+        let store = &ctx.as_context().store;
+        let units = store.inner.code_units();
+        loop {
+            let (res, marker) = self.res_for_execution();
+            let unit = match res.code_cache.use_units(units) {
+                Some(unit) => unit,
+                None => return Ok((res, marker)),
+            };
+            drop(marker);
+            drop(res);
+            if self.executions.is_reentrant() {
+                return Err(InternalError::evicted_func_body().into_trap());
+            }
+            store.engine().recompile_code_unit(unit, units);
+        }
+    }
+
+    /// Returns an error if mutating the [`EngineInner`] might never succeed.
+    ///
+    /// This is the case if the [`EngineInner`] is mutated from within one of its own
+    /// Wasm executions, for example by a host function that compiles a Wasm module.
+    fn ensure_not_executing(&self) -> Result<(), TranslationError> {
+        if self.executions.is_reentrant() {
+            return Err(TranslationError::new(
+                TranslationErrorInner::ReentrantEngineMutation,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write-locks the [`EngineResources`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`EngineInner`] is mutated from within one of its own Wasm executions
+    /// since acquiring the write lock would otherwise never succeed.
+    fn res_mut(&self) -> RwLockWriteGuard<'_, EngineResources> {
+        loop {
+            if let Some(res) = self.res.try_write() {
+                return res;
+            }
+            if self.executions.is_reentrant() {
+                panic!("{}", reentrancy::REENTRANT_MUTATION)
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Removes all compiled code and function types from the [`EngineInner`].
+    fn reset(&self) {
+        self.res_mut().reset(&self.config);
+    }
+
+    /// Checks that the compiled function body of `func` is valid for the engine `backend`.
+    ///
+    /// This check is only performed if [`Config::set_recoverable_internal_errors`] is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If `func` is a Wasm function with an invalid or uninitialized compiled function body.
+    fn check_func_body<T>(
+        &self,
+        res: &EngineResources,
+        ctx: &StoreContextMut<T>,
+        func: &Func,
+        backend: EngineBackend,
+    ) -> Result<(), Trap> {
+        if !self.config().get_recoverable_internal_errors() {
+            return Ok(());
+        }
+        let func_body = match ctx.as_context().store.inner.resolve_func(func) {
+            FuncEntity::Wasm(wasm_func) => wasm_func.func_body(),
+            FuncEntity::Host(_) => return Ok(()),
+        };
+        let is_initialized = match backend {
+            EngineBackend::StackMachine => res.code_map.is_initialized(func_body),
+            EngineBackend::RegisterMachine => res.code_map_2.is_initialized(func_body),
+        };
+        if !is_initialized {
+            return Err(InternalError::invalid_func_body().into_trap());
+        }
+        Ok(())
+    }
+
     /// Allocates a new function type to the [`EngineInner`].
     fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
-        self.res.write().func_types.alloc_func_type(func_type)
+        self.res_mut().func_types.alloc_func_type(func_type)
     }
 
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
@@ -524,22 +993,23 @@ impl EngineInner {
     /// # Errors
     ///
     /// If too many constant values have been allocated for the [`EngineInner`] this way.
+    #[cfg(test)]
     fn alloc_const(&self, value: UntypedValue) -> Result<ConstRef, TranslationError> {
-        self.res.write().const_pool.alloc(value)
+        self.res_mut().const_pool.alloc(value)
     }
 
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`EngineInner`].
     ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
     fn alloc_func(&self) -> CompiledFunc {
-        self.res.write().code_map.alloc_func()
+        self.res_mut().code_map.alloc_func()
     }
 
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`EngineInner`].
     ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
     fn alloc_func_2(&self) -> CompiledFunc {
-        self.res.write().code_map_2.alloc_func()
+        self.res_mut().code_map_2.alloc_func()
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`EngineInner`].
@@ -553,14 +1023,24 @@ impl EngineInner {
         func: CompiledFunc,
         len_locals: usize,
         local_stack_height: usize,
+        consts: &ConstPool,
         instrs: I,
-    ) where
+    ) -> Result<(), TranslationError>
+    where
         I: IntoIterator<Item = Instruction>,
     {
-        self.res
-            .write()
-            .code_map
-            .init_func(func, len_locals, local_stack_height, instrs)
+        let mut res = self.res_mut();
+        let crefs = consts
+            .values()
+            .map(|value| res.const_pool.alloc(value))
+            .collect::<Result<Vec<ConstRef>, _>>()?;
+        let instrs = instrs.into_iter().map(|instr| match instr {
+            Instruction::ConstRef(cref) => Instruction::ConstRef(crefs[cref.to_usize()]),
+            instr => instr,
+        });
+        res.code_map
+            .init_func(func, len_locals, local_stack_height, instrs);
+        Ok(())
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`EngineInner`].
@@ -569,20 +1049,132 @@ impl EngineInner {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    fn init_func_2<I>(
+    fn init_func_2<I, C>(
         &self,
         func: CompiledFunc,
+        func_index: u32,
         len_registers: u16,
         len_results: u16,
-        func_locals: FuncLocalConstsIter,
+        func_locals: C,
         instrs: I,
     ) where
         I: IntoIterator<Item = Instruction2>,
+        C: IntoIterator<Item = UntypedValue>,
     {
-        self.res
-            .write()
+        self.res_mut().code_map_2.init_func(
+            func,
+            func_index,
+            len_registers,
+            len_results,
+            func_locals,
+            instrs,
+        )
+    }
+
+    /// Sets the `messages` of traps folded at compilation time for the [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    fn set_trap_messages_2<M>(&self, func: CompiledFunc, messages: M)
+    where
+        M: IntoIterator<Item = (u32, Box<str>)>,
+    {
+        self.res_mut().code_map_2.set_trap_messages(func, messages)
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`] of the Wasm function at `func_index`.
+    fn set_wasm_offsets(
+        &self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_offsets: &mut WasmOffsets,
+    ) {
+        self.res_mut()
+            .code_map
+            .set_wasm_offsets(func, func_index, wasm_offsets)
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`].
+    fn set_wasm_offsets_2(&self, func: CompiledFunc, wasm_offsets: &mut WasmOffsets) {
+        self.res_mut()
             .code_map_2
-            .init_func(func, len_registers, len_results, func_locals, instrs)
+            .set_wasm_offsets(func, wasm_offsets)
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] used to locate its traps.
+    fn set_func_name(&self, func: CompiledFunc, name: &str) {
+        self.res_mut().code_map.set_func_name(func, name)
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] used to locate its traps.
+    fn set_func_name_2(&self, func: CompiledFunc, name: &str) {
+        self.res_mut().code_map_2.set_func_name(func, name)
+    }
+
+    /// Initializes the uninitialized [`CompiledFunc`] with the next restored function body.
+    ///
+    /// Returns `true` if `func` has been initialized.
+    fn restore_func_2(
+        &self,
+        func: CompiledFunc,
+        func_index: u32,
+        wasm_hash: u64,
+        structure: &ModuleStructure,
+    ) -> bool {
+        let mut res = self.res_mut();
+        res.code_map_2
+            .set_wasm_origin(func, wasm_hash, structure.clone());
+        let is_next = matches!(
+            res.restored_funcs_2.front(),
+            Some(restored) if restored.is_for(func, func_index, wasm_hash, structure)
+        );
+        if !is_next {
+            return false;
+        }
+        let restored = res
+            .restored_funcs_2
+            .pop_front()
+            .expect("the next restored function must exist");
+        restored.init(&mut res.code_map_2);
+        true
+    }
+
+    /// Registers the compiled `funcs` of the Wasm module `wasm` as a new [`CodeUnit`].
+    fn register_code_unit(&self, wasm: Arc<[u8]>, funcs: Arc<[CompiledFunc]>) -> CodeUnit {
+        let mut res = self.res_mut();
+        let footprint = res.code_map_2.funcs_memory_usage(&funcs[..]);
+        let unit = res.code_cache.register(wasm, funcs, footprint);
+        self.evict_lru(&mut res, &[unit]);
+        unit
+    }
+
+    /// Marks the evicted `unit` with its compiled `funcs` as recompiled.
+    fn recompiled(&self, unit: CodeUnit, funcs: &[CompiledFunc], keep: &[CodeUnit]) {
+        let mut res = self.res_mut();
+        let footprint = res.code_map_2.funcs_memory_usage(funcs);
+        res.code_cache.recompiled(unit, footprint);
+        self.evict_lru(&mut res, keep);
+    }
+
+    /// Evicts the compiled code of the least recently used Wasm modules except for `keep`
+    /// while the budget set via [`Config::set_compiled_code_budget`] is exceeded.
+    ///
+    /// Does nothing while a [`SteppedCall`] is alive.
+    fn evict_lru(&self, res: &mut EngineResources, keep: &[CodeUnit]) {
+        let budget = match self.config().get_compiled_code_budget() {
+            Some(budget) => budget,
+            None => return,
+        };
+        if self.stepped_calls.load(Ordering::Acquire) != 0 {
+            return;
+        }
+        let EngineResources {
+            code_map_2,
+            code_cache,
+            ..
+        } = res;
+        code_cache.evict_lru(budget, keep, |func| code_map_2.evict_func(func));
     }
 
     /// Resolves the [`CompiledFuncEntity`] for [`CompiledFunc`] and applies `f` to it.
@@ -597,6 +1189,23 @@ impl EngineInner {
         f(self.res.read().code_map_2.get(func))
     }
 
+    /// Returns the [`TrapLocation`] and the [`Instruction2`] pointed to by `ip`.
+    fn locate_instr_2(&self, ip: InstructionPtr2) -> Option<(TrapLocation, Instruction2)> {
+        let res = self.res.read();
+        let location = res.code_map_2.trap_location(ip)?;
+        let (entity, index) = res.code_map_2.locate(ip)?;
+        Some((location, entity.instrs()[index]))
+    }
+
+    /// Returns the index of the Wasm function within its module that `ip` points into.
+    fn func_index_at_2(&self, ip: InstructionPtr2) -> Option<u32> {
+        self.res
+            .read()
+            .code_map_2
+            .locate(ip)
+            .map(|(entity, _)| entity.func_index())
+    }
+
     #[cfg(test)]
     fn resolve_instr(&self, func_body: CompiledFunc, index: usize) -> Option<Instruction> {
         self.res
@@ -617,6 +1226,11 @@ impl EngineInner {
             .copied()
     }
 
+    #[cfg(test)]
+    fn get_func_len_registers_2(&self, func: CompiledFunc) -> u16 {
+        self.res.read().code_map_2.get(func).len_registers()
+    }
+
     #[cfg(test)]
     fn get_func_const_2(&self, func: CompiledFunc, index: usize) -> Option<UntypedValue> {
         // Function local constants are stored in reverse order of their indices since
@@ -674,7 +1288,8 @@ impl EngineInner {
     where
         Results: CallResults,
     {
-        let res = self.res.read();
+        let (res, _marker) = self.res_for_execution();
+        self.check_func_body(&res, &ctx, func, EngineBackend::StackMachine)?;
         let mut stack = self.stacks.lock().reuse_or_new();
         let results = EngineExecutor::new(&res, &mut stack)
             .execute_func(ctx, func, params, results)
@@ -693,8 +1308,10 @@ impl EngineInner {
     where
         Results: CallResults,
     {
-        let res = self.res.read();
+        let (res, _marker) = self.res_for_execution();
+        self.check_func_body(&res, &ctx, func, EngineBackend::StackMachine)?;
         let mut stack = self.stacks.lock().reuse_or_new();
+        stack.resets = res.resets;
         let results = EngineExecutor::new(&res, &mut stack).execute_func(
             ctx.as_context_mut(),
             func,
@@ -733,7 +1350,8 @@ impl EngineInner {
     where
         Results: CallResults,
     {
-        let res = self.res.read();
+        let (res, _marker) = self.res_for_execution();
+        res.assert_not_reset_since(invocation.stack.resets);
         let host_func = invocation.host_func();
         let results = EngineExecutor::new(&res, &mut invocation.stack)
             .resume_func(ctx, host_func, params, results);
@@ -759,6 +1377,21 @@ impl EngineInner {
     fn recycle_stack(&self, stack: Stack) {
         self.stacks.lock().recycle(stack);
     }
+
+    /// Returns the estimated heap memory held by the compiled code of the [`EngineInner`].
+    fn estimate_memory_usage(&self) -> MemoryUsage {
+        let res = self.res.read();
+        MemoryUsage::new(
+            res.code_map.memory_usage() + res.code_map_2.memory_usage(),
+            res.const_pool.memory_usage(),
+            res.code_map_2.consts_memory_usage(),
+        )
+    }
+
+    /// Sets the [`StackLimits`] used by all future executions of the [`EngineInner`].
+    fn set_stack_limits(&self, limits: StackLimits) {
+        self.stacks.lock().set_limits(limits);
+    }
 }
 
 /// Engine resources that are immutable during function execution.
@@ -770,6 +1403,10 @@ pub struct EngineResources {
     code_map: CodeMap,
     /// Stores information about all compiled functions.
     code_map_2: CodeMap2,
+    /// Compiled functions restored via [`Engine::deserialize_code`] that are not yet in use.
+    restored_funcs_2: VecDeque<RestoredFunc>,
+    /// The compiled functions of Wasm modules that can be evicted and recompiled.
+    code_cache: CodeCache,
     /// A pool of reusable, deduplicated constant values.
     const_pool: ConstPool,
     /// Deduplicated function types.
@@ -779,19 +1416,48 @@ pub struct EngineResources {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: FuncTypeRegistry,
+    /// The number of times the [`EngineResources`] have been reset via [`Engine::reset`].
+    resets: u32,
 }
 
 impl EngineResources {
     /// Creates a new [`EngineResources`].
-    fn new() -> Self {
+    fn new(config: &Config) -> Self {
         let engine_idx = EngineIdx::new();
         Self {
             code_map: CodeMap::default(),
             code_map_2: CodeMap2::default(),
-            const_pool: ConstPool::default(),
+            restored_funcs_2: VecDeque::new(),
+            code_cache: CodeCache::default(),
+            const_pool: ConstPool::new(config.get_const_dedup()),
             func_types: FuncTypeRegistry::new(engine_idx),
+            resets: 0,
         }
     }
+
+    /// Removes all compiled code and function types from the [`EngineResources`].
+    fn reset(&mut self, config: &Config) {
+        self.code_map.reset();
+        self.code_map_2.reset();
+        self.restored_funcs_2 = VecDeque::new();
+        self.code_cache.reset();
+        self.const_pool = ConstPool::new(config.get_const_dedup());
+        self.func_types.reset();
+        self.resets = self.resets.wrapping_add(1);
+    }
+
+    /// Asserts that no [`Engine::reset`] happened since the [`EngineResources`] were reset `resets` times.
+    ///
+    /// # Panics
+    ///
+    /// If [`Engine::reset`] has been called since, for example while an execution was suspended.
+    #[track_caller]
+    fn assert_not_reset_since(&self, resets: u32) {
+        assert_eq!(
+            self.resets, resets,
+            "tried to resume a suspended execution after Engine::reset"
+        );
+    }
 }
 
 /// The internal state of the `wasmi` engine.
@@ -829,7 +1495,19 @@ impl<'engine> EngineExecutor<'engine> {
         Results: CallResults,
     {
         self.stack.reset();
-        self.stack.values.extend(params.call_params());
+        let params = params.call_params();
+        let func_type = *ctx.as_context().store.inner.resolve_func(func).ty_dedup();
+        InternalError::ensure_call_arity(
+            self.res.func_types.resolve_func_type(&func_type),
+            params.len(),
+            results.len_results(),
+        )
+        .map_err(InternalError::into_trap)?;
+        if params.len() != 0 {
+            // Note: Niladic functions such as `_start` are commonly called
+            //       and do not need to feed any parameters to the value stack.
+            self.stack.values.extend(params);
+        }
         match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
                 self.stack
@@ -846,7 +1524,7 @@ impl<'engine> EngineExecutor<'engine> {
                 )?;
             }
         };
-        let results = self.write_results_back(results);
+        let results = self.write_results_back(results)?;
         Ok(results)
     }
 
@@ -878,7 +1556,7 @@ impl<'engine> EngineExecutor<'engine> {
             "a frame must be on the call stack upon resumption"
         );
         self.execute_wasm_func(ctx.as_context_mut())?;
-        let results = self.write_results_back(results);
+        let results = self.write_results_back(results)?;
         Ok(results)
     }
 
@@ -888,15 +1566,22 @@ impl<'engine> EngineExecutor<'engine> {
     ///
     /// The value stack is empty after this operation.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// - If the `results` buffer length does not match the remaining amount of stack values.
+    /// If the `results` buffer length does not match the remaining amount of stack values.
     #[inline]
-    fn write_results_back<Results>(&mut self, results: Results) -> <Results as CallResults>::Results
+    fn write_results_back<Results>(
+        &mut self,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Trap>
     where
         Results: CallResults,
     {
-        results.call_results(self.stack.values.drain())
+        let values = self.stack.values.drain();
+        if values.len() != results.len_results() {
+            return Err(InternalError::call_arity_mismatch().into_trap());
+        }
+        Ok(results.call_results(values))
     }
 
     /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
@@ -922,7 +1607,9 @@ impl<'engine> EngineExecutor<'engine> {
                 } => {
                     let func = host_func;
                     let host_func = match ctx.as_context().store.inner.resolve_func(func) {
-                        FuncEntity::Wasm(_) => unreachable!("`func` must be a host function"),
+                        FuncEntity::Wasm(_) => {
+                            return Err(TaggedTrap::Wasm(invalid_func_body(ctx.engine().config())))
+                        }
                         FuncEntity::Host(host_func) => *host_func,
                     };
                     let result = self.stack.call_host(
@@ -968,12 +1655,20 @@ impl<'engine> EngineExecutor<'engine> {
     ) -> Result<WasmOutcome, Trap> {
         /// Converts a [`TrapCode`] into a [`Trap`].
         ///
+        /// Attaches the [`TrapLocation`] of the trapping instruction at `ip` if recorded.
+        ///
         /// This function exists for performance reasons since its `#[cold]`
         /// annotation has severe effects on performance.
+        ///
+        /// [`TrapLocation`]: crate::core::TrapLocation
         #[inline]
         #[cold]
-        fn make_trap(code: TrapCode) -> Trap {
-            code.into()
+        fn make_trap(code_map: &CodeMap, code: TrapCode, ip: InstructionPtr) -> Trap {
+            let trap = Trap::from(code);
+            match code_map.trap_location(ip) {
+                Some(location) => trap.with_location(location),
+                None => trap,
+            }
         }
 
         let (store_inner, mut resource_limiter) = ctx.store.store_inner_and_resource_limiter_ref();
@@ -991,6 +1686,6 @@ impl<'engine> EngineExecutor<'engine> {
             const_pool,
             &mut resource_limiter,
         )
-        .map_err(make_trap)
+        .map_err(|(code, ip)| make_trap(code_map, code, ip))
     }
 }
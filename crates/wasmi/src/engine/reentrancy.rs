@@ -0,0 +1,144 @@
+//! Detection of reentrant mutations of an [`Engine`] during Wasm execution.
+//!
+//! While executing Wasm the resources of an [`Engine`] are read-locked.
+//! A host function that tries to mutate the very same [`Engine`], for example
+//! by compiling a new Wasm module or by creating a new host function, would
+//! therefore wait forever for the write lock. Instead this situation is
+//! detected using the in-progress Wasm executions of the [`Engine`] on the
+//! current thread and reported via an error or, for infallible operations,
+//! a descriptive panic.
+//!
+//! [`Engine`]: super::Engine
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The number of nested Wasm executions per thread whose [`Executions`] are tracked.
+///
+/// # Note
+///
+/// Deeper nested Wasm executions are only counted by their [`Executions`].
+#[cfg(feature = "std")]
+const MAX_TRACKED_DEPTH: usize = 8;
+
+/// The in-progress Wasm executions of the current thread.
+#[cfg(feature = "std")]
+struct ThreadExecutions {
+    /// The number of in-progress Wasm executions of all engines on the current thread.
+    depth: Cell<usize>,
+    /// The addresses of the [`Executions`] of the outermost in-progress Wasm executions.
+    ///
+    /// # Note
+    ///
+    /// The first `depth` entries are valid up to [`MAX_TRACKED_DEPTH`].
+    tracked: [Cell<usize>; MAX_TRACKED_DEPTH],
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The in-progress Wasm executions of the current thread.
+    static EXECUTING: ThreadExecutions = const {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const UNTRACKED: Cell<usize> = Cell::new(0);
+        ThreadExecutions {
+            depth: Cell::new(0),
+            tracked: [UNTRACKED; MAX_TRACKED_DEPTH],
+        }
+    };
+}
+
+/// The panic message upon reentrant mutation of an engine by an infallible operation.
+pub const REENTRANT_MUTATION: &str = "tried to mutate an engine from within one of its own \
+    Wasm executions: host functions must not compile modules or create functions \
+    using the engine that is executing them";
+
+/// Tracks the in-progress Wasm executions of an [`Engine`].
+///
+/// [`Engine`]: super::Engine
+#[derive(Debug, Default)]
+pub struct Executions {
+    /// The number of in-progress Wasm executions on all threads.
+    count: AtomicUsize,
+}
+
+impl Executions {
+    /// Returns the address identifying `self` on the current thread.
+    #[cfg(feature = "std")]
+    fn address(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// Marks a Wasm execution as in-progress until the returned [`ExecutionGuard`] is dropped.
+    pub fn enter(&self) -> ExecutionGuard<'_> {
+        self.count.fetch_add(1, Ordering::AcqRel);
+        #[cfg(feature = "std")]
+        EXECUTING.with(|executing| {
+            let depth = executing.depth.get();
+            if let Some(tracked) = executing.tracked.get(depth) {
+                tracked.set(self.address());
+            }
+            executing.depth.set(depth + 1);
+        });
+        ExecutionGuard {
+            executions: self,
+            not_send: PhantomData,
+        }
+    }
+
+    /// Returns `true` if mutating the [`Engine`] might never succeed.
+    ///
+    /// # Note
+    ///
+    /// With the `std` crate feature this is the case if the current thread
+    /// is executing Wasm using the [`Engine`]. Executions of the [`Engine`]
+    /// on other threads eventually finish and release the [`Engine`].
+    /// Without the `std` crate feature, or if the current thread is nested deeper
+    /// than [`MAX_TRACKED_DEPTH`] Wasm executions, the current thread cannot be
+    /// identified and this is conservatively the case while the [`Engine`] executes Wasm at all.
+    ///
+    /// [`Engine`]: super::Engine
+    pub fn is_reentrant(&self) -> bool {
+        if self.count.load(Ordering::Acquire) == 0 {
+            return false;
+        }
+        #[cfg(feature = "std")]
+        let executing = EXECUTING.with(|executing| {
+            let depth = executing.depth.get();
+            depth > MAX_TRACKED_DEPTH
+                || executing.tracked[..depth]
+                    .iter()
+                    .any(|tracked| tracked.get() == self.address())
+        });
+        #[cfg(not(feature = "std"))]
+        let executing = true;
+        executing
+    }
+}
+
+/// Marks a Wasm execution of an [`Engine`] as in-progress while alive.
+///
+/// [`Engine`]: super::Engine
+#[derive(Debug)]
+pub struct ExecutionGuard<'a> {
+    /// The executions of the [`Engine`] to which the guarded execution belongs.
+    ///
+    /// [`Engine`]: super::Engine
+    executions: &'a Executions,
+    /// Keeps the guard on the thread of its Wasm execution so that the executions
+    /// of a thread are strictly nested.
+    not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ExecutionGuard<'_> {
+    fn drop(&mut self) {
+        // Note: Wasm executions on a thread are strictly nested so that the guard
+        //       of the innermost execution is always dropped first.
+        #[cfg(feature = "std")]
+        EXECUTING.with(|executing| executing.depth.set(executing.depth.get() - 1));
+        self.executions.count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -0,0 +1,940 @@
+//! Binary encoding of register machine bytecode.
+//!
+//! # Note
+//!
+//! The encoding is used to export single compiled function bodies or all compiled
+//! code from an [`Engine`] and reload them into a compatible one. It is not meant
+//! to be stable across `wasmi` versions.
+//!
+//! [`Engine`]: crate::Engine
+
+use super::{
+    AnyConst16,
+    AnyConst32,
+    BinInstr,
+    BinInstrImm16,
+    BranchBinOpInstr,
+    BranchBinOpInstrImm16,
+    BranchOffset16,
+    CallIndirectParams,
+    Const16,
+    Const32,
+    CopysignImmInstr,
+    Instruction,
+    LoadAtInstr,
+    LoadInstr,
+    LoadOffset16Instr,
+    Register,
+    RegisterSpan,
+    RegisterSpanIter,
+    Sign,
+    StoreAtInstr,
+    StoreInstr,
+    StoreOffset16Instr,
+    UnaryInstr,
+};
+use crate::engine::{
+    bytecode::{
+        BlockFuel,
+        BranchOffset,
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        GlobalIdx,
+        MemoryIdx,
+        SignatureIdx,
+        TableIdx,
+    },
+    CompiledFunc,
+    FuncBodyError,
+};
+use alloc::vec::Vec;
+use wasmi_arena::ArenaIndex;
+use wasmi_core::TrapCode;
+
+/// Types that can be encoded into a byte buffer.
+pub trait Encode {
+    /// Appends the encoding of `self` to `out`.
+    ///
+    /// # Errors
+    ///
+    /// If `self` cannot be encoded in a self-contained way.
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError>;
+}
+
+/// Types that can be decoded from a byte buffer.
+pub trait Decode: Sized {
+    /// Decodes `Self` from the front of `input` and advances `input` past it.
+    ///
+    /// # Errors
+    ///
+    /// If `input` does not start with a valid encoding of `Self`.
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError>;
+}
+
+/// Splits off the first `N` bytes of `input`.
+fn take<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], FuncBodyError> {
+    if input.len() < N {
+        return Err(FuncBodyError::InvalidFormat);
+    }
+    let (head, rest) = input.split_at(N);
+    *input = rest;
+    let mut bytes = [0x00_u8; N];
+    bytes.copy_from_slice(head);
+    Ok(bytes)
+}
+
+macro_rules! impl_codec_for_primitive {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+                    out.extend_from_slice(&self.to_le_bytes());
+                    Ok(())
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+                    take(input).map(<$ty>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_primitive!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+macro_rules! impl_codec_for_index {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+                    self.to_u32().encode(out)
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+                    u32::decode(input).map(<$ty>::from)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_index!(
+    FuncIdx,
+    TableIdx,
+    SignatureIdx,
+    GlobalIdx,
+    MemoryIdx,
+    DataSegmentIdx,
+    ElementSegmentIdx,
+);
+
+impl<const N: usize> Encode for [Register; N] {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.iter().try_for_each(|register| register.encode(out))
+    }
+}
+
+impl<const N: usize> Decode for [Register; N] {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        let mut registers = [Register::from_i16(0); N];
+        for register in &mut registers {
+            *register = Register::decode(input)?;
+        }
+        Ok(registers)
+    }
+}
+
+impl Encode for Register {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.to_i16().encode(out)
+    }
+}
+
+impl Decode for Register {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        i16::decode(input).map(Register::from_i16)
+    }
+}
+
+impl Encode for RegisterSpan {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.head().encode(out)
+    }
+}
+
+impl Decode for RegisterSpan {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        Register::decode(input).map(RegisterSpan::new)
+    }
+}
+
+impl Encode for RegisterSpanIter {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.span().head().encode(out)?;
+        self.len_as_u16().encode(out)
+    }
+}
+
+impl Decode for RegisterSpanIter {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        let head = Register::decode(input)?;
+        let len = u16::decode(input)?;
+        let last = head
+            .to_i16()
+            .checked_add_unsigned(len)
+            .ok_or(FuncBodyError::InvalidFormat)?;
+        Ok(RegisterSpanIter::from_raw_parts(
+            head,
+            Register::from_i16(last),
+        ))
+    }
+}
+
+impl Encode for AnyConst16 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        (self.to_i32() as i16).encode(out)
+    }
+}
+
+impl Decode for AnyConst16 {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        i16::decode(input).map(AnyConst16::from_i16)
+    }
+}
+
+impl Encode for AnyConst32 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.to_u32().encode(out)
+    }
+}
+
+impl Decode for AnyConst32 {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        u32::decode(input).map(AnyConst32::from_u32)
+    }
+}
+
+impl<T> Encode for Const16<T> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.into_inner().encode(out)
+    }
+}
+
+impl<T> Decode for Const16<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        AnyConst16::decode(input).map(Const16::new)
+    }
+}
+
+impl<T> Encode for Const32<T> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.into_inner().encode(out)
+    }
+}
+
+impl<T> Decode for Const32<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        AnyConst32::decode(input).map(Const32::new)
+    }
+}
+
+impl Encode for BranchOffset {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.to_i32().encode(out)
+    }
+}
+
+impl Decode for BranchOffset {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        i32::decode(input).map(BranchOffset::from)
+    }
+}
+
+impl Encode for BranchOffset16 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.to_i16().encode(out)
+    }
+}
+
+impl Decode for BranchOffset16 {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        i16::decode(input).map(BranchOffset16::from)
+    }
+}
+
+impl Encode for BlockFuel {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        self.to_u64().encode(out)
+    }
+}
+
+impl Decode for BlockFuel {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        BlockFuel::try_from(u64::decode(input)?).map_err(|_| FuncBodyError::InvalidFormat)
+    }
+}
+
+impl Encode for CompiledFunc {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        // Note: compiled functions are only meaningful to the engine that compiled them.
+        (self.into_usize() as u32).encode(out)
+    }
+}
+
+impl Decode for CompiledFunc {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        u32::decode(input).map(|index| CompiledFunc::from_usize(index as usize))
+    }
+}
+
+impl Encode for TrapCode {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        let code: u8 = match self {
+            TrapCode::UnreachableCodeReached => 0,
+            TrapCode::MemoryOutOfBounds => 1,
+            TrapCode::TableOutOfBounds => 2,
+            TrapCode::IndirectCallToNull => 3,
+            TrapCode::IntegerDivisionByZero => 4,
+            TrapCode::IntegerOverflow => 5,
+            TrapCode::BadConversionToInteger => 6,
+            TrapCode::StackOverflow => 7,
+            TrapCode::BadSignature => 8,
+            TrapCode::OutOfFuel => 9,
+            TrapCode::GrowthOperationLimited => 10,
+            TrapCode::RegisterOutOfBounds => 11,
+            TrapCode::UnalignedAtomic => 12,
+            TrapCode::ExpectedSharedMemory => 13,
+        };
+        code.encode(out)
+    }
+}
+
+impl Decode for TrapCode {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        let trap_code = match u8::decode(input)? {
+            0 => TrapCode::UnreachableCodeReached,
+            1 => TrapCode::MemoryOutOfBounds,
+            2 => TrapCode::TableOutOfBounds,
+            3 => TrapCode::IndirectCallToNull,
+            4 => TrapCode::IntegerDivisionByZero,
+            5 => TrapCode::IntegerOverflow,
+            6 => TrapCode::BadConversionToInteger,
+            7 => TrapCode::StackOverflow,
+            8 => TrapCode::BadSignature,
+            9 => TrapCode::OutOfFuel,
+            10 => TrapCode::GrowthOperationLimited,
+            11 => TrapCode::RegisterOutOfBounds,
+            12 => TrapCode::UnalignedAtomic,
+            13 => TrapCode::ExpectedSharedMemory,
+            _ => return Err(FuncBodyError::InvalidFormat),
+        };
+        Ok(trap_code)
+    }
+}
+
+impl Encode for Sign {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+        let sign: u8 = match self {
+            Sign::Pos => 0,
+            Sign::Neg => 1,
+        };
+        sign.encode(out)
+    }
+}
+
+impl Decode for Sign {
+    fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+        match u8::decode(input)? {
+            0 => Ok(Sign::Pos),
+            1 => Ok(Sign::Neg),
+            _ => Err(FuncBodyError::InvalidFormat),
+        }
+    }
+}
+
+/// Implements [`Encode`] and [`Decode`] for structs by encoding their fields in order.
+macro_rules! impl_codec_for_struct {
+    ( $( $ty:ident $( <$param:ident> )? { $($field:ident),* $(,)? } ),* $(,)? ) => {
+        $(
+            impl$(<$param: Encode>)? Encode for $ty $(<$param>)? {
+                fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+                    $( self.$field.encode(out)?; )*
+                    Ok(())
+                }
+            }
+
+            impl$(<$param: Decode>)? Decode for $ty $(<$param>)? {
+                fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+                    Ok(Self {
+                        $( $field: Decode::decode(input)?, )*
+                    })
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_struct! {
+    BinInstr { result, lhs, rhs },
+    BinInstrImm16<T> { result, reg_in, imm_in },
+    BranchBinOpInstr { lhs, rhs, offset },
+    BranchBinOpInstrImm16<T> { lhs, rhs, offset },
+    UnaryInstr { result, input },
+    LoadInstr { result, ptr },
+    LoadAtInstr { result, address },
+    LoadOffset16Instr { result, ptr, offset },
+    StoreInstr { ptr, offset },
+    StoreOffset16Instr<T> { ptr, offset, value },
+    StoreAtInstr<T> { address, value },
+    CopysignImmInstr { result, lhs, rhs },
+    CallIndirectParams<T> { table, index },
+}
+
+/// Implements [`Encode`] and [`Decode`] for [`Instruction`].
+///
+/// Also implements [`Instruction::opcode`] and defines [`OPCODE_NAMES`].
+///
+/// # Note
+///
+/// Every [`Instruction`] is encoded as its 16-bit opcode followed by its fields.
+/// The opcode of an [`Instruction`] is its position in the list of variants below.
+macro_rules! impl_codec_for_instruction {
+    (
+        $(
+            $name:ident
+            $( ( $value:ident ) )?
+            $( { $($field:ident),* $(,)? } )?
+        ),* $(,)?
+    ) => {
+        /// The opcodes of all [`Instruction`] variants.
+        #[repr(u16)]
+        enum Opcode {
+            $( $name, )*
+        }
+
+        /// The opcodes of all [`Instruction`] variants as `u16` constants for matching.
+        #[allow(non_upper_case_globals)]
+        mod opcode {
+            $( pub const $name: u16 = super::Opcode::$name as u16; )*
+        }
+
+        /// The names of all [`Instruction`] variants indexed by their opcode.
+        pub const OPCODE_NAMES: [&str; NUM_OPCODES] = [ $( stringify!($name), )* ];
+
+        /// The number of [`Instruction`] opcodes.
+        pub const NUM_OPCODES: usize = [ $( Opcode::$name, )* ].len();
+
+        impl Instruction {
+            /// Returns the opcode of the [`Instruction`].
+            ///
+            /// The opcode is an index into [`OPCODE_NAMES`].
+            pub fn opcode(&self) -> u16 {
+                match self {
+                    $( Self::$name { .. } => Opcode::$name as u16, )*
+                }
+            }
+        }
+
+        impl Encode for Instruction {
+            fn encode(&self, out: &mut Vec<u8>) -> Result<(), FuncBodyError> {
+                match self {
+                    $(
+                        Self::$name $( ( $value ) )? $( { $($field),* } )? => {
+                            (Opcode::$name as u16).encode(out)?;
+                            $( $value.encode(out)?; )?
+                            $( $( $field.encode(out)?; )* )?
+                        }
+                    )*
+                }
+                Ok(())
+            }
+        }
+
+        impl Decode for Instruction {
+            fn decode(input: &mut &[u8]) -> Result<Self, FuncBodyError> {
+                let instr = match u16::decode(input)? {
+                    $(
+                        opcode::$name => Self::$name
+                            $( ( { let $value = Decode::decode(input)?; $value } ) )?
+                            $( { $( $field: Decode::decode(input)?, )* } )?,
+                    )*
+                    _ => return Err(FuncBodyError::InvalidFormat),
+                };
+                Ok(instr)
+            }
+        }
+    };
+}
+impl_codec_for_instruction! {
+    TableIdx(value),
+    DataSegmentIdx(value),
+    ElementSegmentIdx(value),
+    Const32(value),
+    I64Const32(value),
+    F64Const32(value),
+    Register(value),
+    Register2(value),
+    Register3(value),
+    RegisterList(value),
+    RegisterSpan(value),
+    CallIndirectParams(value),
+    CallIndirectParamsImm16(value),
+    Trap(value),
+    ConsumeFuel(value),
+    Return,
+    ReturnReg { value },
+    ReturnReg2 { values },
+    ReturnReg3 { values },
+    ReturnImm32 { value },
+    ReturnI64Imm32 { value },
+    ReturnF64Imm32 { value },
+    ReturnSpan { values },
+    ReturnMany { values },
+    ReturnNez { condition },
+    ReturnNezReg { condition, value },
+    ReturnNezReg2 { condition, values },
+    ReturnNezImm32 { condition, value },
+    ReturnNezI64Imm32 { condition, value },
+    ReturnNezF64Imm32 { condition, value },
+    ReturnNezSpan { condition, values },
+    ReturnNezMany { condition, values },
+    Branch { offset },
+    BranchEqz { condition, offset },
+    BranchNez { condition, offset },
+    BranchI32Eq(value),
+    BranchI32EqImm(value),
+    BranchI32Ne(value),
+    BranchI32NeImm(value),
+    BranchI32LtS(value),
+    BranchI32LtSImm(value),
+    BranchI32LtU(value),
+    BranchI32LtUImm(value),
+    BranchI32LeS(value),
+    BranchI32LeSImm(value),
+    BranchI32LeU(value),
+    BranchI32LeUImm(value),
+    BranchI32GtS(value),
+    BranchI32GtSImm(value),
+    BranchI32GtU(value),
+    BranchI32GtUImm(value),
+    BranchI32GeS(value),
+    BranchI32GeSImm(value),
+    BranchI32GeU(value),
+    BranchI32GeUImm(value),
+    BranchCmpFallback { lhs, rhs, params },
+    BranchTable { index, len_targets },
+    Copy { result, value },
+    Copy2 { results, values },
+    CopyImm32 { result, value },
+    CopyI64Imm32 { result, value },
+    CopyF64Imm32 { result, value },
+    CopySpan { results, values, len },
+    CopySpanNonOverlapping { results, values, len },
+    CopyMany { results, values },
+    CopyManyNonOverlapping { results, values },
+    ReturnCallInternal0 { func },
+    ReturnCallInternal { func },
+    ReturnCallImported0 { func },
+    ReturnCallImported { func },
+    ReturnCallIndirect0 { func_type },
+    ReturnCallIndirect { func_type },
+    CallInternal0 { results, func },
+    CallInternal { results, func },
+    CallImported0 { results, func },
+    CallImported { results, func },
+    CallIndirect0 { results, func_type },
+    CallIndirect { results, func_type },
+    Select { result, condition, lhs },
+    SelectRev { result, condition, rhs },
+    SelectImm32 { result_or_condition, lhs_or_rhs },
+    SelectI64Imm32 { result_or_condition, lhs_or_rhs },
+    SelectF64Imm32 { result_or_condition, lhs_or_rhs },
+    RefFunc { result, func },
+    TableGet { result, index },
+    TableGetImm { result, index },
+    TableSize { result, table },
+    TableSet { index, value },
+    TableSetAt { index, value },
+    TableCopy { dst, src, len },
+    TableCopyTo { dst, src, len },
+    TableCopyFrom { dst, src, len },
+    TableCopyFromTo { dst, src, len },
+    TableCopyExact { dst, src, len },
+    TableCopyToExact { dst, src, len },
+    TableCopyFromExact { dst, src, len },
+    TableCopyFromToExact { dst, src, len },
+    TableInit { dst, src, len },
+    TableInitTo { dst, src, len },
+    TableInitFrom { dst, src, len },
+    TableInitFromTo { dst, src, len },
+    TableInitExact { dst, src, len },
+    TableInitToExact { dst, src, len },
+    TableInitFromExact { dst, src, len },
+    TableInitFromToExact { dst, src, len },
+    TableFill { dst, len, value },
+    TableFillAt { dst, len, value },
+    TableFillExact { dst, len, value },
+    TableFillAtExact { dst, len, value },
+    TableGrow { result, delta, value },
+    TableGrowImm { result, delta, value },
+    ElemDrop(value),
+    DataDrop(value),
+    UseMemory(value),
+    MemorySize { result },
+    MemoryGrow { result, delta },
+    MemoryGrowBy { result, delta },
+    MemoryCopy { dst, src, len },
+    MemoryCopyTo { dst, src, len },
+    MemoryCopyFrom { dst, src, len },
+    MemoryCopyFromTo { dst, src, len },
+    MemoryCopyExact { dst, src, len },
+    MemoryCopyToExact { dst, src, len },
+    MemoryCopyFromExact { dst, src, len },
+    MemoryCopyFromToExact { dst, src, len },
+    MemoryFill { dst, value, len },
+    MemoryFillAt { dst, value, len },
+    MemoryFillImm { dst, value, len },
+    MemoryFillExact { dst, value, len },
+    MemoryFillAtImm { dst, value, len },
+    MemoryFillAtExact { dst, value, len },
+    MemoryFillImmExact { dst, value, len },
+    MemoryFillAtImmExact { dst, value, len },
+    MemoryInit { dst, src, len },
+    MemoryInitTo { dst, src, len },
+    MemoryInitFrom { dst, src, len },
+    MemoryInitFromTo { dst, src, len },
+    MemoryInitExact { dst, src, len },
+    MemoryInitToExact { dst, src, len },
+    MemoryInitFromExact { dst, src, len },
+    MemoryInitFromToExact { dst, src, len },
+    GlobalGet { result, global },
+    GlobalSet { global, input },
+    GlobalSetI32Imm16 { global, input },
+    GlobalSetI64Imm16 { global, input },
+    I32Load(value),
+    I32LoadAt(value),
+    I32LoadOffset16(value),
+    I64Load(value),
+    I64LoadAt(value),
+    I64LoadOffset16(value),
+    F32Load(value),
+    F32LoadAt(value),
+    F32LoadOffset16(value),
+    F64Load(value),
+    F64LoadAt(value),
+    F64LoadOffset16(value),
+    I32Load8s(value),
+    I32Load8sAt(value),
+    I32Load8sOffset16(value),
+    I32Load8u(value),
+    I32Load8uAt(value),
+    I32Load8uOffset16(value),
+    I32Load16s(value),
+    I32Load16sAt(value),
+    I32Load16sOffset16(value),
+    I32Load16u(value),
+    I32Load16uAt(value),
+    I32Load16uOffset16(value),
+    I64Load8s(value),
+    I64Load8sAt(value),
+    I64Load8sOffset16(value),
+    I64Load8u(value),
+    I64Load8uAt(value),
+    I64Load8uOffset16(value),
+    I64Load16s(value),
+    I64Load16sAt(value),
+    I64Load16sOffset16(value),
+    I64Load16u(value),
+    I64Load16uAt(value),
+    I64Load16uOffset16(value),
+    I64Load32s(value),
+    I64Load32sAt(value),
+    I64Load32sOffset16(value),
+    I64Load32u(value),
+    I64Load32uAt(value),
+    I64Load32uOffset16(value),
+    LoopBoundsGuard { index },
+    I32LoadUnchecked(value),
+    I64LoadUnchecked(value),
+    F32LoadUnchecked(value),
+    F64LoadUnchecked(value),
+    I32Load8sUnchecked(value),
+    I32Load8uUnchecked(value),
+    I32Load16sUnchecked(value),
+    I32Load16uUnchecked(value),
+    I64Load8sUnchecked(value),
+    I64Load8uUnchecked(value),
+    I64Load16sUnchecked(value),
+    I64Load16uUnchecked(value),
+    I64Load32sUnchecked(value),
+    I64Load32uUnchecked(value),
+    I32StoreUnchecked(value),
+    I32Store8Unchecked(value),
+    I32Store16Unchecked(value),
+    I64StoreUnchecked(value),
+    I64Store8Unchecked(value),
+    I64Store16Unchecked(value),
+    I64Store32Unchecked(value),
+    F32StoreUnchecked(value),
+    F64StoreUnchecked(value),
+    I32Store(value),
+    I32StoreOffset16(value),
+    I32StoreOffset16Imm16(value),
+    I32StoreAt(value),
+    I32StoreAtImm16(value),
+    I32Store8(value),
+    I32Store8Offset16(value),
+    I32Store8Offset16Imm(value),
+    I32Store8At(value),
+    I32Store8AtImm(value),
+    I32Store16(value),
+    I32Store16Offset16(value),
+    I32Store16Offset16Imm(value),
+    I32Store16At(value),
+    I32Store16AtImm(value),
+    I64Store(value),
+    I64StoreOffset16(value),
+    I64StoreOffset16Imm16(value),
+    I64StoreAt(value),
+    I64StoreAtImm16(value),
+    I64Store8(value),
+    I64Store8Offset16(value),
+    I64Store8Offset16Imm(value),
+    I64Store8At(value),
+    I64Store8AtImm(value),
+    I64Store16(value),
+    I64Store16Offset16(value),
+    I64Store16Offset16Imm(value),
+    I64Store16At(value),
+    I64Store16AtImm(value),
+    I64Store32(value),
+    I64Store32Offset16(value),
+    I64Store32Offset16Imm16(value),
+    I64Store32At(value),
+    I64Store32AtImm16(value),
+    F32Store(value),
+    F32StoreOffset16(value),
+    F32StoreAt(value),
+    F64Store(value),
+    F64StoreOffset16(value),
+    F64StoreAt(value),
+    I32Eqz(value),
+    I64Eqz(value),
+    I32Eq(value),
+    I32EqImm16(value),
+    I64Eq(value),
+    I64EqImm16(value),
+    I32Ne(value),
+    I32NeImm16(value),
+    I64Ne(value),
+    I64NeImm16(value),
+    I32LtS(value),
+    I32LtU(value),
+    I32LtSImm16(value),
+    I32LtUImm16(value),
+    I64LtS(value),
+    I64LtU(value),
+    I64LtSImm16(value),
+    I64LtUImm16(value),
+    I32GtS(value),
+    I32GtU(value),
+    I32GtSImm16(value),
+    I32GtUImm16(value),
+    I64GtS(value),
+    I64GtU(value),
+    I64GtSImm16(value),
+    I64GtUImm16(value),
+    I32LeS(value),
+    I32LeU(value),
+    I32LeSImm16(value),
+    I32LeUImm16(value),
+    I64LeS(value),
+    I64LeU(value),
+    I64LeSImm16(value),
+    I64LeUImm16(value),
+    I32GeS(value),
+    I32GeU(value),
+    I32GeSImm16(value),
+    I32GeUImm16(value),
+    I64GeS(value),
+    I64GeU(value),
+    I64GeSImm16(value),
+    I64GeUImm16(value),
+    F32Eq(value),
+    F64Eq(value),
+    F32Ne(value),
+    F64Ne(value),
+    F32Lt(value),
+    F64Lt(value),
+    F32Le(value),
+    F64Le(value),
+    F32Gt(value),
+    F64Gt(value),
+    F32Ge(value),
+    F64Ge(value),
+    I32Clz(value),
+    I64Clz(value),
+    I32Ctz(value),
+    I64Ctz(value),
+    I32Popcnt(value),
+    I64Popcnt(value),
+    I32Add(value),
+    I32AddFromMem(value),
+    I64Add(value),
+    I32AddImm16(value),
+    I64AddImm16(value),
+    I32Sub(value),
+    I64Sub(value),
+    I32SubImm16(value),
+    I64SubImm16(value),
+    I32SubImm16Rev(value),
+    I64SubImm16Rev(value),
+    I32Mul(value),
+    I64Mul(value),
+    I32MulImm16(value),
+    I64MulImm16(value),
+    I32DivS(value),
+    I64DivS(value),
+    I32DivSImm16(value),
+    I64DivSImm16(value),
+    I32DivSImm16Rev(value),
+    I64DivSImm16Rev(value),
+    I32DivU(value),
+    I64DivU(value),
+    I32DivUImm16(value),
+    I64DivUImm16(value),
+    I32DivUImm16Rev(value),
+    I64DivUImm16Rev(value),
+    I32RemS(value),
+    I64RemS(value),
+    I32RemSImm16(value),
+    I64RemSImm16(value),
+    I32RemSImm16Rev(value),
+    I64RemSImm16Rev(value),
+    I32RemU(value),
+    I64RemU(value),
+    I32RemUImm16(value),
+    I64RemUImm16(value),
+    I32RemUImm16Rev(value),
+    I64RemUImm16Rev(value),
+    I32And(value),
+    I64And(value),
+    I32AndImm16(value),
+    I64AndImm16(value),
+    I32Or(value),
+    I64Or(value),
+    I32OrImm16(value),
+    I64OrImm16(value),
+    I32Xor(value),
+    I64Xor(value),
+    I32XorImm16(value),
+    I64XorImm16(value),
+    I32Shl(value),
+    I64Shl(value),
+    I32ShlImm(value),
+    I64ShlImm(value),
+    I32ShlImm16Rev(value),
+    I64ShlImm16Rev(value),
+    I32ShrU(value),
+    I64ShrU(value),
+    I32ShrUImm(value),
+    I64ShrUImm(value),
+    I32ShrUImm16Rev(value),
+    I64ShrUImm16Rev(value),
+    I32ShrS(value),
+    I64ShrS(value),
+    I32ShrSImm(value),
+    I64ShrSImm(value),
+    I32ShrSImm16Rev(value),
+    I64ShrSImm16Rev(value),
+    I32Rotl(value),
+    I64Rotl(value),
+    I32RotlImm(value),
+    I64RotlImm(value),
+    I32RotlImm16Rev(value),
+    I64RotlImm16Rev(value),
+    I32Rotr(value),
+    I64Rotr(value),
+    I32RotrImm(value),
+    I64RotrImm(value),
+    I32RotrImm16Rev(value),
+    I64RotrImm16Rev(value),
+    F32Abs(value),
+    F64Abs(value),
+    F32Neg(value),
+    F64Neg(value),
+    F32Ceil(value),
+    F64Ceil(value),
+    F32Floor(value),
+    F64Floor(value),
+    F32Trunc(value),
+    F64Trunc(value),
+    F32Nearest(value),
+    F64Nearest(value),
+    F32Sqrt(value),
+    F64Sqrt(value),
+    F32Add(value),
+    F64Add(value),
+    F32Sub(value),
+    F64Sub(value),
+    F32Mul(value),
+    F64Mul(value),
+    F32Div(value),
+    F64Div(value),
+    F32Min(value),
+    F64Min(value),
+    F32Max(value),
+    F64Max(value),
+    F32Copysign(value),
+    F64Copysign(value),
+    F32CopysignImm(value),
+    F64CopysignImm(value),
+    I32WrapI64(value),
+    I64ExtendI32S(value),
+    I64ExtendI32U(value),
+    I32TruncF32S(value),
+    I32TruncF32U(value),
+    I32TruncF64S(value),
+    I32TruncF64U(value),
+    I64TruncF32S(value),
+    I64TruncF32U(value),
+    I64TruncF64S(value),
+    I64TruncF64U(value),
+    I32TruncSatF32S(value),
+    I32TruncSatF32U(value),
+    I32TruncSatF64S(value),
+    I32TruncSatF64U(value),
+    I64TruncSatF32S(value),
+    I64TruncSatF32U(value),
+    I64TruncSatF64S(value),
+    I64TruncSatF64U(value),
+    I32Extend8S(value),
+    I32Extend16S(value),
+    I64Extend8S(value),
+    I64Extend16S(value),
+    I64Extend32S(value),
+    F32DemoteF64(value),
+    F64PromoteF32(value),
+    F32ConvertI32S(value),
+    F32ConvertI32U(value),
+    F32ConvertI64S(value),
+    F32ConvertI64U(value),
+    F64ConvertI32S(value),
+    F64ConvertI32U(value),
+    F64ConvertI64S(value),
+    F64ConvertI64U(value),
+    V128Load(value),
+    V128Store(value),
+    I32x4Splat(value),
+    I32x4MinS(value),
+    I32x4MinU(value),
+    I32x4MaxS(value),
+    I32x4MaxU(value),
+    F32x4Min(value),
+    F32x4Max(value),
+    MemoryAtomicNotify(value),
+    MemoryAtomicWait32 { result, ptr, expected },
+    MemoryAtomicWait64 { result, ptr, expected },
+}
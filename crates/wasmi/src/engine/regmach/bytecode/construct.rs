@@ -3,6 +3,9 @@ use super::{
     AnyConst32,
     BinInstr,
     BinInstrImm16,
+    BranchBinOpInstr,
+    BranchBinOpInstrImm16,
+    BranchOffset16,
     CallIndirectParams,
     Const16,
     Const32,
@@ -19,10 +22,19 @@ use super::{
     UnaryInstr,
 };
 use crate::engine::{
-    bytecode::{BranchOffset, DataSegmentIdx, ElementSegmentIdx, FuncIdx, SignatureIdx, TableIdx},
+    bytecode::{
+        BranchOffset,
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        MemoryIdx,
+        SignatureIdx,
+        TableIdx,
+    },
     regmach::bytecode,
     CompiledFunc,
 };
+use wasmi_core::TrapCode;
 
 macro_rules! constructor_for {
     (
@@ -98,6 +110,24 @@ macro_rules! constructor_for {
             Self::$op_code(BinInstrImm16::new(result, rhs, lhs))
         }
     };
+    ( @impl fn $fn_name:ident(branch_binary) -> Self::$op_code:ident ) => {
+        #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
+        pub fn $fn_name(lhs: Register, rhs: Register, offset: BranchOffset16) -> Self {
+            Self::$op_code(BranchBinOpInstr::new(lhs, rhs, offset))
+        }
+    };
+    ( @impl fn $fn_name:ident(branch_binary_i32imm16) -> Self::$op_code:ident ) => {
+        #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
+        pub fn $fn_name(lhs: Register, rhs: impl Into<Const16<i32>>, offset: BranchOffset16) -> Self {
+            Self::$op_code(BranchBinOpInstrImm16::new(lhs, rhs.into(), offset))
+        }
+    };
+    ( @impl fn $fn_name:ident(branch_binary_u32imm16) -> Self::$op_code:ident ) => {
+        #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
+        pub fn $fn_name(lhs: Register, rhs: impl Into<Const16<u32>>, offset: BranchOffset16) -> Self {
+            Self::$op_code(BranchBinOpInstrImm16::new(lhs, rhs.into(), offset))
+        }
+    };
     ( @impl fn $fn_name:ident(load) -> Self::$op_code:ident ) => {
         #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
         pub fn $fn_name(result: Register, ptr: Register) -> Self {
@@ -176,6 +206,11 @@ impl Instruction {
         Self::F64Const32(value.into())
     }
 
+    /// Creates a new [`Instruction::Trap`] with the given `trap_code`.
+    pub fn trap(trap_code: TrapCode) -> Self {
+        Self::Trap(trap_code)
+    }
+
     /// Creates a new [`Instruction::ReturnReg`] from the given [`Register`] index.
     pub fn return_reg(index: impl Into<Register>) -> Self {
         Self::ReturnReg {
@@ -321,6 +356,34 @@ impl Instruction {
         Self::BranchNez { condition, offset }
     }
 
+    constructor_for! {
+        fn branch_i32_eq(branch_binary) -> Self::BranchI32Eq;
+        fn branch_i32_eq_imm(branch_binary_i32imm16) -> Self::BranchI32EqImm;
+        fn branch_i32_ne(branch_binary) -> Self::BranchI32Ne;
+        fn branch_i32_ne_imm(branch_binary_i32imm16) -> Self::BranchI32NeImm;
+        fn branch_i32_lt_s(branch_binary) -> Self::BranchI32LtS;
+        fn branch_i32_lt_s_imm(branch_binary_i32imm16) -> Self::BranchI32LtSImm;
+        fn branch_i32_lt_u(branch_binary) -> Self::BranchI32LtU;
+        fn branch_i32_lt_u_imm(branch_binary_u32imm16) -> Self::BranchI32LtUImm;
+        fn branch_i32_le_s(branch_binary) -> Self::BranchI32LeS;
+        fn branch_i32_le_s_imm(branch_binary_i32imm16) -> Self::BranchI32LeSImm;
+        fn branch_i32_le_u(branch_binary) -> Self::BranchI32LeU;
+        fn branch_i32_le_u_imm(branch_binary_u32imm16) -> Self::BranchI32LeUImm;
+        fn branch_i32_gt_s(branch_binary) -> Self::BranchI32GtS;
+        fn branch_i32_gt_s_imm(branch_binary_i32imm16) -> Self::BranchI32GtSImm;
+        fn branch_i32_gt_u(branch_binary) -> Self::BranchI32GtU;
+        fn branch_i32_gt_u_imm(branch_binary_u32imm16) -> Self::BranchI32GtUImm;
+        fn branch_i32_ge_s(branch_binary) -> Self::BranchI32GeS;
+        fn branch_i32_ge_s_imm(branch_binary_i32imm16) -> Self::BranchI32GeSImm;
+        fn branch_i32_ge_u(branch_binary) -> Self::BranchI32GeU;
+        fn branch_i32_ge_u_imm(branch_binary_u32imm16) -> Self::BranchI32GeUImm;
+    }
+
+    /// Creates a new [`Instruction::BranchCmpFallback`].
+    pub fn branch_cmp_fallback(lhs: Register, rhs: Register, params: Register) -> Self {
+        Self::BranchCmpFallback { lhs, rhs, params }
+    }
+
     /// Creates a new [`Instruction::BranchTable`] for the given `index` and `len_targets`.
     pub fn branch_table(index: Register, len_targets: impl Into<Const32<u32>>) -> Self {
         Self::BranchTable {
@@ -375,7 +438,6 @@ impl Instruction {
 
     /// Creates a new [`Instruction::CopySpan`] copying multiple consecutive values.
     pub fn copy_span(results: RegisterSpan, values: RegisterSpan, len: u16) -> Self {
-        debug_assert!(!results.iter_u16(len).is_overlapping(&values.iter_u16(len)));
         Self::CopySpan {
             results,
             values,
@@ -389,7 +451,11 @@ impl Instruction {
         values: RegisterSpan,
         len: u16,
     ) -> Self {
-        debug_assert!(!results.iter_u16(len).is_overlapping(&values.iter_u16(len)));
+        // Note: copying to lower registers never reads an already overwritten register.
+        debug_assert!(
+            results.head() <= values.head()
+                || !results.iter_u16(len).is_overlapping(&values.iter_u16(len))
+        );
         Self::CopySpanNonOverlapping {
             results,
             values,
@@ -652,6 +718,11 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::ElemDrop`] for the given element segment `index`.
+    pub fn elem_drop(index: impl Into<ElementSegmentIdx>) -> Self {
+        Self::ElemDrop(index.into())
+    }
+
     /// Creates a new [`Instruction::TableInit`] with the given `dst`, `src` and `len`.
     pub fn table_init(dst: Register, src: Register, len: Register) -> Self {
         Self::TableInit { dst, src, len }
@@ -794,6 +865,11 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::UseMemory`] for the linear memory at `index`.
+    pub fn use_memory(index: impl Into<MemoryIdx>) -> Self {
+        Self::UseMemory(index.into())
+    }
+
     /// Creates a new [`Instruction::MemorySize`] with the given `result`.
     pub fn memory_size(result: Register) -> Self {
         Self::MemorySize { result }
@@ -812,6 +888,11 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::LoopBoundsGuard`] for the loop `index` register.
+    pub fn loop_bounds_guard(index: Register) -> Self {
+        Self::LoopBoundsGuard { index }
+    }
+
     /// Creates a new [`Instruction::MemoryCopy`] with the given `dst`, `src` and `len`.
     pub fn memory_copy(dst: Register, src: Register, len: Register) -> Self {
         Self::MemoryCopy { dst, src, len }
@@ -896,6 +977,11 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::DataDrop`] for the given data segment `index`.
+    pub fn data_drop(index: impl Into<DataSegmentIdx>) -> Self {
+        Self::DataDrop(index.into())
+    }
+
     /// Creates a new [`Instruction::MemoryInit`] with the given `dst`, `src` and `len`.
     pub fn memory_init(dst: Register, src: Register, len: Register) -> Self {
         Self::MemoryInit { dst, src, len }
@@ -1052,6 +1138,24 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::MemoryAtomicWait32`] with the given `result`, `ptr` and `expected`.
+    pub fn memory_atomic_wait32(result: Register, ptr: Register, expected: Register) -> Self {
+        Self::MemoryAtomicWait32 {
+            result,
+            ptr,
+            expected,
+        }
+    }
+
+    /// Creates a new [`Instruction::MemoryAtomicWait64`] with the given `result`, `ptr` and `expected`.
+    pub fn memory_atomic_wait64(result: Register, ptr: Register, expected: Register) -> Self {
+        Self::MemoryAtomicWait64 {
+            result,
+            ptr,
+            expected,
+        }
+    }
+
     /// Creates a new [`Instruction::Register`] instruction parameter.
     pub fn register(reg: impl Into<Register>) -> Self {
         Self::Register(reg.into())
@@ -1080,6 +1184,11 @@ impl Instruction {
         Self::RegisterList([reg0.into(), reg1.into(), reg2.into()])
     }
 
+    /// Creates a new [`Instruction::RegisterSpan`] instruction parameter.
+    pub fn register_span(span: RegisterSpanIter) -> Self {
+        Self::RegisterSpan(span)
+    }
+
     /// Creates a new [`Instruction::CallIndirectParams`] for the given `index` and `table`.
     pub fn call_indirect_params(index: Register, table: impl Into<TableIdx>) -> Self {
         Self::CallIndirectParams(CallIndirectParams {
@@ -1234,6 +1343,35 @@ impl Instruction {
         fn f64_load_at(load_at) -> Self::F64LoadAt;
         fn f64_load_offset16(load_offset16) -> Self::F64LoadOffset16;
 
+        // Load (unchecked)
+
+        fn i32_load_unchecked(load) -> Self::I32LoadUnchecked;
+        fn i64_load_unchecked(load) -> Self::I64LoadUnchecked;
+        fn f32_load_unchecked(load) -> Self::F32LoadUnchecked;
+        fn f64_load_unchecked(load) -> Self::F64LoadUnchecked;
+        fn i32_load8_s_unchecked(load) -> Self::I32Load8sUnchecked;
+        fn i32_load8_u_unchecked(load) -> Self::I32Load8uUnchecked;
+        fn i32_load16_s_unchecked(load) -> Self::I32Load16sUnchecked;
+        fn i32_load16_u_unchecked(load) -> Self::I32Load16uUnchecked;
+        fn i64_load8_s_unchecked(load) -> Self::I64Load8sUnchecked;
+        fn i64_load8_u_unchecked(load) -> Self::I64Load8uUnchecked;
+        fn i64_load16_s_unchecked(load) -> Self::I64Load16sUnchecked;
+        fn i64_load16_u_unchecked(load) -> Self::I64Load16uUnchecked;
+        fn i64_load32_s_unchecked(load) -> Self::I64Load32sUnchecked;
+        fn i64_load32_u_unchecked(load) -> Self::I64Load32uUnchecked;
+
+        // Store (unchecked)
+
+        fn i32_store_unchecked(store) -> Self::I32StoreUnchecked;
+        fn i32_store8_unchecked(store) -> Self::I32Store8Unchecked;
+        fn i32_store16_unchecked(store) -> Self::I32Store16Unchecked;
+        fn i64_store_unchecked(store) -> Self::I64StoreUnchecked;
+        fn i64_store8_unchecked(store) -> Self::I64Store8Unchecked;
+        fn i64_store16_unchecked(store) -> Self::I64Store16Unchecked;
+        fn i64_store32_unchecked(store) -> Self::I64Store32Unchecked;
+        fn f32_store_unchecked(store) -> Self::F32StoreUnchecked;
+        fn f64_store_unchecked(store) -> Self::F64StoreUnchecked;
+
         // Store
 
         fn i32_store(store) -> Self::I32Store;
@@ -1288,6 +1426,9 @@ impl Instruction {
 
         // Integer Unary
 
+        fn i32_eqz(unary) -> Self::I32Eqz;
+        fn i64_eqz(unary) -> Self::I64Eqz;
+
         fn i32_clz(unary) -> Self::I32Clz;
         fn i32_ctz(unary) -> Self::I32Ctz;
         fn i32_popcnt(unary) -> Self::I32Popcnt;
@@ -1411,6 +1552,7 @@ impl Instruction {
         // Integer Arithmetic
 
         fn i32_add(binary) -> Self::I32Add;
+        fn i32_add_from_mem(binary) -> Self::I32AddFromMem;
         fn i32_add_imm16(binary_i32imm16) -> Self::I32AddImm16;
 
         fn i64_add(binary) -> Self::I64Add;
@@ -1570,5 +1712,21 @@ impl Instruction {
         fn f64_convert_i32_u(unary) -> Self::F64ConvertI32U;
         fn f64_convert_i64_s(unary) -> Self::F64ConvertI64S;
         fn f64_convert_i64_u(unary) -> Self::F64ConvertI64U;
+
+        // SIMD
+
+        fn v128_load(load) -> Self::V128Load;
+        fn v128_store(store) -> Self::V128Store;
+        fn i32x4_splat(unary) -> Self::I32x4Splat;
+        fn i32x4_min_s(binary) -> Self::I32x4MinS;
+        fn i32x4_min_u(binary) -> Self::I32x4MinU;
+        fn i32x4_max_s(binary) -> Self::I32x4MaxS;
+        fn i32x4_max_u(binary) -> Self::I32x4MaxU;
+        fn f32x4_min(binary) -> Self::F32x4Min;
+        fn f32x4_max(binary) -> Self::F32x4Max;
+
+        // Threads
+
+        fn memory_atomic_notify(load) -> Self::MemoryAtomicNotify;
     }
 }
@@ -22,6 +22,11 @@ impl<T> Const16<T> {
             marker: PhantomData,
         }
     }
+
+    /// Returns the underlying untyped [`AnyConst16`] value.
+    pub fn into_inner(self) -> AnyConst16 {
+        self.inner
+    }
 }
 
 impl<T> Clone for Const16<T> {
@@ -171,6 +176,11 @@ impl<T> Const32<T> {
             marker: PhantomData,
         }
     }
+
+    /// Returns the underlying untyped [`AnyConst32`] value.
+    pub fn into_inner(self) -> AnyConst32 {
+        self.inner
+    }
 }
 
 impl<T> Clone for Const32<T> {
@@ -1,3 +1,4 @@
+mod codec;
 mod construct;
 mod immediate;
 mod provider;
@@ -7,12 +8,18 @@ mod utils;
 mod tests;
 
 pub(crate) use self::{
+    codec::{Decode, Encode, NUM_OPCODES, OPCODE_NAMES},
     immediate::{AnyConst16, AnyConst32, Const16, Const32},
     provider::{Provider, ProviderSliceStack, UntypedProvider},
     utils::{
         BinInstr,
         BinInstrImm16,
+        BranchBinOpInstr,
+        BranchBinOpInstrImm16,
+        BranchComparator,
+        BranchOffset16,
         CallIndirectParams,
+        ComparatorOffsetParam,
         CopysignImmInstr,
         LoadAtInstr,
         LoadInstr,
@@ -35,6 +42,7 @@ use crate::engine::{
         ElementSegmentIdx,
         FuncIdx,
         GlobalIdx,
+        MemoryIdx,
         SignatureIdx,
         TableIdx,
     },
@@ -145,6 +153,16 @@ pub enum Instruction {
     /// - [`Instruction::Register2`]
     /// - [`Instruction::Register3`]
     RegisterList([Register; 3]),
+    /// A [`RegisterSpanIter`] of contiguous [`Register`] instruction parameters.
+    ///
+    /// # Note
+    ///
+    /// This [`Instruction`] only acts as a parameter to another
+    /// one and will never be executed itself directly.
+    ///
+    /// Used to encode the parameters of call instructions that are stored
+    /// in contiguous registers so that they are copied all at once.
+    RegisterSpan(RegisterSpanIter),
     /// Auxiliary [`Instruction`] to encode table access information for indirect call instructions.
     CallIndirectParams(CallIndirectParams<Register>),
     /// Variant of [`Instruction::CallIndirectParams`] for 16-bit constant `index` parameter.
@@ -386,6 +404,161 @@ pub enum Instruction {
         /// The branching offset for the instruction pointer.
         offset: BranchOffset,
     },
+    /// Fused `i32` equality comparison and branch instruction: `if r0 == r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32Eq`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32Eq(BranchBinOpInstr),
+    /// Fused `i32` equality comparison and branch instruction with immediate: `if r0 == c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32EqImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32EqImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` inequality comparison and branch instruction: `if r0 != r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32Ne`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32Ne(BranchBinOpInstr),
+    /// Fused `i32` inequality comparison and branch instruction with immediate: `if r0 != c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32NeImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32NeImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` signed less-than comparison and branch instruction: `if r0 < r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LtS`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LtS(BranchBinOpInstr),
+    /// Fused `i32` signed less-than comparison and branch instruction with immediate: `if r0 < c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LtSImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LtSImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` unsigned less-than comparison and branch instruction: `if r0 < r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LtU`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LtU(BranchBinOpInstr),
+    /// Fused `i32` unsigned less-than comparison and branch instruction with immediate: `if r0 < c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LtUImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LtUImm(BranchBinOpInstrImm16<u32>),
+    /// Fused `i32` signed less-than-or-equals comparison and branch instruction: `if r0 <= r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LeS`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LeS(BranchBinOpInstr),
+    /// Fused `i32` signed less-than-or-equals comparison and branch instruction with immediate: `if r0 <= c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LeSImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LeSImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` unsigned less-than-or-equals comparison and branch instruction: `if r0 <= r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LeU`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LeU(BranchBinOpInstr),
+    /// Fused `i32` unsigned less-than-or-equals comparison and branch instruction with immediate: `if r0 <= c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32LeUImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32LeUImm(BranchBinOpInstrImm16<u32>),
+    /// Fused `i32` signed greater-than comparison and branch instruction: `if r0 > r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GtS`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GtS(BranchBinOpInstr),
+    /// Fused `i32` signed greater-than comparison and branch instruction with immediate: `if r0 > c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GtSImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GtSImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` unsigned greater-than comparison and branch instruction: `if r0 > r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GtU`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GtU(BranchBinOpInstr),
+    /// Fused `i32` unsigned greater-than comparison and branch instruction with immediate: `if r0 > c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GtUImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GtUImm(BranchBinOpInstrImm16<u32>),
+    /// Fused `i32` signed greater-than-or-equals comparison and branch instruction: `if r0 >= r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GeS`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GeS(BranchBinOpInstr),
+    /// Fused `i32` signed greater-than-or-equals comparison and branch instruction with immediate: `if r0 >= c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GeSImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GeSImm(BranchBinOpInstrImm16<i32>),
+    /// Fused `i32` unsigned greater-than-or-equals comparison and branch instruction: `if r0 >= r1 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GeU`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GeU(BranchBinOpInstr),
+    /// Fused `i32` unsigned greater-than-or-equals comparison and branch instruction with immediate: `if r0 >= c0 { branch }`
+    ///
+    /// # Note
+    ///
+    /// Fused from an [`Instruction::I32GeUImm16`] and a [`Instruction::BranchNez`]
+    /// reading its result.
+    BranchI32GeUImm(BranchBinOpInstrImm16<u32>),
+    /// Fused `i32` comparison and branch instruction for branch offsets that do not fit into 16 bits.
+    ///
+    /// # Note
+    ///
+    /// Replaces any of the fused `i32` compare and branch instructions such as
+    /// [`Instruction::BranchI32Eq`] upon resolving a forward branch whose offset
+    /// turns out to be out of bounds for their [`BranchOffset16`].
+    BranchCmpFallback {
+        /// The register holding the left-hand side value.
+        lhs: Register,
+        /// The register holding the right-hand side value.
+        rhs: Register,
+        /// The register holding the [`ComparatorOffsetParam`] of the instruction.
+        params: Register,
+    },
 
     /// A Wasm `br_table` instruction.
     ///
@@ -1274,6 +1447,16 @@ pub enum Instruction {
     /// A Wasm `data.drop` equalivalent `wasmi` instruction.
     DataDrop(DataSegmentIdx),
 
+    /// Prefixes a memory instruction that accesses the linear memory at the index.
+    ///
+    /// # Note
+    ///
+    /// Used by the multi-memory Wasm proposal. This [`Instruction`] is executed
+    /// together with the directly following memory instruction which then accesses
+    /// the linear memory at the index instead of the default linear memory.
+    /// Memory instructions that access the default linear memory are not prefixed.
+    UseMemory(MemoryIdx),
+
     /// Wasm `memory.size` instruction.
     MemorySize {
         /// Register holding the result of the instruction.
@@ -1901,6 +2084,310 @@ pub enum Instruction {
     /// Variant of [`Instruction::I64Load32u`] for small offset values.
     I64Load32uOffset16(LoadOffset16Instr),
 
+    /// Checks the bounds of all loads of a counted `loop` in front of the `loop`.
+    ///
+    /// Traps with [`TrapCode::MemoryOutOfBounds`] if the loads of the last `loop` iteration
+    /// would access the default linear memory out of bounds. The `loop` counts its `index`
+    /// register from its initial value up to `bound` (exclusive) in increments of `step`.
+    ///
+    /// # Note
+    ///
+    /// All loads from `index` of the guarded `loop` are encoded as unchecked loads such as
+    /// [`Instruction::I32LoadUnchecked`] since their bounds have already been checked.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by
+    ///
+    /// 1. [`Instruction::Const32`]: the exclusive `bound` of `index`
+    /// 2. [`Instruction::Const32`]: the `step` by which `index` is incremented
+    /// 3. [`Instruction::Const32`]: the maximum of `offset + N` of all guarded `N` byte loads
+    LoopBoundsGuard {
+        /// The register holding the `loop` index.
+        index: Register,
+    },
+    /// Wasm `i32.load` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Load`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32LoadUnchecked(LoadInstr),
+    /// Wasm `i64.load` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64LoadUnchecked(LoadInstr),
+    /// Wasm `f32.load` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::F32Load`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    F32LoadUnchecked(LoadInstr),
+    /// Wasm `f64.load` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::F64Load`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    F64LoadUnchecked(LoadInstr),
+    /// Wasm `i32.load8_s` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Load8s`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32Load8sUnchecked(LoadInstr),
+    /// Wasm `i32.load8_u` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Load8u`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32Load8uUnchecked(LoadInstr),
+    /// Wasm `i32.load16_s` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Load16s`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32Load16sUnchecked(LoadInstr),
+    /// Wasm `i32.load16_u` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Load16u`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32Load16uUnchecked(LoadInstr),
+    /// Wasm `i64.load8_s` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load8s`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load8sUnchecked(LoadInstr),
+    /// Wasm `i64.load8_u` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load8u`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load8uUnchecked(LoadInstr),
+    /// Wasm `i64.load16_s` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load16s`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load16sUnchecked(LoadInstr),
+    /// Wasm `i64.load16_u` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load16u`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load16uUnchecked(LoadInstr),
+    /// Wasm `i64.load32_s` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load32s`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load32sUnchecked(LoadInstr),
+    /// Wasm `i64.load32_u` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Load32u`] whose bounds have either been checked by the
+    /// [`Instruction::LoopBoundsGuard`] in front of its enclosing `loop` or whose out of
+    /// bounds accesses are detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I64Load32uUnchecked(LoadInstr),
+    /// Wasm `i32.store` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Store`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I32StoreUnchecked(StoreInstr),
+    /// Wasm `i32.store8` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Store8`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I32Store8Unchecked(StoreInstr),
+    /// Wasm `i32.store16` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I32Store16`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I32Store16Unchecked(StoreInstr),
+    /// Wasm `i64.store` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Store`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I64StoreUnchecked(StoreInstr),
+    /// Wasm `i64.store8` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Store8`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I64Store8Unchecked(StoreInstr),
+    /// Wasm `i64.store16` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Store16`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I64Store16Unchecked(StoreInstr),
+    /// Wasm `i64.store32` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::I64Store32`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    I64Store32Unchecked(StoreInstr),
+    /// Wasm `f32.store` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::F32Store`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    F32StoreUnchecked(StoreInstr),
+    /// Wasm `f64.store` equivalent `wasmi` instruction without bounds checks.
+    ///
+    /// # Note
+    ///
+    /// Variant of [`Instruction::F64Store`] whose out of bounds accesses are
+    /// detected via the guard pages of the linear memory.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode `value`.
+    F64StoreUnchecked(StoreInstr),
+
     /// Wasm `i32.store` equivalent `wasmi` instruction.
     ///
     /// # Encoding
@@ -2028,6 +2515,11 @@ pub enum Instruction {
     /// Variant of [`Instruction::F32Store`] for constant `address`.
     F64StoreAt(StoreAtInstr<Register>),
 
+    /// `i32` equal-to-zero comparison instruction: `r0 = r1 == 0`
+    I32Eqz(UnaryInstr),
+    /// `i64` equal-to-zero comparison instruction: `r0 = r1 == 0`
+    I64Eqz(UnaryInstr),
+
     /// `i32` equality comparison instruction: `r0 = r1 == r2`
     I32Eq(BinInstr),
     /// `i32` equality comparison instruction with immediate: `r0 = r1 == c0`
@@ -2271,6 +2763,20 @@ pub enum Instruction {
 
     /// `i32` add instruction: `r0 = r1 + r2`
     I32Add(BinInstr),
+    /// Fused `i32.load` and `i32.add` instruction: `r0 = i32.load(r1 + offset) + r2`
+    ///
+    /// # Note
+    ///
+    /// The `lhs` field holds the pointer of the load whose bounds are checked
+    /// before the addition is performed. The load always accesses the default
+    /// linear memory and therefore this [`Instruction`] is never prefixed by an
+    /// [`Instruction::UseMemory`].
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    I32AddFromMem(BinInstr),
     /// `i64` add instruction: `r0 = r1 + r2`
     I64Add(BinInstr),
     /// `i32` add (small) immediate instruction: `r0 = r1 + c0`
@@ -2889,6 +3395,146 @@ pub enum Instruction {
     F64ConvertI64S(UnaryInstr),
     /// Wasm `f64.convert_i64_u` instruction.
     F64ConvertI64U(UnaryInstr),
+
+    /// Wasm `v128.load` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    ///
+    /// Since registers are 64-bit wide a `v128` value is stored in two adjacent
+    /// registers: the register referenced by the [`Instruction`] holds the lower
+    /// 64 bits and the register after it holds the upper 64 bits of the value.
+    /// Both halves are stored in little-endian lane order, e.g. the lower register
+    /// holds the `i32x4` lanes `0` and `1` where lane `0` occupies the low 32 bits.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the load operation.
+    V128Load(LoadInstr),
+    /// Wasm `v128.store` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Register`] to encode the lower register of `value`.
+    V128Store(StoreInstr),
+    /// Wasm `i32x4.splat` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    ///
+    /// Also used for the Wasm `f32x4.splat` instruction since it broadcasts
+    /// the same 32-bit pattern to all four lanes.
+    I32x4Splat(UnaryInstr),
+    /// Wasm `i32x4.min_s` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    I32x4MinS(BinInstr),
+    /// Wasm `i32x4.min_u` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    I32x4MinU(BinInstr),
+    /// Wasm `i32x4.max_s` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    I32x4MaxS(BinInstr),
+    /// Wasm `i32x4.max_u` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    I32x4MaxU(BinInstr),
+    /// Wasm `f32x4.min` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    ///
+    /// Every lane follows the NaN propagation and zero-sign rules of [`Instruction::F32Min`].
+    F32x4Min(BinInstr),
+    /// Wasm `f32x4.max` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `simd` proposal.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    ///
+    /// Every lane follows the NaN propagation and zero-sign rules of [`Instruction::F32Max`].
+    F32x4Max(BinInstr),
+
+    /// Wasm `memory.atomic.notify` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `threads` proposal.
+    ///
+    /// The number of waiters to notify is not encoded since `wasmi` executes
+    /// single threaded and therefore there never are any waiters to notify.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the operation.
+    MemoryAtomicNotify(LoadInstr),
+    /// Wasm `memory.atomic.wait32` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `threads` proposal.
+    ///
+    /// The timeout is not encoded since `wasmi` executes single threaded
+    /// and therefore nothing could ever notify the waiter before it times out.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the operation.
+    MemoryAtomicWait32 {
+        /// Register holding the result of the instruction.
+        result: Register,
+        /// The register storing the pointer of the waited on value.
+        ptr: Register,
+        /// The register storing the expected `i32` value.
+        expected: Register,
+    },
+    /// Wasm `memory.atomic.wait64` instruction.
+    ///
+    /// # Note
+    ///
+    /// Instruction from the Wasm `threads` proposal.
+    /// Read [`Instruction::MemoryAtomicWait32`] for more information.
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `offset` for the operation.
+    MemoryAtomicWait64 {
+        /// Register holding the result of the instruction.
+        result: Register,
+        /// The register storing the pointer of the waited on value.
+        ptr: Register,
+        /// The register storing the expected `i64` value.
+        expected: Register,
+    },
 }
 
 impl Instruction {
@@ -37,6 +37,26 @@ pub enum Provider<T> {
     Const(T),
 }
 
+impl<T: Copy> Provider<T> {
+    /// Returns the [`Register`] if `self` is a [`Provider::Register`].
+    #[inline]
+    pub fn as_register(self) -> Option<Register> {
+        match self {
+            Self::Register(register) => Some(register),
+            Self::Const(_) => None,
+        }
+    }
+
+    /// Returns the constant value if `self` is a [`Provider::Const`].
+    #[inline]
+    pub fn as_const(self) -> Option<T> {
+        match self {
+            Self::Register(_) => None,
+            Self::Const(value) => Some(value),
+        }
+    }
+}
+
 /// An untyped [`Provider`].
 ///
 /// # Note
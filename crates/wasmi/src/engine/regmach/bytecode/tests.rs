@@ -1,5 +1,6 @@
 use super::*;
 use std::mem::size_of;
+use wasmi_core::UntypedValue;
 
 #[test]
 fn bytecode_size() {
@@ -10,3 +11,293 @@ fn bytecode_size() {
     assert_eq!(size_of::<BinInstrImm16<i64>>(), 6);
     assert_eq!(size_of::<Instruction>(), 8);
 }
+
+#[test]
+fn provider_as_register() {
+    for index in [i16::MIN, -1, 0, 1, i16::MAX] {
+        let register = Register::from_i16(index);
+        let provider = Provider::<AnyConst32>::Register(register);
+        assert_eq!(provider.as_register(), Some(register));
+        assert_eq!(provider.as_const(), None);
+    }
+}
+
+#[test]
+fn provider_as_const() {
+    for value in [i32::MIN, -1, 0, 1, i32::MAX - 1, i32::MAX] {
+        let provider = Provider::Const(AnyConst32::from(value));
+        assert_eq!(provider.as_const(), Some(AnyConst32::from(value)));
+        assert_eq!(provider.as_register(), None);
+        let provider = UntypedProvider::immediate(value);
+        assert_eq!(provider.as_const(), Some(UntypedValue::from(value)));
+        assert_eq!(provider.as_register(), None);
+    }
+}
+
+/// Returns the [`Register`] with the given `index`.
+fn reg(index: i16) -> Register {
+    Register::from_i16(index)
+}
+
+#[test]
+fn construct_unary() {
+    assert_eq!(
+        Instruction::i32_clz(reg(0), reg(1)),
+        Instruction::I32Clz(UnaryInstr {
+            result: reg(0),
+            input: reg(1),
+        })
+    );
+}
+
+#[test]
+fn construct_binary() {
+    assert_eq!(
+        Instruction::i32_add(reg(0), reg(1), reg(2)),
+        Instruction::I32Add(BinInstr {
+            result: reg(0),
+            lhs: reg(1),
+            rhs: reg(2),
+        })
+    );
+    assert_eq!(
+        Instruction::i32_add_imm16(reg(0), reg(1), 42),
+        Instruction::I32AddImm16(BinInstrImm16 {
+            result: reg(0),
+            reg_in: reg(1),
+            imm_in: Const16::from(42_i16),
+        })
+    );
+    // Reversed variants store their immediate `lhs` operand in `imm_in`.
+    assert_eq!(
+        Instruction::i32_sub_imm16_rev(reg(0), 42, reg(1)),
+        Instruction::I32SubImm16Rev(BinInstrImm16 {
+            result: reg(0),
+            reg_in: reg(1),
+            imm_in: Const16::from(42_i16),
+        })
+    );
+}
+
+#[test]
+fn construct_branch() {
+    let offset = BranchOffset16::from(-3);
+    assert_eq!(
+        Instruction::branch_i32_eq(reg(0), reg(1), offset),
+        Instruction::BranchI32Eq(BranchBinOpInstr {
+            lhs: reg(0),
+            rhs: reg(1),
+            offset,
+        })
+    );
+    assert_eq!(
+        Instruction::branch(BranchOffset::from(5)),
+        Instruction::Branch {
+            offset: BranchOffset::from(5),
+        }
+    );
+}
+
+#[test]
+fn construct_load() {
+    let address = Const32::from(100_u32);
+    let offset = Const16::from(8_u16);
+    assert_eq!(
+        Instruction::i32_load(reg(0), reg(1)),
+        Instruction::I32Load(LoadInstr {
+            result: reg(0),
+            ptr: reg(1),
+        })
+    );
+    assert_eq!(
+        Instruction::i32_load_at(reg(0), address),
+        Instruction::I32LoadAt(LoadAtInstr {
+            result: reg(0),
+            address,
+        })
+    );
+    assert_eq!(
+        Instruction::i32_load_offset16(reg(0), reg(1), offset),
+        Instruction::I32LoadOffset16(LoadOffset16Instr {
+            result: reg(0),
+            ptr: reg(1),
+            offset,
+        })
+    );
+}
+
+#[test]
+fn construct_store() {
+    let address = Const32::from(100_u32);
+    assert_eq!(
+        Instruction::i32_store(reg(0), address),
+        Instruction::I32Store(StoreInstr {
+            ptr: reg(0),
+            offset: address,
+        })
+    );
+    assert_eq!(
+        Instruction::i32_store_at(address, reg(1)),
+        Instruction::I32StoreAt(StoreAtInstr {
+            address,
+            value: reg(1),
+        })
+    );
+    assert_eq!(
+        Instruction::i32_store8_offset16_imm(reg(0), 8, -1),
+        Instruction::I32Store8Offset16Imm(StoreOffset16Instr {
+            ptr: reg(0),
+            offset: Const16::from(8_u16),
+            value: -1,
+        })
+    );
+}
+
+#[test]
+fn construct_simd() {
+    let address = Const32::from(100_u32);
+    assert_eq!(
+        Instruction::v128_load(reg(0), reg(2)),
+        Instruction::V128Load(LoadInstr {
+            result: reg(0),
+            ptr: reg(2),
+        })
+    );
+    assert_eq!(
+        Instruction::v128_store(reg(0), address),
+        Instruction::V128Store(StoreInstr {
+            ptr: reg(0),
+            offset: address,
+        })
+    );
+    assert_eq!(
+        Instruction::i32x4_splat(reg(0), reg(2)),
+        Instruction::I32x4Splat(UnaryInstr {
+            result: reg(0),
+            input: reg(2),
+        })
+    );
+}
+
+#[test]
+fn construct_copy_and_return() {
+    assert_eq!(
+        Instruction::copy(reg(0), reg(1)),
+        Instruction::Copy {
+            result: reg(0),
+            value: reg(1),
+        }
+    );
+    assert_eq!(
+        Instruction::return_reg(reg(2)),
+        Instruction::ReturnReg { value: reg(2) }
+    );
+    assert_eq!(
+        Instruction::return_reg2(reg(1), reg(2)),
+        Instruction::ReturnReg2 {
+            values: [reg(1), reg(2)],
+        }
+    );
+}
+
+#[test]
+fn construct_call() {
+    let results = RegisterSpan::new(reg(3));
+    let func = CompiledFunc::from_u32(7);
+    assert_eq!(
+        Instruction::call_internal(results, func),
+        Instruction::CallInternal { results, func }
+    );
+    assert_eq!(
+        Instruction::call_imported_0(results, 2),
+        Instruction::CallImported0 {
+            results,
+            func: FuncIdx::from(2),
+        }
+    );
+    assert_eq!(
+        Instruction::call_indirect(results, 4),
+        Instruction::CallIndirect {
+            results,
+            func_type: SignatureIdx::from(4),
+        }
+    );
+    assert_eq!(
+        Instruction::call_indirect_params(reg(0), 1),
+        Instruction::CallIndirectParams(CallIndirectParams {
+            index: reg(0),
+            table: TableIdx::from(1),
+        })
+    );
+    assert_eq!(
+        Instruction::return_call_internal(func),
+        Instruction::ReturnCallInternal { func }
+    );
+}
+
+#[test]
+fn construct_select_and_global() {
+    assert_eq!(
+        Instruction::select(reg(0), reg(1), reg(2)),
+        Instruction::Select {
+            result: reg(0),
+            condition: reg(1),
+            lhs: reg(2),
+        }
+    );
+    assert_eq!(
+        Instruction::global_get(reg(0), GlobalIdx::from(3)),
+        Instruction::GlobalGet {
+            result: reg(0),
+            global: GlobalIdx::from(3),
+        }
+    );
+}
+
+#[test]
+fn construct_trap_and_segments() {
+    assert_eq!(
+        Instruction::trap(TrapCode::UnreachableCodeReached),
+        Instruction::Trap(TrapCode::UnreachableCodeReached)
+    );
+    assert_eq!(
+        Instruction::elem_drop(5),
+        Instruction::ElemDrop(ElementSegmentIdx::from(5))
+    );
+    assert_eq!(
+        Instruction::data_drop(6),
+        Instruction::DataDrop(DataSegmentIdx::from(6))
+    );
+}
+
+#[test]
+fn trap_code_codec() {
+    let trap_codes = [
+        TrapCode::UnreachableCodeReached,
+        TrapCode::MemoryOutOfBounds,
+        TrapCode::TableOutOfBounds,
+        TrapCode::IndirectCallToNull,
+        TrapCode::IntegerDivisionByZero,
+        TrapCode::IntegerOverflow,
+        TrapCode::BadConversionToInteger,
+        TrapCode::StackOverflow,
+        TrapCode::BadSignature,
+        TrapCode::OutOfFuel,
+        TrapCode::GrowthOperationLimited,
+        TrapCode::RegisterOutOfBounds,
+        TrapCode::UnalignedAtomic,
+        TrapCode::ExpectedSharedMemory,
+    ];
+    for trap_code in trap_codes {
+        let mut bytes = Vec::new();
+        trap_code.encode(&mut bytes).unwrap();
+        let mut input = &bytes[..];
+        assert_eq!(TrapCode::decode(&mut input).unwrap(), trap_code);
+        assert!(input.is_empty());
+    }
+    // Trap code 11 decodes regardless of the `checked-registers` crate feature.
+    assert_eq!(
+        TrapCode::decode(&mut &[11][..]).unwrap(),
+        TrapCode::RegisterOutOfBounds
+    );
+}
@@ -1,5 +1,12 @@
 use super::{Const16, Const32};
-use crate::engine::{bytecode::TableIdx, func_builder::TranslationErrorInner, TranslationError};
+use crate::{
+    core::UntypedValue,
+    engine::{
+        bytecode::{BranchOffset, TableIdx},
+        func_builder::TranslationErrorInner,
+        TranslationError,
+    },
+};
 
 #[cfg(doc)]
 use super::Instruction;
@@ -280,6 +287,189 @@ impl<T> BinInstrImm16<T> {
     }
 }
 
+/// A 16-bit signed offset for branch instructions.
+///
+/// # Note
+///
+/// Used by branch instructions that cannot fit a full [`BranchOffset`]
+/// into their single instruction word.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BranchOffset16(i16);
+
+impl From<i16> for BranchOffset16 {
+    fn from(offset: i16) -> Self {
+        Self(offset)
+    }
+}
+
+impl TryFrom<BranchOffset> for BranchOffset16 {
+    type Error = TranslationError;
+
+    fn try_from(offset: BranchOffset) -> Result<Self, Self::Error> {
+        i16::try_from(offset.to_i32())
+            .map(Self)
+            .map_err(|_| TranslationError::new(TranslationErrorInner::BranchOffsetOutOfBounds))
+    }
+}
+
+impl From<BranchOffset16> for BranchOffset {
+    fn from(offset: BranchOffset16) -> Self {
+        Self::from(i32::from(offset.to_i16()))
+    }
+}
+
+impl BranchOffset16 {
+    /// Returns `true` if the [`BranchOffset16`] has been initialized.
+    pub fn is_init(self) -> bool {
+        self.to_i16() != 0
+    }
+
+    /// Initializes the [`BranchOffset16`] with a proper value.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`BranchOffset16`] have already been initialized.
+    /// - If the given [`BranchOffset16`] is not properly initialized.
+    pub fn init(&mut self, valid_offset: BranchOffset16) {
+        assert!(valid_offset.is_init());
+        assert!(!self.is_init());
+        *self = valid_offset;
+    }
+
+    /// Returns the `i16` representation of the [`BranchOffset16`].
+    pub fn to_i16(self) -> i16 {
+        self.0
+    }
+}
+
+/// The comparison of an [`Instruction::BranchCmpFallback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BranchComparator {
+    /// The `i32.eq` comparison.
+    I32Eq = 0,
+    /// The `i32.ne` comparison.
+    I32Ne = 1,
+    /// The `i32.lt_s` comparison.
+    I32LtS = 2,
+    /// The `i32.lt_u` comparison.
+    I32LtU = 3,
+    /// The `i32.le_s` comparison.
+    I32LeS = 4,
+    /// The `i32.le_u` comparison.
+    I32LeU = 5,
+    /// The `i32.gt_s` comparison.
+    I32GtS = 6,
+    /// The `i32.gt_u` comparison.
+    I32GtU = 7,
+    /// The `i32.ge_s` comparison.
+    I32GeS = 8,
+    /// The `i32.ge_u` comparison.
+    I32GeU = 9,
+}
+
+impl TryFrom<u32> for BranchComparator {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        let cmp = match value {
+            0 => Self::I32Eq,
+            1 => Self::I32Ne,
+            2 => Self::I32LtS,
+            3 => Self::I32LtU,
+            4 => Self::I32LeS,
+            5 => Self::I32LeU,
+            6 => Self::I32GtS,
+            7 => Self::I32GtU,
+            8 => Self::I32GeS,
+            9 => Self::I32GeU,
+            _ => return Err(value),
+        };
+        Ok(cmp)
+    }
+}
+
+/// The parameters of an [`Instruction::BranchCmpFallback`].
+///
+/// # Note
+///
+/// Stored as a single function local constant value with the
+/// [`BranchComparator`] in its low and the [`BranchOffset`] in its high 32 bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ComparatorOffsetParam {
+    /// The comparison to perform.
+    pub cmp: BranchComparator,
+    /// The branching offset for the instruction pointer.
+    pub offset: BranchOffset,
+}
+
+impl ComparatorOffsetParam {
+    /// Creates a new [`ComparatorOffsetParam`].
+    pub fn new(cmp: BranchComparator, offset: BranchOffset) -> Self {
+        Self { cmp, offset }
+    }
+
+    /// Decodes the [`ComparatorOffsetParam`] from its [`UntypedValue`] representation.
+    ///
+    /// Returns `None` if `value` does not encode a valid [`ComparatorOffsetParam`].
+    pub fn from_untyped(value: UntypedValue) -> Option<Self> {
+        let bits = value.to_bits();
+        let cmp = BranchComparator::try_from(bits as u32).ok()?;
+        let offset = BranchOffset::from((bits >> 32) as u32 as i32);
+        Some(Self { cmp, offset })
+    }
+}
+
+impl From<ComparatorOffsetParam> for UntypedValue {
+    fn from(param: ComparatorOffsetParam) -> Self {
+        let cmp = param.cmp as u64;
+        let offset = u64::from(param.offset.to_i32() as u32);
+        Self::from(cmp | (offset << 32))
+    }
+}
+
+/// A fused binary comparison and conditional branch instruction.
+///
+/// The branch is taken if the comparison of `lhs` and `rhs` evaluates to `true`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BranchBinOpInstr {
+    /// The register holding the left-hand side value.
+    pub lhs: Register,
+    /// The register holding the right-hand side value.
+    pub rhs: Register,
+    /// The branching offset for the instruction pointer.
+    pub offset: BranchOffset16,
+}
+
+impl BranchBinOpInstr {
+    /// Creates a new [`BranchBinOpInstr`].
+    pub fn new(lhs: Register, rhs: Register, offset: BranchOffset16) -> Self {
+        Self { lhs, rhs, offset }
+    }
+}
+
+/// A fused binary comparison and conditional branch instruction with immediate right-hand side value.
+///
+/// # Note
+///
+/// Optimized for small constant values that fit into 16-bit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BranchBinOpInstrImm16<T> {
+    /// The register holding the left-hand side value.
+    pub lhs: Register,
+    /// The 16-bit immediate right-hand side value.
+    pub rhs: Const16<T>,
+    /// The branching offset for the instruction pointer.
+    pub offset: BranchOffset16,
+}
+
+impl<T> BranchBinOpInstrImm16<T> {
+    /// Creates a new [`BranchBinOpInstrImm16`].
+    pub fn new(lhs: Register, rhs: Const16<T>, offset: BranchOffset16) -> Self {
+        Self { lhs, rhs, offset }
+    }
+}
+
 /// A unary instruction.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct UnaryInstr {
@@ -5,13 +5,19 @@
 //! This is the data structure specialized to handle compiled
 //! register machine based bytecode functions.
 
-use crate::engine::{
-    regmach::{bytecode::Instruction, translator::FuncLocalConstsIter},
-    CompiledFunc,
+use crate::{
+    core::TrapLocation,
+    engine::{
+        func_locations::{FuncLocations, WasmOffsets},
+        regmach::bytecode::Instruction,
+        CompiledFunc,
+        ModuleStructure,
+    },
 };
-use alloc::boxed::Box;
-use wasmi_arena::Arena;
-use wasmi_core::UntypedValue;
+use alloc::{boxed::Box, vec::Vec};
+use core::mem::{size_of, size_of_val};
+use wasmi_arena::{Arena, ArenaIndex};
+use wasmi_core::{TrapCode, UntypedValue};
 
 /// Meta information about a [`CompiledFunc`].
 #[derive(Debug)]
@@ -29,6 +35,20 @@ pub struct CompiledFuncEntity {
     len_results: u16,
     /// The constant values local to the [`CompiledFunc`].
     consts: Box<[UntypedValue]>,
+    /// The index of the Wasm function within its module that the [`CompiledFunc`] belongs to.
+    func_index: u32,
+    /// The messages of traps folded at compilation time sorted by their instruction index.
+    trap_messages: Box<[(u32, Box<str>)]>,
+    /// The information to locate traps within the Wasm binary if recorded.
+    locations: Option<Box<FuncLocations>>,
+    /// The fingerprint of the Wasm module bytes up to and including the Wasm function body.
+    ///
+    /// # Note
+    ///
+    /// This is `0` if the [`CompiledFunc`] was not translated from a Wasm module.
+    wasm_hash: u64,
+    /// The structure of the Wasm module that the [`CompiledFunc`] was translated from if any.
+    module_structure: Option<ModuleStructure>,
 }
 
 impl CompiledFuncEntity {
@@ -38,14 +58,16 @@ impl CompiledFuncEntity {
     ///
     /// - If `instrs` is empty.
     /// - If `instrs` contains more than `u32::MAX` instructions.
-    fn new<I>(
+    fn new<I, C>(
+        func_index: u32,
         len_registers: u16,
         len_results: u16,
         instrs: I,
-        func_consts: FuncLocalConstsIter,
+        func_consts: C,
     ) -> Self
     where
         I: IntoIterator<Item = Instruction>,
+        C: IntoIterator<Item = UntypedValue>,
     {
         let instrs: Box<[Instruction]> = instrs.into_iter().collect();
         assert!(
@@ -56,7 +78,12 @@ impl CompiledFuncEntity {
             instrs,
             len_registers,
             len_results,
-            consts: func_consts.collect(),
+            consts: func_consts.into_iter().collect(),
+            func_index,
+            trap_messages: [].into(),
+            locations: None,
+            wasm_hash: 0,
+            module_structure: None,
         }
     }
 
@@ -67,6 +94,11 @@ impl CompiledFuncEntity {
             len_registers: 0,
             len_results: 0,
             consts: [].into(),
+            func_index: 0,
+            trap_messages: [].into(),
+            locations: None,
+            wasm_hash: 0,
+            module_structure: None,
         }
     }
 
@@ -111,13 +143,70 @@ impl CompiledFuncEntity {
     pub fn consts(&self) -> &[UntypedValue] {
         &self.consts
     }
+
+    /// Returns the index of the Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the fingerprint of the Wasm module bytes up to and including the Wasm function body.
+    pub fn wasm_hash(&self) -> u64 {
+        self.wasm_hash
+    }
+
+    /// Returns the structure of the Wasm module that the [`CompiledFunc`] was translated from if any.
+    pub fn module_structure(&self) -> Option<&ModuleStructure> {
+        self.module_structure.as_ref()
+    }
+
+    /// Returns the message of the trap folded at compilation time at `index` if any.
+    fn trap_message(&self, index: usize) -> Option<&str> {
+        let index = u32::try_from(index).ok()?;
+        self.trap_messages
+            .binary_search_by_key(&index, |(instr, _)| *instr)
+            .ok()
+            .map(|pos| &*self.trap_messages[pos].1)
+    }
+
+    /// Returns the estimated bytes held by the [`CompiledFuncEntity`] on the heap.
+    ///
+    /// This excludes the function local constant values.
+    fn memory_usage(&self) -> usize {
+        let trap_messages = self
+            .trap_messages
+            .iter()
+            .map(|(_, message)| message.len())
+            .sum::<usize>();
+        let locations = self
+            .locations
+            .as_ref()
+            .map(|locations| size_of::<FuncLocations>() + locations.memory_usage())
+            .unwrap_or(0);
+        size_of_val(&self.instrs[..])
+            + size_of_val(&self.trap_messages[..])
+            + trap_messages
+            + locations
+    }
 }
 
 /// Datastructure to efficiently store information about compiled functions.
 #[derive(Debug, Default)]
 pub struct CodeMap {
+    /// The number of [`CompiledFunc`] allocated before the last [`CodeMap::reset`].
+    ///
+    /// The `entities` are indexed relative to `base` so that
+    /// [`CompiledFunc`] from before the reset are never reused.
+    base: usize,
     /// The headers of all compiled functions.
     entities: Arena<CompiledFunc, CompiledFuncEntity>,
+    /// Is `true` if any compiled function has messages of traps folded at compilation time.
+    has_trap_messages: bool,
+    /// Is `true` if any compiled function records its [`FuncLocations`].
+    has_locations: bool,
+    /// The address of the first [`Instruction`] of every initialized compiled function.
+    ///
+    /// Sorted by address in order to efficiently locate an [`InstructionPtr`].
+    starts: Vec<(usize, CompiledFunc)>,
 }
 
 impl CodeMap {
@@ -128,7 +217,30 @@ impl CodeMap {
     /// The uninitialized [`CompiledFunc`] must be initialized using
     /// [`CodeMap::init_func`] before it is executed.
     pub fn alloc_func(&mut self) -> CompiledFunc {
-        self.entities.alloc(CompiledFuncEntity::uninit())
+        let func = self.entities.alloc(CompiledFuncEntity::uninit());
+        CompiledFunc::from_usize(self.base + func.into_usize())
+    }
+
+    /// Removes all compiled functions from the [`CodeMap`].
+    ///
+    /// All [`CompiledFunc`] allocated so far are invalidated and
+    /// cause a panic when used with the [`CodeMap`] afterwards.
+    pub fn reset(&mut self) {
+        let base = self.base + self.entities.len();
+        *self = Self {
+            base,
+            ..Self::default()
+        };
+    }
+
+    /// Returns the index of `func` into the `entities` of the [`CodeMap`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` was allocated before the last [`CodeMap::reset`].
+    #[track_caller]
+    fn local(&self, func: CompiledFunc) -> CompiledFunc {
+        CompiledFunc::from_usize(func.index_since(self.base))
     }
 
     /// Initializes the [`CompiledFunc`].
@@ -137,32 +249,252 @@ impl CodeMap {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    pub fn init_func<I>(
+    pub fn init_func<I, C>(
         &mut self,
         func: CompiledFunc,
+        func_index: u32,
         len_registers: u16,
         len_results: u16,
-        func_locals: FuncLocalConstsIter,
+        func_locals: C,
         instrs: I,
     ) where
         I: IntoIterator<Item = Instruction>,
+        C: IntoIterator<Item = UntypedValue>,
     {
         assert!(
             self.get(func).is_uninit(),
             "func {func:?} is already initialized"
         );
+        let local = self.local(func);
         let func = self
             .entities
-            .get_mut(func)
+            .get_mut(local)
             .unwrap_or_else(|| panic!("tried to initialize invalid compiled func: {func:?}"));
-        *func = CompiledFuncEntity::new(len_registers, len_results, instrs, func_locals);
+        let wasm_hash = func.wasm_hash;
+        let module_structure = func.module_structure.take();
+        *func =
+            CompiledFuncEntity::new(func_index, len_registers, len_results, instrs, func_locals);
+        func.wasm_hash = wasm_hash;
+        func.module_structure = module_structure;
+        let start = func.instrs.as_ptr() as usize;
+        let pos = self.starts.partition_point(|(first, _)| *first < start);
+        self.starts.insert(pos, (start, local));
+    }
+
+    /// Sets the fingerprint of the Wasm module bytes up to and including the Wasm function body
+    /// and the `module_structure` of the Wasm module that `func` is translated from.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_wasm_origin(
+        &mut self,
+        func: CompiledFunc,
+        wasm_hash: u64,
+        module_structure: ModuleStructure,
+    ) {
+        let entity = self
+            .entities
+            .get_mut(self.local(func))
+            .unwrap_or_else(|| panic!("invalid compiled func: {func:?}"));
+        entity.wasm_hash = wasm_hash;
+        entity.module_structure = Some(module_structure);
+    }
+
+    /// Sets the `messages` of traps folded at compilation time for the initialized [`CompiledFunc`].
+    ///
+    /// Each message is associated to the index of its [`Instruction::Trap`] within `func`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_trap_messages<M>(&mut self, func: CompiledFunc, messages: M)
+    where
+        M: IntoIterator<Item = (u32, Box<str>)>,
+    {
+        let func = self
+            .entities
+            .get_mut(self.local(func))
+            .unwrap_or_else(|| panic!("invalid compiled func: {func:?}"));
+        let mut messages: Box<[(u32, Box<str>)]> = messages.into_iter().collect();
+        messages.sort_unstable_by_key(|(instr, _)| *instr);
+        self.has_trap_messages |= !messages.is_empty();
+        func.trap_messages = messages;
+    }
+
+    /// Returns the message of the trap folded at compilation time that `ip` points to if any.
+    ///
+    /// Returns `None` if `ip` does not point to an [`Instruction::Trap`] with `trap_code`
+    /// or if no message has been recorded for it.
+    pub fn trap_message(&self, ip: InstructionPtr, trap_code: TrapCode) -> Option<&str> {
+        if !self.has_trap_messages {
+            return None;
+        }
+        let (entity, index) = self.locate(ip)?;
+        match entity.instrs()[index] {
+            Instruction::Trap(code) if code == trap_code => entity.trap_message(index),
+            _ => None,
+        }
+    }
+
+    /// Sets the `wasm_offsets` of the initialized [`CompiledFunc`].
+    ///
+    /// The [`WasmOffsets`] are empty after this operation.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_wasm_offsets(&mut self, func: CompiledFunc, wasm_offsets: &mut WasmOffsets) {
+        self.entities
+            .get_mut(self.local(func))
+            .unwrap_or_else(|| panic!("invalid compiled func: {func:?}"))
+            .locations
+            .get_or_insert_with(Default::default)
+            .set_wasm_offsets(wasm_offsets);
+        self.has_locations = true;
+    }
+
+    /// Sets the `name` of the [`CompiledFunc`] if it records its [`FuncLocations`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_func_name(&mut self, func: CompiledFunc, name: &str) {
+        let entity = self
+            .entities
+            .get_mut(self.local(func))
+            .unwrap_or_else(|| panic!("invalid compiled func: {func:?}"));
+        if let Some(locations) = &mut entity.locations {
+            locations.set_name(name);
+        }
+    }
+
+    /// Returns the [`TrapLocation`] of the instruction that `ip` points to if any.
+    ///
+    /// Returns `None` if `ip` does not point to an [`Instruction`] of a compiled
+    /// function that records its [`FuncLocations`].
+    pub fn trap_location(&self, ip: InstructionPtr) -> Option<TrapLocation> {
+        if !self.has_locations {
+            return None;
+        }
+        let (entity, index) = self.locate(ip)?;
+        entity.locations.as_ref()?.locate(entity.func_index, index)
+    }
+
+    /// Returns `true` if `func` refers to an initialized [`CompiledFunc`] of the [`CodeMap`].
+    pub fn is_initialized(&self, func: CompiledFunc) -> bool {
+        let entity = func
+            .into_usize()
+            .checked_sub(self.base)
+            .and_then(|index| self.entities.get(CompiledFunc::from_usize(index)));
+        matches!(entity, Some(entity) if !entity.is_uninit())
+    }
+
+    /// Returns an iterator over all initialized [`CompiledFunc`] of the [`CodeMap`].
+    pub fn iter(&self) -> impl Iterator<Item = (CompiledFunc, &CompiledFuncEntity)> {
+        let base = self.base;
+        self.entities
+            .iter()
+            .filter(|(_, entity)| !entity.is_uninit())
+            .map(move |(func, entity)| (CompiledFunc::from_usize(base + func.into_usize()), entity))
+    }
+
+    /// Returns the index of the [`Instruction`] pointed to by `ip` within its [`CompiledFunc`].
+    ///
+    /// Returns `None` if `ip` does not point to an [`Instruction`] of the [`CodeMap`].
+    pub fn locate(&self, ip: InstructionPtr) -> Option<(&CompiledFuncEntity, usize)> {
+        let addr = ip.ptr as usize;
+        let pos = self.starts.partition_point(|(first, _)| *first <= addr);
+        let (start, func) = self.starts.get(pos.checked_sub(1)?)?;
+        let entity = self.entities.get(*func)?;
+        let index = (addr - start) / size_of::<Instruction>();
+        (index < entity.instrs.len()).then_some((entity, index))
+    }
+
+    /// Returns the index of the Wasm function within its module that `ip` points into.
+    ///
+    /// # Panics
+    ///
+    /// If `ip` does not point to an [`Instruction`] of the [`CodeMap`].
+    pub fn func_index_at(&self, ip: InstructionPtr) -> u32 {
+        self.locate(ip)
+            .map(|(entity, _)| entity.func_index())
+            .unwrap_or_else(|| panic!("instruction pointer is not part of the code map: {ip:?}"))
+    }
+
+    /// Returns the estimated bytes held by the compiled function bodies of the [`CodeMap`].
+    ///
+    /// This excludes the function local constant values, see [`CodeMap::consts_memory_usage`].
+    pub fn memory_usage(&self) -> usize {
+        let entities = self.entities.capacity() * size_of::<CompiledFuncEntity>()
+            + self.starts.capacity() * size_of::<(usize, CompiledFunc)>();
+        self.entities
+            .iter()
+            .fold(entities, |bytes, (_, func)| bytes + func.memory_usage())
+    }
+
+    /// Returns the estimated bytes held by the function local constant values of the [`CodeMap`].
+    pub fn consts_memory_usage(&self) -> usize {
+        self.entities
+            .iter()
+            .map(|(_, func)| size_of_val(&func.consts[..]))
+            .sum()
+    }
+
+    /// Returns the estimated bytes held by the compiled function bodies of `funcs`.
+    ///
+    /// This includes their function local constant values.
+    ///
+    /// # Panics
+    ///
+    /// If any of `funcs` was allocated before the last [`CodeMap::reset`].
+    pub fn funcs_memory_usage(&self, funcs: &[CompiledFunc]) -> usize {
+        funcs
+            .iter()
+            .map(|func| self.get(*func))
+            .map(|func| func.memory_usage() + size_of_val(&func.consts[..]))
+            .sum()
+    }
+
+    /// Deallocates the compiled function body of `func` which becomes uninitialized again.
+    ///
+    /// # Note
+    ///
+    /// The [`CompiledFunc`] must be initialized again using [`CodeMap::init_func`]
+    /// before it is executed.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn evict_func(&mut self, func: CompiledFunc) {
+        let local = self.local(func);
+        let entity = self
+            .entities
+            .get_mut(local)
+            .unwrap_or_else(|| panic!("tried to evict invalid compiled func: {func:?}"));
+        if entity.is_uninit() {
+            return;
+        }
+        let start = entity.instrs.as_ptr() as usize;
+        *entity = CompiledFuncEntity {
+            wasm_hash: entity.wasm_hash,
+            module_structure: entity.module_structure.take(),
+            ..CompiledFuncEntity::uninit()
+        };
+        let pos = self.starts.partition_point(|(first, _)| *first < start);
+        debug_assert_eq!(self.starts.get(pos), Some(&(start, local)));
+        self.starts.remove(pos);
     }
 
     /// Returns the [`CompiledFuncEntity`] of the [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` was allocated before the last [`CodeMap::reset`].
     #[track_caller]
     pub fn get(&self, func: CompiledFunc) -> &CompiledFuncEntity {
         self.entities
-            .get(func)
+            .get(self.local(func))
             .unwrap_or_else(|| panic!("invalid compiled func: {func:?}"))
     }
 }
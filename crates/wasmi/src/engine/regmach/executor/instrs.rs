@@ -20,6 +20,7 @@ use crate::{
             },
             code_map::{CodeMap, InstructionPtr},
             stack::{CallFrame, CallStack, ValueStack, ValueStackPtr},
+            InstructionProfile,
         },
     },
     store::ResourceLimiterRef,
@@ -28,10 +29,16 @@ use crate::{
     FuncRef,
     StoreInner,
 };
+#[cfg(feature = "checked-registers")]
+use core::cell::Cell;
 use core::cmp;
 
+mod atomic;
 mod binary;
+mod branch;
 mod call;
+#[cfg(feature = "checked-registers")]
+mod checked;
 mod comparison;
 mod conversion;
 mod copy;
@@ -40,22 +47,693 @@ mod load;
 mod memory;
 mod return_;
 mod select;
+mod simd;
 mod store;
 mod table;
 mod unary;
+mod wrapping;
 
 macro_rules! forward_call {
-    ($expr:expr) => {{
+    ($exit:lifetime, $expr:expr) => {{
         if let CallOutcome::Call { results, host_func } = $expr? {
-            return Ok(WasmOutcome::Call { results, host_func });
+            break $exit WasmOutcome::Call { results, host_func };
         }
     }};
 }
 
 macro_rules! forward_return {
-    ($expr:expr) => {{
+    ($exit:lifetime, $expr:expr) => {{
         if let ReturnOutcome::Host = $expr {
-            return Ok(WasmOutcome::Return);
+            break $exit WasmOutcome::Return;
+        }
+    }};
+}
+
+/// Executes the instruction at the current instruction pointer of the [`Executor`] `$this`.
+///
+/// Breaks out of the `$exit` labeled loop or block with the [`WasmOutcome`] if the
+/// execution returns to the host or calls a host function.
+/// Traps are propagated via `?` to the enclosing function.
+///
+/// # Note
+///
+/// This is a macro instead of a method so that the hot loop of [`Executor::execute`]
+/// dispatches instructions without wrapping their outcome, while single-stepping
+/// via [`Executor::execute_instr`] shares the same dispatch.
+macro_rules! dispatch_instr {
+    ($this:ident, $resource_limiter:ident, $exit:lifetime) => {{
+        use Instruction as Instr;
+        match *$this.ip.get() {
+            Instr::TableIdx(_)
+            | Instr::DataSegmentIdx(_)
+            | Instr::ElementSegmentIdx(_)
+            | Instr::Const32(_)
+            | Instr::I64Const32(_)
+            | Instr::F64Const32(_)
+            | Instr::Register(_)
+            | Instr::Register2(_)
+            | Instr::Register3(_)
+            | Instr::RegisterList(_)
+            | Instr::RegisterSpan(_)
+            | Instr::CallIndirectParams(_)
+            | Instr::CallIndirectParamsImm16(_) => $this.invalid_instruction_word()?,
+            Instr::Trap(trap_code) => $this.execute_trap(trap_code)?,
+            Instr::ConsumeFuel(block_fuel) => $this.execute_consume_fuel(block_fuel)?,
+            Instr::Return => {
+                forward_return!($exit, $this.execute_return())
+            }
+            Instr::ReturnReg { value } => {
+                forward_return!($exit, $this.execute_return_reg(value))
+            }
+            Instr::ReturnReg2 { values } => {
+                forward_return!($exit, $this.execute_return_reg2(values))
+            }
+            Instr::ReturnReg3 { values } => {
+                forward_return!($exit, $this.execute_return_reg3(values))
+            }
+            Instr::ReturnImm32 { value } => {
+                forward_return!($exit, $this.execute_return_imm32(value))
+            }
+            Instr::ReturnI64Imm32 { value } => {
+                forward_return!($exit, $this.execute_return_i64imm32(value))
+            }
+            Instr::ReturnF64Imm32 { value } => {
+                forward_return!($exit, $this.execute_return_f64imm32(value))
+            }
+            Instr::ReturnSpan { values } => {
+                forward_return!($exit, $this.execute_return_span(values))
+            }
+            Instr::ReturnMany { values } => {
+                forward_return!($exit, $this.execute_return_many(values))
+            }
+            Instr::ReturnNez { condition } => {
+                forward_return!($exit, $this.execute_return_nez(condition))
+            }
+            Instr::ReturnNezReg { condition, value } => {
+                forward_return!($exit, $this.execute_return_nez_reg(condition, value))
+            }
+            Instr::ReturnNezReg2 { condition, values } => {
+                forward_return!($exit, $this.execute_return_nez_reg2(condition, values))
+            }
+            Instr::ReturnNezImm32 { condition, value } => {
+                forward_return!($exit, $this.execute_return_nez_imm32(condition, value))
+            }
+            Instr::ReturnNezI64Imm32 { condition, value } => {
+                forward_return!($exit, $this.execute_return_nez_i64imm32(condition, value))
+            }
+            Instr::ReturnNezF64Imm32 { condition, value } => {
+                forward_return!($exit, $this.execute_return_nez_f64imm32(condition, value))
+            }
+            Instr::ReturnNezSpan { condition, values } => {
+                forward_return!($exit, $this.execute_return_nez_span(condition, values))
+            }
+            Instr::ReturnNezMany { condition, values } => {
+                forward_return!($exit, $this.execute_return_nez_many(condition, values))
+            }
+            Instr::Branch { offset } => $this.execute_branch(offset),
+            Instr::BranchEqz { condition, offset } => $this.execute_branch_eqz(condition, offset),
+            Instr::BranchNez { condition, offset } => $this.execute_branch_nez(condition, offset),
+            Instr::BranchI32Eq(instr) => $this.execute_branch_i32_eq(instr),
+            Instr::BranchI32EqImm(instr) => $this.execute_branch_i32_eq_imm(instr),
+            Instr::BranchI32Ne(instr) => $this.execute_branch_i32_ne(instr),
+            Instr::BranchI32NeImm(instr) => $this.execute_branch_i32_ne_imm(instr),
+            Instr::BranchI32LtS(instr) => $this.execute_branch_i32_lt_s(instr),
+            Instr::BranchI32LtSImm(instr) => $this.execute_branch_i32_lt_s_imm(instr),
+            Instr::BranchI32LtU(instr) => $this.execute_branch_i32_lt_u(instr),
+            Instr::BranchI32LtUImm(instr) => $this.execute_branch_i32_lt_u_imm(instr),
+            Instr::BranchI32LeS(instr) => $this.execute_branch_i32_le_s(instr),
+            Instr::BranchI32LeSImm(instr) => $this.execute_branch_i32_le_s_imm(instr),
+            Instr::BranchI32LeU(instr) => $this.execute_branch_i32_le_u(instr),
+            Instr::BranchI32LeUImm(instr) => $this.execute_branch_i32_le_u_imm(instr),
+            Instr::BranchI32GtS(instr) => $this.execute_branch_i32_gt_s(instr),
+            Instr::BranchI32GtSImm(instr) => $this.execute_branch_i32_gt_s_imm(instr),
+            Instr::BranchI32GtU(instr) => $this.execute_branch_i32_gt_u(instr),
+            Instr::BranchI32GtUImm(instr) => $this.execute_branch_i32_gt_u_imm(instr),
+            Instr::BranchI32GeS(instr) => $this.execute_branch_i32_ge_s(instr),
+            Instr::BranchI32GeSImm(instr) => $this.execute_branch_i32_ge_s_imm(instr),
+            Instr::BranchI32GeU(instr) => $this.execute_branch_i32_ge_u(instr),
+            Instr::BranchI32GeUImm(instr) => $this.execute_branch_i32_ge_u_imm(instr),
+            Instr::BranchCmpFallback { lhs, rhs, params } => {
+                $this.execute_branch_cmp_fallback(lhs, rhs, params)
+            }
+            Instr::BranchTable { index, len_targets } => {
+                $this.execute_branch_table(index, len_targets)
+            }
+            Instr::Copy { result, value } => $this.execute_copy(result, value),
+            Instr::Copy2 { results, values } => $this.execute_copy_2(results, values),
+            Instr::CopyImm32 { result, value } => $this.execute_copy_imm32(result, value),
+            Instr::CopyI64Imm32 { result, value } => $this.execute_copy_i64imm32(result, value),
+            Instr::CopyF64Imm32 { result, value } => $this.execute_copy_f64imm32(result, value),
+            Instr::CopySpan {
+                results,
+                values,
+                len,
+            } => $this.execute_copy_span(results, values, len),
+            Instr::CopySpanNonOverlapping {
+                results,
+                values,
+                len,
+            } => $this.execute_copy_span_non_overlapping(results, values, len),
+            Instr::CopyMany { results, values } => $this.execute_copy_many(results, values),
+            Instr::CopyManyNonOverlapping { results, values } => {
+                $this.execute_copy_many_non_overlapping(results, values)
+            }
+            Instr::ReturnCallInternal0 { func } => $this.execute_return_call_internal_0(func)?,
+            Instr::ReturnCallInternal { func } => $this.execute_return_call_internal(func)?,
+            Instr::ReturnCallImported0 { func } => {
+                forward_call!($exit, $this.execute_return_call_imported_0(func))
+            }
+            Instr::ReturnCallImported { func } => {
+                forward_call!($exit, $this.execute_return_call_imported(func))
+            }
+            Instr::ReturnCallIndirect0 { func_type } => {
+                forward_call!($exit, $this.execute_return_call_indirect_0(func_type))
+            }
+            Instr::ReturnCallIndirect { func_type } => {
+                forward_call!($exit, $this.execute_return_call_indirect(func_type))
+            }
+            Instr::CallInternal0 { results, func } => {
+                $this.execute_call_internal_0(results, func)?
+            }
+            Instr::CallInternal { results, func } => $this.execute_call_internal(results, func)?,
+            Instr::CallImported0 { results, func } => {
+                forward_call!($exit, $this.execute_call_imported_0(results, func))
+            }
+            Instr::CallImported { results, func } => {
+                forward_call!($exit, $this.execute_call_imported(results, func))
+            }
+            Instr::CallIndirect0 { results, func_type } => {
+                forward_call!($exit, $this.execute_call_indirect_0(results, func_type))
+            }
+            Instr::CallIndirect { results, func_type } => {
+                forward_call!($exit, $this.execute_call_indirect(results, func_type))
+            }
+            Instr::Select {
+                result,
+                condition,
+                lhs,
+            } => $this.execute_select(result, condition, lhs),
+            Instr::SelectRev {
+                result,
+                condition,
+                rhs,
+            } => $this.execute_select_rev(result, condition, rhs),
+            Instr::SelectImm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => $this.execute_select_imm32(result_or_condition, lhs_or_rhs),
+            Instr::SelectI64Imm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => $this.execute_select_i64imm32(result_or_condition, lhs_or_rhs),
+            Instr::SelectF64Imm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => $this.execute_select_f64imm32(result_or_condition, lhs_or_rhs),
+            Instr::RefFunc { result, func } => $this.execute_ref_func(result, func),
+            Instr::TableGet { result, index } => $this.execute_table_get(result, index)?,
+            Instr::TableGetImm { result, index } => $this.execute_table_get_imm(result, index)?,
+            Instr::TableSize { result, table } => $this.execute_table_size(result, table),
+            Instr::TableSet { index, value } => $this.execute_table_set(index, value)?,
+            Instr::TableSetAt { index, value } => $this.execute_table_set_at(index, value)?,
+            Instr::TableCopy { dst, src, len } => $this.execute_table_copy(dst, src, len)?,
+            Instr::TableCopyTo { dst, src, len } => $this.execute_table_copy_to(dst, src, len)?,
+            Instr::TableCopyFrom { dst, src, len } => {
+                $this.execute_table_copy_from(dst, src, len)?
+            }
+            Instr::TableCopyFromTo { dst, src, len } => {
+                $this.execute_table_copy_from_to(dst, src, len)?
+            }
+            Instr::TableCopyExact { dst, src, len } => {
+                $this.execute_table_copy_exact(dst, src, len)?
+            }
+            Instr::TableCopyToExact { dst, src, len } => {
+                $this.execute_table_copy_to_exact(dst, src, len)?
+            }
+            Instr::TableCopyFromExact { dst, src, len } => {
+                $this.execute_table_copy_from_exact(dst, src, len)?
+            }
+            Instr::TableCopyFromToExact { dst, src, len } => {
+                $this.execute_table_copy_from_to_exact(dst, src, len)?
+            }
+            Instr::TableInit { dst, src, len } => $this.execute_table_init(dst, src, len)?,
+            Instr::TableInitTo { dst, src, len } => $this.execute_table_init_to(dst, src, len)?,
+            Instr::TableInitFrom { dst, src, len } => {
+                $this.execute_table_init_from(dst, src, len)?
+            }
+            Instr::TableInitFromTo { dst, src, len } => {
+                $this.execute_table_init_from_to(dst, src, len)?
+            }
+            Instr::TableInitExact { dst, src, len } => {
+                $this.execute_table_init_exact(dst, src, len)?
+            }
+            Instr::TableInitToExact { dst, src, len } => {
+                $this.execute_table_init_to_exact(dst, src, len)?
+            }
+            Instr::TableInitFromExact { dst, src, len } => {
+                $this.execute_table_init_from_exact(dst, src, len)?
+            }
+            Instr::TableInitFromToExact { dst, src, len } => {
+                $this.execute_table_init_from_to_exact(dst, src, len)?
+            }
+            Instr::TableFill { dst, len, value } => $this.execute_table_fill(dst, len, value)?,
+            Instr::TableFillAt { dst, len, value } => {
+                $this.execute_table_fill_at(dst, len, value)?
+            }
+            Instr::TableFillExact { dst, len, value } => {
+                $this.execute_table_fill_exact(dst, len, value)?
+            }
+            Instr::TableFillAtExact { dst, len, value } => {
+                $this.execute_table_fill_at_exact(dst, len, value)?
+            }
+            Instr::TableGrow {
+                result,
+                delta,
+                value,
+            } => $this.execute_table_grow(result, delta, value, &mut *$resource_limiter)?,
+            Instr::TableGrowImm {
+                result,
+                delta,
+                value,
+            } => $this.execute_table_grow_imm(result, delta, value, &mut *$resource_limiter)?,
+            Instr::ElemDrop(element_index) => $this.execute_element_drop(element_index),
+            Instr::DataDrop(data_index) => $this.execute_data_drop(data_index),
+            Instr::UseMemory(memory) => {
+                if let Some(outcome) = $this.execute_use_memory(memory, $resource_limiter)? {
+                    break $exit outcome;
+                }
+            }
+            Instr::MemorySize { result } => $this.execute_memory_size(result),
+            Instr::MemoryGrow { result, delta } => {
+                $this.execute_memory_grow(result, delta, &mut *$resource_limiter)?
+            }
+            Instr::MemoryGrowBy { result, delta } => {
+                $this.execute_memory_grow_by(result, delta, &mut *$resource_limiter)?
+            }
+            Instr::MemoryCopy { dst, src, len } => $this.execute_memory_copy(dst, src, len)?,
+            Instr::MemoryCopyTo { dst, src, len } => $this.execute_memory_copy_to(dst, src, len)?,
+            Instr::MemoryCopyFrom { dst, src, len } => {
+                $this.execute_memory_copy_from(dst, src, len)?
+            }
+            Instr::MemoryCopyFromTo { dst, src, len } => {
+                $this.execute_memory_copy_from_to(dst, src, len)?
+            }
+            Instr::MemoryCopyExact { dst, src, len } => {
+                $this.execute_memory_copy_exact(dst, src, len)?
+            }
+            Instr::MemoryCopyToExact { dst, src, len } => {
+                $this.execute_memory_copy_to_exact(dst, src, len)?
+            }
+            Instr::MemoryCopyFromExact { dst, src, len } => {
+                $this.execute_memory_copy_from_exact(dst, src, len)?
+            }
+            Instr::MemoryCopyFromToExact { dst, src, len } => {
+                $this.execute_memory_copy_from_to_exact(dst, src, len)?
+            }
+            Instr::MemoryFill { dst, value, len } => $this.execute_memory_fill(dst, value, len)?,
+            Instr::MemoryFillAt { dst, value, len } => {
+                $this.execute_memory_fill_at(dst, value, len)?
+            }
+            Instr::MemoryFillImm { dst, value, len } => {
+                $this.execute_memory_fill_imm(dst, value, len)?
+            }
+            Instr::MemoryFillExact { dst, value, len } => {
+                $this.execute_memory_fill_exact(dst, value, len)?
+            }
+            Instr::MemoryFillAtImm { dst, value, len } => {
+                $this.execute_memory_fill_at_imm(dst, value, len)?
+            }
+            Instr::MemoryFillAtExact { dst, value, len } => {
+                $this.execute_memory_fill_at_exact(dst, value, len)?
+            }
+            Instr::MemoryFillImmExact { dst, value, len } => {
+                $this.execute_memory_fill_imm_exact(dst, value, len)?
+            }
+            Instr::MemoryFillAtImmExact { dst, value, len } => {
+                $this.execute_memory_fill_at_imm_exact(dst, value, len)?
+            }
+            Instr::MemoryInit { dst, src, len } => $this.execute_memory_init(dst, src, len)?,
+            Instr::MemoryInitTo { dst, src, len } => $this.execute_memory_init_to(dst, src, len)?,
+            Instr::MemoryInitFrom { dst, src, len } => {
+                $this.execute_memory_init_from(dst, src, len)?
+            }
+            Instr::MemoryInitFromTo { dst, src, len } => {
+                $this.execute_memory_init_from_to(dst, src, len)?
+            }
+            Instr::MemoryInitExact { dst, src, len } => {
+                $this.execute_memory_init_exact(dst, src, len)?
+            }
+            Instr::MemoryInitToExact { dst, src, len } => {
+                $this.execute_memory_init_to_exact(dst, src, len)?
+            }
+            Instr::MemoryInitFromExact { dst, src, len } => {
+                $this.execute_memory_init_from_exact(dst, src, len)?
+            }
+            Instr::MemoryInitFromToExact { dst, src, len } => {
+                $this.execute_memory_init_from_to_exact(dst, src, len)?
+            }
+            Instr::GlobalGet { result, global } => $this.execute_global_get(result, global),
+            Instr::GlobalSet { global, input } => $this.execute_global_set(global, input),
+            Instr::GlobalSetI32Imm16 { global, input } => {
+                $this.execute_global_set_i32imm16(global, input)
+            }
+            Instr::GlobalSetI64Imm16 { global, input } => {
+                $this.execute_global_set_i64imm16(global, input)
+            }
+            Instr::I32Load(instr) => $this.execute_i32_load(instr)?,
+            Instr::I32LoadAt(instr) => $this.execute_i32_load_at(instr)?,
+            Instr::I32LoadOffset16(instr) => $this.execute_i32_load_offset16(instr)?,
+            Instr::I64Load(instr) => $this.execute_i64_load(instr)?,
+            Instr::I64LoadAt(instr) => $this.execute_i64_load_at(instr)?,
+            Instr::I64LoadOffset16(instr) => $this.execute_i64_load_offset16(instr)?,
+            Instr::F32Load(instr) => $this.execute_f32_load(instr)?,
+            Instr::F32LoadAt(instr) => $this.execute_f32_load_at(instr)?,
+            Instr::F32LoadOffset16(instr) => $this.execute_f32_load_offset16(instr)?,
+            Instr::F64Load(instr) => $this.execute_f64_load(instr)?,
+            Instr::F64LoadAt(instr) => $this.execute_f64_load_at(instr)?,
+            Instr::F64LoadOffset16(instr) => $this.execute_f64_load_offset16(instr)?,
+            Instr::I32Load8s(instr) => $this.execute_i32_load8_s(instr)?,
+            Instr::I32Load8sAt(instr) => $this.execute_i32_load8_s_at(instr)?,
+            Instr::I32Load8sOffset16(instr) => $this.execute_i32_load8_s_offset16(instr)?,
+            Instr::I32Load8u(instr) => $this.execute_i32_load8_u(instr)?,
+            Instr::I32Load8uAt(instr) => $this.execute_i32_load8_u_at(instr)?,
+            Instr::I32Load8uOffset16(instr) => $this.execute_i32_load8_u_offset16(instr)?,
+            Instr::I32Load16s(instr) => $this.execute_i32_load16_s(instr)?,
+            Instr::I32Load16sAt(instr) => $this.execute_i32_load16_s_at(instr)?,
+            Instr::I32Load16sOffset16(instr) => $this.execute_i32_load16_s_offset16(instr)?,
+            Instr::I32Load16u(instr) => $this.execute_i32_load16_u(instr)?,
+            Instr::I32Load16uAt(instr) => $this.execute_i32_load16_u_at(instr)?,
+            Instr::I32Load16uOffset16(instr) => $this.execute_i32_load16_u_offset16(instr)?,
+            Instr::I64Load8s(instr) => $this.execute_i64_load8_s(instr)?,
+            Instr::I64Load8sAt(instr) => $this.execute_i64_load8_s_at(instr)?,
+            Instr::I64Load8sOffset16(instr) => $this.execute_i64_load8_s_offset16(instr)?,
+            Instr::I64Load8u(instr) => $this.execute_i64_load8_u(instr)?,
+            Instr::I64Load8uAt(instr) => $this.execute_i64_load8_u_at(instr)?,
+            Instr::I64Load8uOffset16(instr) => $this.execute_i64_load8_u_offset16(instr)?,
+            Instr::I64Load16s(instr) => $this.execute_i64_load16_s(instr)?,
+            Instr::I64Load16sAt(instr) => $this.execute_i64_load16_s_at(instr)?,
+            Instr::I64Load16sOffset16(instr) => $this.execute_i64_load16_s_offset16(instr)?,
+            Instr::I64Load16u(instr) => $this.execute_i64_load16_u(instr)?,
+            Instr::I64Load16uAt(instr) => $this.execute_i64_load16_u_at(instr)?,
+            Instr::I64Load16uOffset16(instr) => $this.execute_i64_load16_u_offset16(instr)?,
+            Instr::I64Load32s(instr) => $this.execute_i64_load32_s(instr)?,
+            Instr::I64Load32sAt(instr) => $this.execute_i64_load32_s_at(instr)?,
+            Instr::I64Load32sOffset16(instr) => $this.execute_i64_load32_s_offset16(instr)?,
+            Instr::I64Load32u(instr) => $this.execute_i64_load32_u(instr)?,
+            Instr::I64Load32uAt(instr) => $this.execute_i64_load32_u_at(instr)?,
+            Instr::I64Load32uOffset16(instr) => $this.execute_i64_load32_u_offset16(instr)?,
+            Instr::LoopBoundsGuard { index } => $this.execute_loop_bounds_guard(index)?,
+            Instr::I32LoadUnchecked(instr) => $this.execute_i32_load_unchecked(instr)?,
+            Instr::I64LoadUnchecked(instr) => $this.execute_i64_load_unchecked(instr)?,
+            Instr::F32LoadUnchecked(instr) => $this.execute_f32_load_unchecked(instr)?,
+            Instr::F64LoadUnchecked(instr) => $this.execute_f64_load_unchecked(instr)?,
+            Instr::I32Load8sUnchecked(instr) => $this.execute_i32_load8_s_unchecked(instr)?,
+            Instr::I32Load8uUnchecked(instr) => $this.execute_i32_load8_u_unchecked(instr)?,
+            Instr::I32Load16sUnchecked(instr) => $this.execute_i32_load16_s_unchecked(instr)?,
+            Instr::I32Load16uUnchecked(instr) => $this.execute_i32_load16_u_unchecked(instr)?,
+            Instr::I64Load8sUnchecked(instr) => $this.execute_i64_load8_s_unchecked(instr)?,
+            Instr::I64Load8uUnchecked(instr) => $this.execute_i64_load8_u_unchecked(instr)?,
+            Instr::I64Load16sUnchecked(instr) => $this.execute_i64_load16_s_unchecked(instr)?,
+            Instr::I64Load16uUnchecked(instr) => $this.execute_i64_load16_u_unchecked(instr)?,
+            Instr::I64Load32sUnchecked(instr) => $this.execute_i64_load32_s_unchecked(instr)?,
+            Instr::I64Load32uUnchecked(instr) => $this.execute_i64_load32_u_unchecked(instr)?,
+            Instr::I32StoreUnchecked(instr) => $this.execute_i32_store_unchecked(instr)?,
+            Instr::I32Store8Unchecked(instr) => $this.execute_i32_store8_unchecked(instr)?,
+            Instr::I32Store16Unchecked(instr) => $this.execute_i32_store16_unchecked(instr)?,
+            Instr::I64StoreUnchecked(instr) => $this.execute_i64_store_unchecked(instr)?,
+            Instr::I64Store8Unchecked(instr) => $this.execute_i64_store8_unchecked(instr)?,
+            Instr::I64Store16Unchecked(instr) => $this.execute_i64_store16_unchecked(instr)?,
+            Instr::I64Store32Unchecked(instr) => $this.execute_i64_store32_unchecked(instr)?,
+            Instr::F32StoreUnchecked(instr) => $this.execute_f32_store_unchecked(instr)?,
+            Instr::F64StoreUnchecked(instr) => $this.execute_f64_store_unchecked(instr)?,
+            Instr::I32Store(instr) => $this.execute_i32_store(instr)?,
+            Instr::I32StoreOffset16(instr) => $this.execute_i32_store_offset16(instr)?,
+            Instr::I32StoreOffset16Imm16(instr) => $this.execute_i32_store_offset16_imm16(instr)?,
+            Instr::I32StoreAt(instr) => $this.execute_i32_store_at(instr)?,
+            Instr::I32StoreAtImm16(instr) => $this.execute_i32_store_at_imm16(instr)?,
+            Instr::I32Store8(instr) => $this.execute_i32_store8(instr)?,
+            Instr::I32Store8Offset16(instr) => $this.execute_i32_store8_offset16(instr)?,
+            Instr::I32Store8Offset16Imm(instr) => $this.execute_i32_store8_offset16_imm(instr)?,
+            Instr::I32Store8At(instr) => $this.execute_i32_store8_at(instr)?,
+            Instr::I32Store8AtImm(instr) => $this.execute_i32_store8_at_imm(instr)?,
+            Instr::I32Store16(instr) => $this.execute_i32_store16(instr)?,
+            Instr::I32Store16Offset16(instr) => $this.execute_i32_store16_offset16(instr)?,
+            Instr::I32Store16Offset16Imm(instr) => $this.execute_i32_store16_offset16_imm(instr)?,
+            Instr::I32Store16At(instr) => $this.execute_i32_store16_at(instr)?,
+            Instr::I32Store16AtImm(instr) => $this.execute_i32_store16_at_imm(instr)?,
+            Instr::I64Store(instr) => $this.execute_i64_store(instr)?,
+            Instr::I64StoreOffset16(instr) => $this.execute_i64_store_offset16(instr)?,
+            Instr::I64StoreOffset16Imm16(instr) => $this.execute_i64_store_offset16_imm16(instr)?,
+            Instr::I64StoreAt(instr) => $this.execute_i64_store_at(instr)?,
+            Instr::I64StoreAtImm16(instr) => $this.execute_i64_store_at_imm16(instr)?,
+            Instr::I64Store8(instr) => $this.execute_i64_store8(instr)?,
+            Instr::I64Store8Offset16(instr) => $this.execute_i64_store8_offset16(instr)?,
+            Instr::I64Store8Offset16Imm(instr) => $this.execute_i64_store8_offset16_imm(instr)?,
+            Instr::I64Store8At(instr) => $this.execute_i64_store8_at(instr)?,
+            Instr::I64Store8AtImm(instr) => $this.execute_i64_store8_at_imm(instr)?,
+            Instr::I64Store16(instr) => $this.execute_i64_store16(instr)?,
+            Instr::I64Store16Offset16(instr) => $this.execute_i64_store16_offset16(instr)?,
+            Instr::I64Store16Offset16Imm(instr) => $this.execute_i64_store16_offset16_imm(instr)?,
+            Instr::I64Store16At(instr) => $this.execute_i64_store16_at(instr)?,
+            Instr::I64Store16AtImm(instr) => $this.execute_i64_store16_at_imm(instr)?,
+            Instr::I64Store32(instr) => $this.execute_i64_store32(instr)?,
+            Instr::I64Store32Offset16(instr) => $this.execute_i64_store32_offset16(instr)?,
+            Instr::I64Store32Offset16Imm16(instr) => {
+                $this.execute_i64_store32_offset16_imm16(instr)?
+            }
+            Instr::I64Store32At(instr) => $this.execute_i64_store32_at(instr)?,
+            Instr::I64Store32AtImm16(instr) => $this.execute_i64_store32_at_imm16(instr)?,
+            Instr::F32Store(instr) => $this.execute_f32_store(instr)?,
+            Instr::F32StoreOffset16(instr) => $this.execute_f32_store_offset16(instr)?,
+            Instr::F32StoreAt(instr) => $this.execute_f32_store_at(instr)?,
+            Instr::F64Store(instr) => $this.execute_f64_store(instr)?,
+            Instr::F64StoreOffset16(instr) => $this.execute_f64_store_offset16(instr)?,
+            Instr::F64StoreAt(instr) => $this.execute_f64_store_at(instr)?,
+            Instr::I32Eqz(instr) => $this.execute_i32_eqz(instr),
+            Instr::I64Eqz(instr) => $this.execute_i64_eqz(instr),
+            Instr::I32Eq(instr) => $this.execute_i32_eq(instr),
+            Instr::I32EqImm16(instr) => $this.execute_i32_eq_imm16(instr),
+            Instr::I32Ne(instr) => $this.execute_i32_ne(instr),
+            Instr::I32NeImm16(instr) => $this.execute_i32_ne_imm16(instr),
+            Instr::I32LtS(instr) => $this.execute_i32_lt_s(instr),
+            Instr::I32LtSImm16(instr) => $this.execute_i32_lt_s_imm16(instr),
+            Instr::I32LtU(instr) => $this.execute_i32_lt_u(instr),
+            Instr::I32LtUImm16(instr) => $this.execute_i32_lt_u_imm16(instr),
+            Instr::I32LeS(instr) => $this.execute_i32_le_s(instr),
+            Instr::I32LeSImm16(instr) => $this.execute_i32_le_s_imm16(instr),
+            Instr::I32LeU(instr) => $this.execute_i32_le_u(instr),
+            Instr::I32LeUImm16(instr) => $this.execute_i32_le_u_imm16(instr),
+            Instr::I32GtS(instr) => $this.execute_i32_gt_s(instr),
+            Instr::I32GtSImm16(instr) => $this.execute_i32_gt_s_imm16(instr),
+            Instr::I32GtU(instr) => $this.execute_i32_gt_u(instr),
+            Instr::I32GtUImm16(instr) => $this.execute_i32_gt_u_imm16(instr),
+            Instr::I32GeS(instr) => $this.execute_i32_ge_s(instr),
+            Instr::I32GeSImm16(instr) => $this.execute_i32_ge_s_imm16(instr),
+            Instr::I32GeU(instr) => $this.execute_i32_ge_u(instr),
+            Instr::I32GeUImm16(instr) => $this.execute_i32_ge_u_imm16(instr),
+            Instr::I64Eq(instr) => $this.execute_i64_eq(instr),
+            Instr::I64EqImm16(instr) => $this.execute_i64_eq_imm16(instr),
+            Instr::I64Ne(instr) => $this.execute_i64_ne(instr),
+            Instr::I64NeImm16(instr) => $this.execute_i64_ne_imm16(instr),
+            Instr::I64LtS(instr) => $this.execute_i64_lt_s(instr),
+            Instr::I64LtSImm16(instr) => $this.execute_i64_lt_s_imm16(instr),
+            Instr::I64LtU(instr) => $this.execute_i64_lt_u(instr),
+            Instr::I64LtUImm16(instr) => $this.execute_i64_lt_u_imm16(instr),
+            Instr::I64LeS(instr) => $this.execute_i64_le_s(instr),
+            Instr::I64LeSImm16(instr) => $this.execute_i64_le_s_imm16(instr),
+            Instr::I64LeU(instr) => $this.execute_i64_le_u(instr),
+            Instr::I64LeUImm16(instr) => $this.execute_i64_le_u_imm16(instr),
+            Instr::I64GtS(instr) => $this.execute_i64_gt_s(instr),
+            Instr::I64GtSImm16(instr) => $this.execute_i64_gt_s_imm16(instr),
+            Instr::I64GtU(instr) => $this.execute_i64_gt_u(instr),
+            Instr::I64GtUImm16(instr) => $this.execute_i64_gt_u_imm16(instr),
+            Instr::I64GeS(instr) => $this.execute_i64_ge_s(instr),
+            Instr::I64GeSImm16(instr) => $this.execute_i64_ge_s_imm16(instr),
+            Instr::I64GeU(instr) => $this.execute_i64_ge_u(instr),
+            Instr::I64GeUImm16(instr) => $this.execute_i64_ge_u_imm16(instr),
+            Instr::F32Eq(instr) => $this.execute_f32_eq(instr),
+            Instr::F32Ne(instr) => $this.execute_f32_ne(instr),
+            Instr::F32Lt(instr) => $this.execute_f32_lt(instr),
+            Instr::F32Le(instr) => $this.execute_f32_le(instr),
+            Instr::F32Gt(instr) => $this.execute_f32_gt(instr),
+            Instr::F32Ge(instr) => $this.execute_f32_ge(instr),
+            Instr::F64Eq(instr) => $this.execute_f64_eq(instr),
+            Instr::F64Ne(instr) => $this.execute_f64_ne(instr),
+            Instr::F64Lt(instr) => $this.execute_f64_lt(instr),
+            Instr::F64Le(instr) => $this.execute_f64_le(instr),
+            Instr::F64Gt(instr) => $this.execute_f64_gt(instr),
+            Instr::F64Ge(instr) => $this.execute_f64_ge(instr),
+            Instr::I32Clz(instr) => $this.execute_i32_clz(instr),
+            Instr::I64Clz(instr) => $this.execute_i64_clz(instr),
+            Instr::I32Ctz(instr) => $this.execute_i32_ctz(instr),
+            Instr::I64Ctz(instr) => $this.execute_i64_ctz(instr),
+            Instr::I32Popcnt(instr) => $this.execute_i32_popcnt(instr),
+            Instr::I64Popcnt(instr) => $this.execute_i64_popcnt(instr),
+            Instr::I32Add(instr) => $this.execute_i32_add(instr),
+            Instr::I32AddFromMem(instr) => $this.execute_i32_add_from_mem(instr)?,
+            Instr::I32AddImm16(instr) => $this.execute_i32_add_imm16(instr),
+            Instr::I32Sub(instr) => $this.execute_i32_sub(instr),
+            Instr::I32SubImm16(instr) => $this.execute_i32_sub_imm16(instr),
+            Instr::I32SubImm16Rev(instr) => $this.execute_i32_sub_imm16_rev(instr),
+            Instr::I32Mul(instr) => $this.execute_i32_mul(instr),
+            Instr::I32MulImm16(instr) => $this.execute_i32_mul_imm16(instr),
+            Instr::I32DivS(instr) => $this.execute_i32_div_s(instr)?,
+            Instr::I32DivSImm16(instr) => $this.execute_i32_div_s_imm16(instr)?,
+            Instr::I32DivSImm16Rev(instr) => $this.execute_i32_div_s_imm16_rev(instr)?,
+            Instr::I32DivU(instr) => $this.execute_i32_div_u(instr)?,
+            Instr::I32DivUImm16(instr) => $this.execute_i32_div_u_imm16(instr)?,
+            Instr::I32DivUImm16Rev(instr) => $this.execute_i32_div_u_imm16_rev(instr)?,
+            Instr::I32RemS(instr) => $this.execute_i32_rem_s(instr)?,
+            Instr::I32RemSImm16(instr) => $this.execute_i32_rem_s_imm16(instr)?,
+            Instr::I32RemSImm16Rev(instr) => $this.execute_i32_rem_s_imm16_rev(instr)?,
+            Instr::I32RemU(instr) => $this.execute_i32_rem_u(instr)?,
+            Instr::I32RemUImm16(instr) => $this.execute_i32_rem_u_imm16(instr)?,
+            Instr::I32RemUImm16Rev(instr) => $this.execute_i32_rem_u_imm16_rev(instr)?,
+            Instr::I32And(instr) => $this.execute_i32_and(instr),
+            Instr::I32AndImm16(instr) => $this.execute_i32_and_imm16(instr),
+            Instr::I32Or(instr) => $this.execute_i32_or(instr),
+            Instr::I32OrImm16(instr) => $this.execute_i32_or_imm16(instr),
+            Instr::I32Xor(instr) => $this.execute_i32_xor(instr),
+            Instr::I32XorImm16(instr) => $this.execute_i32_xor_imm16(instr),
+            Instr::I32Shl(instr) => $this.execute_i32_shl(instr),
+            Instr::I32ShlImm(instr) => $this.execute_i32_shl_imm(instr),
+            Instr::I32ShlImm16Rev(instr) => $this.execute_i32_shl_imm16_rev(instr),
+            Instr::I32ShrU(instr) => $this.execute_i32_shr_u(instr),
+            Instr::I32ShrUImm(instr) => $this.execute_i32_shr_u_imm(instr),
+            Instr::I32ShrUImm16Rev(instr) => $this.execute_i32_shr_u_imm16_rev(instr),
+            Instr::I32ShrS(instr) => $this.execute_i32_shr_s(instr),
+            Instr::I32ShrSImm(instr) => $this.execute_i32_shr_s_imm(instr),
+            Instr::I32ShrSImm16Rev(instr) => $this.execute_i32_shr_s_imm16_rev(instr),
+            Instr::I32Rotl(instr) => $this.execute_i32_rotl(instr),
+            Instr::I32RotlImm(instr) => $this.execute_i32_rotl_imm(instr),
+            Instr::I32RotlImm16Rev(instr) => $this.execute_i32_rotl_imm16_rev(instr),
+            Instr::I32Rotr(instr) => $this.execute_i32_rotr(instr),
+            Instr::I32RotrImm(instr) => $this.execute_i32_rotr_imm(instr),
+            Instr::I32RotrImm16Rev(instr) => $this.execute_i32_rotr_imm16_rev(instr),
+            Instr::I64Add(instr) => $this.execute_i64_add(instr),
+            Instr::I64AddImm16(instr) => $this.execute_i64_add_imm16(instr),
+            Instr::I64Sub(instr) => $this.execute_i64_sub(instr),
+            Instr::I64SubImm16(instr) => $this.execute_i64_sub_imm16(instr),
+            Instr::I64SubImm16Rev(instr) => $this.execute_i64_sub_imm16_rev(instr),
+            Instr::I64Mul(instr) => $this.execute_i64_mul(instr),
+            Instr::I64MulImm16(instr) => $this.execute_i64_mul_imm16(instr),
+            Instr::I64DivS(instr) => $this.execute_i64_div_s(instr)?,
+            Instr::I64DivSImm16(instr) => $this.execute_i64_div_s_imm16(instr)?,
+            Instr::I64DivSImm16Rev(instr) => $this.execute_i64_div_s_imm16_rev(instr)?,
+            Instr::I64DivU(instr) => $this.execute_i64_div_u(instr)?,
+            Instr::I64DivUImm16(instr) => $this.execute_i64_div_u_imm16(instr)?,
+            Instr::I64DivUImm16Rev(instr) => $this.execute_i64_div_u_imm16_rev(instr)?,
+            Instr::I64RemS(instr) => $this.execute_i64_rem_s(instr)?,
+            Instr::I64RemSImm16(instr) => $this.execute_i64_rem_s_imm16(instr)?,
+            Instr::I64RemSImm16Rev(instr) => $this.execute_i64_rem_s_imm16_rev(instr)?,
+            Instr::I64RemU(instr) => $this.execute_i64_rem_u(instr)?,
+            Instr::I64RemUImm16(instr) => $this.execute_i64_rem_u_imm16(instr)?,
+            Instr::I64RemUImm16Rev(instr) => $this.execute_i64_rem_u_imm16_rev(instr)?,
+            Instr::I64And(instr) => $this.execute_i64_and(instr),
+            Instr::I64AndImm16(instr) => $this.execute_i64_and_imm16(instr),
+            Instr::I64Or(instr) => $this.execute_i64_or(instr),
+            Instr::I64OrImm16(instr) => $this.execute_i64_or_imm16(instr),
+            Instr::I64Xor(instr) => $this.execute_i64_xor(instr),
+            Instr::I64XorImm16(instr) => $this.execute_i64_xor_imm16(instr),
+            Instr::I64Shl(instr) => $this.execute_i64_shl(instr),
+            Instr::I64ShlImm(instr) => $this.execute_i64_shl_imm(instr),
+            Instr::I64ShlImm16Rev(instr) => $this.execute_i64_shl_imm16_rev(instr),
+            Instr::I64ShrU(instr) => $this.execute_i64_shr_u(instr),
+            Instr::I64ShrUImm(instr) => $this.execute_i64_shr_u_imm(instr),
+            Instr::I64ShrUImm16Rev(instr) => $this.execute_i64_shr_u_imm16_rev(instr),
+            Instr::I64ShrS(instr) => $this.execute_i64_shr_s(instr),
+            Instr::I64ShrSImm(instr) => $this.execute_i64_shr_s_imm(instr),
+            Instr::I64ShrSImm16Rev(instr) => $this.execute_i64_shr_s_imm16_rev(instr),
+            Instr::I64Rotl(instr) => $this.execute_i64_rotl(instr),
+            Instr::I64RotlImm(instr) => $this.execute_i64_rotl_imm(instr),
+            Instr::I64RotlImm16Rev(instr) => $this.execute_i64_rotl_imm16_rev(instr),
+            Instr::I64Rotr(instr) => $this.execute_i64_rotr(instr),
+            Instr::I64RotrImm(instr) => $this.execute_i64_rotr_imm(instr),
+            Instr::I64RotrImm16Rev(instr) => $this.execute_i64_rotr_imm16_rev(instr),
+            Instr::F32Abs(instr) => $this.execute_f32_abs(instr),
+            Instr::F32Neg(instr) => $this.execute_f32_neg(instr),
+            Instr::F32Ceil(instr) => $this.execute_f32_ceil(instr),
+            Instr::F32Floor(instr) => $this.execute_f32_floor(instr),
+            Instr::F32Trunc(instr) => $this.execute_f32_trunc(instr),
+            Instr::F32Nearest(instr) => $this.execute_f32_nearest(instr),
+            Instr::F32Sqrt(instr) => $this.execute_f32_sqrt(instr),
+            Instr::F64Abs(instr) => $this.execute_f64_abs(instr),
+            Instr::F64Neg(instr) => $this.execute_f64_neg(instr),
+            Instr::F64Ceil(instr) => $this.execute_f64_ceil(instr),
+            Instr::F64Floor(instr) => $this.execute_f64_floor(instr),
+            Instr::F64Trunc(instr) => $this.execute_f64_trunc(instr),
+            Instr::F64Nearest(instr) => $this.execute_f64_nearest(instr),
+            Instr::F64Sqrt(instr) => $this.execute_f64_sqrt(instr),
+            Instr::F32Add(instr) => $this.execute_f32_add(instr),
+            Instr::F32Sub(instr) => $this.execute_f32_sub(instr),
+            Instr::F32Mul(instr) => $this.execute_f32_mul(instr),
+            Instr::F32Div(instr) => $this.execute_f32_div(instr),
+            Instr::F32Min(instr) => $this.execute_f32_min(instr),
+            Instr::F32Max(instr) => $this.execute_f32_max(instr),
+            Instr::F32Copysign(instr) => $this.execute_f32_copysign(instr),
+            Instr::F32CopysignImm(instr) => $this.execute_f32_copysign_imm(instr),
+            Instr::F64Add(instr) => $this.execute_f64_add(instr),
+            Instr::F64Sub(instr) => $this.execute_f64_sub(instr),
+            Instr::F64Mul(instr) => $this.execute_f64_mul(instr),
+            Instr::F64Div(instr) => $this.execute_f64_div(instr),
+            Instr::F64Min(instr) => $this.execute_f64_min(instr),
+            Instr::F64Max(instr) => $this.execute_f64_max(instr),
+            Instr::F64Copysign(instr) => $this.execute_f64_copysign(instr),
+            Instr::F64CopysignImm(instr) => $this.execute_f64_copysign_imm(instr),
+            Instr::I32WrapI64(instr) => $this.execute_i32_wrap_i64(instr),
+            Instr::I64ExtendI32S(instr) => $this.execute_i64_extend_i32_s(instr),
+            Instr::I64ExtendI32U(instr) => $this.execute_i64_extend_i32_u(instr),
+            Instr::I32TruncF32S(instr) => $this.execute_i32_trunc_f32_s(instr)?,
+            Instr::I32TruncF32U(instr) => $this.execute_i32_trunc_f32_u(instr)?,
+            Instr::I32TruncF64S(instr) => $this.execute_i32_trunc_f64_s(instr)?,
+            Instr::I32TruncF64U(instr) => $this.execute_i32_trunc_f64_u(instr)?,
+            Instr::I64TruncF32S(instr) => $this.execute_i64_trunc_f32_s(instr)?,
+            Instr::I64TruncF32U(instr) => $this.execute_i64_trunc_f32_u(instr)?,
+            Instr::I64TruncF64S(instr) => $this.execute_i64_trunc_f64_s(instr)?,
+            Instr::I64TruncF64U(instr) => $this.execute_i64_trunc_f64_u(instr)?,
+            Instr::I32TruncSatF32S(instr) => $this.execute_i32_trunc_sat_f32_s(instr),
+            Instr::I32TruncSatF32U(instr) => $this.execute_i32_trunc_sat_f32_u(instr),
+            Instr::I32TruncSatF64S(instr) => $this.execute_i32_trunc_sat_f64_s(instr),
+            Instr::I32TruncSatF64U(instr) => $this.execute_i32_trunc_sat_f64_u(instr),
+            Instr::I64TruncSatF32S(instr) => $this.execute_i64_trunc_sat_f32_s(instr),
+            Instr::I64TruncSatF32U(instr) => $this.execute_i64_trunc_sat_f32_u(instr),
+            Instr::I64TruncSatF64S(instr) => $this.execute_i64_trunc_sat_f64_s(instr),
+            Instr::I64TruncSatF64U(instr) => $this.execute_i64_trunc_sat_f64_u(instr),
+            Instr::I32Extend8S(instr) => $this.execute_i32_extend8_s(instr),
+            Instr::I32Extend16S(instr) => $this.execute_i32_extend16_s(instr),
+            Instr::I64Extend8S(instr) => $this.execute_i64_extend8_s(instr),
+            Instr::I64Extend16S(instr) => $this.execute_i64_extend16_s(instr),
+            Instr::I64Extend32S(instr) => $this.execute_i64_extend32_s(instr),
+            Instr::F32DemoteF64(instr) => $this.execute_f32_demote_f64(instr),
+            Instr::F64PromoteF32(instr) => $this.execute_f64_promote_f32(instr),
+            Instr::F32ConvertI32S(instr) => $this.execute_f32_convert_i32_s(instr),
+            Instr::F32ConvertI32U(instr) => $this.execute_f32_convert_i32_u(instr),
+            Instr::F32ConvertI64S(instr) => $this.execute_f32_convert_i64_s(instr),
+            Instr::F32ConvertI64U(instr) => $this.execute_f32_convert_i64_u(instr),
+            Instr::F64ConvertI32S(instr) => $this.execute_f64_convert_i32_s(instr),
+            Instr::F64ConvertI32U(instr) => $this.execute_f64_convert_i32_u(instr),
+            Instr::F64ConvertI64S(instr) => $this.execute_f64_convert_i64_s(instr),
+            Instr::F64ConvertI64U(instr) => $this.execute_f64_convert_i64_u(instr),
+            Instr::V128Load(instr) => $this.execute_v128_load(instr)?,
+            Instr::V128Store(instr) => $this.execute_v128_store(instr)?,
+            Instr::I32x4Splat(instr) => $this.execute_i32x4_splat(instr),
+            Instr::I32x4MinS(instr) => $this.execute_i32x4_min_s(instr),
+            Instr::I32x4MinU(instr) => $this.execute_i32x4_min_u(instr),
+            Instr::I32x4MaxS(instr) => $this.execute_i32x4_max_s(instr),
+            Instr::I32x4MaxU(instr) => $this.execute_i32x4_max_u(instr),
+            Instr::F32x4Min(instr) => $this.execute_f32x4_min(instr),
+            Instr::F32x4Max(instr) => $this.execute_f32x4_max(instr),
+            Instr::MemoryAtomicNotify(instr) => $this.execute_memory_atomic_notify(instr)?,
+            Instr::MemoryAtomicWait32 {
+                result,
+                ptr,
+                expected,
+            } => $this.execute_memory_atomic_wait32(result, ptr, expected)?,
+            Instr::MemoryAtomicWait64 {
+                result,
+                ptr,
+                expected,
+            } => $this.execute_memory_atomic_wait64(result, ptr, expected)?,
         }
     }};
 }
@@ -87,6 +765,7 @@ pub enum WasmOutcome {
 ///
 /// If the execution traps.
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 pub fn execute_instrs<'ctx, 'engine>(
     ctx: &'ctx mut StoreInner,
     cache: &'engine mut InstanceCache,
@@ -95,9 +774,35 @@ pub fn execute_instrs<'ctx, 'engine>(
     code_map: &'engine CodeMap,
     func_types: &'engine FuncTypeRegistry,
     resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+    profile: Option<&'engine mut InstructionProfile>,
 ) -> Result<WasmOutcome, TrapCode> {
+    let executor = Executor::new(ctx, cache, value_stack, call_stack, code_map, func_types);
+    match profile {
+        Some(profile) => executor.execute_profiled(resource_limiter, profile),
+        None => executor.execute(resource_limiter),
+    }
+}
+
+/// Executes exactly one instruction of the top most compiled function.
+///
+/// Returns `None` if the execution continues within Wasm and `Some`
+/// [`WasmOutcome`] if the executed instruction returned from the root
+/// function or called a host function.
+///
+/// # Errors
+///
+/// If the execution traps.
+pub fn execute_instr_step<'ctx, 'engine>(
+    ctx: &'ctx mut StoreInner,
+    cache: &'engine mut InstanceCache,
+    value_stack: &'engine mut ValueStack,
+    call_stack: &'engine mut CallStack,
+    code_map: &'engine CodeMap,
+    func_types: &'engine FuncTypeRegistry,
+    resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+) -> Result<Option<WasmOutcome>, TrapCode> {
     Executor::new(ctx, cache, value_stack, call_stack, code_map, func_types)
-        .execute(resource_limiter)
+        .execute_step(resource_limiter)
 }
 
 /// An execution context for executing a `wasmi` function frame.
@@ -138,6 +843,41 @@ struct Executor<'ctx, 'engine> {
     ///
     /// This is used to lookup Wasm function information.
     func_types: &'engine FuncTypeRegistry,
+    /// Is `true` if additional runtime invariants are checked.
+    ///
+    /// Read [`Config::set_checked_execution`] for more information.
+    ///
+    /// [`Config::set_checked_execution`]: crate::Config::set_checked_execution
+    #[cfg(feature = "checked-execution")]
+    checked: bool,
+    /// Is `true` if wrapping integer arithmetic is verified.
+    ///
+    /// Read [`Config::set_checked_arithmetic`] for more information.
+    ///
+    /// [`Config::set_checked_arithmetic`]: crate::Config::set_checked_arithmetic
+    #[cfg(feature = "checked-arithmetic")]
+    checked_arithmetic: bool,
+    /// Is `true` if checked wrapping integer arithmetic saturates instead.
+    #[cfg(all(test, feature = "checked-arithmetic"))]
+    corrupt_arithmetic: bool,
+    /// Is `true` if `f32.sqrt` and `f64.sqrt` are executed using an approximation.
+    ///
+    /// Read [`Config::set_approx_float_sqrt`] for more information.
+    ///
+    /// [`Config::set_approx_float_sqrt`]: crate::Config::set_approx_float_sqrt
+    approx_sqrt: bool,
+    /// Is `true` if an executed instruction accessed a register out of bounds.
+    ///
+    /// # Note
+    ///
+    /// The access itself is prevented and a [`TrapCode::RegisterOutOfBounds`]
+    /// is raised once the executed instruction has finished. Out of bounds reads
+    /// yield zero in the meantime. Most register accesses and the results of
+    /// instructions with side effects are already checked before an instruction
+    /// is executed. This catches all the remaining accesses, for example to the
+    /// upper half of `v128` values.
+    #[cfg(feature = "checked-registers")]
+    register_out_of_bounds: Cell<bool>,
 }
 
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
@@ -159,6 +899,13 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         //         valid for all register indices used by the associated function body.
         let sp = unsafe { value_stack.stack_ptr_at(frame.base_offset()) };
         let ip = frame.instr_ptr();
+        #[cfg(feature = "checked-execution")]
+        let checked = ctx.engine().config().get_checked_execution();
+        #[cfg(feature = "checked-arithmetic")]
+        let checked_arithmetic = ctx.engine().config().get_checked_arithmetic();
+        #[cfg(all(test, feature = "checked-arithmetic"))]
+        let corrupt_arithmetic = ctx.engine().config().get_corrupt_arithmetic();
+        let approx_sqrt = ctx.engine().config().get_approx_float_sqrt();
         Self {
             sp,
             ip,
@@ -168,6 +915,15 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             call_stack,
             code_map,
             func_types,
+            #[cfg(feature = "checked-execution")]
+            checked,
+            #[cfg(feature = "checked-arithmetic")]
+            checked_arithmetic,
+            #[cfg(all(test, feature = "checked-arithmetic"))]
+            corrupt_arithmetic,
+            approx_sqrt,
+            #[cfg(feature = "checked-registers")]
+            register_out_of_bounds: Cell::new(false),
         }
     }
 
@@ -177,619 +933,109 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         mut self,
         resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
     ) -> Result<WasmOutcome, TrapCode> {
-        use Instruction as Instr;
-        loop {
-            match *self.ip.get() {
-                Instr::TableIdx(_)
-                | Instr::DataSegmentIdx(_)
-                | Instr::ElementSegmentIdx(_)
-                | Instr::Const32(_)
-                | Instr::I64Const32(_)
-                | Instr::F64Const32(_)
-                | Instr::Register(_)
-                | Instr::Register2(_)
-                | Instr::Register3(_)
-                | Instr::RegisterList(_)
-                | Instr::CallIndirectParams(_)
-                | Instr::CallIndirectParamsImm16(_) => self.invalid_instruction_word()?,
-                Instr::Trap(trap_code) => self.execute_trap(trap_code)?,
-                Instr::ConsumeFuel(block_fuel) => self.execute_consume_fuel(block_fuel)?,
-                Instr::Return => {
-                    forward_return!(self.execute_return())
-                }
-                Instr::ReturnReg { value } => {
-                    forward_return!(self.execute_return_reg(value))
-                }
-                Instr::ReturnReg2 { values } => {
-                    forward_return!(self.execute_return_reg2(values))
-                }
-                Instr::ReturnReg3 { values } => {
-                    forward_return!(self.execute_return_reg3(values))
-                }
-                Instr::ReturnImm32 { value } => {
-                    forward_return!(self.execute_return_imm32(value))
-                }
-                Instr::ReturnI64Imm32 { value } => {
-                    forward_return!(self.execute_return_i64imm32(value))
-                }
-                Instr::ReturnF64Imm32 { value } => {
-                    forward_return!(self.execute_return_f64imm32(value))
-                }
-                Instr::ReturnSpan { values } => {
-                    forward_return!(self.execute_return_span(values))
-                }
-                Instr::ReturnMany { values } => {
-                    forward_return!(self.execute_return_many(values))
-                }
-                Instr::ReturnNez { condition } => {
-                    forward_return!(self.execute_return_nez(condition))
-                }
-                Instr::ReturnNezReg { condition, value } => {
-                    forward_return!(self.execute_return_nez_reg(condition, value))
-                }
-                Instr::ReturnNezReg2 { condition, values } => {
-                    forward_return!(self.execute_return_nez_reg2(condition, values))
-                }
-                Instr::ReturnNezImm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_imm32(condition, value))
-                }
-                Instr::ReturnNezI64Imm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_i64imm32(condition, value))
-                }
-                Instr::ReturnNezF64Imm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_f64imm32(condition, value))
-                }
-                Instr::ReturnNezSpan { condition, values } => {
-                    forward_return!(self.execute_return_nez_span(condition, values))
-                }
-                Instr::ReturnNezMany { condition, values } => {
-                    forward_return!(self.execute_return_nez_many(condition, values))
-                }
-                Instr::Branch { offset } => self.execute_branch(offset),
-                Instr::BranchEqz { condition, offset } => {
-                    self.execute_branch_eqz(condition, offset)
-                }
-                Instr::BranchNez { condition, offset } => {
-                    self.execute_branch_nez(condition, offset)
-                }
-                Instr::BranchTable { index, len_targets } => {
-                    self.execute_branch_table(index, len_targets)
-                }
-                Instr::Copy { result, value } => self.execute_copy(result, value),
-                Instr::Copy2 { results, values } => self.execute_copy_2(results, values),
-                Instr::CopyImm32 { result, value } => self.execute_copy_imm32(result, value),
-                Instr::CopyI64Imm32 { result, value } => self.execute_copy_i64imm32(result, value),
-                Instr::CopyF64Imm32 { result, value } => self.execute_copy_f64imm32(result, value),
-                Instr::CopySpan {
-                    results,
-                    values,
-                    len,
-                } => self.execute_copy_span(results, values, len),
-                Instr::CopySpanNonOverlapping {
-                    results,
-                    values,
-                    len,
-                } => self.execute_copy_span_non_overlapping(results, values, len),
-                Instr::CopyMany { results, values } => self.execute_copy_many(results, values),
-                Instr::CopyManyNonOverlapping { results, values } => {
-                    self.execute_copy_many_non_overlapping(results, values)
-                }
-                Instr::ReturnCallInternal0 { func } => self.execute_return_call_internal_0(func)?,
-                Instr::ReturnCallInternal { func } => self.execute_return_call_internal(func)?,
-                Instr::ReturnCallImported0 { func } => {
-                    forward_call!(self.execute_return_call_imported_0(func))
-                }
-                Instr::ReturnCallImported { func } => {
-                    forward_call!(self.execute_return_call_imported(func))
-                }
-                Instr::ReturnCallIndirect0 { func_type } => {
-                    forward_call!(self.execute_return_call_indirect_0(func_type))
-                }
-                Instr::ReturnCallIndirect { func_type } => {
-                    forward_call!(self.execute_return_call_indirect(func_type))
-                }
-                Instr::CallInternal0 { results, func } => {
-                    self.execute_call_internal_0(results, func)?
-                }
-                Instr::CallInternal { results, func } => {
-                    self.execute_call_internal(results, func)?
-                }
-                Instr::CallImported0 { results, func } => {
-                    forward_call!(self.execute_call_imported_0(results, func))
-                }
-                Instr::CallImported { results, func } => {
-                    forward_call!(self.execute_call_imported(results, func))
-                }
-                Instr::CallIndirect0 { results, func_type } => {
-                    forward_call!(self.execute_call_indirect_0(results, func_type))
-                }
-                Instr::CallIndirect { results, func_type } => {
-                    forward_call!(self.execute_call_indirect(results, func_type))
-                }
-                Instr::Select {
-                    result,
-                    condition,
-                    lhs,
-                } => self.execute_select(result, condition, lhs),
-                Instr::SelectRev {
-                    result,
-                    condition,
-                    rhs,
-                } => self.execute_select_rev(result, condition, rhs),
-                Instr::SelectImm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_imm32(result_or_condition, lhs_or_rhs),
-                Instr::SelectI64Imm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_i64imm32(result_or_condition, lhs_or_rhs),
-                Instr::SelectF64Imm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_f64imm32(result_or_condition, lhs_or_rhs),
-                Instr::RefFunc { result, func } => self.execute_ref_func(result, func),
-                Instr::TableGet { result, index } => self.execute_table_get(result, index)?,
-                Instr::TableGetImm { result, index } => {
-                    self.execute_table_get_imm(result, index)?
-                }
-                Instr::TableSize { result, table } => self.execute_table_size(result, table),
-                Instr::TableSet { index, value } => self.execute_table_set(index, value)?,
-                Instr::TableSetAt { index, value } => self.execute_table_set_at(index, value)?,
-                Instr::TableCopy { dst, src, len } => self.execute_table_copy(dst, src, len)?,
-                Instr::TableCopyTo { dst, src, len } => {
-                    self.execute_table_copy_to(dst, src, len)?
-                }
-                Instr::TableCopyFrom { dst, src, len } => {
-                    self.execute_table_copy_from(dst, src, len)?
-                }
-                Instr::TableCopyFromTo { dst, src, len } => {
-                    self.execute_table_copy_from_to(dst, src, len)?
-                }
-                Instr::TableCopyExact { dst, src, len } => {
-                    self.execute_table_copy_exact(dst, src, len)?
-                }
-                Instr::TableCopyToExact { dst, src, len } => {
-                    self.execute_table_copy_to_exact(dst, src, len)?
-                }
-                Instr::TableCopyFromExact { dst, src, len } => {
-                    self.execute_table_copy_from_exact(dst, src, len)?
-                }
-                Instr::TableCopyFromToExact { dst, src, len } => {
-                    self.execute_table_copy_from_to_exact(dst, src, len)?
-                }
-                Instr::TableInit { dst, src, len } => self.execute_table_init(dst, src, len)?,
-                Instr::TableInitTo { dst, src, len } => {
-                    self.execute_table_init_to(dst, src, len)?
-                }
-                Instr::TableInitFrom { dst, src, len } => {
-                    self.execute_table_init_from(dst, src, len)?
-                }
-                Instr::TableInitFromTo { dst, src, len } => {
-                    self.execute_table_init_from_to(dst, src, len)?
-                }
-                Instr::TableInitExact { dst, src, len } => {
-                    self.execute_table_init_exact(dst, src, len)?
-                }
-                Instr::TableInitToExact { dst, src, len } => {
-                    self.execute_table_init_to_exact(dst, src, len)?
-                }
-                Instr::TableInitFromExact { dst, src, len } => {
-                    self.execute_table_init_from_exact(dst, src, len)?
-                }
-                Instr::TableInitFromToExact { dst, src, len } => {
-                    self.execute_table_init_from_to_exact(dst, src, len)?
-                }
-                Instr::TableFill { dst, len, value } => self.execute_table_fill(dst, len, value)?,
-                Instr::TableFillAt { dst, len, value } => {
-                    self.execute_table_fill_at(dst, len, value)?
-                }
-                Instr::TableFillExact { dst, len, value } => {
-                    self.execute_table_fill_exact(dst, len, value)?
-                }
-                Instr::TableFillAtExact { dst, len, value } => {
-                    self.execute_table_fill_at_exact(dst, len, value)?
-                }
-                Instr::TableGrow {
-                    result,
-                    delta,
-                    value,
-                } => self.execute_table_grow(result, delta, value, &mut *resource_limiter)?,
-                Instr::TableGrowImm {
-                    result,
-                    delta,
-                    value,
-                } => self.execute_table_grow_imm(result, delta, value, &mut *resource_limiter)?,
-                Instr::ElemDrop(element_index) => self.execute_element_drop(element_index),
-                Instr::DataDrop(data_index) => self.execute_data_drop(data_index),
-                Instr::MemorySize { result } => self.execute_memory_size(result),
-                Instr::MemoryGrow { result, delta } => {
-                    self.execute_memory_grow(result, delta, &mut *resource_limiter)?
-                }
-                Instr::MemoryGrowBy { result, delta } => {
-                    self.execute_memory_grow_by(result, delta, &mut *resource_limiter)?
-                }
-                Instr::MemoryCopy { dst, src, len } => self.execute_memory_copy(dst, src, len)?,
-                Instr::MemoryCopyTo { dst, src, len } => {
-                    self.execute_memory_copy_to(dst, src, len)?
-                }
-                Instr::MemoryCopyFrom { dst, src, len } => {
-                    self.execute_memory_copy_from(dst, src, len)?
-                }
-                Instr::MemoryCopyFromTo { dst, src, len } => {
-                    self.execute_memory_copy_from_to(dst, src, len)?
-                }
-                Instr::MemoryCopyExact { dst, src, len } => {
-                    self.execute_memory_copy_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyToExact { dst, src, len } => {
-                    self.execute_memory_copy_to_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyFromExact { dst, src, len } => {
-                    self.execute_memory_copy_from_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyFromToExact { dst, src, len } => {
-                    self.execute_memory_copy_from_to_exact(dst, src, len)?
-                }
-                Instr::MemoryFill { dst, value, len } => {
-                    self.execute_memory_fill(dst, value, len)?
-                }
-                Instr::MemoryFillAt { dst, value, len } => {
-                    self.execute_memory_fill_at(dst, value, len)?
-                }
-                Instr::MemoryFillImm { dst, value, len } => {
-                    self.execute_memory_fill_imm(dst, value, len)?
-                }
-                Instr::MemoryFillExact { dst, value, len } => {
-                    self.execute_memory_fill_exact(dst, value, len)?
-                }
-                Instr::MemoryFillAtImm { dst, value, len } => {
-                    self.execute_memory_fill_at_imm(dst, value, len)?
-                }
-                Instr::MemoryFillAtExact { dst, value, len } => {
-                    self.execute_memory_fill_at_exact(dst, value, len)?
-                }
-                Instr::MemoryFillImmExact { dst, value, len } => {
-                    self.execute_memory_fill_imm_exact(dst, value, len)?
-                }
-                Instr::MemoryFillAtImmExact { dst, value, len } => {
-                    self.execute_memory_fill_at_imm_exact(dst, value, len)?
-                }
-                Instr::MemoryInit { dst, src, len } => self.execute_memory_init(dst, src, len)?,
-                Instr::MemoryInitTo { dst, src, len } => {
-                    self.execute_memory_init_to(dst, src, len)?
-                }
-                Instr::MemoryInitFrom { dst, src, len } => {
-                    self.execute_memory_init_from(dst, src, len)?
-                }
-                Instr::MemoryInitFromTo { dst, src, len } => {
-                    self.execute_memory_init_from_to(dst, src, len)?
-                }
-                Instr::MemoryInitExact { dst, src, len } => {
-                    self.execute_memory_init_exact(dst, src, len)?
-                }
-                Instr::MemoryInitToExact { dst, src, len } => {
-                    self.execute_memory_init_to_exact(dst, src, len)?
-                }
-                Instr::MemoryInitFromExact { dst, src, len } => {
-                    self.execute_memory_init_from_exact(dst, src, len)?
-                }
-                Instr::MemoryInitFromToExact { dst, src, len } => {
-                    self.execute_memory_init_from_to_exact(dst, src, len)?
-                }
-                Instr::GlobalGet { result, global } => self.execute_global_get(result, global),
-                Instr::GlobalSet { global, input } => self.execute_global_set(global, input),
-                Instr::GlobalSetI32Imm16 { global, input } => {
-                    self.execute_global_set_i32imm16(global, input)
-                }
-                Instr::GlobalSetI64Imm16 { global, input } => {
-                    self.execute_global_set_i64imm16(global, input)
-                }
-                Instr::I32Load(instr) => self.execute_i32_load(instr)?,
-                Instr::I32LoadAt(instr) => self.execute_i32_load_at(instr)?,
-                Instr::I32LoadOffset16(instr) => self.execute_i32_load_offset16(instr)?,
-                Instr::I64Load(instr) => self.execute_i64_load(instr)?,
-                Instr::I64LoadAt(instr) => self.execute_i64_load_at(instr)?,
-                Instr::I64LoadOffset16(instr) => self.execute_i64_load_offset16(instr)?,
-                Instr::F32Load(instr) => self.execute_f32_load(instr)?,
-                Instr::F32LoadAt(instr) => self.execute_f32_load_at(instr)?,
-                Instr::F32LoadOffset16(instr) => self.execute_f32_load_offset16(instr)?,
-                Instr::F64Load(instr) => self.execute_f64_load(instr)?,
-                Instr::F64LoadAt(instr) => self.execute_f64_load_at(instr)?,
-                Instr::F64LoadOffset16(instr) => self.execute_f64_load_offset16(instr)?,
-                Instr::I32Load8s(instr) => self.execute_i32_load8_s(instr)?,
-                Instr::I32Load8sAt(instr) => self.execute_i32_load8_s_at(instr)?,
-                Instr::I32Load8sOffset16(instr) => self.execute_i32_load8_s_offset16(instr)?,
-                Instr::I32Load8u(instr) => self.execute_i32_load8_u(instr)?,
-                Instr::I32Load8uAt(instr) => self.execute_i32_load8_u_at(instr)?,
-                Instr::I32Load8uOffset16(instr) => self.execute_i32_load8_u_offset16(instr)?,
-                Instr::I32Load16s(instr) => self.execute_i32_load16_s(instr)?,
-                Instr::I32Load16sAt(instr) => self.execute_i32_load16_s_at(instr)?,
-                Instr::I32Load16sOffset16(instr) => self.execute_i32_load16_s_offset16(instr)?,
-                Instr::I32Load16u(instr) => self.execute_i32_load16_u(instr)?,
-                Instr::I32Load16uAt(instr) => self.execute_i32_load16_u_at(instr)?,
-                Instr::I32Load16uOffset16(instr) => self.execute_i32_load16_u_offset16(instr)?,
-                Instr::I64Load8s(instr) => self.execute_i64_load8_s(instr)?,
-                Instr::I64Load8sAt(instr) => self.execute_i64_load8_s_at(instr)?,
-                Instr::I64Load8sOffset16(instr) => self.execute_i64_load8_s_offset16(instr)?,
-                Instr::I64Load8u(instr) => self.execute_i64_load8_u(instr)?,
-                Instr::I64Load8uAt(instr) => self.execute_i64_load8_u_at(instr)?,
-                Instr::I64Load8uOffset16(instr) => self.execute_i64_load8_u_offset16(instr)?,
-                Instr::I64Load16s(instr) => self.execute_i64_load16_s(instr)?,
-                Instr::I64Load16sAt(instr) => self.execute_i64_load16_s_at(instr)?,
-                Instr::I64Load16sOffset16(instr) => self.execute_i64_load16_s_offset16(instr)?,
-                Instr::I64Load16u(instr) => self.execute_i64_load16_u(instr)?,
-                Instr::I64Load16uAt(instr) => self.execute_i64_load16_u_at(instr)?,
-                Instr::I64Load16uOffset16(instr) => self.execute_i64_load16_u_offset16(instr)?,
-                Instr::I64Load32s(instr) => self.execute_i64_load32_s(instr)?,
-                Instr::I64Load32sAt(instr) => self.execute_i64_load32_s_at(instr)?,
-                Instr::I64Load32sOffset16(instr) => self.execute_i64_load32_s_offset16(instr)?,
-                Instr::I64Load32u(instr) => self.execute_i64_load32_u(instr)?,
-                Instr::I64Load32uAt(instr) => self.execute_i64_load32_u_at(instr)?,
-                Instr::I64Load32uOffset16(instr) => self.execute_i64_load32_u_offset16(instr)?,
-                Instr::I32Store(instr) => self.execute_i32_store(instr)?,
-                Instr::I32StoreOffset16(instr) => self.execute_i32_store_offset16(instr)?,
-                Instr::I32StoreOffset16Imm16(instr) => {
-                    self.execute_i32_store_offset16_imm16(instr)?
-                }
-                Instr::I32StoreAt(instr) => self.execute_i32_store_at(instr)?,
-                Instr::I32StoreAtImm16(instr) => self.execute_i32_store_at_imm16(instr)?,
-                Instr::I32Store8(instr) => self.execute_i32_store8(instr)?,
-                Instr::I32Store8Offset16(instr) => self.execute_i32_store8_offset16(instr)?,
-                Instr::I32Store8Offset16Imm(instr) => {
-                    self.execute_i32_store8_offset16_imm(instr)?
-                }
-                Instr::I32Store8At(instr) => self.execute_i32_store8_at(instr)?,
-                Instr::I32Store8AtImm(instr) => self.execute_i32_store8_at_imm(instr)?,
-                Instr::I32Store16(instr) => self.execute_i32_store16(instr)?,
-                Instr::I32Store16Offset16(instr) => self.execute_i32_store16_offset16(instr)?,
-                Instr::I32Store16Offset16Imm(instr) => {
-                    self.execute_i32_store16_offset16_imm(instr)?
-                }
-                Instr::I32Store16At(instr) => self.execute_i32_store16_at(instr)?,
-                Instr::I32Store16AtImm(instr) => self.execute_i32_store16_at_imm(instr)?,
-                Instr::I64Store(instr) => self.execute_i64_store(instr)?,
-                Instr::I64StoreOffset16(instr) => self.execute_i64_store_offset16(instr)?,
-                Instr::I64StoreOffset16Imm16(instr) => {
-                    self.execute_i64_store_offset16_imm16(instr)?
-                }
-                Instr::I64StoreAt(instr) => self.execute_i64_store_at(instr)?,
-                Instr::I64StoreAtImm16(instr) => self.execute_i64_store_at_imm16(instr)?,
-                Instr::I64Store8(instr) => self.execute_i64_store8(instr)?,
-                Instr::I64Store8Offset16(instr) => self.execute_i64_store8_offset16(instr)?,
-                Instr::I64Store8Offset16Imm(instr) => {
-                    self.execute_i64_store8_offset16_imm(instr)?
-                }
-                Instr::I64Store8At(instr) => self.execute_i64_store8_at(instr)?,
-                Instr::I64Store8AtImm(instr) => self.execute_i64_store8_at_imm(instr)?,
-                Instr::I64Store16(instr) => self.execute_i64_store16(instr)?,
-                Instr::I64Store16Offset16(instr) => self.execute_i64_store16_offset16(instr)?,
-                Instr::I64Store16Offset16Imm(instr) => {
-                    self.execute_i64_store16_offset16_imm(instr)?
-                }
-                Instr::I64Store16At(instr) => self.execute_i64_store16_at(instr)?,
-                Instr::I64Store16AtImm(instr) => self.execute_i64_store16_at_imm(instr)?,
-                Instr::I64Store32(instr) => self.execute_i64_store32(instr)?,
-                Instr::I64Store32Offset16(instr) => self.execute_i64_store32_offset16(instr)?,
-                Instr::I64Store32Offset16Imm16(instr) => {
-                    self.execute_i64_store32_offset16_imm16(instr)?
-                }
-                Instr::I64Store32At(instr) => self.execute_i64_store32_at(instr)?,
-                Instr::I64Store32AtImm16(instr) => self.execute_i64_store32_at_imm16(instr)?,
-                Instr::F32Store(instr) => self.execute_f32_store(instr)?,
-                Instr::F32StoreOffset16(instr) => self.execute_f32_store_offset16(instr)?,
-                Instr::F32StoreAt(instr) => self.execute_f32_store_at(instr)?,
-                Instr::F64Store(instr) => self.execute_f64_store(instr)?,
-                Instr::F64StoreOffset16(instr) => self.execute_f64_store_offset16(instr)?,
-                Instr::F64StoreAt(instr) => self.execute_f64_store_at(instr)?,
-                Instr::I32Eq(instr) => self.execute_i32_eq(instr),
-                Instr::I32EqImm16(instr) => self.execute_i32_eq_imm16(instr),
-                Instr::I32Ne(instr) => self.execute_i32_ne(instr),
-                Instr::I32NeImm16(instr) => self.execute_i32_ne_imm16(instr),
-                Instr::I32LtS(instr) => self.execute_i32_lt_s(instr),
-                Instr::I32LtSImm16(instr) => self.execute_i32_lt_s_imm16(instr),
-                Instr::I32LtU(instr) => self.execute_i32_lt_u(instr),
-                Instr::I32LtUImm16(instr) => self.execute_i32_lt_u_imm16(instr),
-                Instr::I32LeS(instr) => self.execute_i32_le_s(instr),
-                Instr::I32LeSImm16(instr) => self.execute_i32_le_s_imm16(instr),
-                Instr::I32LeU(instr) => self.execute_i32_le_u(instr),
-                Instr::I32LeUImm16(instr) => self.execute_i32_le_u_imm16(instr),
-                Instr::I32GtS(instr) => self.execute_i32_gt_s(instr),
-                Instr::I32GtSImm16(instr) => self.execute_i32_gt_s_imm16(instr),
-                Instr::I32GtU(instr) => self.execute_i32_gt_u(instr),
-                Instr::I32GtUImm16(instr) => self.execute_i32_gt_u_imm16(instr),
-                Instr::I32GeS(instr) => self.execute_i32_ge_s(instr),
-                Instr::I32GeSImm16(instr) => self.execute_i32_ge_s_imm16(instr),
-                Instr::I32GeU(instr) => self.execute_i32_ge_u(instr),
-                Instr::I32GeUImm16(instr) => self.execute_i32_ge_u_imm16(instr),
-                Instr::I64Eq(instr) => self.execute_i64_eq(instr),
-                Instr::I64EqImm16(instr) => self.execute_i64_eq_imm16(instr),
-                Instr::I64Ne(instr) => self.execute_i64_ne(instr),
-                Instr::I64NeImm16(instr) => self.execute_i64_ne_imm16(instr),
-                Instr::I64LtS(instr) => self.execute_i64_lt_s(instr),
-                Instr::I64LtSImm16(instr) => self.execute_i64_lt_s_imm16(instr),
-                Instr::I64LtU(instr) => self.execute_i64_lt_u(instr),
-                Instr::I64LtUImm16(instr) => self.execute_i64_lt_u_imm16(instr),
-                Instr::I64LeS(instr) => self.execute_i64_le_s(instr),
-                Instr::I64LeSImm16(instr) => self.execute_i64_le_s_imm16(instr),
-                Instr::I64LeU(instr) => self.execute_i64_le_u(instr),
-                Instr::I64LeUImm16(instr) => self.execute_i64_le_u_imm16(instr),
-                Instr::I64GtS(instr) => self.execute_i64_gt_s(instr),
-                Instr::I64GtSImm16(instr) => self.execute_i64_gt_s_imm16(instr),
-                Instr::I64GtU(instr) => self.execute_i64_gt_u(instr),
-                Instr::I64GtUImm16(instr) => self.execute_i64_gt_u_imm16(instr),
-                Instr::I64GeS(instr) => self.execute_i64_ge_s(instr),
-                Instr::I64GeSImm16(instr) => self.execute_i64_ge_s_imm16(instr),
-                Instr::I64GeU(instr) => self.execute_i64_ge_u(instr),
-                Instr::I64GeUImm16(instr) => self.execute_i64_ge_u_imm16(instr),
-                Instr::F32Eq(instr) => self.execute_f32_eq(instr),
-                Instr::F32Ne(instr) => self.execute_f32_ne(instr),
-                Instr::F32Lt(instr) => self.execute_f32_lt(instr),
-                Instr::F32Le(instr) => self.execute_f32_le(instr),
-                Instr::F32Gt(instr) => self.execute_f32_gt(instr),
-                Instr::F32Ge(instr) => self.execute_f32_ge(instr),
-                Instr::F64Eq(instr) => self.execute_f64_eq(instr),
-                Instr::F64Ne(instr) => self.execute_f64_ne(instr),
-                Instr::F64Lt(instr) => self.execute_f64_lt(instr),
-                Instr::F64Le(instr) => self.execute_f64_le(instr),
-                Instr::F64Gt(instr) => self.execute_f64_gt(instr),
-                Instr::F64Ge(instr) => self.execute_f64_ge(instr),
-                Instr::I32Clz(instr) => self.execute_i32_clz(instr),
-                Instr::I64Clz(instr) => self.execute_i64_clz(instr),
-                Instr::I32Ctz(instr) => self.execute_i32_ctz(instr),
-                Instr::I64Ctz(instr) => self.execute_i64_ctz(instr),
-                Instr::I32Popcnt(instr) => self.execute_i32_popcnt(instr),
-                Instr::I64Popcnt(instr) => self.execute_i64_popcnt(instr),
-                Instr::I32Add(instr) => self.execute_i32_add(instr),
-                Instr::I32AddImm16(instr) => self.execute_i32_add_imm16(instr),
-                Instr::I32Sub(instr) => self.execute_i32_sub(instr),
-                Instr::I32SubImm16(instr) => self.execute_i32_sub_imm16(instr),
-                Instr::I32SubImm16Rev(instr) => self.execute_i32_sub_imm16_rev(instr),
-                Instr::I32Mul(instr) => self.execute_i32_mul(instr),
-                Instr::I32MulImm16(instr) => self.execute_i32_mul_imm16(instr),
-                Instr::I32DivS(instr) => self.execute_i32_div_s(instr)?,
-                Instr::I32DivSImm16(instr) => self.execute_i32_div_s_imm16(instr)?,
-                Instr::I32DivSImm16Rev(instr) => self.execute_i32_div_s_imm16_rev(instr)?,
-                Instr::I32DivU(instr) => self.execute_i32_div_u(instr)?,
-                Instr::I32DivUImm16(instr) => self.execute_i32_div_u_imm16(instr)?,
-                Instr::I32DivUImm16Rev(instr) => self.execute_i32_div_u_imm16_rev(instr)?,
-                Instr::I32RemS(instr) => self.execute_i32_rem_s(instr)?,
-                Instr::I32RemSImm16(instr) => self.execute_i32_rem_s_imm16(instr)?,
-                Instr::I32RemSImm16Rev(instr) => self.execute_i32_rem_s_imm16_rev(instr)?,
-                Instr::I32RemU(instr) => self.execute_i32_rem_u(instr)?,
-                Instr::I32RemUImm16(instr) => self.execute_i32_rem_u_imm16(instr)?,
-                Instr::I32RemUImm16Rev(instr) => self.execute_i32_rem_u_imm16_rev(instr)?,
-                Instr::I32And(instr) => self.execute_i32_and(instr),
-                Instr::I32AndImm16(instr) => self.execute_i32_and_imm16(instr),
-                Instr::I32Or(instr) => self.execute_i32_or(instr),
-                Instr::I32OrImm16(instr) => self.execute_i32_or_imm16(instr),
-                Instr::I32Xor(instr) => self.execute_i32_xor(instr),
-                Instr::I32XorImm16(instr) => self.execute_i32_xor_imm16(instr),
-                Instr::I32Shl(instr) => self.execute_i32_shl(instr),
-                Instr::I32ShlImm(instr) => self.execute_i32_shl_imm(instr),
-                Instr::I32ShlImm16Rev(instr) => self.execute_i32_shl_imm16_rev(instr),
-                Instr::I32ShrU(instr) => self.execute_i32_shr_u(instr),
-                Instr::I32ShrUImm(instr) => self.execute_i32_shr_u_imm(instr),
-                Instr::I32ShrUImm16Rev(instr) => self.execute_i32_shr_u_imm16_rev(instr),
-                Instr::I32ShrS(instr) => self.execute_i32_shr_s(instr),
-                Instr::I32ShrSImm(instr) => self.execute_i32_shr_s_imm(instr),
-                Instr::I32ShrSImm16Rev(instr) => self.execute_i32_shr_s_imm16_rev(instr),
-                Instr::I32Rotl(instr) => self.execute_i32_rotl(instr),
-                Instr::I32RotlImm(instr) => self.execute_i32_rotl_imm(instr),
-                Instr::I32RotlImm16Rev(instr) => self.execute_i32_rotl_imm16_rev(instr),
-                Instr::I32Rotr(instr) => self.execute_i32_rotr(instr),
-                Instr::I32RotrImm(instr) => self.execute_i32_rotr_imm(instr),
-                Instr::I32RotrImm16Rev(instr) => self.execute_i32_rotr_imm16_rev(instr),
-                Instr::I64Add(instr) => self.execute_i64_add(instr),
-                Instr::I64AddImm16(instr) => self.execute_i64_add_imm16(instr),
-                Instr::I64Sub(instr) => self.execute_i64_sub(instr),
-                Instr::I64SubImm16(instr) => self.execute_i64_sub_imm16(instr),
-                Instr::I64SubImm16Rev(instr) => self.execute_i64_sub_imm16_rev(instr),
-                Instr::I64Mul(instr) => self.execute_i64_mul(instr),
-                Instr::I64MulImm16(instr) => self.execute_i64_mul_imm16(instr),
-                Instr::I64DivS(instr) => self.execute_i64_div_s(instr)?,
-                Instr::I64DivSImm16(instr) => self.execute_i64_div_s_imm16(instr)?,
-                Instr::I64DivSImm16Rev(instr) => self.execute_i64_div_s_imm16_rev(instr)?,
-                Instr::I64DivU(instr) => self.execute_i64_div_u(instr)?,
-                Instr::I64DivUImm16(instr) => self.execute_i64_div_u_imm16(instr)?,
-                Instr::I64DivUImm16Rev(instr) => self.execute_i64_div_u_imm16_rev(instr)?,
-                Instr::I64RemS(instr) => self.execute_i64_rem_s(instr)?,
-                Instr::I64RemSImm16(instr) => self.execute_i64_rem_s_imm16(instr)?,
-                Instr::I64RemSImm16Rev(instr) => self.execute_i64_rem_s_imm16_rev(instr)?,
-                Instr::I64RemU(instr) => self.execute_i64_rem_u(instr)?,
-                Instr::I64RemUImm16(instr) => self.execute_i64_rem_u_imm16(instr)?,
-                Instr::I64RemUImm16Rev(instr) => self.execute_i64_rem_u_imm16_rev(instr)?,
-                Instr::I64And(instr) => self.execute_i64_and(instr),
-                Instr::I64AndImm16(instr) => self.execute_i64_and_imm16(instr),
-                Instr::I64Or(instr) => self.execute_i64_or(instr),
-                Instr::I64OrImm16(instr) => self.execute_i64_or_imm16(instr),
-                Instr::I64Xor(instr) => self.execute_i64_xor(instr),
-                Instr::I64XorImm16(instr) => self.execute_i64_xor_imm16(instr),
-                Instr::I64Shl(instr) => self.execute_i64_shl(instr),
-                Instr::I64ShlImm(instr) => self.execute_i64_shl_imm(instr),
-                Instr::I64ShlImm16Rev(instr) => self.execute_i64_shl_imm16_rev(instr),
-                Instr::I64ShrU(instr) => self.execute_i64_shr_u(instr),
-                Instr::I64ShrUImm(instr) => self.execute_i64_shr_u_imm(instr),
-                Instr::I64ShrUImm16Rev(instr) => self.execute_i64_shr_u_imm16_rev(instr),
-                Instr::I64ShrS(instr) => self.execute_i64_shr_s(instr),
-                Instr::I64ShrSImm(instr) => self.execute_i64_shr_s_imm(instr),
-                Instr::I64ShrSImm16Rev(instr) => self.execute_i64_shr_s_imm16_rev(instr),
-                Instr::I64Rotl(instr) => self.execute_i64_rotl(instr),
-                Instr::I64RotlImm(instr) => self.execute_i64_rotl_imm(instr),
-                Instr::I64RotlImm16Rev(instr) => self.execute_i64_rotl_imm16_rev(instr),
-                Instr::I64Rotr(instr) => self.execute_i64_rotr(instr),
-                Instr::I64RotrImm(instr) => self.execute_i64_rotr_imm(instr),
-                Instr::I64RotrImm16Rev(instr) => self.execute_i64_rotr_imm16_rev(instr),
-                Instr::F32Abs(instr) => self.execute_f32_abs(instr),
-                Instr::F32Neg(instr) => self.execute_f32_neg(instr),
-                Instr::F32Ceil(instr) => self.execute_f32_ceil(instr),
-                Instr::F32Floor(instr) => self.execute_f32_floor(instr),
-                Instr::F32Trunc(instr) => self.execute_f32_trunc(instr),
-                Instr::F32Nearest(instr) => self.execute_f32_nearest(instr),
-                Instr::F32Sqrt(instr) => self.execute_f32_sqrt(instr),
-                Instr::F64Abs(instr) => self.execute_f64_abs(instr),
-                Instr::F64Neg(instr) => self.execute_f64_neg(instr),
-                Instr::F64Ceil(instr) => self.execute_f64_ceil(instr),
-                Instr::F64Floor(instr) => self.execute_f64_floor(instr),
-                Instr::F64Trunc(instr) => self.execute_f64_trunc(instr),
-                Instr::F64Nearest(instr) => self.execute_f64_nearest(instr),
-                Instr::F64Sqrt(instr) => self.execute_f64_sqrt(instr),
-                Instr::F32Add(instr) => self.execute_f32_add(instr),
-                Instr::F32Sub(instr) => self.execute_f32_sub(instr),
-                Instr::F32Mul(instr) => self.execute_f32_mul(instr),
-                Instr::F32Div(instr) => self.execute_f32_div(instr),
-                Instr::F32Min(instr) => self.execute_f32_min(instr),
-                Instr::F32Max(instr) => self.execute_f32_max(instr),
-                Instr::F32Copysign(instr) => self.execute_f32_copysign(instr),
-                Instr::F32CopysignImm(instr) => self.execute_f32_copysign_imm(instr),
-                Instr::F64Add(instr) => self.execute_f64_add(instr),
-                Instr::F64Sub(instr) => self.execute_f64_sub(instr),
-                Instr::F64Mul(instr) => self.execute_f64_mul(instr),
-                Instr::F64Div(instr) => self.execute_f64_div(instr),
-                Instr::F64Min(instr) => self.execute_f64_min(instr),
-                Instr::F64Max(instr) => self.execute_f64_max(instr),
-                Instr::F64Copysign(instr) => self.execute_f64_copysign(instr),
-                Instr::F64CopysignImm(instr) => self.execute_f64_copysign_imm(instr),
-                Instr::I32WrapI64(instr) => self.execute_i32_wrap_i64(instr),
-                Instr::I64ExtendI32S(instr) => self.execute_i64_extend_i32_s(instr),
-                Instr::I64ExtendI32U(instr) => self.execute_i64_extend_i32_u(instr),
-                Instr::I32TruncF32S(instr) => self.execute_i32_trunc_f32_s(instr)?,
-                Instr::I32TruncF32U(instr) => self.execute_i32_trunc_f32_u(instr)?,
-                Instr::I32TruncF64S(instr) => self.execute_i32_trunc_f64_s(instr)?,
-                Instr::I32TruncF64U(instr) => self.execute_i32_trunc_f64_u(instr)?,
-                Instr::I64TruncF32S(instr) => self.execute_i64_trunc_f32_s(instr)?,
-                Instr::I64TruncF32U(instr) => self.execute_i64_trunc_f32_u(instr)?,
-                Instr::I64TruncF64S(instr) => self.execute_i64_trunc_f64_s(instr)?,
-                Instr::I64TruncF64U(instr) => self.execute_i64_trunc_f64_u(instr)?,
-                Instr::I32TruncSatF32S(instr) => self.execute_i32_trunc_sat_f32_s(instr),
-                Instr::I32TruncSatF32U(instr) => self.execute_i32_trunc_sat_f32_u(instr),
-                Instr::I32TruncSatF64S(instr) => self.execute_i32_trunc_sat_f64_s(instr),
-                Instr::I32TruncSatF64U(instr) => self.execute_i32_trunc_sat_f64_u(instr),
-                Instr::I64TruncSatF32S(instr) => self.execute_i64_trunc_sat_f32_s(instr),
-                Instr::I64TruncSatF32U(instr) => self.execute_i64_trunc_sat_f32_u(instr),
-                Instr::I64TruncSatF64S(instr) => self.execute_i64_trunc_sat_f64_s(instr),
-                Instr::I64TruncSatF64U(instr) => self.execute_i64_trunc_sat_f64_u(instr),
-                Instr::I32Extend8S(instr) => self.execute_i32_extend8_s(instr),
-                Instr::I32Extend16S(instr) => self.execute_i32_extend16_s(instr),
-                Instr::I64Extend8S(instr) => self.execute_i64_extend8_s(instr),
-                Instr::I64Extend16S(instr) => self.execute_i64_extend16_s(instr),
-                Instr::I64Extend32S(instr) => self.execute_i64_extend32_s(instr),
-                Instr::F32DemoteF64(instr) => self.execute_f32_demote_f64(instr),
-                Instr::F64PromoteF32(instr) => self.execute_f64_promote_f32(instr),
-                Instr::F32ConvertI32S(instr) => self.execute_f32_convert_i32_s(instr),
-                Instr::F32ConvertI32U(instr) => self.execute_f32_convert_i32_u(instr),
-                Instr::F32ConvertI64S(instr) => self.execute_f32_convert_i64_s(instr),
-                Instr::F32ConvertI64U(instr) => self.execute_f32_convert_i64_u(instr),
-                Instr::F64ConvertI32S(instr) => self.execute_f64_convert_i32_s(instr),
-                Instr::F64ConvertI32U(instr) => self.execute_f64_convert_i32_u(instr),
-                Instr::F64ConvertI64S(instr) => self.execute_f64_convert_i64_s(instr),
-                Instr::F64ConvertI64U(instr) => self.execute_f64_convert_i64_u(instr),
-            }
+        let outcome = self.execute_loop(resource_limiter, |_| {});
+        #[cfg(feature = "checked-registers")]
+        let outcome = self.check_register_accesses().and(outcome);
+        outcome.map_err(|trap_code| self.sync_trap(trap_code))
+    }
+
+    /// Executes the function frame until it returns or traps and counts
+    /// all executed instructions in `profile`.
+    ///
+    /// # Note
+    ///
+    /// This is a separate instantiation of [`Executor::execute_loop`]
+    /// so that [`Executor::execute`] does not pay for instruction profiling.
+    #[inline(never)]
+    fn execute_profiled(
+        mut self,
+        resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+        profile: &mut InstructionProfile,
+    ) -> Result<WasmOutcome, TrapCode> {
+        let outcome = self.execute_loop(resource_limiter, |instr| profile.record(instr));
+        #[cfg(feature = "checked-registers")]
+        let outcome = self.check_register_accesses().and(outcome);
+        outcome.map_err(|trap_code| self.sync_trap(trap_code))
+    }
+
+    /// Executes instructions until the function frame returns, calls a host function or traps.
+    ///
+    /// Calls `on_instr` before executing each instruction.
+    #[inline(always)]
+    fn execute_loop(
+        &mut self,
+        resource_limiter: &mut ResourceLimiterRef<'ctx>,
+        mut on_instr: impl FnMut(&Instruction),
+    ) -> Result<WasmOutcome, TrapCode> {
+        let outcome = 'exec: loop {
+            on_instr(self.ip.get());
+            #[cfg(feature = "checked-registers")]
+            self.check_instr_registers()?;
+            dispatch_instr!(self, resource_limiter, 'exec);
+            #[cfg(feature = "checked-registers")]
+            self.check_register_accesses()?;
+        };
+        #[cfg(feature = "checked-registers")]
+        self.check_register_accesses()?;
+        Ok(outcome)
+    }
+
+    /// Executes exactly one instruction of the function frame and syncs
+    /// the instruction pointer back into the top most [`CallFrame`].
+    fn execute_step(
+        mut self,
+        resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+    ) -> Result<Option<WasmOutcome>, TrapCode> {
+        let outcome = self.execute_instr(resource_limiter);
+        #[cfg(feature = "checked-registers")]
+        let outcome = self.check_register_accesses().and(outcome);
+        let outcome = outcome.map_err(|trap_code| self.sync_trap(trap_code))?;
+        if outcome.is_none() {
+            // Note: Calls to host functions and returns to the host already
+            //       synced all state back into the call stack.
+            self.call_stack
+                .peek_mut()
+                .expect("must have call frame on the call stack")
+                .update_instr_ptr(self.ip);
         }
+        Ok(outcome)
+    }
+
+    /// Executes the instruction at the current instruction pointer.
+    ///
+    /// Returns `Some` [`WasmOutcome`] if the execution returns to the host
+    /// or calls a host function.
+    ///
+    /// # Note
+    ///
+    /// Used for single-stepping only since wrapping the outcome of every
+    /// instruction slows down the hot loop of [`Executor::execute`].
+    fn execute_instr(
+        &mut self,
+        resource_limiter: &mut ResourceLimiterRef<'ctx>,
+    ) -> Result<Option<WasmOutcome>, TrapCode> {
+        #[cfg(feature = "checked-registers")]
+        self.check_instr_registers()?;
+        if let Some(result) = self.try_execute_load_unchecked_stepped() {
+            result?;
+            return Ok(None);
+        }
+        let outcome = 'instr: {
+            dispatch_instr!(self, resource_limiter, 'instr);
+            return Ok(None);
+        };
+        Ok(Some(outcome))
     }
 
     /// Returns the [`Register`] value.
     fn get_register(&self, register: Register) -> UntypedValue {
+        #[cfg(feature = "checked-execution")]
+        self.check_register(register);
+        #[cfg(feature = "checked-registers")]
+        if !self.is_register_in_bounds(register) {
+            self.register_out_of_bounds.set(true);
+            return UntypedValue::default();
+        }
         // Safety: TODO
         unsafe { self.sp.get(register) }
     }
@@ -804,11 +1050,58 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
 
     /// Sets the [`Register`] value to `value`.
     fn set_register(&mut self, register: Register, value: impl Into<UntypedValue>) {
+        #[cfg(feature = "checked-execution")]
+        self.check_register(register);
+        #[cfg(feature = "checked-registers")]
+        if !self.is_register_in_bounds(register) {
+            self.register_out_of_bounds.set(true);
+            return;
+        }
         // Safety: TODO
         let cell = unsafe { self.sp.get_mut(register) };
         *cell = value.into();
     }
 
+    /// Checks that `register` is within the bounds of the current call frame.
+    ///
+    /// # Note
+    ///
+    /// This only checks anything if enabled via [`Config::set_checked_execution`].
+    ///
+    /// # Panics
+    ///
+    /// - If the current call frame is inconsistent with the value stack.
+    /// - If `register` is out of bounds for the current call frame.
+    ///
+    /// [`Config::set_checked_execution`]: crate::Config::set_checked_execution
+    #[cfg(feature = "checked-execution")]
+    fn check_register(&self, register: Register) {
+        if !self.checked {
+            return;
+        }
+        let frame = self
+            .call_stack
+            .peek()
+            .expect("checked execution: missing call frame");
+        let frame_offset = usize::from(frame.frame_offset());
+        let base_offset = usize::from(frame.base_offset());
+        let len_values = self.value_stack.len();
+        assert!(
+            frame_offset <= base_offset && base_offset <= len_values,
+            "checked execution: inconsistent call frame \
+            (frame offset: {frame_offset}, base offset: {base_offset}, stack height: {len_values})",
+        );
+        let index = isize::from(register.to_i16());
+        let min = -((base_offset - frame_offset) as isize);
+        let max = (len_values - base_offset) as isize;
+        assert!(
+            (min..max).contains(&index),
+            "checked execution: out of bounds access to {register:?} at {:?} \
+            (valid register indices: {min}..{max})",
+            self.ip,
+        );
+    }
+
     /// Shifts the instruction pointer to the next instruction.
     #[inline(always)]
     fn next_instr(&mut self) {
@@ -1157,11 +1450,27 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a Wasm `unreachable` instruction.
-    #[inline(always)]
+    #[cold]
+    #[inline(never)]
     fn execute_trap(&mut self, trap_code: TrapCode) -> Result<(), TrapCode> {
         Err(trap_code)
     }
 
+    /// Stores the instruction pointer into the current call frame upon a trap.
+    ///
+    /// # Note
+    ///
+    /// This way the trapping [`Instruction`] can be located in order to attach the
+    /// message of a trap folded at compilation time or the location of the trap.
+    #[cold]
+    #[inline(never)]
+    fn sync_trap(&mut self, trap_code: TrapCode) -> TrapCode {
+        if let Some(frame) = self.call_stack.peek_mut() {
+            frame.update_instr_ptr(self.ip);
+        }
+        trap_code
+    }
+
     /// Executes an [`Instruction::ConsumeFuel`].
     #[inline(always)]
     fn execute_consume_fuel(&mut self, block_fuel: BlockFuel) -> Result<(), TrapCode> {
@@ -0,0 +1,72 @@
+use super::Executor;
+use crate::{
+    core::{TrapCode, UntypedValue},
+    engine::regmach::bytecode::{LoadInstr, Register},
+};
+
+#[cfg(doc)]
+use crate::engine::regmach::bytecode::Instruction;
+
+/// The function signature of Wasm `memory.atomic.wait` operations.
+type WasmWaitOp = fn(
+    memory: &[u8],
+    address: UntypedValue,
+    offset: u32,
+    expected: UntypedValue,
+) -> Result<UntypedValue, TrapCode>;
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Executes an [`Instruction::MemoryAtomicNotify`].
+    #[inline(always)]
+    pub fn execute_memory_atomic_notify(&mut self, instr: LoadInstr) -> Result<(), TrapCode> {
+        let offset = self.fetch_address_offset(1);
+        let address = self.get_register(instr.ptr);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        let notified = UntypedValue::memory_atomic_notify(memory, address, offset)?;
+        self.set_register(instr.result, notified);
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes an [`Instruction::MemoryAtomicWait32`].
+    #[inline(always)]
+    pub fn execute_memory_atomic_wait32(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        expected: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_memory_atomic_wait(result, ptr, expected, UntypedValue::memory_atomic_wait32)
+    }
+
+    /// Executes an [`Instruction::MemoryAtomicWait64`].
+    #[inline(always)]
+    pub fn execute_memory_atomic_wait64(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        expected: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_memory_atomic_wait(result, ptr, expected, UntypedValue::memory_atomic_wait64)
+    }
+
+    /// Executes a generic `memory.atomic.wait` [`Instruction`].
+    fn execute_memory_atomic_wait(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        expected: Register,
+        wait: WasmWaitOp,
+    ) -> Result<(), TrapCode> {
+        let memory = self.cache.default_memory(self.ctx);
+        if !self.ctx.resolve_memory(memory).ty().is_shared() {
+            return Err(TrapCode::ExpectedSharedMemory);
+        }
+        let offset = self.fetch_address_offset(1);
+        let address = self.get_register(ptr);
+        let expected = self.get_register(expected);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        let status = wait(memory, address, offset, expected)?;
+        self.set_register(result, status);
+        self.try_next_instr_at(2)
+    }
+}
@@ -20,16 +20,10 @@ macro_rules! impl_binary {
 }
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_binary! {
-        (Instruction::I32Add, execute_i32_add, UntypedValue::i32_add),
-        (Instruction::I32Sub, execute_i32_sub, UntypedValue::i32_sub),
-        (Instruction::I32Mul, execute_i32_mul, UntypedValue::i32_mul),
         (Instruction::I32And, execute_i32_and, UntypedValue::i32_and),
         (Instruction::I32Or, execute_i32_or, UntypedValue::i32_or),
         (Instruction::I32Xor, execute_i32_xor, UntypedValue::i32_xor),
 
-        (Instruction::I64Add, execute_i64_add, UntypedValue::i64_add),
-        (Instruction::I64Sub, execute_i64_sub, UntypedValue::i64_sub),
-        (Instruction::I64Mul, execute_i64_mul, UntypedValue::i64_mul),
         (Instruction::I64And, execute_i64_and, UntypedValue::i64_and),
         (Instruction::I64Or, execute_i64_or, UntypedValue::i64_or),
         (Instruction::I64Xor, execute_i64_xor, UntypedValue::i64_xor),
@@ -77,16 +71,10 @@ macro_rules! impl_binary_imm16 {
 }
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_binary_imm16! {
-        (i32, Instruction::I32AddImm16, execute_i32_add_imm16, UntypedValue::i32_add),
-        (i32, Instruction::I32SubImm16, execute_i32_sub_imm16, UntypedValue::i32_sub),
-        (i32, Instruction::I32MulImm16, execute_i32_mul_imm16, UntypedValue::i32_mul),
         (i32, Instruction::I32AndImm16, execute_i32_and_imm16, UntypedValue::i32_and),
         (i32, Instruction::I32OrImm16, execute_i32_or_imm16, UntypedValue::i32_or),
         (i32, Instruction::I32XorImm16, execute_i32_xor_imm16, UntypedValue::i32_xor),
 
-        (i64, Instruction::I64AddImm16, execute_i64_add_imm16, UntypedValue::i64_add),
-        (i64, Instruction::I64SubImm16, execute_i64_sub_imm16, UntypedValue::i64_sub),
-        (i64, Instruction::I64MulImm16, execute_i64_mul_imm16, UntypedValue::i64_mul),
         (i64, Instruction::I64AndImm16, execute_i64_and_imm16, UntypedValue::i64_and),
         (i64, Instruction::I64OrImm16, execute_i64_or_imm16, UntypedValue::i64_or),
         (i64, Instruction::I64XorImm16, execute_i64_xor_imm16, UntypedValue::i64_xor),
@@ -119,9 +107,6 @@ macro_rules! impl_binary_imm16_rev {
 }
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_binary_imm16_rev! {
-        (i32, Instruction::I32SubImm16Rev, execute_i32_sub_imm16_rev, UntypedValue::i32_sub),
-        (i64, Instruction::I64SubImm16Rev, execute_i64_sub_imm16_rev, UntypedValue::i64_sub),
-
         (i32, Instruction::I32ShlImm16Rev, execute_i32_shl_imm16_rev, UntypedValue::i32_shl),
         (i32, Instruction::I32ShrUImm16Rev, execute_i32_shr_u_imm16_rev, UntypedValue::i32_shr_u),
         (i32, Instruction::I32ShrSImm16Rev, execute_i32_shr_s_imm16_rev, UntypedValue::i32_shr_s),
@@ -0,0 +1,130 @@
+use super::Executor;
+use crate::{
+    core::UntypedValue,
+    engine::regmach::bytecode::{
+        BranchBinOpInstr,
+        BranchBinOpInstrImm16,
+        BranchComparator,
+        ComparatorOffsetParam,
+        Const16,
+        Register,
+    },
+};
+
+#[cfg(doc)]
+use crate::engine::regmach::bytecode::Instruction;
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Executes a generic fused compare and branch instruction.
+    #[inline(always)]
+    fn execute_branch_binop(
+        &mut self,
+        instr: BranchBinOpInstr,
+        op: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    ) {
+        let lhs = self.get_register(instr.lhs);
+        let rhs = self.get_register(instr.rhs);
+        match bool::from(op(lhs, rhs)) {
+            true => self.branch_to(instr.offset.into()),
+            false => self.next_instr(),
+        }
+    }
+
+    /// Executes a generic fused compare and branch instruction with immediate `rhs` value.
+    #[inline(always)]
+    fn execute_branch_binop_imm<T>(
+        &mut self,
+        instr: BranchBinOpInstrImm16<T>,
+        op: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    ) where
+        T: From<Const16<T>>,
+        UntypedValue: From<T>,
+    {
+        let lhs = self.get_register(instr.lhs);
+        let rhs = UntypedValue::from(<T>::from(instr.rhs));
+        match bool::from(op(lhs, rhs)) {
+            true => self.branch_to(instr.offset.into()),
+            false => self.next_instr(),
+        }
+    }
+
+    /// Executes an [`Instruction::BranchCmpFallback`].
+    pub fn execute_branch_cmp_fallback(&mut self, lhs: Register, rhs: Register, params: Register) {
+        let params = self.get_register(params);
+        let params = ComparatorOffsetParam::from_untyped(params).unwrap_or_else(|| {
+            panic!("encountered invalid parameter for Instruction::BranchCmpFallback: {params:?}")
+        });
+        let op = match params.cmp {
+            BranchComparator::I32Eq => UntypedValue::i32_eq,
+            BranchComparator::I32Ne => UntypedValue::i32_ne,
+            BranchComparator::I32LtS => UntypedValue::i32_lt_s,
+            BranchComparator::I32LtU => UntypedValue::i32_lt_u,
+            BranchComparator::I32LeS => UntypedValue::i32_le_s,
+            BranchComparator::I32LeU => UntypedValue::i32_le_u,
+            BranchComparator::I32GtS => UntypedValue::i32_gt_s,
+            BranchComparator::I32GtU => UntypedValue::i32_gt_u,
+            BranchComparator::I32GeS => UntypedValue::i32_ge_s,
+            BranchComparator::I32GeU => UntypedValue::i32_ge_u,
+        };
+        let lhs = self.get_register(lhs);
+        let rhs = self.get_register(rhs);
+        match bool::from(op(lhs, rhs)) {
+            true => self.branch_to(params.offset),
+            false => self.next_instr(),
+        }
+    }
+}
+
+macro_rules! impl_execute_branch_binop {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: BranchBinOpInstr) {
+                self.execute_branch_binop(instr, $op)
+            }
+        )*
+    };
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_execute_branch_binop! {
+        (Instruction::BranchI32Eq, execute_branch_i32_eq, UntypedValue::i32_eq),
+        (Instruction::BranchI32Ne, execute_branch_i32_ne, UntypedValue::i32_ne),
+        (Instruction::BranchI32LtS, execute_branch_i32_lt_s, UntypedValue::i32_lt_s),
+        (Instruction::BranchI32LtU, execute_branch_i32_lt_u, UntypedValue::i32_lt_u),
+        (Instruction::BranchI32LeS, execute_branch_i32_le_s, UntypedValue::i32_le_s),
+        (Instruction::BranchI32LeU, execute_branch_i32_le_u, UntypedValue::i32_le_u),
+        (Instruction::BranchI32GtS, execute_branch_i32_gt_s, UntypedValue::i32_gt_s),
+        (Instruction::BranchI32GtU, execute_branch_i32_gt_u, UntypedValue::i32_gt_u),
+        (Instruction::BranchI32GeS, execute_branch_i32_ge_s, UntypedValue::i32_ge_s),
+        (Instruction::BranchI32GeU, execute_branch_i32_ge_u, UntypedValue::i32_ge_u),
+    }
+}
+
+macro_rules! impl_execute_branch_binop_imm {
+    ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: BranchBinOpInstrImm16<$ty>) {
+                self.execute_branch_binop_imm(instr, $op)
+            }
+        )*
+    };
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_execute_branch_binop_imm! {
+        (i32, Instruction::BranchI32EqImm, execute_branch_i32_eq_imm, UntypedValue::i32_eq),
+        (i32, Instruction::BranchI32NeImm, execute_branch_i32_ne_imm, UntypedValue::i32_ne),
+        (i32, Instruction::BranchI32LtSImm, execute_branch_i32_lt_s_imm, UntypedValue::i32_lt_s),
+        (u32, Instruction::BranchI32LtUImm, execute_branch_i32_lt_u_imm, UntypedValue::i32_lt_u),
+        (i32, Instruction::BranchI32LeSImm, execute_branch_i32_le_s_imm, UntypedValue::i32_le_s),
+        (u32, Instruction::BranchI32LeUImm, execute_branch_i32_le_u_imm, UntypedValue::i32_le_u),
+        (i32, Instruction::BranchI32GtSImm, execute_branch_i32_gt_s_imm, UntypedValue::i32_gt_s),
+        (u32, Instruction::BranchI32GtUImm, execute_branch_i32_gt_u_imm, UntypedValue::i32_gt_u),
+        (i32, Instruction::BranchI32GeSImm, execute_branch_i32_ge_s_imm, UntypedValue::i32_ge_s),
+        (u32, Instruction::BranchI32GeUImm, execute_branch_i32_ge_u_imm, UntypedValue::i32_ge_u),
+    }
+}
@@ -4,13 +4,15 @@ use crate::{
     engine::{
         bytecode::{FuncIdx, SignatureIdx, TableIdx},
         regmach::{
-            bytecode::{Instruction, Register, RegisterSpan},
+            bytecode::{Instruction, Register, RegisterSpan, RegisterSpanIter},
             code_map::{CompiledFuncEntity, InstructionPtr},
+            executor::HostCallConvention,
             stack::{CallFrame, Stack, ValueStackPtr},
         },
         CompiledFunc,
     },
     func::FuncEntity,
+    CallTransition,
     Func,
     FuncRef,
 };
@@ -142,6 +144,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             }
         };
         ip.add(1);
+        if let Instruction::RegisterSpan(values) = ip.get() {
+            self.copy_call_params_span(*values, called_regs);
+            return ip;
+        }
         while let Instruction::RegisterList(values) = ip.get() {
             copy_params(values);
             ip.add(1);
@@ -161,6 +167,31 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ip
     }
 
+    /// Copies the contiguous parameters `values` to the `called_regs` at once.
+    ///
+    /// # Note
+    ///
+    /// The translator only encodes an [`Instruction::RegisterSpan`] for call parameters
+    /// if they map exactly onto the contiguous parameter registers of the callee.
+    fn copy_call_params_span(&mut self, values: RegisterSpanIter, mut called_regs: ValueStackPtr) {
+        let len = values.len_as_u16();
+        let values = values.span();
+        #[cfg(feature = "checked-execution")]
+        for register in values.iter_u16(len) {
+            self.check_register(register);
+        }
+        // Safety: The parameter registers of the callee reside in a different call frame
+        //         or in the host buffer and therefore never overlap with `values`.
+        unsafe {
+            called_regs.copy_span_from(
+                Register::from_i16(0),
+                &self.sp,
+                values.head(),
+                usize::from(len),
+            )
+        }
+    }
+
     /// Prepares a [`CompiledFunc`] call with optional [`CallParams`].
     fn prepare_compiled_func_call(
         &mut self,
@@ -181,6 +212,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 self.update_instr_ptr_at(1);
             }
             CallKind::Tail => {
+                // Note: `self.ip` still points into the code of the tail calling function.
+                let (code_map, caller_ip) = (self.code_map, self.ip);
+                self.ctx.notify_call_observer(
+                    || code_map.func_index_at(caller_ip),
+                    CallTransition::Exit,
+                );
                 // In case of a tail call we have to remove the caller call frame after
                 // allocating the callee call frame. This moves all cells of the callee frame
                 // and may invalidate pointers to it.
@@ -195,6 +232,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             }
         }
         self.init_call_frame(&called);
+        self.ctx
+            .notify_call_observer(|| func.func_index(), CallTransition::Enter);
         self.call_stack.push(called)?;
         Ok(())
     }
@@ -313,12 +352,20 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         call_kind: CallKind,
     ) -> Result<CallOutcome, TrapCode> {
         match self.ctx.resolve_func(func) {
-            FuncEntity::Wasm(func) => {
-                let instance = *func.instance();
-                self.prepare_compiled_func_call(results, func.func_body(), params, call_kind)?;
+            FuncEntity::Wasm(wasm_func) if self.code_map.is_initialized(wasm_func.func_body()) => {
+                let instance = *wasm_func.instance();
+                self.prepare_compiled_func_call(results, wasm_func.func_body(), params, call_kind)?;
                 self.cache.update_instance(&instance);
                 Ok(CallOutcome::Continue)
             }
+            FuncEntity::Wasm(_) => {
+                // Note: Wasm functions with an invalid compiled function body are handed
+                //       back the same way as host functions and reported by the engine.
+                Ok(CallOutcome::Call {
+                    results,
+                    host_func: *func,
+                })
+            }
             FuncEntity::Host(host_func) => {
                 // Note: host function calls cannot be implemented as tail calls.
                 //       The Wasm spec is not mandating tail behavior for host calls.
@@ -328,20 +375,25 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     .params_results();
                 let len_params = input_types.len();
                 let len_results = output_types.len();
-                let max_inout = len_params.max(len_results);
-                self.value_stack.reserve(max_inout)?;
-                // We have to reinstantiate the `self.sp` [`ValueStackPtr`] since we just called
-                // [`ValueStack::reserve`] which might invalidate all live [`ValueStackPtr`].
-                let caller = self
-                    .call_stack
-                    .peek()
-                    .expect("need to have a caller on the call stack");
-                // Safety: We use the base offset of a live call frame on the call stack.
-                self.sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
-                let offset = self.value_stack.extend_zeros(max_inout);
-                let offset_sp = unsafe { self.value_stack.stack_ptr_at(offset) };
+                let called_regs = match HostCallConvention::new(len_params, len_results) {
+                    HostCallConvention::RegisterRegion => {
+                        let max_inout = len_params.max(len_results);
+                        self.value_stack.reserve(max_inout)?;
+                        // We have to reinstantiate the `self.sp` [`ValueStackPtr`] since we just called
+                        // [`ValueStack::reserve`] which might invalidate all live [`ValueStackPtr`].
+                        let caller = self
+                            .call_stack
+                            .peek()
+                            .expect("need to have a caller on the call stack");
+                        // Safety: We use the base offset of a live call frame on the call stack.
+                        self.sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
+                        let offset = self.value_stack.extend_zeros(max_inout);
+                        unsafe { self.value_stack.stack_ptr_at(offset) }
+                    }
+                    HostCallConvention::DirectSlice => self.value_stack.host_buffer_ptr(),
+                };
                 if matches!(params, CallParams::Some) {
-                    self.ip = self.copy_call_params(offset_sp);
+                    self.ip = self.copy_call_params(called_regs);
                 }
                 self.update_instr_ptr_at(1);
                 self.cache.reset();
@@ -444,14 +496,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .ok_or(TrapCode::TableOutOfBounds)?;
         let func = funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
         let actual_signature = self.ctx.resolve_func(func).ty_dedup();
-        let expected_signature = self
-            .ctx
-            .resolve_instance(self.cache.instance())
-            .get_signature(func_type.to_u32())
-            .unwrap_or_else(|| {
-                panic!("missing signature for call_indirect at index: {func_type:?}")
-            });
-        if actual_signature != expected_signature {
+        let expected_signature = self.cache.get_signature(self.ctx, func_type);
+        if *actual_signature != expected_signature {
             return Err(TrapCode::BadSignature);
         }
         self.execute_call_imported_impl(results, func, params, call_kind)
@@ -0,0 +1,161 @@
+use super::Executor;
+use crate::{
+    core::TrapCode,
+    engine::regmach::{
+        bytecode::{Instruction, Register, RegisterSpan},
+        translator::VisitInputRegisters,
+    },
+};
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Returns `true` if `register` is within the bounds of the current call frame.
+    pub fn is_register_in_bounds(&self, register: Register) -> bool {
+        let frame = match self.call_stack.peek() {
+            Some(frame) => frame,
+            None => return false,
+        };
+        let frame_offset = usize::from(frame.frame_offset());
+        let base_offset = usize::from(frame.base_offset());
+        let len_values = self.value_stack.len();
+        if frame_offset > base_offset || base_offset > len_values {
+            return false;
+        }
+        let index = isize::from(register.to_i16());
+        let min = -((base_offset - frame_offset) as isize);
+        let max = (len_values - base_offset) as isize;
+        (min..max).contains(&index)
+    }
+
+    /// Returns `true` if all `len` registers of `span` are within the bounds of the current call frame.
+    fn is_span_in_bounds(&self, span: RegisterSpan, len: u16) -> bool {
+        if len == 0 {
+            return true;
+        }
+        let head = span.head();
+        let last = i32::from(head.to_i16()) + i32::from(len) - 1;
+        match i16::try_from(last) {
+            Ok(last) => {
+                self.is_register_in_bounds(head)
+                    && self.is_register_in_bounds(Register::from_i16(last))
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Traps with [`TrapCode::RegisterOutOfBounds`] if the [`Instruction`] at the
+    /// current instruction pointer accesses registers out of bounds of the current call frame.
+    ///
+    /// # Note
+    ///
+    /// This is checked before the [`Instruction`] is executed so that malformed
+    /// bytecode cannot cause any side effects. Checked are:
+    ///
+    /// - All registers read by the [`Instruction`] and its parameter [`Instruction`] words.
+    /// - The result registers of [`Instruction`] that have side effects, such as calls or
+    ///   `memory.grow`. All other register accesses are checked upon reading or writing them.
+    ///
+    /// Parameter [`Instruction`] words are never looked up beyond the end of the function body.
+    pub fn check_instr_registers(&mut self) -> Result<(), TrapCode> {
+        let code_map = self.code_map;
+        let current = [*self.ip.get()];
+        let instrs = match code_map.locate(self.ip) {
+            Some((entity, index)) => &entity.instrs()[index..],
+            None => &current[..],
+        };
+        let mut in_bounds = true;
+        for (n, &instr) in instrs.iter().enumerate() {
+            if n != 0 && !is_instr_param(&instr) {
+                break;
+            }
+            let mut instr = instr;
+            instr.visit_input_registers(|register| {
+                in_bounds &= self.is_register_in_bounds(*register);
+            });
+            in_bounds &= self.check_instr_spans(&instr);
+        }
+        if !in_bounds {
+            return Err(TrapCode::RegisterOutOfBounds);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the register spans and side effecting results of `instr` are in bounds.
+    ///
+    /// # Note
+    ///
+    /// [`VisitInputRegisters`] only visits the first register of a register span.
+    fn check_instr_spans(&mut self, instr: &Instruction) -> bool {
+        match *instr {
+            Instruction::RegisterSpan(values)
+            | Instruction::ReturnSpan { values }
+            | Instruction::ReturnNezSpan { values, .. } => {
+                self.is_span_in_bounds(values.span(), values.len_as_u16())
+            }
+            Instruction::CopySpan {
+                results,
+                values,
+                len,
+            }
+            | Instruction::CopySpanNonOverlapping {
+                results,
+                values,
+                len,
+            } => self.is_span_in_bounds(results, len) && self.is_span_in_bounds(values, len),
+            Instruction::MemoryGrow { result, .. }
+            | Instruction::MemoryGrowBy { result, .. }
+            | Instruction::TableGrow { result, .. }
+            | Instruction::TableGrowImm { result, .. } => self.is_register_in_bounds(result),
+            Instruction::CallInternal0 { results, func }
+            | Instruction::CallInternal { results, func } => {
+                let len_results = self.code_map.get(func).len_results();
+                self.is_span_in_bounds(results, len_results)
+            }
+            Instruction::CallImported0 { results, func }
+            | Instruction::CallImported { results, func } => {
+                let func = self.cache.get_func(self.ctx, func);
+                let func_type = self.ctx.resolve_func(&func).ty_dedup();
+                let len_results = self.func_types.resolve_func_type(func_type).results().len();
+                self.is_span_in_bounds(results, len_results as u16)
+            }
+            Instruction::CallIndirect0 { results, func_type }
+            | Instruction::CallIndirect { results, func_type } => {
+                let func_type = self.cache.get_signature(self.ctx, func_type);
+                let len_results = self
+                    .func_types
+                    .resolve_func_type(&func_type)
+                    .results()
+                    .len();
+                self.is_span_in_bounds(results, len_results as u16)
+            }
+            _ => true,
+        }
+    }
+
+    /// Traps with [`TrapCode::RegisterOutOfBounds`] if a register has been read or written out of bounds.
+    pub fn check_register_accesses(&self) -> Result<(), TrapCode> {
+        if self.register_out_of_bounds.get() {
+            return Err(TrapCode::RegisterOutOfBounds);
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if `instr` is a parameter [`Instruction`] word of its preceding [`Instruction`].
+fn is_instr_param(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::TableIdx(_)
+            | Instruction::DataSegmentIdx(_)
+            | Instruction::ElementSegmentIdx(_)
+            | Instruction::Const32(_)
+            | Instruction::I64Const32(_)
+            | Instruction::F64Const32(_)
+            | Instruction::Register(_)
+            | Instruction::Register2(_)
+            | Instruction::Register3(_)
+            | Instruction::RegisterList(_)
+            | Instruction::RegisterSpan(_)
+            | Instruction::CallIndirectParams(_)
+            | Instruction::CallIndirectParamsImm16(_)
+    )
+}
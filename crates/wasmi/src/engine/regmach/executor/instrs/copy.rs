@@ -59,23 +59,33 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         })
     }
 
+    /// Copies `len` consecutive values starting at `values` to the registers starting at `results`.
+    ///
+    /// # Note
+    ///
+    /// This copies all values at once and thus works for overlapping register spans.
+    fn copy_span_impl(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
+        #[cfg(feature = "checked-execution")]
+        for register in results.iter_u16(len).chain(values.iter_u16(len)) {
+            self.check_register(register);
+        }
+        // Safety: the translator only emits register spans within the bounds of the call frame.
+        unsafe {
+            self.sp
+                .copy_span(results.head(), values.head(), usize::from(len))
+        }
+    }
+
     /// Executes an [`Instruction::CopySpan`].
     ///
     /// # Note
     ///
     /// - This instruction assumes that `results` and `values` _do_ overlap
-    ///   and thus requires a costly temporary buffer to avoid overwriting
-    ///   intermediate copy results.
+    ///   such that copying value by value would overwrite values before they are copied.
     /// - If `results` and `values` do _not_ overlap [`Instruction::CopySpanNonOverlapping`] is used.
     #[inline(always)]
     pub fn execute_copy_span(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
-        let results = results.iter_u16(len);
-        let values = values.iter_u16(len);
-        let mut tmp = <SmallVec<[UntypedValue; 8]>>::default();
-        tmp.extend(values.into_iter().map(|value| self.get_register(value)));
-        for (result, value) in results.into_iter().zip(tmp) {
-            self.set_register(result, value);
-        }
+        self.copy_span_impl(results, values, len);
         self.next_instr();
     }
 
@@ -84,7 +94,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// # Note
     ///
     /// - This instruction assumes that `results` and `values` do _not_ overlap
-    ///   and thus can copy all the elements without a costly temporary buffer.
+    ///   such that copying value by value never overwrites values before they are copied.
     /// - If `results` and `values` _do_ overlap [`Instruction::CopySpan`] is used.
     #[inline(always)]
     pub fn execute_copy_span_non_overlapping(
@@ -93,12 +103,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         values: RegisterSpan,
         len: u16,
     ) {
-        let results = results.iter_u16(len);
-        let values = values.iter_u16(len);
-        for (result, value) in results.into_iter().zip(values.into_iter()) {
-            let value = self.get_register(value);
-            self.set_register(result, value);
-        }
+        self.copy_span_impl(results, values, len);
         self.next_instr();
     }
 
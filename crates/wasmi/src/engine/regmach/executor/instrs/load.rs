@@ -1,16 +1,28 @@
+#[cfg(feature = "checked-arithmetic")]
+use super::wrapping::WrappingOp;
 use super::Executor;
 use crate::{
     core::{TrapCode, UntypedValue},
-    engine::regmach::bytecode::{LoadAtInstr, LoadInstr, LoadOffset16Instr, Register},
+    engine::regmach::bytecode::{
+        BinInstr,
+        Instruction,
+        LoadAtInstr,
+        LoadInstr,
+        LoadOffset16Instr,
+        Register,
+    },
+    memory::guarded,
 };
 
-#[cfg(doc)]
-use crate::engine::regmach::bytecode::Instruction;
 
 /// The function signature of Wasm load operations.
 type WasmLoadOp =
     fn(memory: &[u8], address: UntypedValue, offset: u32) -> Result<UntypedValue, TrapCode>;
 
+/// The function signature of Wasm load operations without bounds checks.
+type WasmLoadUncheckedOp =
+    unsafe fn(memory: &[u8], address: UntypedValue, offset: u32) -> Result<UntypedValue, TrapCode>;
+
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Executes a generic Wasm `store[N_{s|u}]` operation.
     ///
@@ -73,6 +85,105 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.execute_load_extend(instr.result, address, offset, load_extend)?;
         self.try_next_instr()
     }
+
+    /// Executes a generic unchecked `load` [`Instruction`].
+    ///
+    /// # Note
+    ///
+    /// The bounds of the load have either been checked by the [`Instruction::LoopBoundsGuard`]
+    /// in front of its enclosing `loop` or out of bounds accesses are detected via the guard
+    /// pages of the linear memory.
+    ///
+    /// The guarded `loop` body neither calls host functions nor grows or shrinks the linear
+    /// memory. Only single-stepping hands control back to the host in between the guard and
+    /// the loads which is why [`Executor::try_execute_load_unchecked_stepped`] bounds checks
+    /// them explicitly instead.
+    fn execute_load_unchecked_impl(
+        &mut self,
+        instr: LoadInstr,
+        load_extend: WasmLoadUncheckedOp,
+    ) -> Result<(), TrapCode> {
+        let offset = self.fetch_address_offset(1);
+        let address = self.get_register(instr.ptr);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        // SAFETY: the translator only encodes unchecked loads from the index of a guarded
+        //         `loop` whose guard made sure that all of them are in bounds or if all
+        //         linear memories are followed by guard pages. The linear memory cannot
+        //         shrink in between the guard and the load since single-stepped unchecked
+        //         loads are executed via their bounds checked counterparts.
+        let loaded_value = unsafe { load_extend(memory, address, offset) }?;
+        self.set_register(instr.result, loaded_value);
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes the unchecked `load` [`Instruction`] at the current instruction pointer
+    /// via its bounds checked counterpart.
+    ///
+    /// Returns `None` if the current [`Instruction`] is not an unchecked `load`.
+    ///
+    /// # Note
+    ///
+    /// In between two steps of a single-stepped execution the host may shrink the linear
+    /// memory via [`Memory::restore`] which invalidates an [`Instruction::LoopBoundsGuard`]
+    /// that has already been executed.
+    ///
+    /// [`Memory::restore`]: crate::Memory::restore
+    pub fn try_execute_load_unchecked_stepped(&mut self) -> Option<Result<(), TrapCode>> {
+        use Instruction as Instr;
+        let result = match *self.ip.get() {
+            Instr::I32LoadUnchecked(instr) => self.execute_i32_load(instr),
+            Instr::I64LoadUnchecked(instr) => self.execute_i64_load(instr),
+            Instr::F32LoadUnchecked(instr) => self.execute_f32_load(instr),
+            Instr::F64LoadUnchecked(instr) => self.execute_f64_load(instr),
+            Instr::I32Load8sUnchecked(instr) => self.execute_i32_load8_s(instr),
+            Instr::I32Load8uUnchecked(instr) => self.execute_i32_load8_u(instr),
+            Instr::I32Load16sUnchecked(instr) => self.execute_i32_load16_s(instr),
+            Instr::I32Load16uUnchecked(instr) => self.execute_i32_load16_u(instr),
+            Instr::I64Load8sUnchecked(instr) => self.execute_i64_load8_s(instr),
+            Instr::I64Load8uUnchecked(instr) => self.execute_i64_load8_u(instr),
+            Instr::I64Load16sUnchecked(instr) => self.execute_i64_load16_s(instr),
+            Instr::I64Load16uUnchecked(instr) => self.execute_i64_load16_u(instr),
+            Instr::I64Load32sUnchecked(instr) => self.execute_i64_load32_s(instr),
+            Instr::I64Load32uUnchecked(instr) => self.execute_i64_load32_u(instr),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// Executes an [`Instruction::LoopBoundsGuard`].
+    #[inline(always)]
+    pub fn execute_loop_bounds_guard(&mut self, index: Register) -> Result<(), TrapCode> {
+        let bound = self.fetch_address_offset(1);
+        let step = self.fetch_address_offset(2);
+        let end_offset = self.fetch_address_offset(3);
+        let first = u32::from(self.get_register(index));
+        if first < bound {
+            // The `index` of the last loop iteration which performs the last loads.
+            let last = first + (bound - 1 - first) / step * step;
+            let end = u64::from(last) + u64::from(end_offset);
+            let memory = self.cache.default_memory_bytes(self.ctx);
+            if end > memory.len() as u64 {
+                return Err(TrapCode::MemoryOutOfBounds);
+            }
+        }
+        self.try_next_instr_at(4)
+    }
+
+    /// Executes an [`Instruction::I32AddFromMem`].
+    #[inline(always)]
+    pub fn execute_i32_add_from_mem(&mut self, instr: BinInstr) -> Result<(), TrapCode> {
+        let offset = self.fetch_address_offset(1);
+        let address = self.get_register(instr.lhs);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        let loaded = UntypedValue::i32_load(memory, address, offset)?;
+        let rhs = self.get_register(instr.rhs);
+        #[cfg(feature = "checked-arithmetic")]
+        let inputs = self.wrapping_inputs(|_| i32::from(loaded), |_| i32::from(rhs));
+        self.set_register(instr.result, loaded.i32_add(rhs));
+        #[cfg(feature = "checked-arithmetic")]
+        self.check_wrapping(WrappingOp::Add, inputs, instr.result);
+        self.try_next_instr_at(2)
+    }
 }
 
 macro_rules! impl_execute_load {
@@ -196,3 +307,34 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ),
     }
 }
+
+macro_rules! impl_execute_load_unchecked {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $impl_fn:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: LoadInstr) -> Result<(), TrapCode> {
+                self.execute_load_unchecked_impl(instr, $impl_fn)
+            }
+        )*
+    }
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_execute_load_unchecked! {
+        (Instruction::I32LoadUnchecked, execute_i32_load_unchecked, guarded::i32_load),
+        (Instruction::I64LoadUnchecked, execute_i64_load_unchecked, guarded::i64_load),
+        (Instruction::F32LoadUnchecked, execute_f32_load_unchecked, guarded::f32_load),
+        (Instruction::F64LoadUnchecked, execute_f64_load_unchecked, guarded::f64_load),
+        (Instruction::I32Load8sUnchecked, execute_i32_load8_s_unchecked, guarded::i32_load8_s),
+        (Instruction::I32Load8uUnchecked, execute_i32_load8_u_unchecked, guarded::i32_load8_u),
+        (Instruction::I32Load16sUnchecked, execute_i32_load16_s_unchecked, guarded::i32_load16_s),
+        (Instruction::I32Load16uUnchecked, execute_i32_load16_u_unchecked, guarded::i32_load16_u),
+        (Instruction::I64Load8sUnchecked, execute_i64_load8_s_unchecked, guarded::i64_load8_s),
+        (Instruction::I64Load8uUnchecked, execute_i64_load8_u_unchecked, guarded::i64_load8_u),
+        (Instruction::I64Load16sUnchecked, execute_i64_load16_s_unchecked, guarded::i64_load16_s),
+        (Instruction::I64Load16uUnchecked, execute_i64_load16_u_unchecked, guarded::i64_load16_u),
+        (Instruction::I64Load32sUnchecked, execute_i64_load32_s_unchecked, guarded::i64_load32_s),
+        (Instruction::I64Load32uUnchecked, execute_i64_load32_u_unchecked, guarded::i64_load32_u),
+    }
+}
@@ -1,16 +1,18 @@
 use wasmi_core::Pages;
 
-use super::Executor;
+use super::{Executor, WasmOutcome};
 use crate::{
     core::TrapCode,
     engine::{
-        bytecode::DataSegmentIdx,
+        bytecode::{DataSegmentIdx, MemoryIdx},
         regmach::{
             bytecode::{Const16, Instruction, Register},
             code_map::InstructionPtr,
         },
     },
     error::EntityGrowError,
+    memory::fill_bytes,
+    module::DEFAULT_MEMORY_INDEX,
     store::ResourceLimiterRef,
 };
 
@@ -35,6 +37,27 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.next_instr();
     }
 
+    /// Executes an [`Instruction::UseMemory`] together with the memory instruction it prefixes.
+    ///
+    /// # Note
+    ///
+    /// Both instructions are executed as a single step so that the selected
+    /// linear memory never outlives the prefixed memory instruction.
+    #[cold]
+    #[inline(never)]
+    pub fn execute_use_memory(
+        &mut self,
+        memory: MemoryIdx,
+        resource_limiter: &mut ResourceLimiterRef<'ctx>,
+    ) -> Result<Option<WasmOutcome>, TrapCode> {
+        self.cache.use_memory(self.ctx, memory);
+        self.next_instr();
+        let outcome = self.execute_instr(resource_limiter);
+        self.cache
+            .use_memory(self.ctx, MemoryIdx::from(DEFAULT_MEMORY_INDEX));
+        outcome
+    }
+
     /// Executes an [`Instruction::MemorySize`].
     #[inline(always)]
     pub fn execute_memory_size(&mut self, result: Register) {
@@ -371,14 +394,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             |this| {
                 let dst = dst as usize;
                 let len = len as usize;
-                let memory = this
-                    .cache
-                    .default_memory_bytes(this.ctx)
-                    .get_mut(dst..)
-                    .and_then(|memory| memory.get_mut(..len))
-                    .ok_or(TrapCode::MemoryOutOfBounds)?;
-                memory.fill(value);
-                Ok(())
+                let memory = this.cache.default_memory_bytes(this.ctx);
+                fill_bytes(memory, dst, value, len).map_err(|_| TrapCode::MemoryOutOfBounds)
             },
         )?;
         self.try_next_instr()
@@ -5,6 +5,7 @@ use crate::{
         bytecode::{AnyConst32, Const32, Instruction, Register, RegisterSpan, RegisterSpanIter},
         stack::ValueStackPtr,
     },
+    CallTransition,
 };
 use core::slice;
 
@@ -29,6 +30,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .pop()
             .expect("the executing call frame is always on the stack");
         self.value_stack.truncate(returned.frame_offset());
+        // Note: `self.ip` still points into the code of the returning function.
+        let (code_map, returned_ip) = (self.code_map, self.ip);
+        self.ctx
+            .notify_call_observer(|| code_map.func_index_at(returned_ip), CallTransition::Exit);
         match self.call_stack.peek() {
             Some(caller) => {
                 Self::init_call_frame_impl(
@@ -65,6 +70,13 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 // In this case we transfer the single return `value` to the `results`
                 // register span of the caller's call frame.
                 //
+                // Note: The callee call frame is always allocated above its caller call frame.
+                //       Therefore returned registers never alias the caller's result registers
+                //       and returning values can never be an identity copy that could be skipped.
+                debug_assert!(
+                    usize::from(caller.base_offset()) < usize::from(callee.frame_offset()),
+                    "the callee call frame must reside above its caller call frame",
+                );
                 // Safety: The caller call frame is still live on the value stack
                 //         and therefore it is safe to acquire its value stack pointer.
                 let caller_sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
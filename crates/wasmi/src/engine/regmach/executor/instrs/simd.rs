@@ -0,0 +1,134 @@
+use super::Executor;
+use crate::{
+    core::{TrapCode, UntypedValue},
+    engine::regmach::bytecode::{BinInstr, LoadInstr, Register, StoreInstr, UnaryInstr},
+};
+
+#[cfg(doc)]
+use crate::engine::regmach::bytecode::Instruction;
+
+/// Returns the range of the 16 bytes of a `v128` value at `address+offset` in `memory`.
+///
+/// # Errors
+///
+/// If the effective address overflows or the accessed bytes are out of bounds.
+fn v128_bytes_range(
+    memory: &[u8],
+    address: u32,
+    offset: u32,
+) -> Result<core::ops::Range<usize>, TrapCode> {
+    let start = address
+        .checked_add(offset)
+        .ok_or(TrapCode::MemoryOutOfBounds)? as usize;
+    let end = start
+        .checked_add(16)
+        .filter(|&end| end <= memory.len())
+        .ok_or(TrapCode::MemoryOutOfBounds)?;
+    Ok(start..end)
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Returns the lower and upper 64 bits of the `v128` value stored at `value`.
+    fn get_v128(&self, value: Register) -> (u64, u64) {
+        let lo: u64 = self.get_register_as(value);
+        let hi: u64 = self.get_register_as(value.next());
+        (lo, hi)
+    }
+
+    /// Stores the lower and upper 64 bits of a `v128` value into `result`.
+    fn set_v128(&mut self, result: Register, (lo, hi): (u64, u64)) {
+        self.set_register(result, lo);
+        self.set_register(result.next(), hi);
+    }
+
+    /// Executes an [`Instruction::V128Load`].
+    #[inline(always)]
+    pub fn execute_v128_load(&mut self, instr: LoadInstr) -> Result<(), TrapCode> {
+        let offset = self.fetch_address_offset(1);
+        let address: u32 = self.get_register_as(instr.ptr);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        let range = v128_bytes_range(memory, address, offset)?;
+        let mut bytes = [0x00_u8; 16];
+        bytes.copy_from_slice(&memory[range]);
+        let value = u128::from_le_bytes(bytes);
+        self.set_v128(instr.result, (value as u64, (value >> 64) as u64));
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes an [`Instruction::V128Store`].
+    #[inline(always)]
+    pub fn execute_v128_store(&mut self, instr: StoreInstr) -> Result<(), TrapCode> {
+        let value = self.fetch_store_value(1);
+        let (lo, hi) = self.get_v128(value);
+        let address: u32 = self.get_register_as(instr.ptr);
+        let offset = u32::from(instr.offset);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        let range = v128_bytes_range(memory, address, offset)?;
+        let value = u128::from(lo) | (u128::from(hi) << 64);
+        memory[range].copy_from_slice(&value.to_le_bytes());
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes an [`Instruction::I32x4Splat`].
+    #[inline(always)]
+    pub fn execute_i32x4_splat(&mut self, instr: UnaryInstr) {
+        let lane: u32 = self.get_register_as(instr.input);
+        let half = u64::from(lane) | (u64::from(lane) << 32);
+        self.set_v128(instr.result, (half, half));
+        self.next_instr()
+    }
+
+    /// Executes a generic binary `v128` [`Instruction`] applying `op` to each pair of its four 32-bit lanes.
+    fn execute_v128_binary_x4(&mut self, instr: BinInstr, op: fn(u32, u32) -> u32) {
+        let (lhs_lo, lhs_hi) = self.get_v128(instr.lhs);
+        let (rhs_lo, rhs_hi) = self.get_v128(instr.rhs);
+        let lanes = |lhs: u64, rhs: u64| {
+            let lo = op(lhs as u32, rhs as u32);
+            let hi = op((lhs >> 32) as u32, (rhs >> 32) as u32);
+            u64::from(lo) | (u64::from(hi) << 32)
+        };
+        let result = (lanes(lhs_lo, rhs_lo), lanes(lhs_hi, rhs_hi));
+        self.set_v128(instr.result, result);
+        self.next_instr()
+    }
+
+    /// Executes an [`Instruction::I32x4MinS`].
+    #[inline(always)]
+    pub fn execute_i32x4_min_s(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, |lhs, rhs| (lhs as i32).min(rhs as i32) as u32)
+    }
+
+    /// Executes an [`Instruction::I32x4MinU`].
+    #[inline(always)]
+    pub fn execute_i32x4_min_u(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, u32::min)
+    }
+
+    /// Executes an [`Instruction::I32x4MaxS`].
+    #[inline(always)]
+    pub fn execute_i32x4_max_s(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, |lhs, rhs| (lhs as i32).max(rhs as i32) as u32)
+    }
+
+    /// Executes an [`Instruction::I32x4MaxU`].
+    #[inline(always)]
+    pub fn execute_i32x4_max_u(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, u32::max)
+    }
+
+    /// Executes an [`Instruction::F32x4Min`].
+    #[inline(always)]
+    pub fn execute_f32x4_min(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, |lhs, rhs| {
+            u32::from(UntypedValue::from(lhs).f32_min(UntypedValue::from(rhs)))
+        })
+    }
+
+    /// Executes an [`Instruction::F32x4Max`].
+    #[inline(always)]
+    pub fn execute_f32x4_max(&mut self, instr: BinInstr) {
+        self.execute_v128_binary_x4(instr, |lhs, rhs| {
+            u32::from(UntypedValue::from(lhs).f32_max(UntypedValue::from(rhs)))
+        })
+    }
+}
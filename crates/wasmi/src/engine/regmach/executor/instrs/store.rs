@@ -5,6 +5,7 @@ use crate::{
         bytecode::{Const16, Instruction, Register, StoreAtInstr, StoreInstr, StoreOffset16Instr},
         code_map::InstructionPtr,
     },
+    memory::guarded,
 };
 
 /// The function signature of Wasm store operations.
@@ -15,9 +16,17 @@ type WasmStoreOp = fn(
     value: UntypedValue,
 ) -> Result<(), TrapCode>;
 
+/// The function signature of Wasm store operations without explicit bounds checks.
+type WasmStoreUncheckedOp = unsafe fn(
+    memory: &mut [u8],
+    address: UntypedValue,
+    offset: u32,
+    value: UntypedValue,
+) -> Result<(), TrapCode>;
+
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Returns the [`Instruction::Register`] parameter for an [`Instruction`].
-    fn fetch_store_value(&self, offset: usize) -> Register {
+    pub(super) fn fetch_store_value(&self, offset: usize) -> Register {
         let mut addr: InstructionPtr = self.ip;
         addr.add(offset);
         match *addr.get() {
@@ -60,6 +69,26 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.try_next_instr_at(2)
     }
 
+    /// Executes a generic unchecked `store` [`Instruction`].
+    ///
+    /// # Note
+    ///
+    /// Out of bounds accesses are detected via the guard pages of the linear memory.
+    fn execute_store_unchecked(
+        &mut self,
+        instr: StoreInstr,
+        store_op: WasmStoreUncheckedOp,
+    ) -> Result<(), TrapCode> {
+        let value = self.fetch_store_value(1);
+        let address = self.get_register(instr.ptr);
+        let value = self.get_register(value);
+        let memory = self.cache.default_memory_bytes(self.ctx);
+        // SAFETY: the translator only encodes unchecked stores if all
+        //         linear memories are followed by guard pages.
+        unsafe { store_op(memory, address, u32::from(instr.offset), value) }?;
+        self.try_next_instr_at(2)
+    }
+
     fn execute_store_offset16(
         &mut self,
         instr: StoreOffset16Instr<Register>,
@@ -295,3 +324,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ),
     }
 }
+
+macro_rules! impl_execute_store_unchecked {
+    ( $( (Instruction::$var_name:ident, $fn_name:ident, $impl_fn:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: StoreInstr) -> Result<(), TrapCode> {
+                self.execute_store_unchecked(instr, $impl_fn)
+            }
+        )*
+    }
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_execute_store_unchecked! {
+        (Instruction::I32StoreUnchecked, execute_i32_store_unchecked, guarded::i32_store),
+        (Instruction::I32Store8Unchecked, execute_i32_store8_unchecked, guarded::i32_store8),
+        (Instruction::I32Store16Unchecked, execute_i32_store16_unchecked, guarded::i32_store16),
+        (Instruction::I64StoreUnchecked, execute_i64_store_unchecked, guarded::i64_store),
+        (Instruction::I64Store8Unchecked, execute_i64_store8_unchecked, guarded::i64_store8),
+        (Instruction::I64Store16Unchecked, execute_i64_store16_unchecked, guarded::i64_store16),
+        (Instruction::I64Store32Unchecked, execute_i64_store32_unchecked, guarded::i64_store32),
+        (Instruction::F32StoreUnchecked, execute_f32_store_unchecked, guarded::f32_store),
+        (Instruction::F64StoreUnchecked, execute_f64_store_unchecked, guarded::f64_store),
+    }
+}
@@ -18,6 +18,9 @@ macro_rules! impl_unary_impls {
 
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_unary_impls! {
+        (Instruction::I32Eqz, execute_i32_eqz, UntypedValue::i32_eqz),
+        (Instruction::I64Eqz, execute_i64_eqz, UntypedValue::i64_eqz),
+
         (Instruction::I32Clz, execute_i32_clz, UntypedValue::i32_clz),
         (Instruction::I32Ctz, execute_i32_ctz, UntypedValue::i32_ctz),
         (Instruction::I32Popcnt, execute_i32_popcnt, UntypedValue::i32_popcnt),
@@ -32,7 +35,6 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::F32Floor, execute_f32_floor, UntypedValue::f32_floor),
         (Instruction::F32Trunc, execute_f32_trunc, UntypedValue::f32_trunc),
         (Instruction::F32Nearest, execute_f32_nearest, UntypedValue::f32_nearest),
-        (Instruction::F32Sqrt, execute_f32_sqrt, UntypedValue::f32_sqrt),
 
         (Instruction::F64Abs, execute_f64_abs, UntypedValue::f64_abs),
         (Instruction::F64Neg, execute_f64_neg, UntypedValue::f64_neg),
@@ -40,6 +42,24 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::F64Floor, execute_f64_floor, UntypedValue::f64_floor),
         (Instruction::F64Trunc, execute_f64_trunc, UntypedValue::f64_trunc),
         (Instruction::F64Nearest, execute_f64_nearest, UntypedValue::f64_nearest),
-        (Instruction::F64Sqrt, execute_f64_sqrt, UntypedValue::f64_sqrt),
+    }
+    /// Executes an [`Instruction::F32Sqrt`].
+    #[inline(always)]
+    pub fn execute_f32_sqrt(&mut self, instr: UnaryInstr) {
+        if self.approx_sqrt {
+            self.execute_unary(instr, UntypedValue::f32_sqrt_approx)
+        } else {
+            self.execute_unary(instr, UntypedValue::f32_sqrt)
+        }
+    }
+
+    /// Executes an [`Instruction::F64Sqrt`].
+    #[inline(always)]
+    pub fn execute_f64_sqrt(&mut self, instr: UnaryInstr) {
+        if self.approx_sqrt {
+            self.execute_unary(instr, UntypedValue::f64_sqrt_approx)
+        } else {
+            self.execute_unary(instr, UntypedValue::f64_sqrt)
+        }
     }
 }
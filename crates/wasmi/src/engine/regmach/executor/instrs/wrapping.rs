@@ -0,0 +1,208 @@
+use super::Executor;
+use crate::{
+    core::UntypedValue,
+    engine::regmach::bytecode::{BinInstr, BinInstrImm16},
+};
+
+#[cfg(feature = "checked-arithmetic")]
+use crate::engine::regmach::bytecode::Register;
+#[cfg(feature = "checked-arithmetic")]
+use core::fmt::Display;
+
+#[cfg(doc)]
+use crate::engine::regmach::bytecode::Instruction;
+
+/// A Wasm integer operator with wrapping semantics.
+///
+/// Used to verify executed wrapping integer arithmetic.
+/// Read [`Config::set_checked_arithmetic`] for more information.
+///
+/// [`Config::set_checked_arithmetic`]: crate::Config::set_checked_arithmetic
+#[cfg(feature = "checked-arithmetic")]
+#[derive(Debug, Copy, Clone)]
+pub enum WrappingOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+#[cfg(feature = "checked-arithmetic")]
+impl WrappingOp {
+    /// Returns the infinitely precise result of `self` applied to `lhs` and `rhs`.
+    fn precise(self, lhs: i128, rhs: i128) -> i128 {
+        let result = match self {
+            Self::Add => lhs.checked_add(rhs),
+            Self::Sub => lhs.checked_sub(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+        };
+        result.expect("checked arithmetic: `i128` cannot overflow for 64-bit operands")
+    }
+}
+
+/// An integer type of the verified Wasm wrapping integer arithmetic.
+#[cfg(feature = "checked-arithmetic")]
+pub trait WrappingInt:
+    Copy + PartialEq + Display + Into<i128> + From<UntypedValue> + Into<UntypedValue>
+{
+    /// Reduces the precise `value` modulo `2^N` where `N` is the bit width of `Self`.
+    fn wrap(value: i128) -> Self;
+
+    /// Clamps the precise `value` to the bounds of `Self`.
+    #[cfg(test)]
+    fn saturate(value: i128) -> Self;
+}
+
+macro_rules! impl_wrapping_int {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            #[cfg(feature = "checked-arithmetic")]
+            impl WrappingInt for $ty {
+                fn wrap(value: i128) -> Self {
+                    value as Self
+                }
+
+                #[cfg(test)]
+                fn saturate(value: i128) -> Self {
+                    value.clamp(Self::MIN.into(), Self::MAX.into()) as Self
+                }
+            }
+        )*
+    };
+}
+impl_wrapping_int!(i32, i64);
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Returns the `lhs` and `rhs` inputs of a wrapping integer instruction if they are verified.
+    #[cfg(feature = "checked-arithmetic")]
+    #[inline(always)]
+    pub fn wrapping_inputs<T>(
+        &self,
+        lhs: impl FnOnce(&Self) -> T,
+        rhs: impl FnOnce(&Self) -> T,
+    ) -> Option<(T, T)> {
+        if !self.checked_arithmetic {
+            return None;
+        }
+        Some((lhs(self), rhs(self)))
+    }
+
+    /// Verifies the `result` of the executed wrapping integer `op` against Wasm semantics.
+    ///
+    /// # Note
+    ///
+    /// This only checks anything if `inputs` are provided which is the case if enabled via
+    /// [`Config::set_checked_arithmetic`].
+    ///
+    /// # Panics
+    ///
+    /// If the value of the `result` register differs from the infinitely precise result of `op`
+    /// applied to `inputs` reduced modulo `2^N` where `N` is the bit width of `T`.
+    ///
+    /// [`Config::set_checked_arithmetic`]: crate::Config::set_checked_arithmetic
+    #[cfg(feature = "checked-arithmetic")]
+    pub fn check_wrapping<T>(&mut self, op: WrappingOp, inputs: Option<(T, T)>, result: Register)
+    where
+        T: WrappingInt,
+    {
+        let (lhs, rhs) = match inputs {
+            Some(inputs) => inputs,
+            None => return,
+        };
+        let precise = op.precise(lhs.into(), rhs.into());
+        #[cfg(test)]
+        if self.corrupt_arithmetic {
+            self.set_register(result, T::saturate(precise));
+        }
+        let expected = T::wrap(precise);
+        let actual = T::from(self.get_register(result));
+        assert!(
+            actual == expected,
+            "checked arithmetic: wrapping `{op:?}` of {lhs} and {rhs} yielded {actual} instead of {expected}",
+        );
+    }
+}
+
+macro_rules! impl_wrapping_binary {
+    ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr, WrappingOp::$wrapping:ident) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: BinInstr) {
+                #[cfg(feature = "checked-arithmetic")]
+                let inputs = self.wrapping_inputs(
+                    |this| <$ty>::from(this.get_register(instr.lhs)),
+                    |this| <$ty>::from(this.get_register(instr.rhs)),
+                );
+                self.execute_binary(instr, $op);
+                #[cfg(feature = "checked-arithmetic")]
+                self.check_wrapping(WrappingOp::$wrapping, inputs, instr.result);
+            }
+        )*
+    };
+}
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_wrapping_binary! {
+        (i32, Instruction::I32Add, execute_i32_add, UntypedValue::i32_add, WrappingOp::Add),
+        (i32, Instruction::I32Sub, execute_i32_sub, UntypedValue::i32_sub, WrappingOp::Sub),
+        (i32, Instruction::I32Mul, execute_i32_mul, UntypedValue::i32_mul, WrappingOp::Mul),
+
+        (i64, Instruction::I64Add, execute_i64_add, UntypedValue::i64_add, WrappingOp::Add),
+        (i64, Instruction::I64Sub, execute_i64_sub, UntypedValue::i64_sub, WrappingOp::Sub),
+        (i64, Instruction::I64Mul, execute_i64_mul, UntypedValue::i64_mul, WrappingOp::Mul),
+    }
+}
+
+macro_rules! impl_wrapping_binary_imm16 {
+    ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr, WrappingOp::$wrapping:ident) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: BinInstrImm16<$ty>) {
+                #[cfg(feature = "checked-arithmetic")]
+                let inputs = self.wrapping_inputs(
+                    |this| <$ty>::from(this.get_register(instr.reg_in)),
+                    |_| <$ty>::from(instr.imm_in),
+                );
+                self.execute_binary_imm16(instr, $op);
+                #[cfg(feature = "checked-arithmetic")]
+                self.check_wrapping(WrappingOp::$wrapping, inputs, instr.result);
+            }
+        )*
+    };
+}
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_wrapping_binary_imm16! {
+        (i32, Instruction::I32AddImm16, execute_i32_add_imm16, UntypedValue::i32_add, WrappingOp::Add),
+        (i32, Instruction::I32SubImm16, execute_i32_sub_imm16, UntypedValue::i32_sub, WrappingOp::Sub),
+        (i32, Instruction::I32MulImm16, execute_i32_mul_imm16, UntypedValue::i32_mul, WrappingOp::Mul),
+
+        (i64, Instruction::I64AddImm16, execute_i64_add_imm16, UntypedValue::i64_add, WrappingOp::Add),
+        (i64, Instruction::I64SubImm16, execute_i64_sub_imm16, UntypedValue::i64_sub, WrappingOp::Sub),
+        (i64, Instruction::I64MulImm16, execute_i64_mul_imm16, UntypedValue::i64_mul, WrappingOp::Mul),
+    }
+}
+
+macro_rules! impl_wrapping_binary_imm16_rev {
+    ( $( ($ty:ty, Instruction::$var_name:ident, $fn_name:ident, $op:expr, WrappingOp::$wrapping:ident) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
+            #[inline(always)]
+            pub fn $fn_name(&mut self, instr: BinInstrImm16<$ty>) {
+                #[cfg(feature = "checked-arithmetic")]
+                let inputs = self.wrapping_inputs(
+                    |_| <$ty>::from(instr.imm_in),
+                    |this| <$ty>::from(this.get_register(instr.reg_in)),
+                );
+                self.execute_binary_imm16_rev(instr, $op);
+                #[cfg(feature = "checked-arithmetic")]
+                self.check_wrapping(WrappingOp::$wrapping, inputs, instr.result);
+            }
+        )*
+    };
+}
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_wrapping_binary_imm16_rev! {
+        (i32, Instruction::I32SubImm16Rev, execute_i32_sub_imm16_rev, UntypedValue::i32_sub, WrappingOp::Sub),
+        (i64, Instruction::I64SubImm16Rev, execute_i64_sub_imm16_rev, UntypedValue::i64_sub, WrappingOp::Sub),
+    }
+}
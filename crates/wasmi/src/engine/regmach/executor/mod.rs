@@ -1,5 +1,5 @@
-use self::instrs::{execute_instrs, WasmOutcome};
-use super::stack::CallFrame;
+use self::instrs::{execute_instr_step, execute_instrs, WasmOutcome};
+use super::stack::{CallFrame, ValueStack};
 pub use super::Stack;
 use crate::{
     engine::{
@@ -7,17 +7,21 @@ use crate::{
         regmach::{
             bytecode::{Register, RegisterSpan},
             code_map::InstructionPtr,
+            InstructionProfile,
         },
         CallParams,
         CallResults,
         DedupFuncType,
+        invalid_func_body,
         EngineResources,
         FuncParams,
+        InternalError,
         TaggedTrap,
     },
     func::HostFuncEntity,
     AsContext,
     AsContextMut,
+    CallTransition,
     Func,
     FuncEntity,
     Instance,
@@ -37,6 +41,8 @@ pub struct EngineExecutor<'engine> {
     res: &'engine EngineResources,
     /// The value and call stacks.
     stack: &'engine mut Stack,
+    /// The instruction counters updated by the execution if profiling is enabled.
+    profile: Option<&'engine mut InstructionProfile>,
 }
 
 impl<'engine> EngineExecutor<'engine> {
@@ -44,7 +50,17 @@ impl<'engine> EngineExecutor<'engine> {
     ///
     /// [`StackLimits`]: []
     pub fn new(res: &'engine EngineResources, stack: &'engine mut Stack) -> Self {
-        Self { res, stack }
+        Self {
+            res,
+            stack,
+            profile: None,
+        }
+    }
+
+    /// Makes the [`EngineExecutor`] count all executed instructions in `profile`.
+    pub fn with_profile(mut self, profile: &'engine mut InstructionProfile) -> Self {
+        self.profile = Some(profile);
+        self
     }
 
     /// Executes the given [`Func`] using the given `params`.
@@ -66,22 +82,64 @@ impl<'engine> EngineExecutor<'engine> {
     where
         Results: CallResults,
     {
+        let func_type =
+            self.init_root_func(ctx.as_context_mut(), func, params, results.len_results())?;
+        if self.stack.calls.peek().is_some() {
+            self.execute_func(ctx.as_context_mut())?;
+        }
+        let results = self.write_results_back(results, func_type)?;
+        Ok(results)
+    }
+
+    /// Initializes the [`Stack`] for the execution of the root [`Func`] using the given `params`.
+    ///
+    /// Returns the function type of `func`.
+    ///
+    /// # Note
+    ///
+    /// - Wasm functions are pushed onto the call stack but not yet executed.
+    /// - Host functions are executed immediately.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `params` or `len_results` do not match the function type of `func`.
+    /// - If the [`Stack`] overflows while initializing the root call.
+    /// - When encountering a host trap during the execution of a host `func`.
+    pub fn init_root_func<T>(
+        &mut self,
+        mut ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        len_results: usize,
+    ) -> Result<DedupFuncType, TaggedTrap> {
         self.stack.reset();
-        let func_type;
+        let params = params.call_params();
+        let func_type = *ctx.as_context().store.inner.resolve_func(func).ty_dedup();
+        InternalError::ensure_call_arity(
+            self.res.func_types.resolve_func_type(&func_type),
+            params.len(),
+            len_results,
+        )
+        .map_err(InternalError::into_trap)?;
         match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
-                func_type = *wasm_func.ty_dedup();
                 // We reserve space on the stack to write the results of the root function execution.
-                self.stack.values.extend_zeros(results.len_results());
+                self.stack.values.extend_zeros(len_results);
                 let instance = wasm_func.instance();
                 let compiled_func = self.res.code_map_2.get(wasm_func.func_body());
+                if params.len() > usize::from(compiled_func.len_cells()) {
+                    // The compiled function body has fewer cells than its function type has parameters.
+                    return Err(InternalError::call_arity_mismatch().into_trap().into());
+                }
                 let (base_ptr, frame_ptr) = self.stack.values.alloc_call_frame(compiled_func)?;
-                // Safety: We use the `base_ptr` that we just received upon allocating the new
-                //         call frame which is guaranteed to be valid for this particular operation
-                //         until deallocating the call frame again.
-                //         Also we are providing call parameters which have been checked already to
-                //         be exactly the length of the expected function arguments.
-                unsafe { self.stack.values.fill_at(base_ptr, params.call_params()) };
+                if params.len() != 0 {
+                    // Safety: We use the `base_ptr` that we just received upon allocating the new
+                    //         call frame which is guaranteed to be valid for this particular operation
+                    //         until deallocating the call frame again.
+                    //         Also we are providing call parameters which have been checked already to
+                    //         fit into the cells of the new call frame.
+                    unsafe { self.stack.values.fill_at(base_ptr, params) };
+                }
                 self.stack.calls.push(CallFrame::new(
                     InstructionPtr::new(compiled_func.instrs().as_ptr()),
                     frame_ptr,
@@ -89,10 +147,12 @@ impl<'engine> EngineExecutor<'engine> {
                     RegisterSpan::new(Register::from_i16(0)),
                     *instance,
                 ))?;
-                self.execute_func(ctx.as_context_mut())?;
+                ctx.as_context_mut()
+                    .store
+                    .inner
+                    .notify_call_observer(|| compiled_func.func_index(), CallTransition::Enter);
             }
             FuncEntity::Host(host_func) => {
-                func_type = *host_func.ty_dedup();
                 // The host function signature is required for properly
                 // adjusting, inspecting and manipulating the value stack.
                 let (input_types, output_types) = self
@@ -108,15 +168,14 @@ impl<'engine> EngineExecutor<'engine> {
                 self.stack.values.reserve(max_inout)?;
                 self.stack.values.extend_zeros(max_inout);
                 let values = &mut self.stack.values.as_slice_mut()[..len_params];
-                for (value, param) in values.iter_mut().zip(params.call_params()) {
+                for (value, param) in values.iter_mut().zip(params) {
                     *value = param;
                 }
                 let host_func = *host_func;
                 self.dispatch_host_func(ctx.as_context_mut(), host_func, HostFuncCaller::Root)?;
             }
         };
-        let results = self.write_results_back(results, func_type);
-        Ok(results)
+        Ok(func_type)
     }
 
     /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
@@ -156,6 +215,40 @@ impl<'engine> EngineExecutor<'engine> {
         }
     }
 
+    /// Executes exactly one instruction of the top most Wasm function on the [`Stack`].
+    ///
+    /// Host functions called by the executed instruction are executed as part of the same step.
+    ///
+    /// Returns `true` once the root function has returned.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during execution.
+    pub fn step_func<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<bool, Trap> {
+        let mut cache = match self.stack.calls.peek() {
+            Some(frame) => InstanceCache::from(frame.instance()),
+            None => return Ok(true),
+        };
+        match self.execute_compiled_instr(ctx.as_context_mut(), &mut cache)? {
+            None => Ok(false),
+            Some(WasmOutcome::Return) => Ok(true),
+            Some(WasmOutcome::Call {
+                results,
+                ref host_func,
+            }) => {
+                let instance = *self
+                    .stack
+                    .calls
+                    .peek()
+                    .expect("caller must be on the stack")
+                    .instance();
+                self.execute_host_func(&mut ctx, results, host_func, &instance)
+                    .map_err(TaggedTrap::into_trap)?;
+                Ok(self.stack.calls.peek().is_none())
+            }
+        }
+    }
+
     fn execute_host_func<T>(
         &mut self,
         ctx: &mut StoreContextMut<'_, T>,
@@ -164,8 +257,8 @@ impl<'engine> EngineExecutor<'engine> {
         instance: &Instance,
     ) -> Result<(), TaggedTrap> {
         let func_entity = match ctx.as_context().store.inner.resolve_func(func) {
-            FuncEntity::Wasm(wasm_func) => {
-                unreachable!("expected a host function but found: {wasm_func:?}")
+            FuncEntity::Wasm(_) => {
+                return Err(TaggedTrap::Wasm(invalid_func_body(ctx.engine().config())))
             }
             FuncEntity::Host(host_func) => *host_func,
         };
@@ -229,6 +322,31 @@ impl<'a> HostFuncCaller<'a> {
     }
 }
 
+/// The convention used to exchange parameters and results with a called host function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HostCallConvention {
+    /// Parameters and results are exchanged via a temporary region of the value stack.
+    ///
+    /// The value stack is extended by the maximum of the number of parameters
+    /// and results before the call and shrunk again after the call returned.
+    RegisterRegion,
+    /// Parameters and results are exchanged via the host call buffer of the value stack.
+    ///
+    /// This avoids growing and shrinking the value stack for every host function call
+    /// and is used for host functions with small signatures called from Wasm.
+    DirectSlice,
+}
+
+impl HostCallConvention {
+    /// Returns the [`HostCallConvention`] for a host function with the given signature shape.
+    pub fn new(len_params: usize, len_results: usize) -> Self {
+        if len_params.max(len_results) <= ValueStack::HOST_BUFFER_LEN {
+            return Self::DirectSlice;
+        }
+        Self::RegisterRegion
+    }
+}
+
 impl<'engine> EngineExecutor<'engine> {
     /// Dispatches a host function call and returns its result.
     fn dispatch_host_func<T>(
@@ -244,17 +362,25 @@ impl<'engine> EngineExecutor<'engine> {
             .func_types
             .resolve_func_type(host_func.ty_dedup())
             .params_results();
-        // In case the host function returns more values than it takes
-        // we are required to extend the value stack.
         let len_inputs = input_types.len();
         let len_outputs = output_types.len();
         let max_inout = len_inputs.max(len_outputs);
-        let values = self.stack.values.as_slice_mut();
-        let params_results = FuncParams::new(
-            values.split_at_mut(values.len() - max_inout).1,
-            len_inputs,
-            len_outputs,
-        );
+        // Host functions called by the host always use the value stack since
+        // their results are read back from the value stack afterwards.
+        let convention = match caller {
+            HostFuncCaller::Root => HostCallConvention::RegisterRegion,
+            HostFuncCaller::Wasm { .. } => HostCallConvention::new(len_inputs, len_outputs),
+        };
+        let buffer = match convention {
+            HostCallConvention::RegisterRegion => {
+                // In case the host function returns more values than it takes
+                // the value stack has already been extended by the caller.
+                let values = self.stack.values.as_slice_mut();
+                values.split_at_mut(values.len() - max_inout).1
+            }
+            HostCallConvention::DirectSlice => self.stack.values.host_buffer_mut(max_inout),
+        };
+        let params_results = FuncParams::new(buffer, len_inputs, len_outputs);
         // Now we are ready to perform the host function call.
         // Note: We need to clone the host function due to some borrowing issues.
         //       This should not be a big deal since host functions usually are cheap to clone.
@@ -271,7 +397,9 @@ impl<'engine> EngineExecutor<'engine> {
                 //       called host function. Since the host function failed we
                 //       need to clean up the temporary buffer values here.
                 //       This is required for resumable calls to work properly.
-                self.stack.values.drop(max_inout);
+                if convention == HostCallConvention::RegisterRegion {
+                    self.stack.values.drop(max_inout);
+                }
                 error
             })?;
         if let Some(results) = caller.results() {
@@ -292,7 +420,15 @@ impl<'engine> EngineExecutor<'engine> {
             // provide us with valid result registers.
             let mut caller_sp = unsafe { self.stack.values.stack_ptr_at(caller_offset) };
             // # Safety: See Safety (1) above.
-            let callee_sp = unsafe { self.stack.values.stack_ptr_last_n(max_inout) };
+            //
+            // The host call buffer is not part of the value stack cells and thus
+            // never overlaps with the registers of the caller.
+            let callee_sp = match convention {
+                HostCallConvention::RegisterRegion => unsafe {
+                    self.stack.values.stack_ptr_last_n(max_inout)
+                },
+                HostCallConvention::DirectSlice => self.stack.values.host_buffer_ptr(),
+            };
             let results = results.iter(len_outputs);
             let values = RegisterSpan::new(Register::from_i16(0)).iter(len_outputs);
             for (result, value) in results.zip(values) {
@@ -303,7 +439,9 @@ impl<'engine> EngineExecutor<'engine> {
                 *result_cell = value_cell;
             }
             // Finally, the value stack needs to be truncated to its original size.
-            self.stack.values.drop(max_inout);
+            if convention == HostCallConvention::RegisterRegion {
+                self.stack.values.drop(max_inout);
+            }
         }
         Ok(())
     }
@@ -324,16 +462,6 @@ impl<'engine> EngineExecutor<'engine> {
         ctx: StoreContextMut<T>,
         cache: &mut InstanceCache,
     ) -> Result<WasmOutcome, Trap> {
-        /// Converts a [`TrapCode`] into a [`Trap`].
-        ///
-        /// This function exists for performance reasons since its `#[cold]`
-        /// annotation has severe effects on performance.
-        #[inline]
-        #[cold]
-        fn make_trap(code: TrapCode) -> Trap {
-            code.into()
-        }
-
         let (store_inner, mut resource_limiter) = ctx.store.store_inner_and_resource_limiter_ref();
         let value_stack = &mut self.stack.values;
         let call_stack = &mut self.stack.calls;
@@ -348,8 +476,59 @@ impl<'engine> EngineExecutor<'engine> {
             code_map,
             func_types,
             &mut resource_limiter,
+            self.profile.as_deref_mut(),
+        )
+        .map_err(|code| self.make_trap(code))
+    }
+
+    /// Executes exactly one instruction of the top most function frame.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps.
+    fn execute_compiled_instr<T>(
+        &mut self,
+        ctx: StoreContextMut<T>,
+        cache: &mut InstanceCache,
+    ) -> Result<Option<WasmOutcome>, Trap> {
+        let (store_inner, mut resource_limiter) = ctx.store.store_inner_and_resource_limiter_ref();
+        execute_instr_step(
+            store_inner,
+            cache,
+            &mut self.stack.values,
+            &mut self.stack.calls,
+            &self.res.code_map_2,
+            &self.res.func_types,
+            &mut resource_limiter,
         )
-        .map_err(make_trap)
+        .map_err(|code| self.make_trap(code))
+    }
+
+    /// Converts a [`TrapCode`] into a [`Trap`].
+    ///
+    /// The top most call frame is located at the trapping instruction. Attaches the
+    /// message of the trap folded at compilation time if the trapping instruction has
+    /// such a message as well as the [`TrapLocation`] of the trapping instruction if recorded.
+    ///
+    /// This function exists for performance reasons since its `#[cold]`
+    /// annotation has severe effects on performance.
+    ///
+    /// [`TrapLocation`]: crate::core::TrapLocation
+    #[inline]
+    #[cold]
+    fn make_trap(&self, code: TrapCode) -> Trap {
+        let mut trap = Trap::from(code);
+        let ip = match self.stack.calls.peek() {
+            Some(frame) => frame.instr_ptr(),
+            None => return trap,
+        };
+        if let Some(message) = self.res.code_map_2.trap_message(ip, code) {
+            trap = trap.with_message(message);
+        }
+        if let Some(location) = self.res.code_map_2.trap_location(ip) {
+            trap = trap.with_location(location);
+        }
+        trap
     }
 
     /// Writes the results of the function execution back into the `results` buffer.
@@ -358,19 +537,23 @@ impl<'engine> EngineExecutor<'engine> {
     ///
     /// The value stack is empty after this operation.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// - If the `results` buffer length does not match the remaining amount of stack values.
+    /// If the `results` buffer length does not match the remaining amount of stack values.
     #[inline]
-    fn write_results_back<Results>(
+    pub fn write_results_back<Results>(
         &mut self,
         results: Results,
         ty: DedupFuncType,
-    ) -> <Results as CallResults>::Results
+    ) -> Result<<Results as CallResults>::Results, Trap>
     where
         Results: CallResults,
     {
         let len_results = self.res.func_types.resolve_func_type(&ty).results().len();
-        results.call_results(&self.stack.values.as_slice()[..len_results])
+        let values = self.stack.values.as_slice();
+        if len_results != results.len_results() || len_results > values.len() {
+            return Err(InternalError::call_arity_mismatch().into_trap());
+        }
+        Ok(results.call_results(&values[..len_results]))
     }
 }
@@ -1,6 +1,7 @@
 pub mod bytecode;
 pub mod code_map;
 mod executor;
+mod profile;
 mod stack;
 mod translator;
 
@@ -10,15 +11,17 @@ mod tests;
 use self::executor::EngineExecutor;
 pub use self::{
     code_map::CodeMap,
+    profile::InstructionProfile,
     stack::Stack,
-    translator::{FuncLocalConstsIter, FuncTranslator, FuncTranslatorAllocations},
+    translator::{FuncTranslator, FuncTranslatorAllocations, PeepholeReport},
 };
 use crate::{
     core::Trap,
-    engine::{CallParams, CallResults, EngineInner, TaggedTrap},
+    engine::{CallParams, CallResults, DedupFuncType, EngineBackend, EngineInner, TaggedTrap},
     Func,
     StoreContextMut,
 };
+use core::sync::atomic::Ordering;
 
 #[cfg(doc)]
 use crate::Store;
@@ -42,12 +45,118 @@ impl EngineInner {
     where
         Results: CallResults,
     {
-        let res = self.res.read();
+        let (res, _marker) = self.res_for_execution_in(&ctx)?;
+        self.check_func_body(&res, &ctx, func, EngineBackend::RegisterMachine)?;
         let mut stack = self.stacks.lock().reuse_or_new_2();
-        let results = EngineExecutor::new(&res, &mut stack)
-            .execute_root_func(ctx, func, params, results)
-            .map_err(TaggedTrap::into_trap);
+        let results = if self.config().get_profile_instructions() {
+            let mut profile = self.profile.lock().take().unwrap_or_default();
+            let results = EngineExecutor::new(&res, &mut stack)
+                .with_profile(&mut profile)
+                .execute_root_func(ctx, func, params, results)
+                .map_err(TaggedTrap::into_trap);
+            self.merge_instruction_profile(profile);
+            results
+        } else {
+            EngineExecutor::new(&res, &mut stack)
+                .execute_root_func(ctx, func, params, results)
+                .map_err(TaggedTrap::into_trap)
+        };
         self.stacks.lock().recycle_2(stack);
         results
     }
+
+    /// Merges `profile` into the [`InstructionProfile`] accumulated by the engine.
+    ///
+    /// # Note
+    ///
+    /// Concurrent executions each take the accumulated profile if available
+    /// and otherwise start with a fresh one, so both cases must be merged.
+    fn merge_instruction_profile(&self, profile: InstructionProfile) {
+        let mut accumulated = self.profile.lock();
+        match accumulated.as_mut() {
+            Some(accumulated) => accumulated.merge(&profile),
+            None => *accumulated = Some(profile),
+        }
+    }
+
+    /// Initializes the execution of the given [`Func`] with the given `params` step by step.
+    ///
+    /// Returns the [`Stack`] that holds the state of the stepped execution.
+    ///
+    /// # Note
+    ///
+    /// Host functions are executed entirely by this method.
+    ///
+    /// # Panics
+    ///
+    /// If the engine does not use the register-machine backend.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Stack`] overflows while initializing the call.
+    /// - When encountering a host trap during the execution of a host `func`.
+    pub fn init_stepped_func<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        len_results: usize,
+    ) -> Result<Stack, Trap> {
+        let backend = self.config().engine_backend();
+        assert!(
+            matches!(backend, EngineBackend::RegisterMachine),
+            "stepped execution is only supported by the register-machine backend"
+        );
+        let (res, _marker) = self.res_for_execution_in(&ctx)?;
+        self.check_func_body(&res, &ctx, func, EngineBackend::RegisterMachine)?;
+        let mut stack = self.stacks.lock().reuse_or_new_2();
+        stack.resets = res.resets;
+        let result = EngineExecutor::new(&res, &mut stack)
+            .init_root_func(ctx, func, params, len_results)
+            .map_err(TaggedTrap::into_trap);
+        match result {
+            Ok(_) => {
+                self.stepped_calls.fetch_add(1, Ordering::AcqRel);
+                Ok(stack)
+            }
+            Err(trap) => {
+                self.stacks.lock().recycle_2(stack);
+                Err(trap)
+            }
+        }
+    }
+
+    /// Executes exactly one instruction of the stepped execution held by `stack`.
+    ///
+    /// Returns `true` once the root function has returned.
+    ///
+    /// # Errors
+    ///
+    /// When encountering a Wasm or host trap during execution.
+    pub fn step_func<T>(&self, ctx: StoreContextMut<T>, stack: &mut Stack) -> Result<bool, Trap> {
+        let (res, _marker) = self.res_for_execution_in(&ctx)?;
+        res.assert_not_reset_since(stack.resets);
+        EngineExecutor::new(&res, stack).step_func(ctx)
+    }
+
+    /// Writes the results of the finished stepped execution held by `stack` into `results`.
+    pub fn write_stepped_results<Results>(
+        &self,
+        stack: &mut Stack,
+        func_type: DedupFuncType,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, Trap>
+    where
+        Results: CallResults,
+    {
+        let res = self.res.read();
+        res.assert_not_reset_since(stack.resets);
+        EngineExecutor::new(&res, stack).write_results_back(results, func_type)
+    }
+
+    /// Recycles the register-machine `stack` of a stepped execution for reuse.
+    pub fn recycle_stack_2(&self, stack: Stack) {
+        self.stepped_calls.fetch_sub(1, Ordering::AcqRel);
+        self.stacks.lock().recycle_2(stack);
+    }
 }
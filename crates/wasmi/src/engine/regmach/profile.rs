@@ -0,0 +1,86 @@
+//! Per-opcode instruction counters of the register-machine executor.
+
+use super::bytecode::{Instruction, NUM_OPCODES, OPCODE_NAMES};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Display};
+
+#[cfg(doc)]
+use crate::{Config, Engine};
+
+/// Counts how often the [`Engine`] executed each kind of register-machine instruction.
+///
+/// # Note
+///
+/// - The counters are only collected if enabled via [`Config::profile_instructions`]
+///   and are retrieved via [`Engine::take_instruction_profile`].
+/// - Instructions are identified by the names of their register-machine bytecode
+///   variants, e.g. `I32Add` or `I32AddImm16`, not by the names of Wasm operators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionProfile {
+    /// The number of executions of every instruction indexed by its opcode.
+    counts: Box<[u64; NUM_OPCODES]>,
+}
+
+impl Default for InstructionProfile {
+    fn default() -> Self {
+        Self {
+            counts: Box::new([0; NUM_OPCODES]),
+        }
+    }
+}
+
+impl InstructionProfile {
+    /// Records an execution of `instr`.
+    #[inline(always)]
+    pub(crate) fn record(&mut self, instr: &Instruction) {
+        self.counts[usize::from(instr.opcode())] += 1;
+    }
+
+    /// Adds the counters of `other` to the counters of `self`.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other;
+        }
+    }
+
+    /// Returns how often the instruction named `name` has been executed.
+    ///
+    /// Returns `0` if there is no instruction named `name`.
+    pub fn count(&self, name: &str) -> u64 {
+        OPCODE_NAMES
+            .iter()
+            .position(|opcode| *opcode == name)
+            .map(|opcode| self.counts[opcode])
+            .unwrap_or(0)
+    }
+
+    /// Returns the total number of executed instructions.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns an iterator over the names and counters of all executed instructions.
+    ///
+    /// Instructions that have not been executed are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        OPCODE_NAMES
+            .iter()
+            .zip(self.counts.iter())
+            .filter(|(_, count)| **count != 0)
+            .map(|(name, count)| (*name, *count))
+    }
+}
+
+impl Display for InstructionProfile {
+    /// Writes one line per executed instruction sorted by descending counters.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut counts: Vec<(&str, u64)> = self.iter().collect();
+        counts.sort_by(|(lhs_name, lhs), (rhs_name, rhs)| {
+            rhs.cmp(lhs).then_with(|| lhs_name.cmp(rhs_name))
+        });
+        for (name, count) in counts {
+            writeln!(f, "{name}: {count}")?;
+        }
+        Ok(())
+    }
+}
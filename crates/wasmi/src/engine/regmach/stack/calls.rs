@@ -34,14 +34,25 @@ impl CallStack {
     /// Default value for the maximum recursion depth.
     pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 1024;
 
-    /// Creates a new [`CallStack`] using the given recursion limit.
-    pub fn new(recursion_limit: usize) -> Self {
+    /// Creates a new [`CallStack`] using the given initial depth and recursion limit.
+    pub fn new(initial_depth: usize, recursion_limit: usize) -> Self {
         Self {
-            calls: Vec::new(),
+            calls: Vec::with_capacity(initial_depth.min(recursion_limit)),
             recursion_limit,
         }
     }
 
+    /// Sets the maximum allowed recursion depth of the [`CallStack`] to `recursion_limit`.
+    pub fn set_recursion_limit(&mut self, recursion_limit: usize) {
+        self.recursion_limit = recursion_limit;
+    }
+
+    /// Returns the number of [`CallFrame`] the [`CallStack`] can hold without reallocating.
+    #[cfg(test)]
+    pub fn capacity(&self) -> usize {
+        self.calls.capacity()
+    }
+
     /// Clears the [`CallStack`] entirely.
     ///
     /// # Note
@@ -60,6 +71,12 @@ impl CallStack {
         self.calls.len()
     }
 
+    /// Returns the [`CallFrame`] on the [`CallStack`] starting with the root frame.
+    #[inline]
+    pub fn as_slice(&self) -> &[CallFrame] {
+        &self.calls
+    }
+
     /// Pushes a [`CallFrame`] onto the [`CallStack`].
     ///
     /// # Errors
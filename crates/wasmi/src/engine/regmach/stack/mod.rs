@@ -1,11 +1,21 @@
 mod calls;
 mod values;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::{
     calls::{CallFrame, CallStack},
     values::{BaseValueStackOffset, FrameValueStackOffset, ValueStack, ValueStackPtr},
 };
-use crate::{core::TrapCode, StackLimits};
+use super::code_map::CodeMap;
+use crate::{
+    core::{TrapCode, UntypedValue},
+    store::StoreInner,
+    StackLimits,
+};
+use alloc::string::String;
+use core::fmt::Write as _;
 
 /// Returns a [`TrapCode`] signalling a stack overflow.
 #[cold]
@@ -20,6 +30,10 @@ pub struct Stack {
     pub values: ValueStack,
     /// The call stack.
     pub calls: CallStack,
+    /// The number of [`Engine::reset`] calls before the execution held by the [`Stack`] started.
+    ///
+    /// [`Engine::reset`]: crate::Engine::reset
+    pub resets: u32,
 }
 
 impl Stack {
@@ -38,12 +52,29 @@ impl Stack {
     ///
     /// [`Config`]: [`crate::Config`]
     pub fn new(limits: StackLimits) -> Self {
-        let calls = CallStack::new(limits.maximum_recursion_depth);
+        let calls = CallStack::new(
+            limits.initial_recursion_depth(),
+            limits.maximum_recursion_depth,
+        );
         let values = ValueStack::new(
             limits.initial_value_stack_height,
             limits.maximum_value_stack_height,
         );
-        Self { values, calls }
+        Self {
+            values,
+            calls,
+            resets: 0,
+        }
+    }
+
+    /// Applies the new [`StackLimits`] to the [`Stack`].
+    ///
+    /// Returns `false` if the [`Stack`] must be reallocated in order to respect the new `limits`.
+    pub fn set_limits(&mut self, limits: StackLimits) -> bool {
+        self.calls
+            .set_recursion_limit(limits.maximum_recursion_depth);
+        self.values
+            .set_maximum_len(limits.maximum_value_stack_height)
     }
 
     /// Resets the [`Stack`] for clean reuse.
@@ -61,6 +92,7 @@ impl Stack {
         Self {
             values: ValueStack::empty(),
             calls: CallStack::default(),
+            resets: 0,
         }
     }
 
@@ -73,6 +105,98 @@ impl Stack {
         self.values.is_empty()
     }
 
+    /// Renders the [`CallStack`] and the registers of all its [`CallFrame`] into a [`String`].
+    ///
+    /// # Note
+    ///
+    /// The output is meant for debugging and is deterministic for the same [`Stack`] state.
+    /// Frames are rendered from the root frame at `frame #0` up to the top-most frame and
+    /// each frame lists its registers in ascending order of their index.
+    ///
+    /// Each frame renders the index of its Wasm function within its module as `func`
+    /// and the index of the next [`Instruction`] it executes within its compiled function
+    /// as `pc`. Both are recovered from the instruction pointer of the frame using the
+    /// `code_map` and are rendered as `?` if it cannot be located.
+    ///
+    /// Register indices are relative to the base offset of their [`CallFrame`]:
+    ///
+    /// - `r0` and above refer to the parameters, locals and dynamically allocated registers.
+    /// - Negative indices such as `r-1` refer to the function local constant values
+    ///   stored in front of the base offset, the same indexing used by the translator.
+    ///
+    /// [`Instruction`]: crate::engine::regmach::bytecode::Instruction
+    pub fn dump(&self, code_map: &CodeMap) -> String {
+        let values = self.values.as_slice();
+        let frames = self.calls.as_slice();
+        let mut output = String::new();
+        for (n, frame) in frames.iter().enumerate() {
+            let frame_offset = usize::from(frame.frame_offset());
+            let base_offset = usize::from(frame.base_offset());
+            let end = frames
+                .get(n + 1)
+                .map(|next| usize::from(next.frame_offset()))
+                .unwrap_or(values.len());
+            match code_map.locate(frame.instr_ptr()) {
+                Some((func, pc)) => {
+                    write!(output, "frame #{n}: func={} pc={pc}", func.func_index())
+                }
+                None => write!(output, "frame #{n}: func=? pc=?"),
+            }
+            .expect("writing to a String cannot fail");
+            writeln!(
+                output,
+                " frame_offset={frame_offset} base_offset={base_offset} results=r{}",
+                frame.results().head().to_i16(),
+            )
+            .expect("writing to a String cannot fail");
+            for (index, value) in values[frame_offset..end].iter().enumerate() {
+                let register = (frame_offset + index) as isize - base_offset as isize;
+                writeln!(output, "  r{register} = 0x{:016X}", value.to_bits())
+                    .expect("writing to a String cannot fail");
+            }
+        }
+        output
+    }
+
+    /// Returns the value of the `index`-th parameter of the top-most [`CallFrame`].
+    ///
+    /// Returns `None` if there is no [`CallFrame`] or if `index` is out of bounds
+    /// for the parameters of the function executed by the top-most [`CallFrame`].
+    /// The function and its parameters are resolved via `ctx`.
+    ///
+    /// # Note
+    ///
+    /// - This is meant for debugging and host introspection, e.g. upon a trap.
+    /// - The parameters of a function are stored in its first registers starting at `r0`
+    ///   and are valid right after the call has been set up. Since parameters are
+    ///   mutable registers the returned value reflects their current value which
+    ///   might differ from the call arguments after the function overwrote them.
+    pub fn frame_param(&self, ctx: &StoreInner, index: usize) -> Option<UntypedValue> {
+        let frame = self.calls.peek()?;
+        let func_index = ctx.engine().func_index_at_2(frame.instr_ptr())?;
+        let func = ctx
+            .resolve_instance(frame.instance())
+            .get_func(func_index)?;
+        let func_type = ctx.resolve_func(&func).ty_dedup();
+        let len_params =
+            ctx.resolve_func_type_with(func_type, |func_type| func_type.params().len());
+        if index >= len_params {
+            return None;
+        }
+        let base_offset = usize::from(frame.base_offset());
+        self.values.as_slice().get(base_offset + index).copied()
+    }
+
+    /// Returns the registers of the top-most [`CallFrame`] starting at `r0`.
+    ///
+    /// Returns an empty slice if there is no [`CallFrame`].
+    pub fn frame_registers(&self) -> &[UntypedValue] {
+        match self.calls.peek() {
+            Some(frame) => &self.values.as_slice()[usize::from(frame.base_offset())..],
+            None => &[],
+        }
+    }
+
     /// Merge the two top-most [`CallFrame`] with respect to a tail call.
     ///
     /// # Panics (Debug)
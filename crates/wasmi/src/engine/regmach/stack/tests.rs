@@ -0,0 +1,96 @@
+use super::{BaseValueStackOffset, CallFrame, FrameValueStackOffset, Stack};
+use crate::{
+    core::{TrapCode, UntypedValue},
+    engine::regmach::{
+        bytecode::{Instruction, Register, RegisterSpan},
+        code_map::{CodeMap, InstructionPtr},
+    },
+    Engine,
+    StackLimits,
+    Store,
+};
+
+#[test]
+fn dump_two_frames() {
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    let instance = store.inner.alloc_instance();
+    let mut code_map = CodeMap::default();
+    let caller = code_map.alloc_func();
+    let callee = code_map.alloc_func();
+    let instrs = [
+        Instruction::Trap(TrapCode::UnreachableCodeReached),
+        Instruction::Return,
+    ];
+    code_map.init_func(caller, 3, 3, 1, [], instrs);
+    code_map.init_func(callee, 5, 1, 0, [], instrs);
+    let ip_at = |func, pc| InstructionPtr::new(code_map.get(func).instrs()[pc..].as_ptr());
+    let mut stack = Stack::new(StackLimits::default());
+    // Root frame: 1 function local constant, 2 mutable registers.
+    stack
+        .values
+        .extend_slice(&[10_i32, 1, 2].map(UntypedValue::from));
+    stack
+        .calls
+        .push(CallFrame::new(
+            ip_at(caller, 1),
+            FrameValueStackOffset::new(0),
+            BaseValueStackOffset::new(1),
+            RegisterSpan::new(Register::from_i16(0)),
+            instance,
+        ))
+        .unwrap();
+    // Callee frame: no function local constants, 1 mutable register.
+    stack.values.extend_slice(&[UntypedValue::from(3_i32)]);
+    stack
+        .calls
+        .push(CallFrame::new(
+            ip_at(callee, 0),
+            FrameValueStackOffset::new(3),
+            BaseValueStackOffset::new(3),
+            RegisterSpan::new(Register::from_i16(1)),
+            instance,
+        ))
+        .unwrap();
+    let expected = "\
+        frame #0: func=3 pc=1 frame_offset=0 base_offset=1 results=r0\n  \
+          r-1 = 0x000000000000000A\n  \
+          r0 = 0x0000000000000001\n  \
+          r1 = 0x0000000000000002\n\
+        frame #1: func=5 pc=0 frame_offset=3 base_offset=3 results=r1\n  \
+          r0 = 0x0000000000000003\n\
+    ";
+    assert_eq!(stack.dump(&code_map), expected);
+}
+
+#[test]
+fn initial_recursion_depth_preallocates() {
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    let instance = store.inner.alloc_instance();
+    let instrs = [Instruction::Return];
+    let ip = InstructionPtr::new(instrs.as_ptr());
+    let limits = StackLimits::default().with_initial_recursion_depth(100);
+    let mut stack = Stack::new(limits);
+    let capacity = stack.calls.capacity();
+    assert!(capacity >= 100);
+    for _ in 0..100 {
+        stack
+            .calls
+            .push(CallFrame::new(
+                ip,
+                FrameValueStackOffset::new(0),
+                BaseValueStackOffset::new(0),
+                RegisterSpan::new(Register::from_i16(0)),
+                instance,
+            ))
+            .unwrap();
+    }
+    assert_eq!(stack.calls.capacity(), capacity);
+}
+
+#[test]
+fn initial_recursion_depth_is_clamped() {
+    let mut limits = StackLimits::default().with_initial_recursion_depth(usize::MAX);
+    limits.maximum_recursion_depth = 10;
+    let stack = Stack::new(limits);
+    assert!(stack.calls.capacity() < 100);
+}
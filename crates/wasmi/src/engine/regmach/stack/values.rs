@@ -4,7 +4,7 @@ use crate::{
     engine::regmach::{bytecode::Register, code_map::CompiledFuncEntity},
 };
 use alloc::vec::Vec;
-use core::{fmt, fmt::Debug, iter, mem};
+use core::{fmt, fmt::Debug, iter, mem, ptr};
 use wasmi_core::TrapCode;
 
 #[cfg(doc)]
@@ -19,6 +19,13 @@ pub struct ValueStack {
     sp: usize,
     /// Maximal possible `sp` value.
     max_sp: usize,
+    /// Buffer to exchange parameters and results with called host functions.
+    ///
+    /// # Note
+    ///
+    /// This is used by host function calls with small signatures in order
+    /// to avoid growing and shrinking the [`ValueStack`] for every call.
+    host_buffer: [UntypedValue; Self::HOST_BUFFER_LEN],
 }
 
 impl ValueStack {
@@ -27,6 +34,9 @@ impl ValueStack {
 
     /// Default value for maximum value stack height in bytes.
     pub const DEFAULT_MAX_HEIGHT: usize = 1024 * Self::DEFAULT_MIN_HEIGHT;
+
+    /// The number of cells in the host function call buffer.
+    pub const HOST_BUFFER_LEN: usize = 4;
 }
 
 impl Debug for ValueStack {
@@ -79,9 +89,19 @@ impl ValueStack {
             values: vec![UntypedValue::default(); initial_len],
             sp: 0,
             max_sp: maximum_len,
+            host_buffer: Default::default(),
         }
     }
 
+    /// Sets the maximum length of the [`ValueStack`] to `maximum_len`.
+    ///
+    /// Returns `false` if the [`ValueStack`] already holds more than `maximum_len` cells
+    /// and therefore must be reallocated in order to respect the new limit.
+    pub fn set_maximum_len(&mut self, maximum_len: usize) -> bool {
+        self.max_sp = maximum_len;
+        self.capacity() <= maximum_len
+    }
+
     /// Creates an empty [`ValueStack`] that does not allocate heap memory.
     ///
     /// # Note
@@ -93,6 +113,7 @@ impl ValueStack {
             values: Vec::new(),
             sp: 0,
             max_sp: 0,
+            host_buffer: Default::default(),
         }
     }
 
@@ -131,7 +152,7 @@ impl ValueStack {
     }
 
     /// Returns the capacity of the [`ValueStack`].
-    fn capacity(&self) -> usize {
+    pub fn capacity(&self) -> usize {
         self.values.len()
     }
 
@@ -140,8 +161,28 @@ impl ValueStack {
         self.values.capacity() == 0
     }
 
+    /// Returns a [`ValueStackPtr`] to the host function call buffer.
+    ///
+    /// # Note
+    ///
+    /// The returned [`ValueStackPtr`] is valid for registers `0` up to
+    /// [`ValueStack::HOST_BUFFER_LEN`] and does not point into the
+    /// cells of the [`ValueStack`] itself.
+    pub fn host_buffer_ptr(&mut self) -> ValueStackPtr {
+        ValueStackPtr::new(self.host_buffer.as_mut_ptr())
+    }
+
+    /// Returns the first `len` cells of the host function call buffer.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is greater than [`ValueStack::HOST_BUFFER_LEN`].
+    pub fn host_buffer_mut(&mut self, len: usize) -> &mut [UntypedValue] {
+        &mut self.host_buffer[..len]
+    }
+
     /// Returns the current length of the [`ValueStack`].
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.sp
     }
 
@@ -450,6 +491,41 @@ impl ValueStackPtr {
         unsafe { &mut *ptr }
     }
 
+    /// Copies `len` consecutive values starting at `values` to the registers starting at `results`.
+    ///
+    /// # Note
+    ///
+    /// The copied register ranges are allowed to overlap.
+    ///
+    /// # Safety
+    ///
+    /// It is the callers responsibility to provide [`Register`] ranges that
+    /// do not access the underlying [`ValueStack`] out of bounds.
+    pub unsafe fn copy_span(&mut self, results: Register, values: Register, len: usize) {
+        let dst = self.register_ptr(results);
+        let src = self.register_ptr(values);
+        unsafe { ptr::copy(src, dst, len) }
+    }
+
+    /// Copies `len` consecutive values starting at `values` of `src` to the registers starting at `results`.
+    ///
+    /// # Safety
+    ///
+    /// It is the callers responsibility to provide [`Register`] ranges that
+    /// do not access the underlying [`ValueStack`] out of bounds and that
+    /// do not overlap.
+    pub unsafe fn copy_span_from(
+        &mut self,
+        results: Register,
+        src: &Self,
+        values: Register,
+        len: usize,
+    ) {
+        let dst = self.register_ptr(results);
+        let src = src.register_ptr(values);
+        unsafe { ptr::copy_nonoverlapping(src, dst, len) }
+    }
+
     /// Returns the pointer to the [`UntypedValue`] at the [`Register`].
     ///
     /// # Safety
@@ -0,0 +1,437 @@
+//! Renders `wasmi` register-machine bytecode as Wasm-like text.
+//!
+//! This is a debugging aid to compare translated function bodies with their
+//! Wasm input, for example when a [`TranslationTest`] fails.
+//!
+//! [`TranslationTest`]: super::driver::TranslationTest
+
+use super::{create_module, driver::compiled_instrs, wat2wasm};
+use crate::{
+    engine::{
+        bytecode::BranchOffset,
+        regmach::bytecode::{
+            BinInstr,
+            BinInstrImm16,
+            Const16,
+            Instruction,
+            Register,
+            UnaryInstr,
+        },
+    },
+    Config,
+    EngineBackend,
+};
+use core::{fmt, fmt::Display};
+
+/// The output mode of a [`Disassembly`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DisasmMode {
+    /// Prints the raw `Debug` representation of every [`Instruction`].
+    Raw,
+    /// Prints every [`Instruction`] using its Wasm mnemonic and register data flow.
+    ///
+    /// # Note
+    ///
+    /// Instructions without a Wasm-like rendering fall back to [`DisasmMode::Raw`].
+    Wat,
+}
+
+/// [`Display`] wrapper for a sequence of [`Instruction`] of a single function body.
+pub struct Disassembly<'a> {
+    /// The instructions of the function body.
+    instrs: &'a [Instruction],
+    /// The output mode.
+    mode: DisasmMode,
+}
+
+impl<'a> Disassembly<'a> {
+    /// Creates a new [`Disassembly`] for `instrs` using the given output `mode`.
+    pub fn new(instrs: &'a [Instruction], mode: DisasmMode) -> Self {
+        Self { instrs, mode }
+    }
+}
+
+impl Display for Disassembly<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, instr) in self.instrs.iter().enumerate() {
+            write!(f, "{index}: ")?;
+            match self.mode {
+                DisasmMode::Raw => writeln!(f, "{instr:?}")?,
+                DisasmMode::Wat => {
+                    write_wat(f, index, instr)?;
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// [`Display`] wrapper for a [`Register`].
+///
+/// Function local constant values are displayed as `c{n}`
+/// and all other registers as `r{n}`.
+struct DisplayReg(Register);
+
+impl Display for DisplayReg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.0.to_i16();
+        match self.0.is_const() {
+            true => write!(f, "c{}", -1 - i32::from(index)),
+            false => write!(f, "r{index}"),
+        }
+    }
+}
+
+/// Returns the name of the [`Instruction`] variant.
+fn variant_name(instr: &Instruction) -> String {
+    let debug = format!("{instr:?}");
+    let end = debug
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(debug.len());
+    debug[..end].to_string()
+}
+
+/// Returns the Wasm mnemonic for the `wasmi` instruction variant `name`.
+///
+/// For example `I32AddImm16` yields `i32.add` and `I64TruncSatF32S` yields `i64.trunc_sat_f32_s`.
+fn mnemonic(name: &str) -> String {
+    let name = name.strip_suffix("Rev").unwrap_or(name);
+    let name = name
+        .strip_suffix("Imm16")
+        .or_else(|| name.strip_suffix("Imm"))
+        .unwrap_or(name);
+    let (ty, op) = name.split_at(3);
+    let mut mnemonic = ty.to_ascii_lowercase();
+    mnemonic.push('.');
+    for (n, c) in op.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if n != 0 {
+                mnemonic.push('_');
+            }
+            mnemonic.push(c.to_ascii_lowercase());
+        } else {
+            mnemonic.push(c);
+        }
+    }
+    mnemonic
+}
+
+/// Writes the branch target of `offset` relative to the instruction at `index`.
+fn write_target(f: &mut fmt::Formatter<'_>, index: usize, offset: BranchOffset) -> fmt::Result {
+    let target = index as i64 + i64::from(offset.to_i32());
+    write!(f, "@{target}")
+}
+
+/// Writes a binary instruction with two register operands.
+fn write_bin(f: &mut fmt::Formatter<'_>, name: &str, instr: &BinInstr) -> fmt::Result {
+    write!(
+        f,
+        "{} = {} {} {}",
+        DisplayReg(instr.result),
+        mnemonic(name),
+        DisplayReg(instr.lhs),
+        DisplayReg(instr.rhs),
+    )
+}
+
+/// Writes a binary instruction with a 16-bit immediate operand.
+fn write_bin_imm16<T>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    instr: &BinInstrImm16<T>,
+) -> fmt::Result
+where
+    T: From<Const16<T>> + Display,
+{
+    let result = DisplayReg(instr.result);
+    let reg = DisplayReg(instr.reg_in);
+    let imm = T::from(instr.imm_in);
+    let mnemonic = mnemonic(name);
+    match name.ends_with("Rev") {
+        true => write!(f, "{result} = {mnemonic} {imm} {reg}"),
+        false => write!(f, "{result} = {mnemonic} {reg} {imm}"),
+    }
+}
+
+/// Writes a unary instruction.
+fn write_unary(f: &mut fmt::Formatter<'_>, name: &str, instr: &UnaryInstr) -> fmt::Result {
+    write!(
+        f,
+        "{} = {} {}",
+        DisplayReg(instr.result),
+        mnemonic(name),
+        DisplayReg(instr.input),
+    )
+}
+
+/// Writes the Wasm-like rendering of the `instr` at `index`.
+fn write_wat(f: &mut fmt::Formatter<'_>, index: usize, instr: &Instruction) -> fmt::Result {
+    let name = variant_name(instr);
+    match instr {
+        Instruction::I32Eq(instr)
+        | Instruction::I64Eq(instr)
+        | Instruction::I32Ne(instr)
+        | Instruction::I64Ne(instr)
+        | Instruction::I32LtS(instr)
+        | Instruction::I32LtU(instr)
+        | Instruction::I64LtS(instr)
+        | Instruction::I64LtU(instr)
+        | Instruction::I32GtS(instr)
+        | Instruction::I32GtU(instr)
+        | Instruction::I64GtS(instr)
+        | Instruction::I64GtU(instr)
+        | Instruction::I32LeS(instr)
+        | Instruction::I32LeU(instr)
+        | Instruction::I64LeS(instr)
+        | Instruction::I64LeU(instr)
+        | Instruction::I32GeS(instr)
+        | Instruction::I32GeU(instr)
+        | Instruction::I64GeS(instr)
+        | Instruction::I64GeU(instr)
+        | Instruction::F32Eq(instr)
+        | Instruction::F64Eq(instr)
+        | Instruction::F32Ne(instr)
+        | Instruction::F64Ne(instr)
+        | Instruction::F32Lt(instr)
+        | Instruction::F64Lt(instr)
+        | Instruction::F32Le(instr)
+        | Instruction::F64Le(instr)
+        | Instruction::F32Gt(instr)
+        | Instruction::F64Gt(instr)
+        | Instruction::F32Ge(instr)
+        | Instruction::F64Ge(instr)
+        | Instruction::I32Add(instr)
+        | Instruction::I32AddFromMem(instr)
+        | Instruction::I64Add(instr)
+        | Instruction::I32Sub(instr)
+        | Instruction::I64Sub(instr)
+        | Instruction::I32Mul(instr)
+        | Instruction::I64Mul(instr)
+        | Instruction::I32DivS(instr)
+        | Instruction::I64DivS(instr)
+        | Instruction::I32DivU(instr)
+        | Instruction::I64DivU(instr)
+        | Instruction::I32RemS(instr)
+        | Instruction::I64RemS(instr)
+        | Instruction::I32RemU(instr)
+        | Instruction::I64RemU(instr)
+        | Instruction::I32And(instr)
+        | Instruction::I64And(instr)
+        | Instruction::I32Or(instr)
+        | Instruction::I64Or(instr)
+        | Instruction::I32Xor(instr)
+        | Instruction::I64Xor(instr)
+        | Instruction::I32Shl(instr)
+        | Instruction::I64Shl(instr)
+        | Instruction::I32ShrU(instr)
+        | Instruction::I64ShrU(instr)
+        | Instruction::I32ShrS(instr)
+        | Instruction::I64ShrS(instr)
+        | Instruction::I32Rotl(instr)
+        | Instruction::I64Rotl(instr)
+        | Instruction::I32Rotr(instr)
+        | Instruction::I64Rotr(instr)
+        | Instruction::F32Add(instr)
+        | Instruction::F64Add(instr)
+        | Instruction::F32Sub(instr)
+        | Instruction::F64Sub(instr)
+        | Instruction::F32Mul(instr)
+        | Instruction::F64Mul(instr)
+        | Instruction::F32Div(instr)
+        | Instruction::F64Div(instr)
+        | Instruction::F32Min(instr)
+        | Instruction::F64Min(instr)
+        | Instruction::F32Max(instr)
+        | Instruction::F64Max(instr)
+        | Instruction::F32Copysign(instr)
+        | Instruction::F64Copysign(instr) => write_bin(f, &name, instr),
+        Instruction::I32EqImm16(instr)
+        | Instruction::I32NeImm16(instr)
+        | Instruction::I32LtSImm16(instr)
+        | Instruction::I32GtSImm16(instr)
+        | Instruction::I32LeSImm16(instr)
+        | Instruction::I32GeSImm16(instr)
+        | Instruction::I32AddImm16(instr)
+        | Instruction::I32SubImm16(instr)
+        | Instruction::I32SubImm16Rev(instr)
+        | Instruction::I32MulImm16(instr)
+        | Instruction::I32DivSImm16(instr)
+        | Instruction::I32DivSImm16Rev(instr)
+        | Instruction::I32RemSImm16(instr)
+        | Instruction::I32RemSImm16Rev(instr)
+        | Instruction::I32AndImm16(instr)
+        | Instruction::I32OrImm16(instr)
+        | Instruction::I32XorImm16(instr)
+        | Instruction::I32ShlImm(instr)
+        | Instruction::I32ShlImm16Rev(instr)
+        | Instruction::I32ShrUImm(instr)
+        | Instruction::I32ShrUImm16Rev(instr)
+        | Instruction::I32ShrSImm(instr)
+        | Instruction::I32ShrSImm16Rev(instr)
+        | Instruction::I32RotlImm(instr)
+        | Instruction::I32RotlImm16Rev(instr)
+        | Instruction::I32RotrImm(instr)
+        | Instruction::I32RotrImm16Rev(instr) => write_bin_imm16::<i32>(f, &name, instr),
+        Instruction::I32LtUImm16(instr)
+        | Instruction::I32GtUImm16(instr)
+        | Instruction::I32LeUImm16(instr)
+        | Instruction::I32GeUImm16(instr)
+        | Instruction::I32DivUImm16(instr)
+        | Instruction::I32DivUImm16Rev(instr)
+        | Instruction::I32RemUImm16(instr)
+        | Instruction::I32RemUImm16Rev(instr) => write_bin_imm16::<u32>(f, &name, instr),
+        Instruction::I64EqImm16(instr)
+        | Instruction::I64NeImm16(instr)
+        | Instruction::I64LtSImm16(instr)
+        | Instruction::I64GtSImm16(instr)
+        | Instruction::I64LeSImm16(instr)
+        | Instruction::I64GeSImm16(instr)
+        | Instruction::I64AddImm16(instr)
+        | Instruction::I64SubImm16(instr)
+        | Instruction::I64SubImm16Rev(instr)
+        | Instruction::I64MulImm16(instr)
+        | Instruction::I64DivSImm16(instr)
+        | Instruction::I64DivSImm16Rev(instr)
+        | Instruction::I64RemSImm16(instr)
+        | Instruction::I64RemSImm16Rev(instr)
+        | Instruction::I64AndImm16(instr)
+        | Instruction::I64OrImm16(instr)
+        | Instruction::I64XorImm16(instr)
+        | Instruction::I64ShlImm(instr)
+        | Instruction::I64ShlImm16Rev(instr)
+        | Instruction::I64ShrUImm(instr)
+        | Instruction::I64ShrUImm16Rev(instr)
+        | Instruction::I64ShrSImm(instr)
+        | Instruction::I64ShrSImm16Rev(instr)
+        | Instruction::I64RotlImm(instr)
+        | Instruction::I64RotlImm16Rev(instr)
+        | Instruction::I64RotrImm(instr)
+        | Instruction::I64RotrImm16Rev(instr) => write_bin_imm16::<i64>(f, &name, instr),
+        Instruction::I64LtUImm16(instr)
+        | Instruction::I64GtUImm16(instr)
+        | Instruction::I64LeUImm16(instr)
+        | Instruction::I64GeUImm16(instr)
+        | Instruction::I64DivUImm16(instr)
+        | Instruction::I64DivUImm16Rev(instr)
+        | Instruction::I64RemUImm16(instr)
+        | Instruction::I64RemUImm16Rev(instr) => write_bin_imm16::<u64>(f, &name, instr),
+        Instruction::I32Eqz(instr)
+        | Instruction::I64Eqz(instr)
+        | Instruction::I32Clz(instr)
+        | Instruction::I64Clz(instr)
+        | Instruction::I32Ctz(instr)
+        | Instruction::I64Ctz(instr)
+        | Instruction::I32Popcnt(instr)
+        | Instruction::I64Popcnt(instr)
+        | Instruction::F32Abs(instr)
+        | Instruction::F64Abs(instr)
+        | Instruction::F32Neg(instr)
+        | Instruction::F64Neg(instr)
+        | Instruction::F32Ceil(instr)
+        | Instruction::F64Ceil(instr)
+        | Instruction::F32Floor(instr)
+        | Instruction::F64Floor(instr)
+        | Instruction::F32Trunc(instr)
+        | Instruction::F64Trunc(instr)
+        | Instruction::F32Nearest(instr)
+        | Instruction::F64Nearest(instr)
+        | Instruction::F32Sqrt(instr)
+        | Instruction::F64Sqrt(instr)
+        | Instruction::I32WrapI64(instr)
+        | Instruction::I64ExtendI32S(instr)
+        | Instruction::I64ExtendI32U(instr)
+        | Instruction::I32TruncF32S(instr)
+        | Instruction::I32TruncF32U(instr)
+        | Instruction::I32TruncF64S(instr)
+        | Instruction::I32TruncF64U(instr)
+        | Instruction::I64TruncF32S(instr)
+        | Instruction::I64TruncF32U(instr)
+        | Instruction::I64TruncF64S(instr)
+        | Instruction::I64TruncF64U(instr)
+        | Instruction::I32TruncSatF32S(instr)
+        | Instruction::I32TruncSatF32U(instr)
+        | Instruction::I32TruncSatF64S(instr)
+        | Instruction::I32TruncSatF64U(instr)
+        | Instruction::I64TruncSatF32S(instr)
+        | Instruction::I64TruncSatF32U(instr)
+        | Instruction::I64TruncSatF64S(instr)
+        | Instruction::I64TruncSatF64U(instr)
+        | Instruction::I32Extend8S(instr)
+        | Instruction::I32Extend16S(instr)
+        | Instruction::I64Extend8S(instr)
+        | Instruction::I64Extend16S(instr)
+        | Instruction::I64Extend32S(instr)
+        | Instruction::F32DemoteF64(instr)
+        | Instruction::F64PromoteF32(instr)
+        | Instruction::F32ConvertI32S(instr)
+        | Instruction::F32ConvertI32U(instr)
+        | Instruction::F32ConvertI64S(instr)
+        | Instruction::F32ConvertI64U(instr)
+        | Instruction::F64ConvertI32S(instr)
+        | Instruction::F64ConvertI32U(instr)
+        | Instruction::F64ConvertI64S(instr)
+        | Instruction::F64ConvertI64U(instr) => write_unary(f, &name, instr),
+        Instruction::Copy { result, value } => {
+            write!(f, "{} = {}", DisplayReg(*result), DisplayReg(*value))
+        }
+        Instruction::CopyImm32 { result, value } => {
+            write!(f, "{} = const32 {}", DisplayReg(*result), value.to_i32())
+        }
+        Instruction::Return => write!(f, "return"),
+        Instruction::ReturnReg { value } => write!(f, "return {}", DisplayReg(*value)),
+        Instruction::ReturnImm32 { value } => write!(f, "return const32 {}", value.to_i32()),
+        Instruction::Branch { offset } => {
+            write!(f, "br ")?;
+            write_target(f, index, *offset)
+        }
+        Instruction::BranchEqz { condition, offset } => {
+            write!(f, "br_if_eqz {} ", DisplayReg(*condition))?;
+            write_target(f, index, *offset)
+        }
+        Instruction::BranchNez { condition, offset } => {
+            write!(f, "br_if {} ", DisplayReg(*condition))?;
+            write_target(f, index, *offset)
+        }
+        Instruction::Trap(trap_code) => write!(f, "trap {trap_code:?}"),
+        _ => write!(f, "{instr:?}"),
+    }
+}
+
+#[test]
+fn wat_snapshot() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32 i32) (result i32)
+                (local i32)
+                (local.set 2 (i32.add (local.get 0) (local.get 1)))
+                (if (i32.eqz (local.get 2))
+                    (then (return (i32.const 0)))
+                )
+                (i32.shl (i32.const 1) (i32.wrap_i64 (i64.extend_i32_u (local.get 2))))
+            )
+        )
+    "#,
+    );
+    let mut config = Config::default();
+    config.set_engine_backend(EngineBackend::RegisterMachine);
+    let module = create_module(&config, &wasm);
+    let compiled_func = module.internal_funcs().next().unwrap().1;
+    let instrs = compiled_instrs(module.engine(), compiled_func);
+    let expected = "\
+0: r2 = i32.add r0 r1
+1: br_if r2 @3
+2: return const32 0
+3: r3 = i64.extend_i32_u r2
+4: r3 = i32.wrap_i64 r3
+5: r3 = i32.shl 1 r3
+6: return r3
+";
+    assert_eq!(
+        Disassembly::new(&instrs, DisasmMode::Wat).to_string(),
+        expected
+    );
+    let raw = Disassembly::new(&instrs[..1], DisasmMode::Raw).to_string();
+    assert!(raw.starts_with("0: I32Add("), "{raw}");
+}
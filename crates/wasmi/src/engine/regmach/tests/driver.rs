@@ -1,4 +1,7 @@
-use super::create_module;
+use super::{
+    create_module,
+    disasm::{DisasmMode, Disassembly},
+};
 use crate::{
     engine::{regmach::bytecode::Instruction, CompiledFunc, DedupFuncType},
     Config,
@@ -10,6 +13,13 @@ use core::sync::atomic::Ordering;
 use std::sync::atomic::AtomicBool;
 use wasmi_core::UntypedValue;
 
+/// Returns all translated [`Instruction`] of the `compiled_func`.
+pub fn compiled_instrs(engine: &Engine, compiled_func: CompiledFunc) -> Vec<Instruction> {
+    (0..)
+        .map_while(|index| engine.resolve_instr_2(compiled_func, index))
+        .collect()
+}
+
 /// A test driver for translation tests.
 #[derive(Debug)]
 pub struct TranslationTest {
@@ -38,6 +48,8 @@ pub struct ExpectedFunc {
     instrs: Vec<Instruction>,
     /// The function local constant values.
     consts: Vec<UntypedValue>,
+    /// The number of registers used by the function if checked.
+    len_registers: Option<u16>,
 }
 
 impl ExpectedFunc {
@@ -54,6 +66,7 @@ impl ExpectedFunc {
         Self {
             instrs,
             consts: Vec::new(),
+            len_registers: None,
         }
     }
 
@@ -85,6 +98,17 @@ impl ExpectedFunc {
         self
     }
 
+    /// Expects the function to use exactly `len_registers` registers in total.
+    ///
+    /// # Note
+    ///
+    /// This includes the registers for function local constant values,
+    /// function parameters, function locals and dynamically used registers.
+    pub fn len_registers(mut self, len_registers: u16) -> Self {
+        self.len_registers = Some(len_registers);
+        self
+    }
+
     /// Returns the expected [`Instruction`] sequence of the [`ExpectedFunc`] as slice.
     fn expected_instrs(&self) -> &[Instruction] {
         &self.instrs
@@ -99,6 +123,18 @@ impl ExpectedFunc {
     fn assert_func(&self, engine: &Engine, func_type: DedupFuncType, compiled_func: CompiledFunc) {
         self.assert_instrs(engine, compiled_func, func_type);
         self.assert_consts(engine, compiled_func);
+        self.assert_len_registers(engine, compiled_func);
+    }
+
+    /// Asserts that the number of registers of the [`ExpectedFunc`] is as expected if checked.
+    fn assert_len_registers(&self, engine: &Engine, func: CompiledFunc) {
+        if let Some(expected) = self.len_registers {
+            let actual = engine.get_func_len_registers_2(func);
+            assert_eq!(
+                actual, expected,
+                "number of registers mismatch for {func:?}"
+            );
+        }
     }
 
     /// Asserts that the instructions of the [`ExpectedFunc`] have been translated as expected.
@@ -130,7 +166,12 @@ impl ExpectedFunc {
                 actual == expected,
                 "instruction mismatch at index {index} for {compiled_func:?} ({func_type:?})\n    \
                     - expected: {expected:?}\n    \
-                    - found: {actual:?}",
+                    - found: {actual:?}\n\
+                    translated function body:\n{}",
+                Disassembly::new(
+                    &compiled_instrs(engine, compiled_func),
+                    DisasmMode::Wat
+                ),
             );
         }
         if let Some(unexpected) = engine.resolve_instr_2(compiled_func, len_expected) {
@@ -181,6 +222,11 @@ impl TranslationTest {
         &self.config
     }
 
+    /// Returns the [`Config`] used for the test case for modification.
+    pub fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
     /// Returns the WebAssembly bytes used for the test case.
     fn wasm(&self) -> &[u8] {
         &self.wasm
@@ -1,9 +1,11 @@
 //! Tests for the register-machine `wasmi` engine translation implementation.
 
+mod disasm;
 mod display_wasm;
 pub mod driver;
 mod host_calls;
 mod op;
+mod register_reuse;
 pub mod wasm_type;
 
 use self::{
@@ -431,3 +433,30 @@ where
     ));
     assert_func_bodies(wasm, [expected]);
 }
+
+/// Tests a binary `wasm_op` whose operands refer to the same register only after `local.tee`.
+///
+/// The `local.tee` copies the second parameter into the first one so that both
+/// `local.get 0` operands of `wasm_op` refer to the same register.
+fn test_binary_same_reg_after_tee<E>(wasm_op: WasmOp, expected: E)
+where
+    E: IntoIterator<Item = Instruction>,
+{
+    let param_ty = wasm_op.param_ty();
+    let result_ty = wasm_op.result_ty();
+    let wasm = wat2wasm(&format!(
+        r#"
+        (module
+            (func (param {param_ty} {param_ty}) (result {result_ty})
+                (local.tee 0 (local.get 1))
+                local.get 0
+                {wasm_op}
+            )
+        )
+    "#,
+    ));
+    let tee = Instruction::copy(Register::from_i16(0), Register::from_i16(1));
+    TranslationTest::new(wasm)
+        .expect_func_instrs([tee].into_iter().chain(expected))
+        .run()
+}
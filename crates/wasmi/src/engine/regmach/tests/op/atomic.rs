@@ -0,0 +1,87 @@
+//! Translation tests for the supported Wasm `threads` instructions.
+
+use super::*;
+
+/// Runs a [`TranslationTest`] with the threads Wasm proposal enabled.
+fn test_threads(wasm: &[u8], expected: ExpectedFunc) {
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().wasm_threads(true);
+    test.expect_func(expected).run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn notify() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $count i32) (result i32)
+                (memory.atomic.notify offset=8 (local.get $ptr) (local.get $count))
+            )
+        )",
+    );
+    test_threads(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::memory_atomic_notify(Register::from_i16(2), Register::from_i16(0)),
+            Instruction::const32(8_u32),
+            Instruction::return_reg(Register::from_i16(2)),
+        ]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn wait32() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1 1 shared)
+            (func (param $ptr i32) (param $expected i32) (param $timeout i64) (result i32)
+                (memory.atomic.wait32 offset=4
+                    (local.get $ptr) (local.get $expected) (local.get $timeout)
+                )
+            )
+        )",
+    );
+    test_threads(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::memory_atomic_wait32(
+                Register::from_i16(3),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::const32(4_u32),
+            Instruction::return_reg(Register::from_i16(3)),
+        ]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn wait64_imm() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1 1 shared)
+            (func (param $ptr i32) (result i32)
+                (memory.atomic.wait64 (local.get $ptr) (i64.const 42) (i64.const -1))
+            )
+        )",
+    );
+    test_threads(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::memory_atomic_wait64(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                Register::from_i16(-1),
+            ),
+            Instruction::const32(0_u32),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .consts([42_i64]),
+    );
+}
@@ -60,3 +60,144 @@ fn consteval() {
         }],
     )
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn consteval_overflow() {
+    let lhs = i32::MAX;
+    let rhs = 1;
+    test_binary_consteval(
+        WASM_OP,
+        lhs,
+        rhs,
+        [Instruction::ReturnImm32 {
+            value: AnyConst32::from_i32(lhs.wrapping_add(rhs)),
+        }],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fused_load_lhs() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $x i32) (result i32)
+                (i32.add
+                    (i32.load offset=8 (local.get $ptr))
+                    (local.get $x)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add_from_mem(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::const32(8_u32),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fused_load_rhs() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $x i32) (result i32)
+                (i32.add
+                    (local.get $x)
+                    (i32.load offset=100000 (local.get $ptr))
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add_from_mem(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::const32(100_000_u32),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fusion_for_teed_load() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $x i32) (result i32)
+                (local $tmp i32)
+                (i32.add
+                    (local.tee $tmp (i32.load (local.get $ptr)))
+                    (local.get $x)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(0),
+            ),
+            Instruction::i32_add(
+                Register::from_i16(3),
+                Register::from_i16(2),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(3),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fusion_across_loop_header() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $x i32) (result i32)
+                (i32.load (local.get $ptr))
+                (loop (param i32) (result i32)
+                    (local.get $x)
+                    (i32.add)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(0),
+            ),
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(2),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
@@ -78,3 +78,33 @@ fn consteval() {
         }],
     )
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn consteval_overflow() {
+    let lhs = i32::MAX;
+    let rhs = 2;
+    test_binary_consteval(
+        WASM_OP,
+        lhs,
+        rhs,
+        [Instruction::ReturnImm32 {
+            value: AnyConst32::from_i32(lhs.wrapping_mul(rhs)),
+        }],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn consteval_overflow_signed() {
+    let lhs = i32::MIN;
+    let rhs = -1;
+    test_binary_consteval(
+        WASM_OP,
+        lhs,
+        rhs,
+        [Instruction::ReturnImm32 {
+            value: AnyConst32::from_i32(i32::MIN),
+        }],
+    )
+}
@@ -11,6 +11,15 @@ fn same_reg() {
     test_binary_same_reg(WASM_OP, expected)
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn same_reg_after_tee() {
+    let expected = [Instruction::ReturnImm32 {
+        value: AnyConst32::from_i32(0),
+    }];
+    test_binary_same_reg_after_tee(WASM_OP, expected)
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn reg_reg() {
@@ -53,3 +53,16 @@ fn consteval() {
     let rhs = 2;
     test_binary_consteval(WASM_OP, lhs, rhs, [return_i64imm32_instr(lhs + rhs)])
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn consteval_overflow() {
+    let lhs = i64::MAX;
+    let rhs = i64::MAX;
+    test_binary_consteval(
+        WASM_OP,
+        lhs,
+        rhs,
+        [return_i64imm32_instr(lhs.wrapping_add(rhs))],
+    )
+}
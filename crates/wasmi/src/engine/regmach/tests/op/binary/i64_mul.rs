@@ -67,3 +67,16 @@ fn consteval() {
     let rhs = 2;
     test_binary_consteval(WASM_OP, lhs, rhs, [return_i64imm32_instr(lhs * rhs)])
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn consteval_overflow() {
+    let lhs = i64::MAX;
+    let rhs = 2;
+    test_binary_consteval(
+        WASM_OP,
+        lhs,
+        rhs,
+        [return_i64imm32_instr(lhs.wrapping_mul(rhs))],
+    )
+}
@@ -9,6 +9,13 @@ fn same_reg() {
     test_binary_same_reg(WASM_OP, expected)
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn same_reg_after_tee() {
+    let expected = [return_i64imm32_instr(0)];
+    test_binary_same_reg_after_tee(WASM_OP, expected)
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn reg_reg() {
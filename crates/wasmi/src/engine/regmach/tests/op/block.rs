@@ -330,6 +330,72 @@ fn branched_block_2() {
         .run()
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branched_block_2_split_copies() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i64) (result i32)
+                (local.get 0)
+                (local.get 1)
+                (block (param i32 i64) (result i32 i64)
+                    (br 0)
+                )
+                (drop)
+            )
+        )",
+    );
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().set_fused_copies(false);
+    test.expect_func_instrs([
+        Instruction::copy(Register::from_i16(2), Register::from_i16(0)),
+        Instruction::copy(Register::from_i16(3), Register::from_i16(1)),
+        Instruction::branch(BranchOffset::from(1)),
+        Instruction::return_reg(Register::from_i16(2)),
+    ])
+    .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branched_block_3() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32) (result i32)
+                (local.get 2)
+                (local.get 0)
+                (local.get 1)
+                (block (param i32 i32 i32) (result i32 i32 i32)
+                    (br 0)
+                )
+                (drop)
+                (drop)
+            )
+        )",
+    );
+    let expected_fused = [
+        Instruction::copy_many_non_overlapping(RegisterSpan::new(Register::from_i16(3)), 2, 0),
+        Instruction::register(1),
+        Instruction::branch(BranchOffset::from(1)),
+        Instruction::return_reg(Register::from_i16(3)),
+    ];
+    let expected_split = [
+        Instruction::copy(Register::from_i16(3), Register::from_i16(2)),
+        Instruction::copy(Register::from_i16(4), Register::from_i16(0)),
+        Instruction::copy(Register::from_i16(5), Register::from_i16(1)),
+        Instruction::branch(BranchOffset::from(1)),
+        Instruction::return_reg(Register::from_i16(3)),
+    ];
+    TranslationTest::new(&wasm)
+        .expect_func_instrs(expected_fused)
+        .run();
+    let mut test = TranslationTest::new(&wasm);
+    test.config_mut().set_fused_copies(false);
+    test.expect_func_instrs(expected_split).run()
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn branch_if_block_0() {
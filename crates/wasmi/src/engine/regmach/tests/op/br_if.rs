@@ -1,8 +1,8 @@
 use super::*;
 use crate::engine::{
-    bytecode::BranchOffset,
+    bytecode::{BranchOffset, GlobalIdx},
     regmach::{
-        bytecode::RegisterSpan,
+        bytecode::{BranchComparator, BranchOffset16, ComparatorOffsetParam, RegisterSpan},
         tests::{display_wasm::DisplayValueType, driver::ExpectedFunc, wasm_type::WasmType},
     },
 };
@@ -1055,3 +1055,339 @@ fn branch_if_results_4_mixed_2() {
         ])
         .run()
 }
+
+/// Tests `copy_span` to lower registers where the `results` and `values` spans overlap.
+///
+/// # Note
+///
+/// Copying value by value does not overwrite any value before it is copied.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_if_results_3_copy_span_to_lower() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32 i32) (result i32 i32 i32)
+                (block (result i32 i32 i32)
+                    (i32.clz (local.get 0))
+                    (i32.add (local.get 0) (i32.const 1))
+                    (i32.add (local.get 1) (i32.const 2))
+                    (i32.add (local.get 2) (i32.const 3))
+                    (br_if 0
+                        (local.get 3) ;; br_if condition
+                    )
+                    (drop) (drop) (drop) (drop)
+                    (i32.const 10) (i32.const 20) (i32.const 30)
+                )
+                (return)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_clz(Register::from_i16(4), Register::from_i16(0)),
+                Instruction::i32_add_imm16(Register::from_i16(5), Register::from_i16(0), 1),
+                Instruction::i32_add_imm16(Register::from_i16(6), Register::from_i16(1), 2),
+                Instruction::i32_add_imm16(Register::from_i16(7), Register::from_i16(2), 3),
+                Instruction::branch_eqz(Register::from_i16(3), BranchOffset::from(3)),
+                Instruction::copy_span_non_overlapping(
+                    RegisterSpan::new(Register::from_i16(4)),
+                    RegisterSpan::new(Register::from_i16(5)),
+                    3,
+                ),
+                Instruction::branch(BranchOffset::from(3)),
+                Instruction::copy_many_non_overlapping(
+                    RegisterSpan::new(Register::from_i16(4)),
+                    -1,
+                    -2,
+                ),
+                Instruction::register(-3),
+                Instruction::return_reg3(4, 5, 6),
+            ])
+            .consts([10_i32, 20, 30]),
+        )
+        .run()
+}
+
+/// Tests `copy_span` to higher registers where the `results` and `values` spans overlap.
+///
+/// # Note
+///
+/// Copying value by value would overwrite values before they are copied.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_if_results_3_copy_span_to_higher() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32 i32 i32 i32) (result i32 i32 i32)
+                (block (result i32 i32 i32)
+                    (local.get 4)
+                    (local.get 5)
+                    (i32.add (local.get 0) (local.get 1))
+                    (br_if 0
+                        (local.get 3) ;; br_if condition
+                    )
+                    (drop) (drop) (drop)
+                    (i32.const 10) (i32.const 20) (i32.const 30)
+                )
+                (return)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_add(
+                    Register::from_i16(6),
+                    Register::from_i16(0),
+                    Register::from_i16(1),
+                ),
+                Instruction::branch_eqz(Register::from_i16(3), BranchOffset::from(3)),
+                Instruction::copy_span(
+                    RegisterSpan::new(Register::from_i16(6)),
+                    RegisterSpan::new(Register::from_i16(4)),
+                    3,
+                ),
+                Instruction::branch(BranchOffset::from(3)),
+                Instruction::copy_many_non_overlapping(
+                    RegisterSpan::new(Register::from_i16(6)),
+                    -1,
+                    -2,
+                ),
+                Instruction::register(-3),
+                Instruction::return_reg3(6, 7, 8),
+            ])
+            .consts([10_i32, 20, 30]),
+        )
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cmp_fusion() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32)
+                (loop
+                    (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                    (br_if 0 (i32.lt_u (local.get 0) (local.get 1)))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(0), 1),
+            Instruction::branch_i32_lt_u(
+                Register::from_i16(0),
+                Register::from_i16(1),
+                BranchOffset16::from(-1),
+            ),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cmp_fusion_imm() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32)
+                (loop
+                    (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                    (br_if 0 (i32.lt_s (local.get 0) (i32.const 10)))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(0), 1),
+            Instruction::branch_i32_lt_s_imm(Register::from_i16(0), 10, BranchOffset16::from(-1)),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_eqz_fusion_loop() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32)
+                (loop
+                    (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                    (br_if 0 (i32.eqz (local.get 0)))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_sub_imm16(Register::from_i16(0), Register::from_i16(0), 1),
+            Instruction::branch_eqz(Register::from_i16(0), BranchOffset::from(-1)),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_eqz_fusion_forward() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32)
+                (block
+                    (br_if 0 (i32.eqz (local.get 0)))
+                    (drop (i32.const 0))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_eqz(Register::from_i16(0), BranchOffset::from(1)),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_eqz_fusion_results_1() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32)
+                (local.get 0)
+                (block (param i32) (result i32)
+                    (br_if 0 (i32.eqz (local.get 1)))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_nez(Register::from_i16(1), BranchOffset::from(3)),
+            Instruction::copy(Register::from_i16(2), Register::from_i16(0)),
+            Instruction::branch(BranchOffset::from(2)),
+            Instruction::copy(Register::from_i16(2), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_eqz_no_fusion_local_tee() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (block
+                    (br_if 0 (local.tee 1 (i32.eqz (local.get 0))))
+                )
+                (local.get 1)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_eqz(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::branch_nez(Register::from_i16(1), BranchOffset::from(1)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cmp_fusion_forward() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32)
+                (block
+                    (br_if 0 (i32.lt_s (local.get 0) (i32.const 10)))
+                    (drop (i32.const 0))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_i32_lt_s_imm(Register::from_i16(0), 10, BranchOffset16::from(1)),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cmp_fallback_forward() {
+    // Note: enough instructions to push the branch offset out of bounds for 16 bits.
+    let len_padding = i16::MAX as usize;
+    let padding = "(global.set 0 (local.get 0))".repeat(len_padding);
+    let wasm = wat2wasm(&format!(
+        r"
+        (module
+            (global (mut i32) (i32.const 0))
+            (func (param i32)
+                (block
+                    (br_if 0 (i32.lt_s (local.get 0) (i32.const 10)))
+                    {padding}
+                )
+            )
+        )",
+    ));
+    let offset = BranchOffset::from(len_padding as i32 + 1);
+    let param = ComparatorOffsetParam::new(BranchComparator::I32LtS, offset);
+    let expected = [Instruction::branch_cmp_fallback(
+        Register::from_i16(0),
+        Register::from_i16(-1),
+        Register::from_i16(-2),
+    )]
+    .into_iter()
+    .chain(
+        core::iter::repeat_n(
+            Instruction::global_set(GlobalIdx::from(0), Register::from_i16(0)),
+            len_padding,
+        ),
+    )
+    .chain([Instruction::Return]);
+    TranslationTest::new(wasm)
+        .expect_func(ExpectedFunc::new(expected).consts([UntypedValue::from(10_i32), param.into()]))
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cmp_no_fusion_local_tee() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (loop
+                    (br_if 0 (local.tee 1 (i32.lt_s (local.get 0) (i32.const 10))))
+                )
+                (local.get 1)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_lt_s_imm16(Register::from_i16(1), Register::from_i16(0), 10),
+            Instruction::branch_nez(Register::from_i16(1), BranchOffset::from(-1)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run()
+}
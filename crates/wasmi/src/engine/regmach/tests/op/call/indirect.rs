@@ -650,9 +650,7 @@ fn params7_reg_index_local() {
                 SignatureIdx::from(0),
             ),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register(7),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(7)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(8)).iter(7)),
         ])
         .run();
@@ -728,9 +726,7 @@ fn params8_reg_index_local() {
                 SignatureIdx::from(0),
             ),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register2(7, 8),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(8)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(9)).iter(8)),
         ])
         .run();
@@ -808,9 +804,7 @@ fn params9_reg_index_local() {
                 SignatureIdx::from(0),
             ),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register3(7, 8, 9),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(9)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(10)).iter(9)),
         ])
         .run();
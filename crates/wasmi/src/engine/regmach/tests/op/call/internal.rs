@@ -306,9 +306,7 @@ fn params7_reg() {
                 RegisterSpan::new(Register::from_i16(7)),
                 CompiledFunc::from_u32(0),
             ),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(7)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(7)).iter(7)),
         ])
         .run();
@@ -449,9 +447,7 @@ fn params8_reg() {
                 RegisterSpan::new(Register::from_i16(8)),
                 CompiledFunc::from_u32(0),
             ),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register2(6, 7),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(8)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(8)).iter(8)),
         ])
         .run();
@@ -598,9 +594,7 @@ fn params9_reg() {
                 RegisterSpan::new(Register::from_i16(9)),
                 CompiledFunc::from_u32(0),
             ),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register3(6, 7, 8),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(9)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(9)).iter(9)),
         ])
         .run();
@@ -73,3 +73,62 @@ fn consteval() {
         }],
     )
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn masked_reg_imm() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (i32.lt_u
+                    (i32.and (local.get 0) (i32.const 15))
+                    (i32.const 16)
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_and_imm16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<i32>>::from(15),
+            ),
+            Instruction::ReturnImm32 {
+                value: AnyConst32::from(true),
+            },
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn masked_reg_imm_not_bounded() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (i32.lt_u
+                    (i32.and (local.get 0) (i32.const 15))
+                    (i32.const 15)
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_and_imm16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<i32>>::from(15),
+            ),
+            Instruction::i32_lt_u_imm16(
+                Register::from_i16(1),
+                Register::from_i16(1),
+                <Const16<u32>>::from(15),
+            ),
+            Instruction::return_reg(1),
+        ])
+        .run()
+}
@@ -2,7 +2,8 @@ use super::*;
 
 const PARAM: WasmType = WasmType::I64;
 
-#[test] #[cfg_attr(miri, ignore)]
+#[test]
+#[cfg_attr(miri, ignore)]
 fn reg() {
     let wasm = wat2wasm(&format!(
         r#"
@@ -15,12 +16,8 @@ fn reg() {
         "#
     ));
     TranslationTest::new(wasm)
-        .expect_func([
-            Instruction::i64_eq_imm16(
-                Register::from_u16(1),
-                Register::from_u16(0),
-                Const16::from_i16(0),
-            ),
+        .expect_func_instrs([
+            Instruction::i64_eqz(Register::from_i16(1), Register::from_i16(0)),
             Instruction::return_reg(1),
         ])
         .run();
@@ -38,13 +35,14 @@ fn imm_with(value: i64) {
         "#
     ));
     TranslationTest::new(wasm)
-        .expect_func([Instruction::ReturnImm32 {
-            value: Const32::from(value == 0),
+        .expect_func_instrs([Instruction::ReturnImm32 {
+            value: AnyConst32::from(value == 0),
         }])
         .run();
 }
 
-#[test] #[cfg_attr(miri, ignore)]
+#[test]
+#[cfg_attr(miri, ignore)]
 fn imm() {
     imm_with(0);
     imm_with(1);
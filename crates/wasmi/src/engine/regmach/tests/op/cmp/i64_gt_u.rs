@@ -73,3 +73,31 @@ fn consteval() {
         }],
     )
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn masked_reg_imm() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i64) (result i32)
+                (i64.gt_u
+                    (i64.and (local.get 0) (i64.const 255))
+                    (i64.const 255)
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i64_and_imm16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<i64>>::from(255),
+            ),
+            Instruction::ReturnImm32 {
+                value: AnyConst32::from(false),
+            },
+        ])
+        .run()
+}
@@ -10,8 +10,7 @@
 //! # Note
 //!
 //! Technically `{i32, i64}.eqz` are unary instructions but we still
-//! include them here since in `wasmi` bytecode these are represented by
-//! more generic comparison instructions.
+//! include them here since they are comparisons against zero.
 
 use super::*;
 
@@ -20,8 +19,10 @@ mod f32_ne;
 mod f64_eq;
 mod f64_ne;
 mod i32_eq;
+mod i32_eqz;
 mod i32_ne;
 mod i64_eq;
+mod i64_eqz;
 mod i64_ne;
 
 mod f32_ge;
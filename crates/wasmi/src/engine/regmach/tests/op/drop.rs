@@ -0,0 +1,65 @@
+use super::*;
+use crate::engine::{CompiledFunc, RegisterSpan};
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn local() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32)
+                (drop (local.get 0))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::Return])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn imm() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func
+                (drop (i64.const 100_000_000_000))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::Return])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn call() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (result i32)
+                (i32.const 1)
+            )
+            (func
+                (drop (call $f))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::ReturnImm32 {
+            value: AnyConst32::from(1_i32),
+        }])
+        .expect_func_instrs([
+            Instruction::call_internal_0(
+                RegisterSpan::new(Register::from_i16(0)),
+                CompiledFunc::from_u32(0),
+            ),
+            Instruction::Return,
+        ])
+        .run();
+}
@@ -53,6 +53,55 @@ fn simple_if_then_nested() {
         .run()
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn if_eqz_fusion() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (if (result i32) (i32.eqz (local.get 0))
+                    (then (i32.const 10))
+                    (else (i32.const 20))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_nez(Register::from_i16(0), BranchOffset::from(3)),
+            Instruction::copy_imm32(Register::from_i16(1), 10),
+            Instruction::branch(BranchOffset::from(2)),
+            Instruction::copy_imm32(Register::from_i16(1), 20),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn if_eqz_no_fusion_local_tee() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (if (local.tee 1 (i32.eqz (local.get 0)))
+                    (then)
+                )
+                (local.get 1)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_eqz(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::branch_eqz(Register::from_i16(1), BranchOffset::from(1)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run()
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn if_then_global_set() {
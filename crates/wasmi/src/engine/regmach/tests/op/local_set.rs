@@ -1,6 +1,6 @@
 use super::*;
 use crate::engine::{
-    bytecode::{FuncIdx, SignatureIdx, TableIdx},
+    bytecode::{BranchOffset, FuncIdx, SignatureIdx, TableIdx},
     regmach::bytecode::RegisterSpan,
     CompiledFunc,
 };
@@ -464,3 +464,102 @@ fn preserve_multiple_6() {
         ])
         .run()
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dead_copy_imm() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local.set 0 (i32.const 10))
+                (local.set 0 (i32.const 20))
+                (local.get 0)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(0), 20_i32),
+            Instruction::return_reg(Register::from_i16(0)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dead_copy_overwritten_result() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32) (result i32)
+                (local.set 0 (local.get 1))
+                (local.set 0 (i32.add (local.get 1) (local.get 2)))
+                (local.get 0)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(0),
+                Register::from_i16(1),
+                Register::from_i16(2),
+            ),
+            Instruction::return_reg(Register::from_i16(0)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn live_copy_read_by_overwrite() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32)
+                (local.set 0 (local.get 1))
+                (local.set 0 (i32.add (local.get 0) (local.get 1)))
+                (local.get 0)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(0), Register::from_i16(1)),
+            Instruction::i32_add(
+                Register::from_i16(0),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(0)),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn live_copy_across_branch_target() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32)
+                (if (local.get 1)
+                    (then
+                        (local.set 0 (i32.const 10))
+                    )
+                )
+                (local.set 0 (i32.const 20))
+                (local.get 0)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_eqz(Register::from_i16(1), BranchOffset::from(2)),
+            Instruction::copy_imm32(Register::from_i16(0), 10_i32),
+            Instruction::copy_imm32(Register::from_i16(0), 20_i32),
+            Instruction::return_reg(Register::from_i16(0)),
+        ])
+        .run()
+}
@@ -1,5 +1,8 @@
 use super::*;
-use crate::engine::{bytecode::BranchOffset, regmach::bytecode::RegisterSpan};
+use crate::engine::{
+    bytecode::BranchOffset,
+    regmach::bytecode::{BranchOffset16, RegisterSpan},
+};
 
 #[test]
 #[cfg_attr(miri, ignore)]
@@ -242,3 +245,108 @@ fn identity_loop_4_mixed_2() {
         ])
         .run()
 }
+
+/// Runs a [`TranslationTest`] with hoisting of loop bounds checks enabled.
+fn test_hoisted_bounds_checks<I>(wasm: &[u8], expected: I)
+where
+    I: IntoIterator<Item = Instruction>,
+{
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().set_hoist_loop_bounds_checks(true);
+    test.expect_func_instrs(expected).run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn hoisted_bounds_checks() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $i i32) (result i64)
+                (local $sum i64)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.ge_u (local.get $i) (i32.const 100)))
+                        (local.set $sum
+                            (i64.add
+                                (local.get $sum)
+                                (i64.add
+                                    (i64.load (local.get $i))
+                                    (i64.load8_u offset=70000 (local.get $i))
+                                )
+                            )
+                        )
+                        (local.set $i (i32.add (local.get $i) (i32.const 8)))
+                        (br $continue)
+                    )
+                )
+                (local.get $sum)
+            )
+        )",
+    );
+    test_hoisted_bounds_checks(
+        &wasm,
+        [
+            Instruction::loop_bounds_guard(Register::from_i16(0)),
+            Instruction::const32(100),
+            Instruction::const32(8),
+            Instruction::const32(70001),
+            Instruction::branch_i32_ge_u_imm(Register::from_i16(0), 100, BranchOffset16::from(9)),
+            Instruction::i64_load_unchecked(Register::from_i16(2), Register::from_i16(0)),
+            Instruction::const32(0),
+            Instruction::i64_load8_u_unchecked(Register::from_i16(3), Register::from_i16(0)),
+            Instruction::const32(70000),
+            Instruction::i64_add(
+                Register::from_i16(2),
+                Register::from_i16(2),
+                Register::from_i16(3),
+            ),
+            Instruction::i64_add(
+                Register::from_i16(1),
+                Register::from_i16(1),
+                Register::from_i16(2),
+            ),
+            Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(0), 8),
+            Instruction::branch(BranchOffset::from(-8)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn hoisted_bounds_checks_side_effects() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $i i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.ge_u (local.get $i) (i32.const 100)))
+                        (i32.store (local.get $i) (i32.load (local.get $i)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 4)))
+                        (br $continue)
+                    )
+                )
+            )
+        )",
+    );
+    // The store might observably happen before an out of bounds load in a later iteration.
+    test_hoisted_bounds_checks(
+        &wasm,
+        [
+            Instruction::branch_i32_ge_u_imm(Register::from_i16(0), 100, BranchOffset16::from(5)),
+            Instruction::i32_load_offset16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<u32>>::from(0),
+            ),
+            Instruction::i32_store_offset16(Register::from_i16(0), 0, Register::from_i16(1)),
+            Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(0), 4),
+            Instruction::branch(BranchOffset::from(-4)),
+            Instruction::Return,
+        ],
+    )
+}
@@ -19,3 +19,62 @@ fn reg() {
         ])
         .run();
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fixed_size() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m 10 10)
+            (func (result i32)
+                (memory.size $m)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::return_imm32(10_i32)])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fixed_size_cmp_folds() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m 10 10)
+            (func (result i32)
+                (i32.lt_u (memory.size $m) (i32.const 20))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::return_imm32(true)])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn growable() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m 10 20)
+            (func (result i32)
+                (i32.lt_u (memory.size $m) (i32.const 20))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::memory_size(Register::from_i16(0)),
+            Instruction::i32_lt_u_imm16(
+                Register::from_i16(0),
+                Register::from_i16(0),
+                <Const16<u32>>::from(20),
+            ),
+            Instruction::return_reg(Register::from_i16(0)),
+        ])
+        .run();
+}
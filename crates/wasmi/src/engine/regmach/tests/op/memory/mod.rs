@@ -5,3 +5,4 @@ mod memory_fill;
 mod memory_grow;
 mod memory_init;
 mod memory_size;
+mod use_memory;
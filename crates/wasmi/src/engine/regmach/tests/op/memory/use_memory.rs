@@ -0,0 +1,142 @@
+use super::*;
+
+/// Runs a [`TranslationTest`] with the multi-memory Wasm proposal enabled.
+fn test_multi_memory<I>(wasm: &[u8], expected: I)
+where
+    I: IntoIterator<Item = Instruction>,
+{
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().wasm_multi_memory(true);
+    test.expect_func_instrs(expected).run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (param i32) (result i32)
+                (i32.load $m1 offset=4 (local.get 0))
+            )
+        )",
+    );
+    test_multi_memory(
+        &wasm,
+        [
+            Instruction::use_memory(1),
+            Instruction::i32_load_offset16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_default_memory() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (param i32) (result i32)
+                (i32.load $m0 offset=4 (local.get 0))
+            )
+        )",
+    );
+    test_multi_memory(
+        &wasm,
+        [
+            Instruction::i32_load_offset16(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn store() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (param i32 i32)
+                (i32.store $m1 offset=4 (local.get 0) (local.get 1))
+            )
+        )",
+    );
+    test_multi_memory(
+        &wasm,
+        [
+            Instruction::use_memory(1),
+            Instruction::i32_store_offset16(Register::from_i16(0), 4, Register::from_i16(1)),
+            Instruction::Return,
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn memory_size() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (result i32)
+                (memory.size $m1)
+            )
+        )",
+    );
+    test_multi_memory(
+        &wasm,
+        [
+            Instruction::use_memory(1),
+            Instruction::memory_size(Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fused_load_add() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (param i32 i32) (result i32)
+                (i32.add (i32.load $m1 offset=4 (local.get 0)) (local.get 1))
+            )
+        )",
+    );
+    test_multi_memory(
+        &wasm,
+        [
+            Instruction::use_memory(1),
+            Instruction::i32_load_offset16(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(2),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(2)),
+        ],
+    );
+}
@@ -1,3 +1,4 @@
+mod atomic;
 mod binary;
 mod block;
 mod br;
@@ -5,6 +6,7 @@ mod br_if;
 mod br_table;
 mod call;
 mod cmp;
+mod drop;
 mod global_get;
 mod global_set;
 mod if_;
@@ -15,6 +17,7 @@ mod memory;
 mod return_;
 mod return_call;
 mod select;
+mod simd;
 mod store;
 mod table;
 mod unary;
@@ -32,6 +35,7 @@ use super::{
     test_binary_reg_imm_with,
     test_binary_reg_reg,
     test_binary_same_reg,
+    test_binary_same_reg_after_tee,
     testcase_binary_consteval,
     testcase_binary_imm_reg,
     testcase_binary_reg_imm,
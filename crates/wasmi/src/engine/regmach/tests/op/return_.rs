@@ -103,6 +103,33 @@ fn return_1_imm32() {
     test_for::<f32>(-42.25);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn return_1_imm32_implicit() {
+    fn test_for<T>(value: T)
+    where
+        T: WasmType + Into<AnyConst32>,
+        DisplayWasm<T>: Display,
+    {
+        let display_ty = DisplayValueType::from(<T as WasmType>::VALUE_TYPE);
+        let display_value = DisplayWasm::from(value);
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (result {display_ty})
+                    ({display_ty}.const {display_value})
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .expect_func_instrs([Instruction::return_imm32(value)])
+            .run()
+    }
+    test_for::<i32>(7);
+    test_for::<i32>(-1);
+    test_for::<f32>(7.5);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn return_1_i64imm32() {
@@ -1,5 +1,8 @@
 use super::*;
-use crate::engine::bytecode::{GlobalIdx, SignatureIdx, TableIdx};
+use crate::engine::{
+    bytecode::{GlobalIdx, SignatureIdx, TableIdx},
+    RegisterSpan,
+};
 
 #[test]
 #[cfg_attr(miri, ignore)]
@@ -582,9 +585,7 @@ fn params7_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(SignatureIdx::from(0)),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register(7),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(7)),
         ])
         .run();
 }
@@ -652,9 +653,7 @@ fn params8_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(SignatureIdx::from(0)),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register2(7, 8),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(8)),
         ])
         .run();
 }
@@ -724,9 +723,7 @@ fn params9_reg_index_local() {
         .expect_func_instrs([
             Instruction::return_call_indirect(SignatureIdx::from(0)),
             Instruction::call_indirect_params(Register::from_i16(0), TableIdx::from(0)),
-            Instruction::register_list(1, 2, 3),
-            Instruction::register_list(4, 5, 6),
-            Instruction::register3(7, 8, 9),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(1)).iter(9)),
         ])
         .run();
 }
@@ -267,9 +267,7 @@ fn params7_reg() {
         )])
         .expect_func_instrs([
             Instruction::return_call_internal(CompiledFunc::from_u32(0)),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register(6),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(7)),
         ])
         .run();
 }
@@ -398,9 +396,7 @@ fn params8_reg() {
         )])
         .expect_func_instrs([
             Instruction::return_call_internal(CompiledFunc::from_u32(0)),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register2(6, 7),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(8)),
         ])
         .run();
 }
@@ -535,9 +531,7 @@ fn params9_reg() {
         )])
         .expect_func_instrs([
             Instruction::return_call_internal(CompiledFunc::from_u32(0)),
-            Instruction::register_list(0, 1, 2),
-            Instruction::register_list(3, 4, 5),
-            Instruction::register3(6, 7, 8),
+            Instruction::register_span(RegisterSpan::new(Register::from_i16(0)).iter(9)),
         ])
         .run();
 }
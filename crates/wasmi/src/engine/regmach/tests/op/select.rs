@@ -90,6 +90,115 @@ fn reg() {
     test_reg(SelectKind::TypedSelect, ValueType::ExternRef);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_reuse_dead_operands() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (param $rhs i32) (result i32)
+                (select
+                    (i32.add (local.get $lhs) (local.get $rhs))
+                    (i32.sub (local.get $lhs) (local.get $rhs))
+                    (i32.eqz (local.get $condition))
+                )
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let rhs = Register::from_i16(2);
+    let add = Register::from_i16(3);
+    let sub = Register::from_i16(4);
+    let eqz = Register::from_i16(5);
+    // The `select` result reuses the register of its `add` operand
+    // since all operands of the `select` are dead afterwards.
+    // It cannot reuse the register of `eqz` since dynamically allocated
+    // registers are reused in stack order.
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_add(add, lhs, rhs),
+            Instruction::i32_sub(sub, lhs, rhs),
+            Instruction::i32_eqz(eqz, condition),
+            Instruction::select(add, eqz, add),
+            Instruction::Register(sub),
+            Instruction::return_reg(add),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_reuse_dynamic_condition() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (param $rhs i32) (result i32)
+                (select
+                    (local.get $lhs)
+                    (local.get $rhs)
+                    (i32.eqz (local.get $condition))
+                )
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let rhs = Register::from_i16(2);
+    let eqz = Register::from_i16(3);
+    // The `select` result reuses the register of its dynamically allocated `condition`
+    // since it is dead afterwards.
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_eqz(eqz, condition),
+                Instruction::select(eqz, eqz, lhs),
+                Instruction::Register(rhs),
+                Instruction::return_reg(eqz),
+            ])
+            .len_registers(4),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_reuse_dead_condition() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (param $rhs i32) (result i32)
+                (local.get $lhs)
+                (local.get $rhs)
+                (local.get $condition)
+                (local.set $condition (i32.const 0))
+                (select)
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let rhs = Register::from_i16(2);
+    let preserved = Register::from_i16(3);
+    // The `select` result reuses the register holding the preserved `condition`
+    // since its value is dead afterwards. Otherwise a fifth register would be required.
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::copy(preserved, condition),
+                Instruction::copy_imm32(condition, 0_i32),
+                Instruction::select(preserved, preserved, lhs),
+                Instruction::Register(rhs),
+                Instruction::return_reg(preserved),
+            ])
+            .len_registers(4),
+        )
+        .run();
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn same_reg() {
@@ -0,0 +1,157 @@
+//! Translation tests for the supported Wasm `simd` instructions.
+
+use super::*;
+
+/// Runs a [`TranslationTest`] with the simd Wasm proposal enabled.
+fn test_simd(wasm: &[u8], expected: ExpectedFunc) {
+    let mut test = TranslationTest::new(wasm);
+    test.config_mut().wasm_simd(true);
+    test.expect_func(expected).run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn splat_store() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $value i32)
+                (v128.store offset=4
+                    (local.get $ptr)
+                    (i32x4.splat (local.get $value))
+                )
+            )
+        )",
+    );
+    test_simd(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::i32x4_splat(Register::from_i16(2), Register::from_i16(1)),
+            Instruction::v128_store(Register::from_i16(0), Const32::from(4_u32)),
+            Instruction::Register(Register::from_i16(2)),
+            Instruction::Return,
+        ]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn f32x4_splat() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $ptr i32) (param $value f32)
+                (v128.store
+                    (local.get $ptr)
+                    (f32x4.splat (local.get $value))
+                )
+            )
+        )",
+    );
+    test_simd(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::i32x4_splat(Register::from_i16(2), Register::from_i16(1)),
+            Instruction::v128_store(Register::from_i16(0), Const32::from(0_u32)),
+            Instruction::Register(Register::from_i16(2)),
+            Instruction::Return,
+        ]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_store() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $ptr i32)
+                (v128.store
+                    (local.get $ptr)
+                    (v128.load offset=70000 (i32.const 16))
+                )
+            )
+        )",
+    );
+    test_simd(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::v128_load(Register::from_i16(1), Register::from_i16(-1)),
+            Instruction::const32(70000_u32),
+            Instruction::v128_store(Register::from_i16(0), Const32::from(0_u32)),
+            Instruction::Register(Register::from_i16(1)),
+            Instruction::Return,
+        ])
+        .consts([16_i32]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn drop() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (memory 1)
+            (func (param $ptr i32)
+                (drop (v128.load (local.get $ptr)))
+            )
+        )",
+    );
+    test_simd(
+        &wasm,
+        ExpectedFunc::new([
+            Instruction::v128_load(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::const32(0_u32),
+            Instruction::Return,
+        ]),
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn min_max() {
+    fn test_for(op: &str, make_instr: fn(Register, Register, Register) -> Instruction) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (memory 1)
+                (func (param $ptr i32)
+                    (v128.store
+                        (local.get $ptr)
+                        ({op}
+                            (v128.load (local.get $ptr))
+                            (v128.load offset=16 (local.get $ptr))
+                        )
+                    )
+                )
+            )",
+        ));
+        test_simd(
+            &wasm,
+            ExpectedFunc::new([
+                Instruction::v128_load(Register::from_i16(1), Register::from_i16(0)),
+                Instruction::const32(0_u32),
+                Instruction::v128_load(Register::from_i16(3), Register::from_i16(0)),
+                Instruction::const32(16_u32),
+                make_instr(
+                    Register::from_i16(1),
+                    Register::from_i16(1),
+                    Register::from_i16(3),
+                ),
+                Instruction::v128_store(Register::from_i16(0), Const32::from(0_u32)),
+                Instruction::Register(Register::from_i16(1)),
+                Instruction::Return,
+            ]),
+        );
+    }
+    test_for("i32x4.min_s", Instruction::i32x4_min_s);
+    test_for("i32x4.min_u", Instruction::i32x4_min_u);
+    test_for("i32x4.max_s", Instruction::i32x4_max_s);
+    test_for("i32x4.max_u", Instruction::i32x4_max_u);
+    test_for("f32x4.min", Instruction::f32x4_min);
+    test_for("f32x4.max", Instruction::f32x4_max);
+}
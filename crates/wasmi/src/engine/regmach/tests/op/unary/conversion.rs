@@ -284,6 +284,22 @@ mod f32_demote_f64 {
         conversion_imm::<f64, f32>(OP, f64::INFINITY, consteval);
         conversion_imm::<f64, f32>(OP, f64::NEG_INFINITY, consteval);
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn imm_rounding() {
+        // Ties round to even, values above ties round up.
+        conversion_imm::<f64, f32>(OP, 1.0000000596046448, |_| 1.0);
+        conversion_imm::<f64, f32>(OP, 1.0000000894069672, |_| 1.0000001);
+        // Values beyond the `f32` range overflow to infinity.
+        conversion_imm::<f64, f32>(OP, 3.4028235677973366e38, |_| f32::INFINITY);
+        conversion_imm::<f64, f32>(OP, f64::MAX, |_| f32::INFINITY);
+        conversion_imm::<f64, f32>(OP, f64::MIN, |_| f32::NEG_INFINITY);
+        // Subnormal results and underflow to zero.
+        conversion_imm::<f64, f32>(OP, 2.1019476964872256e-45, |_| 3e-45);
+        conversion_imm::<f64, f32>(OP, 1e-50, |_| 0.0);
+        conversion_imm::<f64, f32>(OP, -1e-50, |_| -0.0);
+    }
 }
 
 mod f64_promote_f32 {
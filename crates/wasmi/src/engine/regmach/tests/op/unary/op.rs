@@ -224,6 +224,15 @@ mod f32_nearest {
         unary_imm::<f32>(OP_NAME, 42.5, f32_nearest);
         unary_imm::<f32>(OP_NAME, -42.5, f32_nearest);
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn imm_ties_to_even() {
+        unary_imm::<f32>(OP_NAME, 0.5, |_| 0.0);
+        unary_imm::<f32>(OP_NAME, 1.5, |_| 2.0);
+        unary_imm::<f32>(OP_NAME, 2.5, |_| 2.0);
+        unary_imm::<f32>(OP_NAME, -2.5, |_| -2.0);
+    }
 }
 
 mod f32_sqrt {
@@ -373,6 +382,15 @@ mod f64_nearest {
         unary_imm::<f64>(OP_NAME, 42.5, f64_nearest);
         unary_imm::<f64>(OP_NAME, -42.5, f64_nearest);
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn imm_ties_to_even() {
+        unary_imm::<f64>(OP_NAME, 0.5, |_| 0.0);
+        unary_imm::<f64>(OP_NAME, 1.5, |_| 2.0);
+        unary_imm::<f64>(OP_NAME, 2.5, |_| 2.0);
+        unary_imm::<f64>(OP_NAME, -2.5, |_| -2.0);
+    }
 }
 
 mod f64_sqrt {
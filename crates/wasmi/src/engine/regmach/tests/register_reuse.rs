@@ -0,0 +1,94 @@
+//! Tests asserting that registers of dead temporary values are reused.
+//!
+//! Dynamically allocated registers are freed as soon as their value has been
+//! consumed so that the number of registers used by a function is bounded by
+//! the maximum number of simultaneously live temporaries instead of the total
+//! number of temporaries it computes.
+
+use super::*;
+use crate::engine::bytecode::GlobalIdx;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn tree_of_temporaries() {
+    // A naive allocation would use one register per intermediate result,
+    // that is 1 parameter and 7 temporaries for a total of 8 registers.
+    // The bound of 4 registers is tight: right before the second inner `i32.add`
+    // the result of the first inner `i32.add` and both operands of the second
+    // inner `i32.add` are live at the same time next to the parameter.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (i32.add
+                    (i32.add
+                        (i32.mul (local.get 0) (local.get 0))
+                        (i32.mul (local.get 0) (local.get 0))
+                    )
+                    (i32.add
+                        (i32.mul (local.get 0) (local.get 0))
+                        (i32.mul (local.get 0) (local.get 0))
+                    )
+                )
+            )
+        )",
+    );
+    let r = Register::from_i16;
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_mul(r(1), r(0), r(0)),
+                Instruction::i32_mul(r(2), r(0), r(0)),
+                Instruction::i32_add(r(1), r(1), r(2)),
+                Instruction::i32_mul(r(2), r(0), r(0)),
+                Instruction::i32_mul(r(3), r(0), r(0)),
+                Instruction::i32_add(r(2), r(2), r(3)),
+                Instruction::i32_add(r(1), r(1), r(2)),
+                Instruction::return_reg(r(1)),
+            ])
+            .len_registers(4),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn sequence_of_short_lived_temporaries() {
+    // Every temporary dies right after its use so that all of them share a register.
+    // A naive allocation would use 2 parameters and 5 temporaries for a total of 7 registers.
+    // The bound of 3 registers is tight since each temporary must be stored somewhere
+    // before `global.set` consumes it and both parameters are live throughout.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (global $g (mut i32) (i32.const 0))
+            (func (param i32 i32)
+                (global.set $g (i32.mul (local.get 0) (local.get 1)))
+                (global.set $g (i32.sub (local.get 0) (local.get 1)))
+                (global.set $g (i32.xor (local.get 0) (local.get 1)))
+                (global.set $g (i32.div_u (local.get 0) (local.get 1)))
+                (global.set $g (i32.rem_u (local.get 0) (local.get 1)))
+            )
+        )",
+    );
+    let r = Register::from_i16;
+    let g = GlobalIdx::from(0);
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_mul(r(2), r(0), r(1)),
+                Instruction::global_set(g, r(2)),
+                Instruction::i32_sub(r(2), r(0), r(1)),
+                Instruction::global_set(g, r(2)),
+                Instruction::i32_xor(r(2), r(0), r(1)),
+                Instruction::global_set(g, r(2)),
+                Instruction::i32_div_u(r(2), r(0), r(1)),
+                Instruction::global_set(g, r(2)),
+                Instruction::i32_rem_u(r(2), r(0), r(1)),
+                Instruction::global_set(g, r(2)),
+                Instruction::Return,
+            ])
+            .len_registers(3),
+        )
+        .run();
+}
@@ -5,9 +5,22 @@ use crate::{
         func_builder::{
             labels::{LabelRef, LabelRegistry},
             Instr,
+            TranslationErrorInner,
         },
         regmach::{
-            bytecode::{Const32, Instruction, Provider, Register, RegisterSpan, RegisterSpanIter},
+            bytecode::{
+                BranchBinOpInstr,
+                BranchBinOpInstrImm16,
+                BranchComparator,
+                BranchOffset16,
+                ComparatorOffsetParam,
+                Const32,
+                Instruction,
+                Provider,
+                Register,
+                RegisterSpan,
+                RegisterSpanIter,
+            },
             translator::ValueStack,
         },
         TranslationError,
@@ -15,8 +28,18 @@ use crate::{
     module::ModuleResources,
 };
 use alloc::vec::{Drain, Vec};
+use core::ops::Range;
 use wasmi_core::{UntypedValue, ValueType, F32};
 
+/// The minimum number of neighbouring no-op copies in the interior of a copy
+/// sequence for which [`InstrEncoder::encode_copies`] splits the sequence around them.
+///
+/// # Note
+///
+/// Splitting encodes two copy instructions instead of one and therefore
+/// only pays off if it avoids a sufficient amount of copied registers.
+const MIN_INTERIOR_NOOP_COPIES: usize = 2;
+
 /// Encodes `wasmi` bytecode instructions to an [`Instruction`] stream.
 #[derive(Debug, Default)]
 pub struct InstrEncoder {
@@ -34,6 +57,21 @@ pub struct InstrEncoder {
     /// defragmentation of the register space due to `local.set` register
     /// preservations.
     notified_preservation: Option<Instr>,
+    /// Is `true` if copies shall be encoded as single per-element `copy` instructions.
+    ///
+    /// # Note
+    ///
+    /// This disables the fused `copy2`, `copy_span` and `copy_many` instructions
+    /// wherever their semantics are preserved and is meant for debugging only.
+    split_copies: bool,
+    /// The maximum length of an encoded provider slice if any.
+    max_provider_slice_len: Option<u32>,
+    /// The [`Instr`] at which a label has been pinned most recently.
+    ///
+    /// # Note
+    ///
+    /// This is used to prevent fusing instructions across branch targets.
+    last_pinned: Option<Instr>,
 }
 
 /// The sequence of encoded [`Instruction`].
@@ -92,6 +130,15 @@ impl InstrSequence {
         self.instrs.drain(..)
     }
 
+    /// Removes the [`Instruction`] word at `instr` and shifts all following words.
+    ///
+    /// # Panics
+    ///
+    /// If `instr` is out of bounds for [`InstrSequence`].
+    fn remove(&mut self, instr: Instr) {
+        self.instrs.remove(instr.into_usize());
+    }
+
     /// Returns a slice to the sequence of [`Instruction`] starting at `start`.
     ///
     /// # Panics
@@ -100,6 +147,16 @@ impl InstrSequence {
     pub fn get_slice_at_mut(&mut self, start: Instr) -> &mut [Instruction] {
         &mut self.instrs[start.into_usize()..]
     }
+
+    /// Removes all [`Instruction`] words starting at `start`.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds for [`InstrSequence`].
+    fn truncate(&mut self, start: Instr) {
+        assert!(start.into_usize() <= self.instrs.len());
+        self.instrs.truncate(start.into_usize());
+    }
 }
 
 impl<'a> IntoIterator for &'a mut InstrSequence {
@@ -118,6 +175,32 @@ impl InstrEncoder {
         self.labels.reset();
         self.reset_last_instr();
         self.notified_preservation = None;
+        self.last_pinned = None;
+    }
+
+    /// Configures whether copies are encoded as single per-element `copy` instructions.
+    pub fn set_split_copies(&mut self, enable: bool) {
+        self.split_copies = enable;
+    }
+
+    /// Configures the maximum length of encoded provider slices if any.
+    pub fn set_max_provider_slice_len(&mut self, limit: Option<u32>) {
+        self.max_provider_slice_len = limit;
+    }
+
+    /// Configures the maximum number of labels and branches of a function if any.
+    pub fn set_label_limits(&mut self, max_labels: Option<u32>, max_branches: Option<u32>) {
+        self.labels.set_limits(max_labels, max_branches)
+    }
+
+    /// Returns an error if a provider slice of length `len` exceeds the configured limit.
+    fn ensure_provider_slice_len(&self, len: usize) -> Result<(), TranslationError> {
+        match self.max_provider_slice_len {
+            Some(limit) if len > limit as usize => Err(TranslationError::new(
+                TranslationErrorInner::ProviderSliceTooLong,
+            )),
+            _ => Ok(()),
+        }
     }
 
     /// Resets the [`Instr`] last created via [`InstrEncoder::push_instr`].
@@ -135,6 +218,11 @@ impl InstrEncoder {
         self.last_instr = None;
     }
 
+    /// Returns the [`Instr`] of the next encoded [`Instruction`].
+    pub fn next_instr(&self) -> Instr {
+        self.instrs.next_instr()
+    }
+
     /// Return an iterator over the sequence of generated [`Instruction`].
     ///
     /// # Note
@@ -145,7 +233,11 @@ impl InstrEncoder {
     }
 
     /// Creates a new unresolved label and returns its [`LabelRef`].
-    pub fn new_label(&mut self) -> LabelRef {
+    ///
+    /// # Errors
+    ///
+    /// If this exceeds the configured maximum number of labels.
+    pub fn new_label(&mut self) -> Result<LabelRef, TranslationError> {
         self.labels.new_label()
     }
 
@@ -159,7 +251,9 @@ impl InstrEncoder {
     /// the given label can be resolved properly.
     /// This usually takes place when encountering the Wasm `End` operand for example.
     pub fn pin_label_if_unpinned(&mut self, label: LabelRef) {
-        self.labels.try_pin_label(label, self.instrs.next_instr())
+        let pinned = self.instrs.next_instr();
+        self.last_pinned = Some(pinned);
+        self.labels.try_pin_label(label, pinned)
     }
 
     /// Resolve the label at the current instruction position.
@@ -174,8 +268,10 @@ impl InstrEncoder {
     ///
     /// If the label has already been resolved.
     pub fn pin_label(&mut self, label: LabelRef) {
+        let pinned = self.instrs.next_instr();
+        self.last_pinned = Some(pinned);
         self.labels
-            .pin_label(label, self.instrs.next_instr())
+            .pin_label(label, pinned)
             .unwrap_or_else(|err| panic!("failed to pin label: {err}"));
     }
 
@@ -202,12 +298,23 @@ impl InstrEncoder {
 
     /// Updates the branch offsets of all branch instructions inplace.
     ///
+    /// # Note
+    ///
+    /// Fused compare and branch instructions whose offset does not fit into 16 bits
+    /// are replaced by an [`Instruction::BranchCmpFallback`] that stores its
+    /// parameters in a function local constant allocated on the `stack`.
+    ///
     /// # Panics
     ///
     /// If this is used before all branching labels have been pinned.
-    pub fn update_branch_offsets(&mut self) -> Result<(), TranslationError> {
+    pub fn update_branch_offsets(
+        &mut self,
+        stack: &mut ValueStack,
+    ) -> Result<(), TranslationError> {
         for (user, offset) in self.labels.resolved_users() {
-            self.instrs.get_mut(user).update_branch_offset(offset?);
+            self.instrs
+                .get_mut(user)
+                .update_branch_offset(stack, offset?)?;
         }
         Ok(())
     }
@@ -317,6 +424,43 @@ impl InstrEncoder {
                 return self.encode_copies(stack, results, rest);
             }
         }
+        if self.split_copies && values.len() >= 2 && !Self::has_overlapping_copies(results, values)
+        {
+            // Case: fused copy instructions are disabled so we encode each copy separately.
+            //
+            // Note: this is only valid if no copy reads a register overwritten by one
+            //       of its preceding copies. Otherwise we still encode fused copies below.
+            for (result, value) in results.zip(values) {
+                let result = RegisterSpan::new(result).iter(1);
+                self.encode_copies(stack, result, core::slice::from_ref(value))?;
+            }
+            return Ok(());
+        }
+        if values.len() >= 3 {
+            if let Some(noops) = Self::longest_noop_copies(results, values) {
+                let head = &values[..noops.start];
+                let tail = &values[noops.end..];
+                let head_results = results.span().iter(head.len());
+                if tail.is_empty() {
+                    // Case: the copy sequence ends with no-op copies which we can avoid.
+                    return self.encode_copies(stack, head_results, head);
+                }
+                let tail_reads_head = tail
+                    .iter()
+                    .filter_map(|value| value.as_register())
+                    .any(|value| head_results.contains(value));
+                if noops.len() >= MIN_INTERIOR_NOOP_COPIES && !tail_reads_head {
+                    // Case: the copy sequence has a large run of no-op copies in its interior.
+                    //       We split the copy sequence around them and encode the copies before
+                    //       and after them separately. This is only valid if none of the copies
+                    //       after the no-op copies reads a register overwritten before them.
+                    let mut tail_results = results;
+                    tail_results.nth(noops.end - 1);
+                    self.encode_copies(stack, head_results, head)?;
+                    return self.encode_copies(stack, tail_results, tail);
+                }
+            }
+        }
         let result = results.span().head();
         let instr = match values {
             [] => {
@@ -368,6 +512,7 @@ impl InstrEncoder {
                     ))?;
                     return Ok(());
                 }
+                self.ensure_provider_slice_len(values.len())?;
                 let make_instr = match Self::has_overlapping_copies(results, values) {
                     true => Instruction::copy_many,
                     false => Instruction::copy_many_non_overlapping,
@@ -383,6 +528,36 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Returns the range of the longest run of neighbouring no-op copies in `results <- values`.
+    ///
+    /// Returns `None` if `results <- values` has no no-op copies.
+    ///
+    /// # Examples
+    ///
+    /// - The sequence `[ 0 <- 1, 1 <- 1, 2 <- 2, 3 <- 2 ]` yields `1..3`.
+    /// - The sequence `[ 0 <- 1, 1 <- 0 ]` yields `None`.
+    fn longest_noop_copies(
+        results: RegisterSpanIter,
+        values: &[TypedProvider],
+    ) -> Option<Range<usize>> {
+        debug_assert_eq!(results.len(), values.len());
+        let mut longest = 0..0;
+        let mut run_start = 0;
+        for (n, (result, value)) in results.zip(values).enumerate() {
+            if value.as_register() != Some(result) {
+                run_start = n + 1;
+                continue;
+            }
+            if n + 1 - run_start > longest.len() {
+                longest = run_start..n + 1;
+            }
+        }
+        if longest.is_empty() {
+            return None;
+        }
+        Some(longest)
+    }
+
     /// Returns `true` if `copy_span results <- values` has overlapping copies.
     ///
     /// # Examples
@@ -428,7 +603,7 @@ impl InstrEncoder {
         for (result, value) in results.zip(values) {
             // Note: We only have to check the register case since constant value
             //       copies can never overlap.
-            if let TypedProvider::Register(value) = *value {
+            if let Some(value) = value.as_register() {
                 // If the register `value` index is within range of `result0..result`
                 // then its value has been overwritten by previous copies.
                 if result0 <= value && value < result {
@@ -480,6 +655,7 @@ impl InstrEncoder {
                     self.push_instr(Instruction::return_span(span))?;
                     return Ok(());
                 }
+                self.ensure_provider_slice_len(values.len())?;
                 let reg0 = Self::provider2reg(stack, v0)?;
                 let reg1 = Self::provider2reg(stack, v1)?;
                 let reg2 = Self::provider2reg(stack, v2)?;
@@ -528,6 +704,7 @@ impl InstrEncoder {
                     self.push_instr(Instruction::return_nez_span(condition, span))?;
                     return Ok(());
                 }
+                self.ensure_provider_slice_len(values.len())?;
                 let reg0 = Self::provider2reg(stack, v0)?;
                 let reg1 = Self::provider2reg(stack, v1)?;
                 self.push_instr(Instruction::return_nez_many(condition, reg0, reg1))?;
@@ -572,6 +749,7 @@ impl InstrEncoder {
         stack: &mut ValueStack,
         inputs: &[TypedProvider],
     ) -> Result<(), TranslationError> {
+        self.ensure_provider_slice_len(inputs.len())?;
         let mut remaining = inputs;
         loop {
             match remaining {
@@ -605,6 +783,29 @@ impl InstrEncoder {
         }
     }
 
+    /// Encodes the parameters of a call instruction.
+    ///
+    /// # Note
+    ///
+    /// More than three parameters stored in contiguous registers are encoded as a
+    /// single [`Instruction::RegisterSpan`] so that they are copied to the parameter
+    /// registers of the callee at once. Otherwise this is the same as
+    /// [`InstrEncoder::encode_register_list`].
+    pub fn encode_call_params(
+        &mut self,
+        stack: &mut ValueStack,
+        params: &[TypedProvider],
+    ) -> Result<(), TranslationError> {
+        if params.len() > 3 {
+            if let Some(span) = RegisterSpanIter::from_providers(params) {
+                self.ensure_provider_slice_len(params.len())?;
+                self.instrs.push(Instruction::register_span(span))?;
+                return Ok(());
+            }
+        }
+        self.encode_register_list(stack, params)
+    }
+
     /// Encode a `local.set` or `local.tee` instruction.
     ///
     /// # Note
@@ -613,12 +814,14 @@ impl InstrEncoder {
     /// result is replaced with the `local` [`Register`] instead of encoding
     /// another `copy` instruction if the `local.set` or `local.tee` belongs
     /// to the same basic block.
+    ///
+    /// Returns `true` if this optimization has been applied.
     pub fn encode_local_set(
         &mut self,
         res: &ModuleResources,
         local: Register,
         value: Register,
-    ) -> Result<(), TranslationError> {
+    ) -> Result<bool, TranslationError> {
         if let Some(last_instr) = self.last_instr {
             if let Some(result) = self.instrs.get_mut(last_instr).result_mut(res) {
                 // Case: we can replace the `result` register of the previous
@@ -630,13 +833,188 @@ impl InstrEncoder {
                     //       Therefore, instead of an `if` we originally had a `debug_assert`.
                     //       (Note: the spidermonkey bench test failed without this change.)
                     *result = local;
-                    return Ok(());
+                    return Ok(true);
                 }
             }
         }
         // Case: we need to encode a copy instruction to encode the `local.set` or `local.tee`.
         self.push_instr(Instruction::copy(local, value))?;
-        Ok(())
+        Ok(false)
+    }
+
+    /// Removes the last encoded `i32.load` [`Instruction`] if its result is `value`.
+    ///
+    /// Returns the `ptr` [`Register`] and `offset` of the removed `i32.load`
+    /// so that the caller can encode a fused [`Instruction`] in its place.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` and does nothing if the last encoded [`Instruction`] is
+    /// not an `i32.load` with `value` as its result or if a branch target has
+    /// been pinned after it since fusion must not skip over branch targets.
+    ///
+    /// Also returns `None` if the `i32.load` is prefixed by an [`Instruction::UseMemory`]
+    /// since fused instructions always operate on the default linear memory.
+    pub fn try_take_last_i32_load(&mut self, value: Register) -> Option<(Register, u32)> {
+        let last_instr = self.last_instr?;
+        if matches!(self.last_pinned, Some(pinned) if pinned > last_instr) {
+            return None;
+        }
+        if let Some(prev_instr) = last_instr.into_usize().checked_sub(1) {
+            if let Instruction::UseMemory(_) = self.instrs.get(Instr::from_usize(prev_instr)) {
+                return None;
+            }
+        }
+        let next_instr = self.instrs.next_instr().into_usize();
+        let (ptr, offset) = match *self.instrs.get(last_instr) {
+            Instruction::I32LoadOffset16(instr)
+                if instr.result == value && last_instr.into_usize() + 1 == next_instr =>
+            {
+                (instr.ptr, u32::from(instr.offset))
+            }
+            Instruction::I32Load(instr)
+                if instr.result == value && last_instr.into_usize() + 2 == next_instr =>
+            {
+                let param = Instr::from_usize(last_instr.into_usize() + 1);
+                match *self.instrs.get(param) {
+                    Instruction::Const32(offset) => (instr.ptr, offset.to_u32()),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        self.instrs.truncate(last_instr);
+        self.reset_last_instr();
+        Some((ptr, offset))
+    }
+
+    /// Removes the last encoded [`Instruction::I32Eqz`] if its result is `condition`.
+    ///
+    /// Returns the input [`Register`] of the removed `i32.eqz` so that the caller
+    /// can encode a conditional branch on it with inverted condition instead.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` and does nothing if the last encoded [`Instruction`] is not
+    /// an [`Instruction::I32Eqz`] with `condition` as its result or if a branch target
+    /// has been pinned after it since fusion must not skip over branch targets.
+    ///
+    /// The caller is responsible to make sure that no other instruction reads `condition`.
+    pub fn try_take_last_i32_eqz(&mut self, condition: Register) -> Option<Register> {
+        let last_instr = self.last_instr?;
+        if matches!(self.last_pinned, Some(pinned) if pinned > last_instr) {
+            return None;
+        }
+        let input = match *self.instrs.get(last_instr) {
+            Instruction::I32Eqz(instr) if instr.result == condition => instr.input,
+            _ => return None,
+        };
+        self.instrs.truncate(last_instr);
+        self.reset_last_instr();
+        Some(input)
+    }
+
+    /// Fuses the last encoded `i32` comparison with a `br_if` on its `condition` result to `label`.
+    ///
+    /// # Note
+    ///
+    /// If `label` has already been pinned, e.g. for branches back to the header
+    /// of a `loop`, fusion only applies if its offset fits into 16 bits.
+    /// Forward branches are always fused since their offset is unknown at this
+    /// point and they are patched by [`InstrEncoder::update_branch_offsets`].
+    ///
+    /// The caller is responsible to make sure that no other instruction reads `condition`.
+    ///
+    /// Returns `true` if the comparison has been replaced by the fused [`Instruction`].
+    pub fn try_fuse_branch_cmp(
+        &mut self,
+        condition: Register,
+        label: LabelRef,
+    ) -> Result<bool, TranslationError> {
+        let last_instr = match self.last_instr {
+            Some(last_instr) => last_instr,
+            None => return Ok(false),
+        };
+        if matches!(self.last_pinned, Some(pinned) if pinned > last_instr) {
+            return Ok(false);
+        }
+        let cmp = *self.instrs.get(last_instr);
+        if cmp
+            .fuse_branch_cmp(condition, BranchOffset16::from(0))
+            .is_none()
+        {
+            return Ok(false);
+        }
+        // Note: forward branches resolve to an uninitialized offset which always
+        //       fits into 16 bits and registers the fused instruction as label user.
+        let offset = self.try_resolve_label_for(label, last_instr)?;
+        let offset = match BranchOffset16::try_from(offset) {
+            Ok(offset) => offset,
+            Err(_) => return Ok(false),
+        };
+        *self.instrs.get_mut(last_instr) = cmp
+            .fuse_branch_cmp(condition, offset)
+            .expect("checked that the comparison can be fused");
+        Ok(true)
+    }
+
+    /// Removes the `copy` to `local` directly preceding the last [`Instruction`] if it is dead.
+    ///
+    /// # Note
+    ///
+    /// This is used after encoding a `local.set` or `local.tee` for which the
+    /// last [`Instruction`] writes to `local`. A directly preceding `copy` to
+    /// `local` is dead if the last [`Instruction`] does not read `local` and
+    /// no branch target has been pinned in between both instructions.
+    ///
+    /// This cleans up `copy` instructions left over by consecutive writes to
+    /// the same local variable.
+    ///
+    /// Returns `true` if the dead `copy` has been removed.
+    pub fn try_remove_dead_copy(&mut self, res: &ModuleResources, local: Register) -> bool {
+        let last_instr = match self.last_instr {
+            Some(last_instr) => last_instr,
+            None => return false,
+        };
+        if last_instr.into_usize() == 0
+            || last_instr.into_usize() + 1 != self.instrs.next_instr().into_usize()
+        {
+            // Note: either there is no preceding instruction or the last instruction
+            //       has parameter words that we do not inspect.
+            return false;
+        }
+        let copy_instr = Instr::from_usize(last_instr.into_usize() - 1);
+        if matches!(self.last_pinned, Some(pinned) if pinned > copy_instr) {
+            return false;
+        }
+        if !matches!(self.instrs.get_mut(last_instr).result_mut(res), Some(result) if *result == local)
+        {
+            return false;
+        }
+        let mut reads_local = false;
+        match self.instrs.get_mut(last_instr) {
+            Instruction::Copy { value, .. } => reads_local = *value == local,
+            instr => instr.visit_input_registers(|input| reads_local |= *input == local),
+        }
+        if reads_local {
+            return false;
+        }
+        match self.instrs.get(copy_instr) {
+            Instruction::Copy { result, .. }
+            | Instruction::CopyImm32 { result, .. }
+            | Instruction::CopyI64Imm32 { result, .. }
+            | Instruction::CopyF64Imm32 { result, .. }
+                if *result == local => {}
+            _ => return false,
+        }
+        self.instrs.remove(copy_instr);
+        self.last_instr = Some(copy_instr);
+        if let Some(preserved) = self.notified_preservation {
+            if preserved > copy_instr {
+                self.notified_preservation = Some(Instr::from_usize(preserved.into_usize() - 1));
+            }
+        }
+        true
     }
 
     /// Pushes an [`Instruction::ConsumeFuel`] with base fuel costs to the [`InstrEncoder`].
@@ -672,6 +1050,12 @@ impl InstrEncoder {
         if let Some(notified_preserved) = self.notified_preservation {
             for instr in self.instrs.get_slice_at_mut(notified_preserved) {
                 instr.visit_input_registers(|reg| *reg = stack.defrag_register(*reg));
+                if let Instruction::Select { result, .. } | Instruction::SelectRev { result, .. } =
+                    instr
+                {
+                    // Note: `select` may reuse a dead storage-space register for its `result`.
+                    *result = stack.defrag_register(*result);
+                }
             }
         }
         Ok(())
@@ -681,16 +1065,191 @@ impl InstrEncoder {
 impl Instruction {
     /// Updates the [`BranchOffset`] for the branch [`Instruction].
     ///
+    /// Replaces fused compare and branch [`Instruction`] by an [`Instruction::BranchCmpFallback`]
+    /// if `new_offset` does not fit into their [`BranchOffset16`].
+    ///
+    /// # Errors
+    ///
+    /// If the function local constant of the [`Instruction::BranchCmpFallback`] cannot be allocated.
+    ///
     /// # Panics
     ///
     /// If `self` is not a branch [`Instruction`].
-    pub fn update_branch_offset(&mut self, new_offset: BranchOffset) {
+    pub fn update_branch_offset(
+        &mut self,
+        stack: &mut ValueStack,
+        new_offset: BranchOffset,
+    ) -> Result<(), TranslationError> {
         match self {
             Instruction::Branch { offset }
             | Instruction::BranchEqz { offset, .. }
             | Instruction::BranchNez { offset, .. } => offset.init(new_offset),
+            Instruction::BranchI32Eq(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32EqImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32Ne(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32NeImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32LtS(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32LtSImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32LtU(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32LtUImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32LeS(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32LeSImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32LeU(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32LeUImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32GtS(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32GtSImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32GtU(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32GtUImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32GeS(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32GeSImm(BranchBinOpInstrImm16 { offset, .. })
+            | Instruction::BranchI32GeU(BranchBinOpInstr { offset, .. })
+            | Instruction::BranchI32GeUImm(BranchBinOpInstrImm16 { offset, .. }) => {
+                match BranchOffset16::try_from(new_offset) {
+                    Ok(new_offset) => offset.init(new_offset),
+                    Err(_) => *self = self.make_branch_cmp_fallback(stack, new_offset)?,
+                }
+            }
             _ => panic!("tried to update branch offset of a non-branch instruction: {self:?}"),
         }
+        Ok(())
+    }
+
+    /// Returns the [`Instruction::BranchCmpFallback`] equivalent to the fused compare and branch `self`.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a fused compare and branch [`Instruction`].
+    fn make_branch_cmp_fallback(
+        &self,
+        stack: &mut ValueStack,
+        offset: BranchOffset,
+    ) -> Result<Instruction, TranslationError> {
+        use BranchComparator as Cmp;
+        let (cmp, lhs, rhs) = match *self {
+            Instruction::BranchI32Eq(instr) => (Cmp::I32Eq, instr.lhs, instr.rhs),
+            Instruction::BranchI32Ne(instr) => (Cmp::I32Ne, instr.lhs, instr.rhs),
+            Instruction::BranchI32LtS(instr) => (Cmp::I32LtS, instr.lhs, instr.rhs),
+            Instruction::BranchI32LtU(instr) => (Cmp::I32LtU, instr.lhs, instr.rhs),
+            Instruction::BranchI32LeS(instr) => (Cmp::I32LeS, instr.lhs, instr.rhs),
+            Instruction::BranchI32LeU(instr) => (Cmp::I32LeU, instr.lhs, instr.rhs),
+            Instruction::BranchI32GtS(instr) => (Cmp::I32GtS, instr.lhs, instr.rhs),
+            Instruction::BranchI32GtU(instr) => (Cmp::I32GtU, instr.lhs, instr.rhs),
+            Instruction::BranchI32GeS(instr) => (Cmp::I32GeS, instr.lhs, instr.rhs),
+            Instruction::BranchI32GeU(instr) => (Cmp::I32GeU, instr.lhs, instr.rhs),
+            Instruction::BranchI32EqImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32Eq, instr.lhs, rhs)
+            }
+            Instruction::BranchI32NeImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32Ne, instr.lhs, rhs)
+            }
+            Instruction::BranchI32LtSImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32LtS, instr.lhs, rhs)
+            }
+            Instruction::BranchI32LtUImm(instr) => {
+                let rhs = stack.alloc_const(u32::from(instr.rhs))?;
+                (Cmp::I32LtU, instr.lhs, rhs)
+            }
+            Instruction::BranchI32LeSImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32LeS, instr.lhs, rhs)
+            }
+            Instruction::BranchI32LeUImm(instr) => {
+                let rhs = stack.alloc_const(u32::from(instr.rhs))?;
+                (Cmp::I32LeU, instr.lhs, rhs)
+            }
+            Instruction::BranchI32GtSImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32GtS, instr.lhs, rhs)
+            }
+            Instruction::BranchI32GtUImm(instr) => {
+                let rhs = stack.alloc_const(u32::from(instr.rhs))?;
+                (Cmp::I32GtU, instr.lhs, rhs)
+            }
+            Instruction::BranchI32GeSImm(instr) => {
+                let rhs = stack.alloc_const(i32::from(instr.rhs))?;
+                (Cmp::I32GeS, instr.lhs, rhs)
+            }
+            Instruction::BranchI32GeUImm(instr) => {
+                let rhs = stack.alloc_const(u32::from(instr.rhs))?;
+                (Cmp::I32GeU, instr.lhs, rhs)
+            }
+            _ => panic!("expected a fused compare and branch instruction but found: {self:?}"),
+        };
+        let params = stack.alloc_const(ComparatorOffsetParam::new(cmp, offset))?;
+        Ok(Instruction::branch_cmp_fallback(lhs, rhs, params))
+    }
+
+    /// Returns the fused compare and branch [`Instruction`] for a `br_if` on the result of `self`.
+    ///
+    /// Returns `None` if `self` is not an `i32` comparison with `condition` as its result.
+    fn fuse_branch_cmp(&self, condition: Register, offset: BranchOffset16) -> Option<Instruction> {
+        let fused = match *self {
+            Instruction::I32Eq(instr) if instr.result == condition => {
+                Instruction::branch_i32_eq(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32EqImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_eq_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32Ne(instr) if instr.result == condition => {
+                Instruction::branch_i32_ne(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32NeImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_ne_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32LtS(instr) if instr.result == condition => {
+                Instruction::branch_i32_lt_s(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32LtSImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_lt_s_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32LtU(instr) if instr.result == condition => {
+                Instruction::branch_i32_lt_u(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32LtUImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_lt_u_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32LeS(instr) if instr.result == condition => {
+                Instruction::branch_i32_le_s(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32LeSImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_le_s_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32LeU(instr) if instr.result == condition => {
+                Instruction::branch_i32_le_u(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32LeUImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_le_u_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32GtS(instr) if instr.result == condition => {
+                Instruction::branch_i32_gt_s(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32GtSImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_gt_s_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32GtU(instr) if instr.result == condition => {
+                Instruction::branch_i32_gt_u(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32GtUImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_gt_u_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32GeS(instr) if instr.result == condition => {
+                Instruction::branch_i32_ge_s(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32GeSImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_ge_s_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            Instruction::I32GeU(instr) if instr.result == condition => {
+                Instruction::branch_i32_ge_u(instr.lhs, instr.rhs, offset)
+            }
+            Instruction::I32GeUImm16(instr) if instr.result == condition => {
+                Instruction::branch_i32_ge_u_imm(instr.reg_in, instr.imm_in, offset)
+            }
+            _ => return None,
+        };
+        Some(fused)
     }
 }
 
@@ -868,4 +1427,81 @@ mod tests {
             5
         ));
     }
+
+    #[test]
+    fn longest_noop_copies_works() {
+        let longest = |results: i16, values: &[i16]| {
+            let values: Vec<_> = values
+                .iter()
+                .copied()
+                .map(TypedProvider::register)
+                .collect();
+            InstrEncoder::longest_noop_copies(span(results).iter(values.len()), &values)
+        };
+        assert_eq!(longest(0, &[]), None);
+        assert_eq!(longest(0, &[1, 0]), None);
+        assert_eq!(longest(0, &[0]), Some(0..1));
+        assert_eq!(longest(0, &[1, 1, 2, 2]), Some(1..3));
+        assert_eq!(longest(0, &[5, 1, 6, 3, 4, 7]), Some(3..5));
+        assert_eq!(longest(2, &[0, 3, 1, 5, 6]), Some(3..5));
+    }
+
+    /// Encodes the copies `results <- values` and returns the encoded instructions.
+    fn encode_copies(results: i16, values: &[i16]) -> Vec<Instruction> {
+        let mut encoder = InstrEncoder::default();
+        let mut stack = ValueStack::default();
+        let values: Vec<_> = values
+            .iter()
+            .copied()
+            .map(TypedProvider::register)
+            .collect();
+        encoder
+            .encode_copies(&mut stack, span(results).iter(values.len()), &values)
+            .unwrap();
+        encoder.drain_instrs().collect()
+    }
+
+    #[test]
+    fn encode_copies_avoids_interior_noop_copies() {
+        // Interior no-op copies split the copy sequence.
+        assert_eq!(
+            encode_copies(0, &[1, 1, 2, 2]),
+            [Instruction::copy(0, 1), Instruction::copy(3, 2)],
+        );
+        assert_eq!(
+            encode_copies(0, &[7, 8, 2, 3, 9, 10]),
+            [
+                Instruction::copy2(span(0), 7, 8),
+                Instruction::copy2(span(4), 9, 10),
+            ],
+        );
+        assert_eq!(
+            encode_copies(0, &[7, 8, 9, 3, 4, 5, 10, 11, 12]),
+            [
+                Instruction::copy_span_non_overlapping(span(0), span(7), 3),
+                Instruction::copy_span_non_overlapping(span(6), span(10), 3),
+            ],
+        );
+        // Trailing no-op copies are always avoided.
+        assert_eq!(
+            encode_copies(0, &[5, 6, 2]),
+            [Instruction::copy2(span(0), 5, 6)],
+        );
+        // A single interior no-op copy does not split the copy sequence.
+        assert_eq!(
+            encode_copies(0, &[5, 1, 6]),
+            [
+                Instruction::copy_many_non_overlapping(span(0), 5, 1),
+                Instruction::register(6),
+            ],
+        );
+        // No split if a copy after the no-op copies reads a register overwritten before them.
+        assert_eq!(
+            encode_copies(0, &[5, 1, 2, 0]),
+            [
+                Instruction::copy_many(span(0), 5, 1),
+                Instruction::register2(2, 0),
+            ],
+        );
+    }
 }
@@ -0,0 +1,378 @@
+//! Analysis of counted loops whose bounds checks can be hoisted in front of the loop.
+//!
+//! # Note
+//!
+//! Read [`Config::set_hoist_loop_bounds_checks`] for more information.
+//!
+//! [`Config::set_hoist_loop_bounds_checks`]: crate::Config::set_hoist_loop_bounds_checks
+
+use alloc::vec::Vec;
+use wasmparser::{BlockType, MemArg, Operator, OperatorsReader};
+
+/// A counted `loop` whose bounds checks are hoisted in front of the `loop`.
+///
+/// The guarded `loop` has the following shape:
+///
+/// ```wat
+/// (loop
+///     (br_if $exit (i32.ge_u (local.get $local) (i32.const $bound)))
+///     ;; straight-line body without side effects
+///     (local.set $local (i32.add (local.get $local) (i32.const $step)))
+///     (br 0)
+/// )
+/// ```
+///
+/// The body only contains loads, non-trapping numeric operators and accesses to
+/// local variables other than `$local`. Therefore every `loop` iteration for which
+/// `$local` is less than `$bound` executes all loads and the `loop` can only trap
+/// with [`TrapCode::MemoryOutOfBounds`].
+///
+/// [`TrapCode::MemoryOutOfBounds`]: crate::core::TrapCode::MemoryOutOfBounds
+#[derive(Debug, Copy, Clone)]
+pub struct LoopGuard {
+    /// The Wasm binary offset of the `loop` operator.
+    pos: usize,
+    /// The index of the local variable counting the `loop` iterations.
+    pub local: u32,
+    /// The exclusive upper bound of `local`.
+    pub bound: u32,
+    /// The increment of `local` per `loop` iteration.
+    pub step: u32,
+    /// The maximum `offset + N` of all guarded `N` byte loads of the `loop` body.
+    pub end_offset: u32,
+    /// Is `true` once the guard has been encoded in front of the `loop`.
+    encoded: bool,
+}
+
+/// The counted loops of a Wasm function body whose bounds checks can be hoisted.
+#[derive(Debug, Default)]
+pub struct LoopGuards {
+    /// The guarded loops ordered by the Wasm binary offset of their `loop` operator.
+    guards: Vec<LoopGuard>,
+    /// The Wasm binary offsets of all guarded loads and the index of their guard.
+    ///
+    /// A load is guarded if its address is the `local` of its guard without offset.
+    loads: Vec<(usize, usize)>,
+}
+
+impl LoopGuards {
+    /// Resets the [`LoopGuards`] for the translation of another function.
+    pub fn reset(&mut self) {
+        self.guards.clear();
+        self.loads.clear();
+    }
+
+    /// Analyzes the operators read by `reader` for counted loops.
+    ///
+    /// # Note
+    ///
+    /// This runs before the function body has been validated.
+    /// Malformed function bodies are not analyzed and fail validation later on.
+    pub fn analyze(&mut self, mut reader: OperatorsReader) {
+        self.reset();
+        let mut ops = Vec::new();
+        while !reader.eof() {
+            match reader.read_with_offset() {
+                Ok((op, pos)) => ops.push((pos, op)),
+                Err(_) => return,
+            }
+        }
+        let mut rest = &ops[..];
+        while !rest.is_empty() {
+            let len_loads = self.loads.len();
+            if self.analyze_loop(rest).is_none() {
+                self.loads.truncate(len_loads);
+            }
+            rest = &rest[1..];
+        }
+    }
+
+    /// Registers a [`LoopGuard`] if `ops` start with a counted `loop` that can be guarded.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if no [`LoopGuard`] has been registered in which case
+    /// the caller is responsible to remove the loads registered in the meantime.
+    fn analyze_loop(&mut self, ops: &[(usize, Operator)]) -> Option<()> {
+        let [loop_, get, bound, ge_u, br_if] = first_ops(ops)?;
+        let (local, bound) = match (loop_, get, bound, ge_u, br_if) {
+            (
+                Operator::Loop {
+                    blockty: BlockType::Empty,
+                },
+                Operator::LocalGet { local_index },
+                Operator::I32Const { value },
+                Operator::I32GeU,
+                Operator::BrIf { relative_depth },
+            ) if *relative_depth != 0 => (*local_index, *value as u32),
+            _ => return None,
+        };
+        let pos = ops[0].0;
+        let guard = self.guards.len();
+        let len_loads = self.loads.len();
+        let mut end_offset = 0_u64;
+        let mut body = &ops[5..];
+        let step = loop {
+            if let Some(step) = loop_tail(body, local) {
+                break step;
+            }
+            if let Some((load_pos, memarg, width)) = guarded_load(body, local) {
+                if memarg.memory != 0 {
+                    return None;
+                }
+                end_offset = end_offset.max(memarg.offset.saturating_add(width));
+                self.loads.push((load_pos, guard));
+                body = &body[2..];
+                continue;
+            }
+            match body.first()? {
+                (_, Operator::LocalGet { .. }) => {}
+                (_, Operator::LocalSet { local_index } | Operator::LocalTee { local_index })
+                    if *local_index != local => {}
+                (_, op) if is_pure_op(op) => {}
+                (_, op) if matches!(load_width(op), Some((memarg, _)) if memarg.memory == 0) => {}
+                _ => return None,
+            }
+            body = &body[1..];
+        };
+        let end_offset = u32::try_from(end_offset).ok()?;
+        if self.loads.len() == len_loads || bound == 0 || step == 0 {
+            // Note: without loads there is nothing to guard and
+            //       without iterations there is nothing to loop.
+            return None;
+        }
+        if u64::from(bound) + u64::from(step) > 1 << 32 {
+            // Note: the `loop` index might wrap around in which case
+            //       the last `loop` iteration cannot be determined.
+            return None;
+        }
+        self.guards.push(LoopGuard {
+            pos,
+            local,
+            bound,
+            step,
+            end_offset,
+            encoded: false,
+        });
+        Some(())
+    }
+
+    /// Returns the [`LoopGuard`] of the `loop` at Wasm binary offset `pos` if any.
+    ///
+    /// # Note
+    ///
+    /// The loads of the returned [`LoopGuard`] are guarded from now on.
+    /// The caller is responsible to encode the guard in front of the `loop`.
+    pub fn encode_guard(&mut self, pos: usize) -> Option<LoopGuard> {
+        let index = self
+            .guards
+            .binary_search_by_key(&pos, |guard| guard.pos)
+            .ok()?;
+        let guard = &mut self.guards[index];
+        guard.encoded = true;
+        Some(*guard)
+    }
+
+    /// Returns the `local` of the encoded [`LoopGuard`] of the load at Wasm binary offset `pos` if any.
+    pub fn guarded_load(&self, pos: usize) -> Option<u32> {
+        let index = self
+            .loads
+            .binary_search_by_key(&pos, |(load, _)| *load)
+            .ok()?;
+        let guard = &self.guards[self.loads[index].1];
+        guard.encoded.then_some(guard.local)
+    }
+}
+
+/// Returns the first `N` operators of `ops` if any.
+fn first_ops<'a, 'b, const N: usize>(
+    ops: &'b [(usize, Operator<'a>)],
+) -> Option<[&'b Operator<'a>; N]> {
+    let ops = ops.get(..N)?;
+    Some(core::array::from_fn(|n| &ops[n].1))
+}
+
+/// Returns the `step` if `ops` start with the tail of a counted `loop` of `local`.
+fn loop_tail(ops: &[(usize, Operator)], local: u32) -> Option<u32> {
+    let [get, step, add, set, br, end] = first_ops(ops)?;
+    match (get, step, add, set, br, end) {
+        (
+            Operator::LocalGet { local_index: get },
+            Operator::I32Const { value: step },
+            Operator::I32Add,
+            Operator::LocalSet { local_index: set },
+            Operator::Br { relative_depth: 0 },
+            Operator::End,
+        ) if *get == local && *set == local => Some(*step as u32),
+        _ => None,
+    }
+}
+
+/// Returns the Wasm binary offset, [`MemArg`] and number of loaded bytes if `ops` start with a load from `local`.
+fn guarded_load(ops: &[(usize, Operator)], local: u32) -> Option<(usize, MemArg, u64)> {
+    match ops {
+        [(_, Operator::LocalGet { local_index }), (pos, load), ..] if *local_index == local => {
+            let (memarg, width) = load_width(load)?;
+            Some((*pos, memarg, width))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the [`MemArg`] and the number of loaded bytes if `op` is a load.
+fn load_width(op: &Operator) -> Option<(MemArg, u64)> {
+    let (memarg, width) = match *op {
+        Operator::I32Load { memarg } | Operator::F32Load { memarg } => (memarg, 4),
+        Operator::I64Load { memarg } | Operator::F64Load { memarg } => (memarg, 8),
+        Operator::I32Load8S { memarg }
+        | Operator::I32Load8U { memarg }
+        | Operator::I64Load8S { memarg }
+        | Operator::I64Load8U { memarg } => (memarg, 1),
+        Operator::I32Load16S { memarg }
+        | Operator::I32Load16U { memarg }
+        | Operator::I64Load16S { memarg }
+        | Operator::I64Load16U { memarg } => (memarg, 2),
+        Operator::I64Load32S { memarg } | Operator::I64Load32U { memarg } => (memarg, 4),
+        _ => return None,
+    };
+    Some((memarg, width))
+}
+
+/// Returns `true` if `op` neither traps nor has side effects nor affects control flow.
+///
+/// # Note
+///
+/// Accesses to local variables are not covered since they depend on the accessed local.
+fn is_pure_op(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Nop
+            | Operator::Drop
+            | Operator::Select
+            | Operator::TypedSelect { .. }
+            | Operator::I32Const { .. }
+            | Operator::I64Const { .. }
+            | Operator::F32Const { .. }
+            | Operator::F64Const { .. }
+            | Operator::I32Eqz
+            | Operator::I32Eq
+            | Operator::I32Ne
+            | Operator::I32LtS
+            | Operator::I32LtU
+            | Operator::I32GtS
+            | Operator::I32GtU
+            | Operator::I32LeS
+            | Operator::I32LeU
+            | Operator::I32GeS
+            | Operator::I32GeU
+            | Operator::I64Eqz
+            | Operator::I64Eq
+            | Operator::I64Ne
+            | Operator::I64LtS
+            | Operator::I64LtU
+            | Operator::I64GtS
+            | Operator::I64GtU
+            | Operator::I64LeS
+            | Operator::I64LeU
+            | Operator::I64GeS
+            | Operator::I64GeU
+            | Operator::F32Eq
+            | Operator::F32Ne
+            | Operator::F32Lt
+            | Operator::F32Gt
+            | Operator::F32Le
+            | Operator::F32Ge
+            | Operator::F64Eq
+            | Operator::F64Ne
+            | Operator::F64Lt
+            | Operator::F64Gt
+            | Operator::F64Le
+            | Operator::F64Ge
+            | Operator::I32Clz
+            | Operator::I32Ctz
+            | Operator::I32Popcnt
+            | Operator::I32Add
+            | Operator::I32Sub
+            | Operator::I32Mul
+            | Operator::I32And
+            | Operator::I32Or
+            | Operator::I32Xor
+            | Operator::I32Shl
+            | Operator::I32ShrS
+            | Operator::I32ShrU
+            | Operator::I32Rotl
+            | Operator::I32Rotr
+            | Operator::I64Clz
+            | Operator::I64Ctz
+            | Operator::I64Popcnt
+            | Operator::I64Add
+            | Operator::I64Sub
+            | Operator::I64Mul
+            | Operator::I64And
+            | Operator::I64Or
+            | Operator::I64Xor
+            | Operator::I64Shl
+            | Operator::I64ShrS
+            | Operator::I64ShrU
+            | Operator::I64Rotl
+            | Operator::I64Rotr
+            | Operator::F32Abs
+            | Operator::F32Neg
+            | Operator::F32Ceil
+            | Operator::F32Floor
+            | Operator::F32Trunc
+            | Operator::F32Nearest
+            | Operator::F32Sqrt
+            | Operator::F32Add
+            | Operator::F32Sub
+            | Operator::F32Mul
+            | Operator::F32Div
+            | Operator::F32Min
+            | Operator::F32Max
+            | Operator::F32Copysign
+            | Operator::F64Abs
+            | Operator::F64Neg
+            | Operator::F64Ceil
+            | Operator::F64Floor
+            | Operator::F64Trunc
+            | Operator::F64Nearest
+            | Operator::F64Sqrt
+            | Operator::F64Add
+            | Operator::F64Sub
+            | Operator::F64Mul
+            | Operator::F64Div
+            | Operator::F64Min
+            | Operator::F64Max
+            | Operator::F64Copysign
+            | Operator::I32WrapI64
+            | Operator::I64ExtendI32S
+            | Operator::I64ExtendI32U
+            | Operator::F32ConvertI32S
+            | Operator::F32ConvertI32U
+            | Operator::F32ConvertI64S
+            | Operator::F32ConvertI64U
+            | Operator::F32DemoteF64
+            | Operator::F64ConvertI32S
+            | Operator::F64ConvertI32U
+            | Operator::F64ConvertI64S
+            | Operator::F64ConvertI64U
+            | Operator::F64PromoteF32
+            | Operator::I32ReinterpretF32
+            | Operator::I64ReinterpretF64
+            | Operator::F32ReinterpretI32
+            | Operator::F64ReinterpretI64
+            | Operator::I32Extend8S
+            | Operator::I32Extend16S
+            | Operator::I64Extend8S
+            | Operator::I64Extend16S
+            | Operator::I64Extend32S
+            | Operator::I32TruncSatF32S
+            | Operator::I32TruncSatF32U
+            | Operator::I32TruncSatF64S
+            | Operator::I32TruncSatF64U
+            | Operator::I64TruncSatF32S
+            | Operator::I64TruncSatF32U
+            | Operator::I64TruncSatF64S
+            | Operator::I64TruncSatF64U
+    )
+}
@@ -3,6 +3,8 @@
 mod control_frame;
 mod control_stack;
 mod instr_encoder;
+mod loop_guard;
+mod peephole;
 mod result_mut;
 mod stack;
 mod typed_value;
@@ -19,6 +21,7 @@ use self::{
         UnreachableControlFrame,
     },
     control_stack::AcquiredTarget,
+    loop_guard::LoopGuards,
     stack::ValueStack,
     typed_value::TypedValue,
     utils::{WasmFloat, WasmInteger},
@@ -27,13 +30,16 @@ pub use self::{
     control_frame::{ControlFrame, ControlFrameKind},
     control_stack::ControlStack,
     instr_encoder::InstrEncoder,
-    stack::{FuncLocalConstsIter, TypedProvider},
+    peephole::PeepholeReport,
+    stack::TypedProvider,
+    visit_register::VisitInputRegisters,
 };
 use crate::{
     engine::{
         bytecode::SignatureIdx,
         config::FuelCosts,
-        func_builder::TranslationErrorInner,
+        func_builder::{labels::LabelRef, TranslationErrorInner},
+        func_locations::WasmOffsets,
         regmach::bytecode::{
             AnyConst32,
             Const16,
@@ -48,13 +54,13 @@ use crate::{
         Instr,
         TranslationError,
     },
-    module::{BlockType, FuncIdx, FuncTypeIdx, ModuleResources},
+    module::{BlockType, FuncIdx, FuncTypeIdx, ModuleResources, DEFAULT_MEMORY_INDEX},
     Engine,
     FuncType,
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use wasmi_core::{TrapCode, UntypedValue, ValueType};
-use wasmparser::MemArg;
+use wasmparser::{MemArg, OperatorsReader};
 
 /// Reusable allocations of a [`FuncTranslator`].
 #[derive(Debug, Default)]
@@ -69,6 +75,19 @@ pub struct FuncTranslatorAllocations {
     buffer: Vec<TypedProvider>,
     /// Buffer to temporarily store `br_table` target depths.
     br_table_targets: Vec<u32>,
+    /// The messages of traps folded at compilation time and the indices of their instructions.
+    trap_messages: Vec<(u32, Box<str>)>,
+    /// The Wasm binary offsets of the translated Wasm operators.
+    wasm_offsets: WasmOffsets,
+    /// The counted loops whose bounds checks are hoisted in front of the loop.
+    loop_guards: LoopGuards,
+    /// The peephole optimization counters of all translated functions.
+    ///
+    /// # Note
+    ///
+    /// These are not reset between function translations in order to
+    /// accumulate the counters for all functions of a Wasm module.
+    peepholes: PeepholeReport,
 }
 
 impl FuncTranslatorAllocations {
@@ -79,6 +98,14 @@ impl FuncTranslatorAllocations {
         self.control_stack.reset();
         self.buffer.clear();
         self.br_table_targets.clear();
+        self.trap_messages.clear();
+        self.wasm_offsets.reset();
+        self.loop_guards.reset();
+    }
+
+    /// Returns the [`PeepholeReport`] of all functions translated using these allocations.
+    pub fn peephole_report(&self) -> PeepholeReport {
+        self.peepholes
     }
 }
 
@@ -100,6 +127,8 @@ pub struct FuncTranslator<'parser> {
     /// Visiting the Wasm `Else` or `End` control flow operator resets
     /// reachability to `true` again.
     reachable: bool,
+    /// The Wasm binary offset of the currently translated Wasm operator.
+    pos: usize,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
@@ -135,6 +164,7 @@ impl<'parser> FuncTranslator<'parser> {
             compiled_func,
             res,
             reachable: true,
+            pos: 0,
             alloc,
         }
         .init()
@@ -143,6 +173,19 @@ impl<'parser> FuncTranslator<'parser> {
     /// Initializes a newly constructed [`FuncTranslator`].
     fn init(mut self) -> Result<Self, TranslationError> {
         self.alloc.reset();
+        let split_copies = !self.engine().config().get_fused_copies();
+        self.alloc.instr_encoder.set_split_copies(split_copies);
+        let const_dedup = self.engine().config().get_const_dedup();
+        self.alloc.stack.set_const_dedup(const_dedup);
+        let max_provider_slice_len = self.engine().config().get_max_provider_slice_len();
+        self.alloc
+            .instr_encoder
+            .set_max_provider_slice_len(max_provider_slice_len);
+        let max_labels = self.engine().config().get_max_function_labels();
+        let max_branches = self.engine().config().get_max_function_branches();
+        self.alloc
+            .instr_encoder
+            .set_label_limits(max_labels, max_branches);
         self.init_func_body_block()?;
         self.init_func_params()?;
         Ok(self)
@@ -152,7 +195,7 @@ impl<'parser> FuncTranslator<'parser> {
     fn init_func_body_block(&mut self) -> Result<(), TranslationError> {
         let func_type = self.res.get_type_of_func(self.func);
         let block_type = BlockType::func_type(func_type);
-        let end_label = self.alloc.instr_encoder.new_label();
+        let end_label = self.alloc.instr_encoder.new_label()?;
         let consume_fuel = self
             .is_fuel_metering_enabled()
             .then(|| {
@@ -211,7 +254,9 @@ impl<'parser> FuncTranslator<'parser> {
         self.alloc
             .instr_encoder
             .defrag_registers(&mut self.alloc.stack)?;
-        self.alloc.instr_encoder.update_branch_offsets()?;
+        self.alloc
+            .instr_encoder
+            .update_branch_offsets(&mut self.alloc.stack)?;
         let len_registers = self.alloc.stack.len_registers();
         let len_results = u16::try_from(self.func_type().results().len())
             .map_err(|_| TranslationError::new(TranslationErrorInner::TooManyFunctionResults))?;
@@ -219,14 +264,47 @@ impl<'parser> FuncTranslator<'parser> {
         let instrs = self.alloc.instr_encoder.drain_instrs();
         self.res.engine().init_func_2(
             self.compiled_func,
+            self.func.into_u32(),
             len_registers,
             len_results,
             func_consts,
             instrs,
         );
+        if !self.alloc.trap_messages.is_empty() {
+            self.res
+                .engine()
+                .set_trap_messages_2(self.compiled_func, self.alloc.trap_messages.drain(..));
+        }
+        if !self.alloc.wasm_offsets.is_empty() {
+            self.res
+                .engine()
+                .set_wasm_offsets_2(self.compiled_func, &mut self.alloc.wasm_offsets);
+        }
         Ok(())
     }
 
+    /// Records that the next translated Wasm operator is located at `wasm_offset` in the Wasm binary.
+    pub fn record_wasm_offset(&mut self, wasm_offset: usize) {
+        let instr = self.alloc.instr_encoder.next_instr().into_u32();
+        self.alloc.wasm_offsets.record(instr, wasm_offset);
+    }
+
+    /// Updates the Wasm binary offset of the currently translated Wasm operator.
+    pub fn update_pos(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// Analyzes the counted loops of the operators read by `reader` before their translation.
+    ///
+    /// # Note
+    ///
+    /// Read [`Config::set_hoist_loop_bounds_checks`] for more information.
+    ///
+    /// [`Config::set_hoist_loop_bounds_checks`]: crate::Config::set_hoist_loop_bounds_checks
+    pub fn analyze_loops(&mut self, reader: OperatorsReader) {
+        self.alloc.loop_guards.analyze(reader);
+    }
+
     /// Returns a shared reference to the underlying [`Engine`].
     fn engine(&self) -> &Engine {
         self.res.engine()
@@ -266,6 +344,17 @@ impl<'parser> FuncTranslator<'parser> {
         self.reachable
     }
 
+    /// Returns the height of the emulated value stack if the current code is reachable.
+    pub fn stack_height(&self) -> Option<usize> {
+        self.is_reachable().then(|| self.alloc.stack.height())
+    }
+
+    /// Pushes a value to the emulated value stack that no Wasm operator produced.
+    #[cfg(test)]
+    pub fn corrupt_stack_height(&mut self) {
+        self.alloc.stack.push_const(0_i32)
+    }
+
     /// Returns `true` if fuel metering is enabled for the [`Engine`].
     ///
     /// # Note
@@ -279,6 +368,15 @@ impl<'parser> FuncTranslator<'parser> {
         self.engine().config().get_consume_fuel()
     }
 
+    /// Returns `true` if out of bounds linear memory accesses are detected via guard pages.
+    ///
+    /// Read [`Config::set_signal_bounds_checks`] for more information.
+    ///
+    /// [`Config::set_signal_bounds_checks`]: crate::Config::set_signal_bounds_checks
+    fn uses_signal_bounds_checks(&self) -> bool {
+        self.engine().config().uses_signal_bounds_checks()
+    }
+
     /// Returns the configured [`FuelCosts`] of the [`Engine`].
     fn fuel_costs(&self) -> &FuelCosts {
         self.engine().config().fuel_costs()
@@ -717,6 +815,7 @@ impl<'parser> FuncTranslator<'parser> {
     {
         if let Ok(rhs) = rhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
+            self.alloc.peepholes.imm16_encodings += 1;
             let result = self.alloc.stack.push_dynamic()?;
             self.alloc
                 .instr_encoder
@@ -738,6 +837,7 @@ impl<'parser> FuncTranslator<'parser> {
     {
         if let Ok(lhs) = lhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
+            self.alloc.peepholes.imm16_encodings += 1;
             let result = self.alloc.stack.push_dynamic()?;
             self.alloc
                 .instr_encoder
@@ -754,6 +854,7 @@ impl<'parser> FuncTranslator<'parser> {
         rhs: TypedValue,
         consteval: fn(TypedValue, TypedValue) -> TypedValue,
     ) -> Result<(), TranslationError> {
+        self.alloc.peepholes.constant_folds += 1;
         self.alloc.stack.push_const(consteval(lhs, rhs));
         Ok(())
     }
@@ -804,12 +905,27 @@ impl<'parser> FuncTranslator<'parser> {
         Ok(())
     }
 
-    /// Translates a [`TrapCode`] as [`Instruction`].
-    fn translate_trap(&mut self, trap_code: TrapCode) -> Result<(), TranslationError> {
+    /// Translates a [`TrapCode`] folded at compilation time as [`Instruction`].
+    ///
+    /// The `message` describing the original operation and its operands is recorded
+    /// for the trap if enabled via [`Config::set_folded_trap_messages`].
+    ///
+    /// [`Config::set_folded_trap_messages`]: crate::Config::set_folded_trap_messages
+    fn translate_trap(
+        &mut self,
+        trap_code: TrapCode,
+        message: impl FnOnce() -> String,
+    ) -> Result<(), TranslationError> {
         bail_unreachable!(self);
-        self.alloc
+        let instr = self
+            .alloc
             .instr_encoder
-            .push_instr(Instruction::Trap(trap_code))?;
+            .push_instr(Instruction::trap(trap_code))?;
+        if self.engine().config().get_folded_trap_messages() {
+            self.alloc
+                .trap_messages
+                .push((instr.into_u32(), message().into_boxed_str()));
+        }
         self.reachable = false;
         Ok(())
     }
@@ -866,6 +982,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 self.push_binary_instr(lhs, rhs, make_instr)
@@ -873,6 +990,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Const(rhs)) => {
                 if make_instr_reg_imm_opt(self, lhs, T::from(rhs))? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if self.try_push_binary_instr_imm16(lhs, T::from(rhs), make_instr_imm16)? {
@@ -884,6 +1002,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Const(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_imm_reg_opt(self, T::from(lhs), rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if self.try_push_binary_instr_imm16_rev(T::from(lhs), rhs, make_instr_imm16_rev)? {
@@ -948,6 +1067,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 self.push_binary_instr(lhs, rhs, make_instr)
@@ -955,10 +1075,12 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Const(rhs)) => {
                 if make_instr_reg_imm_opt(self, lhs, T::from(rhs))? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if T::from(rhs).is_nan() {
                     // Optimization: non-canonicalized NaN propagation.
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_const(rhs);
                     return Ok(());
                 }
@@ -967,10 +1089,12 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Const(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_imm_reg_opt(self, T::from(lhs), rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if T::from(lhs).is_nan() {
                     // Optimization: non-canonicalized NaN propagation.
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_const(lhs);
                     return Ok(());
                 }
@@ -1002,6 +1126,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if lhs == rhs {
                     // Optimization: `copysign x x` is always just `x`
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_register(lhs)?;
                     return Ok(());
                 }
@@ -1064,6 +1189,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 self.push_binary_instr(lhs, rhs, make_instr)
@@ -1072,6 +1198,7 @@ impl<'parser> FuncTranslator<'parser> {
             | (TypedProvider::Const(imm_in), TypedProvider::Register(reg_in)) => {
                 if make_instr_imm_opt(self, reg_in, T::from(imm_in))? {
                     // Custom logic applied its optimization: return early.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if self.try_push_binary_instr_imm16(reg_in, T::from(imm_in), make_instr_imm16)? {
@@ -1125,6 +1252,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
                     // Case: the custom logic applied its optimization and we can return.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 self.push_binary_instr(lhs, rhs, make_instr)
@@ -1133,10 +1261,12 @@ impl<'parser> FuncTranslator<'parser> {
             | (TypedProvider::Const(imm_in), TypedProvider::Register(reg_in)) => {
                 if make_instr_imm_opt(self, reg_in, T::from(imm_in))? {
                     // Custom logic applied its optimization: return early.
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if T::from(imm_in).is_nan() {
                     // Optimization: non-canonicalized NaN propagation.
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_const(T::from(imm_in));
                     return Ok(());
                 }
@@ -1190,6 +1320,7 @@ impl<'parser> FuncTranslator<'parser> {
                 let rhs = T::from(rhs).as_shift_amount();
                 if rhs == 0 {
                     // Optimization: Shifting or rotating by zero bits is a no-op.
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_register(lhs)?;
                     return Ok(());
                 }
@@ -1204,10 +1335,12 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Const(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_imm_reg_opt(self, T::from(lhs), rhs)? {
                     // Custom optimization was applied: return early
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if T::from(lhs).eq_zero() {
                     // Optimization: Shifting or rotating a zero value is a no-op.
+                    self.alloc.peepholes.simplifications += 1;
                     self.alloc.stack.push_const(lhs);
                     return Ok(());
                 }
@@ -1239,9 +1372,12 @@ impl<'parser> FuncTranslator<'parser> {
     /// Used for translating the following Wasm operators to `wasmi` bytecode:
     ///
     /// - `{i32, i64}.{div_u, div_s, rem_u, rem_s}`
+    ///
+    /// The `name` of the Wasm operator is used to describe folded traps.
     #[allow(clippy::too_many_arguments)]
     pub fn translate_divrem<T>(
         &mut self,
+        name: &'static str,
         make_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
         make_instr_imm16: fn(result: Register, lhs: Register, rhs: Const16<T>) -> Instruction,
         make_instr_imm16_rev: fn(result: Register, lhs: Const16<T>, rhs: Register) -> Instruction,
@@ -1265,6 +1401,7 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                 if make_instr_opt(self, lhs, rhs)? {
                     // Custom optimization was applied: return early
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 self.push_binary_instr(lhs, rhs, make_instr)
@@ -1272,11 +1409,15 @@ impl<'parser> FuncTranslator<'parser> {
             (TypedProvider::Register(lhs), TypedProvider::Const(rhs)) => {
                 if T::from(rhs).eq_zero() {
                     // Optimization: division by zero always traps
-                    self.translate_trap(TrapCode::IntegerDivisionByZero)?;
+                    self.alloc.peepholes.simplifications += 1;
+                    self.translate_trap(TrapCode::IntegerDivisionByZero, || {
+                        format!("{name} with divisor {rhs}")
+                    })?;
                     return Ok(());
                 }
                 if make_instr_reg_imm_opt(self, lhs, T::from(rhs))? {
                     // Custom optimization was applied: return early
+                    self.alloc.peepholes.simplifications += 1;
                     return Ok(());
                 }
                 if self.try_push_binary_instr_imm16(lhs, T::from(rhs), make_instr_imm16)? {
@@ -1292,13 +1433,18 @@ impl<'parser> FuncTranslator<'parser> {
                 }
                 self.push_binary_instr_imm_rev(lhs, rhs, make_instr)
             }
-            (TypedProvider::Const(lhs), TypedProvider::Const(rhs)) => match consteval(lhs, rhs) {
-                Ok(result) => {
-                    self.alloc.stack.push_const(result);
-                    Ok(())
+            (TypedProvider::Const(lhs), TypedProvider::Const(rhs)) => {
+                self.alloc.peepholes.constant_folds += 1;
+                match consteval(lhs, rhs) {
+                    Ok(result) => {
+                        self.alloc.stack.push_const(result);
+                        Ok(())
+                    }
+                    Err(trap_code) => self.translate_trap(trap_code, || {
+                        format!("{name} with operands {lhs} and {rhs}")
+                    }),
                 }
-                Err(trap_code) => self.translate_trap(trap_code),
-            },
+            }
         }
     }
 
@@ -1311,6 +1457,87 @@ impl<'parser> FuncTranslator<'parser> {
         Ok(false)
     }
 
+    /// Tries to fuse an `i32.add` with the `i32.load` that directly precedes it.
+    ///
+    /// # Note
+    ///
+    /// Fusion only applies if both operands are registers and one of them is the
+    /// dynamically allocated result of the last encoded `i32.load` so that no other
+    /// instruction can observe the loaded value. Since the load is still performed
+    /// before the addition the trap behavior of the fused instruction remains unchanged.
+    ///
+    /// Returns `true` if the fused [`Instruction::I32AddFromMem`] has been encoded.
+    /// Otherwise the operands of the `i32.add` remain on the [`ValueStack`].
+    fn try_fuse_i32_add_from_mem(&mut self) -> Result<bool, TranslationError> {
+        self.alloc.stack.peek_n(2, &mut self.alloc.buffer);
+        let (lhs, rhs) = match self.alloc.buffer[..] {
+            [TypedProvider::Register(lhs), TypedProvider::Register(rhs)] => (lhs, rhs),
+            _ => return Ok(false),
+        };
+        for (loaded, other) in [(lhs, rhs), (rhs, lhs)] {
+            if !self.alloc.stack.is_dynamic(loaded) {
+                continue;
+            }
+            if let Some((ptr, offset)) = self.alloc.instr_encoder.try_take_last_i32_load(loaded) {
+                self.alloc.peepholes.fusions += 1;
+                self.alloc.stack.pop2();
+                let result = self.alloc.stack.push_dynamic()?;
+                self.alloc
+                    .instr_encoder
+                    .push_instr(Instruction::i32_add_from_mem(result, ptr, other))?;
+                self.alloc
+                    .instr_encoder
+                    .append_instr(Instruction::const32(offset))?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Tries to fuse a conditional branch on `condition` with the `i32.eqz` that directly precedes it.
+    ///
+    /// # Note
+    ///
+    /// Fusion only applies if `condition` is the dynamically allocated result of the last
+    /// encoded `i32.eqz` so that no other instruction can observe the `i32.eqz` result.
+    ///
+    /// Returns the input [`Register`] of the removed `i32.eqz` if fusion took place.
+    /// The caller then has to encode the conditional branch with inverted condition on it.
+    fn try_fuse_branch_eqz(&mut self, condition: Register) -> Option<Register> {
+        if !self.alloc.stack.is_dynamic(condition) {
+            return None;
+        }
+        let input = self.alloc.instr_encoder.try_take_last_i32_eqz(condition)?;
+        self.alloc.peepholes.fusions += 1;
+        Some(input)
+    }
+
+    /// Tries to fuse a `br_if` on `condition` to `label` with the `i32` comparison that directly precedes it.
+    ///
+    /// # Note
+    ///
+    /// Fusion only applies if `condition` is the dynamically allocated result of the last
+    /// encoded `i32` comparison so that no other instruction can observe the comparison result.
+    ///
+    /// Returns `true` if the fused compare and branch [`Instruction`] has been encoded.
+    fn try_fuse_branch_cmp(
+        &mut self,
+        condition: Register,
+        label: LabelRef,
+    ) -> Result<bool, TranslationError> {
+        if !self.alloc.stack.is_dynamic(condition) {
+            return Ok(false);
+        }
+        let fused = self
+            .alloc
+            .instr_encoder
+            .try_fuse_branch_cmp(condition, label)?;
+        if fused {
+            self.alloc.peepholes.fusions += 1;
+        }
+        Ok(fused)
+    }
+
     /// Translates a unary Wasm instruction to `wasmi` bytecode.
     pub fn translate_unary(
         &mut self,
@@ -1327,6 +1554,7 @@ impl<'parser> FuncTranslator<'parser> {
                 Ok(())
             }
             TypedProvider::Const(input) => {
+                self.alloc.peepholes.constant_folds += 1;
                 self.alloc.stack.push_const(consteval(input));
                 Ok(())
             }
@@ -1334,8 +1562,11 @@ impl<'parser> FuncTranslator<'parser> {
     }
 
     /// Translates a fallible unary Wasm instruction to `wasmi` bytecode.
+    ///
+    /// The `name` of the Wasm operator is used to describe folded traps.
     pub fn translate_unary_fallible(
         &mut self,
+        name: &'static str,
         make_instr: fn(result: Register, input: Register) -> Instruction,
         consteval: fn(input: TypedValue) -> Result<TypedValue, TrapCode>,
     ) -> Result<(), TranslationError> {
@@ -1348,13 +1579,18 @@ impl<'parser> FuncTranslator<'parser> {
                     .push_instr(make_instr(result, input))?;
                 Ok(())
             }
-            TypedProvider::Const(input) => match consteval(input) {
-                Ok(result) => {
-                    self.alloc.stack.push_const(result);
-                    Ok(())
+            TypedProvider::Const(input) => {
+                self.alloc.peepholes.constant_folds += 1;
+                match consteval(input) {
+                    Ok(result) => {
+                        self.alloc.stack.push_const(result);
+                        Ok(())
+                    }
+                    Err(trap_code) => {
+                        self.translate_trap(trap_code, || format!("{name} with operand {input}"))
+                    }
                 }
-                Err(trap_code) => self.translate_trap(trap_code),
-            },
+            }
         }
     }
 
@@ -1372,6 +1608,21 @@ impl<'parser> FuncTranslator<'parser> {
         })
     }
 
+    /// Encodes an [`Instruction::UseMemory`] prefix for a memory instruction accessing `memory`.
+    ///
+    /// # Note
+    ///
+    /// Nothing is encoded for the default linear memory so that memory instructions
+    /// accessing the default linear memory are executed without overhead.
+    fn encode_use_memory(&mut self, memory: u32) -> Result<(), TranslationError> {
+        if memory != DEFAULT_MEMORY_INDEX {
+            self.alloc
+                .instr_encoder
+                .push_instr(Instruction::use_memory(memory))?;
+        }
+        Ok(())
+    }
+
     /// Calculates the effective address `ptr+offset` and calls `f(address)` if valid.
     ///
     /// Encodes a [`TrapCode::MemoryOutOfBounds`] trap instruction if the effective address is invalid.
@@ -1383,7 +1634,12 @@ impl<'parser> FuncTranslator<'parser> {
     ) -> Result<(), TranslationError> {
         match u32::from(ptr).checked_add(offset) {
             Some(address) => f(self, address),
-            None => self.translate_trap(TrapCode::MemoryOutOfBounds),
+            None => self.translate_trap(TrapCode::MemoryOutOfBounds, || {
+                format!(
+                    "effective address of pointer {} with offset {offset}",
+                    u32::from(ptr)
+                )
+            }),
         }
     }
 
@@ -1393,6 +1649,7 @@ impl<'parser> FuncTranslator<'parser> {
     ///
     /// This chooses the right encoding for the given `load` instruction.
     /// If `ptr+offset` is a constant value the address is pre-calculated.
+    /// If the load is guarded by an [`Instruction::LoopBoundsGuard`] its bounds are not checked.
     ///
     /// # Usage
     ///
@@ -1411,11 +1668,25 @@ impl<'parser> FuncTranslator<'parser> {
             offset: Const16<u32>,
         ) -> Instruction,
         make_instr_at: fn(result: Register, address: Const32<u32>) -> Instruction,
+        make_instr_unchecked: fn(result: Register, ptr: Register) -> Instruction,
     ) -> Result<(), TranslationError> {
         bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
         let offset = Self::memarg_offset(memarg);
         match self.alloc.stack.pop() {
             TypedProvider::Register(ptr) => {
+                if self.is_guarded_load(ptr) || self.uses_signal_bounds_checks() {
+                    // Optimization: the bounds of the load have been checked in front of its `loop`
+                    //               or out of bounds loads are detected via guard pages.
+                    let result = self.alloc.stack.push_dynamic()?;
+                    self.alloc
+                        .instr_encoder
+                        .push_instr(make_instr_unchecked(result, ptr))?;
+                    self.alloc
+                        .instr_encoder
+                        .append_instr(Instruction::const32(offset))?;
+                    return Ok(());
+                }
                 if let Some(offset) = <Const16<u32>>::from_u32(offset) {
                     let result = self.alloc.stack.push_dynamic()?;
                     self.alloc
@@ -1444,6 +1715,14 @@ impl<'parser> FuncTranslator<'parser> {
         }
     }
 
+    /// Returns `true` if the load from `ptr` at the current position is guarded by a [`Instruction::LoopBoundsGuard`].
+    fn is_guarded_load(&self, ptr: Register) -> bool {
+        match self.alloc.loop_guards.guarded_load(self.pos) {
+            Some(local) => matches!(Register::try_from(local), Ok(local) if local == ptr),
+            None => false,
+        }
+    }
+
     /// Translates Wasm integer `store` and `storeN` instructions to `wasmi` bytecode.
     ///
     /// # Note
@@ -1456,6 +1735,7 @@ impl<'parser> FuncTranslator<'parser> {
     /// Used for translating the following Wasm operators to `wasmi` bytecode:
     ///
     /// - `{i32, i64}.{store, store8, store16, store32}`
+    #[allow(clippy::too_many_arguments)]
     fn translate_istore<T, U>(
         &mut self,
         memarg: MemArg,
@@ -1464,14 +1744,20 @@ impl<'parser> FuncTranslator<'parser> {
         make_instr_offset16_imm: fn(ptr: Register, offset: u16, value: U) -> Instruction,
         make_instr_at: fn(address: Const32<u32>, value: Register) -> Instruction,
         make_instr_at_imm: fn(address: Const32<u32>, value: U) -> Instruction,
+        make_instr_unchecked: fn(ptr: Register, offset: Const32<u32>) -> Instruction,
     ) -> Result<(), TranslationError>
     where
         T: Copy + From<TypedValue>,
         U: TryFrom<T>,
     {
         bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
         let offset = Self::memarg_offset(memarg);
-        match self.alloc.stack.pop2() {
+        let (ptr, value) = self.alloc.stack.pop2();
+        if self.try_translate_store_unchecked(ptr, offset, value, make_instr_unchecked)? {
+            return Ok(());
+        }
+        match (ptr, value) {
             (TypedProvider::Register(ptr), TypedProvider::Register(value)) => {
                 if let Ok(offset) = u16::try_from(offset) {
                     self.alloc
@@ -1562,10 +1848,16 @@ impl<'parser> FuncTranslator<'parser> {
         make_instr: fn(ptr: Register, offset: Const32<u32>) -> Instruction,
         make_instr_offset16: fn(ptr: Register, offset: u16, value: Register) -> Instruction,
         make_instr_at: fn(address: Const32<u32>, value: Register) -> Instruction,
+        make_instr_unchecked: fn(ptr: Register, offset: Const32<u32>) -> Instruction,
     ) -> Result<(), TranslationError> {
         bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
         let offset = Self::memarg_offset(memarg);
-        match self.alloc.stack.pop2() {
+        let (ptr, value) = self.alloc.stack.pop2();
+        if self.try_translate_store_unchecked(ptr, offset, value, make_instr_unchecked)? {
+            return Ok(());
+        }
+        match (ptr, value) {
             (TypedProvider::Register(ptr), TypedProvider::Register(value)) => {
                 if let Ok(offset) = u16::try_from(offset) {
                     self.alloc
@@ -1624,6 +1916,202 @@ impl<'parser> FuncTranslator<'parser> {
         }
     }
 
+    /// Translates a Wasm `store` instruction to its unchecked `wasmi` bytecode if possible.
+    ///
+    /// Returns `true` if the unchecked `store` has been encoded.
+    ///
+    /// # Note
+    ///
+    /// This is only possible for dynamic `ptr` values and if out of bounds stores are
+    /// detected via guard pages. Read [`Config::set_signal_bounds_checks`] for more information.
+    ///
+    /// [`Config::set_signal_bounds_checks`]: crate::Config::set_signal_bounds_checks
+    fn try_translate_store_unchecked(
+        &mut self,
+        ptr: TypedProvider,
+        offset: u32,
+        value: TypedProvider,
+        make_instr_unchecked: fn(ptr: Register, offset: Const32<u32>) -> Instruction,
+    ) -> Result<bool, TranslationError> {
+        let ptr = match ptr {
+            TypedProvider::Register(ptr) if self.uses_signal_bounds_checks() => ptr,
+            _ => return Ok(false),
+        };
+        let value = match value {
+            TypedProvider::Register(value) => value,
+            TypedProvider::Const(value) => self.alloc.stack.alloc_const(value)?,
+        };
+        self.alloc
+            .instr_encoder
+            .push_instr(make_instr_unchecked(ptr, Const32::from(offset)))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::Register(value))?;
+        Ok(true)
+    }
+
+    /// Pops the two registers of the top-most `v128` value from the emulated value stack.
+    ///
+    /// Returns the register that holds the lower 64 bits of the `v128` value.
+    ///
+    /// # Panics
+    ///
+    /// If the top-most two providers are not adjacent registers which is
+    /// guaranteed for all `v128` values produced by the supported operators.
+    fn pop_v128(&mut self) -> Register {
+        let hi = self.alloc.stack.pop();
+        let lo = self.alloc.stack.pop();
+        match (lo, hi) {
+            (TypedProvider::Register(lo), TypedProvider::Register(hi)) if lo.next() == hi => lo,
+            (lo, hi) => panic!("expected adjacent v128 registers but found: {lo:?} and {hi:?}"),
+        }
+    }
+
+    /// Translates a Wasm `v128.load` instruction to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// The loaded `v128` value occupies two adjacent dynamic registers.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    pub fn translate_v128_load(&mut self, memarg: MemArg) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
+        let offset = Self::memarg_offset(memarg);
+        let ptr = match self.alloc.stack.pop() {
+            TypedProvider::Register(ptr) => ptr,
+            TypedProvider::Const(ptr) => self.alloc.stack.alloc_const(ptr)?,
+        };
+        let result = self.alloc.stack.push_dynamic_n(2)?.head();
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::v128_load(result, ptr))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
+    /// Translates a Wasm `v128.store` instruction to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    pub fn translate_v128_store(&mut self, memarg: MemArg) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
+        let offset = Self::memarg_offset(memarg);
+        let value = self.pop_v128();
+        let ptr = match self.alloc.stack.pop() {
+            TypedProvider::Register(ptr) => ptr,
+            TypedProvider::Const(ptr) => self.alloc.stack.alloc_const(ptr)?,
+        };
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::v128_store(ptr, Const32::from(offset)))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::Register(value))?;
+        Ok(())
+    }
+
+    /// Translates a Wasm `i32x4.splat` or `f32x4.splat` instruction to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// Both operators broadcast the 32-bit pattern of their input to all four lanes.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    pub fn translate_x4_splat(&mut self) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        let input = match self.alloc.stack.pop() {
+            TypedProvider::Register(input) => input,
+            TypedProvider::Const(input) => self.alloc.stack.alloc_const(input)?,
+        };
+        let result = self.alloc.stack.push_dynamic_n(2)?.head();
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::i32x4_splat(result, input))?;
+        Ok(())
+    }
+
+    /// Translates a binary Wasm `simd` instruction on two `v128` values to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// The `v128` result occupies two adjacent dynamic registers.
+    /// Read [`Instruction::V128Load`] for the register layout of `v128` values.
+    pub fn translate_v128_binary(
+        &mut self,
+        make_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
+    ) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        let rhs = self.pop_v128();
+        let lhs = self.pop_v128();
+        let result = self.alloc.stack.push_dynamic_n(2)?.head();
+        self.alloc
+            .instr_encoder
+            .push_instr(make_instr(result, lhs, rhs))?;
+        Ok(())
+    }
+
+    /// Translates a Wasm `memory.atomic.notify` instruction to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// The number of waiters to notify is dropped since there never are any waiters.
+    pub fn translate_memory_atomic_notify(
+        &mut self,
+        memarg: MemArg,
+    ) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
+        let offset = Self::memarg_offset(memarg);
+        self.alloc.stack.pop();
+        let ptr = match self.alloc.stack.pop() {
+            TypedProvider::Register(ptr) => ptr,
+            TypedProvider::Const(ptr) => self.alloc.stack.alloc_const(ptr)?,
+        };
+        let result = self.alloc.stack.push_dynamic()?;
+        self.alloc
+            .instr_encoder
+            .push_instr(Instruction::memory_atomic_notify(result, ptr))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
+    /// Translates a Wasm `memory.atomic.wait32` or `memory.atomic.wait64` instruction to `wasmi` bytecode.
+    ///
+    /// # Note
+    ///
+    /// The timeout is dropped since nothing could ever notify the waiter before it times out.
+    pub fn translate_memory_atomic_wait(
+        &mut self,
+        memarg: MemArg,
+        make_instr: fn(result: Register, ptr: Register, expected: Register) -> Instruction,
+    ) -> Result<(), TranslationError> {
+        bail_unreachable!(self);
+        self.encode_use_memory(memarg.memory)?;
+        let offset = Self::memarg_offset(memarg);
+        let (ptr, expected, _timeout) = self.alloc.stack.pop3();
+        let ptr = match ptr {
+            TypedProvider::Register(ptr) => ptr,
+            TypedProvider::Const(ptr) => self.alloc.stack.alloc_const(ptr)?,
+        };
+        let expected = match expected {
+            TypedProvider::Register(expected) => expected,
+            TypedProvider::Const(expected) => self.alloc.stack.alloc_const(expected)?,
+        };
+        let result = self.alloc.stack.push_dynamic()?;
+        self.alloc
+            .instr_encoder
+            .push_instr(make_instr(result, ptr, expected))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
     /// Translates a Wasm `select` or `select <ty>` instruction.
     ///
     /// # Note
@@ -1632,6 +2120,10 @@ impl<'parser> FuncTranslator<'parser> {
     /// - If both `lhs` and `rhs` are equal registers or constant values `lhs` is forwarded.
     /// - Properly chooses the correct `select` instruction encoding and optimizes for
     ///   cases with 32-bit constant values.
+    /// - The `result` reuses the register of a dead operand if possible since `select`
+    ///   reads all of its operands before writing its `result`. This is the register of
+    ///   the `condition` unless `lhs` or `rhs` occupy a lower dynamically allocated register
+    ///   since dynamically allocated registers are freed and reused in stack order.
     fn translate_select(&mut self, type_hint: Option<ValueType>) -> Result<(), TranslationError> {
         /// Convenience function to encode a `select` instruction.
         ///
@@ -1729,6 +2221,7 @@ impl<'parser> FuncTranslator<'parser> {
 
         bail_unreachable!(self);
         let (lhs, rhs, condition) = self.alloc.stack.pop3();
+        let operands = [lhs, rhs, condition];
         match condition {
             TypedProvider::Const(condition) => match (bool::from(condition), lhs, rhs) {
                 // # Optimization
@@ -1755,7 +2248,7 @@ impl<'parser> FuncTranslator<'parser> {
                         self.alloc.stack.push_register(lhs)?;
                         return Ok(());
                     }
-                    let result = self.alloc.stack.push_dynamic()?;
+                    let result = self.alloc.stack.push_dynamic_or_reuse(&operands)?;
                     self.alloc
                         .instr_encoder
                         .push_instr(Instruction::select(result, condition, lhs))?;
@@ -1768,7 +2261,7 @@ impl<'parser> FuncTranslator<'parser> {
                     if let Some(type_hint) = type_hint {
                         debug_assert_eq!(rhs.ty(), type_hint);
                     }
-                    let result = self.alloc.stack.push_dynamic()?;
+                    let result = self.alloc.stack.push_dynamic_or_reuse(&operands)?;
                     match rhs.ty() {
                         ValueType::I32 => encode_select_imm32(
                             self,
@@ -1818,7 +2311,7 @@ impl<'parser> FuncTranslator<'parser> {
                     if let Some(type_hint) = type_hint {
                         debug_assert_eq!(lhs.ty(), type_hint);
                     }
-                    let result = self.alloc.stack.push_dynamic()?;
+                    let result = self.alloc.stack.push_dynamic_or_reuse(&operands)?;
                     match lhs.ty() {
                         ValueType::I32 => encode_select_imm32(
                             self,
@@ -1980,7 +2473,7 @@ impl<'parser> FuncTranslator<'parser> {
                         self.alloc.stack.push_const(lhs);
                         return Ok(());
                     }
-                    let result = self.alloc.stack.push_dynamic()?;
+                    let result = self.alloc.stack.push_dynamic_or_reuse(&operands)?;
                     match lhs.ty() {
                         ValueType::I32 => {
                             encode_select_imm32(
@@ -0,0 +1,61 @@
+/// Counts how often the peephole optimizations of the register-machine translator applied.
+///
+/// # Note
+///
+/// - The counters are accumulated over all functions translated for a [`Module`].
+/// - They are only collected at compilation time and do not influence execution.
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PeepholeReport {
+    /// Operators evaluated at compilation time since all their inputs are constant.
+    pub(super) constant_folds: u32,
+    /// Operators elided since their result is known from a constant or repeated operand.
+    pub(super) simplifications: u32,
+    /// Operators encoded with a compact 16-bit immediate operand.
+    pub(super) imm16_encodings: u32,
+    /// `copy` instructions avoided or removed for `local.set` and `local.tee`.
+    pub(super) copy_eliminations: u32,
+    /// Instruction sequences replaced by a single fused instruction.
+    pub(super) fusions: u32,
+}
+
+impl PeepholeReport {
+    /// Adds the counters of `other` to the counters of `self`.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.constant_folds += other.constant_folds;
+        self.simplifications += other.simplifications;
+        self.imm16_encodings += other.imm16_encodings;
+        self.copy_eliminations += other.copy_eliminations;
+        self.fusions += other.fusions;
+    }
+
+    /// Returns the number of operators evaluated at compilation time.
+    ///
+    /// This also includes constant operations that are known to trap such as `i32.div_s 1 0`.
+    pub fn constant_folds(&self) -> u32 {
+        self.constant_folds
+    }
+
+    /// Returns the number of operators elided due to a constant or repeated operand.
+    ///
+    /// Examples are `x + 0`, `x * 1`, `x - x` or `f32.add x NaN`.
+    pub fn simplifications(&self) -> u32 {
+        self.simplifications
+    }
+
+    /// Returns the number of operators encoded with a compact 16-bit immediate operand.
+    pub fn imm16_encodings(&self) -> u32 {
+        self.imm16_encodings
+    }
+
+    /// Returns the number of `copy` instructions avoided or removed for `local.set` and `local.tee`.
+    pub fn copy_eliminations(&self) -> u32 {
+        self.copy_eliminations
+    }
+
+    /// Returns the number of instruction sequences replaced by a single fused instruction.
+    pub fn fusions(&self) -> u32 {
+        self.fusions
+    }
+}
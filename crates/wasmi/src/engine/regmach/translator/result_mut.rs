@@ -47,6 +47,7 @@ impl Instruction {
             Instruction::Register2(_) |
             Instruction::Register3(_) |
             Instruction::RegisterList(_) |
+            Instruction::RegisterSpan(_) |
             Instruction::Trap(_) |
             Instruction::ConsumeFuel(_) |
             Instruction::Return |
@@ -69,6 +70,27 @@ impl Instruction {
             Instruction::Branch { .. } |
             Instruction::BranchEqz { .. } |
             Instruction::BranchNez { .. } |
+            Instruction::BranchI32Eq(_) |
+            Instruction::BranchI32EqImm(_) |
+            Instruction::BranchI32Ne(_) |
+            Instruction::BranchI32NeImm(_) |
+            Instruction::BranchI32LtS(_) |
+            Instruction::BranchI32LtSImm(_) |
+            Instruction::BranchI32LtU(_) |
+            Instruction::BranchI32LtUImm(_) |
+            Instruction::BranchI32LeS(_) |
+            Instruction::BranchI32LeSImm(_) |
+            Instruction::BranchI32LeU(_) |
+            Instruction::BranchI32LeUImm(_) |
+            Instruction::BranchI32GtS(_) |
+            Instruction::BranchI32GtSImm(_) |
+            Instruction::BranchI32GtU(_) |
+            Instruction::BranchI32GtUImm(_) |
+            Instruction::BranchI32GeS(_) |
+            Instruction::BranchI32GeSImm(_) |
+            Instruction::BranchI32GeU(_) |
+            Instruction::BranchI32GeUImm(_) |
+            Instruction::BranchCmpFallback { .. } |
             Instruction::BranchTable { .. } => None,
             Instruction::Copy { result, .. } |
             Instruction::CopyImm32 { result, .. } |
@@ -133,6 +155,7 @@ impl Instruction {
             Instruction::TableGrowImm { result, .. } => Some(result),
             Instruction::ElemDrop(_) => None,
             Instruction::DataDrop(_) => None,
+            Instruction::UseMemory(_) => None,
             Instruction::MemorySize { result } |
             Instruction::MemoryGrow { result, .. } |
             Instruction::MemoryGrowBy { result, .. } => Some(result),
@@ -206,6 +229,30 @@ impl Instruction {
             Instruction::I64Load32u(instr) => instr.result_mut(),
             Instruction::I64Load32uAt(instr) => instr.result_mut(),
             Instruction::I64Load32uOffset16(instr) => instr.result_mut(),
+            Instruction::LoopBoundsGuard { .. } => None,
+            Instruction::I32LoadUnchecked(instr) => instr.result_mut(),
+            Instruction::I64LoadUnchecked(instr) => instr.result_mut(),
+            Instruction::F32LoadUnchecked(instr) => instr.result_mut(),
+            Instruction::F64LoadUnchecked(instr) => instr.result_mut(),
+            Instruction::I32Load8sUnchecked(instr) => instr.result_mut(),
+            Instruction::I32Load8uUnchecked(instr) => instr.result_mut(),
+            Instruction::I32Load16sUnchecked(instr) => instr.result_mut(),
+            Instruction::I32Load16uUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load8sUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load8uUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load16sUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load16uUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load32sUnchecked(instr) => instr.result_mut(),
+            Instruction::I64Load32uUnchecked(instr) => instr.result_mut(),
+            Instruction::I32StoreUnchecked(_) |
+            Instruction::I32Store8Unchecked(_) |
+            Instruction::I32Store16Unchecked(_) |
+            Instruction::I64StoreUnchecked(_) |
+            Instruction::I64Store8Unchecked(_) |
+            Instruction::I64Store16Unchecked(_) |
+            Instruction::I64Store32Unchecked(_) |
+            Instruction::F32StoreUnchecked(_) |
+            Instruction::F64StoreUnchecked(_) |
             Instruction::I32Store(_) |
             Instruction::I32StoreOffset16(_) |
             Instruction::I32StoreOffset16Imm16(_) |
@@ -299,6 +346,8 @@ impl Instruction {
             Instruction::F64Gt(instr) |
             Instruction::F32Ge(instr) |
             Instruction::F64Ge(instr) => instr.result_mut(),
+            Instruction::I32Eqz(instr) |
+            Instruction::I64Eqz(instr) |
             Instruction::I32Clz(instr) |
             Instruction::I64Clz(instr) |
             Instruction::I32Ctz(instr) |
@@ -306,6 +355,7 @@ impl Instruction {
             Instruction::I32Popcnt(instr) |
             Instruction::I64Popcnt(instr) => instr.result_mut(),
             Instruction::I32Add(instr) |
+            Instruction::I32AddFromMem(instr) |
             Instruction::I64Add(instr) => instr.result_mut(),
             Instruction::I32AddImm16(instr) => instr.result_mut(),
             Instruction::I64AddImm16(instr) => instr.result_mut(),
@@ -449,6 +499,19 @@ impl Instruction {
             Instruction::F64ConvertI32U(instr) |
             Instruction::F64ConvertI64S(instr) |
             Instruction::F64ConvertI64U(instr) => instr.result_mut(),
+            // Note: `v128` results span two registers and thus cannot be replaced.
+            Instruction::V128Load(_) |
+            Instruction::V128Store(_) |
+            Instruction::I32x4Splat(_) |
+            Instruction::I32x4MinS(_) |
+            Instruction::I32x4MinU(_) |
+            Instruction::I32x4MaxS(_) |
+            Instruction::I32x4MaxU(_) |
+            Instruction::F32x4Min(_) |
+            Instruction::F32x4Max(_) => None,
+            Instruction::MemoryAtomicNotify(instr) => instr.result_mut(),
+            Instruction::MemoryAtomicWait32 { result, .. } |
+            Instruction::MemoryAtomicWait64 { result, .. } => Some(result),
         }
     }
 }
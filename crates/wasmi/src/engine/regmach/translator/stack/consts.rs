@@ -1,12 +1,14 @@
 use super::Register;
 use crate::{
     core::UntypedValue,
-    engine::{func_builder::TranslationErrorInner, TranslationError},
-};
-use alloc::{
-    collections::{btree_map, BTreeMap},
-    vec::Vec,
+    engine::{
+        const_pool::ConstDedupMap,
+        func_builder::TranslationErrorInner,
+        ConstDedup,
+        TranslationError,
+    },
 };
+use alloc::vec::Vec;
 use core::{iter::Rev, slice::Iter as SliceIter};
 
 /// A pool of deduplicated function local constant values.
@@ -20,7 +22,7 @@ use core::{iter::Rev, slice::Iter as SliceIter};
 #[derive(Debug, Default)]
 pub struct FuncLocalConsts {
     /// Mapping from constant [`UntypedValue`] values to [`Register`] indices.
-    const2idx: BTreeMap<UntypedValue, Register>,
+    const2idx: ConstDedupMap<Register>,
     /// Mapping from [`Register`] indices to constant [`UntypedValue`] values.
     idx2const: Vec<UntypedValue>,
     /// The [`Register`] index for the next allocated function local constant value.
@@ -35,6 +37,18 @@ impl FuncLocalConsts {
         self.next_idx = Self::first_index();
     }
 
+    /// Sets the [`ConstDedup`] data structure used to deduplicate constant values.
+    ///
+    /// # Note
+    ///
+    /// This must only be called while the [`FuncLocalConsts`] are empty.
+    pub fn set_dedup(&mut self, dedup: ConstDedup) {
+        debug_assert!(self.idx2const.is_empty());
+        if self.const2idx.dedup() != dedup {
+            self.const2idx = ConstDedupMap::new(dedup);
+        }
+    }
+
     /// The maximum index for [`Register`] referring to function local constant values.
     ///
     /// # Note
@@ -77,16 +91,14 @@ impl FuncLocalConsts {
                 TranslationErrorInner::TooManyFuncLocalConstValues,
             ));
         }
-        match self.const2idx.entry(value) {
-            btree_map::Entry::Occupied(entry) => Ok(*entry.get()),
-            btree_map::Entry::Vacant(entry) => {
-                let register = Register::from_i16(self.next_idx);
-                self.next_idx -= 1;
-                entry.insert(register);
-                self.idx2const.push(value);
-                Ok(register)
-            }
-        }
+        let next_idx = &mut self.next_idx;
+        let idx2const = &mut self.idx2const;
+        self.const2idx.get_or_try_insert_with(value, || {
+            let register = Register::from_i16(*next_idx);
+            *next_idx -= 1;
+            idx2const.push(value);
+            Ok(register)
+        })
     }
 
     /// Returns the function local constant [`UntypedValue`] of the [`Register`] if any.
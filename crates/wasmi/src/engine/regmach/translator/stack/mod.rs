@@ -13,12 +13,13 @@ use crate::{
     engine::{
         func_builder::TranslationErrorInner,
         regmach::bytecode::{Provider, Register, RegisterSpan, UntypedProvider},
+        ConstDedup,
         TranslationError,
     },
     FuncType,
 };
-use alloc::vec::Vec;
-use wasmi_core::UntypedValue;
+use alloc::{collections::BTreeMap, vec::Vec};
+use wasmi_core::{UntypedValue, ValueType};
 
 /// Typed inputs to `wasmi` bytecode instructions.
 ///
@@ -63,6 +64,14 @@ pub struct ValueStack {
     providers: ProviderStack,
     reg_alloc: RegisterAlloc,
     consts: FuncLocalConsts,
+    /// Known upper bounds of the unsigned values of dynamically allocated [`Register`].
+    ///
+    /// # Note
+    ///
+    /// Dynamically allocated [`Register`] are written only once while they are on the
+    /// [`ValueStack`] so their bounds stay valid until they are allocated again.
+    /// The bounds are only used during translation and are not part of the `wasmi` bytecode.
+    bounds: BTreeMap<Register, u64>,
 }
 
 impl ValueStack {
@@ -71,6 +80,16 @@ impl ValueStack {
         self.providers.reset();
         self.reg_alloc.reset();
         self.consts.reset();
+        self.bounds.clear();
+    }
+
+    /// Sets the [`ConstDedup`] data structure used to deduplicate function local constant values.
+    ///
+    /// # Note
+    ///
+    /// This must only be called right after resetting the [`ValueStack`].
+    pub fn set_const_dedup(&mut self, dedup: ConstDedup) {
+        self.consts.set_dedup(dedup);
     }
 
     /// Pops [`Provider`] from the [`ValueStack`] until it has the given stack `height`.
@@ -216,6 +235,7 @@ impl ValueStack {
         match self.reg_alloc.register_space(reg) {
             RegisterSpace::Dynamic => {
                 self.reg_alloc.push_dynamic()?;
+                self.forget_upper_bound(reg);
                 self.providers.push_dynamic(reg);
                 return Ok(());
             }
@@ -254,10 +274,104 @@ impl ValueStack {
     /// If too many registers have been registered.
     pub fn push_dynamic(&mut self) -> Result<Register, TranslationError> {
         let reg = self.reg_alloc.push_dynamic()?;
+        self.forget_upper_bound(reg);
         self.providers.push_dynamic(reg);
         Ok(reg)
     }
 
+    /// Pushes a [`Register`] for the result of an instruction that popped its `operands`.
+    ///
+    /// # Note
+    ///
+    /// - If one of the `operands` is a dynamically allocated [`Register`] this is
+    ///   equal to [`ValueStack::push_dynamic`] which reuses the [`Register`] of the
+    ///   first dynamically allocated operand.
+    /// - Otherwise a storage [`Register`] is reused if one of the `operands` was
+    ///   the last use of a preserved local variable since its value is dead.
+    ///   This avoids allocating yet another dynamic [`Register`].
+    ///
+    /// The caller is responsible to encode an instruction that reads
+    /// all of its `operands` before writing to the returned [`Register`].
+    ///
+    /// # Errors
+    ///
+    /// If too many registers have been registered.
+    pub fn push_dynamic_or_reuse(
+        &mut self,
+        operands: &[TypedProvider],
+    ) -> Result<Register, TranslationError> {
+        let registers = || {
+            operands.iter().filter_map(|operand| match *operand {
+                TypedProvider::Register(register) => Some(register),
+                TypedProvider::Const(_) => None,
+            })
+        };
+        if registers().any(|register| self.is_dynamic(register))
+            || !registers().any(|register| self.reg_alloc.is_dead_storage(register))
+        {
+            return self.push_dynamic();
+        }
+        let reg = self.reg_alloc.push_storage()?;
+        self.forget_upper_bound(reg);
+        self.providers.push_storage(reg);
+        Ok(reg)
+    }
+
+    /// Returns the known upper bound of the unsigned value of `provider` if any.
+    fn upper_bound(&self, provider: TypedProvider) -> Option<u64> {
+        match provider {
+            TypedProvider::Register(register) => self.bounds.get(&register).copied(),
+            TypedProvider::Const(value) => match value.ty() {
+                ValueType::I32 => Some(u64::from(u32::from(value))),
+                ValueType::I64 => Some(u64::from(value)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the known upper bound of a bitwise `and` of the two top-most [`Provider`].
+    ///
+    /// # Note
+    ///
+    /// The result of a bitwise `and` is never greater than any of its unsigned operands.
+    pub fn and_upper_bound(&self) -> Option<u64> {
+        let operands = self.providers.peek_n(2);
+        let lhs = self.upper_bound(TypedProvider::from(operands[0]));
+        let rhs = self.upper_bound(TypedProvider::from(operands[1]));
+        lhs.into_iter().chain(rhs).min()
+    }
+
+    /// Records the known upper `bound` for the top-most [`Provider`] if any.
+    ///
+    /// # Note
+    ///
+    /// This only has an effect if the top-most [`Provider`] is a dynamically allocated [`Register`].
+    pub fn set_upper_bound(&mut self, bound: Option<u64>) {
+        if let (TaggedProvider::Dynamic(register), Some(bound)) = (self.providers.peek(), bound) {
+            self.bounds.insert(register, bound);
+        }
+    }
+
+    /// Returns `true` if `register` is a dynamically allocated [`Register`].
+    pub fn is_dynamic(&self, register: Register) -> bool {
+        matches!(
+            self.reg_alloc.register_space(register),
+            RegisterSpace::Dynamic
+        )
+    }
+
+    /// Returns `true` if the unsigned value of `register` is known to be at most `value`.
+    pub fn is_known_at_most(&self, register: Register, value: impl Into<u64>) -> bool {
+        matches!(self.bounds.get(&register), Some(&bound) if bound <= value.into())
+    }
+
+    /// Forgets the known upper bound of `register` since it is about to be reallocated.
+    fn forget_upper_bound(&mut self, register: Register) {
+        if !self.bounds.is_empty() {
+            self.bounds.remove(&register);
+        }
+    }
+
     /// Pops the top-most [`Provider`] from the [`ValueStack`].
     pub fn pop(&mut self) -> TypedProvider {
         self.reg_alloc.pop_provider(self.providers.pop())
@@ -351,6 +465,7 @@ impl ValueStack {
     pub fn push_dynamic_n(&mut self, n: usize) -> Result<RegisterSpan, TranslationError> {
         let registers = self.reg_alloc.push_dynamic_n(n)?;
         for register in registers.iter(n) {
+            self.forget_upper_bound(register);
             self.providers.push_dynamic(register);
         }
         Ok(registers)
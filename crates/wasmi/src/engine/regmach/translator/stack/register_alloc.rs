@@ -392,6 +392,15 @@ impl RegisterAlloc {
         self.min_storage < reg.to_i16()
     }
 
+    /// Returns `true` if the [`Register`] is in the [`RegisterSpace::Storage`] but no longer preserved.
+    ///
+    /// # Note
+    ///
+    /// The value of such a [`Register`] is dead and it is free to be reallocated.
+    pub fn is_dead_storage(&self, reg: Register) -> bool {
+        self.is_storage(reg) && self.preservations.get(Self::reg2key(reg)).is_none()
+    }
+
     /// Finalizes register allocation and allows to defragment the register space.
     pub fn finalize_alloc(&mut self) {
         assert!(matches!(self.phase, AllocPhase::Alloc));
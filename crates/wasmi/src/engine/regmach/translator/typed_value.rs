@@ -3,6 +3,7 @@ use crate::{
     ExternRef,
     FuncRef,
 };
+use core::fmt::{self, Display};
 
 /// Types that are associated to a static Wasm type.
 pub trait Typed {
@@ -78,6 +79,18 @@ impl TypedValue {
     }
 }
 
+impl Display for TypedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ty {
+            ValueType::I32 => write!(f, "{}", i32::from(self.value)),
+            ValueType::I64 => write!(f, "{}", i64::from(self.value)),
+            ValueType::F32 => write!(f, "{}", f32::from(self.value)),
+            ValueType::F64 => write!(f, "{}", f64::from(self.value)),
+            ValueType::FuncRef | ValueType::ExternRef => write!(f, "{:#x}", u64::from(self.value)),
+        }
+    }
+}
+
 impl<T> From<T> for TypedValue
 where
     T: Typed + Into<UntypedValue>,
@@ -185,6 +198,9 @@ impl TypedValue {
         fn f32_eq(f32, f32) -> i32;
         fn f64_eq(f64, f64) -> i32;
 
+        fn i32_eqz(i32) -> i32;
+        fn i64_eqz(i64) -> i32;
+
         fn i32_ne(i32, i32) -> i32;
         fn i64_ne(i64, i64) -> i32;
         fn f32_ne(f32, f32) -> i32;
@@ -29,7 +29,7 @@ use crate::{
     FuncRef,
     Mutability,
 };
-use alloc::collections::BTreeMap;
+use alloc::collections::{btree_map, BTreeMap};
 use wasmi_core::{TrapCode, ValueType, F32, F64};
 use wasmparser::VisitOperator;
 
@@ -61,6 +61,52 @@ macro_rules! impl_visit_operator {
     ( @tail_call $($rest:tt)* ) => {
         impl_visit_operator!(@@skipped $($rest)*);
     };
+    ( @simd V128Load $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped V128Load $($rest)*);
+    };
+    ( @simd V128Store $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped V128Store $($rest)*);
+    };
+    ( @simd I32x4Splat $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped I32x4Splat $($rest)*);
+    };
+    ( @simd F32x4Splat $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped F32x4Splat $($rest)*);
+    };
+    ( @simd I32x4MinS $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped I32x4MinS $($rest)*);
+    };
+    ( @simd I32x4MinU $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped I32x4MinU $($rest)*);
+    };
+    ( @simd I32x4MaxS $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped I32x4MaxS $($rest)*);
+    };
+    ( @simd I32x4MaxU $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped I32x4MaxU $($rest)*);
+    };
+    ( @simd F32x4Min $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped F32x4Min $($rest)*);
+    };
+    ( @simd F32x4Max $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped F32x4Max $($rest)*);
+    };
+    ( @simd $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
+        // Only a few operators of the `simd` proposal are supported so far.
+        fn $visit(&mut self $($(, _: $argty)*)?) -> Self::Output {
+            Err(TranslationError::unsupported_operator(stringify!($op)))
+        }
+        impl_visit_operator!($($rest)*);
+    };
+    ( @threads MemoryAtomicNotify $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicNotify $($rest)*);
+    };
+    ( @threads MemoryAtomicWait32 $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicWait32 $($rest)*);
+    };
+    ( @threads MemoryAtomicWait64 $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped MemoryAtomicWait64 $($rest)*);
+    };
     ( @@skipped $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         // We skip Wasm operators that we already implement manually.
         impl_visit_operator!($($rest)*);
@@ -98,7 +144,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         bail_unreachable!(self);
         self.alloc
             .instr_encoder
-            .push_instr(Instruction::Trap(TrapCode::UnreachableCodeReached))?;
+            .push_instr(Instruction::trap(TrapCode::UnreachableCodeReached))?;
         self.reachable = false;
         Ok(())
     }
@@ -130,7 +176,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         // and is applicable since Wasm `block` are entered unconditionally.
         let consume_fuel = self.alloc.control_stack.last().consume_fuel_instr();
         let stack_height = BlockHeight::new(self.engine(), self.alloc.stack.height(), block_type)?;
-        let end_label = self.alloc.instr_encoder.new_label();
+        let end_label = self.alloc.instr_encoder.new_label()?;
         let len_block_params = block_type.len_params(self.engine()) as usize;
         let len_branch_params = block_type.len_results(self.engine()) as usize;
         let branch_params = self.alloc_branch_params(len_block_params, len_branch_params)?;
@@ -167,9 +213,21 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             branch_params.iter(len_block_params),
             &self.alloc.buffer[..],
         )?;
+        // Optionally check the bounds of all loads of the loop in front of the loop.
+        if let Some(guard) = self.alloc.loop_guards.encode_guard(self.pos) {
+            let index = Register::try_from(guard.local)?;
+            self.alloc
+                .instr_encoder
+                .push_instr(Instruction::loop_bounds_guard(index))?;
+            for param in [guard.bound, guard.step, guard.end_offset] {
+                self.alloc
+                    .instr_encoder
+                    .append_instr(Instruction::const32(param))?;
+            }
+        }
         // Create loop header label and immediately pin it.
         let stack_height = BlockHeight::new(self.engine(), self.alloc.stack.height(), block_type)?;
-        let header = self.alloc.instr_encoder.new_label();
+        let header = self.alloc.instr_encoder.new_label()?;
         self.alloc.instr_encoder.pin_label(header);
         // Optionally create the loop's [`Instruction::ConsumeFuel`].
         //
@@ -209,7 +267,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         }
         let condition = self.alloc.stack.pop();
         let stack_height = BlockHeight::new(self.engine(), self.alloc.stack.height(), block_type)?;
-        let end_label = self.alloc.instr_encoder.new_label();
+        let end_label = self.alloc.instr_encoder.new_label()?;
         let len_block_params = block_type.len_params(self.engine()) as usize;
         let len_branch_params = block_type.len_results(self.engine()) as usize;
         let branch_params = self.alloc_branch_params(len_block_params, len_branch_params)?;
@@ -246,11 +304,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                     .control_stack
                     .push_else_providers(self.alloc.buffer.iter().copied())?;
                 // Create the `else` label and the conditional branch to `else`.
-                let else_label = self.alloc.instr_encoder.new_label();
+                //
+                // Note: an `if` on the result of an `i32.eqz` branches to `else`
+                //       if the input of the `i32.eqz` is not zero.
+                let eqz_input = self.try_fuse_branch_eqz(condition);
+                let else_label = self.alloc.instr_encoder.new_label()?;
                 let else_offset = self.alloc.instr_encoder.try_resolve_label(else_label)?;
-                self.alloc
-                    .instr_encoder
-                    .push_instr(Instruction::branch_eqz(condition, else_offset))?;
+                let else_branch = match eqz_input {
+                    Some(input) => Instruction::branch_nez(input, else_offset),
+                    None => Instruction::branch_eqz(condition, else_offset),
+                };
+                self.alloc.instr_encoder.push_instr(else_branch)?;
                 let reachability = IfReachability::both(else_label);
                 // Optionally create the [`Instruction::ConsumeFuel`] for the `then` branch.
                 //
@@ -417,14 +481,27 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                         frame.bump_branches();
                         let branch_dst = frame.branch_destination();
                         let branch_params = frame.branch_params(self.res.engine());
+                        // Note: a `br_if` on the result of an `i32.eqz` is taken
+                        //       if the input of the `i32.eqz` is zero.
+                        let eqz_input = self.try_fuse_branch_eqz(condition);
+                        let branch_if = |branch_offset| match eqz_input {
+                            Some(input) => Instruction::branch_eqz(input, branch_offset),
+                            None => Instruction::branch_nez(condition, branch_offset),
+                        };
                         if branch_params.is_empty() {
                             // Case: no values need to be copied so we can directly
-                            //       encode the `br_if` as efficient `branch_nez`.
+                            //       encode the `br_if` as efficient `branch_nez`
+                            //       or fuse it with the comparison of its `condition`.
+                            if eqz_input.is_none()
+                                && self.try_fuse_branch_cmp(condition, branch_dst)?
+                            {
+                                return Ok(());
+                            }
                             let branch_offset =
                                 self.alloc.instr_encoder.try_resolve_label(branch_dst)?;
                             self.alloc
                                 .instr_encoder
-                                .push_instr(Instruction::branch_nez(condition, branch_offset))?;
+                                .push_instr(branch_if(branch_offset))?;
                             return Ok(());
                         }
                         self.alloc
@@ -446,7 +523,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                                 self.alloc.instr_encoder.try_resolve_label(branch_dst)?;
                             self.alloc
                                 .instr_encoder
-                                .push_instr(Instruction::branch_nez(condition, branch_offset))?;
+                                .push_instr(branch_if(branch_offset))?;
                             return Ok(());
                         }
                         // Case: We need to copy the branch inputs to where the
@@ -458,12 +535,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                         //       Otherwise we copy the values to their expected locations
                         //       and finally perform the actual branch to the target
                         //       control frame.
-                        let skip_label = self.alloc.instr_encoder.new_label();
+                        let skip_label = self.alloc.instr_encoder.new_label()?;
                         let skip_offset = self.alloc.instr_encoder.try_resolve_label(skip_label)?;
                         debug_assert!(!skip_offset.is_init());
-                        self.alloc
-                            .instr_encoder
-                            .push_instr(Instruction::branch_eqz(condition, skip_offset))?;
+                        let skip_branch = match eqz_input {
+                            Some(input) => Instruction::branch_nez(input, skip_offset),
+                            None => Instruction::branch_eqz(condition, skip_offset),
+                        };
+                        self.alloc.instr_encoder.push_instr(skip_branch)?;
                         self.alloc.instr_encoder.encode_copies(
                             &mut self.alloc.stack,
                             branch_params,
@@ -587,9 +666,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             .push_instr(Instruction::branch_table(index, targets.len() + 1))?;
         let mut shared_targets = <BTreeMap<u32, LabelRef>>::new();
         for target in self.alloc.br_table_targets.iter().copied() {
-            let shared_label = *shared_targets
-                .entry(target)
-                .or_insert_with(|| self.alloc.instr_encoder.new_label());
+            let shared_label = match shared_targets.entry(target) {
+                btree_map::Entry::Occupied(entry) => *entry.get(),
+                btree_map::Entry::Vacant(entry) => {
+                    *entry.insert(self.alloc.instr_encoder.new_label()?)
+                }
+            };
             let branch_offset = self.alloc.instr_encoder.try_resolve_label(shared_label)?;
             self.alloc
                 .instr_encoder
@@ -659,7 +741,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         Ok(())
     }
 
@@ -702,7 +784,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         self.alloc.instr_encoder.append_instr(table_params)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         Ok(())
     }
 
@@ -735,7 +817,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         self.reachable = false;
         Ok(())
     }
@@ -773,13 +855,16 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         self.alloc.instr_encoder.append_instr(table_params)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_call_params(&mut self.alloc.stack, provider_params)?;
         self.reachable = false;
         Ok(())
     }
 
     fn visit_drop(&mut self) -> Self::Output {
         bail_unreachable!(self);
+        // Note: `drop` emits no instruction and only frees the dropped value.
+        //       The instruction producing the dropped value, for example a `call`,
+        //       has already been encoded so that its side effects are preserved.
         self.alloc.stack.pop();
         Ok(())
     }
@@ -840,11 +925,22 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 // computation which allows us to exchange the result register of
                 // this previous instruction instead of encoding another `copy`
                 // instruction as an optimization.
-                self.alloc
+                if self
+                    .alloc
                     .instr_encoder
-                    .encode_local_set(&self.res, local_register, value)?;
+                    .encode_local_set(&self.res, local_register, value)?
+                {
+                    self.alloc.peepholes.copy_eliminations += 1;
+                }
             }
         }
+        if self
+            .alloc
+            .instr_encoder
+            .try_remove_dead_copy(&self.res, local_register)
+        {
+            self.alloc.peepholes.copy_eliminations += 1;
+        }
         self.alloc.instr_encoder.reset_last_instr();
         Ok(())
     }
@@ -932,6 +1028,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_load,
             Instruction::i32_load_offset16,
             Instruction::i32_load_at,
+            Instruction::i32_load_unchecked,
         )
     }
 
@@ -941,6 +1038,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load,
             Instruction::i64_load_offset16,
             Instruction::i64_load_at,
+            Instruction::i64_load_unchecked,
         )
     }
 
@@ -950,6 +1048,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::f32_load,
             Instruction::f32_load_offset16,
             Instruction::f32_load_at,
+            Instruction::f32_load_unchecked,
         )
     }
 
@@ -959,6 +1058,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::f64_load,
             Instruction::f64_load_offset16,
             Instruction::f64_load_at,
+            Instruction::f64_load_unchecked,
         )
     }
 
@@ -968,6 +1068,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_load8_s,
             Instruction::i32_load8_s_offset16,
             Instruction::i32_load8_s_at,
+            Instruction::i32_load8_s_unchecked,
         )
     }
 
@@ -977,6 +1078,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_load8_u,
             Instruction::i32_load8_u_offset16,
             Instruction::i32_load8_u_at,
+            Instruction::i32_load8_u_unchecked,
         )
     }
 
@@ -986,6 +1088,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_load16_s,
             Instruction::i32_load16_s_offset16,
             Instruction::i32_load16_s_at,
+            Instruction::i32_load16_s_unchecked,
         )
     }
 
@@ -995,6 +1098,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_load16_u,
             Instruction::i32_load16_u_offset16,
             Instruction::i32_load16_u_at,
+            Instruction::i32_load16_u_unchecked,
         )
     }
 
@@ -1004,6 +1108,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load8_s,
             Instruction::i64_load8_s_offset16,
             Instruction::i64_load8_s_at,
+            Instruction::i64_load8_s_unchecked,
         )
     }
 
@@ -1013,6 +1118,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load8_u,
             Instruction::i64_load8_u_offset16,
             Instruction::i64_load8_u_at,
+            Instruction::i64_load8_u_unchecked,
         )
     }
 
@@ -1022,6 +1128,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load16_s,
             Instruction::i64_load16_s_offset16,
             Instruction::i64_load16_s_at,
+            Instruction::i64_load16_s_unchecked,
         )
     }
 
@@ -1031,6 +1138,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load16_u,
             Instruction::i64_load16_u_offset16,
             Instruction::i64_load16_u_at,
+            Instruction::i64_load16_u_unchecked,
         )
     }
 
@@ -1040,6 +1148,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load32_s,
             Instruction::i64_load32_s_offset16,
             Instruction::i64_load32_s_at,
+            Instruction::i64_load32_s_unchecked,
         )
     }
 
@@ -1049,6 +1158,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_load32_u,
             Instruction::i64_load32_u_offset16,
             Instruction::i64_load32_u_at,
+            Instruction::i64_load32_u_unchecked,
         )
     }
 
@@ -1060,6 +1170,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_store_offset16_imm16,
             Instruction::i32_store_at,
             Instruction::i32_store_at_imm16,
+            Instruction::i32_store_unchecked,
         )
     }
 
@@ -1071,6 +1182,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_store_offset16_imm16,
             Instruction::i64_store_at,
             Instruction::i64_store_at_imm16,
+            Instruction::i64_store_unchecked,
         )
     }
 
@@ -1080,6 +1192,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::f32_store,
             Instruction::f32_store_offset16,
             Instruction::f32_store_at,
+            Instruction::f32_store_unchecked,
         )
     }
 
@@ -1089,6 +1202,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::f64_store,
             Instruction::f64_store_offset16,
             Instruction::f64_store_at,
+            Instruction::f64_store_unchecked,
         )
     }
 
@@ -1100,6 +1214,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_store8_offset16_imm,
             Instruction::i32_store8_at,
             Instruction::i32_store8_at_imm,
+            Instruction::i32_store8_unchecked,
         )
     }
 
@@ -1111,6 +1226,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i32_store16_offset16_imm,
             Instruction::i32_store16_at,
             Instruction::i32_store16_at_imm,
+            Instruction::i32_store16_unchecked,
         )
     }
 
@@ -1122,6 +1238,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_store8_offset16_imm,
             Instruction::i64_store8_at,
             Instruction::i64_store8_at_imm,
+            Instruction::i64_store8_unchecked,
         )
     }
 
@@ -1133,6 +1250,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_store16_offset16_imm,
             Instruction::i64_store16_at,
             Instruction::i64_store16_at_imm,
+            Instruction::i64_store16_unchecked,
         )
     }
 
@@ -1144,23 +1262,33 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             Instruction::i64_store32_offset16_imm16,
             Instruction::i64_store32_at,
             Instruction::i64_store32_at_imm16,
+            Instruction::i64_store32_unchecked,
         )
     }
 
     fn visit_memory_size(&mut self, mem: u32, _mem_byte: u8) -> Self::Output {
-        debug_assert_eq!(
-            mem, 0,
-            "wasmi does not yet support the multi-memory Wasm proposal"
-        );
         bail_unreachable!(self);
+        let memory_type = self.res.get_type_of_memory(module::MemoryIdx::from(mem));
+        if memory_type.maximum_pages() == Some(memory_type.initial_pages()) {
+            // Case: the linear memory has a fixed size and thus can never grow.
+            //
+            // This also holds for imported linear memories since a linear memory
+            // only matches the import if its limits are within the imported limits.
+            // Therefore `memory.size` always yields the same value and we can
+            // replace it with a constant which allows for further constant folding.
+            let size = u32::from(memory_type.initial_pages()) as i32;
+            self.alloc.stack.push_const(size);
+            return Ok(());
+        }
         let result = self.alloc.stack.push_dynamic()?;
+        self.encode_use_memory(mem)?;
         self.alloc
             .instr_encoder
             .push_instr(Instruction::memory_size(result))?;
         Ok(())
     }
 
-    fn visit_memory_grow(&mut self, _mem: u32, _mem_byte: u8) -> Self::Output {
+    fn visit_memory_grow(&mut self, mem: u32, _mem_byte: u8) -> Self::Output {
         bail_unreachable!(self);
         let delta = self.alloc.stack.pop();
         let delta = <Provider<Const16<u32>>>::new(delta, &mut self.alloc.stack)?;
@@ -1177,6 +1305,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             }
             Provider::Const(delta) => Instruction::memory_grow_by(result, delta),
         };
+        self.encode_use_memory(mem)?;
         self.alloc.instr_encoder.push_instr(instr)?;
         Ok(())
     }
@@ -1233,10 +1362,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_eqz(&mut self) -> Self::Output {
-        bail_unreachable!(self);
-        // Push a zero on the value stack so we can translate `i32.eqz` as `i32.eq(x, 0)`.
-        self.alloc.stack.push_const(0_i32);
-        self.visit_i32_eq()
+        self.translate_unary(Instruction::i32_eqz, TypedValue::i32_eqz)
     }
 
     fn visit_i32_eq(&mut self) -> Self::Output {
@@ -1320,12 +1446,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u32| {
+            |this, lhs: Register, rhs: u32| {
                 if rhs == u32::MIN {
                     // Optimization: `x < MIN` is always `false`
                     this.alloc.stack.push_const(false);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs - 1) {
+                    // Optimization: `x < rhs` is always `true` if `x` is known to be smaller
+                    this.alloc.stack.push_const(true);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u32, _rhs: Register| {
@@ -1386,12 +1517,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u32| {
+            |this, lhs: Register, rhs: u32| {
                 if rhs == u32::MAX {
                     // Optimization: `x > MAX` is always `false`
                     this.alloc.stack.push_const(false);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs) {
+                    // Optimization: `x > rhs` is always `false` if `x` is known to be at most `rhs`
+                    this.alloc.stack.push_const(false);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u32, _rhs: Register| {
@@ -1452,12 +1588,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u32| {
+            |this, lhs: Register, rhs: u32| {
                 if rhs == u32::MAX {
                     // Optimization: `x <= MAX` is always `true`
                     this.alloc.stack.push_const(true);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs) {
+                    // Optimization: `x <= rhs` is always `true` if `x` is known to be at most `rhs`
+                    this.alloc.stack.push_const(true);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u32, _rhs: Register| {
@@ -1518,12 +1659,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u32| {
+            |this, lhs: Register, rhs: u32| {
                 if rhs == u32::MIN {
                     // Optimization: `x >= MIN` is always `true`
                     this.alloc.stack.push_const(true);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs - 1) {
+                    // Optimization: `x >= rhs` is always `false` if `x` is known to be smaller
+                    this.alloc.stack.push_const(false);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u32, _rhs: Register| {
@@ -1538,10 +1684,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i64_eqz(&mut self) -> Self::Output {
-        bail_unreachable!(self);
-        // Push a zero on the value stack so we can translate `i64.eqz` as `i64.eq(x, 0)`.
-        self.alloc.stack.push_const(0_i64);
-        self.visit_i64_eq()
+        self.translate_unary(Instruction::i64_eqz, TypedValue::i64_eqz)
     }
 
     fn visit_i64_eq(&mut self) -> Self::Output {
@@ -1625,12 +1768,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u64| {
+            |this, lhs: Register, rhs: u64| {
                 if rhs == u64::MIN {
                     // Optimization: `x < MIN` is always `false`
                     this.alloc.stack.push_const(false);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs - 1) {
+                    // Optimization: `x < rhs` is always `true` if `x` is known to be smaller
+                    this.alloc.stack.push_const(true);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u64, _rhs: Register| {
@@ -1691,12 +1839,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u64| {
+            |this, lhs: Register, rhs: u64| {
                 if rhs == u64::MAX {
                     // Optimization: `x > MAX` is always `false`
                     this.alloc.stack.push_const(false);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs) {
+                    // Optimization: `x > rhs` is always `false` if `x` is known to be at most `rhs`
+                    this.alloc.stack.push_const(false);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u64, _rhs: Register| {
@@ -1757,12 +1910,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u64| {
+            |this, lhs: Register, rhs: u64| {
                 if rhs == u64::MAX {
                     // Optimization: `x <= MAX` is always `true`
                     this.alloc.stack.push_const(true);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs) {
+                    // Optimization: `x <= rhs` is always `true` if `x` is known to be at most `rhs`
+                    this.alloc.stack.push_const(true);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u64, _rhs: Register| {
@@ -1823,12 +1981,17 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-            |this, _lhs: Register, rhs: u64| {
+            |this, lhs: Register, rhs: u64| {
                 if rhs == u64::MIN {
                     // Optimization: `x >= MIN` is always `true`
                     this.alloc.stack.push_const(true);
                     return Ok(true);
                 }
+                if this.alloc.stack.is_known_at_most(lhs, rhs - 1) {
+                    // Optimization: `x >= rhs` is always `false` if `x` is known to be smaller
+                    this.alloc.stack.push_const(false);
+                    return Ok(true);
+                }
                 Ok(false)
             },
             |this, lhs: u64, _rhs: Register| {
@@ -2207,6 +2370,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_add(&mut self) -> Self::Output {
+        bail_unreachable!(self);
+        if self.try_fuse_i32_add_from_mem()? {
+            // Optimization: the `i32.add` has been fused with its preceding `i32.load`.
+            return Ok(());
+        }
         self.translate_binary_commutative(
             Instruction::i32_add,
             Instruction::i32_add_imm16,
@@ -2273,6 +2441,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i32_div_s(&mut self) -> Self::Output {
         self.translate_divrem(
+            "i32.div_s",
             Instruction::i32_div_s,
             Instruction::i32_div_s_imm16,
             Instruction::i32_div_s_imm16_rev,
@@ -2291,6 +2460,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i32_div_u(&mut self) -> Self::Output {
         self.translate_divrem::<u32>(
+            "i32.div_u",
             Instruction::i32_div_u,
             Instruction::i32_div_u_imm16,
             Instruction::i32_div_u_imm16_rev,
@@ -2309,6 +2479,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i32_rem_s(&mut self) -> Self::Output {
         self.translate_divrem(
+            "i32.rem_s",
             Instruction::i32_rem_s,
             Instruction::i32_rem_s_imm16,
             Instruction::i32_rem_s_imm16_rev,
@@ -2327,6 +2498,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i32_rem_u(&mut self) -> Self::Output {
         self.translate_divrem::<u32>(
+            "i32.rem_u",
             Instruction::i32_rem_u,
             Instruction::i32_rem_u_imm16,
             Instruction::i32_rem_u_imm16_rev,
@@ -2344,6 +2516,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_and(&mut self) -> Self::Output {
+        bail_unreachable!(self);
+        let bound = self.alloc.stack.and_upper_bound();
         self.translate_binary_commutative(
             Instruction::i32_and,
             Instruction::i32_and_imm16,
@@ -2372,7 +2546,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-        )
+        )?;
+        self.alloc.stack.set_upper_bound(bound);
+        Ok(())
     }
 
     fn visit_i32_or(&mut self) -> Self::Output {
@@ -2581,6 +2757,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i64_div_s(&mut self) -> Self::Output {
         self.translate_divrem(
+            "i64.div_s",
             Instruction::i64_div_s,
             Instruction::i64_div_s_imm16,
             Instruction::i64_div_s_imm16_rev,
@@ -2599,6 +2776,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i64_div_u(&mut self) -> Self::Output {
         self.translate_divrem::<u64>(
+            "i64.div_u",
             Instruction::i64_div_u,
             Instruction::i64_div_u_imm16,
             Instruction::i64_div_u_imm16_rev,
@@ -2617,6 +2795,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i64_rem_s(&mut self) -> Self::Output {
         self.translate_divrem(
+            "i64.rem_s",
             Instruction::i64_rem_s,
             Instruction::i64_rem_s_imm16,
             Instruction::i64_rem_s_imm16_rev,
@@ -2635,6 +2814,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
 
     fn visit_i64_rem_u(&mut self) -> Self::Output {
         self.translate_divrem::<u64>(
+            "i64.rem_u",
             Instruction::i64_rem_u,
             Instruction::i64_rem_u_imm16,
             Instruction::i64_rem_u_imm16_rev,
@@ -2652,6 +2832,8 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i64_and(&mut self) -> Self::Output {
+        bail_unreachable!(self);
+        let bound = self.alloc.stack.and_upper_bound();
         self.translate_binary_commutative(
             Instruction::i64_and,
             Instruction::i64_and_imm16,
@@ -2680,7 +2862,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 }
                 Ok(false)
             },
-        )
+        )?;
+        self.alloc.stack.set_upper_bound(bound);
+        Ok(())
     }
 
     fn visit_i64_or(&mut self) -> Self::Output {
@@ -3039,19 +3223,35 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i32_trunc_f32_s(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i32_trunc_f32_s, TypedValue::i32_trunc_f32_s)
+        self.translate_unary_fallible(
+            "i32.trunc_f32_s",
+            Instruction::i32_trunc_f32_s,
+            TypedValue::i32_trunc_f32_s,
+        )
     }
 
     fn visit_i32_trunc_f32_u(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i32_trunc_f32_u, TypedValue::i32_trunc_f32_u)
+        self.translate_unary_fallible(
+            "i32.trunc_f32_u",
+            Instruction::i32_trunc_f32_u,
+            TypedValue::i32_trunc_f32_u,
+        )
     }
 
     fn visit_i32_trunc_f64_s(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i32_trunc_f64_s, TypedValue::i32_trunc_f64_s)
+        self.translate_unary_fallible(
+            "i32.trunc_f64_s",
+            Instruction::i32_trunc_f64_s,
+            TypedValue::i32_trunc_f64_s,
+        )
     }
 
     fn visit_i32_trunc_f64_u(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i32_trunc_f64_u, TypedValue::i32_trunc_f64_u)
+        self.translate_unary_fallible(
+            "i32.trunc_f64_u",
+            Instruction::i32_trunc_f64_u,
+            TypedValue::i32_trunc_f64_u,
+        )
     }
 
     fn visit_i64_extend_i32_s(&mut self) -> Self::Output {
@@ -3063,19 +3263,35 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     }
 
     fn visit_i64_trunc_f32_s(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i64_trunc_f32_s, TypedValue::i64_trunc_f32_s)
+        self.translate_unary_fallible(
+            "i64.trunc_f32_s",
+            Instruction::i64_trunc_f32_s,
+            TypedValue::i64_trunc_f32_s,
+        )
     }
 
     fn visit_i64_trunc_f32_u(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i64_trunc_f32_u, TypedValue::i64_trunc_f32_u)
+        self.translate_unary_fallible(
+            "i64.trunc_f32_u",
+            Instruction::i64_trunc_f32_u,
+            TypedValue::i64_trunc_f32_u,
+        )
     }
 
     fn visit_i64_trunc_f64_s(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i64_trunc_f64_s, TypedValue::i64_trunc_f64_s)
+        self.translate_unary_fallible(
+            "i64.trunc_f64_s",
+            Instruction::i64_trunc_f64_s,
+            TypedValue::i64_trunc_f64_s,
+        )
     }
 
     fn visit_i64_trunc_f64_u(&mut self) -> Self::Output {
-        self.translate_unary_fallible(Instruction::i64_trunc_f64_u, TypedValue::i64_trunc_f64_u)
+        self.translate_unary_fallible(
+            "i64.trunc_f64_u",
+            Instruction::i64_trunc_f64_u,
+            TypedValue::i64_trunc_f64_u,
+        )
     }
 
     fn visit_f32_convert_i32_s(&mut self) -> Self::Output {
@@ -3234,7 +3450,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         )
     }
 
-    fn visit_memory_init(&mut self, data_index: u32, _mem: u32) -> Self::Output {
+    fn visit_memory_init(&mut self, data_index: u32, mem: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3266,6 +3482,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 Instruction::memory_init_from_to_exact(dst, src, len)
             }
         };
+        self.encode_use_memory(mem)?;
         self.alloc.instr_encoder.push_instr(instr)?;
         self.alloc
             .instr_encoder
@@ -3277,12 +3494,15 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         bail_unreachable!(self);
         self.alloc
             .instr_encoder
-            .push_instr(Instruction::DataDrop(data_index.into()))?;
+            .push_instr(Instruction::data_drop(data_index))?;
         Ok(())
     }
 
-    fn visit_memory_copy(&mut self, _dst_mem: u32, _src_mem: u32) -> Self::Output {
+    fn visit_memory_copy(&mut self, dst_mem: u32, src_mem: u32) -> Self::Output {
         bail_unreachable!(self);
+        if dst_mem != src_mem {
+            return Err(TranslationError::unsupported_cross_memory_copy());
+        }
         let (dst, src, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
         let src = <Provider<Const16<u32>>>::new(src, &mut self.alloc.stack)?;
@@ -3313,11 +3533,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 Instruction::memory_copy_from_to_exact(dst, src, len)
             }
         };
+        self.encode_use_memory(dst_mem)?;
         self.alloc.instr_encoder.push_instr(instr)?;
         Ok(())
     }
 
-    fn visit_memory_fill(&mut self, _mem: u32) -> Self::Output {
+    fn visit_memory_fill(&mut self, mem: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, value, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3349,6 +3570,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
                 Instruction::memory_fill_at_imm_exact(dst, value, len)
             }
         };
+        self.encode_use_memory(mem)?;
         self.alloc.instr_encoder.push_instr(instr)?;
         Ok(())
     }
@@ -3399,7 +3621,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
         bail_unreachable!(self);
         self.alloc
             .instr_encoder
-            .push_instr(Instruction::ElemDrop(elem_index.into()))?;
+            .push_instr(Instruction::elem_drop(elem_index))?;
         Ok(())
     }
 
@@ -3518,7 +3740,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
     fn visit_table_grow(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (value, delta) = self.alloc.stack.pop2();
-        if let Provider::Const(delta) = delta {
+        if let Some(delta) = delta.as_const() {
             if u32::from(delta) == 0 {
                 // Case: growing by 0 elements.
                 //
@@ -3557,4 +3779,56 @@ impl<'a> VisitOperator<'a> for FuncTranslator<'a> {
             .push_instr(Instruction::table_size(result, table))?;
         Ok(())
     }
+
+    fn visit_v128_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_v128_load(memarg)
+    }
+
+    fn visit_v128_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_v128_store(memarg)
+    }
+
+    fn visit_i32x4_splat(&mut self) -> Self::Output {
+        self.translate_x4_splat()
+    }
+
+    fn visit_f32x4_splat(&mut self) -> Self::Output {
+        self.translate_x4_splat()
+    }
+
+    fn visit_i32x4_min_s(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::i32x4_min_s)
+    }
+
+    fn visit_i32x4_min_u(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::i32x4_min_u)
+    }
+
+    fn visit_i32x4_max_s(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::i32x4_max_s)
+    }
+
+    fn visit_i32x4_max_u(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::i32x4_max_u)
+    }
+
+    fn visit_f32x4_min(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::f32x4_min)
+    }
+
+    fn visit_f32x4_max(&mut self) -> Self::Output {
+        self.translate_v128_binary(Instruction::f32x4_max)
+    }
+
+    fn visit_memory_atomic_notify(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_memory_atomic_notify(memarg)
+    }
+
+    fn visit_memory_atomic_wait32(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_memory_atomic_wait(memarg, Instruction::memory_atomic_wait32)
+    }
+
+    fn visit_memory_atomic_wait64(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_memory_atomic_wait(memarg, Instruction::memory_atomic_wait64)
+    }
 }
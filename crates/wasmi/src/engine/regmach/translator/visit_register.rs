@@ -1,6 +1,8 @@
 use crate::engine::regmach::bytecode::{
     BinInstr,
     BinInstrImm16,
+    BranchBinOpInstr,
+    BranchBinOpInstrImm16,
     Const16,
     CopysignImmInstr,
     Instruction,
@@ -44,6 +46,7 @@ impl VisitInputRegisters for Instruction {
             Instruction::Register2(registers) => registers.visit_input_registers(f),
             Instruction::Register3(registers) |
             Instruction::RegisterList(registers) => registers.visit_input_registers(f),
+            Instruction::RegisterSpan(span) => span.visit_input_registers(f),
             Instruction::Trap(_) |
             Instruction::ConsumeFuel(_) |
             Instruction::Return => {},
@@ -79,6 +82,31 @@ impl VisitInputRegisters for Instruction {
             Instruction::Branch { .. } => {},
             Instruction::BranchEqz { condition, .. } |
             Instruction::BranchNez { condition, .. } => f(condition),
+            Instruction::BranchI32Eq(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32EqImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32Ne(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32NeImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LtS(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LtSImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LtU(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LtUImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LeS(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LeSImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LeU(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32LeUImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GtS(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GtSImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GtU(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GtUImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GeS(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GeSImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GeU(instr) => instr.visit_input_registers(f),
+            Instruction::BranchI32GeUImm(instr) => instr.visit_input_registers(f),
+            Instruction::BranchCmpFallback { lhs, rhs, params } => {
+                f(lhs);
+                f(rhs);
+                f(params);
+            }
             Instruction::BranchTable { index, .. } => f(index),
             Instruction::Copy { result, value } => {
                 // Note: for copy instruction unlike all other instructions
@@ -158,6 +186,7 @@ impl VisitInputRegisters for Instruction {
             Instruction::TableGrowImm { result: _, delta: _, value } => f(value),
             Instruction::ElemDrop(_) => {}
             Instruction::DataDrop(_) => {}
+            Instruction::UseMemory(_) => {}
             Instruction::MemorySize { result: _ } => {},
             Instruction::MemoryGrow { result: _, delta } => f(delta),
             Instruction::MemoryGrowBy { result: _, delta: _ } => {},
@@ -231,6 +260,30 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64Load32u(instr) => instr.visit_input_registers(f),
             Instruction::I64Load32uAt(instr) => instr.visit_input_registers(f),
             Instruction::I64Load32uOffset16(instr) => instr.visit_input_registers(f),
+            Instruction::LoopBoundsGuard { index } => f(index),
+            Instruction::I32LoadUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64LoadUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::F32LoadUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::F64LoadUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Load8sUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Load8uUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Load16sUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Load16uUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load8sUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load8uUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load16sUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load16uUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load32sUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Load32uUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32StoreUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Store8Unchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I32Store16Unchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64StoreUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Store8Unchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Store16Unchecked(instr) => instr.visit_input_registers(f),
+            Instruction::I64Store32Unchecked(instr) => instr.visit_input_registers(f),
+            Instruction::F32StoreUnchecked(instr) => instr.visit_input_registers(f),
+            Instruction::F64StoreUnchecked(instr) => instr.visit_input_registers(f),
             Instruction::I32Store(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreOffset16(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreOffset16Imm16(instr) => instr.visit_input_registers(f),
@@ -324,6 +377,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::F64Gt(instr) => instr.visit_input_registers(f),
             Instruction::F32Ge(instr) => instr.visit_input_registers(f),
             Instruction::F64Ge(instr) => instr.visit_input_registers(f),
+            Instruction::I32Eqz(instr) => instr.visit_input_registers(f),
+            Instruction::I64Eqz(instr) => instr.visit_input_registers(f),
             Instruction::I32Clz(instr) => instr.visit_input_registers(f),
             Instruction::I64Clz(instr) => instr.visit_input_registers(f),
             Instruction::I32Ctz(instr) => instr.visit_input_registers(f),
@@ -331,6 +386,7 @@ impl VisitInputRegisters for Instruction {
             Instruction::I32Popcnt(instr) => instr.visit_input_registers(f),
             Instruction::I64Popcnt(instr) => instr.visit_input_registers(f),
             Instruction::I32Add(instr) => instr.visit_input_registers(f),
+            Instruction::I32AddFromMem(instr) => instr.visit_input_registers(f),
             Instruction::I64Add(instr) => instr.visit_input_registers(f),
             Instruction::I32AddImm16(instr) => instr.visit_input_registers(f),
             Instruction::I64AddImm16(instr) => instr.visit_input_registers(f),
@@ -474,6 +530,18 @@ impl VisitInputRegisters for Instruction {
             Instruction::F64ConvertI32U(instr) => instr.visit_input_registers(f),
             Instruction::F64ConvertI64S(instr) => instr.visit_input_registers(f),
             Instruction::F64ConvertI64U(instr) => instr.visit_input_registers(f),
+            Instruction::V128Load(instr) => instr.visit_input_registers(f),
+            Instruction::V128Store(instr) => instr.visit_input_registers(f),
+            Instruction::I32x4Splat(instr) => instr.visit_input_registers(f),
+            Instruction::I32x4MinS(instr) => instr.visit_input_registers(f),
+            Instruction::I32x4MinU(instr) => instr.visit_input_registers(f),
+            Instruction::I32x4MaxS(instr) => instr.visit_input_registers(f),
+            Instruction::I32x4MaxU(instr) => instr.visit_input_registers(f),
+            Instruction::F32x4Min(instr) => instr.visit_input_registers(f),
+            Instruction::F32x4Max(instr) => instr.visit_input_registers(f),
+            Instruction::MemoryAtomicNotify(instr) => instr.visit_input_registers(f),
+            Instruction::MemoryAtomicWait32 { result: _, ptr, expected } => visit_registers!(f, ptr, expected),
+            Instruction::MemoryAtomicWait64 { result: _, ptr, expected } => visit_registers!(f, ptr, expected),
         }
     }
 }
@@ -606,6 +674,18 @@ impl VisitInputRegisters for BinInstrImm16<u64> {
     }
 }
 
+impl VisitInputRegisters for BranchBinOpInstr {
+    fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
+        visit_registers!(f, &mut self.lhs, &mut self.rhs)
+    }
+}
+
+impl<T> VisitInputRegisters for BranchBinOpInstrImm16<T> {
+    fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
+        f(&mut self.lhs)
+    }
+}
+
 impl VisitInputRegisters for CopysignImmInstr {
     fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
         f(&mut self.lhs)
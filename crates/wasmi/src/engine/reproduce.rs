@@ -0,0 +1,186 @@
+use super::{EngineBackend, FuncBodyError, FuncInstr, StepOutcome};
+use crate::{
+    core::{TrapCode, TrapLocation},
+    func::FuncError,
+    Engine,
+    Error,
+    Extern,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// A recorded invocation of an exported function of a [`Module`].
+///
+/// Used to deterministically reproduce traps via [`Engine::reproduce_trap`].
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    /// The name of the exported function.
+    export: Box<str>,
+    /// The parameters of the invocation.
+    params: Box<[Value]>,
+}
+
+impl RecordedCall {
+    /// Creates a new [`RecordedCall`] of the `export` function with `params`.
+    pub fn new(export: &str, params: &[Value]) -> Self {
+        Self {
+            export: export.into(),
+            params: params.into(),
+        }
+    }
+
+    /// Returns the name of the exported function of the [`RecordedCall`].
+    pub fn export(&self) -> &str {
+        &self.export
+    }
+
+    /// Returns the parameters of the [`RecordedCall`].
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+}
+
+/// A trap reproduced via [`Engine::reproduce_trap`].
+#[derive(Debug)]
+pub struct TrapReproduction {
+    /// The error that the reproduced invocation returned.
+    error: Error,
+    /// The locations of all Wasm function frames at the time of the trap.
+    backtrace: Box<[TrapLocation]>,
+    /// The instruction that caused the trap.
+    instr: FuncInstr,
+}
+
+impl TrapReproduction {
+    /// Returns the error that the reproduced invocation returned.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the [`TrapCode`] of the reproduced trap if any.
+    ///
+    /// Returns `None` if the trap has been raised by a host function without a [`TrapCode`].
+    pub fn trap_code(&self) -> Option<TrapCode> {
+        match &self.error {
+            Error::Trap(trap) => trap.trap_code(),
+            _ => None,
+        }
+    }
+
+    /// Returns the locations of all Wasm function frames at the time of the trap.
+    ///
+    /// # Note
+    ///
+    /// The backtrace starts with the root function frame. Caller frames are located
+    /// at their call instructions and the last frame is located at the trapping instruction.
+    pub fn backtrace(&self) -> &[TrapLocation] {
+        &self.backtrace
+    }
+
+    /// Returns the location of the instruction that caused the trap.
+    pub fn location(&self) -> &TrapLocation {
+        self.backtrace
+            .last()
+            .expect("a reproduced trap always has at least one frame")
+    }
+
+    /// Returns the instruction that caused the trap.
+    ///
+    /// This is either the trapping instruction itself or the call instruction
+    /// of a host function that returned the trap.
+    pub fn instr(&self) -> FuncInstr {
+        self.instr
+    }
+}
+
+impl Engine {
+    /// Reproduces the trap of a recorded invocation of an exported function of `module`.
+    ///
+    /// The `module` is instantiated into a new [`Store`] holding `data` using the
+    /// imports defined by `linker`. Afterwards the recorded `call` is executed step by
+    /// step in order to report the backtrace and faulting instruction upon a trap.
+    ///
+    /// Returns `None` if the recorded `call` did not trap.
+    ///
+    /// # Note
+    ///
+    /// This is a non-standard debugging API meant to make bug reports reproducible.
+    /// Reproductions are deterministic as long as the host functions defined by
+    /// `linker` are deterministic.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] does not use [`EngineBackend::RegisterMachine`].
+    /// - If an executed instruction cannot be located within the Wasm binary of `module`.
+    ///   This requires trap locations to be enabled via [`Config::set_trap_locations`].
+    /// - If the instantiation of `module` fails.
+    /// - If `module` does not export a function with the recorded name.
+    /// - If the recorded parameters do not match the function signature.
+    /// - If the call traps before executing its first instruction, for example
+    ///   if the exported function is a host function.
+    ///
+    /// # Panics
+    ///
+    /// If `linker` or `module` do not use this [`Engine`].
+    ///
+    /// [`Config::set_trap_locations`]: crate::Config::set_trap_locations
+    pub fn reproduce_trap<T>(
+        &self,
+        linker: &Linker<T>,
+        data: T,
+        module: &Module,
+        call: &RecordedCall,
+    ) -> Result<Option<TrapReproduction>, Error> {
+        assert!(
+            Engine::same(self, module.engine()),
+            "the module must use this engine"
+        );
+        if !matches!(
+            self.config().engine_backend(),
+            EngineBackend::RegisterMachine
+        ) {
+            return Err(FuncBodyError::UnsupportedBackend.into());
+        }
+        if !self.config().get_trap_locations() {
+            return Err(FuncBodyError::Unlocated.into());
+        }
+        let mut store = Store::new(self, data);
+        let instance = linker.instantiate(&mut store, module)?.start(&mut store)?;
+        let func = instance
+            .get_export(&store, call.export())
+            .and_then(Extern::into_func)
+            .ok_or(Error::Func(FuncError::ExportedFuncNotFound))?;
+        let mut outputs: Vec<Value> = func
+            .ty(&store)
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect();
+        let mut stepped = func.call_stepped(&mut store, call.params())?;
+        // The last executed instruction of every Wasm function frame on the call stack.
+        let mut backtrace = Vec::new();
+        loop {
+            let (location, instr) = match stepped.next_instr()? {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+            backtrace.truncate(stepped.call_depth() - 1);
+            backtrace.push(location);
+            match stepped.step(&mut store, &mut outputs) {
+                Ok(StepOutcome::Running) => {}
+                Ok(StepOutcome::Finished) => return Ok(None),
+                Err(error) => {
+                    return Ok(Some(TrapReproduction {
+                        error,
+                        backtrace: backtrace.into(),
+                        instr: FuncInstr(instr),
+                    }))
+                }
+            }
+        }
+    }
+}
@@ -29,6 +29,19 @@ pub(crate) enum ResumableCallBase<T> {
 }
 
 /// Returned by calling a [`Func`] in a resumable way.
+///
+/// # Suspending Execution
+///
+/// Host functions can suspend the execution of the calling Wasm function by
+/// returning a [`Trap`] that wraps a custom [`HostError`], e.g. to signal that the
+/// host result is still pending. The execution then unwinds to the caller as
+/// [`ResumableCall::Resumable`] without blocking the executing thread. Once the
+/// host result is available the execution can be continued via
+/// [`ResumableInvocation::resume`] which treats the provided values as the results
+/// of the suspended host function call. Asynchronous hosts can use this to drive
+/// Wasm executions from their event loop.
+///
+/// [`HostError`]: crate::core::HostError
 #[derive(Debug)]
 pub enum ResumableCall {
     /// The resumable call has finished properly and returned a result.
@@ -50,19 +50,24 @@ pub struct CallStack {
 
 impl Default for CallStack {
     fn default() -> Self {
-        Self::new(DEFAULT_MAX_RECURSION_DEPTH)
+        Self::new(0, DEFAULT_MAX_RECURSION_DEPTH)
     }
 }
 
 impl CallStack {
-    /// Creates a new [`CallStack`] using the given recursion limit.
-    pub fn new(recursion_limit: usize) -> Self {
+    /// Creates a new [`CallStack`] using the given initial depth and recursion limit.
+    pub fn new(initial_depth: usize, recursion_limit: usize) -> Self {
         Self {
-            frames: Vec::new(),
+            frames: Vec::with_capacity(initial_depth.min(recursion_limit)),
             recursion_limit,
         }
     }
 
+    /// Sets the maximum allowed depth of the [`CallStack`] to `recursion_limit`.
+    pub fn set_recursion_limit(&mut self, recursion_limit: usize) {
+        self.recursion_limit = recursion_limit;
+    }
+
     /// Initializes the [`CallStack`] given the Wasm function.
     pub fn init(&mut self, ip: InstructionPtr, instance: &Instance) {
         self.reset();
@@ -1,6 +1,9 @@
 mod frames;
 mod values;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::{
     frames::{CallStack, FuncFrame},
     values::{ValueStack, ValueStackPtr},
@@ -43,6 +46,14 @@ pub struct StackLimits {
     pub maximum_value_stack_height: usize,
     /// The maximum number of nested calls that the Wasm stack allows.
     pub maximum_recursion_depth: usize,
+    /// The number of nested calls that the Wasm stack prepares for.
+    ///
+    /// # Note
+    ///
+    /// Calls up to this depth do not require the call stack to reallocate.
+    /// Values greater than `maximum_recursion_depth` are clamped to it.
+    /// Configured via [`StackLimits::with_initial_recursion_depth`].
+    initial_recursion_depth: usize,
 }
 
 /// An error that may occur when configuring [`StackLimits`].
@@ -80,20 +91,76 @@ impl StackLimits {
             initial_value_stack_height,
             maximum_value_stack_height,
             maximum_recursion_depth,
+            initial_recursion_depth: 0,
         })
     }
+
+    /// Returns the number of values that fit into `bytes` of value stack.
+    fn value_stack_height(bytes: usize) -> usize {
+        bytes / size_of::<UntypedValue>()
+    }
+
+    /// Sets the initial value stack height to fit `bytes` of values.
+    ///
+    /// # Note
+    ///
+    /// Use [`StackLimits::validate`] once all limits have been set.
+    pub fn with_initial_value_bytes(mut self, bytes: usize) -> Self {
+        self.initial_value_stack_height = Self::value_stack_height(bytes);
+        self
+    }
+
+    /// Sets the maximum value stack height to fit `bytes` of values.
+    ///
+    /// # Note
+    ///
+    /// Use [`StackLimits::validate`] once all limits have been set.
+    pub fn with_maximum_value_bytes(mut self, bytes: usize) -> Self {
+        self.maximum_value_stack_height = Self::value_stack_height(bytes);
+        self
+    }
+
+    /// Sets the number of nested calls that the Wasm stack prepares for.
+    ///
+    /// # Note
+    ///
+    /// Calls up to this `depth` do not require the call stack to reallocate.
+    /// A `depth` greater than the maximum recursion depth is clamped to it.
+    pub fn with_initial_recursion_depth(mut self, depth: usize) -> Self {
+        self.initial_recursion_depth = depth;
+        self
+    }
+
+    /// Returns the number of nested calls that the Wasm stack prepares for.
+    pub(crate) fn initial_recursion_depth(&self) -> usize {
+        self.initial_recursion_depth
+    }
+
+    /// Returns `self` if the initial value stack height does not exceed the maximum.
+    ///
+    /// # Errors
+    ///
+    /// If the initial value stack height exceeds the maximum value stack height.
+    pub fn validate(self) -> Result<Self, LimitsError> {
+        if self.initial_value_stack_height > self.maximum_value_stack_height {
+            return Err(LimitsError::InitialValueStackExceedsMaximum);
+        }
+        Ok(self)
+    }
 }
 
 impl Default for StackLimits {
     fn default() -> Self {
-        let register_len = size_of::<UntypedValue>();
-        let initial_value_stack_height = DEFAULT_MIN_VALUE_STACK_HEIGHT / register_len;
-        let maximum_value_stack_height = DEFAULT_MAX_VALUE_STACK_HEIGHT / register_len;
         Self {
-            initial_value_stack_height,
-            maximum_value_stack_height,
+            initial_value_stack_height: 0,
+            maximum_value_stack_height: 0,
             maximum_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+            initial_recursion_depth: 0,
         }
+        .with_maximum_value_bytes(DEFAULT_MAX_VALUE_STACK_HEIGHT)
+        .with_initial_value_bytes(DEFAULT_MIN_VALUE_STACK_HEIGHT)
+        .validate()
+        .unwrap_or_else(|error| panic!("the default stack limits are invalid: {error}"))
     }
 }
 
@@ -104,6 +171,10 @@ pub struct Stack {
     pub values: ValueStack,
     /// The frame stack.
     pub frames: CallStack,
+    /// The number of [`Engine::reset`] calls before the execution held by the [`Stack`] started.
+    ///
+    /// [`Engine::reset`]: crate::Engine::reset
+    pub resets: u32,
 }
 
 impl Stack {
@@ -111,12 +182,29 @@ impl Stack {
     ///
     /// [`Config`]: [`crate::Config`]
     pub fn new(limits: StackLimits) -> Self {
-        let frames = CallStack::new(limits.maximum_recursion_depth);
+        let frames = CallStack::new(
+            limits.initial_recursion_depth(),
+            limits.maximum_recursion_depth,
+        );
         let values = ValueStack::new(
             limits.initial_value_stack_height,
             limits.maximum_value_stack_height,
         );
-        Self { values, frames }
+        Self {
+            values,
+            frames,
+            resets: 0,
+        }
+    }
+
+    /// Applies the new [`StackLimits`] to the [`Stack`].
+    ///
+    /// Returns `false` if the [`Stack`] must be reallocated in order to respect the new `limits`.
+    pub fn set_limits(&mut self, limits: StackLimits) -> bool {
+        self.frames
+            .set_recursion_limit(limits.maximum_recursion_depth);
+        self.values
+            .set_maximum_len(limits.maximum_value_stack_height)
     }
 
     /// Create an empty [`Stack`].
@@ -128,6 +216,7 @@ impl Stack {
         Self {
             values: ValueStack::empty(),
             frames: CallStack::default(),
+            resets: 0,
         }
     }
 
@@ -0,0 +1,76 @@
+use super::{
+    LimitsError,
+    StackLimits,
+    DEFAULT_MAX_VALUE_STACK_HEIGHT,
+    DEFAULT_MIN_VALUE_STACK_HEIGHT,
+};
+use crate::core::UntypedValue;
+use core::mem::size_of;
+
+#[test]
+fn value_bytes_to_stack_height() {
+    let value_len = size_of::<UntypedValue>();
+    let limits = StackLimits::default()
+        .with_initial_value_bytes(16 * value_len)
+        .with_maximum_value_bytes(64 * value_len)
+        .validate()
+        .unwrap();
+    assert_eq!(limits.initial_value_stack_height, 16);
+    assert_eq!(limits.maximum_value_stack_height, 64);
+    // Partial values are rounded down.
+    let limits = limits.with_initial_value_bytes(17 * value_len - 1);
+    assert_eq!(limits.initial_value_stack_height, 16);
+}
+
+#[test]
+fn default_matches_value_bytes() {
+    let value_len = size_of::<UntypedValue>();
+    let limits = StackLimits::default();
+    assert_eq!(
+        limits.initial_value_stack_height,
+        DEFAULT_MIN_VALUE_STACK_HEIGHT / value_len
+    );
+    assert_eq!(
+        limits.maximum_value_stack_height,
+        DEFAULT_MAX_VALUE_STACK_HEIGHT / value_len
+    );
+}
+
+#[test]
+fn initial_exceeding_maximum_value_bytes_fails() {
+    let value_len = size_of::<UntypedValue>();
+    let limits = StackLimits::default()
+        .with_initial_value_bytes(8 * value_len)
+        .with_maximum_value_bytes(8 * value_len);
+    assert!(limits.validate().is_ok());
+    assert!(matches!(
+        limits.with_initial_value_bytes(9 * value_len).validate(),
+        Err(LimitsError::InitialValueStackExceedsMaximum)
+    ));
+    assert!(matches!(
+        limits.with_maximum_value_bytes(7 * value_len).validate(),
+        Err(LimitsError::InitialValueStackExceedsMaximum)
+    ));
+}
+
+#[test]
+fn value_bytes_are_order_independent() {
+    let value_len = size_of::<UntypedValue>();
+    // Note: the initial height exceeds the default maximum height in between.
+    let initial = 2 * DEFAULT_MAX_VALUE_STACK_HEIGHT;
+    let maximum = 4 * DEFAULT_MAX_VALUE_STACK_HEIGHT;
+    let initial_first = StackLimits::default()
+        .with_initial_value_bytes(initial)
+        .with_maximum_value_bytes(maximum)
+        .validate()
+        .unwrap();
+    let maximum_first = StackLimits::default()
+        .with_maximum_value_bytes(maximum)
+        .with_initial_value_bytes(initial)
+        .validate()
+        .unwrap();
+    for limits in [initial_first, maximum_first] {
+        assert_eq!(limits.initial_value_stack_height, initial / value_len);
+        assert_eq!(limits.maximum_value_stack_height, maximum / value_len);
+    }
+}
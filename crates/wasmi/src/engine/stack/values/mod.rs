@@ -138,6 +138,15 @@ impl ValueStack {
         }
     }
 
+    /// Sets the maximum value stack height of the [`ValueStack`] to `maximum_len`.
+    ///
+    /// Returns `false` if the [`ValueStack`] already holds more than `maximum_len` values
+    /// and therefore must be reallocated in order to respect the new limit.
+    pub fn set_maximum_len(&mut self, maximum_len: usize) -> bool {
+        self.maximum_len = maximum_len;
+        self.capacity() <= maximum_len
+    }
+
     /// Returns the [`UntypedValue`] at the given `index`.
     ///
     /// # Note
@@ -209,7 +218,7 @@ impl ValueStack {
     }
 
     /// Returns the capacity of the [`ValueStack`].
-    fn capacity(&self) -> usize {
+    pub fn capacity(&self) -> usize {
         self.entries.len()
     }
 
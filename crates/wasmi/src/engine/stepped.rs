@@ -0,0 +1,178 @@
+use super::{Func, FuncBodyError, Instruction2, Stack2};
+use crate::{
+    core::{TrapLocation, UntypedValue},
+    AsContext,
+    AsContextMut,
+    Engine,
+    Error,
+    Value,
+};
+
+/// The outcome of executing a single step of a [`SteppedCall`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The stepped call has more instructions to execute.
+    Running,
+    /// The stepped call has finished and its results have been written back.
+    Finished,
+}
+
+/// A [`Func`] invocation that is executed one instruction at a time.
+///
+/// # Note
+///
+/// This is a non-standard debugging API that is only supported by the
+/// register-machine engine backend. Every call to [`SteppedCall::step`] executes
+/// exactly one `wasmi` bytecode instruction of the top-most function frame and
+/// returns control to the host afterwards. Called host functions are executed
+/// as part of a single step.
+#[derive(Debug)]
+pub struct SteppedCall {
+    /// The engine in use for the function invocation.
+    ///
+    /// # Note
+    ///
+    /// This handle is required to execute the steps as well as
+    /// in the `Drop` impl to recycle the stack.
+    engine: Engine,
+    /// The underlying root function that is executed.
+    func: Func,
+    /// The value and call stack holding the state of the execution.
+    stack: Stack2,
+    /// Is `true` once the root function has returned.
+    finished: bool,
+    /// Is `true` if a step of the execution has trapped.
+    trapped: bool,
+}
+
+impl Drop for SteppedCall {
+    fn drop(&mut self) {
+        let stack = core::mem::take(&mut self.stack);
+        self.engine.recycle_stack_2(stack);
+    }
+}
+
+impl SteppedCall {
+    /// Creates a new [`SteppedCall`] for `func` from its initialized `stack`.
+    pub(crate) fn new(engine: Engine, func: Func, stack: Stack2) -> Self {
+        let finished = stack.calls.peek().is_none();
+        Self {
+            engine,
+            func,
+            stack,
+            finished,
+            trapped: false,
+        }
+    }
+
+    /// Executes the next instruction of the stepped call.
+    ///
+    /// Once the root function has returned its results are written into `outputs`
+    /// and [`StepOutcome::Finished`] is returned for this and all following steps.
+    ///
+    /// # Errors
+    ///
+    /// - If the executed instruction traps.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature.
+    ///
+    /// # Panics
+    ///
+    /// - If `ctx` does not own the [`Func`] of the [`SteppedCall`].
+    /// - If a previous step of the [`SteppedCall`] has trapped.
+    pub fn step<T>(
+        &mut self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<StepOutcome, Error> {
+        assert!(!self.trapped, "tried to step a trapped stepped call");
+        // Note: The stack of the stepped call refers to the instances of the store
+        //       owning the root function so it must not be executed with another store.
+        ctx.as_context().store.inner.resolve_func(&self.func);
+        if !self.finished {
+            match self.engine.step_func(ctx.as_context_mut(), &mut self.stack) {
+                Ok(finished) => self.finished = finished,
+                Err(trap) => {
+                    self.trapped = true;
+                    return Err(trap.into());
+                }
+            }
+        }
+        if !self.finished {
+            return Ok(StepOutcome::Running);
+        }
+        let func_type = *self.func.ty_dedup(ctx.as_context());
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_func_type_with(&func_type, |func_type| {
+                func_type.match_results(outputs, false)?;
+                func_type.prepare_outputs(outputs);
+                Ok::<(), Error>(())
+            })?;
+        self.engine
+            .write_stepped_results(&mut self.stack, func_type, outputs)?;
+        Ok(StepOutcome::Finished)
+    }
+
+    /// Returns `true` once the root function of the [`SteppedCall`] has returned.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the number of Wasm function frames on the call stack.
+    pub fn call_depth(&self) -> usize {
+        self.stack.calls.as_slice().len()
+    }
+
+    /// Returns the registers of the top-most Wasm function frame.
+    ///
+    /// # Note
+    ///
+    /// Register `r0` is the first element of the returned slice and holds the
+    /// first function parameter, followed by the remaining parameters, locals and
+    /// temporary registers. Returns an empty slice once the call has finished.
+    pub fn registers(&self) -> &[UntypedValue] {
+        if self.finished {
+            return &[];
+        }
+        self.stack.frame_registers()
+    }
+
+    /// Returns the value of the `index`-th parameter of the top-most Wasm function frame.
+    ///
+    /// Returns `None` if `index` is out of bounds for the parameters of the function
+    /// executed by the top-most frame or once the call has finished.
+    ///
+    /// # Note
+    ///
+    /// Since parameters are mutable registers the returned value reflects their
+    /// current value which might differ from the call arguments.
+    pub fn param(&self, ctx: impl AsContext, index: usize) -> Option<UntypedValue> {
+        if self.finished {
+            return None;
+        }
+        self.stack.frame_param(&ctx.as_context().store.inner, index)
+    }
+
+    /// Returns the [`Instruction2`] executed by the next step of the [`SteppedCall`] and its [`TrapLocation`].
+    ///
+    /// Returns `None` once the call has finished.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Instruction2`] cannot be located within the Wasm binary, for example
+    /// if trap locations are not enabled via [`Config::set_trap_locations`].
+    ///
+    /// [`Config::set_trap_locations`]: crate::Config::set_trap_locations
+    pub(super) fn next_instr(&self) -> Result<Option<(TrapLocation, Instruction2)>, Error> {
+        let frame = match self.stack.calls.peek() {
+            Some(frame) if !self.finished => frame,
+            _ => return Ok(None),
+        };
+        self.engine
+            .locate_instr_2(frame.instr_ptr())
+            .map(Some)
+            .ok_or_else(|| FuncBodyError::Unlocated.into())
+    }
+}
@@ -841,6 +841,55 @@ fn br_table_return() {
     assert_func_bodies(wasm, [expected]);
 }
 
+#[test]
+fn global_get_immutable() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (global $small i64 (i64.const 42))
+            (global $large i64 (i64.const 0x1_0000_0001))
+            (func (result i64)
+                (i64.add (global.get $small) (global.get $large))
+            )
+        )
+    "#,
+    );
+    let module = create_module(&test_config(), &wasm);
+    let engine = module.engine();
+    // Note: the constant value of `$large` has already been allocated during translation.
+    let large = engine.alloc_const(0x1_0000_0001_i64).unwrap();
+    let expected = [
+        Instruction::I64Const32(42),
+        Instruction::ConstRef(large),
+        Instruction::I64Add,
+        Instruction::Return(drop_keep(0, 1)),
+    ];
+    let (func_type, func_body) = module.internal_funcs().next().unwrap();
+    assert_func_body(engine, func_type, func_body, expected);
+}
+
+#[test]
+fn global_get_mutable_or_imported() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "imported" (global $imported i64))
+            (global $mutable (mut i64) (i64.const 42))
+            (func (result i64)
+                (i64.add (global.get $imported) (global.get $mutable))
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::GlobalGet(global_idx(0)),
+        Instruction::GlobalGet(global_idx(1)),
+        Instruction::I64Add,
+        Instruction::Return(drop_keep(0, 1)),
+    ];
+    assert_func_bodies(wasm, [expected]);
+}
+
 /// Returns the default [`FuelCosts`].
 pub fn fuel_costs() -> FuelCosts {
     *Config::default().fuel_costs()
@@ -1407,3 +1456,853 @@ fn metered_store_01() {
     ];
     assert_func_bodies_metered(wasm, [expected]);
 }
+
+/// Returns a copy of the Wasm function `func` that refers to a compiled function body that does not exist.
+fn corrupted_func(store: &mut crate::Store<()>, func: &crate::Func) -> crate::Func {
+    use crate::func::WasmFuncEntity;
+    let corrupted = match store.inner.resolve_func(func) {
+        FuncEntity::Wasm(wasm_func) => WasmFuncEntity::new(
+            *wasm_func.ty_dedup(),
+            CompiledFunc::from_u32(u32::MAX),
+            *wasm_func.instance(),
+        ),
+        FuncEntity::Host(_) => panic!("`func` must be a Wasm function"),
+    };
+    store.inner.alloc_func(FuncEntity::Wasm(corrupted))
+}
+
+#[test]
+fn invalid_func_body_is_internal_error() {
+    use crate::{errors::InternalError, Error, Linker, Store};
+    let wasm = wat2wasm(r#"(module (func (export "f")))"#);
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config
+            .set_engine_backend(backend)
+            .set_recoverable_internal_errors(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = instance.get_func(&store, "f").unwrap();
+        let corrupted = corrupted_func(&mut store, &func);
+        let error = corrupted.call(&mut store, &[], &mut []).unwrap_err();
+        assert!(
+            matches!(error, Error::Internal(InternalError::InvalidFuncBody)),
+            "{backend:?}: {error}"
+        );
+    }
+}
+
+#[test]
+fn invalid_nested_func_body_is_internal_error() {
+    use crate::{errors::InternalError, Error, Linker, Store};
+    let callee = wat2wasm(r#"(module (func (export "f")))"#);
+    let caller = wat2wasm(
+        r#"
+        (module
+            (import "env" "f" (func $f))
+            (func (export "call") (call $f))
+        )
+    "#,
+    );
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config
+            .set_engine_backend(backend)
+            .set_recoverable_internal_errors(true);
+        let engine = Engine::new(&config);
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let func = linker
+            .instantiate(&mut store, &Module::new(&engine, &callee[..]).unwrap())
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_func(&store, "f")
+            .unwrap();
+        let corrupted = corrupted_func(&mut store, &func);
+        linker.define("env", "f", corrupted).unwrap();
+        let call = linker
+            .instantiate(&mut store, &Module::new(&engine, &caller[..]).unwrap())
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_func(&store, "call")
+            .unwrap();
+        let error = call.call(&mut store, &[], &mut []).unwrap_err();
+        assert!(
+            matches!(error, Error::Internal(InternalError::InvalidFuncBody)),
+            "{backend:?}: {error}"
+        );
+    }
+}
+
+#[test]
+fn host_errors_are_not_internal_errors() {
+    use crate::{
+        core::{HostError, Trap},
+        errors::InternalError,
+        Caller,
+        Error,
+        Func,
+        Store,
+    };
+    use core::fmt;
+
+    /// A host error that looks like an [`InternalError`] of the engine.
+    #[derive(Debug)]
+    struct FakeInternalError(InternalError);
+
+    impl fmt::Display for FakeInternalError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl HostError for FakeInternalError {}
+
+    let mut config = Config::default();
+    config.set_recoverable_internal_errors(true);
+    let mut store = Store::new(&Engine::new(&config), ());
+    let func = Func::wrap(&mut store, |_caller: Caller<()>| -> Result<(), Trap> {
+        Err(Trap::from(FakeInternalError(
+            InternalError::InvalidFuncBody,
+        )))
+    });
+    let error = func.call(&mut store, &[], &mut []).unwrap_err();
+    assert!(matches!(error, Error::Trap(_)), "{error}");
+}
+
+#[test]
+fn call_arity_mismatch_is_internal_error() {
+    use crate::{errors::InternalError, AsContextMut, Error, Linker, Store, Value};
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "f") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    );
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config.set_engine_backend(backend);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = instance.get_func(&store, "f").unwrap();
+        // Bypass the type checks of `Func::call` to feed mismatching arities to the engine.
+        let params = [Value::I32(1), Value::I32(2), Value::I32(3)];
+        let mut results = [Value::I32(0), Value::I32(0)];
+        for (len_params, len_results) in [(1, 1), (3, 1), (2, 0), (2, 2)] {
+            let trap = engine
+                .execute_func(
+                    store.as_context_mut(),
+                    &func,
+                    &params[..len_params],
+                    &mut results[..len_results],
+                )
+                .unwrap_err();
+            assert!(
+                matches!(
+                    Error::from(trap),
+                    Error::Internal(InternalError::CallArityMismatch)
+                ),
+                "{backend:?}: ({len_params}, {len_results})"
+            );
+        }
+        let mut results = [Value::I32(0)];
+        engine
+            .execute_func(
+                store.as_context_mut(),
+                &func,
+                &params[..2],
+                &mut results[..],
+            )
+            .unwrap();
+        assert_eq!(results[0].i32(), Some(3));
+    }
+}
+
+#[test]
+fn call_arity_mismatch_of_func_body_is_internal_error() {
+    use crate::{
+        engine::regmach::bytecode::{Instruction as Instruction2, Register},
+        errors::InternalError,
+        func::WasmFuncEntity,
+        Error,
+        Linker,
+        Store,
+        Value,
+    };
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "f") (param i32 i32) (result i32)
+                (local.get 0)
+            )
+        )
+    "#,
+    );
+    let mut config = Config::default();
+    config.set_engine_backend(EngineBackend::RegisterMachine);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "f").unwrap();
+    // Create a copy of `f` with a corrupted function body that has
+    // fewer registers than `f` has parameters.
+    let corrupted = engine.alloc_func_2();
+    engine.init_func_2(
+        corrupted,
+        0,
+        1,
+        1,
+        [],
+        [Instruction2::return_reg(Register::from_i16(0))],
+    );
+    let corrupted = match store.inner.resolve_func(&func) {
+        FuncEntity::Wasm(wasm_func) => {
+            WasmFuncEntity::new(*wasm_func.ty_dedup(), corrupted, *wasm_func.instance())
+        }
+        FuncEntity::Host(_) => panic!("`f` must be a Wasm function"),
+    };
+    let corrupted = store.inner.alloc_func(FuncEntity::Wasm(corrupted));
+    let mut results = [Value::I32(0)];
+    let error = corrupted
+        .call(&mut store, &[Value::I32(1), Value::I32(2)], &mut results)
+        .unwrap_err();
+    assert!(
+        matches!(error, Error::Internal(InternalError::CallArityMismatch)),
+        "{error}"
+    );
+}
+
+#[test]
+// Note: with `checked-registers` the out of bounds access traps before it is executed.
+#[cfg(all(feature = "checked-execution", not(feature = "checked-registers")))]
+#[should_panic(expected = "checked execution: out of bounds access")]
+fn checked_execution_catches_out_of_bounds_register() {
+    use crate::{
+        engine::regmach::bytecode::{Instruction as Instruction2, Register},
+        func::WasmFuncEntity,
+        Linker,
+        Store,
+        Value,
+    };
+    let wasm = wat2wasm(r#"(module (func (export "f") (result i32) (i32.const 0)))"#);
+    let mut config = Config::default();
+    config
+        .set_engine_backend(EngineBackend::RegisterMachine)
+        .set_checked_execution(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "f").unwrap();
+    // Create a copy of `f` with a miscompiled function body that returns
+    // the value of a register that is out of bounds for its call frame.
+    let miscompiled = engine.alloc_func_2();
+    engine.init_func_2(
+        miscompiled,
+        0,
+        1,
+        1,
+        [],
+        [Instruction2::return_reg(Register::from_i16(100))],
+    );
+    let miscompiled = match store.inner.resolve_func(&func) {
+        FuncEntity::Wasm(wasm_func) => {
+            WasmFuncEntity::new(*wasm_func.ty_dedup(), miscompiled, *wasm_func.instance())
+        }
+        FuncEntity::Host(_) => panic!("`f` must be a Wasm function"),
+    };
+    let miscompiled = store.inner.alloc_func(FuncEntity::Wasm(miscompiled));
+    let mut results = [Value::I32(0)];
+    miscompiled.call(&mut store, &[], &mut results).unwrap();
+}
+
+#[test]
+#[cfg(feature = "checked-registers")]
+fn checked_registers_trap_out_of_bounds_register() {
+    use crate::{
+        core::{Pages, TrapCode},
+        engine::regmach::bytecode::{Const32, Instruction as Instruction2, Register},
+        func::WasmFuncEntity,
+        Error,
+        Linker,
+        Store,
+        Value,
+    };
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory (export "mem") 1)
+            (func (export "f") (result i32) (i32.const 0))
+        )
+    "#,
+    );
+    let mut config = Config::default();
+    config.set_engine_backend(EngineBackend::RegisterMachine);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "f").unwrap();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    // Create a copy of `f` with a corrupted function body that reads
+    // and writes registers that are out of bounds for its call frame.
+    let (func_type, instance) = match store.inner.resolve_func(&func) {
+        FuncEntity::Wasm(wasm_func) => (*wasm_func.ty_dedup(), *wasm_func.instance()),
+        FuncEntity::Host(_) => panic!("`f` must be a Wasm function"),
+    };
+    let r0 = Register::from_i16(0);
+    let r100 = Register::from_i16(100);
+    for instrs in [
+        &[
+            Instruction2::i32_add(r0, r100, r0),
+            Instruction2::return_reg(r0),
+        ][..],
+        &[
+            Instruction2::i32_add(r100, r0, r0),
+            Instruction2::return_reg(r0),
+        ],
+        &[Instruction2::copy(r0, r100), Instruction2::return_reg(r0)],
+        &[Instruction2::copy(r100, r0), Instruction2::return_reg(r0)],
+        &[
+            Instruction2::memory_grow(r0, r100),
+            Instruction2::return_reg(r0),
+        ],
+        &[
+            Instruction2::memory_grow_by(r100, 1),
+            Instruction2::return_reg(r0),
+        ],
+        // The upper half of the `v128` value at `r0` is read out of bounds
+        // which is only detected while executing the `v128.store`.
+        &[
+            Instruction2::copy_imm32(r0, 0),
+            Instruction2::v128_store(r0, Const32::from(0_u32)),
+            Instruction2::register(r0),
+            Instruction2::return_reg(r0),
+        ],
+    ] {
+        let compiled = engine.alloc_func_2();
+        engine.init_func_2(compiled, 0, 1, 1, [], instrs.iter().copied());
+        let corrupted = WasmFuncEntity::new(func_type, compiled, instance);
+        let corrupted = store.inner.alloc_func(FuncEntity::Wasm(corrupted));
+        let mut results = [Value::I32(0)];
+        let error = corrupted.call(&mut store, &[], &mut results).unwrap_err();
+        match error {
+            Error::Trap(trap) => assert_eq!(trap.trap_code(), Some(TrapCode::RegisterOutOfBounds)),
+            error => panic!("expected a trap but found: {error}"),
+        }
+        // The trap must be raised before `memory.grow` has any side effects.
+        assert_eq!(memory.current_pages(&store), Pages::new(1).unwrap());
+    }
+}
+
+/// Executes wrapping integer arithmetic with [`Config::set_checked_arithmetic`] enabled.
+///
+/// If `corrupt` is `true` the executor saturates instead of wrapping its results.
+///
+/// Returns the results of the exported functions applied to operands that overflow.
+#[cfg(feature = "checked-arithmetic")]
+fn execute_checked_arithmetic(corrupt: bool) -> Vec<i64> {
+    use crate::{Linker, Store, Value};
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "i32.add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+            (func (export "i32.sub_imm") (param i32) (result i32)
+                (i32.sub (local.get 0) (i32.const 1))
+            )
+            (func (export "i32.sub_imm_rev") (param i32) (result i32)
+                (i32.sub (i32.const -2) (local.get 0))
+            )
+            (func (export "i64.mul") (param i64 i64) (result i64)
+                (i64.mul (local.get 0) (local.get 1))
+            )
+            (func (export "i64.add_imm") (param i64) (result i64)
+                (i64.add (local.get 0) (i64.const 1))
+            )
+        )
+        "#,
+    );
+    let mut config = Config::default();
+    config
+        .set_engine_backend(EngineBackend::RegisterMachine)
+        .set_checked_arithmetic(true)
+        .set_corrupt_arithmetic(corrupt);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let mut call = |name: &str, inputs: &[Value]| {
+        let func = instance.get_func(&store, name).unwrap();
+        let mut results = [inputs[0].clone()];
+        func.call(&mut store, inputs, &mut results).unwrap();
+        match results[0] {
+            Value::I32(value) => i64::from(value),
+            Value::I64(value) => value,
+            ref value => panic!("unexpected result value: {value:?}"),
+        }
+    };
+    vec![
+        call("i32.add", &[Value::I32(i32::MAX), Value::I32(1)]),
+        call("i32.sub_imm", &[Value::I32(i32::MIN)]),
+        call("i32.sub_imm_rev", &[Value::I32(i32::MAX)]),
+        call("i64.mul", &[Value::I64(i64::MAX), Value::I64(3)]),
+        call("i64.add_imm", &[Value::I64(i64::MAX)]),
+    ]
+}
+
+#[test]
+#[cfg(feature = "checked-arithmetic")]
+fn checked_arithmetic_accepts_wrapping_results() {
+    let expected = vec![
+        i64::from(i32::MIN),
+        i64::from(i32::MAX),
+        i64::from(i32::MAX),
+        i64::MAX - 2,
+        i64::MIN,
+    ];
+    assert_eq!(execute_checked_arithmetic(false), expected);
+}
+
+#[test]
+#[cfg(feature = "checked-arithmetic")]
+#[should_panic(
+    expected = "checked arithmetic: wrapping `Add` of 2147483647 and 1 yielded 2147483647"
+)]
+fn checked_arithmetic_catches_miscompiled_arithmetic() {
+    execute_checked_arithmetic(true);
+}
+
+/// Calls a trivial Wasm function many times using `config` and checks the cached engine stacks.
+///
+/// Before that a deeply recursive Wasm function grows the value stack beyond its initial height.
+///
+/// Returns the value stack capacities of the cached stacks after the recursion and the last call.
+fn call_many_times(config: &Config) -> (Vec<usize>, Vec<usize>) {
+    use crate::{Linker, Store};
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $grow (export "grow") (param i32)
+                (if (local.get 0)
+                    (then (call $grow (i32.sub (local.get 0) (i32.const 1))))
+                )
+            )
+            (func (export "f") (param i32) (result i32)
+                (local.get 0)
+            )
+        )
+    "#,
+    );
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<i32, i32>(&store, "f").unwrap();
+    let capacities = || {
+        let stacks = engine.inner.stacks.lock();
+        assert!(stacks
+            .stacks
+            .iter()
+            .all(|stack| stack.frames.peek().is_none()));
+        assert!(stacks
+            .stacks2
+            .iter()
+            .all(|stack| stack.calls.as_slice().is_empty()));
+        stacks
+            .stacks
+            .iter()
+            .map(|stack| stack.values.capacity())
+            .chain(stacks.stacks2.iter().map(|stack| stack.values.capacity()))
+            .collect::<Vec<_>>()
+    };
+    instance
+        .get_typed_func::<i32, ()>(&store, "grow")
+        .unwrap()
+        .call(&mut store, 500)
+        .unwrap();
+    let first = capacities();
+    for n in 0..10_000 {
+        assert_eq!(func.call(&mut store, n).unwrap(), n);
+    }
+    (first, capacities())
+}
+
+#[test]
+fn stacks_are_reused_across_calls() {
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config.set_engine_backend(backend);
+        let (first, last) = call_many_times(&config);
+        assert_eq!(first.len(), 1);
+        assert!(first[0] > StackLimits::default().initial_value_stack_height);
+        assert_eq!(first, last);
+    }
+}
+
+#[test]
+fn stacks_are_not_cached() {
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config.set_engine_backend(backend).set_cached_stacks(0);
+        let (first, last) = call_many_times(&config);
+        assert!(first.is_empty());
+        assert!(last.is_empty());
+    }
+}
+
+/// Returns a Wasm module with many functions that use constant values and globals.
+fn many_funcs_wasm() -> Vec<u8> {
+    let mut wat = String::from(
+        r#"
+        (module
+            (global $g i64 (i64.const 0x7FFF_FFFF_FFFF))
+            (global $h (mut i32) (i32.const 0))
+        "#,
+    );
+    for n in 0..64_i64 {
+        wat.push_str(&format!(
+            r#"
+            (func (export "f{n}") (param i64) (result i64)
+                (i64.add (local.get 0) (i64.const {large}))
+                (i64.mul (global.get $g))
+                (i64.sub (i64.const {shared}))
+                (f64.const {n}.5)
+                (drop)
+                (global.set $h (i32.const {n}))
+            )
+            "#,
+            large = 0x1_0000_0000_i64 + n,
+            shared = 0x7_0000_0000_i64 + n % 4,
+        ));
+    }
+    wat.push(')');
+    wat2wasm(&wat)
+}
+
+/// Compiles `wasm` using `config` and returns the debug representations of all function bodies.
+///
+/// This includes the function local constant values of the register-machine backend.
+fn compiled_func_bodies(config: &Config, wasm: &[u8]) -> Vec<String> {
+    let module = create_module(config, wasm);
+    let engine = module.engine();
+    module
+        .internal_funcs()
+        .map(|(_, func_body)| match config.engine_backend() {
+            EngineBackend::StackMachine => {
+                let instrs: Vec<_> = (0..)
+                    .map_while(|index| engine.resolve_instr(func_body, index))
+                    .collect();
+                format!("{instrs:?}")
+            }
+            EngineBackend::RegisterMachine => engine.resolve_func_2(func_body, |func_body| {
+                format!("{:?} {:?}", func_body.instrs(), func_body.consts())
+            }),
+        })
+        .collect()
+}
+
+#[test]
+fn parallel_compilation_matches_serial_compilation() {
+    let wasm = many_funcs_wasm();
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config.set_engine_backend(backend);
+        let serial = compiled_func_bodies(&config, &wasm);
+        for threads in [2, 4, 100] {
+            config.set_compilation_threads(threads);
+            let parallel = compiled_func_bodies(&config, &wasm);
+            assert_eq!(serial.len(), 64);
+            assert_eq!(serial, parallel, "{backend:?} using {threads} threads");
+        }
+    }
+}
+
+#[test]
+fn parallel_compilation_reports_first_invalid_func() {
+    // Note: the first of the 4 threads is busy translating the large first function
+    //       while the other threads might already have failed at the function at index 5.
+    //       Still the error of the function at index 4 must be reported.
+    let large_body = "(drop (i32.add (local.get 0) (local.get 0)))".repeat(10_000);
+    let wasm = wat2wasm(&format!(
+        r#"
+        (module
+            (func (param i32) {large_body})
+            (func (result i32) (i32.const 1))
+            (func (result i32) (i32.const 2))
+            (func (result i32) (i32.const 3))
+            (func (result i32) (i64.const 4))
+            (func (result i32) (f32.const 5))
+        )
+    "#
+    ));
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        let mut config = Config::default();
+        config.set_engine_backend(backend);
+        let serial = Module::new(&Engine::new(&config), &wasm[..]).unwrap_err();
+        config.set_compilation_threads(4);
+        let parallel = Module::new(&Engine::new(&config), &wasm[..]).unwrap_err();
+        assert_eq!(serial.to_string(), parallel.to_string(), "{backend:?}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "after Engine::reset")]
+fn resolve_instr_after_reset_panics() {
+    let wasm = wat2wasm(r#"(module (func (result i32) (i32.const 0)))"#);
+    let module = create_module(&test_config(), &wasm);
+    let (_, func_body) = module.internal_funcs().next().unwrap();
+    let engine = module.engine();
+    assert!(engine.resolve_instr(func_body, 0).is_some());
+    engine.reset();
+    engine.resolve_instr(func_body, 0);
+}
+
+#[test]
+#[should_panic(expected = "after Engine::reset")]
+fn resolve_instr_2_after_reset_panics() {
+    let wasm = wat2wasm(r#"(module (func (result i32) (i32.const 0)))"#);
+    let mut config = Config::default();
+    config.set_engine_backend(EngineBackend::RegisterMachine);
+    let module = create_module(&config, &wasm);
+    let (_, func_body) = module.internal_funcs().next().unwrap();
+    let engine = module.engine();
+    assert!(engine.resolve_instr_2(func_body, 0).is_some());
+    engine.reset();
+    engine.resolve_instr_2(func_body, 0);
+}
+
+#[test]
+#[should_panic(expected = "after Engine::reset")]
+fn resolve_func_type_after_reset_panics() {
+    let engine = Engine::default();
+    let func_type = engine.alloc_func_type(FuncType::new([crate::core::ValueType::I32], []));
+    engine.reset();
+    engine.resolve_func_type(&func_type, Clone::clone);
+}
+
+#[test]
+fn reset_allows_compiling_new_modules() {
+    let wasm = wat2wasm(r#"(module (func (result i32) (i32.const 1)))"#);
+    let engine = Engine::new(&test_config());
+    let before = Module::new(&engine, &wasm[..]).unwrap();
+    let (_, func_before) = before.internal_funcs().next().unwrap();
+    let memory_usage = engine.estimate_memory_usage().total();
+    engine.reset();
+    assert!(engine.estimate_memory_usage().total() < memory_usage);
+    let after = Module::new(&engine, &wasm[..]).unwrap();
+    let (func_type, func_after) = after.internal_funcs().next().unwrap();
+    assert_ne!(func_before, func_after);
+    assert_func_body(
+        &engine,
+        func_type,
+        func_after,
+        [instr::i32_const(1), Instruction::Return(drop_keep(0, 1))],
+    );
+}
+
+/// The Wasm shift and rotate operators of the `i32` and `i64` types.
+const SHIFT_OPS: [&str; 5] = ["shl", "shr_s", "shr_u", "rotl", "rotr"];
+
+/// Returns a Wasm module with a single function shifting its `ty` parameter by `amount` via `op`.
+fn shift_by_const(ty: &str, op: &str, amount: &str) -> Vec<u8> {
+    wat2wasm(&format!(
+        r#"
+        (module
+            (func (param {ty}) (result {ty})
+                ({ty}.{op} (local.get 0) ({ty}.const {amount}))
+            )
+        )
+        "#,
+    ))
+}
+
+#[test]
+fn shift_by_zero_is_noop() {
+    let expected = [instr::local_get(1), Instruction::Return(drop_keep(1, 1))];
+    for op in SHIFT_OPS {
+        // Note: shift amounts are taken modulo the bit width of the shifted type.
+        for amount in ["0", "32", "-32"] {
+            assert_func_bodies(shift_by_const("i32", op, amount), [expected]);
+        }
+        for amount in ["0", "64", "0x1_0000_0000"] {
+            assert_func_bodies(shift_by_const("i64", op, amount), [expected]);
+        }
+    }
+}
+
+#[test]
+fn shift_by_nonzero_is_kept() {
+    let ops = [
+        Instruction::I32Shl,
+        Instruction::I32ShrS,
+        Instruction::I32ShrU,
+        Instruction::I32Rotl,
+        Instruction::I32Rotr,
+    ];
+    for (op, instr) in SHIFT_OPS.into_iter().zip(ops) {
+        let expected = [
+            instr::local_get(1),
+            instr::i32_const(33),
+            instr,
+            Instruction::Return(drop_keep(1, 1)),
+        ];
+        assert_func_bodies(shift_by_const("i32", op, "33"), [expected]);
+    }
+    let ops = [
+        Instruction::I64Shl,
+        Instruction::I64ShrS,
+        Instruction::I64ShrU,
+        Instruction::I64Rotl,
+        Instruction::I64Rotr,
+    ];
+    for (op, instr) in SHIFT_OPS.into_iter().zip(ops) {
+        let expected = [
+            instr::local_get(1),
+            Instruction::I64Const32(1),
+            instr,
+            Instruction::Return(drop_keep(1, 1)),
+        ];
+        assert_func_bodies(shift_by_const("i64", op, "1"), [expected]);
+    }
+}
+
+#[test]
+fn shift_by_zero_after_label_is_kept() {
+    // The shift amount is the result of the block which might be provided by branches.
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32) (result i32)
+                (local.get 0)
+                (block (result i32)
+                    (i32.const 0)
+                )
+                (i32.shl)
+            )
+        )
+        "#,
+    );
+    let expected = [
+        instr::local_get(1),
+        instr::i32_const(0),
+        Instruction::I32Shl,
+        Instruction::Return(drop_keep(1, 1)),
+    ];
+    assert_func_bodies(wasm, [expected]);
+}
+
+/// Returns a Wasm module with a single function comparing its `ty` parameter with `rhs` via `op`.
+fn cmp_with_const(ty: &str, op: &str, rhs: &str) -> Vec<u8> {
+    wat2wasm(&format!(
+        r#"
+        (module
+            (func (param {ty}) (result i32)
+                ({ty}.{op} (local.get 0) ({ty}.const {rhs}))
+            )
+        )
+        "#,
+    ))
+}
+
+#[test]
+fn cmp_with_extreme_const_is_folded() {
+    let cases = [
+        ("lt_u", "0", false),
+        ("ge_u", "0", true),
+        ("gt_u", "-1", false),
+        ("le_u", "-1", true),
+    ];
+    let signed_cases = |min, max| {
+        [
+            ("lt_s", min, false),
+            ("ge_s", min, true),
+            ("gt_s", max, false),
+            ("le_s", max, true),
+        ]
+    };
+    let i32_cases = cases
+        .into_iter()
+        .chain(signed_cases("-0x8000_0000", "0x7FFF_FFFF"))
+        .map(|(op, rhs, result)| ("i32", op, rhs, result));
+    let i64_cases = cases
+        .into_iter()
+        .chain(signed_cases(
+            "-0x8000_0000_0000_0000",
+            "0x7FFF_FFFF_FFFF_FFFF",
+        ))
+        .map(|(op, rhs, result)| ("i64", op, rhs, result));
+    for (ty, op, rhs, result) in i32_cases.chain(i64_cases) {
+        let expected = [
+            instr::local_get(1),
+            Instruction::Drop,
+            instr::i32_const(i32::from(result)),
+            Instruction::Return(drop_keep(1, 1)),
+        ];
+        assert_func_bodies(cmp_with_const(ty, op, rhs), [expected]);
+    }
+}
+
+#[test]
+fn cmp_with_other_const_is_kept() {
+    let expected = [
+        instr::local_get(1),
+        instr::i32_const(1),
+        Instruction::I32LtU,
+        Instruction::Return(drop_keep(1, 1)),
+    ];
+    assert_func_bodies(cmp_with_const("i32", "lt_u", "1"), [expected]);
+    let expected = [
+        instr::local_get(1),
+        instr::i32_const(0),
+        Instruction::I32LtS,
+        Instruction::Return(drop_keep(1, 1)),
+    ];
+    assert_func_bodies(cmp_with_const("i32", "lt_s", "0"), [expected]);
+    let expected = [
+        instr::local_get(1),
+        Instruction::I64Const32(-1),
+        Instruction::I64GtS,
+        Instruction::Return(drop_keep(1, 1)),
+    ];
+    assert_func_bodies(cmp_with_const("i64", "gt_s", "-1"), [expected]);
+}
@@ -10,12 +10,14 @@ use crate::{
             FuncTranslator as RegisterMachineFuncTranslator,
             FuncTranslatorAllocations as RegisterMachineFuncTranslatorAllocations,
         },
+        PeepholeReport,
     },
-    module::{FuncIdx, ModuleResources, ReusableAllocations},
+    module::{ensure_supported_value_type, FuncIdx, ModuleResources, ReusableAllocations},
     Engine,
     EngineBackend,
 };
-use wasmparser::{BinaryReaderError, VisitOperator};
+use alloc::boxed::Box;
+use wasmparser::{BinaryReaderError, OperatorsReader, ValType, VisitOperator};
 
 /// The used function validator type.
 type FuncValidator = wasmparser::FuncValidator<wasmparser::ValidatorResources>;
@@ -25,11 +27,12 @@ type FuncValidator = wasmparser::FuncValidator<wasmparser::ValidatorResources>;
 /// # Note
 ///
 /// This is chosen via [`Config`](crate::Config) at [`Engine`] creation.
+/// Both translators are boxed since they differ a lot in size.
 enum ChosenFuncTranslator<'parser> {
     /// The function translator of `wasmi`'s [`EngineBackend::StackMachine`].
-    StackMachine(StackMachineFuncTranslator<'parser>),
+    StackMachine(Box<StackMachineFuncTranslator<'parser>>),
     /// The function translator of `wasmi`'s [`EngineBackend::RegisterMachine`].
-    RegisterMachine(RegisterMachineFuncTranslator<'parser>),
+    RegisterMachine(Box<RegisterMachineFuncTranslator<'parser>>),
 }
 
 /// The chosen function translation allocations [`EngineBackend`].
@@ -52,6 +55,20 @@ impl ChosenFuncTranslatorAllocations {
             }
         }
     }
+
+    /// Returns the [`PeepholeReport`] of all functions translated using these allocations.
+    ///
+    /// # Note
+    ///
+    /// The [`EngineBackend::StackMachine`] does not collect peephole optimization counters.
+    pub fn peephole_report(&self) -> PeepholeReport {
+        match &self.inner {
+            ChosenFuncTranslatorAllocationsInner::StackMachine(_) => PeepholeReport::default(),
+            ChosenFuncTranslatorAllocationsInner::RegisterMachine(allocations) => {
+                allocations.peephole_report()
+            }
+        }
+    }
 }
 
 impl From<ChosenFuncTranslatorAllocationsInner> for ChosenFuncTranslatorAllocations {
@@ -102,6 +119,15 @@ pub struct FuncBuilder<'parser> {
     validator: FuncValidator,
     /// The chosen function translator.
     translator: ChosenFuncTranslator<'parser>,
+    /// Is `true` if the emulated value stack height is checked after every operator.
+    strict_stack_height_checks: bool,
+    /// Is `true` if the Wasm binary offset of every translated operator is recorded.
+    trap_locations: bool,
+    /// Is `true` if bounds checks of counted loops are hoisted in front of the loop.
+    hoist_loop_bounds_checks: bool,
+    /// The number of operators to translate until the emulated value stack is corrupted if any.
+    #[cfg(test)]
+    corrupt_stack_height_after: Option<usize>,
 }
 
 impl<'parser> FuncBuilder<'parser> {
@@ -115,30 +141,38 @@ impl<'parser> FuncBuilder<'parser> {
         allocations: ChosenFuncTranslatorAllocations,
     ) -> Result<Self, TranslationError> {
         let engine_backend = res.engine().config().engine_backend();
+        let strict_stack_height_checks = res.engine().config().get_strict_stack_height_checks();
+        let trap_locations = res.engine().config().get_trap_locations();
+        let hoist_loop_bounds_checks = res.engine().config().get_hoist_loop_bounds_checks();
         let translator = match allocations.inner {
             ChosenFuncTranslatorAllocationsInner::StackMachine(allocations) => {
                 debug_assert!(matches!(engine_backend, EngineBackend::StackMachine));
-                ChosenFuncTranslator::StackMachine(StackMachineFuncTranslator::new(
+                ChosenFuncTranslator::StackMachine(Box::new(StackMachineFuncTranslator::new(
                     func,
                     compiled_func,
                     res,
                     allocations,
-                ))
+                )?))
             }
             ChosenFuncTranslatorAllocationsInner::RegisterMachine(allocations) => {
                 debug_assert!(matches!(engine_backend, EngineBackend::RegisterMachine));
-                ChosenFuncTranslator::RegisterMachine(RegisterMachineFuncTranslator::new(
+                ChosenFuncTranslator::RegisterMachine(Box::new(RegisterMachineFuncTranslator::new(
                     func,
                     compiled_func_2,
                     res,
                     allocations,
-                )?)
+                )?))
             }
         };
         Ok(Self {
             pos: 0,
             validator,
             translator,
+            strict_stack_height_checks,
+            trap_locations,
+            hoist_loop_bounds_checks,
+            #[cfg(test)]
+            corrupt_stack_height_after: res.engine().config().get_corrupt_stack_height_after(),
         })
     }
 
@@ -150,6 +184,7 @@ impl<'parser> FuncBuilder<'parser> {
         value_type: wasmparser::ValType,
     ) -> Result<(), TranslationError> {
         self.validator.define_locals(offset, amount, value_type)?;
+        ensure_supported_value_type(value_type)?;
         match &mut self.translator {
             ChosenFuncTranslator::StackMachine(translator) => translator.register_locals(amount),
             ChosenFuncTranslator::RegisterMachine(translator) => {
@@ -181,6 +216,26 @@ impl<'parser> FuncBuilder<'parser> {
     /// Updates the current position within the Wasm binary while parsing operators.
     pub fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        if let ChosenFuncTranslator::RegisterMachine(translator) = &mut self.translator {
+            translator.update_pos(pos);
+        }
+    }
+
+    /// Prepares the translation of the Wasm operators read by `reader`.
+    ///
+    /// # Note
+    ///
+    /// This analyzes the counted loops of the function body if enabled via
+    /// [`Config::set_hoist_loop_bounds_checks`]. Has no effect on the stack-machine backend.
+    ///
+    /// [`Config::set_hoist_loop_bounds_checks`]: crate::Config::set_hoist_loop_bounds_checks
+    pub fn prepare_operators(&mut self, reader: &OperatorsReader<'parser>) {
+        if !self.hoist_loop_bounds_checks {
+            return;
+        }
+        if let ChosenFuncTranslator::RegisterMachine(translator) = &mut self.translator {
+            translator.analyze_loops(reader.clone());
+        }
     }
 
     /// Returns the current position within the Wasm binary while parsing operators.
@@ -222,12 +277,111 @@ impl<'parser> FuncBuilder<'parser> {
         T2: FnOnce(&mut RegisterMachineFuncTranslator<'parser>) -> Result<(), TranslationError>,
     {
         validate(&mut self.validator)?;
+        if self.trap_locations {
+            match &mut self.translator {
+                ChosenFuncTranslator::StackMachine(translator) => {
+                    translator.record_wasm_offset(self.pos)
+                }
+                ChosenFuncTranslator::RegisterMachine(translator) => {
+                    translator.record_wasm_offset(self.pos)
+                }
+            }
+        }
         match &mut self.translator {
             ChosenFuncTranslator::StackMachine(translator) => translate(translator)?,
             ChosenFuncTranslator::RegisterMachine(translator) => translate2(translator)?,
         }
+        #[cfg(test)]
+        self.try_corrupt_stack_height();
+        if self.strict_stack_height_checks {
+            self.check_stack_height()?;
+        }
         Ok(())
     }
+
+    /// Corrupts the emulated value stack of the translator once the configured amount of operators has been translated.
+    ///
+    /// # Note
+    ///
+    /// This simulates a buggy translation step for testing purposes.
+    #[cfg(test)]
+    fn try_corrupt_stack_height(&mut self) {
+        match &mut self.corrupt_stack_height_after {
+            Some(0) => {
+                self.corrupt_stack_height_after = None;
+                match &mut self.translator {
+                    ChosenFuncTranslator::StackMachine(translator) => {
+                        translator.corrupt_stack_height()
+                    }
+                    ChosenFuncTranslator::RegisterMachine(translator) => {
+                        translator.corrupt_stack_height()
+                    }
+                }
+            }
+            Some(amount) => *amount -= 1,
+            None => {}
+        }
+    }
+
+    /// Checks that the emulated value stack height of the translator matches validation.
+    ///
+    /// # Note
+    ///
+    /// Unreachable code and the end of the function are not checked since
+    /// the translator does not emulate the value stack for them.
+    ///
+    /// # Errors
+    ///
+    /// If the emulated value stack height differs from the validated operand stack height.
+    fn check_stack_height(&self) -> Result<(), TranslationError> {
+        if self.validator.control_stack_height() == 0 {
+            return Ok(());
+        }
+        let found = match &self.translator {
+            ChosenFuncTranslator::StackMachine(translator) => translator.stack_height(),
+            ChosenFuncTranslator::RegisterMachine(translator) => translator.stack_height(),
+        };
+        let height = self.validator.operand_stack_height() as usize;
+        // The register-machine occupies two emulated value stack entries per `v128` operand.
+        let v128_operands = (0..height)
+            .filter(|&depth| self.operand_is_v128(depth))
+            .count();
+        check_stack_height(height + v128_operands, found)
+    }
+
+    /// Returns `true` if the operand at `depth` of the validated operand stack is a `v128` value.
+    fn operand_is_v128(&self, depth: usize) -> bool {
+        matches!(
+            self.validator.get_operand_type(depth),
+            Some(Some(ValType::V128))
+        )
+    }
+}
+
+/// Returns an error if `block_type` has a `v128` result which `wasmi` does not support.
+fn ensure_supported_block_type(block_type: wasmparser::BlockType) -> Result<(), TranslationError> {
+    match block_type {
+        wasmparser::BlockType::Type(ValType::V128) => {
+            Err(TranslationError::unsupported_block_type(block_type))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the emulated value stack height `found` matches the `expected` height.
+///
+/// A `found` height of `None` signals unreachable code which is not checked.
+///
+/// # Errors
+///
+/// If `found` differs from `expected`.
+fn check_stack_height(expected: usize, found: Option<usize>) -> Result<(), TranslationError> {
+    match found {
+        Some(found) if found != expected => {
+            Err(TranslationError::stack_height_mismatch(expected, found))
+        }
+        _ => Ok(()),
+    }
 }
 
 macro_rules! impl_visit_operator {
@@ -245,6 +399,58 @@ macro_rules! impl_visit_operator {
         }
         impl_visit_operator!($($rest)*);
     };
+    ( @mvp Drop => $visit:ident $($rest:tt)* ) => {
+        // The register-machine occupies two emulated value stack entries per `v128` value.
+        fn $visit(&mut self) -> Self::Output {
+            let offset = self.current_pos();
+            let is_v128 = self.operand_is_v128(0);
+            self.validate_then_translate(
+                |validator| validator.visitor(offset).$visit(),
+                |translator| translator.$visit(),
+                |translator| {
+                    if is_v128 {
+                        translator.$visit()?;
+                    }
+                    translator.$visit().map_err(Into::into)
+                },
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
+    ( @mvp Select => $visit:ident $($rest:tt)* ) => {
+        fn $visit(&mut self) -> Self::Output {
+            let offset = self.current_pos();
+            let is_v128 = self.operand_is_v128(1);
+            self.validate_then_translate(
+                |validator| validator.visitor(offset).$visit(),
+                |translator| translator.$visit(),
+                |translator| {
+                    if is_v128 {
+                        return Err(TranslationError::unsupported_value_type(ValType::V128));
+                    }
+                    translator.$visit().map_err(Into::into)
+                },
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
+    ( @mvp $op:ident { blockty: $argty:ty } => $visit:ident $($rest:tt)* ) => {
+        fn $visit(&mut self, blockty: $argty) -> Self::Output {
+            let offset = self.current_pos();
+            self.validate_then_translate(
+                move |validator| validator.visitor(offset).$visit(blockty),
+                move |translator| {
+                    ensure_supported_block_type(blockty)?;
+                    translator.$visit(blockty)
+                },
+                move |translator| {
+                    ensure_supported_block_type(blockty)?;
+                    translator.$visit(blockty).map_err(Into::into)
+                },
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
     ( @mvp $($rest:tt)* ) => {
         impl_visit_operator!(@@supported $($rest)*);
     };
@@ -257,12 +463,62 @@ macro_rules! impl_visit_operator {
     ( @bulk_memory $($rest:tt)* ) => {
         impl_visit_operator!(@@supported $($rest)*);
     };
+    ( @reference_types TypedSelect { ty: $argty:ty } => $visit:ident $($rest:tt)* ) => {
+        fn $visit(&mut self, ty: $argty) -> Self::Output {
+            let offset = self.current_pos();
+            self.validate_then_translate(
+                move |validator| validator.visitor(offset).$visit(ty),
+                move |translator| {
+                    ensure_supported_value_type(ty)?;
+                    translator.$visit(ty)
+                },
+                move |translator| {
+                    ensure_supported_value_type(ty)?;
+                    translator.$visit(ty).map_err(Into::into)
+                },
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
     ( @reference_types $($rest:tt)* ) => {
         impl_visit_operator!(@@supported $($rest)*);
     };
     ( @tail_call $($rest:tt)* ) => {
         impl_visit_operator!(@@supported $($rest)*);
     };
+    ( @simd $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
+        // Only the register-machine supports a few operators of the `simd` proposal.
+        fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
+            let offset = self.current_pos();
+            self.validate_then_translate(
+                move |validator| validator.visitor(offset).$visit($($($arg),*)?),
+                move |_translator| Err(TranslationError::unsupported_operator(stringify!($op))),
+                move |translator| translator.$visit($($($arg),*)?).map_err(Into::into),
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
+    ( @threads MemoryAtomicNotify $($rest:tt)* ) => {
+        impl_visit_operator!(@@supported MemoryAtomicNotify $($rest)*);
+    };
+    ( @threads MemoryAtomicWait32 $($rest:tt)* ) => {
+        impl_visit_operator!(@@supported MemoryAtomicWait32 $($rest)*);
+    };
+    ( @threads MemoryAtomicWait64 $($rest:tt)* ) => {
+        impl_visit_operator!(@@supported MemoryAtomicWait64 $($rest)*);
+    };
+    ( @threads $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
+        // Only `memory.atomic.notify` and `memory.atomic.wait` of the `threads` proposal are supported so far.
+        fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
+            let offset = self.current_pos();
+            self.validate_then_translate(
+                move |validator| validator.visitor(offset).$visit($($($arg),*)?),
+                move |_translator| Err(TranslationError::unsupported_operator(stringify!($op))),
+                move |_translator| Err(TranslationError::unsupported_operator(stringify!($op))),
+            )
+        }
+        impl_visit_operator!($($rest)*);
+    };
     ( @@supported $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
             let offset = self.current_pos();
@@ -290,3 +546,86 @@ impl<'a> VisitOperator<'a> for FuncBuilder<'a> {
 
     wasmparser::for_each_operator!(impl_visit_operator);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, Module};
+
+    #[test]
+    fn strict_stack_height_checks_accept_valid_input() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (memory 1)
+                (func $f (param i32 i32) (result i32 i32)
+                    (local.get 1) (local.get 0)
+                )
+                (func (export "g") (param $x i32) (result i32)
+                    (local $t i32)
+                    (block $exit (result i32)
+                        (loop $continue
+                            (local.set $t (i32.add (local.get $t) (i32.load (local.get $x))))
+                            (br_if $continue (local.tee $x (i32.sub (local.get $x) (i32.const 1))))
+                        )
+                        (if (result i32) (local.get $t)
+                            (then (call $f (local.get $t) (local.get $x)) (drop))
+                            (else (br $exit (i32.const 0)))
+                        )
+                    )
+                    (select (i32.const 1) (local.get $t))
+                    (unreachable)
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+            let mut config = Config::default();
+            config.set_engine_backend(backend);
+            config.set_strict_stack_height_checks(true);
+            let engine = Engine::new(&config);
+            Module::new(&engine, &wasm[..]).unwrap();
+        }
+    }
+
+    #[test]
+    fn strict_stack_height_checks_catch_corrupted_translation() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (func (param i32 i32) (result i32)
+                    (i32.add (local.get 0) (local.get 1))
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+            let mut config = Config::default();
+            config.set_engine_backend(backend);
+            config.set_strict_stack_height_checks(true);
+            // Note: corrupts the translation of the second `local.get`.
+            config.set_corrupt_stack_height_after(1);
+            let engine = Engine::new(&config);
+            let error = Module::new(&engine, &wasm[..]).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("emulated value stack height 3 does not match validated height 2"),
+                "{backend:?}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_stack_height_checks_catch_mismatch() {
+        assert!(check_stack_height(2, Some(2)).is_ok());
+        assert!(check_stack_height(2, None).is_ok());
+        assert!(check_stack_height(2, Some(3))
+            .unwrap_err()
+            .to_string()
+            .contains("does not match"));
+        assert!(check_stack_height(2, Some(1)).is_err());
+    }
+}
@@ -1,14 +1,19 @@
 use super::errors::{
     FuelError,
+    FuncBodyError,
     FuncError,
     GlobalError,
     InstantiationError,
+    InternalError,
     LinkerError,
     MemoryError,
     ModuleError,
     TableError,
 };
-use crate::core::{Trap, TrapCode};
+use crate::{
+    core::{Trap, TrapCode},
+    engine::EngineInternalError,
+};
 use core::{fmt, fmt::Display};
 
 /// An error that may occur upon operating on Wasm modules or module instances.
@@ -31,8 +36,18 @@ pub enum Error {
     Store(FuelError),
     /// A function error.
     Func(FuncError),
+    /// An error upon exporting or reloading a compiled function body.
+    FuncBody(FuncBodyError),
     /// A trap as defined by the WebAssembly specification.
     Trap(Trap),
+    /// A violated internal invariant of the `wasmi` engine.
+    ///
+    /// # Note
+    ///
+    /// This is only reported if enabled via [`Config::set_recoverable_internal_errors`].
+    ///
+    /// [`Config::set_recoverable_internal_errors`]: crate::Config::set_recoverable_internal_errors
+    Internal(InternalError),
 }
 
 #[cfg(feature = "std")]
@@ -47,16 +62,21 @@ impl Display for Error {
             Self::Table(error) => Display::fmt(error, f),
             Self::Linker(error) => Display::fmt(error, f),
             Self::Func(error) => Display::fmt(error, f),
+            Self::FuncBody(error) => Display::fmt(error, f),
             Self::Instantiation(error) => Display::fmt(error, f),
             Self::Module(error) => Display::fmt(error, f),
             Self::Store(error) => Display::fmt(error, f),
+            Self::Internal(error) => Display::fmt(error, f),
         }
     }
 }
 
 impl From<Trap> for Error {
     fn from(error: Trap) -> Self {
-        Self::Trap(error)
+        match error.downcast_ref::<EngineInternalError>() {
+            Some(internal) => Self::Internal(internal.error()),
+            None => Self::Trap(error),
+        }
     }
 }
 
@@ -108,6 +128,18 @@ impl From<FuncError> for Error {
     }
 }
 
+impl From<FuncBodyError> for Error {
+    fn from(error: FuncBodyError) -> Self {
+        Self::FuncBody(error)
+    }
+}
+
+impl From<InternalError> for Error {
+    fn from(error: InternalError) -> Self {
+        Self::Internal(error)
+    }
+}
+
 /// An error that can occur upon `memory.grow` or `table.grow`.
 #[derive(Copy, Clone)]
 pub enum EntityGrowError {
@@ -13,6 +13,8 @@ pub enum FuncError {
     MismatchingResultType,
     /// Specified an incorrect number of results.
     MismatchingResultLen,
+    /// Stepped execution is not supported by the engine backend.
+    UnsupportedSteppedCall,
 }
 
 impl Display for FuncError {
@@ -33,6 +35,12 @@ impl Display for FuncError {
             FuncError::MismatchingResultLen => {
                 write!(f, "encountered an incorrect number of results")
             }
+            FuncError::UnsupportedSteppedCall => {
+                write!(
+                    f,
+                    "stepped execution is only supported by the register-machine engine backend"
+                )
+            }
         }
     }
 }
@@ -22,8 +22,15 @@ use super::{
     StoreContext,
     Stored,
 };
-use crate::{core::Trap, engine::ResumableCall, Engine, Error, Value};
-use alloc::{boxed::Box, sync::Arc};
+use crate::{
+    core::Trap,
+    engine::{ResumableCall, SteppedCall},
+    Engine,
+    EngineBackend,
+    Error,
+    Value,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
 use wasmi_arena::ArenaIndex;
 
@@ -376,6 +383,30 @@ impl Func {
             .resolve_func_type(self.ty_dedup(&ctx))
     }
 
+    /// Returns `true` if the [`Func`] is defined by a Wasm module.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Func`].
+    pub fn is_wasm(&self, ctx: impl AsContext) -> bool {
+        matches!(
+            ctx.as_context().store.inner.resolve_func(self),
+            FuncEntity::Wasm(_)
+        )
+    }
+
+    /// Returns `true` if the [`Func`] is a host function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Func`].
+    pub fn is_host(&self, ctx: impl AsContext) -> bool {
+        matches!(
+            ctx.as_context().store.inner.resolve_func(self),
+            FuncEntity::Host(_)
+        )
+    }
+
     /// Calls the Wasm or host function with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
@@ -406,6 +437,49 @@ impl Func {
         Ok(())
     }
 
+    /// Calls the Wasm or host function with the given inputs.
+    ///
+    /// The `outputs` buffer is cleared and then filled with the results of the call.
+    ///
+    /// # Note
+    ///
+    /// This is useful to call the same function many times in a row since
+    /// `outputs` keeps its capacity and thus only allocates if it is too small
+    /// to hold the results of the function.
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Trap`].
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    pub fn call_into_vec<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut Vec<Value>,
+    ) -> Result<(), Error> {
+        let fn_type = self.ty_dedup(ctx.as_context());
+        ctx.as_context().store.inner.resolve_func_type_with(
+            fn_type,
+            |func_type| -> Result<(), FuncError> {
+                func_type.match_params(inputs)?;
+                outputs.clear();
+                outputs.extend(func_type.results().iter().copied().map(Value::default));
+                Ok(())
+            },
+        )?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func(
+            ctx.as_context_mut(),
+            self,
+            inputs,
+            &mut outputs[..],
+        )?;
+        Ok(())
+    }
+
     /// Calls the Wasm or host function with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
@@ -446,6 +520,52 @@ impl Func {
             .map(ResumableCall::new)
     }
 
+    /// Calls the Wasm or host function with the given inputs step by step.
+    ///
+    /// Returns a [`SteppedCall`] handle that executes one `wasmi` bytecode
+    /// instruction per [`SteppedCall::step`] and allows to inspect the
+    /// registers of the executing function frame in between steps.
+    ///
+    /// # Note
+    ///
+    /// This is a non-standard debugging API that is only supported by the
+    /// register-machine engine backend. Host functions called via this API are
+    /// executed entirely before the [`SteppedCall`] is returned.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Engine`] of `ctx` does not use [`EngineBackend::RegisterMachine`].
+    /// - If a called host function returned a [`Trap`].
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`EngineBackend::RegisterMachine`]: crate::EngineBackend::RegisterMachine
+    pub fn call_stepped<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+    ) -> Result<SteppedCall, Error> {
+        let backend = ctx.as_context().store.engine().config().engine_backend();
+        if !matches!(backend, EngineBackend::RegisterMachine) {
+            return Err(Error::from(FuncError::UnsupportedSteppedCall));
+        }
+        let fn_type = self.ty_dedup(ctx.as_context());
+        let len_results = ctx.as_context().store.inner.resolve_func_type_with(
+            fn_type,
+            |func_type| -> Result<usize, FuncError> {
+                func_type.match_params(inputs)?;
+                Ok(func_type.results().len())
+            },
+        )?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        let engine = ctx.as_context().store.engine().clone();
+        let stack = engine.init_stepped_func(ctx.as_context_mut(), self, inputs, len_results)?;
+        Ok(SteppedCall::new(engine, *self, stack))
+    }
+
     /// Verify that the `inputs` and `outputs` value types match the function signature.
     ///
     /// Since [`Func`] is a dynamically typed function instance there is
@@ -177,6 +177,28 @@ impl Instance {
             .get_func(index)
     }
 
+    /// Returns the global variable at the `index` if any.
+    ///
+    /// The `index` refers to the global index space of the instantiated [`Module`]
+    /// which starts with the imported global variables followed by the ones defined
+    /// by the [`Module`] itself. This allows to access global variables that are not
+    /// exported, for example in order to snapshot and restore global state.
+    ///
+    /// Reads and writes go through [`Global::get`] and [`Global::set`] and are
+    /// thus subject to their mutability and type checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub fn get_global_by_index(&self, store: impl AsContext, index: u32) -> Option<Global> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .get_global(index)
+    }
+
     /// Returns the value exported to the given `name` if any.
     ///
     /// # Panics
@@ -76,7 +76,7 @@
     clippy::default_trait_access,
     clippy::items_after_statements
 )]
-#![recursion_limit = "750"]
+#![recursion_limit = "1000"]
 
 #[cfg(not(feature = "std"))]
 #[macro_use]
@@ -109,6 +109,7 @@ pub use wasmi_core as core;
 /// Defines some errors that may occur upon interaction with `wasmi`.
 pub mod errors {
     pub use super::{
+        engine::{FuncBodyError, InternalError},
         func::FuncError,
         global::GlobalError,
         linker::LinkerError,
@@ -122,14 +123,25 @@ pub mod errors {
 pub use self::{
     engine::{
         Config,
+        ConstDedup,
         Engine,
         EngineBackend,
         FuelConsumptionMode,
+        FuncInstr,
+        FuncInstrs,
+        InstructionProfile,
+        MemoryUsage,
+        PeepholeReport,
+        RecordedCall,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
+        StepOutcome,
+        SteppedCall,
+        TrapReproduction,
         TypedResumableCall,
         TypedResumableInvocation,
+        BYTECODE_VERSION,
     },
     error::Error,
     externref::ExternRef,
@@ -150,7 +162,7 @@ pub use self::{
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
     linker::Linker,
-    memory::{Memory, MemoryType},
+    memory::{Memory, MemorySnapshot, MemoryType},
     module::{
         ExportType,
         ImportType,
@@ -160,10 +172,12 @@ pub use self::{
         ModuleImportsIter,
         Read,
     },
-    store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
-    table::{Table, TableType},
+    store::{AsContext, AsContextMut, CallTransition, Store, StoreContext, StoreContextMut},
+    table::{Table, TableSnapshot, TableType},
     value::Value,
 };
+#[cfg(feature = "test-utils")]
+pub use self::engine::ExecutionOutcome;
 use self::{
     func::{FuncEntity, FuncIdx},
     global::{GlobalEntity, GlobalIdx},
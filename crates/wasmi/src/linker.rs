@@ -1,7 +1,7 @@
 use crate::{
     core::Trap,
     func::{FuncEntity, HostFuncEntity, HostFuncTrampolineEntity},
-    module::{ImportName, ImportType},
+    module::{ImportName, ImportType, InstantiationError},
     AsContext,
     AsContextMut,
     Caller,
@@ -12,6 +12,7 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    Instance,
     InstancePre,
     IntoFunc,
     MemoryType,
@@ -658,6 +659,36 @@ impl<T> Linker<T> {
         module.instantiate(context, externals)
     }
 
+    /// Instantiates the given [`Module`] and runs its `start` function if any.
+    ///
+    /// # Note
+    ///
+    /// This is a convenience method for [`Linker::instantiate`] followed by [`InstancePre::start`].
+    /// All memories, tables and globals are set up before the `start` function is executed,
+    /// so their initialization by the `start` function is observable via the returned [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the instantiated [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    /// - If the `start` function traps which is reported as [`InstantiationError::StartFnTrapped`].
+    pub fn instantiate_and_start(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+        module: &Module,
+    ) -> Result<Instance, Error> {
+        self.instantiate(&mut context, module)?
+            .start(&mut context)
+            .map_err(|error| match error {
+                Error::Trap(trap) => Error::from(InstantiationError::StartFnTrapped(trap)),
+                error => error,
+            })
+    }
+
     /// Processes a single [`Module`] import.
     ///
     /// # Panics
@@ -849,4 +880,52 @@ mod tests {
         wasm_set_b.call(&mut store, 200).unwrap();
         assert_eq!(wasm_get_b.call(&mut store, ()).unwrap(), 200);
     }
+
+    #[test]
+    fn instantiate_and_start_works() {
+        let engine = Engine::default();
+        let linker = <Linker<()>>::new(&engine);
+        let mut store = <Store<()>>::new(&engine, ());
+        let wat = r#"
+                (module
+                    (memory (export "memory") 1)
+                    (global (export "initialized") (mut i32) (i32.const 0))
+                    (func $start
+                        (i32.store (i32.const 8) (i32.const 42))
+                        (global.set 0 (i32.const 1))
+                    )
+                    (start $start)
+                )
+            "#;
+        let wasm = wat::parse_str(wat).unwrap();
+        let module = Module::new(&engine, &mut &wasm[..]).unwrap();
+        let instance = linker.instantiate_and_start(&mut store, &module).unwrap();
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        assert_eq!(memory.data(&store)[8], 42);
+        let initialized = instance.get_global(&store, "initialized").unwrap();
+        assert_eq!(initialized.get(&store).i32(), Some(1));
+    }
+
+    #[test]
+    fn instantiate_and_start_traps() {
+        let engine = Engine::default();
+        let linker = <Linker<()>>::new(&engine);
+        let mut store = <Store<()>>::new(&engine, ());
+        let wat = r#"
+                (module
+                    (func $start (unreachable))
+                    (start $start)
+                )
+            "#;
+        let wasm = wat::parse_str(wat).unwrap();
+        let module = Module::new(&engine, &mut &wasm[..]).unwrap();
+        let error = linker
+            .instantiate_and_start(&mut store, &module)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Instantiation(InstantiationError::StartFnTrapped(trap))
+                if trap.trap_code() == Some(crate::core::TrapCode::UnreachableCodeReached)
+        ));
+    }
 }
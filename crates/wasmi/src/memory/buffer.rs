@@ -1,52 +1,97 @@
+use super::guarded::GuardedBuffer;
 use alloc::{vec, vec::Vec};
 
-/// A `Vec`-based byte buffer implementation.
+/// A byte buffer implementation.
 ///
 /// # Note
 ///
-/// This is less efficient than the byte buffer implementation that is
-/// based on actual OS provided virtual memory but it is a safe fallback
-/// solution fitting any platform.
+/// By default this is `Vec`-based which is less efficient than the byte buffer
+/// implementation that is based on actual OS provided virtual memory but it
+/// is a safe fallback solution fitting any platform.
+///
+/// Byte buffers created via [`ByteBuffer::new_guarded`] are followed by guard pages
+/// so that out of bounds accesses can be detected without explicit bounds checks.
 #[derive(Debug)]
 pub struct ByteBuffer {
-    bytes: Vec<u8>,
+    bytes: Bytes,
+}
+
+/// The underlying storage of a [`ByteBuffer`].
+#[derive(Debug)]
+enum Bytes {
+    /// A `Vec`-based byte buffer fitting any platform.
+    Vec(Vec<u8>),
+    /// A byte buffer followed by guard pages.
+    Guarded(GuardedBuffer),
 }
 
 impl ByteBuffer {
     /// Creates a new byte buffer with the given initial length.
-    ///
-    /// # Errors
-    ///
-    /// - If the initial length is 0.
-    /// - If the initial length exceeds the maximum supported limit.
     pub fn new(initial_len: usize) -> Self {
         Self {
-            bytes: vec![0x00_u8; initial_len],
+            bytes: Bytes::Vec(vec![0x00_u8; initial_len]),
         }
     }
 
+    /// Creates a new byte buffer with the given initial length that is followed by guard pages.
+    ///
+    /// Returns `None` if guard pages are not supported on the current platform
+    /// or if the underlying virtual memory could not be reserved.
+    pub fn new_guarded(initial_len: usize) -> Option<Self> {
+        GuardedBuffer::new(initial_len).map(|buffer| Self {
+            bytes: Bytes::Guarded(buffer),
+        })
+    }
+
     /// Grows the byte buffer to the given `new_size`.
     ///
+    /// Returns `false` if the underlying virtual memory could not be grown.
+    ///
     /// # Panics
     ///
     /// If the current size of the [`ByteBuffer`] is larger than `new_size`.
-    pub fn grow(&mut self, new_size: usize) {
+    pub fn grow(&mut self, new_size: usize) -> bool {
         assert!(new_size >= self.len());
-        self.bytes.resize(new_size, 0x00_u8);
+        match &mut self.bytes {
+            Bytes::Vec(bytes) => {
+                bytes.resize(new_size, 0x00_u8);
+                true
+            }
+            Bytes::Guarded(bytes) => bytes.grow(new_size),
+        }
+    }
+
+    /// Shrinks the byte buffer to the given `new_size`.
+    ///
+    /// # Panics
+    ///
+    /// If the current size of the [`ByteBuffer`] is smaller than `new_size`.
+    pub fn shrink(&mut self, new_size: usize) {
+        assert!(new_size <= self.len());
+        match &mut self.bytes {
+            Bytes::Vec(bytes) => bytes.truncate(new_size),
+            Bytes::Guarded(bytes) => bytes.shrink(new_size),
+        }
     }
 
     /// Returns the length of the byte buffer in bytes.
     pub fn len(&self) -> usize {
-        self.bytes.len()
+        self.data().len()
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
     pub fn data(&self) -> &[u8] {
-        &self.bytes[..]
+        match &self.bytes {
+            Bytes::Vec(bytes) => &bytes[..],
+            Bytes::Guarded(bytes) => bytes.data(),
+        }
     }
 
     /// Returns an exclusive slice to the bytes underlying to the byte buffer.
     pub fn data_mut(&mut self) -> &mut [u8] {
-        &mut self.bytes[..]
+        match &mut self.bytes {
+            Bytes::Vec(bytes) => &mut bytes[..],
+            Bytes::Guarded(bytes) => bytes.data_mut(),
+        }
     }
 }
@@ -22,6 +22,10 @@ pub enum MemoryError {
     },
     /// Tried to create too many memories
     TooManyMemories,
+    /// Tried to restore a [`MemorySnapshot`] that is smaller than the minimum size of the memory.
+    ///
+    /// [`MemorySnapshot`]: crate::MemorySnapshot
+    InvalidSnapshot,
 }
 
 impl Display for MemoryError {
@@ -45,6 +49,9 @@ impl Display for MemoryError {
             Self::TooManyMemories => {
                 write!(f, "too many memories")
             }
+            Self::InvalidSnapshot => {
+                write!(f, "memory snapshot is smaller than the minimum memory size")
+            }
         }
     }
 }
@@ -0,0 +1,95 @@
+//! Fallback for platforms without guard page support.
+//!
+//! No [`GuardedBuffer`] can be created and all linear memory accesses are bounds checked
+//! explicitly, except for loads whose bounds have already been checked by the caller.
+
+use wasmi_core::TrapCode;
+
+/// Is `true` if linear memories with guard pages are supported on the current platform.
+pub const SUPPORTED: bool = false;
+
+/// A byte buffer followed by guard pages.
+///
+/// # Note
+///
+/// This type cannot be instantiated on the current platform.
+#[derive(Debug)]
+pub enum GuardedBuffer {}
+
+impl GuardedBuffer {
+    /// Always returns `None` since guard pages are not supported on the current platform.
+    pub fn new(_len: usize) -> Option<Self> {
+        None
+    }
+
+    /// Grows the [`GuardedBuffer`] to `new_len` bytes.
+    pub fn grow(&mut self, _new_len: usize) -> bool {
+        match *self {}
+    }
+
+    /// Shrinks the [`GuardedBuffer`] to `new_len` bytes.
+    pub fn shrink(&mut self, _new_len: usize) {
+        match *self {}
+    }
+
+    /// Returns the accessible bytes of the [`GuardedBuffer`].
+    pub fn data(&self) -> &[u8] {
+        match *self {}
+    }
+
+    /// Returns the accessible bytes of the [`GuardedBuffer`].
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        match *self {}
+    }
+}
+
+/// Reads the `N` bytes at `address + offset` of `memory`.
+///
+/// # Safety
+///
+/// The caller must guarantee that `address + offset` does not overflow and that
+/// all read bytes are in bounds of `memory` since there are no guard pages.
+pub unsafe fn read<const N: usize>(
+    memory: &[u8],
+    address: u32,
+    offset: u32,
+) -> Result<[u8; N], TrapCode> {
+    let address = address as usize + offset as usize;
+    debug_assert!(matches!(address.checked_add(N), Some(end) if end <= memory.len()));
+    let mut bytes = [0x00_u8; N];
+    // SAFETY: the caller guarantees that `address..address + N` is in bounds of `memory`.
+    bytes.copy_from_slice(unsafe { memory.get_unchecked(address..address + N) });
+    Ok(bytes)
+}
+
+/// Writes `bytes` to `address + offset` of `memory`.
+///
+/// # Note
+///
+/// Stores are only executed without explicit bounds checks on linear memories with
+/// guard pages which do not exist on the current platform. Therefore this checks
+/// the bounds of the store explicitly instead.
+///
+/// # Errors
+///
+/// If `address + offset` overflows or the written bytes are out of bounds of `memory`.
+///
+/// # Safety
+///
+/// This function is safe to call but mirrors the signature of the guard page implementation.
+pub unsafe fn write<const N: usize>(
+    memory: &mut [u8],
+    address: u32,
+    offset: u32,
+    bytes: [u8; N],
+) -> Result<(), TrapCode> {
+    let address = offset
+        .checked_add(address)
+        .ok_or(TrapCode::MemoryOutOfBounds)? as usize;
+    memory
+        .get_mut(address..)
+        .and_then(|memory| memory.get_mut(..N))
+        .ok_or(TrapCode::MemoryOutOfBounds)?
+        .copy_from_slice(&bytes);
+    Ok(())
+}
@@ -0,0 +1,405 @@
+//! Guard page support for Linux on `x86_64`.
+//!
+//! # Note
+//!
+//! Every linear memory access that relies on guard pages is a single `mov` instruction
+//! whose address is recorded in the `wasmi_guard_fixups` link section together with the
+//! address of its out of bounds handling code. Upon a `SIGSEGV` the installed signal
+//! handler looks up the faulting instruction in this table and resumes execution at its
+//! fixup code which then returns a [`TrapCode::MemoryOutOfBounds`] trap normally.
+//! Signals that are not caused by guarded accesses are forwarded to the previous handler.
+
+use core::{
+    arch::asm,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+    slice,
+};
+use std::sync::OnceLock;
+use wasmi_core::TrapCode;
+
+/// Is `true` if linear memories with guard pages are supported on the current platform.
+pub const SUPPORTED: bool = true;
+
+/// The number of bytes reserved for every [`GuardedBuffer`].
+///
+/// # Note
+///
+/// This covers the maximum linear memory size of 4 GiB, all 32-bit static offsets
+/// and the width of the widest access so that no access can escape the reservation.
+const RESERVED_LEN: usize = (1 << 33) + (1 << 16);
+
+/// A byte buffer followed by guard pages.
+///
+/// # Note
+///
+/// The whole address range that can be reached by Wasm loads and stores is reserved
+/// upfront and only the bytes within the length of the buffer are accessible.
+#[derive(Debug)]
+pub struct GuardedBuffer {
+    /// The start of the reserved address range.
+    base: NonNull<u8>,
+    /// The number of accessible bytes.
+    len: usize,
+}
+
+// SAFETY: `GuardedBuffer` uniquely owns its reserved address range just like a `Vec<u8>`.
+unsafe impl Send for GuardedBuffer {}
+
+// SAFETY: `GuardedBuffer` only hands out shared access to its bytes via `&self`.
+unsafe impl Sync for GuardedBuffer {}
+
+impl GuardedBuffer {
+    /// Creates a new [`GuardedBuffer`] with `len` zero initialized accessible bytes.
+    ///
+    /// Returns `None` if the address range could not be reserved or
+    /// if the signal handler could not be installed.
+    pub fn new(len: usize) -> Option<Self> {
+        if !install_signal_handler() || len > RESERVED_LEN {
+            return None;
+        }
+        // SAFETY: reserves a fresh inaccessible address range that is owned by the buffer.
+        let base = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                RESERVED_LEN,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_NORESERVE,
+                -1,
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return None;
+        }
+        let mut buffer = Self {
+            base: NonNull::new(base.cast::<u8>())?,
+            len: 0,
+        };
+        if !buffer.grow(len) {
+            return None;
+        }
+        Some(buffer)
+    }
+
+    /// Grows the [`GuardedBuffer`] to `new_len` bytes.
+    ///
+    /// Returns `false` if the additional bytes could not be made accessible.
+    ///
+    /// # Panics
+    ///
+    /// If the current length of the [`GuardedBuffer`] is larger than `new_len`.
+    pub fn grow(&mut self, new_len: usize) -> bool {
+        assert!(new_len >= self.len);
+        if new_len > RESERVED_LEN {
+            return false;
+        }
+        // SAFETY: the protected range is within the reservation owned by the buffer.
+        let result = unsafe {
+            libc::mprotect(
+                self.base.as_ptr().cast(),
+                new_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+            )
+        };
+        if result != 0 {
+            return false;
+        }
+        self.len = new_len;
+        true
+    }
+
+    /// Shrinks the [`GuardedBuffer`] to `new_len` bytes.
+    ///
+    /// # Note
+    ///
+    /// The removed bytes are zeroed and become guard pages again.
+    ///
+    /// # Panics
+    ///
+    /// - If the current length of the [`GuardedBuffer`] is smaller than `new_len`.
+    /// - If `new_len` is not a multiple of the OS page size.
+    pub fn shrink(&mut self, new_len: usize) {
+        assert!(new_len <= self.len);
+        assert_eq!(
+            new_len % page_size(),
+            0,
+            "guarded buffer length must be a multiple of the page size"
+        );
+        let start = self.base.as_ptr().wrapping_add(new_len).cast();
+        let len = self.len - new_len;
+        // SAFETY: the released range is within the reservation owned by the buffer
+        //         and no references to the removed bytes exist since `self` is borrowed
+        //         exclusively.
+        let result = unsafe { libc::madvise(start, len, libc::MADV_DONTNEED) };
+        assert_eq!(
+            result, 0,
+            "failed to release the removed bytes of the guarded buffer"
+        );
+        // SAFETY: same as above.
+        let result = unsafe { libc::mprotect(start, len, libc::PROT_NONE) };
+        assert_eq!(
+            result, 0,
+            "failed to turn the removed bytes of the guarded buffer into guard pages"
+        );
+        self.len = new_len;
+    }
+
+    /// Returns the accessible bytes of the [`GuardedBuffer`].
+    pub fn data(&self) -> &[u8] {
+        // SAFETY: the first `len` bytes of the reservation are readable and initialized.
+        unsafe { slice::from_raw_parts(self.base.as_ptr(), self.len) }
+    }
+
+    /// Returns the accessible bytes of the [`GuardedBuffer`].
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the first `len` bytes of the reservation are writable and initialized.
+        unsafe { slice::from_raw_parts_mut(self.base.as_ptr(), self.len) }
+    }
+}
+
+/// Returns the page size of the operating system in bytes.
+fn page_size() -> usize {
+    static PAGE_SIZE: OnceLock<usize> = OnceLock::new();
+    // SAFETY: querying the page size has no preconditions.
+    *PAGE_SIZE.get_or_init(|| unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize)
+}
+
+impl Drop for GuardedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: the reservation is owned by the buffer and no longer in use.
+        unsafe { libc::munmap(self.base.as_ptr().cast(), RESERVED_LEN) };
+    }
+}
+
+/// An entry of the `wasmi_guard_fixups` link section.
+///
+/// # Note
+///
+/// Both fields are relative to their own address so that the table is position independent.
+#[repr(C)]
+struct Fixup {
+    /// The address of the guarded `mov` instruction.
+    access: i32,
+    /// The address at which execution resumes if the guarded instruction faults.
+    fixup: i32,
+}
+
+extern "C" {
+    static __start_wasmi_guard_fixups: Fixup;
+    static __stop_wasmi_guard_fixups: Fixup;
+}
+
+/// Returns the fixup address of the guarded access at `pc` if any.
+fn find_fixup(pc: usize) -> Option<usize> {
+    /// Resolves the address that is stored relative to `field`.
+    ///
+    /// # Safety
+    ///
+    /// `field` must point to a field of a [`Fixup`] entry.
+    unsafe fn resolve(field: *const i32) -> usize {
+        (field as usize).wrapping_add(unsafe { *field } as isize as usize)
+    }
+    // The linker defines both symbols as the bounds of the fixup table.
+    let mut entry = ptr::addr_of!(__start_wasmi_guard_fixups);
+    let end = ptr::addr_of!(__stop_wasmi_guard_fixups);
+    while entry < end {
+        // SAFETY: `entry` points to an entry of the fixup table.
+        unsafe {
+            if resolve(ptr::addr_of!((*entry).access)) == pc {
+                return Some(resolve(ptr::addr_of!((*entry).fixup)));
+            }
+            entry = entry.add(1);
+        }
+    }
+    None
+}
+
+/// The signal action that was installed before the guard page signal handler.
+static mut PREVIOUS_ACTION: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+
+/// Installs the guard page signal handler once.
+///
+/// Returns `true` if the signal handler is installed.
+fn install_signal_handler() -> bool {
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+    *INSTALLED.get_or_init(|| {
+        // SAFETY: the handler is installed exactly once and `PREVIOUS_ACTION` is
+        //         initialized before the handler can observe it.
+        unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = handle_signal as *const () as usize;
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+            libc::sigemptyset(&mut action.sa_mask);
+            let previous = (*ptr::addr_of_mut!(PREVIOUS_ACTION)).as_mut_ptr();
+            libc::sigaction(libc::SIGSEGV, &action, previous) == 0
+        }
+    })
+}
+
+/// Resumes guarded accesses that fault at their fixup code or forwards the signal.
+unsafe extern "C" fn handle_signal(
+    signal: libc::c_int,
+    info: *mut libc::siginfo_t,
+    context: *mut libc::c_void,
+) {
+    let context = context.cast::<libc::ucontext_t>();
+    // SAFETY: the kernel passes a valid `ucontext_t` to `SA_SIGINFO` handlers.
+    let pc = unsafe { &mut (*context).uc_mcontext.gregs[libc::REG_RIP as usize] };
+    if let Some(fixup) = find_fixup(*pc as usize) {
+        *pc = fixup as libc::greg_t;
+        return;
+    }
+    // SAFETY: `PREVIOUS_ACTION` is initialized before the handler is installed.
+    let previous = unsafe { (*ptr::addr_of!(PREVIOUS_ACTION)).assume_init_ref() };
+    match previous.sa_sigaction {
+        libc::SIG_DFL | libc::SIG_IGN => {
+            // Reset to the default action so that the faulting instruction raises the
+            // signal again once the signal handler returns and terminates the process.
+            //
+            // Note: an ignored `SIGSEGV` is reset as well since returning to the faulting
+            //       instruction would otherwise fault forever.
+            // SAFETY: `sigaction` is async-signal-safe and `default` is a valid signal action.
+            unsafe {
+                let mut default: libc::sigaction = mem::zeroed();
+                default.sa_sigaction = libc::SIG_DFL;
+                libc::sigemptyset(&mut default.sa_mask);
+                libc::sigaction(signal, &default, ptr::null_mut());
+            }
+        }
+        handler if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+            // SAFETY: `SA_SIGINFO` handlers have this signature.
+            let handler: unsafe extern "C" fn(
+                libc::c_int,
+                *mut libc::siginfo_t,
+                *mut libc::c_void,
+            ) = unsafe { mem::transmute(handler) };
+            unsafe { handler(signal, info, context.cast()) }
+        }
+        handler => {
+            // SAFETY: handlers without `SA_SIGINFO` have this signature.
+            let handler: unsafe extern "C" fn(libc::c_int) = unsafe { mem::transmute(handler) };
+            unsafe { handler(signal) }
+        }
+    }
+}
+
+/// Executes the guarded `mov` instruction `$instr` with the given operands.
+///
+/// Returns [`TrapCode::MemoryOutOfBounds`] from the enclosing function if it faults.
+macro_rules! guarded_access {
+    ( $($instr:literal),+ ; $($operands:tt)* ) => {
+        asm!(
+            "2:",
+            $($instr),+,
+            ".pushsection wasmi_guard_fixups,\"aR\"",
+            ".balign 4",
+            ".long 2b - .",
+            ".long {fault} - .",
+            ".popsection",
+            $($operands)*
+            fault = label { return Err(TrapCode::MemoryOutOfBounds) },
+            options(nostack, preserves_flags),
+        )
+    };
+}
+
+/// Reads the `N` bytes at `address + offset` of `memory`.
+///
+/// # Errors
+///
+/// If the read accesses the guard pages of a [`GuardedBuffer`].
+///
+/// # Safety
+///
+/// The caller must guarantee that `memory` holds the bytes of a [`GuardedBuffer`]
+/// or that all read bytes are in bounds of `memory`.
+#[inline(always)]
+pub unsafe fn read<const N: usize>(
+    memory: &[u8],
+    address: u32,
+    offset: u32,
+) -> Result<[u8; N], TrapCode> {
+    let src = memory
+        .as_ptr()
+        .wrapping_add(address as usize + offset as usize);
+    let mut bytes = [0x00_u8; N];
+    let dst = bytes.as_mut_ptr();
+    // SAFETY: the faulting `mov` is the first instruction of each access and
+    //         out of bounds accesses only ever touch the guard pages of `memory`.
+    unsafe {
+        match N {
+            1 => guarded_access!(
+                "mov {tmp:l}, byte ptr [{src}]",
+                "mov byte ptr [{dst}], {tmp:l}";
+                src = in(reg) src, dst = in(reg) dst, tmp = out(reg) _,
+            ),
+            2 => guarded_access!(
+                "mov {tmp:x}, word ptr [{src}]",
+                "mov word ptr [{dst}], {tmp:x}";
+                src = in(reg) src, dst = in(reg) dst, tmp = out(reg) _,
+            ),
+            4 => guarded_access!(
+                "mov {tmp:e}, dword ptr [{src}]",
+                "mov dword ptr [{dst}], {tmp:e}";
+                src = in(reg) src, dst = in(reg) dst, tmp = out(reg) _,
+            ),
+            8 => guarded_access!(
+                "mov {tmp}, qword ptr [{src}]",
+                "mov qword ptr [{dst}], {tmp}";
+                src = in(reg) src, dst = in(reg) dst, tmp = out(reg) _,
+            ),
+            _ => unreachable!("unsupported guarded read of {N} bytes"),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Writes `bytes` to `address + offset` of `memory`.
+///
+/// # Errors
+///
+/// If the write accesses the guard pages of a [`GuardedBuffer`].
+///
+/// # Safety
+///
+/// The caller must guarantee that `memory` holds the bytes of a [`GuardedBuffer`]
+/// or that all written bytes are in bounds of `memory`.
+#[inline(always)]
+pub unsafe fn write<const N: usize>(
+    memory: &mut [u8],
+    address: u32,
+    offset: u32,
+    bytes: [u8; N],
+) -> Result<(), TrapCode> {
+    let dst = memory
+        .as_mut_ptr()
+        .wrapping_add(address as usize + offset as usize);
+    let mut value = [0x00_u8; 8];
+    value[..N].copy_from_slice(&bytes);
+    let value = u64::from_le_bytes(value);
+    // SAFETY: the faulting `mov` is the only instruction of each access and
+    //         out of bounds accesses only ever touch the guard pages of `memory`.
+    unsafe {
+        match N {
+            1 => guarded_access!(
+                "mov byte ptr [{dst}], {value:l}";
+                dst = in(reg) dst, value = in(reg) value,
+            ),
+            2 => guarded_access!(
+                "mov word ptr [{dst}], {value:x}";
+                dst = in(reg) dst, value = in(reg) value,
+            ),
+            4 => guarded_access!(
+                "mov dword ptr [{dst}], {value:e}";
+                dst = in(reg) dst, value = in(reg) value,
+            ),
+            8 => guarded_access!(
+                "mov qword ptr [{dst}], {value}";
+                dst = in(reg) dst, value = in(reg) value,
+            ),
+            _ => unreachable!("unsupported guarded write of {N} bytes"),
+        }
+    }
+    Ok(())
+}
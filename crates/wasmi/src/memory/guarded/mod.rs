@@ -0,0 +1,101 @@
+//! Linear memories whose out of bounds accesses are detected via guard pages.
+//!
+//! # Note
+//!
+//! Read [`Config::set_signal_bounds_checks`] for more information.
+//!
+//! [`Config::set_signal_bounds_checks`]: crate::Config::set_signal_bounds_checks
+
+#[cfg(all(feature = "std", target_os = "linux", target_arch = "x86_64"))]
+#[path = "linux_x86_64.rs"]
+mod sys;
+
+#[cfg(not(all(feature = "std", target_os = "linux", target_arch = "x86_64")))]
+#[path = "fallback.rs"]
+mod sys;
+
+pub use self::sys::{GuardedBuffer, SUPPORTED};
+use wasmi_core::{TrapCode, UntypedValue, F32, F64};
+
+macro_rules! impl_guarded_load {
+    ( $( ($fn_name:ident, $wasm_name:literal, $len:literal, $from_le_bytes:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes the `", $wasm_name, "` Wasm operation without explicit bounds checks.")]
+            ///
+            /// # Errors
+            ///
+            /// If the load accesses the guard pages of a [`GuardedBuffer`].
+            ///
+            /// # Safety
+            ///
+            /// The caller must guarantee that `memory` holds the bytes of a [`GuardedBuffer`]
+            /// or that `address + offset` does not overflow and all loaded bytes are in bounds.
+            pub unsafe fn $fn_name(
+                memory: &[u8],
+                address: UntypedValue,
+                offset: u32,
+            ) -> Result<UntypedValue, TrapCode> {
+                // SAFETY: the caller upholds the safety guarantees of this function.
+                let bytes = unsafe { sys::read::<$len>(memory, u32::from(address), offset) }?;
+                Ok(UntypedValue::from(($from_le_bytes)(bytes)))
+            }
+        )*
+    };
+}
+impl_guarded_load! {
+    (i32_load, "i32.load", 4, i32::from_le_bytes),
+    (i64_load, "i64.load", 8, i64::from_le_bytes),
+    (f32_load, "f32.load", 4, |bytes| F32::from_bits(u32::from_le_bytes(bytes))),
+    (f64_load, "f64.load", 8, |bytes| F64::from_bits(u64::from_le_bytes(bytes))),
+    (i32_load8_s, "i32.load8_s", 1, |bytes| i32::from(i8::from_le_bytes(bytes))),
+    (i32_load8_u, "i32.load8_u", 1, |bytes| i32::from(u8::from_le_bytes(bytes))),
+    (i32_load16_s, "i32.load16_s", 2, |bytes| i32::from(i16::from_le_bytes(bytes))),
+    (i32_load16_u, "i32.load16_u", 2, |bytes| i32::from(u16::from_le_bytes(bytes))),
+    (i64_load8_s, "i64.load8_s", 1, |bytes| i64::from(i8::from_le_bytes(bytes))),
+    (i64_load8_u, "i64.load8_u", 1, |bytes| i64::from(u8::from_le_bytes(bytes))),
+    (i64_load16_s, "i64.load16_s", 2, |bytes| i64::from(i16::from_le_bytes(bytes))),
+    (i64_load16_u, "i64.load16_u", 2, |bytes| i64::from(u16::from_le_bytes(bytes))),
+    (i64_load32_s, "i64.load32_s", 4, |bytes| i64::from(i32::from_le_bytes(bytes))),
+    (i64_load32_u, "i64.load32_u", 4, |bytes| i64::from(u32::from_le_bytes(bytes))),
+}
+
+macro_rules! impl_guarded_store {
+    ( $( ($fn_name:ident, $wasm_name:literal, $to_le_bytes:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes the `", $wasm_name, "` Wasm operation without explicit bounds checks.")]
+            ///
+            /// # Note
+            ///
+            /// Without guard page support the store is bounds checked explicitly.
+            ///
+            /// # Errors
+            ///
+            /// If the store accesses the guard pages of a [`GuardedBuffer`].
+            ///
+            /// # Safety
+            ///
+            /// The caller must guarantee that `memory` holds the bytes of a [`GuardedBuffer`].
+            pub unsafe fn $fn_name(
+                memory: &mut [u8],
+                address: UntypedValue,
+                offset: u32,
+                value: UntypedValue,
+            ) -> Result<(), TrapCode> {
+                let bytes = ($to_le_bytes)(value);
+                // SAFETY: the caller upholds the safety guarantees of this function.
+                unsafe { sys::write(memory, u32::from(address), offset, bytes) }
+            }
+        )*
+    };
+}
+impl_guarded_store! {
+    (i32_store, "i32.store", |value| i32::from(value).to_le_bytes()),
+    (i64_store, "i64.store", |value| i64::from(value).to_le_bytes()),
+    (f32_store, "f32.store", |value| F32::from(value).to_bits().to_le_bytes()),
+    (f64_store, "f64.store", |value| F64::from(value).to_bits().to_le_bytes()),
+    (i32_store8, "i32.store8", |value| i8::from(value).to_le_bytes()),
+    (i32_store16, "i32.store16", |value| i16::from(value).to_le_bytes()),
+    (i64_store8, "i64.store8", |value| i8::from(value).to_le_bytes()),
+    (i64_store16, "i64.store16", |value| i16::from(value).to_le_bytes()),
+    (i64_store32, "i64.store32", |value| i32::from(value).to_le_bytes()),
+}
@@ -1,6 +1,7 @@
 mod buffer;
 mod data;
 mod error;
+pub(crate) mod guarded;
 
 #[cfg(test)]
 mod tests;
@@ -12,6 +13,7 @@ pub use self::{
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{error::EntityGrowError, store::ResourceLimiterRef};
+use alloc::boxed::Box;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{Pages, TrapCode};
 
@@ -37,6 +39,7 @@ impl ArenaIndex for MemoryIdx {
 pub struct MemoryType {
     initial_pages: Pages,
     maximum_pages: Option<Pages>,
+    shared: bool,
 }
 
 impl MemoryType {
@@ -57,6 +60,25 @@ impl MemoryType {
         Ok(Self {
             initial_pages,
             maximum_pages,
+            shared: false,
+        })
+    }
+
+    /// Creates a new shared memory type with initial and maximum pages.
+    ///
+    /// # Note
+    ///
+    /// Shared memories are part of the Wasm `threads` proposal and always have a maximum size.
+    ///
+    /// # Errors
+    ///
+    /// If the linear memory type initial or maximum size exceeds the
+    /// maximum limits of 2^16 pages.
+    pub fn new_shared(initial: u32, maximum: u32) -> Result<Self, MemoryError> {
+        let memory_type = Self::new(initial, Some(maximum))?;
+        Ok(Self {
+            shared: true,
+            ..memory_type
         })
     }
 
@@ -75,6 +97,11 @@ impl MemoryType {
         self.maximum_pages
     }
 
+    /// Returns `true` if the memory type is a shared memory type.
+    pub fn is_shared(self) -> bool {
+        self.shared
+    }
+
     /// Checks if `self` is a subtype of `other`.
     ///
     /// # Note
@@ -88,6 +115,7 @@ impl MemoryType {
     ///
     /// - If the `minimum` size of `self` is less than or equal to the `minimum` size of `other`.
     /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`.
+    /// - If exactly one of `self` and `other` is shared.
     pub(crate) fn is_subtype_or_err(&self, other: &MemoryType) -> Result<(), MemoryError> {
         match self.is_subtype_of(other) {
             true => Ok(()),
@@ -107,6 +135,9 @@ impl MemoryType {
     /// [import subtyping]:
     /// https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
     pub(crate) fn is_subtype_of(&self, other: &MemoryType) -> bool {
+        if self.is_shared() != other.is_shared() {
+            return false;
+        }
         if self.initial_pages() < other.initial_pages() {
             return false;
         }
@@ -128,8 +159,11 @@ pub struct MemoryEntity {
 
 impl MemoryEntity {
     /// Creates a new memory entity with the given memory type.
+    ///
+    /// If `guarded` is `true` the linear memory is followed by guard pages.
     pub fn new(
         memory_type: MemoryType,
+        guarded: bool,
         limiter: &mut ResourceLimiterRef<'_>,
     ) -> Result<Self, MemoryError> {
         let initial_pages = memory_type.initial_pages();
@@ -146,9 +180,14 @@ impl MemoryEntity {
             }
         }
 
-        if let Some(initial_len) = initial_len {
+        let bytes = match (initial_len, guarded) {
+            (Some(initial_len), false) => Some(ByteBuffer::new(initial_len)),
+            (Some(initial_len), true) => ByteBuffer::new_guarded(initial_len),
+            (None, _) => None,
+        };
+        if let Some(bytes) = bytes {
             let memory = Self {
-                bytes: ByteBuffer::new(initial_len),
+                bytes,
                 memory_type,
                 current_pages: initial_pages,
             };
@@ -174,10 +213,10 @@ impl MemoryEntity {
     /// This respects the current size of the [`MemoryEntity`] as
     /// its minimum size and is useful for import subtyping checks.
     pub fn dynamic_ty(&self) -> MemoryType {
-        let current_pages = self.current_pages().into();
-        let maximum_pages = self.ty().maximum_pages().map(Into::into);
-        MemoryType::new(current_pages, maximum_pages)
-            .unwrap_or_else(|_| panic!("must result in valid memory type due to invariants"))
+        MemoryType {
+            initial_pages: self.current_pages(),
+            ..self.ty()
+        }
     }
 
     /// Returns the amount of pages in use by the linear memory.
@@ -229,9 +268,10 @@ impl MemoryEntity {
                 if let Some(new_size) = new_pages.to_bytes() {
                     // At this point it is okay to grow the underlying virtual memory
                     // by the given amount of additional pages.
-                    self.bytes.grow(new_size);
-                    self.current_pages = new_pages;
-                    ret = Ok(current_pages)
+                    if self.bytes.grow(new_size) {
+                        self.current_pages = new_pages;
+                        ret = Ok(current_pages)
+                    }
                 }
             }
         }
@@ -256,6 +296,43 @@ impl MemoryEntity {
         self.bytes.data_mut()
     }
 
+    /// Returns a [`MemorySnapshot`] of the current size and bytes of the linear memory.
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot {
+            pages: self.current_pages(),
+            bytes: self.data().into(),
+        }
+    }
+
+    /// Restores the size and bytes of the linear memory to the `snapshot`.
+    ///
+    /// # Note
+    ///
+    /// The linear memory is grown if the `snapshot` is larger than the linear
+    /// memory and shrunk if it is smaller.
+    ///
+    /// # Errors
+    ///
+    /// - If the `snapshot` is smaller than the minimum size of the linear memory.
+    /// - If the linear memory cannot be grown to the size of the `snapshot`.
+    pub fn restore(
+        &mut self,
+        snapshot: &MemorySnapshot,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<(), MemoryError> {
+        if snapshot.pages < self.ty().initial_pages() {
+            return Err(MemoryError::InvalidSnapshot);
+        }
+        if let Some(additional) = snapshot.pages.checked_sub(self.current_pages()) {
+            self.grow(additional, limiter)
+                .map_err(|_| MemoryError::OutOfBoundsGrowth)?;
+        }
+        self.bytes.shrink(snapshot.bytes.len());
+        self.bytes.data_mut().copy_from_slice(&snapshot.bytes);
+        self.current_pages = snapshot.pages;
+        Ok(())
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -287,6 +364,67 @@ impl MemoryEntity {
         slice.copy_from_slice(buffer);
         Ok(())
     }
+
+    /// Fills `n` bytes of `memory[offset..offset+n]` with `byte`
+    /// where `n` is `len`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn fill(&mut self, offset: usize, byte: u8, len: usize) -> Result<(), MemoryError> {
+        fill_bytes(self.data_mut(), offset, byte, len)
+    }
+}
+
+/// Fills `n` bytes of `bytes[offset..offset+n]` with `byte` where `n` is `len`.
+///
+/// # Note
+///
+/// This is shared between [`Memory::fill`] and the execution of the Wasm
+/// `memory.fill` instruction so that both have identical semantics.
+/// Nothing is written if the filled region is out of bounds.
+///
+/// # Errors
+///
+/// If this operation accesses out of bounds linear memory.
+pub(crate) fn fill_bytes(
+    bytes: &mut [u8],
+    offset: usize,
+    byte: u8,
+    len: usize,
+) -> Result<(), MemoryError> {
+    bytes
+        .get_mut(offset..)
+        .and_then(|bytes| bytes.get_mut(..len))
+        .ok_or(MemoryError::OutOfBoundsAccess)?
+        .fill(byte);
+    Ok(())
+}
+
+/// A snapshot of the size and bytes of a linear [`Memory`].
+///
+/// # Note
+///
+/// - Created via [`Memory::snapshot`] and restored via [`Memory::restore`].
+/// - Owns a full copy of the bytes of the linear [`Memory`] at the time of the snapshot.
+#[derive(Debug, Clone)]
+pub struct MemorySnapshot {
+    /// The size of the linear [`Memory`] in pages.
+    pages: Pages,
+    /// The bytes of the linear [`Memory`].
+    bytes: Box<[u8]>,
+}
+
+impl MemorySnapshot {
+    /// Returns the size of the snapshotted linear [`Memory`] in pages.
+    pub fn pages(&self) -> Pages {
+        self.pages
+    }
+
+    /// Returns the bytes of the snapshotted linear [`Memory`].
+    pub fn data(&self) -> &[u8] {
+        &self.bytes
+    }
 }
 
 /// A Wasm linear memory reference.
@@ -316,7 +454,8 @@ impl Memory {
             .store
             .store_inner_and_resource_limiter_ref();
 
-        let entity = MemoryEntity::new(ty, &mut resource_limiter)?;
+        let guarded = inner.engine().config().uses_signal_bounds_checks();
+        let entity = MemoryEntity::new(ty, guarded, &mut resource_limiter)?;
         let memory = inner.alloc_memory(entity);
         Ok(memory)
     }
@@ -420,6 +559,46 @@ impl Memory {
         (memory.data_mut(), store)
     }
 
+    /// Returns a [`MemorySnapshot`] of the current size and bytes of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn snapshot(&self, ctx: impl AsContext) -> MemorySnapshot {
+        ctx.as_context().store.inner.resolve_memory(self).snapshot()
+    }
+
+    /// Restores the size and bytes of the [`Memory`] to the `snapshot`.
+    ///
+    /// # Note
+    ///
+    /// This allows to deterministically replay executions by restoring a
+    /// [`MemorySnapshot`] taken via [`Memory::snapshot`]. The [`Memory`] is
+    /// grown if the `snapshot` is larger than the [`Memory`] and shrunk if it is
+    /// smaller, e.g. after it has been grown by a Wasm execution.
+    ///
+    /// # Errors
+    ///
+    /// - If the `snapshot` is smaller than the minimum size of the [`Memory`].
+    /// - If the [`Memory`] cannot be grown to the size of the `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn restore(
+        &self,
+        mut ctx: impl AsContextMut,
+        snapshot: &MemorySnapshot,
+    ) -> Result<(), MemoryError> {
+        let (inner, mut limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        inner
+            .resolve_memory_mut(self)
+            .restore(snapshot, &mut limiter)
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -465,4 +644,32 @@ impl Memory {
             .resolve_memory_mut(self)
             .write(offset, buffer)
     }
+
+    /// Fills `n` bytes of `memory[offset..offset+n]` with `byte`
+    /// where `n` is `len`.
+    ///
+    /// # Note
+    ///
+    /// This has the same semantics as the Wasm `memory.fill` instruction.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn fill(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: usize,
+        byte: u8,
+        len: usize,
+    ) -> Result<(), MemoryError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .fill(offset, byte, len)
+    }
 }
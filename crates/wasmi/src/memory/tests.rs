@@ -14,3 +14,12 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+#[test]
+fn shared_subtyping_works() {
+    let shared = |minimum, maximum| MemoryType::new_shared(minimum, maximum).unwrap();
+    assert!(shared(0, 1).is_subtype_of(&shared(0, 2)));
+    assert!(!shared(0, 2).is_subtype_of(&shared(0, 1)));
+    assert!(!shared(0, 1).is_subtype_of(&memory_type(0, 1)));
+    assert!(!memory_type(0, 1).is_subtype_of(&shared(0, 1)));
+}
@@ -10,18 +10,20 @@ use super::{
     GlobalIdx,
     Import,
     ImportName,
+    MemoryIdx,
     Module,
 };
 use crate::{
-    engine::{CompiledFunc, DedupFuncType},
+    engine::{CodeUnit, CompiledFunc, DedupFuncType},
     errors::ModuleError,
     Engine,
     FuncType,
     GlobalType,
     MemoryType,
+    PeepholeReport,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
@@ -40,6 +42,11 @@ pub struct ModuleBuilder<'engine> {
     pub compiled_funcs_2: Vec<CompiledFunc>,
     pub element_segments: Vec<ElementSegment>,
     pub data_segments: Vec<DataSegment>,
+    pub peephole_report: PeepholeReport,
+    /// The [`CodeUnit`] of the compiled functions if they can be evicted.
+    pub code_unit: Option<CodeUnit>,
+    /// The evicted compiled functions that are recompiled instead of allocating new ones.
+    pub evicted_funcs: Arc<[CompiledFunc]>,
 }
 
 /// The import names of the [`Module`] imports.
@@ -95,6 +102,11 @@ impl<'a> ModuleResources<'a> {
         self.res.globals[global_idx.into_u32() as usize]
     }
 
+    /// Returns the [`MemoryType`] of the indexed linear memory.
+    pub fn get_type_of_memory(&self, memory_idx: MemoryIdx) -> MemoryType {
+        self.res.memories[memory_idx.into_u32() as usize]
+    }
+
     /// Returns the [`CompiledFunc`] for the given [`FuncIdx`].
     ///
     /// Returns `None` if [`FuncIdx`] refers to an imported function.
@@ -153,6 +165,9 @@ impl<'engine> ModuleBuilder<'engine> {
             compiled_funcs_2: Vec::new(),
             element_segments: Vec::new(),
             data_segments: Vec::new(),
+            peephole_report: PeepholeReport::default(),
+            code_unit: None,
+            evicted_funcs: [].into(),
         }
     }
 
@@ -247,8 +262,16 @@ impl<'engine> ModuleBuilder<'engine> {
             let func_type_idx = func?;
             let func_type = self.func_types[func_type_idx.into_u32() as usize];
             self.funcs.push(func_type);
-            self.compiled_funcs.push(self.engine.alloc_func());
-            self.compiled_funcs_2.push(self.engine.alloc_func_2());
+            match self.evicted_funcs.get(self.compiled_funcs.len()) {
+                Some(&evicted) => {
+                    self.compiled_funcs.push(evicted);
+                    self.compiled_funcs_2.push(evicted);
+                }
+                None => {
+                    self.compiled_funcs.push(self.engine.alloc_func());
+                    self.compiled_funcs_2.push(self.engine.alloc_func_2());
+                }
+            }
         }
         Ok(())
     }
@@ -1,4 +1,6 @@
 pub use self::block_type::BlockType;
+#[cfg(feature = "std")]
+pub use self::parallel::{translate_parallel, PendingFunc};
 use super::{parser::ReusableAllocations, FuncIdx, ModuleResources};
 use crate::{
     engine::{ChosenFuncTranslatorAllocations, CompiledFunc, FuncBuilder},
@@ -7,6 +9,8 @@ use crate::{
 use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
 
 mod block_type;
+#[cfg(feature = "std")]
+mod parallel;
 
 /// Translates the Wasm bytecode into `wasmi` bytecode.
 ///
@@ -74,12 +78,22 @@ impl<'parser> FunctionTranslator<'parser> {
 
     /// Starts translation of the Wasm stream into `wasmi` bytecode.
     fn translate(mut self) -> Result<ReusableAllocations, ModuleError> {
-        self.translate_locals()?;
-        let offset = self.translate_operators()?;
+        let offset = self.translate_body()?;
         let allocations = self.finish(offset)?;
         Ok(allocations)
     }
 
+    /// Translates the local variables and Wasm operators of the Wasm function.
+    ///
+    /// Returns the offset of the `End` Wasm operator required to [`FunctionTranslator::finish`]
+    /// the translation which commits the translated function to the [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    fn translate_body(&mut self) -> Result<usize, ModuleError> {
+        self.translate_locals()?;
+        self.translate_operators()
+    }
+
     /// Finishes construction of the function and returns its [`CompiledFunc`].
     fn finish(self, offset: usize) -> Result<ReusableAllocations, ModuleError> {
         self.func_builder.finish(offset).map_err(Into::into)
@@ -104,6 +118,7 @@ impl<'parser> FunctionTranslator<'parser> {
     /// Returns the offset of the `End` Wasm operator.
     fn translate_operators(&mut self) -> Result<usize, ModuleError> {
         let mut reader = self.func_body.get_operators_reader()?;
+        self.func_builder.prepare_operators(&reader);
         while !reader.eof() {
             let pos = reader.original_position();
             self.func_builder.update_pos(pos);
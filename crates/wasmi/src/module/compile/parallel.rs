@@ -0,0 +1,306 @@
+//! Parallel translation of the function bodies of a Wasm module.
+
+use super::FunctionTranslator;
+use crate::{
+    engine::{CompiledFunc, PeepholeReport},
+    module::{FuncIdx, ModuleBuilder, ModuleError, ModuleResources, ReusableAllocations},
+};
+use std::{
+    boxed::Box,
+    collections::BTreeMap,
+    iter::Enumerate,
+    panic,
+    slice::IterMut,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    thread::{self, ScopedJoinHandle},
+    vec::Vec,
+};
+use wasmparser::{FuncToValidate, FunctionBody, ValidatorResources};
+
+/// A validated function body awaiting its translation via [`translate_parallel`].
+pub struct PendingFunc {
+    /// The index of the function within the Wasm module.
+    func: FuncIdx,
+    /// The compiled functions of both engine backends that are translated into.
+    compiled_funcs: (CompiledFunc, CompiledFunc),
+    /// The offset of the function body within the Wasm module.
+    offset: usize,
+    /// The Wasm bytecode of the function body.
+    ///
+    /// # Note
+    ///
+    /// This is a copy since the streaming parser reuses its buffer for later sections.
+    bytes: Box<[u8]>,
+    /// The validator of the function body.
+    ///
+    /// This is `None` once the translation of the function body has started.
+    validator: Option<FuncToValidate<ValidatorResources>>,
+}
+
+impl PendingFunc {
+    /// Creates a new [`PendingFunc`] for `func_body` of the function `func`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes of `func_body` cannot be read.
+    pub fn new(
+        func: FuncIdx,
+        compiled_funcs: (CompiledFunc, CompiledFunc),
+        func_body: &FunctionBody,
+        validator: FuncToValidate<ValidatorResources>,
+    ) -> Result<Self, ModuleError> {
+        let mut reader = func_body.get_binary_reader();
+        let offset = reader.original_position();
+        let bytes = reader.read_bytes(reader.bytes_remaining())?.into();
+        Ok(Self {
+            func,
+            compiled_funcs,
+            offset,
+            bytes,
+            validator: Some(validator),
+        })
+    }
+
+    /// Translates the [`PendingFunc`] without committing it to the [`Engine`].
+    ///
+    /// # Panics
+    ///
+    /// If the translation of the [`PendingFunc`] has already been started.
+    ///
+    /// [`Engine`]: crate::Engine
+    fn translate<'a>(
+        &'a mut self,
+        builder: &'a ModuleBuilder,
+        allocations: ReusableAllocations,
+    ) -> Result<Translated<'a>, ModuleError> {
+        let validator = self
+            .validator
+            .take()
+            .expect("function bodies are translated only once");
+        let func_body = FunctionBody::new(self.offset, &self.bytes[..]);
+        let mut translator = FunctionTranslator::new(
+            self.func,
+            self.compiled_funcs,
+            func_body,
+            validator.into_validator(allocations.validation),
+            ModuleResources::new(builder),
+            allocations.translation,
+        )?;
+        let offset = translator.translate_body()?;
+        Ok(Translated { translator, offset })
+    }
+}
+
+/// A translated function body awaiting its commit to the [`Engine`].
+///
+/// [`Engine`]: crate::Engine
+struct Translated<'a> {
+    /// The translator holding the translated function body.
+    translator: FunctionTranslator<'a>,
+    /// The offset of the `End` Wasm operator of the function body.
+    offset: usize,
+}
+
+/// Commits the translated function bodies to the [`Engine`] in the order of their indices.
+///
+/// # Note
+///
+/// Threads never wait for the function bodies before their own to be committed.
+/// Instead a translated function body is parked until all function bodies before
+/// it have been committed. The thread that provides the next function body in order
+/// commits it together with all parked function bodies following it.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Default)]
+struct Commits<'a> {
+    /// The state shared by all threads of [`translate_parallel`].
+    state: Mutex<CommitState<'a>>,
+}
+
+/// The state of [`Commits`].
+#[derive(Default)]
+struct CommitState<'a> {
+    /// The index of the next function body to commit.
+    next: usize,
+    /// The function bodies that await the commits of the function bodies before them.
+    ///
+    /// # Note
+    ///
+    /// This is `None` for function bodies that failed or skipped their translation.
+    parked: BTreeMap<usize, Option<Translated<'a>>>,
+    /// The reusable allocations of committed function bodies not in use by any thread.
+    allocations: Vec<ReusableAllocations>,
+    /// The error of the first failing function body in order and its index.
+    first_error: Option<(usize, ModuleError)>,
+}
+
+impl<'a> Commits<'a> {
+    /// Locks the [`CommitState`].
+    fn lock(&self) -> MutexGuard<'_, CommitState<'a>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns reusable allocations of a committed function body if any.
+    fn take_allocations(&self) -> Option<ReusableAllocations> {
+        self.lock().allocations.pop()
+    }
+
+    /// Returns `allocations` that are no longer in use by a thread.
+    fn put_allocations(&self, allocations: Option<ReusableAllocations>) {
+        self.lock().allocations.extend(allocations);
+    }
+
+    /// Records the `error` of the function body at `index`.
+    fn fail(&self, index: usize, error: ModuleError) {
+        self.lock().fail(index, error)
+    }
+
+    /// Provides the `translated` function body at `index` and commits all function bodies possible.
+    ///
+    /// The `translated` function body is `None` if it failed or skipped its translation.
+    /// The reusable allocations of committed function bodies are put into `allocations` if empty.
+    fn commit(
+        &self,
+        index: usize,
+        translated: Option<Translated<'a>>,
+        allocations: &mut Option<ReusableAllocations>,
+    ) {
+        let mut state = self.lock();
+        let state = &mut *state;
+        state.parked.insert(index, translated);
+        while let Some(translated) = state.parked.remove(&state.next) {
+            let index = state.next;
+            state.next += 1;
+            match translated {
+                Some(Translated { translator, offset }) if state.first_error.is_none() => {
+                    match translator.finish(offset) {
+                        Ok(reusable) => match allocations {
+                            None => *allocations = Some(reusable),
+                            Some(_) => state.allocations.push(reusable),
+                        },
+                        Err(error) => state.fail(index, error),
+                    }
+                }
+                // Note: nothing is committed for failed or skipped function bodies
+                //       and for any function body once compilation is known to fail.
+                _ => {}
+            }
+        }
+    }
+}
+
+impl CommitState<'_> {
+    /// Records the `error` of the function body at `index` if it is the first failing in order.
+    fn fail(&mut self, index: usize, error: ModuleError) {
+        let is_first = match &self.first_error {
+            Some((first, _)) => index < *first,
+            None => true,
+        };
+        if is_first {
+            self.first_error = Some((index, error));
+        }
+    }
+}
+
+/// The function bodies of [`translate_parallel`] that are handed out to the threads in order.
+type Jobs<'a> = Mutex<Enumerate<IterMut<'a, PendingFunc>>>;
+
+/// Translates the `pending` function bodies using up to `threads` threads.
+///
+/// Returns the merged [`PeepholeReport`] of all translated function bodies.
+///
+/// # Note
+///
+/// Every thread takes the next function body to translate once it has finished
+/// its previous one. The function bodies are committed to the [`Engine`] in the order
+/// of `pending` without any thread waiting for its turn, see [`Commits`].
+/// Since committing a stack-machine function body allocates its constant values
+/// this yields the same bytecode as translating all function bodies serially.
+///
+/// # Errors
+///
+/// If any function body fails to validate or translate. In this case the
+/// error of the first failing function body in the order of `pending` is returned.
+///
+/// [`Engine`]: crate::Engine
+pub fn translate_parallel(
+    mut pending: Vec<PendingFunc>,
+    builder: &ModuleBuilder,
+    threads: usize,
+) -> Result<PeepholeReport, ModuleError> {
+    let threads = threads.clamp(1, pending.len().max(1));
+    let jobs: Jobs = Mutex::new(pending.iter_mut().enumerate());
+    let commits = Commits::default();
+    let first_failure = AtomicUsize::new(usize::MAX);
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                let (jobs, commits, first_failure) = (&jobs, &commits, &first_failure);
+                scope.spawn(move || translate_jobs(jobs, builder, commits, first_failure))
+            })
+            .collect();
+        // Note: all workers are joined before propagating a panic
+        //       since no worker ever waits for another worker.
+        let outcomes: Vec<_> = workers.into_iter().map(ScopedJoinHandle::join).collect();
+        for outcome in outcomes {
+            outcome.unwrap_or_else(|error| panic::resume_unwind(error))
+        }
+    });
+    let state = commits
+        .state
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some((_, error)) = state.first_error {
+        return Err(error);
+    }
+    debug_assert!(state.parked.is_empty());
+    let mut peepholes = PeepholeReport::default();
+    for allocations in &state.allocations {
+        peepholes.merge(&allocations.translation.peephole_report());
+    }
+    Ok(peepholes)
+}
+
+/// Returns the next function body of `jobs` and its index if any.
+fn next_job<'a>(jobs: &Jobs<'a>) -> Option<(usize, &'a mut PendingFunc)> {
+    jobs.lock().unwrap_or_else(PoisonError::into_inner).next()
+}
+
+/// Translates the `jobs` on a single thread of [`translate_parallel`] until none are left.
+///
+/// Skips the translations of all function bodies after the `first_failure` of
+/// any thread. Function bodies before it are still translated since one of them
+/// might fail as well and is the one whose error must be reported.
+fn translate_jobs<'a>(
+    jobs: &Jobs<'a>,
+    builder: &'a ModuleBuilder,
+    commits: &Commits<'a>,
+    first_failure: &AtomicUsize,
+) {
+    let mut allocations: Option<ReusableAllocations> = None;
+    while let Some((index, func)) = next_job(jobs) {
+        if index > first_failure.load(Ordering::Relaxed) {
+            commits.commit(index, None, &mut allocations);
+            continue;
+        }
+        let reusable = allocations
+            .take()
+            .or_else(|| commits.take_allocations())
+            .unwrap_or_else(|| ReusableAllocations::new(builder.engine()));
+        let translated = match func.translate(builder, reusable) {
+            Ok(translated) => Some(translated),
+            Err(error) => {
+                first_failure.fetch_min(index, Ordering::Relaxed);
+                commits.fail(index, error);
+                None
+            }
+        };
+        commits.commit(index, translated, &mut allocations);
+    }
+    commits.put_allocations(allocations);
+}
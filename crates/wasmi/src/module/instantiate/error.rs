@@ -1,4 +1,5 @@
 use crate::{
+    core::Trap,
     errors::{MemoryError, TableError},
     global::GlobalError,
     Extern,
@@ -49,6 +50,8 @@ pub enum InstantiationError {
         /// The index of the found `start` function.
         index: u32,
     },
+    /// Caused when executing the `start` function of the instantiated module trapped.
+    StartFnTrapped(Trap),
     TooManyInstances,
 }
 
@@ -83,6 +86,9 @@ impl Display for InstantiationError {
             Self::FoundStartFn { index } => {
                 write!(f, "found an unexpected start function with index {index}")
             }
+            Self::StartFnTrapped(trap) => {
+                write!(f, "executing the start function trapped: {trap}")
+            }
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
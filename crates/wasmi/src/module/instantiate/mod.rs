@@ -59,6 +59,9 @@ impl Module {
             .store
             .check_new_instances_limit(1)?;
         let handle = context.as_context_mut().store.inner.alloc_instance();
+        if let Some(unit) = self.code_unit {
+            context.as_context_mut().store.inner.push_code_unit(unit);
+        }
         let mut builder = InstanceEntity::build(self);
 
         self.extract_imports(&context, &mut builder, externals)?;
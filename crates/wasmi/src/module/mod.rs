@@ -35,16 +35,18 @@ pub(crate) use self::{
     data::{DataSegment, DataSegmentKind},
     element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
     init_expr::ConstExpr,
-    utils::WasmiValueType,
+    parser::recompile,
+    utils::{ensure_supported_value_type, WasmiValueType},
 };
 use crate::{
-    engine::{CompiledFunc, DedupFuncType},
+    engine::{CodeUnit, CompiledFunc, DedupFuncType},
     Engine,
     Error,
     ExternType,
     FuncType,
     GlobalType,
     MemoryType,
+    PeepholeReport,
     TableType,
 };
 use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
@@ -66,6 +68,8 @@ pub struct Module {
     compiled_funcs: Box<[CompiledFunc]>,
     element_segments: Box<[ElementSegment]>,
     data_segments: Box<[DataSegment]>,
+    peephole_report: PeepholeReport,
+    code_unit: Option<CodeUnit>,
 }
 
 /// The index of the default Wasm linear memory.
@@ -144,6 +148,7 @@ impl Module {
     ///
     /// - If the `stream` cannot be decoded into a valid Wasm module.
     /// - If unsupported Wasm proposals are encountered.
+    /// - If called from within a Wasm execution of the `engine`, e.g. by a host function.
     pub fn new(engine: &Engine, stream: impl Read) -> Result<Self, Error> {
         parse(engine, stream).map_err(Into::into)
     }
@@ -153,6 +158,19 @@ impl Module {
         &self.engine
     }
 
+    /// Returns the [`PeepholeReport`] of the translation of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// Only the [`EngineBackend::RegisterMachine`] collects these counters.
+    /// For the [`EngineBackend::StackMachine`] all counters are zero.
+    ///
+    /// [`EngineBackend::RegisterMachine`]: crate::EngineBackend::RegisterMachine
+    /// [`EngineBackend::StackMachine`]: crate::EngineBackend::StackMachine
+    pub fn peephole_report(&self) -> PeepholeReport {
+        self.peephole_report
+    }
+
     /// Creates a new [`Module`] from the [`ModuleBuilder`].
     fn from_builder(builder: ModuleBuilder) -> Self {
         Self {
@@ -169,6 +187,8 @@ impl Module {
             compiled_funcs: builder.compiled_funcs.into(),
             element_segments: builder.element_segments.into(),
             data_segments: builder.data_segments.into(),
+            peephole_report: builder.peephole_report,
+            code_unit: builder.code_unit,
         }
     }
 
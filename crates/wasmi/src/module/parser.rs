@@ -1,5 +1,8 @@
+#[cfg(feature = "std")]
+use super::compile::{translate_parallel, PendingFunc};
 use super::{
     compile::translate,
+    ensure_supported_value_type,
     export::ExternIdx,
     global::Global,
     import::{FuncTypeIdx, Import},
@@ -13,16 +16,24 @@ use super::{
     Read,
 };
 use crate::{
-    engine::{ChosenFuncTranslatorAllocations, CompiledFunc},
+    engine::{
+        ChosenFuncTranslatorAllocations,
+        CompiledFunc,
+        Fnv1a,
+        ModuleStructure,
+        TranslationError,
+    },
     Engine,
+    EngineBackend,
     FuncType,
     MemoryType,
     TableType,
 };
 use alloc::{boxed::Box, vec::Vec};
-use core::{mem::replace, ops::Range};
+use core::{hash::Hasher, mem::replace, ops::Range};
 use wasmparser::{
     Chunk,
+    CustomSectionReader,
     DataSectionReader,
     ElementSectionReader,
     Encoding,
@@ -33,9 +44,12 @@ use wasmparser::{
     GlobalSectionReader,
     ImportSectionReader,
     MemorySectionReader,
+    Name,
+    NameSectionReader,
     Parser as WasmParser,
     Payload,
     TableSectionReader,
+    TypeRef,
     TypeSectionReader,
     Validator,
     WasmFeatures,
@@ -50,9 +64,29 @@ use wasmparser::{
 ///
 /// If the Wasm bytecode stream fails to validate.
 pub fn parse(engine: &Engine, stream: impl Read) -> Result<Module, ModuleError> {
+    engine.ensure_not_executing()?;
     ModuleParser::new(engine).parse(stream)
 }
 
+/// Recompiles the evicted compiled functions `funcs` of the Wasm module `wasm`.
+///
+/// # Note
+///
+/// The `funcs` must be the compiled functions of the internal functions of `wasm`
+/// in their order. They are initialized again instead of allocating new ones.
+///
+/// # Errors
+///
+/// If the Wasm module bytes `wasm` fail to validate.
+pub fn recompile(engine: &Engine, wasm: &[u8], funcs: &[CompiledFunc]) -> Result<(), ModuleError> {
+    engine.ensure_not_executing()?;
+    let mut parser = ModuleParser::new(engine);
+    parser.builder.evicted_funcs = funcs.into();
+    parser.wasm = None;
+    parser.parse(wasm)?;
+    Ok(())
+}
+
 /// Context used to construct a WebAssembly module from a stream of bytes.
 pub struct ModuleParser<'engine> {
     /// The module builder used throughout stream parsing.
@@ -65,6 +99,28 @@ pub struct ModuleParser<'engine> {
     compiled_funcs: u32,
     /// Reusable allocations for validating and translation functions.
     allocations: ReusableAllocations,
+    /// The fingerprint of the Wasm module bytes parsed so far.
+    ///
+    /// This is `None` unless the [`Engine`] uses [`EngineBackend::RegisterMachine`]
+    /// since only its compiled functions can be restored via [`Engine::deserialize_code`].
+    wasm_hasher: Option<Fnv1a>,
+    /// The structure of the Wasm module checked by restored function bodies.
+    ///
+    /// This is `None` unless the code section has been started and the [`Engine`]
+    /// uses [`EngineBackend::RegisterMachine`].
+    module_structure: Option<ModuleStructure>,
+    /// The validated function bodies awaiting their parallel translation.
+    ///
+    /// This is `None` if function bodies are translated serially while parsing.
+    #[cfg(feature = "std")]
+    pending_funcs: Option<Vec<PendingFunc>>,
+    /// The Wasm module bytes parsed so far.
+    ///
+    /// This is `None` unless a budget has been set via [`Config::set_compiled_code_budget`]
+    /// since only then the compiled functions are recompiled from the Wasm module bytes.
+    ///
+    /// [`Config::set_compiled_code_budget`]: crate::Config::set_compiled_code_budget
+    wasm: Option<Vec<u8>>,
 }
 
 /// Reusable heap allocations for function validation and translation.
@@ -97,6 +153,18 @@ impl<'engine> ModuleParser<'engine> {
             parser,
             compiled_funcs: 0,
             allocations: ReusableAllocations::new(engine),
+            wasm_hasher: matches!(
+                engine.config().engine_backend(),
+                EngineBackend::RegisterMachine
+            )
+            .then(Fnv1a::default),
+            module_structure: None,
+            #[cfg(feature = "std")]
+            pending_funcs: (engine.config().get_compilation_threads() > 1).then(Vec::new),
+            wasm: engine
+                .config()
+                .get_compiled_code_budget()
+                .map(|_| Vec::new()),
         }
     }
 
@@ -122,6 +190,12 @@ impl<'engine> ModuleParser<'engine> {
                     continue 'outer;
                 }
                 Chunk::Parsed { consumed, payload } => {
+                    if let Some(hasher) = &mut self.wasm_hasher {
+                        hasher.write(&buffer[..consumed]);
+                    }
+                    if let Some(wasm) = &mut self.wasm {
+                        wasm.extend_from_slice(&buffer[..consumed]);
+                    }
                     eof = self.process_payload(payload)?;
                     // Cut away the parts from the intermediate buffer that have already been parsed.
                     buffer.drain(..consumed);
@@ -131,6 +205,14 @@ impl<'engine> ModuleParser<'engine> {
                 }
             }
         }
+        self.builder
+            .peephole_report
+            .merge(&self.allocations.translation.peephole_report());
+        if let Some(wasm) = self.wasm {
+            let funcs = self.builder.compiled_funcs_2[..].into();
+            let unit = self.builder.engine().register_code_unit(wasm.into(), funcs);
+            self.builder.code_unit = Some(unit);
+        }
         Ok(self.builder.finish())
     }
 
@@ -186,7 +268,7 @@ impl<'engine> ModuleParser<'engine> {
             Payload::ElementSection(section) => self.process_element(section),
             Payload::DataCountSection { count, range } => self.process_data_count(count, range),
             Payload::DataSection(section) => self.process_data(section),
-            Payload::CustomSection { .. } => Ok(()),
+            Payload::CustomSection(reader) => self.process_custom_section(reader),
             Payload::CodeSectionStart { count, range, .. } => self.process_code_start(count, range),
             Payload::CodeSectionEntry(func_body) => self.process_code_entry(func_body),
             Payload::UnknownSection { id, range, .. } => self.process_unknown(id, range),
@@ -254,11 +336,32 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If an unsupported function type is encountered.
+    /// - If an unsupported function type is encountered.
+    /// - If a function type declares more parameters or results than allowed by the [`Config`].
+    ///
+    /// [`Config`]: crate::Config
     fn process_types(&mut self, section: TypeSectionReader) -> Result<(), ModuleError> {
         self.validator.type_section(&section)?;
+        let config = self.builder.engine().config();
+        let max_params = config.get_max_function_params();
+        let max_results = config.get_max_function_results();
+        let exceeds = |limit: Option<u32>, len: usize| match limit {
+            Some(limit) => len > limit as usize,
+            None => false,
+        };
         let func_types = section.into_iter().map(|result| match result? {
-            wasmparser::Type::Func(ty) => Ok(FuncType::from_wasmparser(ty)),
+            wasmparser::Type::Func(ty) => {
+                if exceeds(max_params, ty.params().len()) {
+                    return Err(TranslationError::too_many_function_type_params().into());
+                }
+                if exceeds(max_results, ty.results().len()) {
+                    return Err(TranslationError::too_many_function_type_results().into());
+                }
+                for value_type in ty.params().iter().chain(ty.results()) {
+                    ensure_supported_value_type(*value_type)?;
+                }
+                Ok(FuncType::from_wasmparser(ty))
+            }
         });
         self.builder.push_func_types(func_types)?;
         Ok(())
@@ -276,9 +379,13 @@ impl<'engine> ModuleParser<'engine> {
     /// - If an unsupported import declaration is encountered.
     fn process_imports(&mut self, section: ImportSectionReader) -> Result<(), ModuleError> {
         self.validator.import_section(&section)?;
-        let imports = section
-            .into_iter()
-            .map(|import| import.map(Import::from).map_err(ModuleError::from));
+        let imports = section.into_iter().map(|import| {
+            let import = import?;
+            if let TypeRef::Global(global_type) = import.ty {
+                ensure_supported_value_type(global_type.content_type)?;
+            }
+            Ok(Import::from(import))
+        });
         self.builder.push_imports(imports)?;
         Ok(())
     }
@@ -306,9 +413,17 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If a function declaration fails to validate.
+    /// - If a function declaration fails to validate.
+    /// - If the module defines more functions than allowed by the [`Config`].
+    ///
+    /// [`Config`]: crate::Config
     fn process_functions(&mut self, section: FunctionSectionReader) -> Result<(), ModuleError> {
         self.validator.function_section(&section)?;
+        if let Some(limit) = self.builder.engine().config().get_max_functions() {
+            if section.count() > limit {
+                return Err(ModuleError::from(TranslationError::too_many_functions()));
+            }
+        }
         let funcs = section
             .into_iter()
             .map(|func| func.map(FuncTypeIdx::from).map_err(ModuleError::from));
@@ -377,9 +492,11 @@ impl<'engine> ModuleParser<'engine> {
     /// If a global variable declaration fails to validate.
     fn process_globals(&mut self, section: GlobalSectionReader) -> Result<(), ModuleError> {
         self.validator.global_section(&section)?;
-        let globals = section
-            .into_iter()
-            .map(|global| global.map(Global::from).map_err(ModuleError::from));
+        let globals = section.into_iter().map(|global| {
+            let global = global?;
+            ensure_supported_value_type(global.ty.content_type)?;
+            Ok(Global::from(global))
+        });
         self.builder.push_globals(globals)?;
         Ok(())
     }
@@ -468,19 +585,66 @@ impl<'engine> ModuleParser<'engine> {
         Ok(())
     }
 
+    /// Process a custom section.
+    ///
+    /// # Note
+    ///
+    /// If enabled via [`Config::set_trap_locations`] this attaches the function names
+    /// of the `name` custom section to the compiled functions in order to locate their traps.
+    /// All other custom sections are ignored. Since custom sections are not validated a
+    /// malformed `name` section is ignored as well.
+    ///
+    /// [`Config::set_trap_locations`]: crate::Config::set_trap_locations
+    fn process_custom_section(&mut self, reader: CustomSectionReader) -> Result<(), ModuleError> {
+        if reader.name() != "name" || !self.builder.engine().config().get_trap_locations() {
+            return Ok(());
+        }
+        let len_func_imports = self.builder.imports.funcs.len();
+        for subsection in NameSectionReader::new(reader.data(), reader.data_offset()) {
+            let names = match subsection {
+                Ok(Name::Function(names)) => names,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            for naming in names.into_iter().map_while(Result::ok) {
+                let index = match (naming.index as usize).checked_sub(len_func_imports) {
+                    Some(index) if index < self.compiled_funcs as usize => index,
+                    _ => continue,
+                };
+                let engine = self.builder.engine();
+                match engine.config().engine_backend() {
+                    EngineBackend::StackMachine => {
+                        engine.set_func_name(self.builder.compiled_funcs[index], naming.name)
+                    }
+                    EngineBackend::RegisterMachine => {
+                        engine.set_func_name_2(self.builder.compiled_funcs_2[index], naming.name)
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Process module code section start.
     ///
     /// # Note
     ///
-    /// This currently does not do a lot but it might become important in the
-    /// future if we add parallel translation of function bodies to prepare for
-    /// the translation.
+    /// This records the [`ModuleStructure`] that function bodies restored
+    /// via [`Engine::deserialize_code`] are checked against.
     ///
     /// # Errors
     ///
     /// If the code start section fails to validate.
     fn process_code_start(&mut self, count: u32, range: Range<usize>) -> Result<(), ModuleError> {
         self.validator.code_section_start(count, &range)?;
+        if self.wasm_hasher.is_some() {
+            // Note: all tables, memories and globals are declared before the code section.
+            self.module_structure = Some(ModuleStructure::new(
+                &self.builder.tables,
+                &self.builder.memories,
+                &self.builder.globals,
+            ));
+        }
         Ok(())
     }
 
@@ -504,14 +668,46 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// This contains the local variables and Wasm instructions of
     /// a single function body.
-    /// This procedure is translating the Wasm bytecode into `wasmi` bytecode.
+    /// This procedure is translating the Wasm bytecode into `wasmi` bytecode
+    /// unless the function body has been restored via [`Engine::deserialize_code`].
+    ///
+    /// If parallel translation is enabled via [`Config::set_compilation_threads`]
+    /// the function body is only validated and translated together with all other
+    /// function bodies after processing the last code section entry.
     ///
     /// # Errors
     ///
     /// If the function body fails to validate.
+    ///
+    /// [`Config::set_compilation_threads`]: crate::Config::set_compilation_threads
     fn process_code_entry(&mut self, func_body: FunctionBody) -> Result<(), ModuleError> {
         let (func, compiled_func, compiled_func_2) = self.next_func();
         let validator = self.validator.code_section_entry(&func_body)?;
+        if let (Some(hasher), Some(structure)) = (&self.wasm_hasher, &self.module_structure) {
+            let restored = self.builder.engine().restore_func_2(
+                compiled_func_2,
+                func.into_u32(),
+                hasher.finish(),
+                structure,
+            );
+            if restored {
+                // Note: restored function bodies have already been validated and
+                //       translated by the engine that serialized them from the same
+                //       Wasm module bytes.
+                return self.process_code_end();
+            }
+        }
+        #[cfg(feature = "std")]
+        if let Some(pending_funcs) = &mut self.pending_funcs {
+            let compiled_funcs = (compiled_func, compiled_func_2);
+            pending_funcs.push(PendingFunc::new(
+                func,
+                compiled_funcs,
+                &func_body,
+                validator,
+            )?);
+            return self.process_code_end();
+        }
         let module_resources = ModuleResources::new(&self.builder);
         let dummy_allocations = ReusableAllocations::new(self.builder.engine());
         let allocations = replace(&mut self.allocations, dummy_allocations);
@@ -527,6 +723,25 @@ impl<'engine> ModuleParser<'engine> {
         Ok(())
     }
 
+    /// Translates the pending function bodies in parallel after the last code section entry.
+    ///
+    /// Does nothing if function bodies are translated serially while parsing.
+    ///
+    /// # Errors
+    ///
+    /// If any pending function body fails to validate or translate.
+    fn process_code_end(&mut self) -> Result<(), ModuleError> {
+        #[cfg(feature = "std")]
+        if self.compiled_funcs as usize == self.builder.compiled_funcs.len() {
+            if let Some(pending_funcs) = self.pending_funcs.take() {
+                let threads = self.builder.engine().config().get_compilation_threads();
+                let peepholes = translate_parallel(pending_funcs, &self.builder, threads)?;
+                self.builder.peephole_report.merge(&peepholes);
+            }
+        }
+        Ok(())
+    }
+
     /// Process the entries for the Wasm component model proposal.
     fn process_unsupported_component_model(
         &mut self,
@@ -1,4 +1,4 @@
-use crate::{FuncType, GlobalType, MemoryType, Mutability, TableType};
+use crate::{engine::TranslationError, FuncType, GlobalType, MemoryType, Mutability, TableType};
 use wasmi_core::ValueType;
 
 impl TableType {
@@ -28,10 +28,6 @@ impl MemoryType {
             !memory_type.memory64,
             "wasmi does not support the `memory64` Wasm proposal"
         );
-        assert!(
-            !memory_type.shared,
-            "wasmi does not support the `threads` Wasm proposal"
-        );
         let initial: u32 = memory_type
             .initial
             .try_into()
@@ -41,8 +37,11 @@ impl MemoryType {
             .map(TryInto::try_into)
             .transpose()
             .expect("wasm32 memories must have a valid u32 maximum size if any");
-        Self::new(initial, maximum)
-            .expect("encountered invalid wasmparser::MemoryType after validation")
+        let memory_type = match (memory_type.shared, maximum) {
+            (true, Some(maximum)) => Self::new_shared(initial, maximum),
+            _ => Self::new(initial, maximum),
+        };
+        memory_type.expect("encountered invalid wasmparser::MemoryType after validation")
     }
 }
 
@@ -107,6 +106,22 @@ impl From<ValueType> for WasmiValueType {
     }
 }
 
+/// Returns an error if `wasmi` does not support values of type `value_type`.
+///
+/// # Note
+///
+/// Values of the `v128` type of the Wasm `simd` proposal may only flow between the
+/// few `simd` operators supported by `wasmi` and thus must not appear in function
+/// signatures, block types, local or global variables.
+pub fn ensure_supported_value_type(
+    value_type: wasmparser::ValType,
+) -> Result<(), TranslationError> {
+    match value_type {
+        wasmparser::ValType::V128 => Err(TranslationError::unsupported_value_type(value_type)),
+        _ => Ok(()),
+    }
+}
+
 impl From<wasmparser::ValType> for WasmiValueType {
     fn from(value_type: wasmparser::ValType) -> Self {
         match value_type {
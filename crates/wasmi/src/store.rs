@@ -1,5 +1,5 @@
 use crate::{
-    engine::DedupFuncType,
+    engine::{CodeUnit, DedupFuncType},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
     func::{Trampoline, TrampolineEntity, TrampolineIdx},
     memory::{DataSegment, MemoryError},
@@ -29,7 +29,7 @@ use crate::{
     TableEntity,
     TableIdx,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     fmt::{self, Debug},
     sync::atomic::{AtomicU32, Ordering},
@@ -101,6 +101,32 @@ impl<T> Debug for ResourceLimiterQuery<T> {
     }
 }
 
+/// The kind of transition reported to a call observer of a [`Store`].
+///
+/// # Note
+///
+/// Call observers are installed via [`Store::set_call_observer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CallTransition {
+    /// The execution enters a called Wasm function.
+    Enter,
+    /// The execution exits a Wasm function by returning from it.
+    ///
+    /// Tail calls exit the calling Wasm function before entering the called one.
+    Exit,
+}
+
+/// A wrapper around a boxed `dyn FnMut(u32, CallTransition)` that observes Wasm calls.
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works on structs that contain it.
+struct CallObserver(Box<dyn FnMut(u32, CallTransition) + Send + Sync>);
+impl Debug for CallObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CallObserver(...)")
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -150,6 +176,15 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// User provided observer of Wasm function calls and returns.
+    call_observer: Option<CallObserver>,
+    /// The [`CodeUnit`] of all Wasm modules instantiated in the [`Store`].
+    ///
+    /// Only Wasm modules compiled with a budget set via [`Config::set_compiled_code_budget`]
+    /// have a [`CodeUnit`].
+    ///
+    /// [`Config::set_compiled_code_budget`]: crate::Config::set_compiled_code_budget
+    code_units: Vec<CodeUnit>,
 }
 
 #[test]
@@ -272,6 +307,8 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel: Fuel::default(),
+            call_observer: None,
+            code_units: Vec::new(),
         }
     }
 
@@ -280,6 +317,18 @@ impl StoreInner {
         &self.engine
     }
 
+    /// Returns the [`CodeUnit`] of all Wasm modules instantiated in the [`Store`].
+    pub fn code_units(&self) -> &[CodeUnit] {
+        &self.code_units
+    }
+
+    /// Records that the Wasm module of the [`CodeUnit`] has been instantiated in the [`Store`].
+    pub fn push_code_unit(&mut self, unit: CodeUnit) {
+        if !self.code_units.contains(&unit) {
+            self.code_units.push(unit);
+        }
+    }
+
     /// Returns a shared reference to the [`Fuel`] counters.
     pub fn fuel(&self) -> &Fuel {
         &self.fuel
@@ -290,6 +339,24 @@ impl StoreInner {
         &mut self.fuel
     }
 
+    /// Notifies the installed call observer about a `transition` of the Wasm function `func_index`.
+    ///
+    /// Does nothing if no call observer has been installed.
+    ///
+    /// # Note
+    ///
+    /// The `func_index` is only queried if a call observer has been installed.
+    #[inline]
+    pub fn notify_call_observer(
+        &mut self,
+        func_index: impl FnOnce() -> u32,
+        transition: CallTransition,
+    ) {
+        if let Some(CallObserver(observer)) = &mut self.call_observer {
+            observer(func_index(), transition)
+        }
+    }
+
     /// Wraps an entity `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -761,6 +828,32 @@ impl<T> Store<T> {
         self.limiter = Some(ResourceLimiterQuery(Box::new(limiter)))
     }
 
+    /// Installs an `observer` into the [`Store`] that is called every time the
+    /// execution enters or exits a Wasm function.
+    ///
+    /// The `observer` receives the index of the Wasm function within its module
+    /// and the kind of [`CallTransition`]. This replaces any previously installed observer.
+    ///
+    /// # Note
+    ///
+    /// - Only the [`EngineBackend::RegisterMachine`] reports call transitions.
+    /// - Host functions are not reported.
+    /// - No [`CallTransition::Exit`] is reported for Wasm functions that are
+    ///   unwound because of a trap.
+    ///
+    /// [`EngineBackend::RegisterMachine`]: crate::EngineBackend::RegisterMachine
+    pub fn set_call_observer(
+        &mut self,
+        observer: impl FnMut(u32, CallTransition) + Send + Sync + 'static,
+    ) {
+        self.inner.call_observer = Some(CallObserver(Box::new(observer)))
+    }
+
+    /// Removes the call observer installed via [`Store::set_call_observer`] if any.
+    pub fn clear_call_observer(&mut self) {
+        self.inner.call_observer = None;
+    }
+
     pub(crate) fn check_new_instances_limit(
         &mut self,
         num_new_instances: usize,
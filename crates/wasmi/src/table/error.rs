@@ -39,6 +39,10 @@ pub enum TableError {
         other: TableType,
     },
     TooManyTables,
+    /// Tried to restore a [`TableSnapshot`] that is smaller than the minimum size of the table.
+    ///
+    /// [`TableSnapshot`]: crate::TableSnapshot
+    InvalidSnapshot,
 }
 
 impl Display for TableError {
@@ -74,6 +78,9 @@ impl Display for TableError {
             Self::TooManyTables => {
                 write!(f, "too many tables")
             }
+            Self::InvalidSnapshot => {
+                write!(f, "table snapshot is smaller than the minimum table size")
+            }
         }
     }
 }
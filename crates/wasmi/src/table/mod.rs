@@ -12,7 +12,7 @@ use crate::{
     FuncRef,
     Value,
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use core::cmp::max;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{TrapCode, UntypedValue, ValueType};
@@ -504,6 +504,88 @@ impl TableEntity {
         dst.fill(val);
         Ok(())
     }
+
+    /// Returns a [`TableSnapshot`] of the current elements of the table.
+    pub fn snapshot(&self) -> TableSnapshot {
+        TableSnapshot {
+            element: self.ty().element(),
+            elements: self.elements[..].into(),
+        }
+    }
+
+    /// Restores the elements of the table to the `snapshot`.
+    ///
+    /// # Note
+    ///
+    /// The table is grown if the `snapshot` is larger than the table
+    /// and shrunk if it is smaller.
+    ///
+    /// # Errors
+    ///
+    /// - If the element type of the `snapshot` does not match the table element type.
+    /// - If the `snapshot` is smaller than the minimum size of the table.
+    /// - If the table cannot be grown to the size of the `snapshot`.
+    pub fn restore(
+        &mut self,
+        snapshot: &TableSnapshot,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<(), TableError> {
+        self.ty().matches_element_type(snapshot.element)?;
+        let desired = snapshot.size();
+        if desired < self.ty().minimum() {
+            return Err(TableError::InvalidSnapshot);
+        }
+        let current = self.size();
+        if let Some(delta) = desired.checked_sub(current) {
+            self.grow_untyped(delta, UntypedValue::default(), limiter)
+                .map_err(|_| TableError::GrowOutOfBounds {
+                    maximum: self.ty().maximum().unwrap_or(u32::MAX),
+                    current,
+                    delta,
+                })?;
+        }
+        self.elements.truncate(snapshot.elements.len());
+        self.elements.copy_from_slice(&snapshot.elements);
+        Ok(())
+    }
+}
+
+/// A snapshot of the elements of a [`Table`].
+///
+/// # Note
+///
+/// - Created via [`Table::snapshot`] and restored via [`Table::restore`].
+/// - Function references stored in the snapshot are only valid for the
+///   [`Store`] owning the snapshotted [`Table`].
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Clone)]
+pub struct TableSnapshot {
+    /// The element type of the snapshotted [`Table`].
+    element: ValueType,
+    /// The elements of the snapshotted [`Table`].
+    elements: Box<[UntypedValue]>,
+}
+
+impl TableSnapshot {
+    /// Returns the element type of the snapshotted [`Table`].
+    pub fn element(&self) -> ValueType {
+        self.element
+    }
+
+    /// Returns the number of elements of the snapshotted [`Table`].
+    pub fn size(&self) -> u32 {
+        self.elements.len() as u32
+    }
+
+    /// Returns the element of the snapshotted [`Table`] at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: u32) -> Option<Value> {
+        self.elements
+            .get(index as usize)
+            .map(|untyped| untyped.with_type(self.element))
+    }
 }
 
 /// A Wasm table reference.
@@ -645,6 +727,47 @@ impl Table {
             .set(index, value)
     }
 
+    /// Returns a [`TableSnapshot`] of the current elements of the [`Table`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn snapshot(&self, ctx: impl AsContext) -> TableSnapshot {
+        ctx.as_context().store.inner.resolve_table(self).snapshot()
+    }
+
+    /// Restores the elements of the [`Table`] to the `snapshot`.
+    ///
+    /// # Note
+    ///
+    /// This allows to deterministically replay executions by restoring a
+    /// [`TableSnapshot`] taken via [`Table::snapshot`]. The [`Table`] is
+    /// grown if the `snapshot` is larger than the [`Table`] and shrunk if it is
+    /// smaller, e.g. after it has been grown by a Wasm execution.
+    ///
+    /// # Errors
+    ///
+    /// - If the element type of the `snapshot` does not match the [`Table`] element type.
+    /// - If the `snapshot` is smaller than the minimum size of the [`Table`].
+    /// - If the [`Table`] cannot be grown to the size of the `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn restore(
+        &self,
+        mut ctx: impl AsContextMut,
+        snapshot: &TableSnapshot,
+    ) -> Result<(), TableError> {
+        let (inner, mut limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        inner
+            .resolve_table_mut(self)
+            .restore(snapshot, &mut limiter)
+    }
+
     /// Returns `true` if `lhs` and `rhs` [`Table`] refer to the same entity.
     ///
     /// # Note
@@ -0,0 +1,118 @@
+//! Tests the opt-in approximate execution of the Wasm `f32.sqrt` and `f64.sqrt` operators.
+//!
+//! # Note
+//!
+//! Read [`Config::set_approx_float_sqrt`] for more information.
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{
+    core::{F32, F64},
+    EngineBackend,
+    Store,
+    TypedFunc,
+};
+
+/// The positive normal operands for which the approximation may differ from `sqrt`.
+const INPUTS: [f64; 6] = [2.0, 3.0, 0.7, 1e-20, 1e20, 12345.678];
+
+/// The exported `f32.sqrt` and `f64.sqrt` functions of the module under test.
+struct Sqrt {
+    store: Store<()>,
+    f32_sqrt: TypedFunc<F32, F32>,
+    f64_sqrt: TypedFunc<F64, F64>,
+}
+
+impl Sqrt {
+    /// Instantiates the module under test, approximating `sqrt` if `approx` is `true`.
+    fn new(backend: EngineBackend, approx: bool) -> Self {
+        let wat = r#"
+            (module
+                (func (export "f32.sqrt") (param f32) (result f32)
+                    (f32.sqrt (local.get 0))
+                )
+                (func (export "f64.sqrt") (param f64) (result f64)
+                    (f64.sqrt (local.get 0))
+                )
+            )
+        "#;
+        let mut config = backend_config(backend);
+        config.set_approx_float_sqrt(approx);
+        let (store, instance) = instantiate(&config, wat);
+        let f32_sqrt = instance.get_typed_func(&store, "f32.sqrt").unwrap();
+        let f64_sqrt = instance.get_typed_func(&store, "f64.sqrt").unwrap();
+        Self {
+            store,
+            f32_sqrt,
+            f64_sqrt,
+        }
+    }
+
+    /// Executes the Wasm `f32.sqrt` operator.
+    fn f32(&mut self, input: f32) -> f32 {
+        let result = self.f32_sqrt.call(&mut self.store, F32::from(input));
+        f32::from(result.unwrap())
+    }
+
+    /// Executes the Wasm `f64.sqrt` operator.
+    fn f64(&mut self, input: f64) -> f64 {
+        let result = self.f64_sqrt.call(&mut self.store, F64::from(input));
+        f64::from(result.unwrap())
+    }
+}
+
+fn assert_exact_by_default(backend: EngineBackend) {
+    let mut sqrt = Sqrt::new(backend, false);
+    for input in INPUTS {
+        let result = sqrt.f64(input);
+        assert_eq!(result.to_bits(), input.sqrt().to_bits(), "{input:?}");
+        let input = input as f32;
+        let result = sqrt.f32(input);
+        assert_eq!(result.to_bits(), input.sqrt().to_bits(), "{input:?}");
+    }
+}
+
+fn assert_approx(backend: EngineBackend) {
+    let mut sqrt = Sqrt::new(backend, true);
+    let mut inexact = 0;
+    for input in INPUTS {
+        let exact = input.sqrt();
+        let result = sqrt.f64(input);
+        assert!(((result - exact) / exact).abs() < 1e-5, "{input:?}");
+        inexact += usize::from(result != exact);
+        let input = input as f32;
+        let exact = input.sqrt();
+        let result = sqrt.f32(input);
+        assert!(((result - exact) / exact).abs() < 1e-3, "{input:?}");
+        inexact += usize::from(result != exact);
+    }
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        // The approximation is only used where the host CPU provides an estimate.
+        assert_ne!(inexact, 0);
+    }
+    // Special operands are still handled exactly.
+    for input in [0.0, -0.0, f64::INFINITY] {
+        let result = sqrt.f64(input);
+        assert_eq!(result.to_bits(), input.sqrt().to_bits(), "{input:?}");
+        let input = input as f32;
+        let result = sqrt.f32(input);
+        assert_eq!(result.to_bits(), input.sqrt().to_bits(), "{input:?}");
+    }
+    for input in [-1.0, f64::NEG_INFINITY, f64::NAN] {
+        assert!(sqrt.f64(input).is_nan(), "{input:?}");
+        assert!(sqrt.f32(input as f32).is_nan(), "{input:?}");
+    }
+}
+
+#[test]
+fn exact_by_default() {
+    for backend in BACKENDS {
+        assert_exact_by_default(backend)
+    }
+}
+
+#[test]
+fn approx() {
+    for backend in BACKENDS {
+        assert_approx(backend)
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests for `br_table` instructions whose targets receive branch parameters.
+//!
+//! # Note
+//!
+//! The register-machine translator copies the branch parameters separately
+//! for every target if the targets do not share their branch parameter registers.
+
+use super::for_each_backend;
+
+/// The Wasm module under test.
+///
+/// Every `br_table` target forwards `$input` to a block that computes a distinct result.
+/// The `global.get` forces the targets to use different branch parameter registers.
+const WAT: &str = r#"
+    (module
+        (global $g (mut i32) (i32.const 100))
+        (func (export "br_table") (param $index i32) (param $input i32) (result i32)
+            (block (result i32)
+                (block (result i32)
+                    (global.get $g)
+                    (block (result i32)
+                        (local.get $input)
+                        (br_table 0 1 2 3 1 (local.get $index))
+                    )
+                    (return (i32.add))
+                )
+                (return (i32.sub (i32.const 10)))
+            )
+            (return (i32.mul (i32.const 10)))
+        )
+    )
+"#;
+
+#[test]
+fn br_table() {
+    for_each_backend(WAT, |mut store, instance| {
+        let func = instance
+            .get_typed_func::<(u32, i32), i32>(&store, "br_table")
+            .unwrap();
+        let input = 7;
+        let expected = [
+            (0, input + 100),
+            (1, input - 10),
+            (2, input * 10),
+            (3, input),
+            // Out of bounds indices branch to the default target.
+            (4, input - 10),
+            (5, input - 10),
+            (u32::MAX, input - 10),
+        ];
+        for (index, result) in expected {
+            let actual = func.call(&mut store, (index, input)).unwrap();
+            assert_eq!(actual, result, "index {index}");
+        }
+    });
+}
@@ -0,0 +1,134 @@
+//! Tests for `br_if` instructions branching on the result of an `i32` comparison.
+//!
+//! # Note
+//!
+//! The register-machine translator fuses these into a single compare and branch
+//! instruction for backward branches to `loop` headers and for forward branches.
+//! Forward branches too far for the fused instruction use a fallback instruction.
+
+use super::for_each_backend;
+
+/// The Rust semantics of a Wasm `i32` comparison operator.
+type Cmp = fn(i32, i32) -> bool;
+
+/// All Wasm `i32` comparison operators together with their Rust semantics.
+const OPS: &[(&str, Cmp)] = &[
+    ("i32.eq", |lhs, rhs| lhs == rhs),
+    ("i32.ne", |lhs, rhs| lhs != rhs),
+    ("i32.lt_s", |lhs, rhs| lhs < rhs),
+    ("i32.lt_u", |lhs, rhs| (lhs as u32) < (rhs as u32)),
+    ("i32.le_s", |lhs, rhs| lhs <= rhs),
+    ("i32.le_u", |lhs, rhs| (lhs as u32) <= (rhs as u32)),
+    ("i32.gt_s", |lhs, rhs| lhs > rhs),
+    ("i32.gt_u", |lhs, rhs| (lhs as u32) > (rhs as u32)),
+    ("i32.ge_s", |lhs, rhs| lhs >= rhs),
+    ("i32.ge_u", |lhs, rhs| (lhs as u32) >= (rhs as u32)),
+];
+
+/// The operand values tested for every pair of inputs.
+const INPUTS: [i32; 6] = [i32::MIN, -1, 0, 1, 5, i32::MAX];
+
+/// The constant right-hand side operands tested for every input.
+const IMMS: [i32; 4] = [-1, 0, 5, 100_000];
+
+/// Returns a function body that returns `1` if the `br_if` on `cond` back to the loop is taken.
+fn loop_body(cond: &str) -> String {
+    format!(
+        r#"
+        (local $taken i32)
+        (block $exit
+            (loop $loop
+                (br_if $exit (local.get $taken))
+                (local.set $taken (i32.const 1))
+                (br_if $loop {cond})
+                (local.set $taken (i32.const 0))
+            )
+        )
+        (local.get $taken)
+        "#
+    )
+}
+
+/// The number of `br_table` targets skipped by forward branches that do not fit into 16 bits.
+const FAR: usize = 1 << 15;
+
+/// Returns a function body that returns `1` if the forward `br_if` on `cond` is taken.
+///
+/// The `br_if` skips over a `br_table` with `skipped` targets which is never executed.
+fn forward_body(cond: &str, skipped: usize) -> String {
+    let targets = "0 ".repeat(skipped);
+    format!(
+        r#"
+        (local $taken i32)
+        (local.set $taken (i32.const 1))
+        (block $exit
+            (br_if $exit {cond})
+            (local.set $taken (i32.const 0))
+            (if (global.get $g) (then (br_table {targets} 0 (local.get 0))))
+        )
+        (local.get $taken)
+        "#
+    )
+}
+
+/// Builds a function body from the condition of its `br_if`.
+type BodyBuilder = fn(cond: &str) -> String;
+
+/// The kinds of tested branches with their function body builder.
+const KINDS: [(&str, BodyBuilder); 3] = [
+    ("loop", loop_body),
+    ("fwd", |cond| forward_body(cond, 1)),
+    ("far", |cond| forward_body(cond, FAR)),
+];
+
+/// Returns the Wasm module under test.
+///
+/// For every operator and every kind of branch this exports a function comparing
+/// both of its parameters and a function comparing its parameter with each of the [`IMMS`].
+/// The kind of branch is denoted by the `loop`, `fwd` or `far` prefix of the export names.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for (kind, make_body) in KINDS {
+        for (op, _) in OPS {
+            let body = make_body(&format!("({op} (local.get 0) (local.get 1))"));
+            funcs.push_str(&format!(
+                r#"(func (export "{kind} {op}") (param i32 i32) (result i32) {body})"#
+            ));
+            for imm in IMMS {
+                let body = make_body(&format!("({op} (local.get 0) (i32.const {imm}))"));
+                funcs.push_str(&format!(
+                    r#"(func (export "{kind} {op} {imm}") (param i32) (result i32) {body})"#
+                ));
+            }
+        }
+    }
+    format!("(module (global $g (mut i32) (i32.const 0)) {funcs})")
+}
+
+#[test]
+fn branch_cmp() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for (kind, _) in KINDS {
+            for (op, cmp) in OPS {
+                let func = instance
+                    .get_typed_func::<(i32, i32), i32>(&store, &format!("{kind} {op}"))
+                    .unwrap();
+                for lhs in INPUTS {
+                    for rhs in INPUTS {
+                        let taken = func.call(&mut store, (lhs, rhs)).unwrap();
+                        assert_eq!(taken, i32::from(cmp(lhs, rhs)), "{kind} {op} {lhs} {rhs}");
+                    }
+                }
+                for imm in IMMS {
+                    let func = instance
+                        .get_typed_func::<i32, i32>(&store, &format!("{kind} {op} {imm}"))
+                        .unwrap();
+                    for lhs in INPUTS {
+                        let taken = func.call(&mut store, lhs).unwrap();
+                        assert_eq!(taken, i32::from(cmp(lhs, imm)), "{kind} {op} {lhs} {imm}");
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,115 @@
+//! Tests for observing Wasm function calls via [`Store::set_call_observer`].
+
+use super::{backend_config, instantiate};
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    CallTransition::{self, Enter, Exit},
+    EngineBackend,
+    Instance,
+    Store,
+};
+
+/// The Wasm module under test.
+///
+/// The function indices are denoted in the comments.
+const WAT: &str = r#"
+    (module
+        ;; 0
+        (func $root (export "root") (result i32)
+            (i32.add (call $a) (call $b))
+        )
+        ;; 1
+        (func $a (result i32)
+            (i32.add (call $b) (i32.const 1))
+        )
+        ;; 2
+        (func $b (result i32)
+            (i32.const 10)
+        )
+        ;; 3
+        (func (export "tail") (result i32)
+            (return_call $a)
+        )
+    )
+"#;
+
+/// The call events recorded by the call observer.
+type Events = Arc<Mutex<Vec<(u32, CallTransition)>>>;
+
+/// Instantiates the module under test with tail calls enabled and installs a call observer.
+///
+/// Returns the events recorded by the installed call observer.
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Events) {
+    let mut config = backend_config(backend);
+    config.wasm_tail_call(true);
+    let (mut store, instance) = instantiate(&config, WAT);
+    let events = Events::default();
+    let recorded = events.clone();
+    store.set_call_observer(move |func_index, transition| {
+        recorded.lock().unwrap().push((func_index, transition));
+    });
+    (store, instance, events)
+}
+
+/// Calls the export `name` of the `instance` and returns the recorded call events.
+fn call(
+    store: &mut Store<()>,
+    instance: &Instance,
+    events: &Events,
+    name: &str,
+) -> Vec<(u32, CallTransition)> {
+    events.lock().unwrap().clear();
+    instance
+        .get_typed_func::<(), i32>(&*store, name)
+        .unwrap()
+        .call(store, ())
+        .unwrap();
+    events.lock().unwrap().clone()
+}
+
+#[test]
+fn nested_calls() {
+    let (mut store, instance, events) = setup(EngineBackend::RegisterMachine);
+    assert_eq!(
+        call(&mut store, &instance, &events, "root"),
+        [
+            (0, Enter),
+            (1, Enter),
+            (2, Enter),
+            (2, Exit),
+            (1, Exit),
+            (2, Enter),
+            (2, Exit),
+            (0, Exit),
+        ]
+    );
+}
+
+#[test]
+fn tail_calls() {
+    let (mut store, instance, events) = setup(EngineBackend::RegisterMachine);
+    assert_eq!(
+        call(&mut store, &instance, &events, "tail"),
+        [
+            (3, Enter),
+            (3, Exit),
+            (1, Enter),
+            (2, Enter),
+            (2, Exit),
+            (1, Exit),
+        ]
+    );
+}
+
+#[test]
+fn cleared_observer() {
+    let (mut store, instance, events) = setup(EngineBackend::RegisterMachine);
+    store.clear_call_observer();
+    assert!(call(&mut store, &instance, &events, "root").is_empty());
+}
+
+#[test]
+fn stack_machine_is_unobserved() {
+    let (mut store, instance, events) = setup(EngineBackend::StackMachine);
+    assert!(call(&mut store, &instance, &events, "root").is_empty());
+}
@@ -0,0 +1,138 @@
+//! Tests for calls whose parameters are stored in contiguous registers.
+//!
+//! # Note
+//!
+//! The register-machine engine backend copies such parameters to the
+//! parameter registers of the callee at once instead of one by one.
+
+use super::{backend_config, instantiate_with, BACKENDS};
+use wasmi::{Caller, Engine, EngineBackend, Func, Instance, Linker, Store};
+
+/// The Wasm module under test.
+///
+/// All exports forward their parameters to a function computing their
+/// weighted sum so that the order of the parameters is observable.
+///
+/// - `internal` calls a Wasm function.
+/// - `imported4` and `imported6` call host functions with differently sized signatures.
+/// - `indirect` calls a Wasm function via a table.
+/// - `reversed` passes the parameters in reversed and thus non-contiguous order.
+const WAT: &str = r#"
+    (module
+        (import "env" "sum4" (func $sum4 (param i32 i32 i32 i32) (result i32)))
+        (import "env" "sum6" (func $sum6 (param i32 i32 i32 i32 i32 i32) (result i32)))
+        (type $sum5_type (func (param i32 i32 i32 i32 i32) (result i32)))
+        (table 1 funcref)
+        (elem (i32.const 0) $sum5)
+        (func $sum5 (param i32 i32 i32 i32 i32) (result i32)
+            (i32.add
+                (i32.add
+                    (i32.add (local.get 0) (i32.mul (local.get 1) (i32.const 10)))
+                    (i32.add
+                        (i32.mul (local.get 2) (i32.const 100))
+                        (i32.mul (local.get 3) (i32.const 1000))
+                    )
+                )
+                (i32.mul (local.get 4) (i32.const 10000))
+            )
+        )
+        (func (export "internal") (param i32 i32 i32 i32 i32) (result i32)
+            (call $sum5 (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4))
+        )
+        (func (export "imported4") (param i32 i32 i32 i32) (result i32)
+            (call $sum4 (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+        )
+        (func (export "imported6") (param i32 i32 i32 i32 i32 i32) (result i32)
+            (call $sum6
+                (local.get 0) (local.get 1) (local.get 2)
+                (local.get 3) (local.get 4) (local.get 5)
+            )
+        )
+        (func (export "indirect") (param i32 i32 i32 i32 i32) (result i32)
+            (call_indirect (type $sum5_type)
+                (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+                (i32.const 0)
+            )
+        )
+        (func (export "reversed") (param i32 i32 i32 i32 i32) (result i32)
+            (call $sum5 (local.get 4) (local.get 3) (local.get 2) (local.get 1) (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates the module under test with its `sum4` and `sum6` host functions.
+fn setup(backend: EngineBackend) -> (Store<()>, Instance) {
+    let engine = Engine::new(&backend_config(backend));
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "sum4",
+            |_caller: Caller<()>, a: i32, b: i32, c: i32, d: i32| -> i32 {
+                a + 10 * b + 100 * c + 1000 * d
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "sum6",
+            |_caller: Caller<()>, a: i32, b: i32, c: i32, d: i32, e: i32, f: i32| -> i32 {
+                a + 10 * b + 100 * c + 1000 * d + 10000 * e + 100000 * f
+            },
+        )
+        .unwrap();
+    instantiate_with(&linker, (), WAT)
+}
+
+/// Returns `true` if `func` passes its call parameters via a single register span.
+fn uses_register_span(store: &Store<()>, func: &Func) -> bool {
+    store
+        .engine()
+        .func_instrs(store, func)
+        .unwrap()
+        .any(|instr| format!("{instr:?}").starts_with("RegisterSpan"))
+}
+
+fn assert_call_params(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    for name in ["internal", "indirect", "reversed"] {
+        let func = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32), i32>(&store, name)
+            .unwrap();
+        let expected = match name {
+            "reversed" => 12345,
+            _ => 54321,
+        };
+        assert_eq!(func.call(&mut store, (1, 2, 3, 4, 5)).unwrap(), expected);
+    }
+    let imported4 = instance
+        .get_typed_func::<(i32, i32, i32, i32), i32>(&store, "imported4")
+        .unwrap();
+    assert_eq!(imported4.call(&mut store, (1, 2, 3, 4)).unwrap(), 4321);
+    let imported6 = instance
+        .get_typed_func::<(i32, i32, i32, i32, i32, i32), i32>(&store, "imported6")
+        .unwrap();
+    assert_eq!(
+        imported6.call(&mut store, (1, 2, 3, 4, 5, 6)).unwrap(),
+        654321
+    );
+}
+
+#[test]
+fn call_params() {
+    for backend in BACKENDS {
+        assert_call_params(backend)
+    }
+}
+
+#[test]
+fn contiguous_call_params_use_span_register_machine() {
+    let (store, instance) = setup(EngineBackend::RegisterMachine);
+    for name in ["internal", "imported4", "imported6", "indirect"] {
+        let func = instance.get_func(&store, name).unwrap();
+        assert!(uses_register_span(&store, &func), "{name}");
+    }
+    let reversed = instance.get_func(&store, "reversed").unwrap();
+    assert!(!uses_register_span(&store, &reversed));
+}
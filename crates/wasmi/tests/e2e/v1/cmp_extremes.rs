@@ -0,0 +1,76 @@
+//! Tests that comparisons against the extreme values of their operand type
+//! evaluate to the same constant result for every input.
+
+use super::for_each_backend;
+use wasmi::Value;
+
+/// Comparisons against a constant extreme value with their always-expected result.
+///
+/// Each entry consists of the Wasm operator, its operand type, the constant
+/// right-hand side operand and the expected result.
+const CMPS: &[(&str, &str, &str, bool)] = &[
+    ("i32.lt_u", "i32", "0", false),
+    ("i32.ge_u", "i32", "0", true),
+    ("i32.le_u", "i32", "0xFFFF_FFFF", true),
+    ("i32.gt_u", "i32", "0xFFFF_FFFF", false),
+    ("i32.lt_s", "i32", "0x8000_0000", false),
+    ("i32.ge_s", "i32", "0x8000_0000", true),
+    ("i32.le_s", "i32", "0x7FFF_FFFF", true),
+    ("i32.gt_s", "i32", "0x7FFF_FFFF", false),
+    ("i64.lt_u", "i64", "0", false),
+    ("i64.ge_u", "i64", "0", true),
+    ("i64.le_u", "i64", "0xFFFF_FFFF_FFFF_FFFF", true),
+    ("i64.gt_u", "i64", "0xFFFF_FFFF_FFFF_FFFF", false),
+    ("i64.lt_s", "i64", "0x8000_0000_0000_0000", false),
+    ("i64.ge_s", "i64", "0x8000_0000_0000_0000", true),
+    ("i64.le_s", "i64", "0x7FFF_FFFF_FFFF_FFFF", true),
+    ("i64.gt_s", "i64", "0x7FFF_FFFF_FFFF_FFFF", false),
+];
+
+/// Returns the Wasm module under test.
+///
+/// For every entry in [`CMPS`] this exports a function, named by its index,
+/// that compares its parameter against the constant operand.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for (n, (op, ty, rhs, _)) in CMPS.iter().enumerate() {
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{n}") (param {ty}) (result i32)
+                ({op} (local.get 0) ({ty}.const {rhs}))
+            )
+            "#
+        ));
+    }
+    format!("(module {funcs})")
+}
+
+#[test]
+fn cmp_extremes() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for (n, (op, ty, rhs, expected)) in CMPS.iter().enumerate() {
+            let func = instance.get_func(&store, &n.to_string()).unwrap();
+            let inputs: Vec<Value> = match *ty {
+                "i32" => [0, 1, -1, i32::MIN, i32::MAX]
+                    .into_iter()
+                    .map(Value::I32)
+                    .collect(),
+                "i64" => [0, 1, -1, i64::MIN, i64::MAX]
+                    .into_iter()
+                    .map(Value::I64)
+                    .collect(),
+                _ => panic!("unexpected operand type: {ty}"),
+            };
+            for input in inputs {
+                let mut result = [Value::I32(0)];
+                func.call(&mut store, core::slice::from_ref(&input), &mut result)
+                    .unwrap();
+                assert_eq!(
+                    result[0].i32(),
+                    Some(i32::from(*expected)),
+                    "{op} {input:?} {rhs}",
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,71 @@
+//! Tests that all [`ConstDedup`] data structures yield identical compilation results.
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{ConstDedup, EngineBackend, Instance, Store};
+
+/// The Wasm module under test.
+///
+/// The function uses many duplicate constant values that cannot be encoded inline.
+const WAT: &str = r#"
+    (module
+        (func (export "f") (param $x i64) (result i64)
+            (i64.add
+                (i64.mul (local.get $x) (i64.const 0x1234_5678_9ABC))
+                (i64.sub
+                    (i64.xor (local.get $x) (i64.const -0x1234_5678_9ABC))
+                    (i64.or
+                        (i64.and (local.get $x) (i64.const 0x1234_5678_9ABC))
+                        (i64.rotl (local.get $x) (i64.const 0x7FFF_FFFF_FFFF))
+                    )
+                )
+            )
+            (i64.mul (i64.const 0x7FFF_FFFF_FFFF))
+            (i64.add (i64.const -0x1234_5678_9ABC))
+        )
+    )
+"#;
+
+/// Instantiates the module under test deduplicating its constant values via `dedup`.
+fn setup(backend: EngineBackend, dedup: ConstDedup) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.set_const_dedup(dedup);
+    instantiate(&config, WAT)
+}
+
+/// Returns the results of calling the `f` export with some inputs.
+fn call_all(store: &mut Store<()>, instance: &Instance) -> Vec<i64> {
+    let func = instance.get_typed_func::<i64, i64>(&*store, "f").unwrap();
+    [0, 1, -1, i64::MIN, 42]
+        .into_iter()
+        .map(|input| func.call(&mut *store, input).unwrap())
+        .collect()
+}
+
+/// Returns the disassembled instructions of the `f` export.
+fn disasm(store: &Store<()>, instance: &Instance) -> Vec<String> {
+    let func = instance.get_func(store, "f").unwrap();
+    store
+        .engine()
+        .func_instrs(store, &func)
+        .unwrap()
+        .map(|instr| format!("{instr:?}"))
+        .collect()
+}
+
+#[test]
+fn hash_dedup_matches_btree_dedup() {
+    for backend in BACKENDS {
+        let (mut store, instance) = setup(backend, ConstDedup::BTree);
+        let (mut hash_store, hash_instance) = setup(backend, ConstDedup::Hash);
+        assert_eq!(
+            call_all(&mut store, &instance),
+            call_all(&mut hash_store, &hash_instance),
+        );
+    }
+    let (store, instance) = setup(EngineBackend::RegisterMachine, ConstDedup::BTree);
+    let (hash_store, hash_instance) = setup(EngineBackend::RegisterMachine, ConstDedup::Hash);
+    assert_eq!(
+        disasm(&store, &instance),
+        disasm(&hash_store, &hash_instance)
+    );
+}
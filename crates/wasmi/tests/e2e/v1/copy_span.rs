@@ -0,0 +1,68 @@
+//! Tests for copying overlapping register spans upon branching with multiple values.
+//!
+//! # Note
+//!
+//! The register-machine translator encodes the branches of this test module using
+//! `copy_span` instructions whose source and destination register spans overlap.
+
+use super::for_each_backend;
+
+/// The Wasm module under test.
+///
+/// - `to_lower` copies the values to registers below their own registers.
+/// - `to_higher` copies the values to registers above their own registers.
+const WAT: &str = r#"
+    (module
+        (func (export "to_lower") (param i32 i32 i32 i32) (result i32 i32 i32)
+            (block (result i32 i32 i32)
+                (i32.clz (local.get 0))
+                (i32.add (local.get 0) (i32.const 1))
+                (i32.add (local.get 1) (i32.const 2))
+                (i32.add (local.get 2) (i32.const 3))
+                (br_if 0 (local.get 3))
+                (drop) (drop) (drop) (drop)
+                (i32.const 10) (i32.const 20) (i32.const 30)
+            )
+            (return)
+        )
+        (func (export "to_higher") (param i32 i32 i32 i32 i32 i32) (result i32 i32 i32)
+            (block (result i32 i32 i32)
+                (local.get 4)
+                (local.get 5)
+                (i32.add (local.get 0) (local.get 1))
+                (br_if 0 (local.get 3))
+                (drop) (drop) (drop)
+                (i32.const 10) (i32.const 20) (i32.const 30)
+            )
+            (return)
+        )
+    )
+"#;
+
+#[test]
+fn copy_span() {
+    for_each_backend(WAT, |mut store, instance| {
+        let to_lower = instance
+            .get_typed_func::<(i32, i32, i32, i32), (i32, i32, i32)>(&store, "to_lower")
+            .unwrap();
+        assert_eq!(
+            to_lower.call(&mut store, (100, 200, 300, 1)).unwrap(),
+            (101, 202, 303)
+        );
+        assert_eq!(
+            to_lower.call(&mut store, (100, 200, 300, 0)).unwrap(),
+            (10, 20, 30)
+        );
+        let to_higher = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32, i32), (i32, i32, i32)>(&store, "to_higher")
+            .unwrap();
+        assert_eq!(
+            to_higher.call(&mut store, (1, 2, 0, 1, 40, 50)).unwrap(),
+            (40, 50, 3)
+        );
+        assert_eq!(
+            to_higher.call(&mut store, (1, 2, 0, 0, 40, 50)).unwrap(),
+            (10, 20, 30)
+        );
+    });
+}
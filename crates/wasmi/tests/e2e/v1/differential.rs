@@ -0,0 +1,97 @@
+//! Differential tests asserting that different [`Config`] execute Wasm identically.
+//!
+//! These use [`Engine::compare_execution`] of the `test-utils` crate feature.
+
+use super::backend_config;
+use wasmi::{core::TrapCode, Config, Engine, EngineBackend, ExecutionOutcome, Value};
+
+/// Returns all pairs of [`Config`] whose executions must be identical.
+fn config_pairs() -> Vec<(Config, Config)> {
+    let stack = backend_config(EngineBackend::StackMachine);
+    let register = backend_config(EngineBackend::RegisterMachine);
+    let mut unfused = backend_config(EngineBackend::RegisterMachine);
+    unfused.set_fused_copies(false);
+    vec![(stack, register), (stack, unfused), (register, unfused)]
+}
+
+/// A branchy and arithmetic heavy function that traps for some inputs.
+///
+/// Iterates a Collatz-like sequence starting at `$n` for at most `$steps` steps
+/// and returns an accumulated checksum divided by `$div` as well as the final value.
+const WAT: &str = r#"
+    (module
+        (func (export "run") (param $n i64) (param $steps i32) (param $div i32) (result i32 i64)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $steps)))
+                    (br_if $exit (i64.le_u (local.get $n) (i64.const 1)))
+                    (if (i64.eqz (i64.and (local.get $n) (i64.const 1)))
+                        (then
+                            (local.set $n (i64.shr_u (local.get $n) (i64.const 1)))
+                        )
+                        (else
+                            (local.set $n
+                                (i64.add
+                                    (i64.mul (local.get $n) (i64.const 3))
+                                    (i64.const 1)
+                                )
+                            )
+                        )
+                    )
+                    (local.set $acc
+                        (i32.xor
+                            (i32.rotl (local.get $acc) (i32.const 5))
+                            (i32.wrap_i64 (local.get $n))
+                        )
+                    )
+                    (local.set $steps (i32.sub (local.get $steps) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i32.div_s (local.get $acc) (local.get $div))
+            (local.get $n)
+        )
+    )
+"#;
+
+#[test]
+fn compare_execution_works() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let inputs = [
+        [Value::I64(27), Value::I32(1000), Value::I32(3)],
+        [Value::I64(1), Value::I32(1000), Value::I32(1)],
+        [Value::I64(-1), Value::I32(50), Value::I32(-7)],
+        [Value::I64(97), Value::I32(10), Value::I32(0)],
+        [Value::I64(0), Value::I32(0), Value::I32(0)],
+        [Value::I64(i64::MAX), Value::I32(100), Value::I32(-1)],
+    ];
+    for (lhs, rhs) in config_pairs() {
+        for inputs in &inputs {
+            Engine::compare_execution(&lhs, &rhs, &wasm, "run", inputs);
+        }
+    }
+}
+
+#[test]
+fn compare_execution_traps() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let inputs = [Value::I64(97), Value::I32(10), Value::I32(0)];
+    for (lhs, rhs) in config_pairs() {
+        assert_eq!(
+            Engine::compare_execution(&lhs, &rhs, &wasm, "run", &inputs),
+            ExecutionOutcome::Trap(Some(TrapCode::IntegerDivisionByZero)),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "outcomes differ for run")]
+fn compare_execution_detects_mismatch() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let inputs = [Value::I64(27), Value::I32(1000), Value::I32(3)];
+    // Executions run out of fuel immediately since stores start without fuel.
+    let mut metered = Config::default();
+    metered.consume_fuel(true);
+    Engine::compare_execution(&Config::default(), &metered, &wasm, "run", &inputs);
+}
@@ -0,0 +1,57 @@
+//! Tests for compiling, instantiating and inspecting an empty Wasm module.
+
+use super::{backend_config, BACKENDS};
+use wasmi::{errors::FuncError, Engine, EngineBackend, Error, Instance, Linker, Module, Store};
+
+/// The smallest valid Wasm binary consisting of just the magic number and version.
+const EMPTY_WASM: &[u8] = b"\0asm\x01\0\0\0";
+
+/// Compiles and instantiates the `wasm` module using the given engine `backend`.
+fn setup(backend: EngineBackend, wasm: &[u8]) -> (Store<()>, Module, Instance) {
+    let engine = Engine::new(&backend_config(backend));
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance)
+}
+
+fn assert_empty_module(backend: EngineBackend) {
+    let wat = wat::parse_str("(module)").unwrap();
+    for wasm in [EMPTY_WASM, &wat[..]] {
+        let (store, module, instance) = setup(backend, wasm);
+        assert_eq!(module.imports().len(), 0);
+        assert_eq!(module.exports().count(), 0);
+        assert_eq!(instance.exports(&store).count(), 0);
+        assert!(instance.get_export(&store, "f").is_none());
+        assert!(instance.get_func(&store, "f").is_none());
+        assert!(instance.get_memory(&store, "memory").is_none());
+        assert!(instance.get_table(&store, "table").is_none());
+        assert!(instance.get_global(&store, "global").is_none());
+        assert!(instance.get_global_by_index(&store, 0).is_none());
+        let error = instance.get_typed_func::<(), ()>(&store, "f").unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Func(FuncError::ExportedFuncNotFound)
+        ));
+        assert_eq!(error.to_string(), "could not find exported function");
+    }
+}
+
+#[test]
+fn empty_module() {
+    for backend in BACKENDS {
+        assert_empty_module(backend)
+    }
+}
+
+#[test]
+fn truncated_module_fails() {
+    let engine = Engine::default();
+    for len in 0..EMPTY_WASM.len() {
+        assert!(Module::new(&engine, &EMPTY_WASM[..len]).is_err());
+    }
+}
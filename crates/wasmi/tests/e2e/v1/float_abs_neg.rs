@@ -0,0 +1,143 @@
+//! Tests the sign bit semantics of the Wasm `abs` and `neg` float operators.
+//!
+//! # Note
+//!
+//! The Wasm operators only ever touch the sign bit so that they preserve NaN
+//! payloads and work on infinities and zeros without comparing values.
+//!
+//! The register-machine translator constant folds these operators.
+//! The results must be bit-identical to executing the operators.
+
+use super::for_each_backend;
+use wasmi::{
+    core::{F32, F64},
+    Value,
+};
+
+/// The `f32` operand bit patterns tested for every operator.
+const INPUTS_F32: [u32; 10] = [
+    0x7FC0_0000, // quiet NaN
+    0xFFC0_0000, // negative quiet NaN
+    0x7FC0_0001, // quiet NaN with payload
+    0x7F80_0001, // signaling NaN
+    0x7F80_0000, // +inf
+    0xFF80_0000, // -inf
+    0x0000_0000, // +0
+    0x8000_0000, // -0
+    0x3FC0_0000, // 1.5
+    0xBFC0_0000, // -1.5
+];
+
+/// The `f64` operand bit patterns tested for every operator.
+const INPUTS_F64: [u64; 10] = [
+    0x7FF8_0000_0000_0000, // quiet NaN
+    0xFFF8_0000_0000_0000, // negative quiet NaN
+    0x7FF8_0000_0000_0001, // quiet NaN with payload
+    0x7FF0_0000_0000_0001, // signaling NaN
+    0x7FF0_0000_0000_0000, // +inf
+    0xFFF0_0000_0000_0000, // -inf
+    0x0000_0000_0000_0000, // +0
+    0x8000_0000_0000_0000, // -0
+    0x3FF8_0000_0000_0000, // 1.5
+    0xBFF8_0000_0000_0000, // -1.5
+];
+
+/// The Wasm `abs` and `neg` operators.
+const OPS: [&str; 2] = ["abs", "neg"];
+
+/// Returns the expected result bits of the Wasm `op` applied to `bits` with `sign` as sign bit.
+fn expected(op: &str, bits: u64, sign: u64) -> u64 {
+    match op {
+        "abs" => bits & !sign,
+        "neg" => bits ^ sign,
+        _ => panic!("unexpected operator: {op}"),
+    }
+}
+
+/// Returns the Wasm text format of the float with the given `bits` of type `ty`.
+fn to_wat(ty: &str, bits: u64) -> String {
+    let (exponent, sign, mantissa) = match ty {
+        "f32" => (0x7F80_0000, 1 << 31, 0x007F_FFFF),
+        _ => (0x7FF0_0000_0000_0000, 1 << 63, 0x000F_FFFF_FFFF_FFFF),
+    };
+    let prefix = if bits & sign != 0 { "-" } else { "" };
+    let payload = bits & mantissa;
+    match bits & exponent == exponent {
+        true if payload != 0 => format!("{prefix}nan:0x{payload:x}"),
+        true => format!("{prefix}inf"),
+        false => match ty {
+            "f32" => format!("{:?}", f32::from_bits(bits as u32)),
+            _ => format!("{:?}", f64::from_bits(bits)),
+        },
+    }
+}
+
+/// Returns all `(ty, bits, sign)` operands of the tests.
+fn operands() -> Vec<(&'static str, u64, u64)> {
+    let f32s = INPUTS_F32.map(|bits| ("f32", u64::from(bits), 1 << 31));
+    let f64s = INPUTS_F64.map(|bits| ("f64", bits, 1 << 63));
+    f32s.into_iter().chain(f64s).collect()
+}
+
+/// Returns the Wasm module under test.
+///
+/// For every operator and operand `i` this exports a function `executed {op} {i}`
+/// applying the operator to its parameter and a function `folded {op} {i}`
+/// applying it to the constant operand.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for op in OPS {
+        for (i, (ty, bits, _)) in operands().into_iter().enumerate() {
+            let operand = to_wat(ty, bits);
+            funcs.push_str(&format!(
+                r#"
+                (func (export "executed {op} {i}") (param {ty}) (result {ty})
+                    ({ty}.{op} (local.get 0))
+                )
+                (func (export "folded {op} {i}") (result {ty})
+                    ({ty}.{op} ({ty}.const {operand}))
+                )
+                "#
+            ));
+        }
+    }
+    format!("(module {funcs})")
+}
+
+/// Converts the `bits` into a [`Value`] of the Wasm type `ty`.
+fn bits_to_value(ty: &str, bits: u64) -> Value {
+    match ty {
+        "f32" => Value::F32(F32::from_bits(bits as u32)),
+        _ => Value::F64(F64::from_bits(bits)),
+    }
+}
+
+/// Returns the bits of the float `value`.
+fn value_to_bits(value: &Value) -> u64 {
+    match value {
+        Value::F32(value) => u64::from(value.to_bits()),
+        Value::F64(value) => value.to_bits(),
+        _ => panic!("unexpected result value: {value:?}"),
+    }
+}
+
+#[test]
+fn abs_neg() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for op in OPS {
+            for (i, (ty, bits, sign)) in operands().into_iter().enumerate() {
+                let expected = expected(op, bits, sign);
+                let mut call = |name: &str, inputs: &[Value]| {
+                    let func = instance.get_func(&store, name).unwrap();
+                    let mut results = [bits_to_value(ty, 0)];
+                    func.call(&mut store, inputs, &mut results).unwrap();
+                    value_to_bits(&results[0])
+                };
+                let executed = call(&format!("executed {op} {i}"), &[bits_to_value(ty, bits)]);
+                let folded = call(&format!("folded {op} {i}"), &[]);
+                assert_eq!(executed, expected, "executed {ty}.{op} {bits:#X}");
+                assert_eq!(folded, expected, "folded {ty}.{op} {bits:#X}");
+            }
+        }
+    });
+}
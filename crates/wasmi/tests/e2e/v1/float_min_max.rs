@@ -0,0 +1,274 @@
+//! Tests the NaN and zero-sign semantics of the Wasm `min` and `max` float operators.
+//!
+//! # Note
+//!
+//! Contrary to Rust's `f32::min` and `f32::max` the Wasm operators propagate
+//! NaN inputs and order `-0.0` below `+0.0`.
+//!
+//! The register-machine translator constant folds these operators and propagates
+//! constant NaN operands without executing them. The results must be bit-identical
+//! to executing the operators on the same inputs.
+
+use super::for_each_backend;
+use wasmi::{
+    core::{F32, F64},
+    Store,
+    Value,
+};
+
+/// All Wasm `min` and `max` operators together with their operand type.
+const OPS: &[(&str, &str)] = &[
+    ("f32.min", "f32"),
+    ("f32.max", "f32"),
+    ("f64.min", "f64"),
+    ("f64.max", "f64"),
+];
+
+/// The operand values tested for every pair of inputs.
+const INPUTS: [f64; 4] = [f64::NAN, 0.0, -0.0, 1.0];
+
+/// Returns the Wasm module under test.
+///
+/// For every operator this exports a function that applies it to its two parameters.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for (op, ty) in OPS {
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{op}") (param {ty} {ty}) (result {ty})
+                ({op} (local.get 0) (local.get 1))
+            )
+            "#
+        ));
+    }
+    format!("(module {funcs})")
+}
+
+/// Returns the result of the Wasm `min` or `max` operator for `lhs` and `rhs`.
+fn expected(op: &str, lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_nan() || rhs.is_nan() {
+        return f64::NAN;
+    }
+    let is_min = op.ends_with("min");
+    if lhs == 0.0 && rhs == 0.0 {
+        let any_negative = lhs.is_sign_negative() || rhs.is_sign_negative();
+        let any_positive = lhs.is_sign_positive() || rhs.is_sign_positive();
+        return match (is_min, any_negative, any_positive) {
+            (true, true, _) | (false, _, false) => -0.0,
+            _ => 0.0,
+        };
+    }
+    match is_min {
+        true => lhs.min(rhs),
+        false => lhs.max(rhs),
+    }
+}
+
+/// Converts `value` into a [`Value`] of the Wasm type `ty`.
+fn to_value(ty: &str, value: f64) -> Value {
+    match ty {
+        "f32" => Value::F32(F32::from_float(value as f32)),
+        "f64" => Value::F64(F64::from_float(value)),
+        _ => panic!("unexpected operand type: {ty}"),
+    }
+}
+
+/// Converts the float `value` into an `f64` preserving NaN and zero signs.
+fn from_value(value: &Value) -> f64 {
+    match value {
+        Value::F32(value) => f64::from(value.to_float()),
+        Value::F64(value) => value.to_float(),
+        _ => panic!("unexpected result value: {value:?}"),
+    }
+}
+
+#[test]
+fn float_min_max() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for (op, ty) in OPS {
+            let func = instance.get_func(&store, op).unwrap();
+            for lhs in INPUTS {
+                for rhs in INPUTS {
+                    let mut result = [to_value(ty, 0.0)];
+                    func.call(
+                        &mut store,
+                        &[to_value(ty, lhs), to_value(ty, rhs)],
+                        &mut result,
+                    )
+                    .unwrap();
+                    let actual = from_value(&result[0]);
+                    let expected = expected(op, lhs, rhs);
+                    match expected.is_nan() {
+                        true => assert!(actual.is_nan(), "{op} {lhs} {rhs} = {actual}"),
+                        false => assert_eq!(
+                            actual.to_bits(),
+                            expected.to_bits(),
+                            "{op} {lhs} {rhs} = {actual}, expected {expected}",
+                        ),
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Returns the NaN operands of type `ty` as `(wat, bits)` pairs.
+///
+/// The NaN operands differ in sign, quietness and payload.
+fn nan_operands(ty: &str) -> Vec<(String, u64)> {
+    let (exponent, quiet, sign) = match ty {
+        "f32" => (0x7F80_0000, 1 << 22, 1 << 31),
+        "f64" => (0x7FF0_0000_0000_0000, 1 << 51, 1 << 63),
+        _ => panic!("unexpected operand type: {ty}"),
+    };
+    [(false, quiet), (true, quiet), (false, quiet | 1), (true, 1)]
+        .into_iter()
+        .map(|(is_negative, payload)| {
+            let (prefix, sign) = if is_negative { ("-", sign) } else { ("", 0) };
+            let wat = format!("{prefix}nan:0x{payload:x}");
+            (wat, sign | exponent | payload)
+        })
+        .collect()
+}
+
+/// Returns the non-NaN operands of type `ty` as `(wat, bits)` pairs.
+fn number_operands(ty: &str) -> Vec<(String, u64)> {
+    [0.0, -0.0, 1.5, f64::INFINITY, f64::NEG_INFINITY]
+        .into_iter()
+        .map(|value: f64| {
+            let wat = match value.is_infinite() {
+                true if value.is_sign_negative() => String::from("-inf"),
+                true => String::from("inf"),
+                false => format!("{value:?}"),
+            };
+            let bits = match ty {
+                "f32" => u64::from((value as f32).to_bits()),
+                _ => value.to_bits(),
+            };
+            (wat, bits)
+        })
+        .collect()
+}
+
+/// A `min` or `max` operator applied to a NaN and a non-NaN operand.
+struct NanCase {
+    /// The Wasm operator, e.g. `f32.min`.
+    op: &'static str,
+    /// The Wasm operand type, e.g. `f32`.
+    ty: &'static str,
+    /// The left-hand side operand as `(wat, bits)` pair.
+    lhs: (String, u64),
+    /// The right-hand side operand as `(wat, bits)` pair.
+    rhs: (String, u64),
+}
+
+/// Returns all [`NanCase`] with one NaN operand in either position.
+fn nan_cases() -> Vec<NanCase> {
+    let mut cases = Vec::new();
+    for (op, ty) in OPS {
+        for nan in nan_operands(ty) {
+            for number in number_operands(ty) {
+                cases.push(NanCase {
+                    op,
+                    ty,
+                    lhs: nan.clone(),
+                    rhs: number.clone(),
+                });
+                cases.push(NanCase {
+                    op,
+                    ty,
+                    lhs: number,
+                    rhs: nan.clone(),
+                });
+            }
+        }
+    }
+    cases
+}
+
+/// Returns the Wasm module under test for the `cases`.
+///
+/// For every case `i` this exports a function `executed {i}` applying the operator
+/// to its two parameters, a function `folded {i}` applying it to both constant operands
+/// and a function `nan {i}` applying it to a parameter and the constant NaN operand
+/// in their original order.
+fn nan_cases_wat(cases: &[NanCase]) -> String {
+    let mut funcs = String::new();
+    for (i, NanCase { op, ty, lhs, rhs }) in cases.iter().enumerate() {
+        let (lhs, rhs) = (&lhs.0, &rhs.0);
+        let (param_lhs, param_rhs) = match lhs.contains("nan") {
+            true => (format!("({ty}.const {lhs})"), String::from("(local.get 0)")),
+            false => (String::from("(local.get 0)"), format!("({ty}.const {rhs})")),
+        };
+        funcs.push_str(&format!(
+            r#"
+            (func (export "executed {i}") (param {ty} {ty}) (result {ty})
+                ({op} (local.get 0) (local.get 1))
+            )
+            (func (export "folded {i}") (result {ty})
+                ({op} ({ty}.const {lhs}) ({ty}.const {rhs}))
+            )
+            (func (export "nan {i}") (param {ty}) (result {ty})
+                ({op} {param_lhs} {param_rhs})
+            )
+            "#
+        ));
+    }
+    format!("(module {funcs})")
+}
+
+/// Converts the `bits` into a [`Value`] of the Wasm type `ty`.
+fn bits_to_value(ty: &str, bits: u64) -> Value {
+    match ty {
+        "f32" => Value::F32(F32::from_bits(bits as u32)),
+        "f64" => Value::F64(F64::from_bits(bits)),
+        _ => panic!("unexpected operand type: {ty}"),
+    }
+}
+
+/// Returns the bits of the float `value`.
+fn value_to_bits(value: &Value) -> u64 {
+    match value {
+        Value::F32(value) => u64::from(value.to_bits()),
+        Value::F64(value) => value.to_bits(),
+        _ => panic!("unexpected result value: {value:?}"),
+    }
+}
+
+#[test]
+fn nan_folding() {
+    let cases = nan_cases();
+    for_each_backend(&nan_cases_wat(&cases), |mut store, instance| {
+        for (i, NanCase { op, ty, lhs, rhs }) in cases.iter().enumerate() {
+            let zero = bits_to_value(ty, 0);
+            let call = |store: &mut Store<()>, name: &str, inputs: &[Value]| {
+                let func = instance.get_func(&*store, name).unwrap();
+                let mut results = [zero.clone()];
+                func.call(store, inputs, &mut results).unwrap();
+                value_to_bits(&results[0])
+            };
+            let inputs = [bits_to_value(ty, lhs.1), bits_to_value(ty, rhs.1)];
+            let executed = call(&mut store, &format!("executed {i}"), &inputs);
+            let folded = call(&mut store, &format!("folded {i}"), &[]);
+            let number = match lhs.0.contains("nan") {
+                true => inputs[1].clone(),
+                false => inputs[0].clone(),
+            };
+            let propagated = call(&mut store, &format!("nan {i}"), &[number]);
+            let is_nan = match *ty {
+                "f32" => f32::from_bits(executed as u32).is_nan(),
+                _ => f64::from_bits(executed).is_nan(),
+            };
+            let case = format!("{op} {} {}", lhs.0, rhs.0);
+            assert!(is_nan, "{case}: executed result is not NaN: {executed:#x}");
+            assert_eq!(
+                folded, executed,
+                "{case}: folded {folded:#x} != executed {executed:#x}"
+            );
+            assert_eq!(
+                propagated, executed,
+                "{case}: propagated {propagated:#x} != executed {executed:#x}"
+            );
+        }
+    });
+}
@@ -0,0 +1,114 @@
+//! Tests for disabling floating point support via [`Config::floats`].
+
+use wasmi::{Config, Engine, Error, Module};
+
+/// Compiles the `wat` source using an [`Engine`] with floating point support set to `floats`.
+fn compile(floats: bool, wat: &str) -> Result<Module, Error> {
+    let mut config = Config::default();
+    config.floats(floats);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+/// Asserts that `wat` compiles with floats enabled and is rejected with floats disabled.
+fn assert_rejects_floats(wat: &str, message: &str) {
+    compile(true, wat).unwrap();
+    let error = compile(false, wat).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert!(
+        error.to_string().contains(message),
+        "unexpected error message: {error}"
+    );
+}
+
+#[test]
+fn rejects_float_instr() {
+    assert_rejects_floats(
+        r#"
+        (module
+            (func (param i32 i32) (result i32)
+                (i32.reinterpret_f32
+                    (f32.add
+                        (f32.convert_i32_s (local.get 0))
+                        (f32.convert_i32_s (local.get 1))
+                    )
+                )
+            )
+        )
+        "#,
+        "floating-point instruction disallowed",
+    )
+}
+
+#[test]
+fn rejects_float_load() {
+    assert_rejects_floats(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32) (result i32)
+                (i32.trunc_f32_s (f32.load (local.get 0)))
+            )
+        )
+        "#,
+        "floating-point instruction disallowed",
+    )
+}
+
+#[test]
+fn rejects_float_store() {
+    assert_rejects_floats(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i64)
+                (f64.store (local.get 0) (f64.reinterpret_i64 (local.get 1)))
+            )
+        )
+        "#,
+        "floating-point instruction disallowed",
+    )
+}
+
+#[test]
+fn rejects_float_param() {
+    assert_rejects_floats(
+        r#"
+        (module
+            (func (param f32))
+        )
+        "#,
+        "floating-point support is disabled",
+    )
+}
+
+#[test]
+fn rejects_float_global() {
+    assert_rejects_floats(
+        r#"
+        (module
+            (global f64 (f64.const 1.0))
+        )
+        "#,
+        "floating-point support is disabled",
+    )
+}
+
+#[test]
+fn accepts_int_module() {
+    compile(
+        false,
+        r#"
+        (module
+            (memory 1)
+            (global (mut i64) (i64.const 0))
+            (func (param i32 i32) (result i32)
+                (i32.store (local.get 0) (local.get 1))
+                (i32.add (local.get 0) (i32.load (local.get 1)))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+}
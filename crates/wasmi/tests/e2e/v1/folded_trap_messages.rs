@@ -0,0 +1,90 @@
+//! Tests for descriptive messages of traps folded at compilation time via [`Config::set_folded_trap_messages`].
+
+use super::{backend_config, instantiate};
+use wasmi::{core::TrapCode, EngineBackend, Instance, Store};
+
+/// The Wasm module under test.
+///
+/// - `div_const` divides two constants and always traps.
+/// - `div_param` divides its parameter by the constant `0` and always traps.
+/// - `trunc_const` truncates a `NaN` constant and always traps.
+const WAT: &str = r#"
+    (module
+        (func (export "div_const") (result i32)
+            (i32.div_s (i32.const 5) (i32.const 0))
+        )
+        (func (export "div_param") (param i64) (result i64)
+            (i64.rem_u (local.get 0) (i64.const 0))
+        )
+        (func (export "trunc_const") (result i32)
+            (i32.trunc_f32_s (f32.const nan))
+        )
+    )
+"#;
+
+/// Instantiates the module under test whose folded traps carry descriptive messages if `messages` is `true`.
+fn setup(backend: EngineBackend, messages: bool) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.set_folded_trap_messages(messages);
+    instantiate(&config, WAT)
+}
+
+#[test]
+fn folded_trap_messages_register_machine() {
+    let (mut store, instance) = setup(EngineBackend::RegisterMachine, true);
+    let trap = instance
+        .get_typed_func::<(), i32>(&store, "div_const")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(trap.message(), Some("i32.div_s with operands 5 and 0"));
+    assert_eq!(
+        trap.to_string(),
+        "integer divide by zero (i32.div_s with operands 5 and 0)"
+    );
+    let trap = instance
+        .get_typed_func::<i64, i64>(&store, "div_param")
+        .unwrap()
+        .call(&mut store, 42)
+        .unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(trap.message(), Some("i64.rem_u with divisor 0"));
+    let trap = instance
+        .get_typed_func::<(), i32>(&store, "trunc_const")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::BadConversionToInteger));
+    assert_eq!(trap.message(), Some("i32.trunc_f32_s with operand NaN"));
+}
+
+fn assert_no_messages(backend: EngineBackend, messages: bool) {
+    let (mut store, instance) = setup(backend, messages);
+    let trap = instance
+        .get_typed_func::<(), i32>(&store, "div_const")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(trap.message(), None);
+    assert_eq!(trap.to_string(), "integer divide by zero");
+    let trap = instance
+        .get_typed_func::<i64, i64>(&store, "div_param")
+        .unwrap()
+        .call(&mut store, 42)
+        .unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(trap.message(), None);
+}
+
+#[test]
+fn no_folded_trap_messages_register_machine() {
+    assert_no_messages(EngineBackend::RegisterMachine, false)
+}
+
+#[test]
+fn no_folded_trap_messages_stack_machine() {
+    // The stack-machine backend does not fold traps at compilation time.
+    assert_no_messages(EngineBackend::StackMachine, true)
+}
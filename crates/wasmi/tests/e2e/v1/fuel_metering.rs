@@ -1,7 +1,8 @@
 //! Tests to check if wasmi's fuel metering works as intended.
 
+use super::{backend_config, instantiate, BACKENDS};
 use std::fmt::Debug;
-use wasmi::{Config, Engine, Func, Linker, Module, Store};
+use wasmi::{Config, Engine, EngineBackend, Func, Instance, Linker, Module, Store};
 use wasmi_core::{Trap, TrapCode};
 
 /// Setup [`Engine`] and [`Store`] for fuel metering.
@@ -93,3 +94,104 @@ fn metered_i32_add() {
     assert_success(func.call(&mut store, (1, 2)));
     assert_eq!(store.fuel_consumed(), Some(5));
 }
+
+/// Setup [`Store`] and the exported `test` [`Func`] of `wat` for fuel metering using `backend`.
+fn backend_test_setup(backend: EngineBackend, wat: &str) -> (Store<()>, Instance, Func) {
+    let mut config = backend_config(backend);
+    config.consume_fuel(true);
+    let (store, instance) = instantiate(&config, wat);
+    let func = instance.get_func(&store, "test").unwrap();
+    (store, instance, func)
+}
+
+/// A Wasm module with an infinite loop counting its iterations in the `iters` global.
+const INFINITE_LOOP: &str = r#"
+    (module
+        (global $iters (export "iters") (mut i32) (i32.const 0))
+        (func (export "test")
+            (loop $continue
+                (global.set $iters
+                    (i32.add (global.get $iters) (i32.const 1))
+                )
+                (br $continue)
+            )
+        )
+    )
+"#;
+
+/// Runs [`INFINITE_LOOP`] with `fuel` and returns the consumed fuel and loop iterations.
+fn run_infinite_loop(backend: EngineBackend, fuel: u64) -> (u64, i32) {
+    let (mut store, instance, func) = backend_test_setup(backend, INFINITE_LOOP);
+    let func = func.typed::<(), ()>(&store).unwrap();
+    store.add_fuel(fuel).unwrap();
+    assert_out_of_fuel(func.call(&mut store, ()));
+    let consumed = store.fuel_consumed().unwrap();
+    let iters = instance
+        .get_global(&store, "iters")
+        .unwrap()
+        .get(&store)
+        .i32()
+        .unwrap();
+    (consumed, iters)
+}
+
+fn assert_infinite_loop_traps(backend: EngineBackend) {
+    let (consumed, iters) = run_infinite_loop(backend, 1_000);
+    assert!(consumed <= 1_000);
+    assert!(iters > 0);
+    // Running out of fuel is deterministic.
+    for _ in 0..3 {
+        assert_eq!(run_infinite_loop(backend, 1_000), (consumed, iters));
+    }
+    // More fuel allows for more loop iterations.
+    let (_, more_iters) = run_infinite_loop(backend, 2_000);
+    assert!(more_iters > iters);
+}
+
+#[test]
+fn infinite_loop_traps() {
+    for backend in BACKENDS {
+        assert_infinite_loop_traps(backend)
+    }
+}
+
+/// Asserts that a counted loop runs with exactly the fuel it requires but not with less.
+fn assert_counted_loop_fuel_limit(backend: EngineBackend) {
+    let wat = r#"
+        (module
+            (func (export "test") (param $n i32)
+                (loop $continue
+                    (br_if $continue
+                        (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+                    )
+                )
+            )
+        )
+    "#;
+    // Measure the fuel required to run 100 loop iterations.
+    let (mut store, _instance, func) = backend_test_setup(backend, wat);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.add_fuel(u64::MAX).unwrap();
+    assert_success(func.call(&mut store, 100));
+    let required = store.fuel_consumed().unwrap();
+    // Exactly the required fuel is sufficient.
+    let (mut store, _instance, func) = backend_test_setup(backend, wat);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.add_fuel(required).unwrap();
+    assert_success(func.call(&mut store, 100));
+    assert_eq!(store.fuel_consumed(), Some(required));
+    // One unit of fuel less always traps.
+    for _ in 0..3 {
+        let (mut store, _instance, func) = backend_test_setup(backend, wat);
+        let func = func.typed::<i32, ()>(&store).unwrap();
+        store.add_fuel(required - 1).unwrap();
+        assert_out_of_fuel(func.call(&mut store, 100));
+    }
+}
+
+#[test]
+fn counted_loop_fuel_limit() {
+    for backend in BACKENDS {
+        assert_counted_loop_fuel_limit(backend)
+    }
+}
@@ -3,7 +3,19 @@
 use core::slice;
 
 use assert_matches::assert_matches;
-use wasmi::{errors::FuncError, Engine, Error, Func, FuncType, Store, Value};
+use wasmi::{
+    errors::FuncError,
+    Config,
+    Engine,
+    EngineBackend,
+    Error,
+    Func,
+    FuncType,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
 use wasmi_core::{ValueType, F32, F64};
 
 fn test_setup() -> Store<()> {
@@ -43,6 +55,101 @@ fn setup_add2() -> (Store<()>, Func, Func) {
     (store, add2, add2_dyn)
 }
 
+/// Calls an exported Wasm function without parameters many times for both engine backends.
+fn assert_niladic_wasm_func_works(backend: EngineBackend) {
+    let wat = r#"
+        (module
+            (global $counter (mut i32) (i32.const 0))
+            (func (export "bump") (result i32)
+                (global.set $counter
+                    (i32.add (global.get $counter) (i32.const 1))
+                )
+                (global.get $counter)
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.set_engine_backend(backend);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let bump = instance.get_typed_func::<(), i32>(&store, "bump").unwrap();
+    for expected in 1..=100 {
+        assert_eq!(bump.call(&mut store, ()).unwrap(), expected);
+    }
+    let bump = instance.get_func(&store, "bump").unwrap();
+    let mut result = Value::I32(0);
+    bump.call(&mut store, &[], slice::from_mut(&mut result))
+        .unwrap();
+    assert_eq!(result.i32(), Some(101));
+}
+
+#[test]
+fn niladic_wasm_func_works() {
+    assert_niladic_wasm_func_works(EngineBackend::StackMachine);
+    assert_niladic_wasm_func_works(EngineBackend::RegisterMachine);
+}
+
+/// Calls an exported multi-value Wasm function many times reusing the same results buffer.
+fn assert_call_into_vec_works(backend: EngineBackend) {
+    let wat = r#"
+        (module
+            (func (export "divrem") (param i32 i32) (result i32 i32)
+                (i32.div_u (local.get 0) (local.get 1))
+                (i32.rem_u (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.set_engine_backend(backend);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let divrem = instance.get_func(&store, "divrem").unwrap();
+    // The stale value is expected to be cleared by the call.
+    let mut results = vec![Value::I64(-1)];
+    divrem
+        .call_into_vec(&mut store, &[Value::I32(7), Value::I32(2)], &mut results)
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].i32(), Some(3));
+    assert_eq!(results[1].i32(), Some(1));
+    let buffer = (results.as_ptr(), results.capacity());
+    for lhs in 0..100 {
+        let params = [Value::I32(lhs), Value::I32(7)];
+        divrem
+            .call_into_vec(&mut store, &params, &mut results)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].i32(), Some(lhs / 7));
+        assert_eq!(results[1].i32(), Some(lhs % 7));
+        // The results buffer has not been reallocated.
+        assert_eq!((results.as_ptr(), results.capacity()), buffer);
+    }
+    assert_matches!(
+        divrem.call_into_vec(&mut store, &[Value::I32(1)], &mut results),
+        Err(Error::Func(FuncError::MismatchingParameterLen))
+    );
+}
+
+#[test]
+fn call_into_vec_works() {
+    assert_call_into_vec_works(EngineBackend::StackMachine);
+    assert_call_into_vec_works(EngineBackend::RegisterMachine);
+}
+
 #[test]
 fn dynamic_add2_works() {
     let (mut store, add2, add2_dyn) = setup_add2();
@@ -503,3 +610,32 @@ fn static_type_check_works() {
         Err(Error::Func(FuncError::MismatchingResultType))
     );
 }
+
+#[test]
+fn func_kind_works() {
+    let wat = r#"
+        (module
+            (import "env" "host" (func $host (result i32)))
+            (func (export "wasm") (result i32)
+                (call $host)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let host = Func::wrap(&mut store, || 42_i32);
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wasm = instance.get_func(&store, "wasm").unwrap();
+    assert!(host.is_host(&store));
+    assert!(!host.is_wasm(&store));
+    assert!(wasm.is_wasm(&store));
+    assert!(!wasm.is_host(&store));
+}
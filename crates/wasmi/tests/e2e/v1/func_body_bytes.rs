@@ -0,0 +1,125 @@
+//! Tests for exporting and reloading single compiled functions via [`Engine::function_body_bytes`].
+
+use super::{backend_config, instantiate};
+use wasmi::{errors::FuncBodyError, Config, EngineBackend, Error, Func, Store};
+
+/// The Wasm module under test.
+///
+/// The `f` export uses function local constants, control flow, linear memory
+/// and global variables. The `g` export calls another Wasm function.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (global $counter (mut i32) (i32.const 0))
+        (func $f (export "f") (param $n i32) (result i64)
+            (local $acc i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc
+                        (i64.add
+                            (i64.mul (local.get $acc) (i64.const 0x1_0000_0001))
+                            (i64.extend_i32_u (local.get $n))
+                        )
+                    )
+                    (i32.store (i32.const 8) (i32.add (i32.load (i32.const 8)) (local.get $n)))
+                    (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i64.add
+                (local.get $acc)
+                (i64.extend_i32_u
+                    (i32.add (i32.load (i32.const 8)) (global.get $counter))
+                )
+            )
+        )
+        (func (export "g") (param i32) (result i64)
+            (call $f (local.get 0))
+        )
+    )
+"#;
+
+/// Exports the function body of the export `name` of a freshly instantiated module.
+fn export_bytes(config: &Config, name: &str) -> Result<Vec<u8>, Error> {
+    let (store, instance) = instantiate(config, WAT);
+    let func = instance.get_func(&store, name).unwrap();
+    store.engine().function_body_bytes(&store, &func)
+}
+
+/// Loads the function body `bytes` for the export `name` of a freshly instantiated module.
+fn load(config: &Config, name: &str, bytes: &[u8]) -> Result<(Store<()>, Func), Error> {
+    let (mut store, instance) = instantiate(config, WAT);
+    let func = instance.get_func(&store, name).unwrap();
+    let engine = store.engine().clone();
+    // SAFETY: `bytes` have been exported for the same Wasm module.
+    let reloaded = unsafe { engine.load_function_body(&mut store, &func, bytes)? };
+    Ok((store, reloaded))
+}
+
+#[test]
+fn round_trip() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let bytes = export_bytes(&config, "f").unwrap();
+    let (mut store, reloaded) = load(&config, "f", &bytes).unwrap();
+    let reloaded = reloaded.typed::<i32, i64>(&store).unwrap();
+    let (mut expected_store, instance) = instantiate(&config, WAT);
+    let original = instance
+        .get_typed_func::<i32, i64>(&expected_store, "f")
+        .unwrap();
+    for input in [0, 1, 2, 10, 100, 1000] {
+        let expected = original.call(&mut expected_store, input).unwrap();
+        let actual = reloaded.call(&mut store, input).unwrap();
+        assert_eq!(actual, expected, "f({input})");
+    }
+}
+
+#[test]
+fn round_trip_is_deterministic() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let bytes = export_bytes(&config, "f").unwrap();
+    let (store, reloaded) = load(&config, "f", &bytes).unwrap();
+    let reexported = store.engine().function_body_bytes(&store, &reloaded);
+    assert_eq!(reexported.unwrap(), bytes);
+}
+
+#[test]
+fn config_mismatch() {
+    let bytes = export_bytes(&backend_config(EngineBackend::RegisterMachine), "f").unwrap();
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.consume_fuel(true);
+    assert!(matches!(
+        load(&config, "f", &bytes),
+        Err(Error::FuncBody(FuncBodyError::ConfigMismatch))
+    ));
+}
+
+#[test]
+fn malformed_bytes() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let bytes = export_bytes(&config, "f").unwrap();
+    for len in [0, 4, bytes.len() - 1] {
+        assert!(matches!(
+            load(&config, "f", &bytes[..len]),
+            Err(Error::FuncBody(FuncBodyError::InvalidFormat))
+        ));
+    }
+}
+
+#[test]
+fn internal_calls_are_not_self_contained() {
+    assert!(matches!(
+        export_bytes(&backend_config(EngineBackend::RegisterMachine), "g"),
+        Err(Error::FuncBody(FuncBodyError::NotSelfContained))
+    ));
+}
+
+#[test]
+fn stack_machine_is_unsupported() {
+    let config = backend_config(EngineBackend::StackMachine);
+    assert!(matches!(
+        export_bytes(&config, "f"),
+        Err(Error::FuncBody(FuncBodyError::UnsupportedBackend))
+    ));
+}
@@ -0,0 +1,54 @@
+//! Tests for inspecting compiled functions via [`Engine::func_instrs`].
+
+use super::instantiate_backend;
+use wasmi::{errors::FuncBodyError, EngineBackend, Error, Func};
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module
+        (func (export "f") (param $x i32) (param $y i32) (result i32)
+            (i32.add (local.get $x) (i32.mul (local.get $y) (i32.const 3)))
+        )
+    )
+"#;
+
+#[test]
+fn iterates_instrs() {
+    let (mut store, instance) = instantiate_backend(EngineBackend::RegisterMachine, WAT);
+    let func = instance.get_func(&store, "f").unwrap();
+    let engine = store.engine().clone();
+    let instrs = engine.func_instrs(&store, &func).unwrap();
+    assert_eq!(instrs.len(), 3);
+    let expected = ["I32MulImm16", "I32Add", "ReturnReg"];
+    for (instr, expected) in instrs.zip(expected) {
+        // The iterator does not lock the engine so calling into it is fine.
+        let result = func
+            .typed::<(i32, i32), i32>(&store)
+            .unwrap()
+            .call(&mut store, (1, 2))
+            .unwrap();
+        assert_eq!(result, 7);
+        let disasm = format!("{instr:?}");
+        assert!(disasm.starts_with(expected), "{disasm}");
+    }
+}
+
+#[test]
+fn host_func_is_unsupported() {
+    let (mut store, _instance) = instantiate_backend(EngineBackend::RegisterMachine, WAT);
+    let func = Func::wrap(&mut store, || {});
+    assert!(matches!(
+        store.engine().func_instrs(&store, &func),
+        Err(Error::FuncBody(FuncBodyError::HostFunc))
+    ));
+}
+
+#[test]
+fn stack_machine_is_unsupported() {
+    let (store, instance) = instantiate_backend(EngineBackend::StackMachine, WAT);
+    let func = instance.get_func(&store, "f").unwrap();
+    assert!(matches!(
+        store.engine().func_instrs(&store, &func),
+        Err(Error::FuncBody(FuncBodyError::UnsupportedBackend))
+    ));
+}
@@ -0,0 +1,122 @@
+//! Tests for `i32.add` whose operand is directly produced by an `i32.load`.
+//!
+//! The register machine fuses both operators into a single instruction
+//! which must behave exactly like the unfused sequence, including traps.
+
+use super::{backend_config, for_each_backend, instantiate};
+use wasmi::{EngineBackend, Instance, Store};
+use wasmi_core::{Trap, TrapCode};
+
+/// The module under test.
+///
+/// Memory starts with the little endian `i32` values `10`, `20` and `-1`.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (data (i32.const 0) "\0a\00\00\00\14\00\00\00\ff\ff\ff\ff")
+        (func (export "load_lhs") (param $ptr i32) (param $x i32) (result i32)
+            (i32.add (i32.load offset=4 (local.get $ptr)) (local.get $x))
+        )
+        (func (export "load_rhs") (param $ptr i32) (param $x i32) (result i32)
+            (i32.add (local.get $x) (i32.load (local.get $ptr)))
+        )
+        (func (export "load_far") (param $ptr i32) (param $x i32) (result i32)
+            (i32.add (i32.load offset=65532 (local.get $ptr)) (local.get $x))
+        )
+        (func (export "load_teed") (param $ptr i32) (param $x i32) (result i32)
+            (local $tmp i32)
+            (i32.add (local.tee $tmp (i32.load offset=4 (local.get $ptr))) (local.get $x))
+        )
+    )
+"#;
+
+/// Instantiates the module under test with fuel metering enabled.
+fn setup_metered(backend: EngineBackend) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.consume_fuel(true);
+    instantiate(&config, WAT)
+}
+
+/// Calls the exported function `name` with `ptr` and `x`.
+fn call(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    ptr: i32,
+    x: i32,
+) -> Result<i32, Trap> {
+    instance
+        .get_typed_func::<(i32, i32), i32>(&*store, name)
+        .unwrap()
+        .call(store, (ptr, x))
+}
+
+/// Asserts that `result` is a trap due to an out of bounds memory access.
+fn assert_out_of_bounds(result: Result<i32, Trap>) {
+    match result {
+        Err(trap) => assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds)),
+        Ok(value) => panic!("expected an out of bounds trap but found: {value}"),
+    }
+}
+
+#[test]
+fn fused_load_add() {
+    for_each_backend(WAT, |mut store, instance| {
+        let mut call = |name, ptr, x| call(&mut store, instance, name, ptr, x);
+        assert_eq!(call("load_lhs", 0, 1).unwrap(), 21);
+        assert_eq!(call("load_lhs", 4, 1).unwrap(), 0);
+        assert_eq!(call("load_rhs", 0, 5).unwrap(), 15);
+        assert_eq!(call("load_rhs", 8, i32::MIN).unwrap(), i32::MAX);
+        assert_eq!(call("load_far", 0, 0).unwrap(), 0);
+        assert_out_of_bounds(call("load_lhs", 65532, 1));
+        assert_out_of_bounds(call("load_rhs", 65533, 1));
+        assert_out_of_bounds(call("load_rhs", -1, 1));
+        assert_out_of_bounds(call("load_far", 1, 1));
+        assert_out_of_bounds(call("load_far", -4, 1));
+    });
+}
+
+/// Returns the fuel consumed by calling the exported function `name` with `ptr` and `x`.
+fn fuel_consumed(backend: EngineBackend, name: &str, ptr: i32, x: i32) -> u64 {
+    let (mut store, instance) = setup_metered(backend);
+    store.add_fuel(1_000).unwrap();
+    assert_eq!(call(&mut store, instance, name, ptr, x).unwrap(), 21);
+    store.fuel_consumed().unwrap()
+}
+
+#[test]
+fn fused_load_add_fuel_register_machine() {
+    // The `local.tee` prevents fusion and thus serves as the unfused reference.
+    let backend = EngineBackend::RegisterMachine;
+    assert_eq!(
+        fuel_consumed(backend, "load_lhs", 0, 1),
+        fuel_consumed(backend, "load_teed", 0, 1),
+    );
+}
+
+#[test]
+fn load_add_from_other_memory_register_machine() {
+    let wat = r#"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (data (memory $m0) (i32.const 0) "\0a\00\00\00")
+            (data (memory $m1) (i32.const 0) "\14\00\00\00")
+            (func (export "load_m0") (param $ptr i32) (param $x i32) (result i32)
+                (i32.add (i32.load $m0 (local.get $ptr)) (local.get $x))
+            )
+            (func (export "load_m1") (param $ptr i32) (param $x i32) (result i32)
+                (i32.add (i32.load $m1 (local.get $ptr)) (local.get $x))
+            )
+        )
+    "#;
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.wasm_multi_memory(true);
+    let (mut store, instance) = instantiate(&config, wat);
+    let mut call = |name, ptr, x| call(&mut store, instance, name, ptr, x);
+    assert_eq!(call("load_m0", 0, 1).unwrap(), 11);
+    assert_eq!(call("load_m1", 0, 1).unwrap(), 21);
+    // The default linear memory is used again after accessing `$m1`.
+    assert_eq!(call("load_m0", 0, 2).unwrap(), 12);
+    assert_out_of_bounds(call("load_m1", 65533, 1));
+}
@@ -0,0 +1,59 @@
+//! Tests for accessing global variables of an [`Instance`] by their index.
+
+use super::instantiate;
+use wasmi::{errors::GlobalError, Config, Instance, Store, Value};
+
+/// Instantiates a module with non-exported mutable and immutable global variables.
+fn setup() -> (Store<()>, Instance) {
+    let wat = r#"
+        (module
+            (global $mut (mut i32) (i32.const 10))
+            (global $imm i64 (i64.const 20))
+            (func (export "get") (result i32)
+                (global.get $mut)
+            )
+        )
+    "#;
+    instantiate(&Config::default(), wat)
+}
+
+#[test]
+fn read_and_write_mutable_global() {
+    let (mut store, instance) = setup();
+    let global = instance.get_global_by_index(&store, 0).unwrap();
+    assert_eq!(global.get(&store).i32(), Some(10));
+    global.set(&mut store, Value::I32(42)).unwrap();
+    assert_eq!(global.get(&store).i32(), Some(42));
+    // The new value must be observable from within Wasm.
+    let get = instance.get_typed_func::<(), i32>(&store, "get").unwrap();
+    assert_eq!(get.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn write_immutable_global_fails() {
+    let (mut store, instance) = setup();
+    let global = instance.get_global_by_index(&store, 1).unwrap();
+    assert_eq!(global.get(&store).i64(), Some(20));
+    assert!(matches!(
+        global.set(&mut store, Value::I64(0)),
+        Err(GlobalError::ImmutableWrite)
+    ));
+    assert_eq!(global.get(&store).i64(), Some(20));
+}
+
+#[test]
+fn write_mismatching_type_fails() {
+    let (mut store, instance) = setup();
+    let global = instance.get_global_by_index(&store, 0).unwrap();
+    assert!(matches!(
+        global.set(&mut store, Value::I64(0)),
+        Err(GlobalError::TypeMismatch { .. })
+    ));
+    assert_eq!(global.get(&store).i32(), Some(10));
+}
+
+#[test]
+fn out_of_bounds_index() {
+    let (store, instance) = setup();
+    assert!(instance.get_global_by_index(&store, 2).is_none());
+}
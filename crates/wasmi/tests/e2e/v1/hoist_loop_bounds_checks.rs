@@ -0,0 +1,214 @@
+//! Tests for counted loops whose bounds checks are hoisted in front of the loop.
+//!
+//! # Note
+//!
+//! Read [`Config::set_hoist_loop_bounds_checks`] for more information.
+
+use super::{backend_config, instantiate};
+use wasmi::{Config, EngineBackend, Error, Instance, StepOutcome, Store, Value};
+use wasmi_core::TrapCode;
+
+/// The module under test.
+///
+/// - `sum` loads in bounds of the first memory page in all loop iterations.
+/// - `sum_offset` loads out of bounds of the first memory page in its last loop iteration.
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (func (export "sum") (param $i i32) (result i64)
+            (local $sum i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.ge_u (local.get $i) (i32.const 65533)))
+                    (local.set $sum
+                        (i64.add
+                            (local.get $sum)
+                            (i64.add
+                                (i64.load32_u (local.get $i))
+                                (i64.load8_s offset=3 (local.get $i))
+                            )
+                        )
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 4)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "sum_offset") (param $i i32) (result i64)
+            (local $sum i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.ge_u (local.get $i) (i32.const 65536)))
+                    (local.set $sum
+                        (i64.xor (local.get $sum) (i64.load offset=1 (local.get $i)))
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 8)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "grow") (param $delta i32) (result i32)
+            (memory.grow (local.get $delta))
+        )
+    )
+"#;
+
+/// The outcome of calling one of the `sum` functions of the module under test.
+type Outcome = Result<i64, Option<TrapCode>>;
+
+/// The instantiated module under test.
+struct Test {
+    store: Store<()>,
+    instance: Instance,
+}
+
+impl Test {
+    /// Instantiates the module under test hoisting the bounds checks of counted loops if `hoist` is `true`.
+    fn new(backend: EngineBackend, hoist: bool) -> Self {
+        let mut config = backend_config(backend);
+        config.set_hoist_loop_bounds_checks(hoist);
+        Self::with_config(&config)
+    }
+
+    /// Instantiates the module under test using the given `config` and fills its memory.
+    fn with_config(config: &Config) -> Self {
+        let (mut store, instance) = instantiate(config, WAT);
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        let bytes: Vec<u8> = (0..1 << 16).map(|n| (n % 251) as u8).collect();
+        memory.write(&mut store, 0, &bytes).unwrap();
+        Self { store, instance }
+    }
+
+    /// Calls the exported `sum` function `name` with the initial loop index `i`.
+    fn sum(&mut self, name: &str, i: u32) -> Outcome {
+        self.instance
+            .get_typed_func::<i32, i64>(&self.store, name)
+            .unwrap()
+            .call(&mut self.store, i as i32)
+            .map_err(|trap| trap.trap_code())
+    }
+
+    /// Grows the linear memory by `delta` pages.
+    fn grow(&mut self, delta: i32) {
+        let result = self
+            .instance
+            .get_typed_func::<i32, i32>(&self.store, "grow")
+            .unwrap()
+            .call(&mut self.store, delta)
+            .unwrap();
+        assert_ne!(result, -1);
+    }
+}
+
+/// The initial loop indices used for testing.
+const STARTS: [u32; 9] = [0, 1, 3, 7, 4096, 65528, 65535, 65536, u32::MAX];
+
+/// Returns the outcomes of both `sum` functions for all [`STARTS`].
+fn outcomes(test: &mut Test) -> Vec<(Outcome, Outcome)> {
+    STARTS
+        .iter()
+        .map(|&i| (test.sum("sum", i), test.sum("sum_offset", i)))
+        .collect()
+}
+
+#[test]
+fn same_outcomes() {
+    let expected = outcomes(&mut Test::new(EngineBackend::StackMachine, false));
+    for (i, (sum, sum_offset)) in STARTS.iter().zip(&expected) {
+        assert!(sum.is_ok(), "{i}");
+        let expected_offset = match i {
+            0..=65535 => Err(Some(TrapCode::MemoryOutOfBounds)),
+            _ => Ok(0),
+        };
+        assert_eq!(sum_offset, &expected_offset, "{i}");
+    }
+    for backend in [EngineBackend::StackMachine, EngineBackend::RegisterMachine] {
+        for hoist in [false, true] {
+            let found = outcomes(&mut Test::new(backend, hoist));
+            assert_eq!(found, expected, "{backend:?}, hoist: {hoist}");
+        }
+    }
+}
+
+#[test]
+fn guard_uses_current_memory_size() {
+    let mut test = Test::new(EngineBackend::RegisterMachine, true);
+    assert_eq!(
+        test.sum("sum_offset", 0),
+        Err(Some(TrapCode::MemoryOutOfBounds))
+    );
+    test.grow(1);
+    let mut expected = Test::new(EngineBackend::RegisterMachine, false);
+    expected.grow(1);
+    for i in STARTS {
+        let found = test.sum("sum_offset", i);
+        assert!(found.is_ok(), "{i}");
+        assert_eq!(found, expected.sum("sum_offset", i), "{i}");
+    }
+}
+
+#[test]
+fn loads_are_unchecked() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.set_hoist_loop_bounds_checks(true);
+    config.profile_instructions(true);
+    let mut test = Test::with_config(&config);
+    let expected = Test::new(EngineBackend::RegisterMachine, false).sum("sum", 0);
+    assert_eq!(test.sum("sum", 0), expected);
+    let profile = test.store.engine().take_instruction_profile().unwrap();
+    assert_eq!(profile.count("LoopBoundsGuard"), 1);
+    assert_eq!(profile.count("I64Load32uUnchecked"), 16384);
+    assert_eq!(profile.count("I64Load8sUnchecked"), 16384);
+    assert_eq!(profile.count("I64Load32uOffset16"), 0);
+    assert_eq!(profile.count("I64Load8sOffset16"), 0);
+}
+
+#[test]
+fn no_effect_with_fuel_metering() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.set_hoist_loop_bounds_checks(true);
+    config.consume_fuel(true);
+    config.profile_instructions(true);
+    let mut test = Test::with_config(&config);
+    test.store.add_fuel(u64::MAX).unwrap();
+    assert_eq!(
+        test.sum("sum_offset", 0),
+        Err(Some(TrapCode::MemoryOutOfBounds))
+    );
+    let profile = test.store.engine().take_instruction_profile().unwrap();
+    assert_eq!(profile.count("LoopBoundsGuard"), 0);
+    // All iterations up to the out of bounds load have been executed.
+    assert_eq!(profile.count("I64LoadOffset16"), 8192);
+}
+
+#[test]
+fn stepped_restore_after_guard_traps() {
+    let mut test = Test::new(EngineBackend::RegisterMachine, true);
+    let memory = test.instance.get_memory(&test.store, "memory").unwrap();
+    let snapshot = memory.snapshot(&test.store);
+    test.grow(1);
+    memory.write(&mut test.store, 65536, &[0x41; 16]).unwrap();
+    let sum_offset = test.instance.get_func(&test.store, "sum_offset").unwrap();
+    let mut call = sum_offset
+        .call_stepped(&mut test.store, &[Value::I32(65528)])
+        .unwrap();
+    let mut results = [Value::I64(0)];
+    // Step 1: the guard in front of the loop passes for the grown linear memory.
+    assert_eq!(
+        call.step(&mut test.store, &mut results).unwrap(),
+        StepOutcome::Running
+    );
+    // Shrinking the linear memory invalidates the guard for the remaining steps.
+    memory.restore(&mut test.store, &snapshot).unwrap();
+    let trap_code = loop {
+        match call.step(&mut test.store, &mut results) {
+            Ok(StepOutcome::Running) => continue,
+            Ok(StepOutcome::Finished) => panic!("unexpected results: {results:?}"),
+            Err(Error::Trap(trap)) => break trap.trap_code(),
+            Err(error) => panic!("unexpected error: {error}"),
+        }
+    };
+    assert_eq!(trap_code, Some(TrapCode::MemoryOutOfBounds));
+}
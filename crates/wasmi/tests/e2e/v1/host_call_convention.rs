@@ -0,0 +1,96 @@
+//! Tests for the calling conventions used to call host functions from Wasm.
+//!
+//! # Note
+//!
+//! The register-machine engine backend exchanges parameters and results of host
+//! functions with small signatures via a dedicated buffer instead of temporarily
+//! growing the value stack. Host functions with larger signatures still use the
+//! value stack. Both conventions must yield identical results.
+
+use super::{backend_config, instantiate_with, BACKENDS};
+use wasmi::{core::Trap, Config, Engine, EngineBackend, Instance, Linker, StackLimits, Store};
+
+/// The Wasm module under test.
+///
+/// Both exported functions compute `2 * x + 1` via a host function call.
+/// The `call_small` export calls a host function with a small signature
+/// whereas `call_large` calls one that takes many parameters.
+const WAT: &str = r#"
+    (module
+        (import "env" "small" (func $small (param i32) (result i32)))
+        (import "env" "large" (func $large (param i32 i32 i32 i32 i32) (result i32)))
+        (func (export "call_small") (param i32) (result i32)
+            (call $small (local.get 0))
+        )
+        (func (export "call_large") (param i32) (result i32)
+            (call $large
+                (local.get 0) (local.get 0) (local.get 0) (local.get 0) (local.get 0)
+            )
+        )
+    )
+"#;
+
+/// Instantiates the module under test with its `small` and `large` host functions using `config`.
+fn setup(config: &Config) -> (Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "small", |x: i32| x.wrapping_mul(2).wrapping_add(1))
+        .unwrap();
+    linker
+        .func_wrap("env", "large", |a: i32, b: i32, c: i32, d: i32, e: i32| {
+            a.wrapping_add(b)
+                .wrapping_add(c.wrapping_sub(d))
+                .wrapping_add(e.wrapping_sub(e))
+                .wrapping_add(1)
+        })
+        .unwrap();
+    instantiate_with(&linker, (), WAT)
+}
+
+/// Calls the exported function `name` with `input`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, input: i32) -> Result<i32, Trap> {
+    instance
+        .get_typed_func::<i32, i32>(&mut *store, name)
+        .unwrap()
+        .call(store, input)
+}
+
+fn assert_identical_results(backend: EngineBackend) {
+    let (mut store, instance) = setup(&backend_config(backend));
+    for input in [0, 1, -1, 42, i32::MIN, i32::MAX] {
+        let expected = input.wrapping_mul(2).wrapping_add(1);
+        let small = call(&mut store, &instance, "call_small", input).unwrap();
+        let large = call(&mut store, &instance, "call_large", input).unwrap();
+        assert_eq!(small, expected, "call_small({input})");
+        assert_eq!(large, expected, "call_large({input})");
+    }
+}
+
+#[test]
+fn identical_results() {
+    for backend in BACKENDS {
+        assert_identical_results(backend)
+    }
+}
+
+/// Returns the minimum maximum value stack height required to call the export `name`.
+fn min_value_stack_height(name: &str) -> usize {
+    (1..=32)
+        .find(|&height| {
+            let mut config = backend_config(EngineBackend::RegisterMachine);
+            config.set_stack_limits(StackLimits::new(height, height, 16).unwrap());
+            let (mut store, instance) = setup(&config);
+            call(&mut store, &instance, name, 1).is_ok()
+        })
+        .unwrap_or_else(|| panic!("failed to call {name} with any value stack height"))
+}
+
+#[test]
+fn direct_slice_avoids_value_stack_growth() {
+    let small = min_value_stack_height("call_small");
+    let large = min_value_stack_height("call_large");
+    // The `large` host function needs 5 temporary cells on the value stack
+    // for its parameters whereas `small` requires no value stack growth.
+    assert_eq!(large, small + 5);
+}
@@ -0,0 +1,98 @@
+//! Tests for accessing imported global variables from within Wasm.
+
+use super::{backend_config, BACKENDS};
+use wasmi::{Engine, EngineBackend, Global, Instance, Linker, Module, Mutability, Store, Value};
+
+/// The Wasm module under test.
+///
+/// The `$init` global is defined by the module and initialized from the immutable import.
+const WAT: &str = r#"
+    (module
+        (import "env" "mut" (global $mut (mut i32)))
+        (import "env" "imm" (global $imm i64))
+        (global $init i64 (global.get $imm))
+        (func (export "get_mut") (result i32)
+            (global.get $mut)
+        )
+        (func (export "set_mut") (param i32)
+            (global.set $mut (local.get 0))
+        )
+        (func (export "get_imm") (result i64)
+            (global.get $imm)
+        )
+        (func (export "get_init") (result i64)
+            (global.get $init)
+        )
+    )
+"#;
+
+/// Instantiates the module under test with imported globals using the given engine `backend`.
+fn setup(backend: EngineBackend, mut_value: i32, imm_value: i64) -> (Store<()>, Instance, Global) {
+    let engine = Engine::new(&backend_config(backend));
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let global_mut = Global::new(&mut store, Value::I32(mut_value), Mutability::Var);
+    let global_imm = Global::new(&mut store, Value::I64(imm_value), Mutability::Const);
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "mut", global_mut).unwrap();
+    linker.define("env", "imm", global_imm).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, global_mut)
+}
+
+fn assert_imported_globals(backend: EngineBackend) {
+    for (mut_value, imm_value) in [(1, 2), (-10, i64::MAX)] {
+        let (mut store, instance, global_mut) = setup(backend, mut_value, imm_value);
+        let get_mut = instance
+            .get_typed_func::<(), i32>(&store, "get_mut")
+            .unwrap();
+        let set_mut = instance
+            .get_typed_func::<i32, ()>(&store, "set_mut")
+            .unwrap();
+        let get_imm = instance
+            .get_typed_func::<(), i64>(&store, "get_imm")
+            .unwrap();
+        let get_init = instance
+            .get_typed_func::<(), i64>(&store, "get_init")
+            .unwrap();
+        assert_eq!(get_mut.call(&mut store, ()).unwrap(), mut_value);
+        assert_eq!(get_imm.call(&mut store, ()).unwrap(), imm_value);
+        assert_eq!(get_init.call(&mut store, ()).unwrap(), imm_value);
+        // Writes from the host are observable from within Wasm.
+        global_mut.set(&mut store, Value::I32(42)).unwrap();
+        assert_eq!(get_mut.call(&mut store, ()).unwrap(), 42);
+        // Writes from within Wasm are observable from the host.
+        set_mut.call(&mut store, 100).unwrap();
+        assert_eq!(global_mut.get(&store).i32(), Some(100));
+        assert_eq!(get_mut.call(&mut store, ()).unwrap(), 100);
+    }
+}
+
+#[test]
+fn imported_globals() {
+    for backend in BACKENDS {
+        assert_imported_globals(backend)
+    }
+}
+
+#[test]
+fn set_immutable_imported_global_fails_compilation() {
+    let wat = r#"
+        (module
+            (import "env" "imm" (global $imm i32))
+            (func (param i32)
+                (global.set $imm (local.get 0))
+            )
+        )
+    "#;
+    let wasm = wat::parse_str(wat).unwrap();
+    for backend in BACKENDS {
+        let engine = Engine::new(&backend_config(backend));
+        assert!(Module::new(&engine, &wasm[..]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Tests for per-opcode instruction counters via [`Config::profile_instructions`].
+
+use super::{backend_config, instantiate};
+use wasmi::{EngineBackend, Instance, Store};
+
+/// The Wasm module under test.
+///
+/// The `sum` export adds `$x` to an accumulator `$n` times in a loop.
+const WAT: &str = r#"
+    (module
+        (func (export "sum") (param $n i32) (param $x i32) (result i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc (i32.add (local.get $acc) (local.get $x)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+    )
+"#;
+
+/// Instantiates the module under test counting executed instructions if `profile` is `true`.
+fn setup(backend: EngineBackend, profile: bool) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.profile_instructions(profile);
+    instantiate(&config, WAT)
+}
+
+/// Calls the `sum` export of the `instance` with `n` and `x`.
+fn sum(store: &mut Store<()>, instance: &Instance, n: i32, x: i32) -> i32 {
+    instance
+        .get_typed_func::<(i32, i32), i32>(&*store, "sum")
+        .unwrap()
+        .call(store, (n, x))
+        .unwrap()
+}
+
+#[test]
+fn instruction_profile_register_machine() {
+    let (mut store, instance) = setup(EngineBackend::RegisterMachine, true);
+    assert!(store.engine().take_instruction_profile().is_none());
+    assert_eq!(sum(&mut store, &instance, 100, 3), 300);
+    let profile = store.engine().take_instruction_profile().unwrap();
+    assert_eq!(profile.count("I32Add"), 100);
+    assert_eq!(profile.count("Unknown"), 0);
+    assert!(profile.total() > 100);
+    assert!(profile.to_string().contains("I32Add: 100\n"));
+    // The lines of the displayed profile are sorted by descending counters.
+    let counts: Vec<u64> = profile
+        .to_string()
+        .lines()
+        .map(|line| line.rsplit(": ").next().unwrap().parse().unwrap())
+        .collect();
+    assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert_eq!(counts.iter().sum::<u64>(), profile.total());
+    // Counters accumulate over executions until taken.
+    assert!(store.engine().take_instruction_profile().is_none());
+    assert_eq!(sum(&mut store, &instance, 10, 1), 10);
+    assert_eq!(sum(&mut store, &instance, 20, 1), 20);
+    let profile = store.engine().take_instruction_profile().unwrap();
+    assert_eq!(profile.count("I32Add"), 30);
+}
+
+fn assert_no_profile(backend: EngineBackend, profile: bool) {
+    let (mut store, instance) = setup(backend, profile);
+    assert_eq!(sum(&mut store, &instance, 100, 3), 300);
+    assert!(store.engine().take_instruction_profile().is_none());
+}
+
+#[test]
+fn no_instruction_profile_register_machine() {
+    assert_no_profile(EngineBackend::RegisterMachine, false)
+}
+
+#[test]
+fn no_instruction_profile_stack_machine() {
+    // The stack-machine backend does not support instruction profiling.
+    assert_no_profile(EngineBackend::StackMachine, true)
+}
@@ -0,0 +1,215 @@
+//! Tests for Wasm loads, stores and `memory.fill` that access linear memory at its very end.
+//!
+//! A load, store or `memory.fill` whose start address is in bounds but whose last
+//! byte is out of bounds must trap without reading or writing any bytes.
+
+use super::for_each_backend;
+use wasmi::{Error, Func, Store, Value};
+use wasmi_core::TrapCode;
+
+/// The size of the linear memory under test in bytes.
+const MEMORY_SIZE: i32 = 65536;
+
+/// All Wasm load operators together with their access width in bytes.
+const LOADS: &[(&str, &str, i32)] = &[
+    ("i32.load", "i32", 4),
+    ("i64.load", "i64", 8),
+    ("f32.load", "f32", 4),
+    ("f64.load", "f64", 8),
+    ("i32.load8_s", "i32", 1),
+    ("i32.load8_u", "i32", 1),
+    ("i32.load16_s", "i32", 2),
+    ("i32.load16_u", "i32", 2),
+    ("i64.load8_s", "i64", 1),
+    ("i64.load8_u", "i64", 1),
+    ("i64.load16_s", "i64", 2),
+    ("i64.load16_u", "i64", 2),
+    ("i64.load32_s", "i64", 4),
+    ("i64.load32_u", "i64", 4),
+];
+
+/// All Wasm store operators together with a value that has all bits set and their access width in bytes.
+const STORES: &[(&str, &str, i32)] = &[
+    ("i32.store", "i32.const -1", 4),
+    ("i64.store", "i64.const -1", 8),
+    ("f32.store", "f32.const -nan:0x7fffff", 4),
+    ("f64.store", "f64.const -nan:0xfffffffffffff", 8),
+    ("i32.store8", "i32.const -1", 1),
+    ("i32.store16", "i32.const -1", 2),
+    ("i64.store8", "i64.const -1", 1),
+    ("i64.store16", "i64.const -1", 2),
+    ("i64.store32", "i64.const -1", 4),
+];
+
+/// The byte value written by the `memory.fill` functions under test.
+const FILL_VALUE: u8 = 0xAB;
+
+/// Returns the address at which an access of `width` bytes ends one byte past the memory.
+fn edge_address(width: i32) -> i32 {
+    MEMORY_SIZE - (width - 1)
+}
+
+/// Creates the Wasm module source under test.
+///
+/// For every load and store operator this exports a function that takes its
+/// address as parameter as well as a function that uses a constant address.
+///
+/// The `memory.fill` function takes its destination and length as parameters
+/// whereas `memory.fill/const` fills the last 8 bytes and one byte past the memory.
+fn test_wat() -> String {
+    let mut funcs = String::new();
+    for (op, ty, width) in LOADS {
+        let address = edge_address(*width);
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{op}") (param i32) (result {ty})
+                ({op} (local.get 0))
+            )
+            (func (export "{op}/const") (result {ty})
+                ({op} (i32.const {address}))
+            )
+            "#
+        ));
+    }
+    for (op, value, width) in STORES {
+        let address = edge_address(*width);
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{op}") (param i32)
+                ({op} (local.get 0) ({value}))
+            )
+            (func (export "{op}/const")
+                ({op} (i32.const {address}) ({value}))
+            )
+            "#
+        ));
+    }
+    let address = MEMORY_SIZE - 8;
+    funcs.push_str(&format!(
+        r#"
+        (func (export "memory.fill") (param i32 i32)
+            (memory.fill (local.get 0) (i32.const {FILL_VALUE}) (local.get 1))
+        )
+        (func (export "memory.fill/const")
+            (memory.fill (i32.const {address}) (i32.const {FILL_VALUE}) (i32.const 9))
+        )
+        "#
+    ));
+    format!("(module (memory (export \"mem\") 1 1) {funcs})")
+}
+
+/// Calls `func` with `params` and returns `true` if it trapped with an out of bounds memory access.
+fn traps_out_of_bounds(store: &mut Store<()>, func: Func, params: &[Value]) -> bool {
+    let mut results = func
+        .ty(&*store)
+        .results()
+        .iter()
+        .copied()
+        .map(Value::default)
+        .collect::<Vec<_>>();
+    match func.call(store, params, &mut results) {
+        Ok(_) => false,
+        Err(Error::Trap(trap)) => {
+            assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+            true
+        }
+        Err(error) => panic!("expected a trap but found: {error}"),
+    }
+}
+
+#[test]
+fn loads_trap_at_edge() {
+    for_each_backend(&test_wat(), |mut store, instance| {
+        for (op, _, width) in LOADS {
+            let func = instance.get_func(&store, op).unwrap();
+            let address = edge_address(*width);
+            assert!(
+                !traps_out_of_bounds(&mut store, func, &[Value::I32(address - 1)]),
+                "{op} must not trap at the last valid address"
+            );
+            assert!(
+                traps_out_of_bounds(&mut store, func, &[Value::I32(address)]),
+                "{op} must trap when its last byte is out of bounds"
+            );
+            let func = instance.get_func(&store, &format!("{op}/const")).unwrap();
+            assert!(
+                traps_out_of_bounds(&mut store, func, &[]),
+                "{op} must trap for a constant address when its last byte is out of bounds"
+            );
+        }
+    });
+}
+
+#[test]
+fn stores_trap_at_edge() {
+    for_each_backend(&test_wat(), |mut store, instance| {
+        let memory = instance.get_memory(&store, "mem").unwrap();
+        let tail = MEMORY_SIZE as usize - 8..;
+        for (op, _, width) in STORES {
+            let func = instance.get_func(&store, op).unwrap();
+            let address = edge_address(*width);
+            assert!(
+                traps_out_of_bounds(&mut store, func, &[Value::I32(address)]),
+                "{op} must trap when its last byte is out of bounds"
+            );
+            let func = instance.get_func(&store, &format!("{op}/const")).unwrap();
+            assert!(
+                traps_out_of_bounds(&mut store, func, &[]),
+                "{op} must trap for a constant address when its last byte is out of bounds"
+            );
+            assert!(
+                memory.data(&store)[tail.clone()]
+                    .iter()
+                    .all(|byte| *byte == 0),
+                "{op} must not partially write bytes before trapping"
+            );
+        }
+        // Stores at the last valid address are expected to write all their bytes.
+        for (op, _, width) in STORES {
+            let func = instance.get_func(&store, op).unwrap();
+            let address = edge_address(*width) - 1;
+            assert!(!traps_out_of_bounds(
+                &mut store,
+                func,
+                &[Value::I32(address)]
+            ));
+            let written = &memory.data(&store)[address as usize..];
+            assert!(written.iter().all(|byte| *byte == 0xFF), "{op}");
+            memory.data_mut(&mut store)[tail.clone()].fill(0);
+        }
+    });
+}
+
+#[test]
+fn fill_traps_at_edge() {
+    for_each_backend(&test_wat(), |mut store, instance| {
+        let memory = instance.get_memory(&store, "mem").unwrap();
+        let fill = instance.get_func(&store, "memory.fill").unwrap();
+        let tail = MEMORY_SIZE as usize - 8..;
+        let address = MEMORY_SIZE - 8;
+        assert!(
+            traps_out_of_bounds(&mut store, fill, &[Value::I32(address), Value::I32(9)]),
+            "memory.fill must trap when its last byte is out of bounds"
+        );
+        let fill_const = instance.get_func(&store, "memory.fill/const").unwrap();
+        assert!(
+            traps_out_of_bounds(&mut store, fill_const, &[]),
+            "memory.fill must trap for constant operands when its last byte is out of bounds"
+        );
+        assert!(
+            memory.data(&store)[tail.clone()]
+                .iter()
+                .all(|byte| *byte == 0),
+            "memory.fill must not partially write bytes before trapping"
+        );
+        assert!(!traps_out_of_bounds(
+            &mut store,
+            fill,
+            &[Value::I32(address), Value::I32(8)]
+        ));
+        assert!(memory.data(&store)[tail.clone()]
+            .iter()
+            .all(|byte| *byte == FILL_VALUE));
+        assert_eq!(memory.data(&store)[tail.start - 1], 0);
+    });
+}
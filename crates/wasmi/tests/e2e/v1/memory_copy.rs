@@ -0,0 +1,133 @@
+//! Tests for the Wasm `memory.copy` operator with overlapping and empty regions.
+//!
+//! Overlapping regions must be copied as if through a temporary buffer.
+//! Out of bounds source or destination regions must trap without writing any bytes.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{EngineBackend, Instance, Memory, Store, TypedFunc};
+use wasmi_core::{Trap, TrapCode};
+
+/// The size of the linear memory under test in bytes.
+const MEMORY_SIZE: u32 = 65536;
+
+/// The Wasm module under test.
+///
+/// The `copy` export takes all operands as parameters whereas the `copy/*`
+/// exports use constant operands for the same overlapping copies as the tests.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1 1)
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "copy/forward")
+            (memory.copy (i32.const 2) (i32.const 0) (i32.const 8))
+        )
+        (func (export "copy/backward")
+            (memory.copy (i32.const 0) (i32.const 2) (i32.const 8))
+        )
+    )
+"#;
+
+/// Instantiates the module under test and initializes the first 10 bytes of its [`Memory`] to `1..=10`.
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Memory) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    memory
+        .write(&mut store, 0, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])
+        .unwrap();
+    (store, instance, memory)
+}
+
+/// Returns the `copy` export of the `instance`.
+fn copy(store: &Store<()>, instance: &Instance) -> TypedFunc<(u32, u32, u32), ()> {
+    instance.get_typed_func(store, "copy").unwrap()
+}
+
+/// Calls the `name` export of the `instance` that takes no parameters.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str) {
+    instance
+        .get_typed_func::<(), ()>(&*store, name)
+        .unwrap()
+        .call(store, ())
+        .unwrap();
+}
+
+/// Asserts that `result` is a trap due to an out of bounds memory access.
+fn assert_out_of_bounds(result: Result<(), Trap>) {
+    let trap = result.expect_err("expected an out of bounds trap");
+    assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+}
+
+fn assert_forward_overlap(backend: EngineBackend) {
+    let expected = [1, 2, 1, 2, 3, 4, 5, 6, 7, 8];
+    let (mut store, instance, memory) = setup(backend);
+    copy(&store, &instance).call(&mut store, (2, 0, 8)).unwrap();
+    assert_eq!(&memory.data(&store)[..10], expected);
+    let (mut store, instance, memory) = setup(backend);
+    call(&mut store, &instance, "copy/forward");
+    assert_eq!(&memory.data(&store)[..10], expected);
+}
+
+fn assert_backward_overlap(backend: EngineBackend) {
+    let expected = [3, 4, 5, 6, 7, 8, 9, 10, 9, 10];
+    let (mut store, instance, memory) = setup(backend);
+    copy(&store, &instance).call(&mut store, (0, 2, 8)).unwrap();
+    assert_eq!(&memory.data(&store)[..10], expected);
+    let (mut store, instance, memory) = setup(backend);
+    call(&mut store, &instance, "copy/backward");
+    assert_eq!(&memory.data(&store)[..10], expected);
+}
+
+fn assert_zero_length(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    let copy = copy(&store, &instance);
+    let before = memory.data(&store).to_vec();
+    for (dst, src) in [(0, 2), (2, 0), (MEMORY_SIZE, 0), (0, MEMORY_SIZE)] {
+        copy.call(&mut store, (dst, src, 0)).unwrap();
+    }
+    assert_eq!(memory.data(&store), before);
+    // Zero length copies still trap if either region starts past the end of the memory.
+    assert_out_of_bounds(copy.call(&mut store, (MEMORY_SIZE + 1, 0, 0)));
+    assert_out_of_bounds(copy.call(&mut store, (0, MEMORY_SIZE + 1, 0)));
+}
+
+fn assert_out_of_bounds_does_not_write(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    let copy = copy(&store, &instance);
+    let before = memory.data(&store).to_vec();
+    // The source region ends one byte past the memory.
+    assert_out_of_bounds(copy.call(&mut store, (0, MEMORY_SIZE - 9, 10)));
+    // The destination region ends one byte past the memory.
+    assert_out_of_bounds(copy.call(&mut store, (MEMORY_SIZE - 9, 0, 10)));
+    assert_out_of_bounds(copy.call(&mut store, (0, 0, u32::MAX)));
+    assert_eq!(memory.data(&store), before);
+}
+
+#[test]
+fn forward_overlap() {
+    for backend in BACKENDS {
+        assert_forward_overlap(backend)
+    }
+}
+
+#[test]
+fn backward_overlap() {
+    for backend in BACKENDS {
+        assert_backward_overlap(backend)
+    }
+}
+
+#[test]
+fn zero_length() {
+    for backend in BACKENDS {
+        assert_zero_length(backend)
+    }
+}
+
+#[test]
+fn out_of_bounds_does_not_write() {
+    for backend in BACKENDS {
+        assert_out_of_bounds_does_not_write(backend)
+    }
+}
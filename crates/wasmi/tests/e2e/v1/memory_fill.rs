@@ -0,0 +1,96 @@
+//! Tests that [`Memory::fill`] and the Wasm `memory.fill` operator agree.
+//!
+//! Both must produce identical memory contents for the same operands and
+//! must reject out of bounds regions without writing any bytes.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{errors::MemoryError, EngineBackend, Memory, Store, TypedFunc};
+use wasmi_core::TrapCode;
+
+/// The size of the linear memory under test in bytes.
+const MEMORY_SIZE: u32 = 65536;
+
+/// The Wasm module under test.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1 1)
+        (func (export "fill") (param $dst i32) (param $byte i32) (param $len i32)
+            (memory.fill (local.get $dst) (local.get $byte) (local.get $len))
+        )
+    )
+"#;
+
+/// The `fill` export of the module under test.
+type Fill = TypedFunc<(u32, u32, u32), ()>;
+
+/// Instantiates the module under test and initializes the first 10 bytes of its [`Memory`] to `1..=10`.
+fn setup(backend: EngineBackend) -> (Store<()>, Memory, Fill) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    memory
+        .write(&mut store, 0, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])
+        .unwrap();
+    let fill = instance.get_typed_func(&store, "fill").unwrap();
+    (store, memory, fill)
+}
+
+/// In bounds `(dst, byte, len)` operands.
+const IN_BOUNDS: &[(u32, u8, u32)] = &[
+    (0, 0xAB, 16),
+    (2, 0x00, 5),
+    (100, 0x01, 0),
+    (MEMORY_SIZE - 4, 0xFF, 4),
+    (MEMORY_SIZE, 0x07, 0),
+];
+
+/// Out of bounds `(dst, byte, len)` operands.
+const OUT_OF_BOUNDS: &[(u32, u8, u32)] = &[
+    (MEMORY_SIZE - 3, 0x01, 4),
+    (MEMORY_SIZE + 1, 0x01, 0),
+    (u32::MAX, 0x01, 1),
+    (1, 0x01, u32::MAX),
+];
+
+fn assert_fill_in_bounds(backend: EngineBackend) {
+    for &(dst, byte, len) in IN_BOUNDS {
+        let (mut host_store, host_memory, _) = setup(backend);
+        host_memory
+            .fill(&mut host_store, dst as usize, byte, len as usize)
+            .unwrap();
+        let (mut wasm_store, wasm_memory, fill) = setup(backend);
+        fill.call(&mut wasm_store, (dst, u32::from(byte), len))
+            .unwrap();
+        let host_data = host_memory.data(&host_store);
+        assert_eq!(host_data, wasm_memory.data(&wasm_store));
+        let (dst, len) = (dst as usize, len as usize);
+        assert!(host_data[dst..dst + len].iter().all(|&b| b == byte));
+    }
+}
+
+fn assert_fill_out_of_bounds(backend: EngineBackend) {
+    for &(dst, byte, len) in OUT_OF_BOUNDS {
+        let (mut store, memory, fill) = setup(backend);
+        let before = memory.data(&store).to_vec();
+        let host_result = memory.fill(&mut store, dst as usize, byte, len as usize);
+        assert!(matches!(host_result, Err(MemoryError::OutOfBoundsAccess)));
+        let trap = fill
+            .call(&mut store, (dst, u32::from(byte), len))
+            .expect_err("expected an out of bounds trap");
+        assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+        assert_eq!(memory.data(&store), before);
+    }
+}
+
+#[test]
+fn fill_in_bounds() {
+    for backend in BACKENDS {
+        assert_fill_in_bounds(backend)
+    }
+}
+
+#[test]
+fn fill_out_of_bounds() {
+    for backend in BACKENDS {
+        assert_fill_out_of_bounds(backend)
+    }
+}
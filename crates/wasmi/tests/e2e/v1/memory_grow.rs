@@ -0,0 +1,209 @@
+//! Tests for the semantics of the Wasm `memory.grow` instruction.
+//!
+//! Executors cache the bytes of the default linear memory. Growing the memory either
+//! via `memory.grow` or via a host function calling [`Memory::grow`] must invalidate
+//! that cache so that subsequent accesses never use a stale view of the memory.
+
+use super::{backend_config, instantiate_with, BACKENDS};
+use wasmi::{
+    core::Pages,
+    Caller,
+    Engine,
+    EngineBackend,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+};
+
+/// The size of a linear memory page in bytes.
+const PAGE_SIZE: usize = 65536;
+
+/// The Wasm module under test.
+///
+/// Both exports first load from the memory so that executors cache it, then grow
+/// the memory by one page and store into and load from the last bytes of the new page.
+const WAT: &str = r#"
+    (module
+        (import "env" "grow" (func $host_grow (param i32) (result i32)))
+        (memory (export "mem") 1)
+        (func $last_address (result i32)
+            (i32.sub (i32.shl (memory.size) (i32.const 16)) (i32.const 4))
+        )
+        (func (export "grow_then_store") (param $value i32) (result i32)
+            (drop (i32.load (i32.const 0)))
+            (drop (memory.grow (i32.const 1)))
+            (i32.store (call $last_address) (local.get $value))
+            (i32.load (call $last_address))
+        )
+        (func (export "host_grow_then_store") (param $value i32) (result i32)
+            (drop (i32.load (i32.const 0)))
+            (drop (call $host_grow (i32.const 1)))
+            (i32.store (call $last_address) (local.get $value))
+            (i32.load (call $last_address))
+        )
+    )
+"#;
+
+/// Instantiates the module under test whose imported `grow` host function grows
+/// the exported memory via [`Memory::grow`].
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Memory) {
+    let engine = Engine::new(&backend_config(backend));
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "grow", |mut caller: Caller<()>, delta: u32| -> u32 {
+            let memory = caller
+                .get_export("mem")
+                .and_then(Extern::into_memory)
+                .unwrap();
+            let delta = Pages::new(delta).unwrap();
+            memory.grow(&mut caller, delta).map(u32::from).unwrap()
+        })
+        .unwrap();
+    let (store, instance) = instantiate_with(&linker, (), WAT);
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    (store, instance, memory)
+}
+
+fn assert_grow_then_store(backend: EngineBackend, name: &str) {
+    let (mut store, instance, memory) = setup(backend);
+    let func = instance.get_typed_func::<i32, i32>(&store, name).unwrap();
+    for (pages, value) in [(2, 42), (3, -1)] {
+        let result = func.call(&mut store, value).unwrap();
+        assert_eq!(result, value);
+        let data = memory.data(&store);
+        assert_eq!(data.len(), pages * PAGE_SIZE);
+        assert_eq!(data[data.len() - 4..], value.to_le_bytes());
+    }
+}
+
+#[test]
+fn grow_then_store() {
+    for backend in BACKENDS {
+        assert_grow_then_store(backend, "grow_then_store")
+    }
+}
+
+#[test]
+fn host_grow_then_store() {
+    for backend in BACKENDS {
+        assert_grow_then_store(backend, "host_grow_then_store")
+    }
+}
+
+/// The Wasm module for testing the results of `memory.grow`.
+///
+/// The `grow_u` and `grow_s` exports return the result of `memory.grow`
+/// zero-extended and sign-extended respectively.
+const GROW_WAT: &str = r#"
+    (module
+        (memory 1 3)
+        (func (export "grow") (param $delta i32) (result i32)
+            (memory.grow (local.get $delta))
+        )
+        (func (export "grow_u") (param $delta i32) (result i64)
+            (i64.extend_i32_u (memory.grow (local.get $delta)))
+        )
+        (func (export "grow_s") (param $delta i32) (result i64)
+            (i64.extend_i32_s (memory.grow (local.get $delta)))
+        )
+        (func (export "grow_failed") (param $delta i32) (result i32)
+            (i32.eq (memory.grow (local.get $delta)) (i32.const -1))
+        )
+        (func (export "grow_by_3") (result i32)
+            (memory.grow (i32.const 3))
+        )
+        (func (export "size") (result i32)
+            (memory.size)
+        )
+    )
+"#;
+
+/// Instantiates the [`GROW_WAT`] module using the given engine `backend` and `limits`.
+fn setup_grow(backend: EngineBackend, limits: StoreLimits) -> (Store<StoreLimits>, Instance) {
+    let engine = Engine::new(&backend_config(backend));
+    let wasm = wat::parse_str(GROW_WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    let instance = <Linker<StoreLimits>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Asserts that growing the memory of `instance` by `delta` pages fails without side effects.
+fn assert_grow_fails(store: &mut Store<StoreLimits>, instance: &Instance, delta: u32) {
+    let size = instance.get_typed_func::<(), u32>(&*store, "size").unwrap();
+    let pages = size.call(&mut *store, ()).unwrap();
+    let call = |store: &mut Store<StoreLimits>, name: &str| -> i64 {
+        match name {
+            "grow" | "grow_failed" => instance
+                .get_typed_func::<u32, i32>(&*store, name)
+                .unwrap()
+                .call(store, delta)
+                .map(i64::from),
+            _ => instance
+                .get_typed_func::<u32, i64>(&*store, name)
+                .unwrap()
+                .call(store, delta),
+        }
+        .unwrap()
+    };
+    assert_eq!(call(store, "grow"), -1);
+    assert_eq!(call(store, "grow_u"), 0xFFFF_FFFF);
+    assert_eq!(call(store, "grow_s"), -1);
+    assert_eq!(call(store, "grow_failed"), 1);
+    assert_eq!(size.call(&mut *store, ()).unwrap(), pages);
+}
+
+fn assert_grow_results(backend: EngineBackend) {
+    let (mut store, instance) = setup_grow(backend, StoreLimits::default());
+    let grow = instance.get_typed_func::<u32, i32>(&store, "grow").unwrap();
+    let grow_u = instance
+        .get_typed_func::<u32, i64>(&store, "grow_u")
+        .unwrap();
+    let grow_by_3 = instance
+        .get_typed_func::<(), i32>(&store, "grow_by_3")
+        .unwrap();
+    // Successful growth returns the previous number of pages.
+    assert_eq!(grow.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(grow_u.call(&mut store, 0).unwrap(), 2);
+    // Growing beyond the maximum of the memory fails.
+    assert_grow_fails(&mut store, &instance, 2);
+    assert_eq!(grow_by_3.call(&mut store, ()).unwrap(), -1);
+    // Growing by more than the maximum number of Wasm pages fails.
+    assert_grow_fails(&mut store, &instance, 0x1_0001);
+    assert_grow_fails(&mut store, &instance, u32::MAX);
+    assert_eq!(grow.call(&mut store, 1).unwrap(), 2);
+    assert_grow_fails(&mut store, &instance, 1);
+}
+
+fn assert_grow_results_limited(backend: EngineBackend) {
+    let limits = StoreLimitsBuilder::new().memory_size(2 * PAGE_SIZE).build();
+    let (mut store, instance) = setup_grow(backend, limits);
+    let grow = instance.get_typed_func::<u32, i32>(&store, "grow").unwrap();
+    assert_eq!(grow.call(&mut store, 1).unwrap(), 1);
+    // Growing beyond the limit of the embedder fails even below the maximum of the memory.
+    assert_grow_fails(&mut store, &instance, 1);
+}
+
+#[test]
+fn grow_results() {
+    for backend in BACKENDS {
+        assert_grow_results(backend)
+    }
+}
+
+#[test]
+fn grow_results_limited() {
+    for backend in BACKENDS {
+        assert_grow_results_limited(backend)
+    }
+}
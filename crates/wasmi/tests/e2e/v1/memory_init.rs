@@ -0,0 +1,135 @@
+//! Tests for the Wasm `memory.init` and `data.drop` operators with passive data segments.
+//!
+//! A `memory.init` whose segment or memory region is out of bounds must trap without
+//! writing any bytes. Dropped data segments behave as if they were empty.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{EngineBackend, Instance, Memory, Store, TypedFunc};
+use wasmi_core::{Trap, TrapCode};
+
+/// The size of the linear memory under test in bytes.
+const MEMORY_SIZE: u32 = 65536;
+
+/// The bytes of the passive data segment under test.
+const PASSIVE: &[u8] = b"hello";
+
+/// The Wasm module under test.
+///
+/// Data segment `0` is passive whereas data segment `1` is active and thus
+/// implicitly dropped upon instantiation.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1 1)
+        (data "hello")
+        (data (i32.const 100) "world")
+        (func (export "init") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init 0 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "init/active") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init 1 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "drop")
+            (data.drop 0)
+        )
+    )
+"#;
+
+/// Instantiates the module under test and returns its exported [`Memory`].
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Memory) {
+    let (store, instance) = instantiate_backend(backend, WAT);
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    (store, instance, memory)
+}
+
+/// Returns the `(dst, src, len)` taking export `name` of the `instance`.
+fn init(store: &Store<()>, instance: &Instance, name: &str) -> TypedFunc<(u32, u32, u32), ()> {
+    instance.get_typed_func(store, name).unwrap()
+}
+
+/// Calls the `drop` export of the `instance`.
+fn drop_passive(store: &mut Store<()>, instance: &Instance) {
+    instance
+        .get_typed_func::<(), ()>(&*store, "drop")
+        .unwrap()
+        .call(store, ())
+        .unwrap();
+}
+
+/// Asserts that `result` is a trap due to an out of bounds memory access.
+fn assert_out_of_bounds(result: Result<(), Trap>) {
+    let trap = result.expect_err("expected an out of bounds trap");
+    assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+}
+
+fn assert_init(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    let init = init(&store, &instance, "init");
+    init.call(&mut store, (10, 0, 5)).unwrap();
+    assert_eq!(&memory.data(&store)[10..15], PASSIVE);
+    init.call(&mut store, (MEMORY_SIZE - 2, 3, 2)).unwrap();
+    assert_eq!(&memory.data(&store)[MEMORY_SIZE as usize - 2..], b"lo");
+    // Zero length initializations are valid at the end of the segment and memory.
+    init.call(&mut store, (MEMORY_SIZE, 5, 0)).unwrap();
+}
+
+fn assert_out_of_bounds_does_not_write(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    let init = init(&store, &instance, "init");
+    let before = memory.data(&store).to_vec();
+    // The segment region ends one byte past the segment.
+    assert_out_of_bounds(init.call(&mut store, (0, 3, 3)));
+    assert_out_of_bounds(init.call(&mut store, (0, 6, 0)));
+    // The memory region ends one byte past the memory.
+    assert_out_of_bounds(init.call(&mut store, (MEMORY_SIZE - 4, 0, 5)));
+    assert_out_of_bounds(init.call(&mut store, (MEMORY_SIZE + 1, 0, 0)));
+    assert_eq!(memory.data(&store), before);
+}
+
+fn assert_init_after_drop(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    let init = init(&store, &instance, "init");
+    drop_passive(&mut store, &instance);
+    // Dropping a data segment twice is valid.
+    drop_passive(&mut store, &instance);
+    let before = memory.data(&store).to_vec();
+    init.call(&mut store, (0, 0, 0)).unwrap();
+    assert_out_of_bounds(init.call(&mut store, (0, 0, 1)));
+    assert_out_of_bounds(init.call(&mut store, (0, 1, 0)));
+    assert_eq!(memory.data(&store), before);
+}
+
+fn assert_active_is_dropped(backend: EngineBackend) {
+    let (mut store, instance, memory) = setup(backend);
+    assert_eq!(&memory.data(&store)[100..105], b"world");
+    let init = init(&store, &instance, "init/active");
+    init.call(&mut store, (0, 0, 0)).unwrap();
+    assert_out_of_bounds(init.call(&mut store, (0, 0, 1)));
+}
+
+#[test]
+fn init_works() {
+    for backend in BACKENDS {
+        assert_init(backend)
+    }
+}
+
+#[test]
+fn out_of_bounds_does_not_write() {
+    for backend in BACKENDS {
+        assert_out_of_bounds_does_not_write(backend)
+    }
+}
+
+#[test]
+fn init_after_drop() {
+    for backend in BACKENDS {
+        assert_init_after_drop(backend)
+    }
+}
+
+#[test]
+fn active_is_dropped() {
+    for backend in BACKENDS {
+        assert_active_is_dropped(backend)
+    }
+}
@@ -0,0 +1,60 @@
+//! Tests for estimating the memory held by the compiled code of an [`Engine`].
+
+use super::{backend_config, BACKENDS};
+use wasmi::{Engine, EngineBackend, MemoryUsage, Module};
+
+/// Returns a Wasm module with `len_funcs` functions each using a distinct large constant.
+///
+/// The constants are distinct across calls if `seed` differs.
+fn wasm_with_funcs(seed: i64, len_funcs: i64) -> Vec<u8> {
+    let funcs: String = (0..len_funcs)
+        .map(|n| {
+            let value = (seed << 32) + n + i64::from(i32::MAX);
+            format!(
+                r#"
+                (func (param i64) (result i64)
+                    (i64.add (local.get 0) (i64.const {value}))
+                )"#
+            )
+        })
+        .collect();
+    wat::parse_str(format!("(module {funcs})")).unwrap()
+}
+
+/// Asserts that no estimation of `after` is below the one of `before`.
+fn assert_not_shrunk(before: MemoryUsage, after: MemoryUsage) {
+    assert!(after.code_map() >= before.code_map());
+    assert!(after.const_pool() >= before.const_pool());
+    assert!(after.provider_pool() >= before.provider_pool());
+    assert!(after.total() >= before.total());
+}
+
+fn assert_usage_grows(backend: EngineBackend) {
+    let engine = Engine::new(&backend_config(backend));
+    let empty = engine.estimate_memory_usage();
+    let mut before = empty;
+    for seed in 1..=8 {
+        let wasm = wasm_with_funcs(seed, 10 * seed);
+        Module::new(&engine, &wasm[..]).unwrap();
+        let after = engine.estimate_memory_usage();
+        assert_not_shrunk(before, after);
+        before = after;
+    }
+    assert!(before.code_map() > empty.code_map());
+    assert!(before.total() > empty.total());
+    if let EngineBackend::RegisterMachine = backend {
+        // The large constants are stored as function local constant values.
+        assert!(before.provider_pool() > empty.provider_pool());
+    }
+    assert_eq!(
+        before.total(),
+        before.code_map() + before.const_pool() + before.provider_pool()
+    );
+}
+
+#[test]
+fn usage_grows() {
+    for backend in BACKENDS {
+        assert_usage_grows(backend)
+    }
+}
@@ -1,6 +1,104 @@
+mod approx_sqrt;
+mod br_table;
+mod branch_cmp;
+mod call_observer;
+mod call_params_span;
+mod cmp_extremes;
+mod const_dedup;
+mod copy_span;
+#[cfg(feature = "test-utils")]
+mod differential;
+mod empty_module;
+mod float_abs_neg;
+mod float_min_max;
+mod floats;
+mod folded_trap_messages;
 mod fuel_consumption_mode;
 mod fuel_metering;
 mod func;
+mod func_body_bytes;
+mod func_instrs;
+mod fused_load_add;
+mod global_access;
+mod hoist_loop_bounds_checks;
+mod host_call_convention;
 mod host_calls_wasm;
+mod imported_globals;
+mod instruction_profile;
+mod memory_bounds;
+mod memory_copy;
+mod memory_fill;
+mod memory_grow;
+mod memory_init;
+mod memory_usage;
+mod module_limits;
+mod mul_overflow;
+mod multi_memory;
+mod narrow_loads;
+mod peephole_report;
+mod reentrancy;
+mod reference_results;
+mod reproduce_trap;
 mod resource_limiter;
 mod resumable_call;
+mod return_values;
+mod serialize_code;
+mod shift_by_zero;
+mod signal_bounds_checks;
+mod simd;
+mod snapshot;
+mod stack_limits;
+mod stepped_call;
+mod table_access;
+mod table_grow;
+mod tail_call;
+mod threads;
+mod trap_locations;
+mod typed_select;
+
+use wasmi::{Config, Engine, EngineBackend, Instance, Linker, Module, Store};
+
+/// The engine backends that the end-to-end tests are run against.
+pub const BACKENDS: [EngineBackend; 2] =
+    [EngineBackend::StackMachine, EngineBackend::RegisterMachine];
+
+/// Returns the default [`Config`] using the given engine `backend`.
+pub fn backend_config(backend: EngineBackend) -> Config {
+    let mut config = Config::default();
+    config.set_engine_backend(backend);
+    config
+}
+
+/// Instantiates the Wasm module `wat` without imports using the given `config`.
+pub fn instantiate(config: &Config, wat: &str) -> (Store<()>, Instance) {
+    instantiate_with(&<Linker<()>>::new(&Engine::new(config)), (), wat)
+}
+
+/// Instantiates the Wasm module `wat` using the definitions of `linker` and the store `data`.
+pub fn instantiate_with<T>(linker: &Linker<T>, data: T, wat: &str) -> (Store<T>, Instance) {
+    let engine = linker.engine();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, data);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Instantiates the Wasm module `wat` without imports using the given engine `backend`.
+pub fn instantiate_backend(backend: EngineBackend, wat: &str) -> (Store<()>, Instance) {
+    instantiate(&backend_config(backend), wat)
+}
+
+/// Instantiates the Wasm module `wat` without imports for each of the [`BACKENDS`].
+///
+/// Calls `f` with the [`Store`] and [`Instance`] of every instantiation.
+pub fn for_each_backend(wat: &str, mut f: impl FnMut(Store<()>, Instance)) {
+    for backend in BACKENDS {
+        let (store, instance) = instantiate_backend(backend, wat);
+        f(store, instance);
+    }
+}
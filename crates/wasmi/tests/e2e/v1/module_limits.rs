@@ -0,0 +1,172 @@
+//! Tests for the module limits configurable via [`Config`].
+
+use super::{backend_config, BACKENDS};
+use wasmi::{Config, Engine, EngineBackend, Error, Module};
+
+/// Compiles the `wat` source using an [`Engine`] with the given [`Config`].
+fn compile(config: &Config, wat: &str) -> Result<Module, Error> {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+/// A Wasm module that imports one function and defines three functions.
+const THREE_FUNCS: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func)
+        (func)
+        (func)
+    )
+"#;
+
+#[test]
+fn max_functions_works() {
+    let mut config = Config::default();
+    config.set_max_functions(3);
+    compile(&config, THREE_FUNCS).unwrap();
+}
+
+#[test]
+fn max_functions_exceeded() {
+    let mut config = Config::default();
+    config.set_max_functions(2);
+    let error = compile(&config, THREE_FUNCS).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered module defining more functions than configured"
+    );
+}
+
+/// A Wasm module with a function that requires a provider slice of length 5.
+///
+/// The call parameters are in reversed order so that they cannot be encoded
+/// as a contiguous register span.
+const FIVE_PROVIDERS: &str = r#"
+    (module
+        (func $f (param i32 i32 i32 i32 i32))
+        (func (param i32 i32 i32 i32 i32)
+            (call $f
+                (local.get 4)
+                (local.get 3)
+                (local.get 2)
+                (local.get 1)
+                (local.get 0)
+            )
+        )
+    )
+"#;
+
+#[test]
+fn max_provider_slice_len_works() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.set_max_provider_slice_len(5);
+    compile(&config, FIVE_PROVIDERS).unwrap();
+}
+
+#[test]
+fn max_provider_slice_len_exceeded() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.set_max_provider_slice_len(4);
+    let error = compile(&config, FIVE_PROVIDERS).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered provider slice longer than configured"
+    );
+}
+
+/// A Wasm module with an imported and a defined function with 3 parameters and 2 results.
+const THREE_PARAMS_TWO_RESULTS: &str = r#"
+    (module
+        (import "env" "f" (func (param i32 i64 f32) (result i32 i64)))
+        (func (param i32 i64 f32) (result i32 i64)
+            (local.get 0)
+            (local.get 1)
+        )
+    )
+"#;
+
+#[test]
+fn max_function_params_and_results_works() {
+    let mut config = Config::default();
+    config.set_max_function_params(3);
+    config.set_max_function_results(2);
+    compile(&config, THREE_PARAMS_TWO_RESULTS).unwrap();
+}
+
+#[test]
+fn max_function_params_exceeded() {
+    let mut config = Config::default();
+    config.set_max_function_params(2);
+    let error = compile(&config, THREE_PARAMS_TWO_RESULTS).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered function type with more parameters than configured"
+    );
+}
+
+#[test]
+fn max_function_results_exceeded() {
+    let mut config = Config::default();
+    config.set_max_function_results(1);
+    let error = compile(&config, THREE_PARAMS_TWO_RESULTS).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered function type with more results than configured"
+    );
+}
+
+/// Returns a Wasm module with a function containing `n` nested Wasm `block`s.
+fn nested_blocks(n: usize) -> String {
+    format!("(module (func {}{}))", "(block ".repeat(n), ")".repeat(n))
+}
+
+/// Returns a Wasm module with a function containing a `br_table` with `n` targets.
+fn br_table_targets(n: usize) -> String {
+    format!(
+        "(module (func (param i32) (block (br_table {} 0 (local.get 0)))))",
+        "0 ".repeat(n)
+    )
+}
+
+fn assert_max_function_labels(backend: EngineBackend) {
+    let mut config = backend_config(backend);
+    config.set_max_function_labels(200);
+    compile(&config, &nested_blocks(100)).unwrap();
+    let error = compile(&config, &nested_blocks(1000)).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered function with more labels than configured"
+    );
+}
+
+#[test]
+fn max_function_labels() {
+    for backend in BACKENDS {
+        assert_max_function_labels(backend)
+    }
+}
+
+fn assert_max_function_branches(backend: EngineBackend) {
+    let mut config = backend_config(backend);
+    config.set_max_function_branches(200);
+    compile(&config, &br_table_targets(100)).unwrap();
+    let error = compile(&config, &br_table_targets(1000)).unwrap_err();
+    assert!(matches!(error, Error::Module(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered function with more branches than configured"
+    );
+}
+
+#[test]
+fn max_function_branches() {
+    for backend in BACKENDS {
+        assert_max_function_branches(backend)
+    }
+}
@@ -0,0 +1,103 @@
+//! Tests that `i32.mul` and `i64.mul` wrap around upon overflow.
+
+use super::for_each_backend;
+
+/// The Wasm module under test.
+///
+/// - `mul` multiplies its two parameters at runtime.
+/// - `mul_2` multiplies its parameter with the small constant `2`.
+/// - `const` multiplies two overflowing constants that are evaluated at compilation time.
+const WAT: &str = r#"
+    (module
+        (func (export "i32.mul") (param i32 i32) (result i32)
+            (i32.mul (local.get 0) (local.get 1))
+        )
+        (func (export "i32.mul_2") (param i32) (result i32)
+            (i32.mul (local.get 0) (i32.const 2))
+        )
+        (func (export "i32.const") (result i32)
+            (i32.mul (i32.const -2147483648) (i32.const -1))
+        )
+        (func (export "i64.mul") (param i64 i64) (result i64)
+            (i64.mul (local.get 0) (local.get 1))
+        )
+        (func (export "i64.mul_2") (param i64) (result i64)
+            (i64.mul (local.get 0) (i64.const 2))
+        )
+        (func (export "i64.const") (result i64)
+            (i64.mul (i64.const -9223372036854775808) (i64.const -1))
+        )
+    )
+"#;
+
+/// Operands of `i32.mul` whose products overflow the 32-bit range.
+const I32_OVERFLOWS: &[(i32, i32)] = &[
+    (i32::MIN, -1),
+    (i32::MAX, 2),
+    (i32::MAX, i32::MAX),
+    (i32::MIN, i32::MIN),
+    (0x1234_5678, 0x10),
+    (-0x4000_0000, 4),
+];
+
+/// Operands of `i64.mul` whose products overflow the 64-bit range.
+const I64_OVERFLOWS: &[(i64, i64)] = &[
+    (i64::MIN, -1),
+    (i64::MAX, 2),
+    (i64::MAX, i64::MAX),
+    (i64::MIN, i64::MIN),
+    (0x1234_5678_9ABC_DEF0, 0x100),
+    (-0x4000_0000_0000_0000, 4),
+];
+
+#[test]
+fn mul_wraps() {
+    for_each_backend(WAT, |mut store, instance| {
+        let i32_mul = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "i32.mul")
+            .unwrap();
+        for &(lhs, rhs) in I32_OVERFLOWS {
+            let expected = lhs.wrapping_mul(rhs);
+            assert_eq!(i32_mul.call(&mut store, (lhs, rhs)).unwrap(), expected);
+            assert_eq!(i32_mul.call(&mut store, (rhs, lhs)).unwrap(), expected);
+        }
+        let i64_mul = instance
+            .get_typed_func::<(i64, i64), i64>(&store, "i64.mul")
+            .unwrap();
+        for &(lhs, rhs) in I64_OVERFLOWS {
+            let expected = lhs.wrapping_mul(rhs);
+            assert_eq!(i64_mul.call(&mut store, (lhs, rhs)).unwrap(), expected);
+            assert_eq!(i64_mul.call(&mut store, (rhs, lhs)).unwrap(), expected);
+        }
+    });
+}
+
+#[test]
+fn mul_imm_wraps() {
+    for_each_backend(WAT, |mut store, instance| {
+        let i32_mul_2 = instance
+            .get_typed_func::<i32, i32>(&store, "i32.mul_2")
+            .unwrap();
+        assert_eq!(i32_mul_2.call(&mut store, i32::MAX).unwrap(), -2);
+        assert_eq!(i32_mul_2.call(&mut store, i32::MIN).unwrap(), 0);
+        let i64_mul_2 = instance
+            .get_typed_func::<i64, i64>(&store, "i64.mul_2")
+            .unwrap();
+        assert_eq!(i64_mul_2.call(&mut store, i64::MAX).unwrap(), -2);
+        assert_eq!(i64_mul_2.call(&mut store, i64::MIN).unwrap(), 0);
+    });
+}
+
+#[test]
+fn mul_const_wraps() {
+    for_each_backend(WAT, |mut store, instance| {
+        let i32_const = instance
+            .get_typed_func::<(), i32>(&store, "i32.const")
+            .unwrap();
+        assert_eq!(i32_const.call(&mut store, ()).unwrap(), i32::MIN);
+        let i64_const = instance
+            .get_typed_func::<(), i64>(&store, "i64.const")
+            .unwrap();
+        assert_eq!(i64_const.call(&mut store, ()).unwrap(), i64::MIN);
+    });
+}
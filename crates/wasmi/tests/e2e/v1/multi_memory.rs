@@ -0,0 +1,237 @@
+//! Tests for the multi-memory Wasm proposal enabled via [`Config::wasm_multi_memory`].
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{
+    core::{Trap, TrapCode},
+    Engine,
+    EngineBackend,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    StepOutcome,
+    Store,
+    Value,
+};
+
+/// The Wasm module under test.
+///
+/// Imports two linear memories and exports functions that access either of them.
+const WAT: &str = r#"
+    (module
+        (import "env" "mem0" (memory $m0 1))
+        (import "env" "mem1" (memory $m1 1 2))
+        (func (export "store0") (param $ptr i32) (param $value i32)
+            (i32.store $m0 (local.get $ptr) (local.get $value))
+        )
+        (func (export "store1") (param $ptr i32) (param $value i32)
+            (i32.store $m1 (local.get $ptr) (local.get $value))
+        )
+        (func (export "load0") (param $ptr i32) (result i32)
+            (i32.load $m0 (local.get $ptr))
+        )
+        (func (export "load1") (param $ptr i32) (result i32)
+            (i32.load $m1 (local.get $ptr))
+        )
+        (func (export "size1") (result i32)
+            (memory.size $m1)
+        )
+        (func (export "grow1") (param $delta i32) (result i32)
+            (memory.grow $m1 (local.get $delta))
+        )
+        (func (export "fill1") (param $dst i32) (param $value i32) (param $len i32)
+            (memory.fill $m1 (local.get $dst) (local.get $value) (local.get $len))
+        )
+    )
+"#;
+
+/// The linear memories imported by the module under test.
+struct Memories {
+    mem0: Memory,
+    mem1: Memory,
+}
+
+/// Instantiates the module under test with its imported [`Memories`].
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Memories) {
+    let mut config = backend_config(backend);
+    config.wasm_multi_memory(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mem0 = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let mem1 = Memory::new(&mut store, MemoryType::new(1, Some(2)).unwrap()).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "mem0", mem0).unwrap();
+    linker.define("env", "mem1", mem1).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, Memories { mem0, mem1 })
+}
+
+/// Reads the little-endian `i32` at `ptr` of `memory`.
+fn read_i32(store: &Store<()>, memory: Memory, ptr: usize) -> i32 {
+    let mut bytes = [0x00; 4];
+    memory.read(store, ptr, &mut bytes).unwrap();
+    i32::from_le_bytes(bytes)
+}
+
+fn assert_store_and_load(backend: EngineBackend) {
+    let (mut store, instance, memories) = setup(backend);
+    let store0 = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store0")
+        .unwrap();
+    let store1 = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store1")
+        .unwrap();
+    let load0 = instance
+        .get_typed_func::<i32, i32>(&store, "load0")
+        .unwrap();
+    let load1 = instance
+        .get_typed_func::<i32, i32>(&store, "load1")
+        .unwrap();
+    store0.call(&mut store, (8, 10)).unwrap();
+    store1.call(&mut store, (8, 20)).unwrap();
+    assert_eq!(read_i32(&store, memories.mem0, 8), 10);
+    assert_eq!(read_i32(&store, memories.mem1, 8), 20);
+    assert_eq!(load0.call(&mut store, 8).unwrap(), 10);
+    assert_eq!(load1.call(&mut store, 8).unwrap(), 20);
+    // Accesses to the default linear memory are unaffected by prior accesses to another.
+    store0.call(&mut store, (12, 30)).unwrap();
+    assert_eq!(read_i32(&store, memories.mem0, 12), 30);
+    assert_eq!(read_i32(&store, memories.mem1, 12), 0);
+}
+
+fn assert_memory_ops(backend: EngineBackend) {
+    let (mut store, instance, memories) = setup(backend);
+    let size1 = instance.get_typed_func::<(), i32>(&store, "size1").unwrap();
+    let grow1 = instance
+        .get_typed_func::<i32, i32>(&store, "grow1")
+        .unwrap();
+    let fill1 = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "fill1")
+        .unwrap();
+    let load1 = instance
+        .get_typed_func::<i32, i32>(&store, "load1")
+        .unwrap();
+    let trap_code = |trap: Trap| trap.trap_code();
+    assert_eq!(
+        load1.call(&mut store, 65536).map_err(trap_code),
+        Err(Some(TrapCode::MemoryOutOfBounds))
+    );
+    assert_eq!(size1.call(&mut store, ()).unwrap(), 1);
+    assert_eq!(grow1.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(grow1.call(&mut store, 1).unwrap(), -1);
+    assert_eq!(size1.call(&mut store, ()).unwrap(), 2);
+    assert_eq!(memories.mem0.current_pages(&store), 1.into());
+    assert_eq!(memories.mem1.current_pages(&store), 2.into());
+    fill1.call(&mut store, (65536, 0x11, 4)).unwrap();
+    assert_eq!(load1.call(&mut store, 65536).unwrap(), 0x1111_1111);
+    assert_eq!(read_i32(&store, memories.mem1, 65536), 0x1111_1111);
+    assert_eq!(
+        fill1
+            .call(&mut store, (2 * 65536, 0x11, 1))
+            .map_err(trap_code),
+        Err(Some(TrapCode::MemoryOutOfBounds))
+    );
+}
+
+fn assert_cross_memory_copy_unsupported(backend: EngineBackend) {
+    let mut config = backend_config(backend);
+    config.wasm_multi_memory(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory $m0 1)
+            (memory $m1 1)
+            (func (param i32 i32 i32)
+                (memory.copy $m0 $m1 (local.get 0) (local.get 1) (local.get 2))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    assert!(Module::new(&engine, &wasm[..]).is_err());
+}
+
+/// A Wasm module whose data segments initialize the non-default linear memory `$m1`.
+///
+/// - The active data segment initializes `$m1` upon instantiation.
+/// - The `init1` export initializes `$m1` with the passive data segment.
+const DATA_WAT: &str = r#"
+    (module
+        (memory $m0 (export "mem0") 1)
+        (memory $m1 (export "mem1") 1)
+        (data (memory $m1) (i32.const 8) "\01\02\03\04")
+        (data $passive "\05\06\07\08")
+        (func (export "init1") (param $dst i32)
+            (memory.init $m1 $passive (local.get $dst) (i32.const 0) (i32.const 4))
+        )
+    )
+"#;
+
+fn assert_data_segments(backend: EngineBackend) {
+    let mut config = backend_config(backend);
+    config.wasm_multi_memory(true);
+    let (mut store, instance) = instantiate(&config, DATA_WAT);
+    let mem0 = instance.get_memory(&store, "mem0").unwrap();
+    let mem1 = instance.get_memory(&store, "mem1").unwrap();
+    assert_eq!(read_i32(&store, mem1, 8), 0x0403_0201);
+    assert!(mem0.data(&store).iter().all(|byte| *byte == 0x00));
+    let init1 = instance.get_typed_func::<i32, ()>(&store, "init1").unwrap();
+    init1.call(&mut store, 16).unwrap();
+    assert_eq!(read_i32(&store, mem1, 16), 0x0807_0605);
+    assert!(mem0.data(&store).iter().all(|byte| *byte == 0x00));
+}
+
+#[test]
+fn stepped_store_register_machine() {
+    let (mut store, instance, memories) = setup(EngineBackend::RegisterMachine);
+    let store1 = instance.get_func(&store, "store1").unwrap();
+    let mut call = store1
+        .call_stepped(&mut store, &[Value::I32(16), Value::I32(40)])
+        .unwrap();
+    while let StepOutcome::Running = call.step(&mut store, &mut []).unwrap() {}
+    assert_eq!(read_i32(&store, memories.mem0, 16), 0);
+    assert_eq!(read_i32(&store, memories.mem1, 16), 40);
+}
+
+#[test]
+fn multi_memory_disabled_by_default() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    assert!(Module::new(&engine, &wasm[..]).is_err());
+}
+
+#[test]
+fn store_and_load() {
+    for backend in BACKENDS {
+        assert_store_and_load(backend)
+    }
+}
+
+#[test]
+fn memory_ops() {
+    for backend in BACKENDS {
+        assert_memory_ops(backend)
+    }
+}
+
+#[test]
+fn cross_memory_copy_unsupported() {
+    for backend in BACKENDS {
+        assert_cross_memory_copy_unsupported(backend)
+    }
+}
+
+#[test]
+fn data_segments() {
+    for backend in BACKENDS {
+        assert_data_segments(backend)
+    }
+}
@@ -0,0 +1,90 @@
+//! Tests that narrow Wasm loads properly zero or sign extend the loaded value.
+
+use super::for_each_backend;
+use wasmi::Value;
+
+/// The bytes stored at address 0 of the linear memory under test.
+///
+/// All bytes have their high bit set so that sign extension is observable.
+const BYTES: [u8; 4] = [0x80, 0x90, 0xA0, 0xB0];
+
+/// All narrow Wasm load operators together with their result type.
+const LOADS: &[(&str, &str)] = &[
+    ("i32.load8_s", "i32"),
+    ("i32.load8_u", "i32"),
+    ("i32.load16_s", "i32"),
+    ("i32.load16_u", "i32"),
+    ("i64.load8_s", "i64"),
+    ("i64.load8_u", "i64"),
+    ("i64.load16_s", "i64"),
+    ("i64.load16_u", "i64"),
+    ("i64.load32_s", "i64"),
+    ("i64.load32_u", "i64"),
+];
+
+/// Returns the expected result of the narrow load operator `op` at address 0.
+fn expected(op: &str) -> Value {
+    let byte = BYTES[0];
+    let half = u16::from_le_bytes([BYTES[0], BYTES[1]]);
+    let word = u32::from_le_bytes(BYTES);
+    match op {
+        "i32.load8_s" => Value::I32(i32::from(byte as i8)),
+        "i32.load8_u" => Value::I32(i32::from(byte)),
+        "i32.load16_s" => Value::I32(i32::from(half as i16)),
+        "i32.load16_u" => Value::I32(i32::from(half)),
+        "i64.load8_s" => Value::I64(i64::from(byte as i8)),
+        "i64.load8_u" => Value::I64(i64::from(byte)),
+        "i64.load16_s" => Value::I64(i64::from(half as i16)),
+        "i64.load16_u" => Value::I64(i64::from(half)),
+        "i64.load32_s" => Value::I64(i64::from(word as i32)),
+        "i64.load32_u" => Value::I64(i64::from(word)),
+        _ => panic!("unexpected narrow load operator: {op}"),
+    }
+}
+
+/// Returns the Wasm module under test.
+///
+/// For every narrow load operator this exports a function that takes its
+/// address as parameter as well as a function that uses a constant address.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for (op, ty) in LOADS {
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{op}") (param i32) (result {ty})
+                ({op} (local.get 0))
+            )
+            (func (export "{op}/const") (result {ty})
+                ({op} (i32.const 0))
+            )
+            "#
+        ));
+    }
+    format!(r#"(module (memory 1) (data (i32.const 0) "\80\90\a0\b0") {funcs})"#)
+}
+
+/// Returns `true` if both [`Value`] are integers with equal bits.
+fn same_int(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::I32(lhs), Value::I32(rhs)) => lhs == rhs,
+        (Value::I64(lhs), Value::I64(rhs)) => lhs == rhs,
+        _ => false,
+    }
+}
+
+#[test]
+fn narrow_loads_extend() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for (op, _) in LOADS {
+            let expected = expected(op);
+            let mut result = [Value::default(expected.ty())];
+            let func = instance.get_func(&store, op).unwrap();
+            func.call(&mut store, &[Value::I32(0)], &mut result)
+                .unwrap();
+            assert!(same_int(&result[0], &expected), "{op}: {result:?}");
+            let func = instance.get_func(&store, &format!("{op}/const")).unwrap();
+            func.call(&mut store, &[], &mut result).unwrap();
+            assert!(same_int(&result[0], &expected), "{op}/const: {result:?}");
+        }
+    });
+}
@@ -0,0 +1,57 @@
+//! Tests for the peephole optimization counters reported via [`Module::peephole_report`].
+
+use super::backend_config;
+use wasmi::{Engine, EngineBackend, Module, PeepholeReport};
+
+/// The Wasm module under test.
+///
+/// The comments denote the peephole optimizations expected to apply.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (func (export "f") (param $x i32) (param $p i32) (result i32)
+            (local $t i32)
+            ;; imm16 encoding and copy elimination
+            (local.set $t (i32.mul (local.get $x) (i32.const 3)))
+            ;; fusion and copy elimination
+            (local.set $t (i32.add (i32.load (local.get $p)) (local.get $t)))
+            (i32.add
+                ;; simplification: `x - x` is always `0`
+                (i32.sub (local.get $t) (local.get $t))
+                ;; constant folding of `1 + 2` and imm16 encoding of `x + 3`
+                (i32.add (local.get $x) (i32.add (i32.const 1) (i32.const 2)))
+            ) ;; simplification: `0 + x` is same as `x`
+        )
+        (func (export "g") (param $x i64) (result i64)
+            (local $t i64)
+            ;; copy elimination of the dead `copy` of the constant to `$t`
+            (local.set $t (i64.const 5))
+            (local.set $t (i64.add (local.get $x) (local.get $x)))
+            ;; constant folding of `i64.extend_i32_s` and simplification `x * 1`
+            (i64.mul (local.get $t) (i64.extend_i32_s (i32.const 1)))
+        )
+    )
+"#;
+
+/// Compiles the module under test using the given engine `backend`.
+fn compile(backend: EngineBackend) -> Module {
+    let engine = Engine::new(&backend_config(backend));
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..]).unwrap()
+}
+
+#[test]
+fn peephole_report_register_machine() {
+    let report = compile(EngineBackend::RegisterMachine).peephole_report();
+    assert_eq!(report.constant_folds(), 2);
+    assert_eq!(report.simplifications(), 3);
+    assert_eq!(report.imm16_encodings(), 2);
+    assert_eq!(report.copy_eliminations(), 4);
+    assert_eq!(report.fusions(), 1);
+}
+
+#[test]
+fn peephole_report_stack_machine() {
+    let report = compile(EngineBackend::StackMachine).peephole_report();
+    assert_eq!(report, PeepholeReport::default());
+}
@@ -0,0 +1,199 @@
+//! Tests for host functions that try to mutate the engine executing them.
+//!
+//! Such mutations can never succeed while the engine is executing and must
+//! result in a descriptive error, or a descriptive panic for infallible
+//! operations, instead of a deadlock. Host functions may still call back
+//! into Wasm and engines executing on other threads may still be mutated.
+
+use super::{backend_config, instantiate_with, BACKENDS};
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::Duration,
+};
+use wasmi::{Caller, Engine, EngineBackend, Extern, Func, Linker, Module, Store, Value};
+
+/// A Wasm module without imports that is compiled by host functions.
+const INNER_WAT: &str = r#"
+    (module
+        (func (export "inner") (result i32)
+            (i32.const 1)
+        )
+    )
+"#;
+
+/// Calls the exported Wasm function that calls the `host` function using the given engine `backend`.
+fn call_host(backend: EngineBackend, host: fn(&mut Caller<()>)) -> i32 {
+    let engine = Engine::new(&backend_config(backend));
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", move |mut caller: Caller<()>| -> i32 {
+            host(&mut caller);
+            42
+        })
+        .unwrap();
+    let wat = r#"
+        (module
+            (import "env" "host" (func $host (result i32)))
+            (func (export "outer") (result i32)
+                (call $host)
+            )
+            (func (export "inner") (result i32)
+                (i32.const 1)
+            )
+        )
+    "#;
+    let (mut store, instance) = instantiate_with(&linker, (), wat);
+    instance
+        .get_typed_func::<(), i32>(&store, "outer")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap()
+}
+
+/// Compiles [`INNER_WAT`] using the engine of the `caller` which must fail.
+fn compile_with_caller_engine(caller: &mut Caller<()>) {
+    let wasm = wat::parse_str(INNER_WAT).unwrap();
+    let error = Module::new(caller.engine(), &wasm[..]).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("tried to compile using an engine from within one of its own Wasm executions"));
+}
+
+/// Compiles [`INNER_WAT`] using a new engine.
+fn compile_with_other_engine(_caller: &mut Caller<()>) {
+    let wasm = wat::parse_str(INNER_WAT).unwrap();
+    Module::new(&Engine::default(), &wasm[..]).unwrap();
+}
+
+/// Calls the exported `inner` Wasm function of the `caller` instance via [`Func::call`].
+fn call_inner(caller: &mut Caller<()>) {
+    let inner = caller
+        .get_export("inner")
+        .and_then(Extern::into_func)
+        .unwrap();
+    let mut results = [Value::I32(0)];
+    inner.call(&mut *caller, &[], &mut results).unwrap();
+    assert_eq!(results[0].i32(), Some(1));
+}
+
+/// Creates a host function with a new function type in the store of the `caller`.
+fn create_host_func(caller: &mut Caller<()>) {
+    Func::wrap(caller, |a: i64, b: i32, c: i64| -> i64 {
+        a + i64::from(b) + c
+    });
+}
+
+#[test]
+fn compile_in_host_fails() {
+    for backend in BACKENDS {
+        assert_eq!(call_host(backend, compile_with_caller_engine), 42);
+    }
+}
+
+#[test]
+#[should_panic(expected = "tried to mutate an engine from within one of its own Wasm executions")]
+fn create_func_in_host_stack_machine() {
+    call_host(EngineBackend::StackMachine, create_host_func);
+}
+
+#[test]
+#[should_panic(expected = "tried to mutate an engine from within one of its own Wasm executions")]
+fn create_func_in_host_register_machine() {
+    call_host(EngineBackend::RegisterMachine, create_host_func);
+}
+
+#[test]
+fn compile_other_engine_in_host_works() {
+    for backend in BACKENDS {
+        assert_eq!(call_host(backend, compile_with_other_engine), 42);
+    }
+}
+
+#[test]
+fn call_in_host_works() {
+    for backend in BACKENDS {
+        assert_eq!(call_host(backend, call_inner), 42);
+    }
+}
+
+#[test]
+fn compile_in_nested_host_of_other_engine_fails() {
+    // The host function of `outer` calls into Wasm of `inner` whose
+    // host function then tries to compile using `outer`.
+    let outer = Engine::default();
+    let mut store = Store::new(&outer, ());
+    let host = Func::wrap(&mut store, {
+        let outer = outer.clone();
+        move || {
+            let mut inner_store = Store::new(&Engine::default(), ());
+            let outer = outer.clone();
+            let compile = Func::wrap(&mut inner_store, move || {
+                let wasm = wat::parse_str(INNER_WAT).unwrap();
+                assert!(Module::new(&outer, &wasm[..]).is_err());
+            });
+            compile.call(&mut inner_store, &[], &mut []).unwrap();
+        }
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host))
+            (func (export "outer")
+                (call $host)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&outer, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&outer);
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "outer")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    // Once all executions have finished the engine can be mutated again.
+    let wasm = wat::parse_str(INNER_WAT).unwrap();
+    Module::new(&outer, &wasm[..]).unwrap();
+}
+
+#[test]
+fn compile_while_other_thread_executes_works() {
+    // Executes Wasm on another thread using `engine` until `done` is signaled.
+    let engine = Engine::default();
+    let started = Arc::new(Barrier::new(2));
+    let done = Arc::new(Barrier::new(2));
+    let executing = thread::spawn({
+        let engine = engine.clone();
+        let started = started.clone();
+        let done = done.clone();
+        move || {
+            let mut store = Store::new(&engine, ());
+            let host = Func::wrap(&mut store, move || {
+                started.wait();
+                done.wait();
+                // Keeps executing for a while so that the other thread
+                // tries to compile while this execution is still in progress.
+                thread::sleep(Duration::from_millis(50));
+            });
+            host.call(&mut store, &[], &mut []).unwrap();
+        }
+    });
+    started.wait();
+    // Compiles using `engine` from within a Wasm execution of another engine.
+    let mut store = Store::new(&Engine::default(), ());
+    let compile = Func::wrap(&mut store, move || {
+        done.wait();
+        let wasm = wat::parse_str(INNER_WAT).unwrap();
+        Module::new(&engine, &wasm[..]).unwrap();
+    });
+    compile.call(&mut store, &[], &mut []).unwrap();
+    executing.join().unwrap();
+}
@@ -0,0 +1,122 @@
+//! Tests that functions returning `funcref` and `externref` values return
+//! exactly the reference they were given on all call paths.
+
+use super::{backend_config, instantiate_with, BACKENDS};
+use wasmi::{Engine, EngineBackend, ExternRef, Func, FuncRef, Instance, Linker, Store};
+
+/// The module under test.
+///
+/// Every exported function returns its reference parameter, either directly
+/// or through an internal call, an indirect call or a host function call.
+const WAT: &str = r#"
+    (module
+        (import "env" "host_funcref" (func $host_funcref (param funcref) (result funcref)))
+        (import "env" "host_externref" (func $host_externref (param externref) (result externref)))
+        (type $funcref (func (param funcref) (result funcref)))
+        (type $externref (func (param externref) (result externref)))
+        (table 2 funcref)
+        (elem (i32.const 0) func $id_funcref $id_externref)
+        (func $id_funcref (export "funcref") (param funcref) (result funcref)
+            (local.get 0)
+        )
+        (func $id_externref (export "externref") (param externref) (result externref)
+            (local.get 0)
+        )
+        (func (export "call.funcref") (param funcref) (result funcref)
+            (call $id_funcref (local.get 0))
+        )
+        (func (export "call.externref") (param externref) (result externref)
+            (call $id_externref (local.get 0))
+        )
+        (func (export "call_indirect.funcref") (param funcref) (result funcref)
+            (call_indirect (type $funcref) (local.get 0) (i32.const 0))
+        )
+        (func (export "call_indirect.externref") (param externref) (result externref)
+            (call_indirect (type $externref) (local.get 0) (i32.const 1))
+        )
+        (func (export "call_host.funcref") (param funcref) (result funcref)
+            (call $host_funcref (local.get 0))
+        )
+        (func (export "call_host.externref") (param externref) (result externref)
+            (call $host_externref (local.get 0))
+        )
+    )
+"#;
+
+/// The names of all exported functions returning their `funcref` parameter.
+const FUNCREF_EXPORTS: &[&str] = &[
+    "funcref",
+    "call.funcref",
+    "call_indirect.funcref",
+    "call_host.funcref",
+];
+
+/// The names of all exported functions returning their `externref` parameter.
+const EXTERNREF_EXPORTS: &[&str] = &[
+    "externref",
+    "call.externref",
+    "call_indirect.externref",
+    "call_host.externref",
+];
+
+/// Instantiates the module under test with its identity host functions.
+fn setup(backend: EngineBackend) -> (Store<()>, Instance) {
+    let engine = Engine::new(&backend_config(backend));
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host_funcref", |value: FuncRef| value)
+        .unwrap();
+    linker
+        .func_wrap("env", "host_externref", |value: ExternRef| value)
+        .unwrap();
+    instantiate_with(&linker, (), WAT)
+}
+
+fn assert_funcref_results(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    let marker = Func::wrap(&mut store, || 42_i32);
+    for name in FUNCREF_EXPORTS {
+        let func = instance
+            .get_typed_func::<FuncRef, FuncRef>(&store, name)
+            .unwrap();
+        let result = func.call(&mut store, FuncRef::new(marker)).unwrap();
+        let returned = result.func().copied().unwrap();
+        let returned = returned.typed::<(), i32>(&store).unwrap();
+        assert_eq!(returned.call(&mut store, ()).unwrap(), 42, "{name}");
+        let result = func.call(&mut store, FuncRef::null()).unwrap();
+        assert!(result.is_null(), "{name}");
+    }
+}
+
+fn assert_externref_results(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    for name in EXTERNREF_EXPORTS {
+        let func = instance
+            .get_typed_func::<ExternRef, ExternRef>(&store, name)
+            .unwrap();
+        let input = ExternRef::new::<u64>(&mut store, 0xDEAD_BEEF_CAFE_BABE);
+        let result = func.call(&mut store, input).unwrap();
+        let data = result.data(&store).unwrap();
+        assert_eq!(
+            data.downcast_ref::<u64>(),
+            Some(&0xDEAD_BEEF_CAFE_BABE),
+            "{name}"
+        );
+        let result = func.call(&mut store, ExternRef::null()).unwrap();
+        assert!(result.is_null(), "{name}");
+    }
+}
+
+#[test]
+fn funcref_results() {
+    for backend in BACKENDS {
+        assert_funcref_results(backend)
+    }
+}
+
+#[test]
+fn externref_results() {
+    for backend in BACKENDS {
+        assert_externref_results(backend)
+    }
+}
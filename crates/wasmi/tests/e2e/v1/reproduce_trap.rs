@@ -0,0 +1,131 @@
+//! Tests for reproducing traps of recorded invocations via [`Engine::reproduce_trap`].
+
+use super::backend_config;
+use wasmi::{
+    core::TrapCode,
+    errors::FuncBodyError,
+    Engine,
+    EngineBackend,
+    Error,
+    Linker,
+    Module,
+    RecordedCall,
+    Store,
+    Value,
+};
+
+/// The Wasm module under test.
+///
+/// The `run` export calls `div` which traps if its divisor `$y` is zero.
+const WAT: &str = r#"
+    (module
+        (func $div (export "div") (param $x i32) (param $y i32) (result i32)
+            (i32.div_s (local.get $x) (local.get $y))
+        )
+        (func (export "run") (param $y i32) (result i32)
+            (i32.add
+                (call $div (i32.const 100) (local.get $y))
+                (i32.const 1)
+            )
+        )
+    )
+"#;
+
+/// Compiles the module under test locating traps if `locations` is `true`.
+fn setup(backend: EngineBackend, locations: bool) -> (Engine, Module) {
+    let mut config = backend_config(backend);
+    config.set_trap_locations(locations);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    (engine, module)
+}
+
+#[test]
+fn reproduce_trap_register_machine() {
+    let (engine, module) = setup(EngineBackend::RegisterMachine, true);
+    let linker = <Linker<()>>::new(&engine);
+    let call = RecordedCall::new("run", &[Value::I32(0)]);
+    let first = engine
+        .reproduce_trap(&linker, (), &module, &call)
+        .unwrap()
+        .expect("the recorded call must trap");
+    let second = engine
+        .reproduce_trap(&linker, (), &module, &call)
+        .unwrap()
+        .expect("the recorded call must trap");
+    assert_eq!(first.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(first.trap_code(), second.trap_code());
+    assert_eq!(first.backtrace(), second.backtrace());
+    assert_eq!(first.instr(), second.instr());
+    // The backtrace starts at `run` and ends at `div`.
+    let backtrace = first.backtrace();
+    assert_eq!(backtrace.len(), 2);
+    assert_eq!(backtrace[0].func_name(), "<wasm function 1>");
+    assert_eq!(backtrace[1].func_name(), "div");
+    assert_eq!(first.location(), &backtrace[1]);
+    // The reported instruction is an instruction of `div`.
+    let mut store = Store::new(&engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let div = instance.get_func(&store, "div").unwrap();
+    assert!(engine
+        .func_instrs(&store, &div)
+        .unwrap()
+        .any(|instr| instr == first.instr()));
+    // The reproduced trap is the same as the one of a normal call.
+    let trap = instance
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, 0)
+        .unwrap_err();
+    assert_eq!(first.trap_code(), trap.trap_code());
+    assert_eq!(Some(first.location()), trap.location());
+}
+
+#[test]
+fn reproduce_no_trap_register_machine() {
+    let (engine, module) = setup(EngineBackend::RegisterMachine, true);
+    let linker = <Linker<()>>::new(&engine);
+    let call = RecordedCall::new("run", &[Value::I32(5)]);
+    assert!(engine
+        .reproduce_trap(&linker, (), &module, &call)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn reproduce_missing_export_register_machine() {
+    let (engine, module) = setup(EngineBackend::RegisterMachine, true);
+    let linker = <Linker<()>>::new(&engine);
+    let call = RecordedCall::new("missing", &[]);
+    assert!(matches!(
+        engine.reproduce_trap(&linker, (), &module, &call),
+        Err(Error::Func(_))
+    ));
+}
+
+#[test]
+fn reproduce_trap_stack_machine() {
+    let (engine, module) = setup(EngineBackend::StackMachine, true);
+    let linker = <Linker<()>>::new(&engine);
+    let call = RecordedCall::new("run", &[Value::I32(0)]);
+    assert!(matches!(
+        engine.reproduce_trap(&linker, (), &module, &call),
+        Err(Error::FuncBody(FuncBodyError::UnsupportedBackend))
+    ));
+}
+
+#[test]
+fn reproduce_trap_without_locations() {
+    let (engine, module) = setup(EngineBackend::RegisterMachine, false);
+    let linker = <Linker<()>>::new(&engine);
+    let call = RecordedCall::new("run", &[Value::I32(0)]);
+    assert!(matches!(
+        engine.reproduce_trap(&linker, (), &module, &call),
+        Err(Error::FuncBody(FuncBodyError::Unlocated))
+    ));
+}
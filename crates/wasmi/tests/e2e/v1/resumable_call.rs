@@ -19,7 +19,7 @@ use wasmi::{
     TypedResumableInvocation,
     Value,
 };
-use wasmi_core::{Trap, TrapCode, ValueType};
+use wasmi_core::{HostError, Trap, TrapCode, ValueType};
 
 fn test_setup() -> (Store<()>, Linker<()>) {
     let mut config = Config::default();
@@ -361,3 +361,68 @@ fn run_test_typed(wasm_fn: Func, mut store: &mut Store<()>, wasm_trap: bool) {
         assert_eq!(call.unwrap().assert_finish(), 4);
     }
 }
+
+/// Signals that the result of a host function call is not yet available.
+#[derive(Debug)]
+struct Pending {
+    /// Identifies the host operation that has to complete before resumption.
+    ticket: u32,
+}
+
+impl core::fmt::Display for Pending {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "pending host operation #{}", self.ticket)
+    }
+}
+
+impl HostError for Pending {}
+
+#[test]
+fn resumable_call_pending_host() {
+    let (mut store, mut linker) = test_setup();
+    // The host function never completes synchronously but always signals
+    // a pending result that is provided upon resumption.
+    let host_fn = Func::wrap(&mut store, |ticket: u32| -> Result<i32, Trap> {
+        Err(Trap::from(Pending { ticket }))
+    });
+    linker.define("env", "fetch", host_fn).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "fetch" (func $fetch (param i32) (result i32)))
+            (func (export "sum") (result i32)
+                (i32.add
+                    (call $fetch (i32.const 1))
+                    (call $fetch (i32.const 2))
+                )
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sum = instance.get_func(&store, "sum").unwrap();
+    let mut results = [Value::I32(0)];
+    let mut call = sum.call_resumable(&mut store, &[], &mut results).unwrap();
+    let mut tickets = Vec::new();
+    while let ResumableCall::Resumable(invocation) = call {
+        let ticket = invocation
+            .host_error()
+            .downcast_ref::<Pending>()
+            .expect("expected a pending host operation")
+            .ticket;
+        tickets.push(ticket);
+        // Complete the pending host operation and resume with its result.
+        let result = Value::I32(ticket as i32 * 10);
+        call = invocation
+            .resume(&mut store, &[result], &mut results)
+            .unwrap();
+    }
+    assert_eq!(tickets, [1, 2]);
+    assert_eq!(results[0].i32(), Some(30));
+}
@@ -0,0 +1,58 @@
+//! Tests for returning values from callees whose results map onto the same
+//! relative registers in the caller.
+
+use super::for_each_backend;
+
+/// The Wasm module under test.
+///
+/// Every function forwards its parameters unchanged, so the returned registers of
+/// each callee have the same relative positions as the result registers of its caller.
+const WAT: &str = r#"
+    (module
+        (func $id2 (param i32 i32) (result i32 i32)
+            (local.get 0) (local.get 1)
+        )
+        (func $id5 (param i32 i32 i32 i32 i32) (result i32 i32 i32 i32 i32)
+            (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+        )
+        (func $fwd2 (param i32 i32) (result i32 i32)
+            (call $id2 (local.get 0) (local.get 1))
+        )
+        (func $fwd5 (param i32 i32 i32 i32 i32) (result i32 i32 i32 i32 i32)
+            (call $id5 (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4))
+        )
+        (func (export "id2") (param i32 i32) (result i32 i32)
+            (call $fwd2 (local.get 0) (local.get 1))
+        )
+        (func (export "id5") (param i32 i32 i32 i32 i32) (result i32 i32 i32 i32 i32)
+            (call $fwd5 (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4))
+        )
+        (func (export "root2") (param i32 i32) (result i32 i32)
+            (local.get 0) (local.get 1)
+        )
+        (func (export "root5") (param i32 i32 i32 i32 i32) (result i32 i32 i32 i32 i32)
+            (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+        )
+    )
+"#;
+
+#[test]
+fn forwarded_returns() {
+    for_each_backend(WAT, |mut store, instance| {
+        for name in ["id2", "root2"] {
+            let func = instance
+                .get_typed_func::<(i32, i32), (i32, i32)>(&store, name)
+                .unwrap();
+            assert_eq!(func.call(&mut store, (1, 2)).unwrap(), (1, 2), "{name}");
+        }
+        for name in ["id5", "root5"] {
+            let func = instance
+                .get_typed_func::<(i32, i32, i32, i32, i32), (i32, i32, i32, i32, i32)>(
+                    &store, name,
+                )
+                .unwrap();
+            let values = (1, 2, 3, 4, 5);
+            assert_eq!(func.call(&mut store, values).unwrap(), values, "{name}");
+        }
+    });
+}
@@ -0,0 +1,227 @@
+//! Tests for serializing and restoring compiled code via [`Engine::serialize_code`].
+
+use super::backend_config;
+use wasmi::{
+    errors::FuncBodyError,
+    Config,
+    Engine,
+    EngineBackend,
+    Error,
+    Linker,
+    Module,
+    PeepholeReport,
+    Store,
+    BYTECODE_VERSION,
+};
+
+/// The Wasm module under test.
+///
+/// The `fib` export uses function local constants, control flow and calls another Wasm function.
+const WAT: &str = r#"
+    (module
+        (func $add (param i64 i64) (result i64)
+            (i64.add (local.get 0) (local.get 1))
+        )
+        (func (export "fib") (param $n i32) (result i64)
+            (local $a i64) (local $b i64) (local $t i64)
+            (local.set $b (i64.const 1))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $t (call $add (local.get $a) (local.get $b)))
+                    (local.set $a (local.get $b))
+                    (local.set $b (local.get $t))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i64.mul (local.get $a) (i64.const 0x1_0000_0001))
+        )
+    )
+"#;
+
+/// Compiles the module under test using `engine` and returns the results of calling `fib`.
+fn compile_and_run(engine: &Engine) -> (Module, Vec<i64>) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let fib = <Linker<()>>::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<i32, i64>(&store, "fib")
+        .unwrap();
+    let results = [0, 1, 2, 10, 50, 90]
+        .into_iter()
+        .map(|n| fib.call(&mut store, n).unwrap())
+        .collect();
+    (module, results)
+}
+
+/// Returns the serialized code of an [`Engine`] that compiled the module under test.
+fn serialized(config: &Config) -> Result<(Vec<u8>, Vec<i64>), Error> {
+    let engine = Engine::new(config);
+    let (_module, results) = compile_and_run(&engine);
+    let bytes = engine.serialize_code()?;
+    Ok((bytes, results))
+}
+
+/// Returns a new [`Engine`] with the restored `bytes`.
+fn restored(config: &Config, bytes: &[u8]) -> Result<Engine, Error> {
+    let engine = Engine::new(config);
+    // SAFETY: `bytes` have been serialized for the same Wasm module.
+    unsafe { engine.deserialize_code(bytes)? };
+    Ok(engine)
+}
+
+#[test]
+fn round_trip() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let (bytes, expected) = serialized(&config).unwrap();
+    let engine = restored(&config, &bytes).unwrap();
+    let (module, results) = compile_and_run(&engine);
+    assert_eq!(results, expected);
+    // No peephole optimization applied since no function has been translated.
+    assert_eq!(module.peephole_report(), PeepholeReport::default());
+    assert_eq!(engine.serialize_code().unwrap(), bytes);
+}
+
+#[test]
+fn other_modules_are_translated() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let (bytes, expected) = serialized(&config).unwrap();
+    let engine = restored(&config, &bytes).unwrap();
+    let other = wat::parse_str(r#"(module (func (export "f")))"#).unwrap();
+    Module::new(&engine, &other[..]).unwrap();
+    // The module under test is compiled to different function bodies now
+    // and therefore translated instead of using the restored function bodies.
+    let (module, results) = compile_and_run(&engine);
+    assert_eq!(results, expected);
+    assert_ne!(module.peephole_report(), PeepholeReport::default());
+}
+
+#[test]
+fn foreign_module_is_validated_and_translated() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let (bytes, _) = serialized(&config).unwrap();
+    // The foreign module is compiled to the same function bodies as the module under test.
+    let foreign = WAT.replace("i64.add", "i64.sub");
+    let foreign = wat::parse_str(&foreign).unwrap();
+    let expected = Module::new(&Engine::new(&config), &foreign[..])
+        .unwrap()
+        .peephole_report();
+    assert_ne!(expected, PeepholeReport::default());
+    let engine = restored(&config, &bytes).unwrap();
+    let module = Module::new(&engine, &foreign[..]).unwrap();
+    assert_eq!(module.peephole_report(), expected);
+    // Invalid foreign modules must not use the restored function bodies either.
+    let invalid = WAT.replace("(i64.mul (local.get $a)", "(i32.mul (local.get $a)");
+    let invalid = wat::parse_str(&invalid).unwrap();
+    let engine = restored(&config, &bytes).unwrap();
+    assert!(Module::new(&engine, &invalid[..]).is_err());
+}
+
+#[test]
+fn module_structure_mismatch() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let wasm = WAT.replacen("(module", "(module (memory 1 12345)", 1);
+    let wasm = wat::parse_str(&wasm).unwrap();
+    let engine = Engine::new(&config);
+    let expected = Module::new(&engine, &wasm[..]).unwrap().peephole_report();
+    assert_ne!(expected, PeepholeReport::default());
+    let mut bytes = engine.serialize_code().unwrap();
+    let engine = restored(&config, &bytes).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(module.peephole_report(), PeepholeReport::default());
+    // Simulates a fingerprint collision of the Wasm module bytes by only changing
+    // the maximum of the linear memory within the serialized module structure.
+    let (min, max) = (1_u32.to_le_bytes(), 12345_u32.to_le_bytes());
+    let recorded = [&min[..], &[1], &max[..]].concat();
+    let pos = bytes
+        .windows(recorded.len())
+        .position(|window| window == recorded)
+        .unwrap();
+    let max_pos = pos + min.len() + 1;
+    bytes[max_pos..max_pos + max.len()].copy_from_slice(&12346_u32.to_le_bytes());
+    let engine = restored(&config, &bytes).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(module.peephole_report(), expected);
+}
+
+#[test]
+fn config_mismatch() {
+    let (bytes, _) = serialized(&backend_config(EngineBackend::RegisterMachine)).unwrap();
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.consume_fuel(true);
+    assert!(matches!(
+        restored(&config, &bytes),
+        Err(Error::FuncBody(FuncBodyError::ConfigMismatch))
+    ));
+}
+
+#[test]
+fn bytecode_version_mismatch() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let (mut bytes, _) = serialized(&config).unwrap();
+    // The bytecode version follows the magic bytes and the format version.
+    let older = BYTECODE_VERSION - 1;
+    bytes[5..9].copy_from_slice(&older.to_le_bytes());
+    assert!(matches!(
+        restored(&config, &bytes),
+        Err(Error::FuncBody(FuncBodyError::BytecodeVersionMismatch {
+            expected: BYTECODE_VERSION,
+            found,
+        })) if found == older
+    ));
+}
+
+#[test]
+fn pinned_bytecode_version() {
+    let (bytes, _) = serialized(&backend_config(EngineBackend::RegisterMachine)).unwrap();
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.pin_bytecode_version(BYTECODE_VERSION + 1);
+    let mismatch = FuncBodyError::BytecodeVersionMismatch {
+        expected: BYTECODE_VERSION + 1,
+        found: BYTECODE_VERSION,
+    };
+    assert!(matches!(
+        restored(&config, &bytes),
+        Err(Error::FuncBody(error)) if error == mismatch
+    ));
+    assert!(matches!(
+        serialized(&config),
+        Err(Error::FuncBody(error)) if error == mismatch
+    ));
+    // Pinning the current bytecode version is the default.
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.pin_bytecode_version(BYTECODE_VERSION);
+    restored(&config, &bytes).unwrap();
+}
+
+#[test]
+fn malformed_bytes() {
+    let config = backend_config(EngineBackend::RegisterMachine);
+    let (bytes, _) = serialized(&config).unwrap();
+    for len in [0, 4, 13, bytes.len() - 1] {
+        assert!(matches!(
+            restored(&config, &bytes[..len]),
+            Err(Error::FuncBody(FuncBodyError::InvalidFormat))
+        ));
+    }
+    let mut trailing = bytes;
+    trailing.push(0);
+    assert!(matches!(
+        restored(&config, &trailing),
+        Err(Error::FuncBody(FuncBodyError::InvalidFormat))
+    ));
+}
+
+#[test]
+fn stack_machine_is_unsupported() {
+    let config = backend_config(EngineBackend::StackMachine);
+    assert!(matches!(
+        serialized(&config),
+        Err(Error::FuncBody(FuncBodyError::UnsupportedBackend))
+    ));
+}
@@ -0,0 +1,64 @@
+//! Tests that shifting or rotating by a constant zero yields the original value.
+
+use super::for_each_backend;
+use wasmi::Value;
+
+/// All Wasm shift and rotate operators together with their operand type.
+const OPS: &[(&str, &str)] = &[
+    ("i32.shl", "i32"),
+    ("i32.shr_s", "i32"),
+    ("i32.shr_u", "i32"),
+    ("i32.rotl", "i32"),
+    ("i32.rotr", "i32"),
+    ("i64.shl", "i64"),
+    ("i64.shr_s", "i64"),
+    ("i64.shr_u", "i64"),
+    ("i64.rotl", "i64"),
+    ("i64.rotr", "i64"),
+];
+
+/// Returns the Wasm module under test.
+///
+/// For every operator this exports a function that shifts or rotates its
+/// parameter by a constant zero.
+fn wat() -> String {
+    let mut funcs = String::new();
+    for (op, ty) in OPS {
+        funcs.push_str(&format!(
+            r#"
+            (func (export "{op}") (param {ty}) (result {ty})
+                ({op} (local.get 0) ({ty}.const 0))
+            )
+            "#
+        ));
+    }
+    format!("(module {funcs})")
+}
+
+#[test]
+fn shift_by_zero_is_identity() {
+    for_each_backend(&wat(), |mut store, instance| {
+        for (op, ty) in OPS {
+            let func = instance.get_func(&store, op).unwrap();
+            match *ty {
+                "i32" => {
+                    for input in [0, 1, -1, i32::MIN, i32::MAX, 0x1234_5678] {
+                        let mut result = [Value::I32(0)];
+                        func.call(&mut store, &[Value::I32(input)], &mut result)
+                            .unwrap();
+                        assert_eq!(result[0].i32(), Some(input), "{op}: {input}");
+                    }
+                }
+                "i64" => {
+                    for input in [0, 1, -1, i64::MIN, i64::MAX, 0x1234_5678_9ABC_DEF0] {
+                        let mut result = [Value::I64(0)];
+                        func.call(&mut store, &[Value::I64(input)], &mut result)
+                            .unwrap();
+                        assert_eq!(result[0].i64(), Some(input), "{op}: {input}");
+                    }
+                }
+                _ => panic!("unexpected operand type: {ty}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,233 @@
+//! Tests for linear memory bounds checks via [`Config::set_signal_bounds_checks`].
+//!
+//! Out of bounds loads and stores must trap identically whether the effective
+//! mode uses guard pages or explicit bounds checks.
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{
+    core::{Pages, Trap, TrapCode},
+    Config,
+    EngineBackend,
+    Instance,
+    Memory,
+    Store,
+};
+
+/// The Wasm module under test.
+///
+/// The linear memory initially spans a single page of 65536 bytes.
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (func (export "load") (param $ptr i32) (result i64)
+            (i64.load (local.get $ptr))
+        )
+        (func (export "load_offset") (param $ptr i32) (result i32)
+            (i32.load offset=65535 (local.get $ptr))
+        )
+        (func (export "load8") (param $ptr i32) (result i32)
+            (i32.load8_u (local.get $ptr))
+        )
+        (func (export "store") (param $ptr i32) (param $value i64)
+            (i64.store (local.get $ptr) (local.get $value))
+        )
+        (func (export "store8") (param $ptr i32)
+            (i32.store8 offset=1 (local.get $ptr) (i32.const 0xFF))
+        )
+    )
+"#;
+
+/// The instantiated module under test.
+struct Test {
+    store: Store<()>,
+    instance: Instance,
+}
+
+impl Test {
+    /// Instantiates the `wat` module using the given `config`.
+    fn with_config(config: &Config, wat: &str) -> Self {
+        let (store, instance) = instantiate(config, wat);
+        Self { store, instance }
+    }
+
+    /// Instantiates the module under test with guard page bounds checks enabled.
+    fn new(backend: EngineBackend) -> Self {
+        let mut config = backend_config(backend);
+        config.set_signal_bounds_checks(true);
+        Self::with_config(&config, WAT)
+    }
+
+    /// Returns the exported linear memory.
+    fn memory(&self) -> Memory {
+        self.instance.get_memory(&self.store, "memory").unwrap()
+    }
+
+    /// Calls the exported function `name` with `params`.
+    fn call<Params, Results>(
+        &mut self,
+        name: &str,
+        params: Params,
+    ) -> Result<Results, Option<TrapCode>>
+    where
+        Params: wasmi::WasmParams,
+        Results: wasmi::WasmResults,
+    {
+        self.instance
+            .get_typed_func::<Params, Results>(&self.store, name)
+            .unwrap()
+            .call(&mut self.store, params)
+            .map_err(|trap: Trap| trap.trap_code())
+    }
+}
+
+/// Out of bounds trap of the tested linear memory accesses.
+const OUT_OF_BOUNDS: Option<TrapCode> = Some(TrapCode::MemoryOutOfBounds);
+
+fn assert_out_of_bounds_traps(backend: EngineBackend) {
+    let mut test = Test::new(backend);
+    test.call::<_, ()>("store", (65528, 42_i64)).unwrap();
+    assert_eq!(test.call::<_, i64>("load", 65528), Ok(42));
+    for ptr in [65529, 65536, i32::MAX, -1] {
+        assert_eq!(test.call::<_, i64>("load", ptr), Err(OUT_OF_BOUNDS));
+        assert_eq!(
+            test.call::<_, ()>("store", (ptr, 0_i64)),
+            Err(OUT_OF_BOUNDS)
+        );
+    }
+    for ptr in [1, 65536, -1] {
+        assert_eq!(test.call::<_, i32>("load_offset", ptr), Err(OUT_OF_BOUNDS));
+    }
+    assert_eq!(test.call::<_, ()>("store8", 65520), Ok(()));
+    assert_eq!(test.call::<_, i32>("load8", 65521), Ok(0xFF));
+    assert_eq!(test.call::<_, ()>("store8", 65535), Err(OUT_OF_BOUNDS));
+    assert_eq!(test.call::<_, i32>("load8", 65536), Err(OUT_OF_BOUNDS));
+    // Out of bounds stores must not have modified the linear memory.
+    assert_eq!(test.call::<_, i64>("load", 65528), Ok(42));
+}
+
+#[test]
+fn out_of_bounds_traps() {
+    for backend in BACKENDS {
+        assert_out_of_bounds_traps(backend)
+    }
+}
+
+#[test]
+fn effective_mode() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    assert!(!config.uses_signal_bounds_checks());
+    config.set_signal_bounds_checks(true);
+    assert_eq!(
+        config.uses_signal_bounds_checks(),
+        cfg!(all(target_os = "linux", target_arch = "x86_64"))
+    );
+    config.set_engine_backend(EngineBackend::StackMachine);
+    assert!(!config.uses_signal_bounds_checks());
+}
+
+#[test]
+fn accesses_are_unchecked() {
+    let mut config = backend_config(EngineBackend::RegisterMachine);
+    config.set_signal_bounds_checks(true);
+    config.profile_instructions(true);
+    let mut test = Test::with_config(&config, WAT);
+    test.call::<_, ()>("store", (8, 1_i64)).unwrap();
+    assert_eq!(test.call::<_, i64>("load", 8), Ok(1));
+    assert_eq!(test.call::<_, i64>("load", 65536), Err(OUT_OF_BOUNDS));
+    let profile = test.store.engine().take_instruction_profile().unwrap();
+    let (unchecked, checked) = match config.uses_signal_bounds_checks() {
+        true => (1, 0),
+        false => (0, 1),
+    };
+    assert_eq!(profile.count("I64StoreUnchecked"), unchecked);
+    assert_eq!(profile.count("I64LoadUnchecked"), 2 * unchecked);
+    assert_eq!(profile.count("I64StoreOffset16"), checked);
+    assert_eq!(profile.count("I64LoadOffset16"), 2 * checked);
+}
+
+#[test]
+fn grow_and_restore() {
+    let mut test = Test::new(EngineBackend::RegisterMachine);
+    let memory = test.memory();
+    let snapshot = memory.snapshot(&test.store);
+    memory.grow(&mut test.store, Pages::from(1)).unwrap();
+    test.call::<_, ()>("store", (131064, 42_i64)).unwrap();
+    assert_eq!(test.call::<_, i64>("load", 131064), Ok(42));
+    assert_eq!(test.call::<_, i64>("load", 131065), Err(OUT_OF_BOUNDS));
+    memory.restore(&mut test.store, &snapshot).unwrap();
+    assert_eq!(test.call::<_, i64>("load", 65536), Err(OUT_OF_BOUNDS));
+    assert_eq!(
+        test.call::<_, ()>("store", (131064, 0_i64)),
+        Err(OUT_OF_BOUNDS)
+    );
+    // Bytes of regrown pages are zeroed.
+    memory.grow(&mut test.store, Pages::from(1)).unwrap();
+    assert_eq!(test.call::<_, i64>("load", 131064), Ok(0));
+}
+
+/// All Wasm loads and stores with dynamic addresses and their tested static offsets.
+const ACCESSES: &str = r#"
+    (module
+        (memory 1)
+        (func (export "i32.load") (param i32) (result i64) (i64.extend_i32_u (i32.load offset=3 (local.get 0))))
+        (func (export "i64.load") (param i32) (result i64) (i64.load offset=3 (local.get 0)))
+        (func (export "f32.load") (param i32) (result i64) (i64.extend_i32_u (i32.reinterpret_f32 (f32.load offset=3 (local.get 0)))))
+        (func (export "f64.load") (param i32) (result i64) (i64.reinterpret_f64 (f64.load offset=3 (local.get 0))))
+        (func (export "i32.load8_s") (param i32) (result i64) (i64.extend_i32_u (i32.load8_s offset=3 (local.get 0))))
+        (func (export "i32.load8_u") (param i32) (result i64) (i64.extend_i32_u (i32.load8_u offset=3 (local.get 0))))
+        (func (export "i32.load16_s") (param i32) (result i64) (i64.extend_i32_u (i32.load16_s offset=3 (local.get 0))))
+        (func (export "i32.load16_u") (param i32) (result i64) (i64.extend_i32_u (i32.load16_u offset=3 (local.get 0))))
+        (func (export "i64.load8_s") (param i32) (result i64) (i64.load8_s offset=3 (local.get 0)))
+        (func (export "i64.load8_u") (param i32) (result i64) (i64.load8_u offset=3 (local.get 0)))
+        (func (export "i64.load16_s") (param i32) (result i64) (i64.load16_s offset=3 (local.get 0)))
+        (func (export "i64.load16_u") (param i32) (result i64) (i64.load16_u offset=3 (local.get 0)))
+        (func (export "i64.load32_s") (param i32) (result i64) (i64.load32_s offset=3 (local.get 0)))
+        (func (export "i64.load32_u") (param i32) (result i64) (i64.load32_u offset=3 (local.get 0)))
+        (func (export "i32.store") (param i32) (result i64)
+            (i32.store offset=3 (local.get 0) (i32.const 0x8182_8384)) (i64.const 0))
+        (func (export "i64.store") (param i32) (result i64)
+            (i64.store offset=3 (local.get 0) (i64.const 0x8182_8384_8586_8788)) (i64.const 0))
+        (func (export "f32.store") (param i32) (result i64)
+            (f32.store offset=3 (local.get 0) (f32.const -1.5)) (i64.const 0))
+        (func (export "f64.store") (param i32) (result i64)
+            (f64.store offset=3 (local.get 0) (f64.const -1.5)) (i64.const 0))
+        (func (export "i32.store8") (param i32) (result i64)
+            (i32.store8 offset=3 (local.get 0) (i32.const 0x8182_8384)) (i64.const 0))
+        (func (export "i32.store16") (param i32) (result i64)
+            (i32.store16 offset=3 (local.get 0) (i32.const 0x8182_8384)) (i64.const 0))
+        (func (export "i64.store8") (param i32) (result i64)
+            (i64.store8 offset=3 (local.get 0) (i64.const 0x8182_8384_8586_8788)) (i64.const 0))
+        (func (export "i64.store16") (param i32) (result i64)
+            (i64.store16 offset=3 (local.get 0) (i64.const 0x8182_8384_8586_8788)) (i64.const 0))
+        (func (export "i64.store32") (param i32) (result i64)
+            (i64.store32 offset=3 (local.get 0) (i64.const 0x8182_8384_8586_8788)) (i64.const 0))
+    )
+"#;
+
+#[test]
+fn all_accesses_match_explicit_bounds_checks() {
+    let test = |signal_bounds_checks: bool| {
+        let mut config = backend_config(EngineBackend::RegisterMachine);
+        config.set_signal_bounds_checks(signal_bounds_checks);
+        Test::with_config(&config, ACCESSES)
+    };
+    let mut found = test(true);
+    let mut expected = test(false);
+    let names: Vec<String> = found
+        .instance
+        .exports(&found.store)
+        .map(|export| export.name().into())
+        .collect();
+    // Stores are tested first so that loads observe the stored values.
+    let (stores, loads): (Vec<_>, Vec<_>) = names.iter().partition(|name| name.contains("store"));
+    for name in stores.into_iter().chain(loads) {
+        for ptr in (65512..65536).chain([0, 65536, i32::MAX as u32, u32::MAX - 2, u32::MAX]) {
+            let ptr = ptr as i32;
+            assert_eq!(
+                found.call::<_, i64>(name, ptr),
+                expected.call::<_, i64>(name, ptr),
+                "{name} at {ptr}"
+            );
+        }
+    }
+}
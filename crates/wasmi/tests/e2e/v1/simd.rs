@@ -0,0 +1,293 @@
+//! Tests for the `simd` operators supported if enabled via [`Config::wasm_simd`].
+
+use super::{backend_config, BACKENDS};
+use wasmi::{
+    core::{TrapCode, F32},
+    Engine,
+    EngineBackend,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The Wasm module under test.
+///
+/// - `i32x4.splat` stores four copies of `$value` at `$ptr`.
+/// - `f32x4.splat` stores four copies of `$value` at `$ptr`.
+/// - `copy` loads the `v128` value at `$src` and stores it at `$dst`.
+/// - the `min` and `max` operators store the result for the `v128` values at `$lhs` and `$rhs` at `$dst`.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (func (export "i32x4.splat") (param $ptr i32) (param $value i32)
+            (v128.store (local.get $ptr) (i32x4.splat (local.get $value)))
+        )
+        (func (export "f32x4.splat") (param $ptr i32) (param $value f32)
+            (v128.store (local.get $ptr) (f32x4.splat (local.get $value)))
+        )
+        (func (export "copy") (param $src i32) (param $dst i32)
+            (v128.store offset=1 (local.get $dst) (v128.load offset=1 (local.get $src)))
+        )
+        (func (export "i32x4.min_s") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (i32x4.min_s (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+        (func (export "i32x4.min_u") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (i32x4.min_u (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+        (func (export "i32x4.max_s") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (i32x4.max_s (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+        (func (export "i32x4.max_u") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (i32x4.max_u (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+        (func (export "f32x4.min") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (f32x4.min (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+        (func (export "f32x4.max") (param $dst i32) (param $lhs i32) (param $rhs i32)
+            (v128.store (local.get $dst) (f32x4.max (v128.load (local.get $lhs)) (v128.load (local.get $rhs))))
+        )
+    )
+"#;
+
+/// Compiles the `wat` source with the `simd` proposal set to `simd` using the given engine `backend`.
+fn compile(backend: EngineBackend, simd: bool, wat: &str) -> Result<Module, Error> {
+    let mut config = backend_config(backend);
+    config.wasm_simd(simd);
+    // Checks that `v128` values occupy two entries of the emulated value stack.
+    config.set_strict_stack_height_checks(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+/// Instantiates the module under test using the [`EngineBackend::RegisterMachine`].
+fn setup() -> (Store<()>, Instance) {
+    let module = compile(EngineBackend::RegisterMachine, true, WAT).unwrap();
+    let mut store = Store::new(module.engine(), ());
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Reads the 18 bytes surrounding the `v128` value at `ptr` of the exported memory.
+fn read_v128_with_neighbors(store: &Store<()>, instance: &Instance, ptr: usize) -> [u8; 18] {
+    let mut bytes = [0x00_u8; 18];
+    instance
+        .get_memory(store, "mem")
+        .unwrap()
+        .read(store, ptr - 1, &mut bytes)
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn i32x4_splat_store_register_machine() {
+    let (mut store, instance) = setup();
+    let splat = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "i32x4.splat")
+        .unwrap();
+    splat.call(&mut store, (8, 0x1122_3344)).unwrap();
+    let lane = 0x1122_3344_u32.to_le_bytes();
+    let mut expected = [0x00_u8; 18];
+    for chunk in expected[1..17].chunks_exact_mut(4) {
+        chunk.copy_from_slice(&lane);
+    }
+    assert_eq!(read_v128_with_neighbors(&store, &instance, 8), expected);
+}
+
+#[test]
+fn f32x4_splat_store_register_machine() {
+    let (mut store, instance) = setup();
+    let splat = instance
+        .get_typed_func::<(i32, F32), ()>(&store, "f32x4.splat")
+        .unwrap();
+    splat.call(&mut store, (8, F32::from(-1.5))).unwrap();
+    let lane = (-1.5_f32).to_le_bytes();
+    let mut expected = [0x00_u8; 18];
+    for chunk in expected[1..17].chunks_exact_mut(4) {
+        chunk.copy_from_slice(&lane);
+    }
+    assert_eq!(read_v128_with_neighbors(&store, &instance, 8), expected);
+}
+
+#[test]
+fn load_store_register_machine() {
+    let (mut store, instance) = setup();
+    let value: [u8; 16] = core::array::from_fn(|i| i as u8 + 1);
+    instance
+        .get_memory(&store, "mem")
+        .unwrap()
+        .write(&mut store, 1, &value)
+        .unwrap();
+    let copy = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "copy")
+        .unwrap();
+    copy.call(&mut store, (0, 31)).unwrap();
+    let mut expected = [0x00_u8; 18];
+    expected[1..17].copy_from_slice(&value);
+    assert_eq!(read_v128_with_neighbors(&store, &instance, 32), expected);
+    // The last 16 bytes of the linear memory are still in bounds.
+    copy.call(&mut store, (0, 65536 - 17)).unwrap();
+    for (src, dst) in [(65536 - 16, 0), (0, 65536 - 16), (-1, 0), (0, -1)] {
+        let trap = copy.call(&mut store, (src, dst)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    }
+}
+
+/// The lanes of the `lhs` operand of the `min` and `max` tests.
+///
+/// The `rhs` operand rotates these lanes so that every pair of inputs is tested.
+const MIN_MAX_LANES: [f32; 4] = [f32::NAN, 0.0, -0.0, 1.0];
+
+/// Runs the exported `v128` `min` or `max` operator `op` for all pairs of [`MIN_MAX_LANES`].
+///
+/// Calls `check` with the `lhs`, `rhs` and result bits of every lane.
+fn run_min_max(op: &str, mut check: impl FnMut(u32, u32, u32)) {
+    let (mut store, instance) = setup();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, op)
+        .unwrap();
+    let lhs = MIN_MAX_LANES.map(f32::to_bits);
+    for rotation in 0..lhs.len() {
+        let mut rhs = lhs;
+        rhs.rotate_left(rotation);
+        for (ptr, lanes) in [(16, lhs), (32, rhs)] {
+            let bytes: Vec<u8> = lanes.iter().flat_map(|lane| lane.to_le_bytes()).collect();
+            memory.write(&mut store, ptr, &bytes).unwrap();
+        }
+        func.call(&mut store, (48, 16, 32)).unwrap();
+        let mut bytes = [0x00_u8; 16];
+        memory.read(&store, 48, &mut bytes).unwrap();
+        for (lane, result) in bytes.chunks_exact(4).enumerate() {
+            let result = u32::from_le_bytes(result.try_into().unwrap());
+            check(lhs[lane], rhs[lane], result);
+        }
+    }
+}
+
+/// Asserts that the `f32x4` operator `op` follows the semantics of the scalar `expected` operator per lane.
+///
+/// The Wasm operators propagate NaN inputs and order `-0.0` below `+0.0`.
+fn assert_f32x4_min_max(op: &str, expected: fn(f32, f32) -> f32) {
+    run_min_max(op, |lhs, rhs, result| {
+        let lhs = f32::from_bits(lhs);
+        let rhs = f32::from_bits(rhs);
+        let result = f32::from_bits(result);
+        if lhs.is_nan() || rhs.is_nan() {
+            assert!(result.is_nan(), "{op}({lhs:?}, {rhs:?}) = {result:?}");
+            return;
+        }
+        let expected = expected(lhs, rhs);
+        assert_eq!(
+            result.to_bits(),
+            expected.to_bits(),
+            "{op}({lhs:?}, {rhs:?}) = {result:?} but expected {expected:?}"
+        );
+    });
+}
+
+#[test]
+fn f32x4_min_register_machine() {
+    assert_f32x4_min_max("f32x4.min", |lhs, rhs| {
+        if lhs == rhs {
+            // Orders `-0.0` below `+0.0`.
+            f32::from_bits(lhs.to_bits() | rhs.to_bits())
+        } else {
+            lhs.min(rhs)
+        }
+    });
+}
+
+#[test]
+fn f32x4_max_register_machine() {
+    assert_f32x4_min_max("f32x4.max", |lhs, rhs| {
+        if lhs == rhs {
+            // Orders `-0.0` below `+0.0`.
+            f32::from_bits(lhs.to_bits() & rhs.to_bits())
+        } else {
+            lhs.max(rhs)
+        }
+    });
+}
+
+/// An exported `i32x4` operator name and the scalar semantics of each of its lanes.
+type I32x4Op = (&'static str, fn(u32, u32) -> u32);
+
+#[test]
+fn i32x4_min_max_register_machine() {
+    let ops: [I32x4Op; 4] = [
+        ("i32x4.min_s", |lhs, rhs| {
+            (lhs as i32).min(rhs as i32) as u32
+        }),
+        ("i32x4.min_u", u32::min),
+        ("i32x4.max_s", |lhs, rhs| {
+            (lhs as i32).max(rhs as i32) as u32
+        }),
+        ("i32x4.max_u", u32::max),
+    ];
+    for (op, expected) in ops {
+        run_min_max(op, |lhs, rhs, result| {
+            assert_eq!(result, expected(lhs, rhs), "{op}({lhs:#x}, {rhs:#x})");
+        });
+    }
+}
+
+#[test]
+fn unsupported_register_machine() {
+    for wat in [
+        r#"
+        (module
+            (func (param i32) (result i32)
+                (i32x4.extract_lane 0 (i32x4.add (i32x4.splat (local.get 0)) (i32x4.splat (local.get 0))))
+            )
+        )
+        "#,
+        r#"
+        (module
+            (func (param v128))
+        )
+        "#,
+        r#"
+        (module
+            (func (local v128))
+        )
+        "#,
+        r#"
+        (module
+            (global v128 (v128.const i64x2 0 0))
+        )
+        "#,
+        r#"
+        (module
+            (func (param i32)
+                (drop (block (result v128) (i32x4.splat (local.get 0))))
+            )
+        )
+        "#,
+    ] {
+        let error = compile(EngineBackend::RegisterMachine, true, wat).unwrap_err();
+        assert!(matches!(error, Error::Module(_)), "{error}");
+        assert!(error.to_string().contains("unsupported"), "{error}");
+    }
+}
+
+#[test]
+fn unsupported_stack_machine() {
+    let error = compile(EngineBackend::StackMachine, true, WAT).unwrap_err();
+    assert!(matches!(error, Error::Module(_)), "{error}");
+    assert!(error.to_string().contains("unsupported"), "{error}");
+}
+
+#[test]
+fn disabled() {
+    for backend in BACKENDS {
+        let error = compile(backend, false, WAT).unwrap_err();
+        assert!(matches!(error, Error::Module(_)), "{error}");
+    }
+}
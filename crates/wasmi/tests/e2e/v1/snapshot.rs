@@ -0,0 +1,103 @@
+//! Tests for snapshotting and restoring [`Memory`] and [`Table`] entities.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{errors::MemoryError, EngineBackend, Instance, Memory, MemoryType, Store, Table};
+
+/// The Wasm module under test.
+///
+/// The `mutate` export writes to and grows both the exported memory and table.
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1 4)
+        (table (export "table") 2 8 funcref)
+        (elem (i32.const 0) $one)
+        (elem declare func $two)
+        (func $one (result i32) (i32.const 1))
+        (func $two (result i32) (i32.const 2))
+        (func (export "mutate")
+            (i32.store (i32.const 0) (i32.add (i32.load (i32.const 0)) (i32.const 1)))
+            (drop (memory.grow (i32.const 1)))
+            (i32.store (i32.const 65540) (i32.const 42))
+            (table.set (i32.const 1) (ref.func $two))
+            (drop (table.grow (ref.func $one) (i32.const 3)))
+        )
+        (func (export "call") (param i32) (result i32)
+            (call_indirect (result i32) (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates the module under test and returns its exported [`Memory`] and [`Table`].
+fn setup(backend: EngineBackend) -> (Store<()>, Instance, Memory, Table) {
+    let (store, instance) = instantiate_backend(backend, WAT);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let table = instance.get_table(&store, "table").unwrap();
+    (store, instance, memory, table)
+}
+
+/// Calls the `mutate` export of the `instance`.
+fn mutate(store: &mut Store<()>, instance: &Instance) {
+    instance
+        .get_typed_func::<(), ()>(&*store, "mutate")
+        .unwrap()
+        .call(store, ())
+        .unwrap();
+}
+
+/// Returns the results of calling all `table` elements via `call_indirect`.
+///
+/// Null elements are represented by `None`.
+fn call_all(store: &mut Store<()>, instance: &Instance, table: &Table) -> Vec<Option<i32>> {
+    let call = instance
+        .get_typed_func::<u32, i32>(&*store, "call")
+        .unwrap();
+    (0..table.size(&*store))
+        .map(|index| call.call(&mut *store, index).ok())
+        .collect()
+}
+
+fn assert_snapshot_restore(backend: EngineBackend) {
+    let (mut store, instance, memory, table) = setup(backend);
+    memory.write(&mut store, 100, &[1, 2, 3, 4]).unwrap();
+    let memory_snapshot = memory.snapshot(&store);
+    let table_snapshot = table.snapshot(&store);
+    let calls_before = call_all(&mut store, &instance, &table);
+    assert_eq!(calls_before, [Some(1), None]);
+
+    mutate(&mut store, &instance);
+    assert_ne!(memory.data(&store), memory_snapshot.data());
+    assert_eq!(u32::from(memory.current_pages(&store)), 2);
+    assert_eq!(table.size(&store), 5);
+    let memory_mutated = memory.snapshot(&store);
+    let calls_mutated = call_all(&mut store, &instance, &table);
+
+    memory.restore(&mut store, &memory_snapshot).unwrap();
+    table.restore(&mut store, &table_snapshot).unwrap();
+    assert_eq!(memory.current_pages(&store), memory_snapshot.pages());
+    assert_eq!(memory.data(&store), memory_snapshot.data());
+    assert_eq!(table.size(&store), table_snapshot.size());
+    assert_eq!(call_all(&mut store, &instance, &table), calls_before);
+
+    // Replaying the mutation after restoring yields the same state again.
+    mutate(&mut store, &instance);
+    assert_eq!(memory.data(&store), memory_mutated.data());
+    assert_eq!(call_all(&mut store, &instance, &table), calls_mutated);
+}
+
+#[test]
+fn snapshot_restore() {
+    for backend in BACKENDS {
+        assert_snapshot_restore(backend)
+    }
+}
+
+#[test]
+fn restore_below_minimum_size_fails() {
+    let (mut store, _instance, memory, _table) = setup(EngineBackend::RegisterMachine);
+    let snapshot = memory.snapshot(&store);
+    let larger = Memory::new(&mut store, MemoryType::new(2, None).unwrap()).unwrap();
+    assert!(matches!(
+        larger.restore(&mut store, &snapshot),
+        Err(MemoryError::InvalidSnapshot)
+    ));
+}
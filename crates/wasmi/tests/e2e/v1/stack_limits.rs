@@ -0,0 +1,79 @@
+//! Tests for updating the stack limits of an existing [`Engine`] via [`Engine::set_stack_limits`].
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{core::TrapCode, EngineBackend, Instance, StackLimits, Store};
+
+/// The Wasm module under test.
+///
+/// The `rec` export recursively calls itself `n` times and returns `n`.
+const WAT: &str = r#"
+    (module
+        (func $rec (export "rec") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $rec (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// The recursion depth used by the tests.
+const DEPTH: i32 = 500;
+
+/// Calls the `rec` export of the `instance` with `n`.
+fn rec(store: &mut Store<()>, instance: &Instance, n: i32) -> Result<i32, TrapCode> {
+    instance
+        .get_typed_func::<i32, i32>(&*store, "rec")
+        .unwrap()
+        .call(store, n)
+        .map_err(|trap| trap.trap_code().expect("expected a trap code"))
+}
+
+fn assert_lowered_recursion_limit(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    assert_eq!(rec(&mut store, &instance, DEPTH), Ok(DEPTH));
+    let mut limits = StackLimits::default();
+    limits.maximum_recursion_depth = DEPTH as usize / 2;
+    store.engine().set_stack_limits(limits);
+    assert_eq!(
+        rec(&mut store, &instance, DEPTH),
+        Err(TrapCode::StackOverflow)
+    );
+    assert_eq!(rec(&mut store, &instance, DEPTH / 4), Ok(DEPTH / 4));
+    store.engine().set_stack_limits(StackLimits::default());
+    assert_eq!(rec(&mut store, &instance, DEPTH), Ok(DEPTH));
+}
+
+fn assert_lowered_value_stack_height(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    // Grows the cached stack of the engine beyond the lowered maximum below.
+    assert_eq!(rec(&mut store, &instance, DEPTH), Ok(DEPTH));
+    store
+        .engine()
+        .set_stack_limits(StackLimits::new(16, 64, 1024).unwrap());
+    assert_eq!(
+        rec(&mut store, &instance, DEPTH),
+        Err(TrapCode::StackOverflow)
+    );
+    store.engine().set_stack_limits(StackLimits::default());
+    assert_eq!(rec(&mut store, &instance, DEPTH), Ok(DEPTH));
+}
+
+#[test]
+fn lowered_recursion_limit() {
+    for backend in BACKENDS {
+        assert_lowered_recursion_limit(backend)
+    }
+}
+
+#[test]
+fn lowered_value_stack_height() {
+    for backend in BACKENDS {
+        assert_lowered_value_stack_height(backend)
+    }
+}
@@ -0,0 +1,179 @@
+//! Tests for executing functions one instruction at a time via [`Func::call_stepped`].
+
+use super::instantiate_backend;
+use wasmi::{
+    core::UntypedValue,
+    errors::FuncError,
+    EngineBackend,
+    Error,
+    Func,
+    StepOutcome,
+    Store,
+    Value,
+};
+
+/// Instantiates the `wat` module on the register-machine backend and returns its `"f"` export.
+fn setup(wat: &str) -> (Store<()>, Func) {
+    setup_with(EngineBackend::RegisterMachine, wat)
+}
+
+/// Instantiates the `wat` module on the engine `backend` and returns its `"f"` export.
+fn setup_with(backend: EngineBackend, wat: &str) -> (Store<()>, Func) {
+    let (store, instance) = instantiate_backend(backend, wat);
+    let func = instance.get_func(&store, "f").unwrap();
+    (store, func)
+}
+
+/// Converts the `registers` into `i32` values for easier comparison.
+fn i32_registers(registers: &[UntypedValue]) -> Vec<i32> {
+    registers.iter().copied().map(i32::from).collect()
+}
+
+#[test]
+fn step_arithmetic() {
+    let (mut store, func) = setup(
+        r#"
+        (module
+            (func (export "f") (param i32 i32) (result i32)
+                (local i32)
+                (local.set 2 (i32.add (local.get 0) (local.get 1)))
+                (i32.mul (local.get 2) (i32.const 3))
+            )
+        )
+        "#,
+    );
+    let mut call = func
+        .call_stepped(&mut store, &[Value::I32(4), Value::I32(5)])
+        .unwrap();
+    let mut results = [Value::I32(0)];
+    assert_eq!(call.call_depth(), 1);
+    assert_eq!(i32_registers(call.registers()), [4, 5, 0, 0]);
+    // Step 1: `local.set 2 (i32.add ...)`
+    assert_eq!(
+        call.step(&mut store, &mut results).unwrap(),
+        StepOutcome::Running
+    );
+    assert_eq!(i32_registers(call.registers()), [4, 5, 9, 0]);
+    // Step 2: `i32.mul (local.get 2) (i32.const 3)`
+    assert_eq!(
+        call.step(&mut store, &mut results).unwrap(),
+        StepOutcome::Running
+    );
+    assert_eq!(i32_registers(call.registers()), [4, 5, 9, 27]);
+    // Step 3: return to the host.
+    assert_eq!(
+        call.step(&mut store, &mut results).unwrap(),
+        StepOutcome::Finished
+    );
+    assert!(call.is_finished());
+    assert_eq!(call.call_depth(), 0);
+    assert!(call.registers().is_empty());
+    assert_eq!(results[0].i32(), Some(27));
+}
+
+#[test]
+fn step_into_call() {
+    let (mut store, func) = setup(
+        r#"
+        (module
+            (func $double (param i32) (result i32)
+                (i32.add (local.get 0) (local.get 0))
+            )
+            (func (export "f") (param i32) (result i32)
+                (call $double (local.get 0))
+            )
+        )
+        "#,
+    );
+    let mut call = func.call_stepped(&mut store, &[Value::I32(21)]).unwrap();
+    let mut results = [Value::I32(0)];
+    let mut max_depth = call.call_depth();
+    let mut steps = 0;
+    while call.step(&mut store, &mut results).unwrap() == StepOutcome::Running {
+        max_depth = max_depth.max(call.call_depth());
+        steps += 1;
+    }
+    assert_eq!(max_depth, 2);
+    assert!(steps >= 3, "expected one step per instruction: {steps}");
+    assert_eq!(results[0].i32(), Some(42));
+}
+
+#[test]
+fn step_params() {
+    let (mut store, func) = setup(
+        r#"
+        (module
+            (func $callee (param i32 i64) (result i32)
+                (i32.add (local.get 0) (i32.wrap_i64 (local.get 1)))
+            )
+            (func (export "f") (param i32) (result i32)
+                (call $callee (i32.const 7) (i64.const 11))
+            )
+        )
+        "#,
+    );
+    let mut call = func.call_stepped(&mut store, &[Value::I32(4)]).unwrap();
+    let mut results = [Value::I32(0)];
+    assert_eq!(call.param(&store, 0), Some(UntypedValue::from(4_i32)));
+    assert_eq!(call.param(&store, 1), None);
+    while call.call_depth() < 2 {
+        call.step(&mut store, &mut results).unwrap();
+    }
+    assert_eq!(call.param(&store, 0), Some(UntypedValue::from(7_i32)));
+    assert_eq!(call.param(&store, 1), Some(UntypedValue::from(11_i64)));
+    // Out of bounds for the parameters even though the register exists.
+    assert!(call.registers().len() > 2);
+    assert_eq!(call.param(&store, 2), None);
+    while call.step(&mut store, &mut results).unwrap() == StepOutcome::Running {}
+    assert_eq!(results[0].i32(), Some(18));
+    assert_eq!(call.param(&store, 0), None);
+}
+
+#[test]
+fn step_trap() {
+    let (mut store, func) = setup(
+        r#"
+        (module
+            (func (export "f")
+                (unreachable)
+            )
+        )
+        "#,
+    );
+    let mut call = func.call_stepped(&mut store, &[]).unwrap();
+    assert!(call.step(&mut store, &mut []).is_err());
+}
+
+#[test]
+#[should_panic(expected = "does not belong to store")]
+fn step_foreign_store() {
+    let (mut store, func) = setup(
+        r#"
+        (module
+            (func (export "f") (result i32)
+                (i32.const 1)
+            )
+        )
+        "#,
+    );
+    let mut call = func.call_stepped(&mut store, &[]).unwrap();
+    let mut other = Store::new(store.engine(), ());
+    let _ = call.step(&mut other, &mut [Value::I32(0)]);
+}
+
+#[test]
+fn step_unsupported_backend() {
+    let (mut store, func) = setup_with(
+        EngineBackend::StackMachine,
+        r#"
+        (module
+            (func (export "f"))
+        )
+        "#,
+    );
+    let error = func.call_stepped(&mut store, &[]).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Func(FuncError::UnsupportedSteppedCall)
+    ));
+}
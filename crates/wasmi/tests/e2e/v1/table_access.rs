@@ -0,0 +1,102 @@
+//! Tests for the semantics of the Wasm `table.get` and `table.set` instructions.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{core::TrapCode, EngineBackend, ExternRef, Store};
+
+/// The Wasm module under test.
+///
+/// The `$refs` table is not the default table so that accesses must resolve the table index.
+/// The `_imm` exports access the table with a constant out of bounds index.
+const WAT: &str = r#"
+    (module
+        (table $funcs 1 funcref)
+        (table $refs 3 externref)
+        (func (export "get") (param i32) (result externref)
+            (table.get $refs (local.get 0))
+        )
+        (func (export "set") (param i32 externref)
+            (table.set $refs (local.get 0) (local.get 1))
+        )
+        (func (export "get_imm") (result externref)
+            (table.get $refs (i32.const 3))
+        )
+        (func (export "set_imm") (param externref)
+            (table.set $refs (i32.const 3) (local.get 0))
+        )
+    )
+"#;
+
+/// Returns the data of the `externref` or `None` if it is null.
+fn externref_data(store: &Store<()>, externref: ExternRef) -> Option<u32> {
+    let data = externref.data(store)?;
+    Some(*data.downcast_ref::<u32>().unwrap())
+}
+
+fn assert_table_access(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let get = instance
+        .get_typed_func::<u32, ExternRef>(&store, "get")
+        .unwrap();
+    let set = instance
+        .get_typed_func::<(u32, ExternRef), ()>(&store, "set")
+        .unwrap();
+    for index in 0..3 {
+        let result = get.call(&mut store, index).unwrap();
+        assert!(result.is_null());
+    }
+    for index in 0..3 {
+        let value = ExternRef::new::<u32>(&mut store, index + 10);
+        set.call(&mut store, (index, value)).unwrap();
+    }
+    for index in 0..3 {
+        let result = get.call(&mut store, index).unwrap();
+        assert_eq!(externref_data(&store, result), Some(index + 10));
+    }
+    set.call(&mut store, (1, ExternRef::null())).unwrap();
+    assert!(get.call(&mut store, 1).unwrap().is_null());
+}
+
+fn assert_table_access_out_of_bounds(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let get = instance
+        .get_typed_func::<u32, ExternRef>(&store, "get")
+        .unwrap();
+    let set = instance
+        .get_typed_func::<(u32, ExternRef), ()>(&store, "set")
+        .unwrap();
+    let get_imm = instance
+        .get_typed_func::<(), ExternRef>(&store, "get_imm")
+        .unwrap();
+    let set_imm = instance
+        .get_typed_func::<ExternRef, ()>(&store, "set_imm")
+        .unwrap();
+    let value = ExternRef::new::<u32>(&mut store, 42);
+    for index in [3, 4, u32::MAX] {
+        let error = get.call(&mut store, index).unwrap_err();
+        assert_eq!(error.trap_code(), Some(TrapCode::TableOutOfBounds));
+        let error = set.call(&mut store, (index, value)).unwrap_err();
+        assert_eq!(error.trap_code(), Some(TrapCode::TableOutOfBounds));
+    }
+    let error = get_imm.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.trap_code(), Some(TrapCode::TableOutOfBounds));
+    let error = set_imm.call(&mut store, value).unwrap_err();
+    assert_eq!(error.trap_code(), Some(TrapCode::TableOutOfBounds));
+    // Out of bounds writes must not modify the table.
+    for index in 0..3 {
+        assert!(get.call(&mut store, index).unwrap().is_null());
+    }
+}
+
+#[test]
+fn table_access() {
+    for backend in BACKENDS {
+        assert_table_access(backend)
+    }
+}
+
+#[test]
+fn table_access_out_of_bounds() {
+    for backend in BACKENDS {
+        assert_table_access_out_of_bounds(backend)
+    }
+}
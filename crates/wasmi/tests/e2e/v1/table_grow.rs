@@ -0,0 +1,187 @@
+//! Tests for the semantics of the Wasm `table.grow` instruction.
+
+use super::{backend_config, BACKENDS};
+use wasmi::{
+    Engine,
+    EngineBackend,
+    Linker,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    TypedFunc,
+};
+
+/// The exported functions of the module under test.
+struct Test {
+    store: Store<StoreLimits>,
+    /// Grows the table by the given delta initializing new elements to `$f`.
+    grow: TypedFunc<i32, i32>,
+    /// Returns the size of the table.
+    size: TypedFunc<(), i32>,
+    /// Returns `1` if the table element at the given index is `null`.
+    is_null: TypedFunc<i32, i32>,
+    /// Calls the function at the given index of the table.
+    call: TypedFunc<i32, i32>,
+    /// Grows the table by the constant delta of zero.
+    grow_by_0: TypedFunc<(), i32>,
+    /// Grows the table by the constant delta of five which exceeds its maximum.
+    grow_by_5: TypedFunc<(), i32>,
+}
+
+impl Test {
+    /// Instantiates the module under test whose resources are limited by `limits`.
+    fn new(backend: EngineBackend, limits: StoreLimits) -> Self {
+        let wat = r#"
+            (module
+                (type $t (func (result i32)))
+                (table $table 1 4 funcref)
+                (func $f (type $t)
+                    (i32.const 42)
+                )
+                (elem declare func $f)
+                (func (export "grow") (param $delta i32) (result i32)
+                    (table.grow $table (ref.func $f) (local.get $delta))
+                )
+                (func (export "size") (result i32)
+                    (table.size $table)
+                )
+                (func (export "is_null") (param $index i32) (result i32)
+                    (ref.is_null (table.get $table (local.get $index)))
+                )
+                (func (export "call") (param $index i32) (result i32)
+                    (call_indirect $table (type $t) (local.get $index))
+                )
+                (func (export "grow_by_0") (result i32)
+                    (table.grow $table (ref.null func) (i32.const 0))
+                )
+                (func (export "grow_by_5") (result i32)
+                    (table.grow $table (ref.null func) (i32.const 5))
+                )
+            )
+        "#;
+        let engine = Engine::new(&backend_config(backend));
+        let wasm = wat::parse_str(wat).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, limits);
+        store.limiter(|limits| limits);
+        let instance = <Linker<StoreLimits>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let grow = instance.get_typed_func(&store, "grow").unwrap();
+        let size = instance.get_typed_func(&store, "size").unwrap();
+        let is_null = instance.get_typed_func(&store, "is_null").unwrap();
+        let call = instance.get_typed_func(&store, "call").unwrap();
+        let grow_by_0 = instance.get_typed_func(&store, "grow_by_0").unwrap();
+        let grow_by_5 = instance.get_typed_func(&store, "grow_by_5").unwrap();
+        Self {
+            store,
+            grow,
+            size,
+            is_null,
+            call,
+            grow_by_0,
+            grow_by_5,
+        }
+    }
+
+    fn grow(&mut self, delta: i32) -> i32 {
+        self.grow.call(&mut self.store, delta).unwrap()
+    }
+
+    fn size(&mut self) -> i32 {
+        self.size.call(&mut self.store, ()).unwrap()
+    }
+
+    fn is_null(&mut self, index: i32) -> bool {
+        self.is_null.call(&mut self.store, index).unwrap() == 1
+    }
+}
+
+fn assert_grow_initializes_elements(backend: EngineBackend) {
+    let mut test = Test::new(backend, StoreLimits::default());
+    assert_eq!(test.grow(2), 1);
+    assert_eq!(test.size(), 3);
+    // The initial element is still `null` whereas the new ones refer to `$f`.
+    assert!(test.is_null(0));
+    for index in [1, 2] {
+        assert!(!test.is_null(index));
+        assert_eq!(test.call.call(&mut test.store, index).unwrap(), 42);
+    }
+    // Growing by zero returns the current size and does not change the table.
+    assert_eq!(test.grow(0), 3);
+    assert_eq!(test.size(), 3);
+}
+
+fn assert_grow_beyond_maximum_fails(backend: EngineBackend) {
+    let mut test = Test::new(backend, StoreLimits::default());
+    // The module declares a maximum of 4 elements.
+    assert_eq!(test.grow(4), -1);
+    assert_eq!(test.size(), 1);
+    assert_eq!(test.grow(3), 1);
+    assert_eq!(test.size(), 4);
+    assert_eq!(test.grow(1), -1);
+    assert_eq!(test.size(), 4);
+}
+
+fn assert_grow_at_maximum(backend: EngineBackend) {
+    let mut test = Test::new(backend, StoreLimits::default());
+    assert_eq!(test.grow_by_5.call(&mut test.store, ()).unwrap(), -1);
+    assert_eq!(test.grow_by_0.call(&mut test.store, ()).unwrap(), 1);
+    assert_eq!(test.grow(3), 1);
+    // Growing by zero at the maximum returns the current size.
+    assert_eq!(test.grow(0), 4);
+    assert_eq!(test.grow_by_0.call(&mut test.store, ()).unwrap(), 4);
+    // Failing to grow leaves the size and all elements unchanged.
+    assert_eq!(test.grow(1), -1);
+    assert_eq!(test.grow(-1), -1);
+    assert_eq!(test.grow_by_5.call(&mut test.store, ()).unwrap(), -1);
+    assert_eq!(test.size(), 4);
+    assert!(test.is_null(0));
+    for index in [1, 2, 3] {
+        assert_eq!(test.call.call(&mut test.store, index).unwrap(), 42);
+    }
+}
+
+fn assert_grow_beyond_limiter_fails(backend: EngineBackend) {
+    let limits = StoreLimitsBuilder::new().table_elements(2).build();
+    let mut test = Test::new(backend, limits);
+    // The embedder limits tables to 2 elements which is below the declared maximum.
+    assert_eq!(test.grow(2), -1);
+    assert_eq!(test.size(), 1);
+    assert_eq!(test.grow(1), 1);
+    assert_eq!(test.size(), 2);
+    assert!(!test.is_null(1));
+    assert_eq!(test.grow(1), -1);
+    assert_eq!(test.size(), 2);
+}
+
+#[test]
+fn grow_initializes_elements() {
+    for backend in BACKENDS {
+        assert_grow_initializes_elements(backend)
+    }
+}
+
+#[test]
+fn grow_beyond_maximum_fails() {
+    for backend in BACKENDS {
+        assert_grow_beyond_maximum_fails(backend)
+    }
+}
+
+#[test]
+fn grow_at_maximum() {
+    for backend in BACKENDS {
+        assert_grow_at_maximum(backend)
+    }
+}
+
+#[test]
+fn grow_beyond_limiter_fails() {
+    for backend in BACKENDS {
+        assert_grow_beyond_limiter_fails(backend)
+    }
+}
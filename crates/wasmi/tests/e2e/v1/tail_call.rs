@@ -0,0 +1,113 @@
+//! Tests that tail calls of the [`tail-call`] Wasm proposal reuse the caller's call frame.
+//!
+//! [`tail-call`]: https://github.com/WebAssembly/tail-calls
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{EngineBackend, Instance, StackLimits, Store};
+
+/// The Wasm module under test.
+///
+/// - `countdown` tail calls itself until its parameter is zero.
+/// - `even` and `odd` tail call each other until their parameter is zero.
+///   Since `odd` uses more locals than `even` their call frames differ in size.
+/// - `countdown_indirect` tail calls itself indirectly through a table.
+const WAT: &str = r#"
+    (module
+        (type $countdown (func (param i64) (result i64)))
+        (table funcref (elem $countdown_indirect))
+        (func $countdown (export "countdown") (param $n i64) (result i64)
+            (if (result i64) (i64.eqz (local.get $n))
+                (then (i64.const 0))
+                (else (return_call $countdown (i64.sub (local.get $n) (i64.const 1))))
+            )
+        )
+        (func $even (export "even") (param $n i64) (result i32)
+            (if (result i32) (i64.eqz (local.get $n))
+                (then (i32.const 1))
+                (else (return_call $odd (i64.sub (local.get $n) (i64.const 1))))
+            )
+        )
+        (func $odd (param $n i64) (result i32)
+            (local $a i64) (local $b i64) (local $c i64) (local $d i64)
+            (local.set $a (i64.sub (local.get $n) (i64.const 1)))
+            (local.set $b (i64.add (local.get $a) (local.get $a)))
+            (local.set $c (i64.mul (local.get $b) (local.get $n)))
+            (local.set $d (i64.xor (local.get $c) (local.get $b)))
+            (if (result i32) (i64.eqz (local.get $n))
+                (then (i32.const 0))
+                (else (return_call $even (local.get $a)))
+            )
+        )
+        (func $countdown_indirect (export "countdown_indirect") (param $n i64) (result i64)
+            (if (result i64) (i64.eqz (local.get $n))
+                (then (i64.const 0))
+                (else
+                    (return_call_indirect (type $countdown)
+                        (i64.sub (local.get $n) (i64.const 1))
+                        (i32.const 0)
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// The number of tail calls performed by the tests.
+const DEPTH: i64 = 100_000;
+
+/// Instantiates the module under test with tail calls enabled.
+///
+/// The maximum recursion depth is far smaller than [`DEPTH`] so that
+/// the tests fail if tail calls push new call frames.
+fn setup(backend: EngineBackend) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.wasm_tail_call(true);
+    let mut limits = StackLimits::default();
+    limits.maximum_recursion_depth = 4;
+    config.set_stack_limits(limits);
+    instantiate(&config, WAT)
+}
+
+fn assert_tail_call_countdown(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    let countdown = instance
+        .get_typed_func::<i64, i64>(&store, "countdown")
+        .unwrap();
+    assert_eq!(countdown.call(&mut store, DEPTH).unwrap(), 0);
+}
+
+fn assert_tail_call_resizes_frame(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    let even = instance.get_typed_func::<i64, i32>(&store, "even").unwrap();
+    assert_eq!(even.call(&mut store, DEPTH).unwrap(), 1);
+    assert_eq!(even.call(&mut store, DEPTH + 1).unwrap(), 0);
+}
+
+fn assert_tail_call_indirect_countdown(backend: EngineBackend) {
+    let (mut store, instance) = setup(backend);
+    let countdown = instance
+        .get_typed_func::<i64, i64>(&store, "countdown_indirect")
+        .unwrap();
+    assert_eq!(countdown.call(&mut store, DEPTH).unwrap(), 0);
+}
+
+#[test]
+fn tail_call_countdown() {
+    for backend in BACKENDS {
+        assert_tail_call_countdown(backend)
+    }
+}
+
+#[test]
+fn tail_call_resizes_frame() {
+    for backend in BACKENDS {
+        assert_tail_call_resizes_frame(backend)
+    }
+}
+
+#[test]
+fn tail_call_indirect_countdown() {
+    for backend in BACKENDS {
+        assert_tail_call_indirect_countdown(backend)
+    }
+}
@@ -0,0 +1,155 @@
+//! Tests for modules using the Wasm `threads` proposal.
+//!
+//! `wasmi` supports shared memories and the `memory.atomic.notify` and
+//! `memory.atomic.wait` operators with single threaded semantics.
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{core::TrapCode, Engine, EngineBackend, Error, Instance, Module, Store};
+
+/// Compiles the `wat` source using the given engine `backend`.
+///
+/// The `threads` Wasm proposal is enabled if `threads` is `true`.
+fn compile(backend: EngineBackend, threads: bool, wat: &str) -> Result<(Engine, Module), Error> {
+    let mut config = backend_config(backend);
+    config.wasm_threads(threads);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..])?;
+    Ok((engine, module))
+}
+
+/// Instantiates the `wat` source with the `threads` Wasm proposal enabled.
+fn instantiate_threads(backend: EngineBackend, wat: &str) -> (Store<()>, Instance) {
+    let mut config = backend_config(backend);
+    config.wasm_threads(true);
+    instantiate(&config, wat)
+}
+
+/// A module with a shared memory that stores `42` at address `8`.
+const SHARED: &str = r#"
+    (module
+        (memory 1 1 shared)
+        (data (i32.const 8) "\2a")
+        (func (export "notify") (param i32 i32) (result i32)
+            (memory.atomic.notify (local.get 0) (local.get 1))
+        )
+        (func (export "wait32") (param i32 i32 i64) (result i32)
+            (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "wait64") (param i32 i64 i64) (result i32)
+            (memory.atomic.wait64 (local.get 0) (local.get 1) (local.get 2))
+        )
+    )
+"#;
+
+#[test]
+fn atomic_operators_are_rejected_without_threads() {
+    for backend in BACKENDS {
+        let error = compile(backend, false, SHARED).unwrap_err();
+        assert!(matches!(error, Error::Module(_)), "{error}");
+    }
+}
+
+#[test]
+fn notify_has_no_waiters() {
+    for backend in BACKENDS {
+        let (mut store, instance) = instantiate_threads(backend, SHARED);
+        let notify = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "notify")
+            .unwrap();
+        assert_eq!(notify.call(&mut store, (8, 1)).unwrap(), 0);
+        assert_eq!(notify.call(&mut store, (65532, -1)).unwrap(), 0);
+    }
+}
+
+#[test]
+fn notify_traps() {
+    for backend in BACKENDS {
+        let (mut store, instance) = instantiate_threads(backend, SHARED);
+        let notify = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "notify")
+            .unwrap();
+        let trap = notify.call(&mut store, (9, 1)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::UnalignedAtomic));
+        let trap = notify.call(&mut store, (65536, 1)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    }
+}
+
+#[test]
+fn wait_returns_status() {
+    for backend in BACKENDS {
+        let (mut store, instance) = instantiate_threads(backend, SHARED);
+        let wait32 = instance
+            .get_typed_func::<(i32, i32, i64), i32>(&store, "wait32")
+            .unwrap();
+        let wait64 = instance
+            .get_typed_func::<(i32, i64, i64), i32>(&store, "wait64")
+            .unwrap();
+        // Case: the loaded value differs from the expected value: "not-equal"
+        assert_eq!(wait32.call(&mut store, (8, 0, -1)).unwrap(), 1);
+        assert_eq!(wait64.call(&mut store, (8, 0, -1)).unwrap(), 1);
+        // Case: there is nobody to notify the waiter: "timed-out"
+        assert_eq!(wait32.call(&mut store, (8, 42, 0)).unwrap(), 2);
+        assert_eq!(wait64.call(&mut store, (8, 42, 1_000)).unwrap(), 2);
+    }
+}
+
+#[test]
+fn wait_traps() {
+    for backend in BACKENDS {
+        let (mut store, instance) = instantiate_threads(backend, SHARED);
+        let wait32 = instance
+            .get_typed_func::<(i32, i32, i64), i32>(&store, "wait32")
+            .unwrap();
+        let wait64 = instance
+            .get_typed_func::<(i32, i64, i64), i32>(&store, "wait64")
+            .unwrap();
+        let trap = wait32.call(&mut store, (10, 0, 0)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::UnalignedAtomic));
+        let trap = wait64.call(&mut store, (12, 0, 0)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::UnalignedAtomic));
+        let trap = wait64.call(&mut store, (65536, 0, 0)).unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    }
+}
+
+#[test]
+fn wait_traps_on_non_shared_memory() {
+    let wat = r#"
+        (module
+            (memory 1)
+            (func (export "wait32") (param i32 i32 i64) (result i32)
+                (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2))
+            )
+        )
+    "#;
+    for backend in BACKENDS {
+        let (mut store, instance) = instantiate_threads(backend, wat);
+        let trap = instance
+            .get_typed_func::<(i32, i32, i64), i32>(&store, "wait32")
+            .unwrap()
+            .call(&mut store, (0, 0, 0))
+            .unwrap_err();
+        assert_eq!(trap.trap_code(), Some(TrapCode::ExpectedSharedMemory));
+    }
+}
+
+#[test]
+fn other_atomic_operators_are_unsupported() {
+    let wat = r#"
+        (module
+            (memory 1 1 shared)
+            (func (param i32) (result i32)
+                (i32.atomic.load (local.get 0))
+            )
+        )
+    "#;
+    for backend in BACKENDS {
+        let error = compile(backend, true, wat).unwrap_err();
+        assert!(
+            error.to_string().contains("I32AtomicLoad"),
+            "unexpected error: {error}"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Tests for locating traps within the Wasm binary via [`Config::set_trap_locations`].
+
+use super::{backend_config, instantiate, BACKENDS};
+use wasmi::{core::TrapCode, EngineBackend, Instance, Store};
+use wasmparser::{Operator, Parser, Payload};
+
+/// The Wasm module under test.
+///
+/// - `divide` divides its parameters and traps if the divisor is zero.
+/// - The unnamed function loads from its parameter address and traps if out of bounds.
+/// - `load_indirect` calls the unnamed function.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (func $divide (export "divide") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func $load_indirect (export "load_indirect") (param i32) (result i32)
+            (call 1 (local.get 0))
+        )
+    )
+"#;
+
+/// Returns the Wasm binary offset of the first operator of the `func` body that matches `op`.
+fn offset_of(wasm: &[u8], func: usize, op: fn(&Operator) -> bool) -> usize {
+    let body = Parser::new(0)
+        .parse_all(wasm)
+        .filter_map(|payload| match payload.unwrap() {
+            Payload::CodeSectionEntry(body) => Some(body),
+            _ => None,
+        })
+        .nth(func)
+        .unwrap();
+    body.get_operators_reader()
+        .unwrap()
+        .into_iter_with_offsets()
+        .map(Result::unwrap)
+        .find_map(|(operator, offset)| op(&operator).then_some(offset))
+        .unwrap()
+}
+
+/// Instantiates the module under test locating traps if `locations` is `true`.
+///
+/// Also returns the Wasm binary of the module under test.
+fn setup(backend: EngineBackend, locations: bool) -> (Store<()>, Instance, Vec<u8>) {
+    let mut config = backend_config(backend);
+    config.set_trap_locations(locations);
+    let (store, instance) = instantiate(&config, WAT);
+    (store, instance, wat::parse_str(WAT).unwrap())
+}
+
+fn assert_trap_locations(backend: EngineBackend) {
+    let (mut store, instance, wasm) = setup(backend, true);
+    let trap = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "divide")
+        .unwrap()
+        .call(&mut store, (1, 0))
+        .unwrap_err();
+    let offset = offset_of(&wasm, 0, |op| matches!(op, Operator::I32DivS));
+    assert_eq!(trap.trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(
+        trap.to_string(),
+        format!("divide@{offset:#x}: integer divide by zero")
+    );
+    let trap = instance
+        .get_typed_func::<i32, i32>(&store, "load_indirect")
+        .unwrap()
+        .call(&mut store, 1 << 16)
+        .unwrap_err();
+    let offset = offset_of(&wasm, 1, |op| matches!(op, Operator::I32Load { .. }));
+    assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    assert_eq!(
+        trap.to_string(),
+        format!("<wasm function 1>@{offset:#x}: out of bounds memory access")
+    );
+}
+
+#[test]
+fn trap_locations() {
+    for backend in BACKENDS {
+        assert_trap_locations(backend)
+    }
+}
+
+#[test]
+fn no_trap_locations() {
+    for backend in BACKENDS {
+        let (mut store, instance, _) = setup(backend, false);
+        let trap = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "divide")
+            .unwrap()
+            .call(&mut store, (1, 0))
+            .unwrap_err();
+        assert_eq!(trap.to_string(), "integer divide by zero");
+    }
+}
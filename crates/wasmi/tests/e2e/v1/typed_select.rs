@@ -0,0 +1,94 @@
+//! Tests that the typed `select (result <ty>)` instruction preserves the
+//! identity of the selected `funcref` and `externref` values.
+
+use super::{instantiate_backend, BACKENDS};
+use wasmi::{EngineBackend, ExternRef, Func, FuncRef, Store};
+
+/// The module under test.
+///
+/// The `select` exports select between their two reference parameters
+/// whereas the `select_null` exports select between their reference parameter
+/// and a constant null reference.
+const WAT: &str = r#"
+    (module
+        (func (export "select.funcref") (param funcref funcref i32) (result funcref)
+            (select (result funcref) (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "select.externref") (param externref externref i32) (result externref)
+            (select (result externref) (local.get 0) (local.get 1) (local.get 2))
+        )
+        (func (export "select_null.funcref") (param funcref i32) (result funcref)
+            (select (result funcref) (local.get 0) (ref.null func) (local.get 1))
+        )
+        (func (export "select_null.externref") (param externref i32) (result externref)
+            (select (result externref) (ref.null extern) (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Calls the `funcref` and returns its result or `None` if it is null.
+fn call_funcref(store: &mut Store<()>, funcref: FuncRef) -> Option<i32> {
+    let func = funcref.func().copied()?;
+    let func = func.typed::<(), i32>(&*store).unwrap();
+    Some(func.call(store, ()).unwrap())
+}
+
+/// Returns the data of the `externref` or `None` if it is null.
+fn externref_data(store: &Store<()>, externref: ExternRef) -> Option<u64> {
+    let data = externref.data(store)?;
+    Some(*data.downcast_ref::<u64>().unwrap())
+}
+
+fn assert_typed_select_funcref(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let one = FuncRef::new(Func::wrap(&mut store, || 1_i32));
+    let two = FuncRef::new(Func::wrap(&mut store, || 2_i32));
+    let select = instance
+        .get_typed_func::<(FuncRef, FuncRef, i32), FuncRef>(&store, "select.funcref")
+        .unwrap();
+    let result = select.call(&mut store, (one, two, 1)).unwrap();
+    assert_eq!(call_funcref(&mut store, result), Some(1));
+    let result = select.call(&mut store, (one, two, 0)).unwrap();
+    assert_eq!(call_funcref(&mut store, result), Some(2));
+    let select_null = instance
+        .get_typed_func::<(FuncRef, i32), FuncRef>(&store, "select_null.funcref")
+        .unwrap();
+    let result = select_null.call(&mut store, (two, 1)).unwrap();
+    assert_eq!(call_funcref(&mut store, result), Some(2));
+    let result = select_null.call(&mut store, (two, 0)).unwrap();
+    assert!(result.is_null());
+}
+
+fn assert_typed_select_externref(backend: EngineBackend) {
+    let (mut store, instance) = instantiate_backend(backend, WAT);
+    let one = ExternRef::new::<u64>(&mut store, 1);
+    let two = ExternRef::new::<u64>(&mut store, 2);
+    let select = instance
+        .get_typed_func::<(ExternRef, ExternRef, i32), ExternRef>(&store, "select.externref")
+        .unwrap();
+    let result = select.call(&mut store, (one, two, 1)).unwrap();
+    assert_eq!(externref_data(&store, result), Some(1));
+    let result = select.call(&mut store, (one, two, 0)).unwrap();
+    assert_eq!(externref_data(&store, result), Some(2));
+    let select_null = instance
+        .get_typed_func::<(ExternRef, i32), ExternRef>(&store, "select_null.externref")
+        .unwrap();
+    let result = select_null.call(&mut store, (two, 1)).unwrap();
+    assert!(result.is_null());
+    let result = select_null.call(&mut store, (two, 0)).unwrap();
+    assert_eq!(externref_data(&store, result), Some(2));
+}
+
+#[test]
+fn typed_select_funcref() {
+    for backend in BACKENDS {
+        assert_typed_select_funcref(backend)
+    }
+}
+
+#[test]
+fn typed_select_externref() {
+    for backend in BACKENDS {
+        assert_typed_select_externref(backend)
+    }
+}
@@ -0,0 +1,128 @@
+//! Tests that the guard page signal handler of [`Config::set_signal_bounds_checks`]
+//! forwards unrelated `SIGSEGV` signals to a host handler installed before it.
+//!
+//! # Note
+//!
+//! This is a separate test binary since the guard page signal handler is installed
+//! once per process and the host handler must be installed before it.
+//!
+//! [`Config::set_signal_bounds_checks`]: wasmi::Config::set_signal_bounds_checks
+
+#![cfg(all(target_os = "linux", target_arch = "x86_64"))]
+
+use core::{
+    mem,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use wasmi::{
+    core::{Trap, TrapCode},
+    Config,
+    Engine,
+    EngineBackend,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The page that is inaccessible until the host signal handler unprotects it.
+static HOST_PAGE: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of `SIGSEGV` signals handled by the host signal handler.
+static HOST_HITS: AtomicUsize = AtomicUsize::new(0);
+
+/// The `SA_SIGINFO` host signal handler that unprotects [`HOST_PAGE`] upon access.
+unsafe extern "C" fn host_handler(
+    _signal: libc::c_int,
+    info: *mut libc::siginfo_t,
+    _context: *mut libc::c_void,
+) {
+    let page = HOST_PAGE.load(Ordering::SeqCst);
+    let addr = unsafe { (*info).si_addr() } as usize;
+    if page == 0 || !(page..page + page_size()).contains(&addr) {
+        unsafe { libc::abort() }
+    }
+    HOST_HITS.fetch_add(1, Ordering::SeqCst);
+    let result = unsafe {
+        libc::mprotect(
+            page as *mut libc::c_void,
+            page_size(),
+            libc::PROT_READ | libc::PROT_WRITE,
+        )
+    };
+    if result != 0 {
+        unsafe { libc::abort() }
+    }
+}
+
+/// Returns the page size of the operating system in bytes.
+fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[test]
+fn forwards_to_previous_siginfo_handler() {
+    // Install the host signal handler before any guarded linear memory exists.
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = host_handler as *const () as usize;
+        action.sa_flags = libc::SA_SIGINFO;
+        libc::sigemptyset(&mut action.sa_mask);
+        assert_eq!(libc::sigaction(libc::SIGSEGV, &action, ptr::null_mut()), 0);
+    }
+    let page = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size(),
+            libc::PROT_NONE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(page, libc::MAP_FAILED);
+    HOST_PAGE.store(page as usize, Ordering::SeqCst);
+
+    // Creating a guarded linear memory installs the guard page signal handler.
+    let mut config = Config::default();
+    config.set_engine_backend(EngineBackend::RegisterMachine);
+    config.set_signal_bounds_checks(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (export "load") (param $ptr i32) (result i32)
+                (i32.load (local.get $ptr))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let load = instance
+        .get_typed_func::<i32, i32>(&store, "load")
+        .unwrap();
+
+    // Guarded accesses are still handled by `wasmi` itself.
+    let trap: Trap = load.call(&mut store, 65536).unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    assert_eq!(HOST_HITS.load(Ordering::SeqCst), 0);
+
+    // Unrelated faults are forwarded to the host signal handler.
+    unsafe { ptr::write_volatile(page.cast::<u32>(), 42) };
+    assert_eq!(HOST_HITS.load(Ordering::SeqCst), 1);
+    assert_eq!(unsafe { ptr::read_volatile(page.cast::<u32>()) }, 42);
+
+    // And guarded accesses keep working afterwards.
+    assert_eq!(load.call(&mut store, 0).unwrap(), 0);
+    assert!(load.call(&mut store, 65533).is_err());
+    assert_eq!(HOST_HITS.load(Ordering::SeqCst), 1);
+    unsafe { libc::munmap(page, page_size()) };
+}